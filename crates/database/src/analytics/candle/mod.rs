@@ -0,0 +1,7 @@
+pub mod model;
+pub mod rebuild;
+pub mod repository;
+
+pub use model::*;
+pub use rebuild::*;
+pub use repository::*;
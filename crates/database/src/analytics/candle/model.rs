@@ -0,0 +1,430 @@
+use crate::clmm::clmm_swap_event::model::ClmmSwapEventModel;
+use crate::cpmm::swap_event::model::SwapEventModel;
+use crate::events::event_scanner::model::bson_datetime;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// K线周期
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, ToSchema)]
+pub enum CandleInterval {
+    #[serde(rename = "1m")]
+    OneMinute,
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "15m")]
+    FifteenMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "4h")]
+    FourHours,
+    #[serde(rename = "1d")]
+    OneDay,
+}
+
+impl CandleInterval {
+    /// 全部维护的K线周期（从小到大）
+    pub const ALL: [CandleInterval; 6] = [
+        CandleInterval::OneMinute,
+        CandleInterval::FiveMinutes,
+        CandleInterval::FifteenMinutes,
+        CandleInterval::OneHour,
+        CandleInterval::FourHours,
+        CandleInterval::OneDay,
+    ];
+
+    /// 获取存储用的周期字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CandleInterval::OneMinute => "1m",
+            CandleInterval::FiveMinutes => "5m",
+            CandleInterval::FifteenMinutes => "15m",
+            CandleInterval::OneHour => "1h",
+            CandleInterval::FourHours => "4h",
+            CandleInterval::OneDay => "1d",
+        }
+    }
+
+    /// 周期长度（秒）
+    pub fn seconds(&self) -> i64 {
+        match self {
+            CandleInterval::OneMinute => 60,
+            CandleInterval::FiveMinutes => 300,
+            CandleInterval::FifteenMinutes => 900,
+            CandleInterval::OneHour => 3_600,
+            CandleInterval::FourHours => 14_400,
+            CandleInterval::OneDay => 86_400,
+        }
+    }
+
+    /// 计算时间戳所在K线的开盘时间（按UTC对齐）
+    pub fn bucket_start(&self, timestamp: i64) -> i64 {
+        timestamp.div_euclid(self.seconds()) * self.seconds()
+    }
+}
+
+impl std::fmt::Display for CandleInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CandleInterval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1m" => Ok(CandleInterval::OneMinute),
+            "5m" => Ok(CandleInterval::FiveMinutes),
+            "15m" => Ok(CandleInterval::FifteenMinutes),
+            "1h" => Ok(CandleInterval::OneHour),
+            "4h" => Ok(CandleInterval::FourHours),
+            "1d" => Ok(CandleInterval::OneDay),
+            _ => Err(format!("不支持的K线周期: {}（可选: 1m/5m/15m/1h/4h/1d）", s)),
+        }
+    }
+}
+
+/// 池子K线
+///
+/// 价格为交换后的池子现价（token_1/token_0，未按精度调整），成交量为原始代币数量，
+/// 展示时再按代币精度和基础/计价方向换算。
+/// 开盘价和收盘价按交换的链上顺序（slot、指令索引、事件序号）决定，
+/// 乱序到达（如回填）的交换不会覆盖更晚的收盘价
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PoolCandle {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    /// 池子地址
+    pub pool_id: String,
+    /// K线周期
+    pub interval: CandleInterval,
+    /// 开盘时间（Unix秒，按周期对齐）
+    pub open_time: i64,
+
+    /// 开盘价
+    pub open: f64,
+    /// 最高价
+    pub high: f64,
+    /// 最低价
+    pub low: f64,
+    /// 收盘价
+    pub close: f64,
+
+    /// token_0成交量（原始数量）
+    pub volume_0: f64,
+    /// token_1成交量（原始数量）
+    pub volume_1: f64,
+    /// 成交笔数
+    pub trade_count: u64,
+
+    /// 开盘交换所在slot
+    pub open_slot: u64,
+    /// 开盘交换的顶层指令索引
+    #[serde(default)]
+    pub open_instruction_index: u32,
+    /// 开盘交换在指令中的事件序号
+    #[serde(default)]
+    pub open_event_ordinal: u32,
+    /// 收盘交换所在slot
+    pub close_slot: u64,
+    /// 收盘交换的顶层指令索引
+    #[serde(default)]
+    pub close_instruction_index: u32,
+    /// 收盘交换在指令中的事件序号
+    #[serde(default)]
+    pub close_event_ordinal: u32,
+
+    /// 更新时间
+    #[serde(with = "bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PoolCandle {
+    /// 由一笔交换创建K线
+    pub fn from_trade(interval: CandleInterval, trade: &CandleTrade) -> Self {
+        Self {
+            id: None,
+            pool_id: trade.pool_id.clone(),
+            interval,
+            open_time: interval.bucket_start(trade.timestamp),
+            open: trade.price,
+            high: trade.price,
+            low: trade.price,
+            close: trade.price,
+            volume_0: trade.amount_0 as f64,
+            volume_1: trade.amount_1 as f64,
+            trade_count: 1,
+            open_slot: trade.slot,
+            open_instruction_index: trade.instruction_index,
+            open_event_ordinal: trade.event_ordinal,
+            close_slot: trade.slot,
+            close_instruction_index: trade.instruction_index,
+            close_event_ordinal: trade.event_ordinal,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// 合并一笔交换（与仓库层增量更新的规则一致）
+    pub fn apply(&mut self, trade: &CandleTrade) {
+        let order = trade.order();
+        if order < (self.open_slot, self.open_instruction_index, self.open_event_ordinal) {
+            self.open = trade.price;
+            (self.open_slot, self.open_instruction_index, self.open_event_ordinal) = order;
+        }
+        if order >= (self.close_slot, self.close_instruction_index, self.close_event_ordinal) {
+            self.close = trade.price;
+            (self.close_slot, self.close_instruction_index, self.close_event_ordinal) = order;
+        }
+        self.high = self.high.max(trade.price);
+        self.low = self.low.min(trade.price);
+        self.volume_0 += trade.amount_0 as f64;
+        self.volume_1 += trade.amount_1 as f64;
+        self.trade_count += 1;
+        self.updated_at = Utc::now();
+    }
+}
+
+/// 参与K线聚合的一笔交换
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandleTrade {
+    pub pool_id: String,
+    /// 成交时间（Unix秒），优先取出块时间，缺失时取入库时间
+    pub timestamp: i64,
+    pub slot: u64,
+    pub instruction_index: u32,
+    pub event_ordinal: u32,
+    /// 交换后的池子现价（token_1/token_0，未按精度调整）
+    pub price: f64,
+    /// token_0成交数量
+    pub amount_0: u64,
+    /// token_1成交数量
+    pub amount_1: u64,
+}
+
+impl CandleTrade {
+    /// 交换的链上顺序（slot, 顶层指令索引, 事件序号）
+    pub fn order(&self) -> (u64, u32, u32) {
+        (self.slot, self.instruction_index, self.event_ordinal)
+    }
+
+    /// 由CPMM交换事件构造
+    ///
+    /// token_0为mint地址较小的一方（与池子创建时的排序一致），
+    /// 价格取交换后两侧金库余额之比
+    pub fn from_cpmm_swap(event: &SwapEventModel) -> Option<Self> {
+        let input_mint = Pubkey::from_str(&event.input_mint).ok()?;
+        let output_mint = Pubkey::from_str(&event.output_mint).ok()?;
+        let input_is_token_0 = input_mint < output_mint;
+
+        let input_vault_after = event.input_vault_before.saturating_add(event.input_amount);
+        let output_vault_after = event.output_vault_before.saturating_sub(event.output_amount);
+        let (reserve_0, reserve_1, amount_0, amount_1) = if input_is_token_0 {
+            (
+                input_vault_after,
+                output_vault_after,
+                event.input_amount,
+                event.output_amount,
+            )
+        } else {
+            (
+                output_vault_after,
+                input_vault_after,
+                event.output_amount,
+                event.input_amount,
+            )
+        };
+        if reserve_0 == 0 || reserve_1 == 0 {
+            return None;
+        }
+
+        Some(Self {
+            pool_id: event.pool_id.clone(),
+            timestamp: event.block_time.unwrap_or_else(|| event.created_at.timestamp()),
+            slot: event.slot,
+            instruction_index: event.identity.instruction_index.unwrap_or(0),
+            event_ordinal: event.identity.event_ordinal.unwrap_or(0),
+            price: reserve_1 as f64 / reserve_0 as f64,
+            amount_0,
+            amount_1,
+        })
+    }
+
+    /// 由CLMM交换事件构造，价格取交换后的sqrt_price_x64
+    pub fn from_clmm_swap(event: &ClmmSwapEventModel) -> Option<Self> {
+        let price = event.get_raw_price();
+        if !price.is_finite() || price <= 0.0 {
+            return None;
+        }
+
+        Some(Self {
+            pool_id: event.pool_id.clone(),
+            timestamp: event.block_time.unwrap_or_else(|| event.created_at.timestamp()),
+            slot: event.slot,
+            instruction_index: event.identity.instruction_index.unwrap_or(0),
+            event_ordinal: event.identity.event_ordinal.unwrap_or(0),
+            price,
+            amount_0: event.amount_0,
+            amount_1: event.amount_1,
+        })
+    }
+}
+
+/// 将一组交换聚合为指定周期的K线（按周期、开盘时间排序）
+pub fn aggregate_trades(trades: &[CandleTrade], intervals: &[CandleInterval]) -> Vec<PoolCandle> {
+    let mut ordered: Vec<&CandleTrade> = trades.iter().collect();
+    ordered.sort_by_key(|trade| trade.order());
+
+    let mut candles: BTreeMap<(&str, CandleInterval, i64), PoolCandle> = BTreeMap::new();
+    for trade in ordered {
+        for interval in intervals {
+            let key = (
+                trade.pool_id.as_str(),
+                *interval,
+                interval.bucket_start(trade.timestamp),
+            );
+            candles
+                .entry(key)
+                .and_modify(|candle| candle.apply(trade))
+                .or_insert_with(|| PoolCandle::from_trade(*interval, trade));
+        }
+    }
+
+    candles.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::event_identity::EventIdentity;
+
+    fn create_trade(slot: u64, timestamp: i64, price: f64, amount_0: u64) -> CandleTrade {
+        CandleTrade {
+            pool_id: "test_pool".to_string(),
+            timestamp,
+            slot,
+            instruction_index: 0,
+            event_ordinal: 0,
+            price,
+            amount_0,
+            amount_1: amount_0 * 2,
+        }
+    }
+
+    #[test]
+    fn test_interval_parse_and_bucket() {
+        for interval in CandleInterval::ALL {
+            assert_eq!(interval.as_str().parse::<CandleInterval>().unwrap(), interval);
+        }
+        assert!("2h".parse::<CandleInterval>().is_err());
+
+        assert_eq!(CandleInterval::OneMinute.bucket_start(125), 120);
+        assert_eq!(CandleInterval::FourHours.bucket_start(14_400 * 3 + 5), 14_400 * 3);
+        assert_eq!(CandleInterval::OneDay.bucket_start(-1), -86_400);
+    }
+
+    #[test]
+    fn test_aggregate_trades_orders_by_slot() {
+        // 乱序输入：开盘价和收盘价仍按slot决定
+        let trades = vec![
+            create_trade(30, 150, 1.5, 100),
+            create_trade(10, 60, 1.0, 100),
+            create_trade(20, 100, 3.0, 100),
+            create_trade(40, 200, 0.5, 100),
+        ];
+
+        let candles = aggregate_trades(&trades, &[CandleInterval::OneMinute, CandleInterval::FiveMinutes]);
+        let one_minute: Vec<&PoolCandle> = candles
+            .iter()
+            .filter(|candle| candle.interval == CandleInterval::OneMinute)
+            .collect();
+        assert_eq!(
+            one_minute.iter().map(|candle| candle.open_time).collect::<Vec<_>>(),
+            vec![60, 120, 180]
+        );
+        assert_eq!(one_minute[1].trade_count, 2);
+        assert_eq!(one_minute[1].open, 3.0);
+        assert_eq!(one_minute[1].close, 1.5);
+
+        let five_minutes = candles
+            .iter()
+            .find(|candle| candle.interval == CandleInterval::FiveMinutes)
+            .unwrap();
+        assert_eq!(five_minutes.open_time, 0);
+        assert_eq!(five_minutes.open, 1.0);
+        assert_eq!(five_minutes.high, 3.0);
+        assert_eq!(five_minutes.low, 0.5);
+        assert_eq!(five_minutes.close, 0.5);
+        assert_eq!(five_minutes.volume_0, 400.0);
+        assert_eq!(five_minutes.volume_1, 800.0);
+        assert_eq!(five_minutes.trade_count, 4);
+        assert_eq!((five_minutes.open_slot, five_minutes.close_slot), (10, 40));
+    }
+
+    #[test]
+    fn test_same_slot_close_follows_instruction_order() {
+        // 同一slot内乱序到达：收盘价取指令索引、事件序号最大的交换
+        let mut later = create_trade(10, 60, 2.0, 100);
+        later.instruction_index = 2;
+        let mut earlier = create_trade(10, 60, 1.0, 100);
+        earlier.instruction_index = 1;
+        earlier.event_ordinal = 3;
+
+        let mut candle = PoolCandle::from_trade(CandleInterval::OneMinute, &later);
+        candle.apply(&earlier);
+
+        assert_eq!((candle.open, candle.close), (1.0, 2.0));
+        assert_eq!((candle.open_instruction_index, candle.open_event_ordinal), (1, 3));
+        assert_eq!((candle.close_instruction_index, candle.close_event_ordinal), (2, 0));
+    }
+
+    #[test]
+    fn test_cpmm_trade_uses_post_swap_reserves() {
+        let mint_a = Pubkey::new_from_array([1; 32]);
+        let mint_b = Pubkey::new_from_array([2; 32]);
+        let mut event = SwapEventModel {
+            id: None,
+            payer: "payer".to_string(),
+            pool_id: "cpmm_pool".to_string(),
+            input_vault_before: 1_000,
+            output_vault_before: 4_000,
+            input_amount: 1_000,
+            output_amount: 2_000,
+            input_transfer_fee: 0,
+            output_transfer_fee: 0,
+            base_input: true,
+            input_mint: mint_a.to_string(),
+            output_mint: mint_b.to_string(),
+            trade_fee: 0,
+            creator_fee: 0,
+            creator_fee_on_input: true,
+            signature: "sig".to_string(),
+            slot: 100,
+            identity: EventIdentity::new(1, 0),
+            block_time: Some(1_700_000_000),
+            created_at: Utc::now(),
+        };
+
+        // 输入为token_0：交换后储备为 2000 / 2000
+        let trade = CandleTrade::from_cpmm_swap(&event).unwrap();
+        assert_eq!(trade.price, 1.0);
+        assert_eq!((trade.amount_0, trade.amount_1), (1_000, 2_000));
+        assert_eq!(trade.timestamp, 1_700_000_000);
+
+        // 输入为token_1：交换后token_0储备为4000，token_1储备为2000
+        event.input_mint = mint_b.to_string();
+        event.output_mint = mint_a.to_string();
+        event.output_vault_before = 6_000;
+        let trade = CandleTrade::from_cpmm_swap(&event).unwrap();
+        assert_eq!(trade.price, 0.5);
+        assert_eq!((trade.amount_0, trade.amount_1), (2_000, 1_000));
+
+        event.input_mint = "invalid".to_string();
+        assert!(CandleTrade::from_cpmm_swap(&event).is_none());
+    }
+}
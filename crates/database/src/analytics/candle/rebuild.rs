@@ -0,0 +1,168 @@
+use crate::analytics::candle::model::{aggregate_trades, CandleInterval, CandleTrade};
+use crate::clmm::clmm_swap_event::model::ClmmSwapEventModel;
+use crate::cpmm::swap_event::model::SwapEventModel;
+use crate::Database;
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use mongodb::{
+    bson::{doc, Bson, Document},
+    options::FindOptions,
+};
+use serde::Serialize;
+use tracing::{debug, info};
+use utoipa::ToSchema;
+
+/// 入库时间以字符串存储且精度不固定，按入库时间筛选时向两侧放宽的秒数
+const CREATED_AT_SLACK_SECS: i64 = 60;
+
+/// K线重建结果
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CandleRebuildSummary {
+    /// 池子地址
+    pub pool_id: String,
+    /// 参与聚合的交换笔数
+    pub trades: u64,
+    /// 写入的K线数
+    pub candles: u64,
+}
+
+impl Database {
+    /// 将新写入的CPMM交换合并到K线，无法计算价格时返回false
+    pub async fn apply_cpmm_swap_to_candles(&self, event: &SwapEventModel) -> Result<bool> {
+        match CandleTrade::from_cpmm_swap(event) {
+            Some(trade) => {
+                self.pool_candle_repository.apply_trade(&trade).await?;
+                Ok(true)
+            }
+            None => {
+                debug!("⏭️ CPMM交换无法计算价格，跳过K线更新: signature={}", event.signature);
+                Ok(false)
+            }
+        }
+    }
+
+    /// 将新写入的CLMM交换合并到K线，无法计算价格时返回false
+    pub async fn apply_clmm_swap_to_candles(&self, event: &ClmmSwapEventModel) -> Result<bool> {
+        match CandleTrade::from_clmm_swap(event) {
+            Some(trade) => {
+                self.pool_candle_repository.apply_trade(&trade).await?;
+                Ok(true)
+            }
+            None => {
+                debug!("⏭️ CLMM交换无法计算价格，跳过K线更新: signature={}", event.signature);
+                Ok(false)
+            }
+        }
+    }
+
+    /// 从交换历史重建池子K线
+    ///
+    /// `window`为成交时间的左闭右开区间（需按日对齐，保证覆盖的各周期K线完整），
+    /// 为None时重建该池子的全部K线
+    pub async fn rebuild_pool_candles(
+        &self,
+        pool_id: &str,
+        window: Option<(i64, i64)>,
+    ) -> Result<CandleRebuildSummary> {
+        info!("🔄 开始重建池子K线: pool={}, window={:?}", pool_id, window);
+
+        let trades = self.load_candle_trades(pool_id, window).await?;
+        let candles = aggregate_trades(&trades, &CandleInterval::ALL);
+        let written = self
+            .pool_candle_repository
+            .replace_pool_candles(pool_id, window, candles)
+            .await?;
+
+        Ok(CandleRebuildSummary {
+            pool_id: pool_id.to_string(),
+            trades: trades.len() as u64,
+            candles: written,
+        })
+    }
+
+    /// 重建成交时间所在自然日（UTC）的池子K线，用于交换被回滚后修正
+    pub async fn rebuild_pool_candles_for_day(&self, pool_id: &str, timestamp: i64) -> Result<CandleRebuildSummary> {
+        let day_start = CandleInterval::OneDay.bucket_start(timestamp);
+        self.rebuild_pool_candles(pool_id, Some((day_start, day_start + CandleInterval::OneDay.seconds())))
+            .await
+    }
+
+    /// 加载池子的CPMM和CLMM交换并转换为K线成交
+    async fn load_candle_trades(&self, pool_id: &str, window: Option<(i64, i64)>) -> Result<Vec<CandleTrade>> {
+        let filter = candle_trade_filter(pool_id, window);
+        let options = FindOptions::builder().sort(doc! { "slot": 1 }).build();
+
+        let cpmm_swaps = self
+            .swap_event_repository
+            .find_with_filter(filter.clone(), options.clone())
+            .await?;
+        let clmm_swaps = self
+            .clmm_swap_event_repository
+            .find_with_filter(filter, options)
+            .await?;
+
+        let trades: Vec<CandleTrade> = cpmm_swaps
+            .iter()
+            .filter_map(CandleTrade::from_cpmm_swap)
+            .chain(clmm_swaps.iter().filter_map(CandleTrade::from_clmm_swap))
+            .filter(|trade| window.map_or(true, |(from, to)| trade.timestamp >= from && trade.timestamp < to))
+            .collect();
+
+        debug!(
+            "📊 加载K线成交完成: pool={}, cpmm={}, clmm={}, trades={}",
+            pool_id,
+            cpmm_swaps.len(),
+            clmm_swaps.len(),
+            trades.len()
+        );
+        Ok(trades)
+    }
+}
+
+/// 构造按池子和成交时间筛选交换的条件
+///
+/// 有出块时间的按出块时间筛选，否则按入库时间粗筛，精确范围由调用方按成交时间过滤
+fn candle_trade_filter(pool_id: &str, window: Option<(i64, i64)>) -> Document {
+    let mut filter = doc! { "pool_id": pool_id };
+    if let Some((from, to)) = window {
+        filter.insert(
+            "$or",
+            vec![
+                Bson::Document(doc! { "block_time": { "$gte": from, "$lt": to } }),
+                Bson::Document(doc! {
+                    "block_time": Bson::Null,
+                    "created_at": {
+                        "$gte": format_created_at(from - CREATED_AT_SLACK_SECS),
+                        "$lt": format_created_at(to + CREATED_AT_SLACK_SECS),
+                    },
+                }),
+            ],
+        );
+    }
+    filter
+}
+
+/// 按交换事件入库时间的存储格式（RFC 3339字符串）格式化时间戳
+pub(crate) fn format_created_at(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .unwrap_or_default()
+        .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candle_trade_filter() {
+        let filter = candle_trade_filter("pool", None);
+        assert_eq!(filter, doc! { "pool_id": "pool" });
+
+        let filter = candle_trade_filter("pool", Some((86_400, 172_800)));
+        let branches = filter.get_array("$or").unwrap();
+        assert_eq!(branches.len(), 2);
+        let created_at = branches[1].as_document().unwrap().get_document("created_at").unwrap();
+        assert_eq!(created_at.get_str("$gte").unwrap(), "1970-01-01T23:59:00Z");
+        assert_eq!(created_at.get_str("$lt").unwrap(), "1970-01-03T00:01:00Z");
+    }
+}
@@ -0,0 +1,277 @@
+use crate::analytics::candle::model::{CandleInterval, CandleTrade, PoolCandle};
+use anyhow::Result;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, Bson, DateTime as BsonDateTime, Document},
+    options::{FindOptions, IndexOptions, UpdateOptions},
+    Collection, IndexModel,
+};
+use std::collections::HashMap;
+use tracing::{debug, error, info};
+
+/// PoolCandle仓储接口
+#[derive(Clone, Debug)]
+pub struct PoolCandleRepository {
+    collection: Collection<PoolCandle>,
+}
+
+impl PoolCandleRepository {
+    /// 创建新的PoolCandle仓储
+    pub fn new(collection: Collection<PoolCandle>) -> Self {
+        Self { collection }
+    }
+
+    /// 初始化数据库索引
+    pub async fn init_indexes(&self) -> Result<()> {
+        info!("🔧 初始化PoolCandle集合索引...");
+
+        let indexes = vec![
+            // 每个池子、周期、开盘时间唯一（增量更新按此upsert，K线查询按此排序）
+            IndexModel::builder()
+                .keys(doc! { "pool_id": 1, "interval": 1, "open_time": -1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("idx_pool_interval_open_time_unique".to_string())
+                        .unique(true)
+                        .build(),
+                )
+                .build(),
+            // 全局按周期、时间查询（统计类接口）
+            IndexModel::builder()
+                .keys(doc! { "interval": 1, "open_time": -1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("idx_interval_open_time".to_string())
+                        .build(),
+                )
+                .build(),
+        ];
+
+        match self.collection.create_indexes(indexes, None).await {
+            Ok(_) => {
+                info!("✅ PoolCandle索引创建成功");
+                Ok(())
+            }
+            Err(e) => {
+                error!("❌ PoolCandle索引创建失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 将一笔交换增量合并到全部周期的K线
+    ///
+    /// 使用管道更新保证单条K线的合并是原子的：开盘价只被链上顺序更早的交换覆盖，
+    /// 收盘价只被相同或更晚的交换覆盖，最高/最低价和成交量直接累计。
+    /// 链上顺序为 (slot, 指令索引, 事件序号)，同一slot内的多笔交换也能确定收盘价
+    pub async fn apply_trade(&self, trade: &CandleTrade) -> Result<()> {
+        let options = UpdateOptions::builder().upsert(true).build();
+        let slot = trade.slot as i64;
+        let instruction_index = trade.instruction_index as i64;
+        let event_ordinal = trade.event_ordinal as i64;
+        let order = Bson::Array(vec![slot.into(), instruction_index.into(), event_ordinal.into()]);
+
+        for interval in CandleInterval::ALL {
+            let filter = doc! {
+                "pool_id": &trade.pool_id,
+                "interval": interval.as_str(),
+                "open_time": interval.bucket_start(trade.timestamp),
+            };
+            let is_earlier = doc! { "$lt": [order.clone(), recorded_order("open", i64::MAX)] };
+            let is_later = doc! { "$gte": [order.clone(), recorded_order("close", -1)] };
+            let pipeline = vec![doc! {
+                "$set": {
+                    "open": { "$cond": [is_earlier.clone(), trade.price, "$open"] },
+                    "open_slot": { "$cond": [is_earlier.clone(), slot, "$open_slot"] },
+                    "open_instruction_index": {
+                        "$cond": [is_earlier.clone(), instruction_index, "$open_instruction_index"]
+                    },
+                    "open_event_ordinal": { "$cond": [is_earlier, event_ordinal, "$open_event_ordinal"] },
+                    "close": { "$cond": [is_later.clone(), trade.price, "$close"] },
+                    "close_slot": { "$cond": [is_later.clone(), slot, "$close_slot"] },
+                    "close_instruction_index": {
+                        "$cond": [is_later.clone(), instruction_index, "$close_instruction_index"]
+                    },
+                    "close_event_ordinal": { "$cond": [is_later, event_ordinal, "$close_event_ordinal"] },
+                    "high": { "$max": ["$high", trade.price] },
+                    "low": { "$min": ["$low", trade.price] },
+                    "volume_0": { "$add": [{ "$ifNull": ["$volume_0", 0.0] }, trade.amount_0 as f64] },
+                    "volume_1": { "$add": [{ "$ifNull": ["$volume_1", 0.0] }, trade.amount_1 as f64] },
+                    "trade_count": { "$add": [{ "$ifNull": ["$trade_count", 0i64] }, 1i64] },
+                    "updated_at": BsonDateTime::now(),
+                }
+            }];
+
+            if let Err(e) = self.collection.update_one(filter, pipeline, options.clone()).await {
+                error!(
+                    "❌ K线增量更新失败: pool={}, interval={}, slot={} - {}",
+                    trade.pool_id, interval, trade.slot, e
+                );
+                return Err(e.into());
+            }
+        }
+
+        debug!(
+            "📈 K线增量更新完成: pool={}, slot={}, price={}",
+            trade.pool_id, trade.slot, trade.price
+        );
+        Ok(())
+    }
+
+    /// 用重建结果替换池子在时间范围内的K线，返回写入的K线数
+    ///
+    /// `range`为开盘时间的左闭右开区间，为None时替换该池子的全部K线
+    pub async fn replace_pool_candles(
+        &self,
+        pool_id: &str,
+        range: Option<(i64, i64)>,
+        candles: Vec<PoolCandle>,
+    ) -> Result<u64> {
+        let mut filter = doc! { "pool_id": pool_id };
+        if let Some((from, to)) = range {
+            filter.insert("open_time", doc! { "$gte": from, "$lt": to });
+        }
+
+        let deleted = self.collection.delete_many(filter, None).await?.deleted_count;
+        if candles.is_empty() {
+            info!("🧹 池子K线已清空: pool={}, deleted={}", pool_id, deleted);
+            return Ok(0);
+        }
+
+        let inserted = self.collection.insert_many(candles, None).await?.inserted_ids.len() as u64;
+        info!(
+            "✅ 池子K线重建完成: pool={}, deleted={}, inserted={}",
+            pool_id, deleted, inserted
+        );
+        Ok(inserted)
+    }
+
+    /// 查询池子K线（按开盘时间升序）
+    ///
+    /// 未指定起始时间时返回截止时间之前最近的`limit`条
+    pub async fn find_candles(
+        &self,
+        pool_id: &str,
+        interval: CandleInterval,
+        from: Option<i64>,
+        to: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<PoolCandle>> {
+        let mut filter = doc! { "pool_id": pool_id, "interval": interval.as_str() };
+        let mut time_range = Document::new();
+        if let Some(from) = from {
+            time_range.insert("$gte", interval.bucket_start(from));
+        }
+        if let Some(to) = to {
+            time_range.insert("$lte", to);
+        }
+        if !time_range.is_empty() {
+            filter.insert("open_time", time_range);
+        }
+
+        // 有起始时间时从起点向后取，否则从截止时间向前取最近的K线
+        let ascending = from.is_some();
+        let options = FindOptions::builder()
+            .sort(doc! { "open_time": if ascending { 1 } else { -1 } })
+            .limit(limit)
+            .build();
+
+        let cursor = self.collection.find(filter, options).await?;
+        let mut candles: Vec<PoolCandle> = cursor.try_collect().await?;
+        if !ascending {
+            candles.reverse();
+        }
+
+        debug!(
+            "✅ 查询池子K线成功: pool={}, interval={}, count={}",
+            pool_id,
+            interval,
+            candles.len()
+        );
+        Ok(candles)
+    }
+
+    /// 统计各池子开盘时间不早于`from`所在周期的K线价格区间（未按精度调整），
+    /// 返回 pool_id -> (最低价, 最高价)
+    pub async fn find_price_ranges(&self, interval: CandleInterval, from: i64) -> Result<HashMap<String, (f64, f64)>> {
+        let pipeline = vec![
+            doc! {
+                "$match": {
+                    "interval": interval.as_str(),
+                    "open_time": { "$gte": interval.bucket_start(from) },
+                }
+            },
+            doc! {
+                "$group": {
+                    "_id": "$pool_id",
+                    "low": { "$min": "$low" },
+                    "high": { "$max": "$high" },
+                }
+            },
+        ];
+
+        let mut cursor = self.collection.aggregate(pipeline, None).await?;
+        let mut ranges = HashMap::new();
+        while let Some(doc) = cursor.try_next().await? {
+            if let (Ok(pool_id), Ok(low), Ok(high)) = (doc.get_str("_id"), doc.get_f64("low"), doc.get_f64("high")) {
+                ranges.insert(pool_id.to_string(), (low, high));
+            }
+        }
+
+        debug!(
+            "✅ 统计K线价格区间: interval={}, from={}, pools={}",
+            interval,
+            from,
+            ranges.len()
+        );
+        Ok(ranges)
+    }
+
+    /// 查询各池子开盘时间早于`before`的最后一根K线的收盘价（未按精度调整），
+    /// 返回 pool_id -> 收盘价；当天没有成交的池子沿用更早的收盘价
+    pub async fn find_latest_closes(&self, interval: CandleInterval, before: i64) -> Result<HashMap<String, f64>> {
+        let pipeline = vec![
+            doc! {
+                "$match": {
+                    "interval": interval.as_str(),
+                    "open_time": { "$lt": before },
+                }
+            },
+            doc! { "$sort": { "open_time": -1 } },
+            doc! {
+                "$group": {
+                    "_id": "$pool_id",
+                    "close": { "$first": "$close" },
+                }
+            },
+        ];
+
+        let mut cursor = self.collection.aggregate(pipeline, None).await?;
+        let mut closes = HashMap::new();
+        while let Some(doc) = cursor.try_next().await? {
+            if let (Ok(pool_id), Ok(close)) = (doc.get_str("_id"), doc.get_f64("close")) {
+                closes.insert(pool_id.to_string(), close);
+            }
+        }
+
+        debug!(
+            "✅ 查询K线收盘价: interval={}, before={}, pools={}",
+            interval,
+            before,
+            closes.len()
+        );
+        Ok(closes)
+    }
+}
+
+/// K线已记录的开盘（`open`）或收盘（`close`）交换的链上顺序表达式
+///
+/// 数组按元素依次比较，即 (slot, 指令索引, 事件序号) 的字典序；
+/// 新建的K线slot取`missing_slot`，旧数据缺少的指令索引和事件序号按0处理
+fn recorded_order(prefix: &str, missing_slot: i64) -> Bson {
+    Bson::Array(vec![
+        doc! { "$ifNull": [format!("${}_slot", prefix), missing_slot] }.into(),
+        doc! { "$ifNull": [format!("${}_instruction_index", prefix), 0i64] }.into(),
+        doc! { "$ifNull": [format!("${}_event_ordinal", prefix), 0i64] }.into(),
+    ])
+}
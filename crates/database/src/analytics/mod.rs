@@ -0,0 +1,3 @@
+pub mod candle;
+pub mod pool_stats;
+pub mod protocol_metrics;
@@ -0,0 +1,6 @@
+pub mod model;
+pub mod repository;
+pub mod volume;
+
+pub use model::*;
+pub use repository::*;
@@ -0,0 +1,188 @@
+use crate::analytics::candle::CandleInterval;
+use crate::events::event_scanner::model::bson_datetime;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+/// 滚动统计周期
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatsPeriod {
+    /// 最近24小时
+    Day,
+    /// 最近7天
+    Week,
+    /// 最近30天
+    Month,
+}
+
+impl StatsPeriod {
+    /// 全部统计周期
+    pub const ALL: [StatsPeriod; 3] = [StatsPeriod::Day, StatsPeriod::Week, StatsPeriod::Month];
+
+    /// 周期字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatsPeriod::Day => "day",
+            StatsPeriod::Week => "week",
+            StatsPeriod::Month => "month",
+        }
+    }
+
+    /// 周期天数
+    pub fn days(&self) -> i64 {
+        match self {
+            StatsPeriod::Day => 1,
+            StatsPeriod::Week => 7,
+            StatsPeriod::Month => 30,
+        }
+    }
+
+    /// 周期长度（秒）
+    pub fn seconds(&self) -> i64 {
+        self.days() * 86_400
+    }
+
+    /// 统计价格区间使用的K线周期
+    pub fn candle_interval(&self) -> CandleInterval {
+        match self {
+            StatsPeriod::Day => CandleInterval::OneHour,
+            StatsPeriod::Week | StatsPeriod::Month => CandleInterval::OneDay,
+        }
+    }
+}
+
+impl std::fmt::Display for StatsPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 池子单个周期的统计数据
+///
+/// 金额均已按代币精度换算，USD金额使用池子TVL信息中的代币USD价格，APR为百分比
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PoolPeriodStats {
+    /// 交易量（USD）
+    pub volume: f64,
+    /// 交易量（token_1数量）
+    pub volume_quote: f64,
+    /// 手续费（USD）
+    pub volume_fee: f64,
+    /// 总年化收益率（手续费APR + 奖励APR）
+    pub apr: f64,
+    /// 手续费年化收益率
+    pub fee_apr: f64,
+    /// 各奖励的年化收益率（按池子奖励槽位顺序，仅CLMM池子）
+    pub reward_apr: Vec<f64>,
+    /// 周期内最低价格（token_1/token_0）
+    pub price_min: f64,
+    /// 周期内最高价格（token_1/token_0）
+    pub price_max: f64,
+    /// 成交笔数
+    pub trade_count: u64,
+}
+
+/// 池子滚动统计快照
+///
+/// 由统计任务定期根据交换事件、K线和池子TVL重新计算，每个池子一条
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PoolStatsSnapshot {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    /// 池子地址
+    pub pool_id: String,
+    /// 计算时的锁仓价值（USD）
+    pub tvl: f64,
+
+    /// 最近24小时统计
+    pub day: PoolPeriodStats,
+    /// 最近7天统计
+    pub week: PoolPeriodStats,
+    /// 最近30天统计
+    pub month: PoolPeriodStats,
+
+    /// 更新时间
+    #[serde(with = "bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 池子在统计窗口内的交换汇总（原始代币数量，未按精度调整）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SwapVolume {
+    /// token_0成交量
+    pub volume_0: f64,
+    /// token_1成交量
+    pub volume_1: f64,
+    /// 以token_0为输入的成交量（CLMM按费率计算手续费）
+    pub input_0: f64,
+    /// 以token_1为输入的成交量（CLMM按费率计算手续费）
+    pub input_1: f64,
+    /// 事件记录的token_0交易手续费（仅CPMM）
+    pub fee_0: f64,
+    /// 事件记录的token_1交易手续费（仅CPMM）
+    pub fee_1: f64,
+    /// 成交笔数
+    pub trade_count: u64,
+}
+
+impl SwapVolume {
+    /// 合并另一组交换汇总
+    pub fn merge(&mut self, other: &SwapVolume) {
+        self.volume_0 += other.volume_0;
+        self.volume_1 += other.volume_1;
+        self.input_0 += other.input_0;
+        self.input_1 += other.input_1;
+        self.fee_0 += other.fee_0;
+        self.fee_1 += other.fee_1;
+        self.trade_count += other.trade_count;
+    }
+}
+
+/// 将周期内的收益折算为年化收益率（百分比），TVL为0时返回0
+pub fn annualized_rate(amount_usd: f64, tvl: f64, days: i64) -> f64 {
+    if tvl <= 0.0 || days <= 0 || !amount_usd.is_finite() {
+        return 0.0;
+    }
+    amount_usd / tvl * 365.0 / days as f64 * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annualized_rate() {
+        // 1天赚取TVL的0.1% => 年化36.5%
+        assert!((annualized_rate(1_000.0, 1_000_000.0, 1) - 36.5).abs() < 1e-9);
+        // 7天赚取TVL的0.7% => 年化36.5%
+        assert!((annualized_rate(7_000.0, 1_000_000.0, 7) - 36.5).abs() < 1e-9);
+        assert_eq!(annualized_rate(1_000.0, 0.0, 1), 0.0);
+    }
+
+    #[test]
+    fn test_swap_volume_merge() {
+        let mut volume = SwapVolume {
+            volume_0: 100.0,
+            volume_1: 200.0,
+            input_0: 100.0,
+            trade_count: 1,
+            ..Default::default()
+        };
+        volume.merge(&SwapVolume {
+            volume_0: 50.0,
+            volume_1: 120.0,
+            input_1: 120.0,
+            fee_1: 0.3,
+            trade_count: 2,
+            ..Default::default()
+        });
+
+        assert_eq!(volume.volume_0, 150.0);
+        assert_eq!(volume.volume_1, 320.0);
+        assert_eq!(volume.input_0, 100.0);
+        assert_eq!(volume.input_1, 120.0);
+        assert_eq!(volume.fee_1, 0.3);
+        assert_eq!(volume.trade_count, 3);
+    }
+}
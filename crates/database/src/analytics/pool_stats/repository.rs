@@ -0,0 +1,92 @@
+use crate::analytics::pool_stats::model::PoolStatsSnapshot;
+use anyhow::Result;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::doc,
+    options::{IndexOptions, ReplaceOptions},
+    Collection, IndexModel,
+};
+use tracing::{debug, error, info};
+
+/// PoolStats仓储接口
+#[derive(Clone, Debug)]
+pub struct PoolStatsRepository {
+    collection: Collection<PoolStatsSnapshot>,
+}
+
+impl PoolStatsRepository {
+    /// 创建新的PoolStats仓储
+    pub fn new(collection: Collection<PoolStatsSnapshot>) -> Self {
+        Self { collection }
+    }
+
+    /// 初始化数据库索引
+    pub async fn init_indexes(&self) -> Result<()> {
+        info!("🔧 初始化PoolStats集合索引...");
+
+        let indexes = vec![
+            // 每个池子一条统计快照
+            IndexModel::builder()
+                .keys(doc! { "pool_id": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("idx_pool_id_unique".to_string())
+                        .unique(true)
+                        .build(),
+                )
+                .build(),
+            // 按更新时间查询（排查过期快照）
+            IndexModel::builder()
+                .keys(doc! { "updated_at": -1 })
+                .options(IndexOptions::builder().name("idx_updated_at".to_string()).build())
+                .build(),
+        ];
+
+        match self.collection.create_indexes(indexes, None).await {
+            Ok(_) => {
+                info!("✅ PoolStats索引创建成功");
+                Ok(())
+            }
+            Err(e) => {
+                error!("❌ PoolStats索引创建失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 按池子写入统计快照（存在则整体替换），返回写入数量
+    pub async fn upsert_snapshots(&self, snapshots: &[PoolStatsSnapshot]) -> Result<u64> {
+        let options = ReplaceOptions::builder().upsert(true).build();
+        let mut written = 0;
+
+        for snapshot in snapshots {
+            let filter = doc! { "pool_id": &snapshot.pool_id };
+            if let Err(e) = self.collection.replace_one(filter, snapshot, options.clone()).await {
+                error!("❌ 写入池子统计快照失败: pool={} - {}", snapshot.pool_id, e);
+                return Err(e.into());
+            }
+            written += 1;
+        }
+
+        debug!("✅ 写入池子统计快照: count={}", written);
+        Ok(written)
+    }
+
+    /// 查询指定池子的统计快照
+    pub async fn find_by_pool_ids(&self, pool_ids: &[String]) -> Result<Vec<PoolStatsSnapshot>> {
+        if pool_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let filter = doc! { "pool_id": { "$in": pool_ids } };
+        let cursor = self.collection.find(filter, None).await?;
+        let snapshots: Vec<PoolStatsSnapshot> = cursor.try_collect().await?;
+
+        debug!(
+            "✅ 查询池子统计快照: requested={}, found={}",
+            pool_ids.len(),
+            snapshots.len()
+        );
+        Ok(snapshots)
+    }
+}
@@ -0,0 +1,181 @@
+use crate::analytics::candle::rebuild::format_created_at;
+use crate::analytics::pool_stats::model::SwapVolume;
+use crate::Database;
+use anyhow::Result;
+use futures::stream::TryStreamExt;
+use mongodb::bson::{doc, Bson, Document};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::str::FromStr;
+use tracing::debug;
+
+impl Database {
+    /// 汇总成交时间在`[from, to)`内的CPMM和CLMM交换，返回 pool_id -> 交换汇总
+    pub async fn aggregate_swap_volumes(&self, from: i64, to: i64) -> Result<HashMap<String, SwapVolume>> {
+        let mut volumes: HashMap<String, SwapVolume> = HashMap::new();
+
+        // CLMM: 按交换方向分组，输入侧即zero_for_one对应的代币
+        let clmm_pipeline = vec![
+            doc! { "$match": event_window_filter(from, to) },
+            doc! {
+                "$group": {
+                    "_id": { "pool_id": "$pool_id", "zero_for_one": "$zero_for_one" },
+                    "amount_0": { "$sum": { "$toDouble": "$amount_0" } },
+                    "amount_1": { "$sum": { "$toDouble": "$amount_1" } },
+                    "count": { "$sum": 1 }
+                }
+            },
+        ];
+        let mut cursor = self.clmm_swap_events.aggregate(clmm_pipeline, None).await?;
+        let mut clmm_groups = 0;
+        while let Some(doc) = cursor.try_next().await? {
+            let key = doc.get_document("_id")?;
+            let pool_id = key.get_str("pool_id")?.to_string();
+            let zero_for_one = key.get_bool("zero_for_one").unwrap_or(true);
+            let amount_0 = number_field(&doc, "amount_0");
+            let amount_1 = number_field(&doc, "amount_1");
+
+            volumes.entry(pool_id).or_default().merge(&SwapVolume {
+                volume_0: amount_0,
+                volume_1: amount_1,
+                input_0: if zero_for_one { amount_0 } else { 0.0 },
+                input_1: if zero_for_one { 0.0 } else { amount_1 },
+                trade_count: number_field(&doc, "count") as u64,
+                ..Default::default()
+            });
+            clmm_groups += 1;
+        }
+
+        // CPMM: 按输入/输出代币分组，token_0为mint地址较小的一方，手续费记录在输入代币上
+        let cpmm_pipeline = vec![
+            doc! { "$match": event_window_filter(from, to) },
+            doc! {
+                "$group": {
+                    "_id": { "pool_id": "$pool_id", "input_mint": "$input_mint", "output_mint": "$output_mint" },
+                    "input_amount": { "$sum": { "$toDouble": "$input_amount" } },
+                    "output_amount": { "$sum": { "$toDouble": "$output_amount" } },
+                    "trade_fee": { "$sum": { "$toDouble": "$trade_fee" } },
+                    "count": { "$sum": 1 }
+                }
+            },
+        ];
+        let mut cursor = self.swap_events.aggregate(cpmm_pipeline, None).await?;
+        let mut cpmm_groups = 0;
+        while let Some(doc) = cursor.try_next().await? {
+            let key = doc.get_document("_id")?;
+            let pool_id = key.get_str("pool_id")?.to_string();
+            let input_is_token_0 = match (
+                Pubkey::from_str(key.get_str("input_mint")?),
+                Pubkey::from_str(key.get_str("output_mint")?),
+            ) {
+                (Ok(input_mint), Ok(output_mint)) => input_mint < output_mint,
+                _ => continue,
+            };
+
+            volumes.entry(pool_id).or_default().merge(&cpmm_swap_volume(
+                input_is_token_0,
+                number_field(&doc, "input_amount"),
+                number_field(&doc, "output_amount"),
+                number_field(&doc, "trade_fee"),
+                number_field(&doc, "count") as u64,
+            ));
+            cpmm_groups += 1;
+        }
+
+        debug!(
+            "📊 交换汇总完成: from={}, to={}, clmm_groups={}, cpmm_groups={}, pools={}",
+            from,
+            to,
+            clmm_groups,
+            cpmm_groups,
+            volumes.len()
+        );
+        Ok(volumes)
+    }
+}
+
+/// 构造按出块时间筛选事件（交换、池子初始化、LP变更等）的条件
+///
+/// 有出块时间的按出块时间筛选，否则按入库时间（RFC 3339字符串）筛选
+pub(crate) fn event_window_filter(from: i64, to: i64) -> Document {
+    doc! {
+        "$or": [
+            { "block_time": { "$gte": from, "$lt": to } },
+            {
+                "block_time": Bson::Null,
+                "created_at": { "$gte": format_created_at(from), "$lt": format_created_at(to) },
+            },
+        ]
+    }
+}
+
+/// 将CPMM一组同方向交换换算为token_0/token_1的汇总
+fn cpmm_swap_volume(
+    input_is_token_0: bool,
+    input_amount: f64,
+    output_amount: f64,
+    trade_fee: f64,
+    trade_count: u64,
+) -> SwapVolume {
+    if input_is_token_0 {
+        SwapVolume {
+            volume_0: input_amount,
+            volume_1: output_amount,
+            input_0: input_amount,
+            fee_0: trade_fee,
+            trade_count,
+            ..Default::default()
+        }
+    } else {
+        SwapVolume {
+            volume_0: output_amount,
+            volume_1: input_amount,
+            input_1: input_amount,
+            fee_1: trade_fee,
+            trade_count,
+            ..Default::default()
+        }
+    }
+}
+
+/// 读取聚合结果中的数值字段（$sum结果可能为int32/int64/double）
+fn number_field(doc: &Document, key: &str) -> f64 {
+    match doc.get(key) {
+        Some(Bson::Double(value)) => *value,
+        Some(Bson::Int32(value)) => *value as f64,
+        Some(Bson::Int64(value)) => *value as f64,
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_window_filter() {
+        let filter = event_window_filter(86_400, 172_800);
+        let branches = filter.get_array("$or").unwrap();
+        assert_eq!(branches.len(), 2);
+
+        let block_time = branches[0].as_document().unwrap().get_document("block_time").unwrap();
+        assert_eq!(block_time.get_i64("$gte").unwrap(), 86_400);
+        assert_eq!(block_time.get_i64("$lt").unwrap(), 172_800);
+
+        let created_at = branches[1].as_document().unwrap().get_document("created_at").unwrap();
+        assert_eq!(created_at.get_str("$gte").unwrap(), "1970-01-02T00:00:00Z");
+        assert_eq!(created_at.get_str("$lt").unwrap(), "1970-01-03T00:00:00Z");
+    }
+
+    #[test]
+    fn test_cpmm_swap_volume_direction() {
+        let volume = cpmm_swap_volume(false, 300.0, 2.0, 0.75, 3);
+        assert_eq!(volume.volume_0, 2.0);
+        assert_eq!(volume.volume_1, 300.0);
+        assert_eq!(volume.input_0, 0.0);
+        assert_eq!(volume.input_1, 300.0);
+        assert_eq!(volume.fee_0, 0.0);
+        assert_eq!(volume.fee_1, 0.75);
+        assert_eq!(volume.trade_count, 3);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::analytics::pool_stats::volume::event_window_filter;
+use crate::analytics::protocol_metrics::model::DailyActivity;
+use crate::Database;
+use anyhow::Result;
+use mongodb::bson::{doc, Bson, Document};
+use std::collections::HashSet;
+use tracing::debug;
+
+/// CPMM LP变更类型: 存入
+const LP_CHANGE_DEPOSIT: i32 = 0;
+/// CPMM LP变更类型: 初始化池子
+const LP_CHANGE_INITIALIZE: i32 = 2;
+
+impl Database {
+    /// 汇总`[from, to)`内的交易者、新池子、新仓位、代币发射和推荐领取
+    pub async fn aggregate_daily_activity(&self, from: i64, to: i64) -> Result<DailyActivity> {
+        let window = event_window_filter(from, to);
+
+        let clmm_traders = collect_strings(self.clmm_swap_events.distinct("sender", window.clone(), None).await?);
+        let cpmm_traders = collect_strings(self.swap_events.distinct("payer", window.clone(), None).await?);
+
+        // CLMM池子按API创建时间统计（链上事件同步的池子同样记录该时间）
+        let clmm_new_pools = self
+            .clmm_pools
+            .count_documents(
+                doc! {
+                    "pool_type": { "$ne": "standard" },
+                    "api_created_at": { "$gte": from as f64, "$lt": to as f64 },
+                },
+                None,
+            )
+            .await?;
+        let cpmm_new_pools = self.init_pool_events.count_documents(window.clone(), None).await?;
+
+        let clmm_new_positions = self
+            .clmm_position_events
+            .count_documents(create_position_filter(window.clone()), None)
+            .await?;
+        let cpmm_new_positions = self
+            .lp_change_events
+            .count_documents(first_deposit_filter(window), None)
+            .await?;
+
+        let launches = self
+            .launch_events
+            .count_documents(doc! { "launched_at": { "$gte": from, "$lt": to } }, None)
+            .await?;
+        let referral_claims = self
+            .referral_established_events
+            .count_documents(doc! { "established_at": { "$gte": from, "$lt": to } }, None)
+            .await?;
+
+        let activity = DailyActivity {
+            clmm_traders,
+            cpmm_traders,
+            clmm_new_pools,
+            cpmm_new_pools,
+            clmm_new_positions,
+            cpmm_new_positions,
+            launches,
+            referral_claims,
+        };
+        debug!(
+            "📊 链上活动汇总完成: from={}, to={}, clmm_traders={}, cpmm_traders={}, launches={}",
+            from,
+            to,
+            activity.clmm_traders.len(),
+            activity.cpmm_traders.len(),
+            activity.launches
+        );
+        Ok(activity)
+    }
+}
+
+/// 构造CLMM开仓事件的筛选条件
+fn create_position_filter(window: Document) -> Document {
+    doc! { "$and": [window, { "event_type": "create_position" }] }
+}
+
+/// 构造CPMM首次注入流动性（存入或初始化前LP余额为0）的筛选条件
+fn first_deposit_filter(window: Document) -> Document {
+    doc! {
+        "$and": [
+            window,
+            {
+                "change_type": { "$in": [LP_CHANGE_DEPOSIT, LP_CHANGE_INITIALIZE] },
+                "lp_amount_before": 0,
+            },
+        ]
+    }
+}
+
+/// 收集distinct结果中的字符串值
+fn collect_strings(values: Vec<Bson>) -> HashSet<String> {
+    values
+        .into_iter()
+        .filter_map(|value| match value {
+            Bson::String(value) => Some(value),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_deposit_filter() {
+        let filter = first_deposit_filter(event_window_filter(0, 86_400));
+        let conditions = filter.get_array("$and").unwrap();
+        assert_eq!(conditions.len(), 2);
+        assert!(conditions[0].as_document().unwrap().contains_key("$or"));
+
+        let deposit = conditions[1].as_document().unwrap();
+        assert_eq!(deposit.get_i32("lp_amount_before").unwrap(), 0);
+        let change_types = deposit.get_document("change_type").unwrap().get_array("$in").unwrap();
+        assert_eq!(change_types, &vec![Bson::Int32(0), Bson::Int32(2)]);
+    }
+
+    #[test]
+    fn test_create_position_filter_uses_block_time() {
+        let filter = create_position_filter(event_window_filter(0, 86_400));
+        let conditions = filter.get_array("$and").unwrap();
+        let window = conditions[0].as_document().unwrap().get_array("$or").unwrap();
+        let by_block_time = window[0].as_document().unwrap().get_document("block_time").unwrap();
+        assert_eq!(by_block_time.get_i64("$gte").unwrap(), 0);
+        assert_eq!(by_block_time.get_i64("$lt").unwrap(), 86_400);
+        assert_eq!(
+            conditions[1].as_document().unwrap().get_str("event_type").unwrap(),
+            "create_position"
+        );
+    }
+
+    #[test]
+    fn test_collect_strings_skips_non_strings() {
+        let traders = collect_strings(vec![
+            Bson::String("alice".to_string()),
+            Bson::Null,
+            Bson::String("bob".to_string()),
+            Bson::String("alice".to_string()),
+        ]);
+        assert_eq!(traders.len(), 2);
+        assert!(traders.contains("alice") && traders.contains("bob"));
+    }
+}
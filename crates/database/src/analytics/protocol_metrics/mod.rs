@@ -0,0 +1,6 @@
+pub mod activity;
+pub mod model;
+pub mod repository;
+
+pub use model::*;
+pub use repository::*;
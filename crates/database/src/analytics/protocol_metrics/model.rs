@@ -0,0 +1,192 @@
+use crate::events::event_scanner::model::bson_datetime;
+use chrono::{DateTime, TimeZone, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// 一天的秒数
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+/// 单个程序(CLMM/CPMM)的每日指标
+///
+/// USD金额使用计算时池子TVL信息中的代币USD价格
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProgramDailyMetrics {
+    /// 锁仓价值（USD），取当天最后一次计算时的池子TVL
+    pub tvl: f64,
+    /// 交易量（USD）
+    pub volume: f64,
+    /// 手续费（USD）
+    pub fees: f64,
+    /// 成交笔数
+    pub trade_count: u64,
+    /// 活跃交易者数量（去重的交换发起者）
+    pub active_traders: u64,
+    /// 新建池子数量
+    pub new_pools: u64,
+    /// 新建仓位数量（CLMM开仓 / CPMM首次注入流动性）
+    pub new_positions: u64,
+}
+
+/// 协议每日指标快照
+///
+/// 由指标任务按UTC自然日聚合池子和事件集合，每天一条。
+/// 合计值中活跃交易者按两个程序的交易者去重，其余为CLMM和CPMM之和
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProtocolDailyMetrics {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    /// 日期（UTC，YYYY-MM-DD）
+    pub date: String,
+    /// 当天起始时间（Unix秒）
+    pub day_start: i64,
+
+    /// 锁仓价值合计（USD）
+    pub tvl: f64,
+    /// 交易量合计（USD）
+    pub volume: f64,
+    /// 手续费合计（USD）
+    pub fees: f64,
+    /// 成交笔数合计
+    pub trade_count: u64,
+    /// 活跃交易者数量（两个程序去重）
+    pub active_traders: u64,
+    /// 新建池子数量合计
+    pub new_pools: u64,
+    /// 新建仓位数量合计
+    pub new_positions: u64,
+    /// 代币发射数量
+    pub launches: u64,
+    /// 推荐领取数量（建立推荐关系）
+    pub referral_claims: u64,
+
+    /// CLMM指标
+    pub clmm: ProgramDailyMetrics,
+    /// CPMM指标
+    pub cpmm: ProgramDailyMetrics,
+
+    /// 更新时间
+    #[serde(with = "bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ProtocolDailyMetrics {
+    /// 根据两个程序的指标和当天活动汇总生成快照
+    pub fn new(
+        day_start: i64,
+        clmm: ProgramDailyMetrics,
+        cpmm: ProgramDailyMetrics,
+        activity: &DailyActivity,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: None,
+            date: format_day(day_start),
+            day_start,
+            tvl: clmm.tvl + cpmm.tvl,
+            volume: clmm.volume + cpmm.volume,
+            fees: clmm.fees + cpmm.fees,
+            trade_count: clmm.trade_count + cpmm.trade_count,
+            active_traders: activity.clmm_traders.union(&activity.cpmm_traders).count() as u64,
+            new_pools: clmm.new_pools + cpmm.new_pools,
+            new_positions: clmm.new_positions + cpmm.new_positions,
+            launches: activity.launches,
+            referral_claims: activity.referral_claims,
+            clmm,
+            cpmm,
+            updated_at,
+        }
+    }
+}
+
+/// 一天内的链上活动汇总（来自事件集合）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DailyActivity {
+    /// CLMM交换发起者
+    pub clmm_traders: HashSet<String>,
+    /// CPMM交换发起者
+    pub cpmm_traders: HashSet<String>,
+    /// CLMM新建池子数量
+    pub clmm_new_pools: u64,
+    /// CPMM新建池子数量
+    pub cpmm_new_pools: u64,
+    /// CLMM开仓数量
+    pub clmm_new_positions: u64,
+    /// CPMM首次注入流动性数量
+    pub cpmm_new_positions: u64,
+    /// 代币发射数量
+    pub launches: u64,
+    /// 推荐领取数量
+    pub referral_claims: u64,
+}
+
+/// 将时间戳截断到所在UTC自然日的起始时间
+pub fn day_start_of(timestamp: i64) -> i64 {
+    timestamp.div_euclid(SECONDS_PER_DAY) * SECONDS_PER_DAY
+}
+
+/// 将当天起始时间格式化为YYYY-MM-DD
+pub fn format_day(day_start: i64) -> String {
+    Utc.timestamp_opt(day_start, 0)
+        .single()
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_start_and_format() {
+        // 2024-01-02 03:04:05 UTC
+        let timestamp = 1_704_164_645;
+        let day_start = day_start_of(timestamp);
+        assert_eq!(day_start, 1_704_153_600);
+        assert_eq!(format_day(day_start), "2024-01-02");
+        assert_eq!(day_start_of(day_start), day_start);
+    }
+
+    #[test]
+    fn test_protocol_daily_metrics_totals() {
+        let clmm = ProgramDailyMetrics {
+            tvl: 1_000.0,
+            volume: 500.0,
+            fees: 1.5,
+            trade_count: 10,
+            active_traders: 2,
+            new_pools: 1,
+            new_positions: 3,
+        };
+        let cpmm = ProgramDailyMetrics {
+            tvl: 2_000.0,
+            volume: 250.0,
+            fees: 0.5,
+            trade_count: 5,
+            active_traders: 2,
+            new_pools: 2,
+            new_positions: 1,
+        };
+        let activity = DailyActivity {
+            clmm_traders: ["alice", "bob"].iter().map(|s| s.to_string()).collect(),
+            cpmm_traders: ["bob", "carol"].iter().map(|s| s.to_string()).collect(),
+            launches: 4,
+            referral_claims: 6,
+            ..Default::default()
+        };
+
+        let metrics = ProtocolDailyMetrics::new(1_704_153_600, clmm, cpmm, &activity, Utc::now());
+        assert_eq!(metrics.date, "2024-01-02");
+        assert_eq!(metrics.tvl, 3_000.0);
+        assert_eq!(metrics.volume, 750.0);
+        assert_eq!(metrics.fees, 2.0);
+        assert_eq!(metrics.trade_count, 15);
+        // bob在两个程序都有交易，只计一次
+        assert_eq!(metrics.active_traders, 3);
+        assert_eq!(metrics.new_pools, 3);
+        assert_eq!(metrics.new_positions, 4);
+        assert_eq!(metrics.launches, 4);
+        assert_eq!(metrics.referral_claims, 6);
+    }
+}
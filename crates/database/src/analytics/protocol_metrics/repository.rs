@@ -0,0 +1,90 @@
+use crate::analytics::protocol_metrics::model::ProtocolDailyMetrics;
+use anyhow::Result;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::doc,
+    options::{FindOptions, IndexOptions, ReplaceOptions},
+    Collection, IndexModel,
+};
+use tracing::{debug, error, info};
+
+/// ProtocolMetrics仓储接口
+#[derive(Clone, Debug)]
+pub struct ProtocolMetricsRepository {
+    collection: Collection<ProtocolDailyMetrics>,
+}
+
+impl ProtocolMetricsRepository {
+    /// 创建新的ProtocolMetrics仓储
+    pub fn new(collection: Collection<ProtocolDailyMetrics>) -> Self {
+        Self { collection }
+    }
+
+    /// 初始化数据库索引
+    pub async fn init_indexes(&self) -> Result<()> {
+        info!("🔧 初始化ProtocolMetrics集合索引...");
+
+        let indexes = vec![
+            // 每天一条快照（按日期upsert，按日期范围查询）
+            IndexModel::builder()
+                .keys(doc! { "day_start": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("idx_day_start_unique".to_string())
+                        .unique(true)
+                        .build(),
+                )
+                .build(),
+        ];
+
+        match self.collection.create_indexes(indexes, None).await {
+            Ok(_) => {
+                info!("✅ ProtocolMetrics索引创建成功");
+                Ok(())
+            }
+            Err(e) => {
+                error!("❌ ProtocolMetrics索引创建失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 写入一天的指标快照（存在则整体替换）
+    pub async fn upsert_day(&self, metrics: &ProtocolDailyMetrics) -> Result<()> {
+        let options = ReplaceOptions::builder().upsert(true).build();
+        let filter = doc! { "day_start": metrics.day_start };
+
+        self.collection
+            .replace_one(filter, metrics, options)
+            .await
+            .map_err(|e| {
+                error!("❌ 写入协议每日指标失败: date={} - {}", metrics.date, e);
+                e
+            })?;
+
+        debug!("✅ 写入协议每日指标: date={}", metrics.date);
+        Ok(())
+    }
+
+    /// 查询指定日期的指标快照
+    pub async fn find_by_day(&self, day_start: i64) -> Result<Option<ProtocolDailyMetrics>> {
+        Ok(self.collection.find_one(doc! { "day_start": day_start }, None).await?)
+    }
+
+    /// 查询`[from_day, to_day]`内的指标快照，按日期升序
+    pub async fn find_range(&self, from_day: i64, to_day: i64) -> Result<Vec<ProtocolDailyMetrics>> {
+        let filter = doc! { "day_start": { "$gte": from_day, "$lte": to_day } };
+        let options = FindOptions::builder().sort(doc! { "day_start": 1 }).build();
+
+        let cursor = self.collection.find(filter, options).await?;
+        let metrics: Vec<ProtocolDailyMetrics> = cursor.try_collect().await?;
+
+        debug!(
+            "✅ 查询协议每日指标: from={}, to={}, found={}",
+            from_day,
+            to_day,
+            metrics.len()
+        );
+        Ok(metrics)
+    }
+}
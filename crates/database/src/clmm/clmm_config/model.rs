@@ -55,6 +55,10 @@ pub struct ClmmConfigModel {
     /// 最后同步时间 (从链上同步的时间)
     #[serde(rename = "lastSyncAt", skip_serializing_if = "Option::is_none")]
     pub last_sync_at: Option<DateTime<Utc>>,
+
+    /// 最后处理的链上ConfigChangeEvent所在slot (用于防止旧事件覆盖新配置)
+    #[serde(rename = "lastEventSlot", default, skip_serializing_if = "Option::is_none")]
+    pub last_event_slot: Option<u64>,
 }
 
 impl ClmmConfigModel {
@@ -84,6 +88,7 @@ impl ClmmConfigModel {
             created_at: now,
             updated_at: now,
             last_sync_at: Some(now),
+            last_event_slot: None,
         }
     }
 
@@ -129,6 +129,63 @@ impl ClmmConfigRepository {
         Ok(saved_ids)
     }
 
+    /// 应用链上ConfigChangeEvent (原地更新费率字段，配置不存在时按默认值创建)
+    ///
+    /// 仅当事件slot不早于已处理的slot时才会更新，返回false表示事件已过期被忽略
+    pub async fn apply_chain_config_change(&self, config: &ClmmConfigModel, event_slot: u64) -> Result<bool> {
+        let filter = doc! {
+            "configId": &config.config_id,
+            "$or": [
+                { "lastEventSlot": { "$exists": false } },
+                { "lastEventSlot": { "$lte": event_slot as i64 } }
+            ]
+        };
+        let now = mongodb::bson::to_bson(&chrono::Utc::now())?;
+        let update = doc! {
+            "$set": {
+                "index": config.index,
+                "protocolFeeRate": config.protocol_fee_rate as i64,
+                "tradeFeeRate": config.trade_fee_rate as i64,
+                "tickSpacing": config.tick_spacing,
+                "fundFeeRate": config.fund_fee_rate as i64,
+                "lastEventSlot": event_slot as i64,
+                "updatedAt": now.clone(),
+                "lastSyncAt": now.clone()
+            },
+            "$setOnInsert": {
+                "defaultRange": config.default_range,
+                "defaultRangePoint": config.default_range_point.clone(),
+                "enabled": config.enabled,
+                "createdAt": now
+            }
+        };
+
+        let options = mongodb::options::UpdateOptions::builder().upsert(true).build();
+
+        match self.collection.update_one(filter, update, options).await {
+            Ok(result) => {
+                if result.upserted_id.is_some() {
+                    info!("✅ 根据链上事件新建CLMM配置: {}", config.config_id);
+                } else {
+                    info!("🔄 根据链上事件更新CLMM配置: {}", config.config_id);
+                }
+                Ok(true)
+            }
+            Err(e) => {
+                // 已存在更新slot的配置时过滤条件不匹配，upsert会触发configId唯一索引冲突
+                if e.to_string().contains("duplicate key") {
+                    info!(
+                        "⏭️ 忽略过期的ConfigChangeEvent: config={}, slot={}",
+                        config.config_id, event_slot
+                    );
+                    return Ok(false);
+                }
+                error!("❌ 应用链上CLMM配置变更失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
     /// 根据配置ID获取配置
     pub async fn get_config_by_id(&self, config_id: &str) -> Result<Option<ClmmConfigModel>> {
         let filter = doc! { "configId": config_id };
@@ -393,6 +450,7 @@ mod tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             last_sync_at: Some(chrono::Utc::now()),
+            last_event_slot: None,
         }
     }
 
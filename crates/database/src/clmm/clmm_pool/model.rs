@@ -153,6 +153,10 @@ pub struct ClmmPool {
     /// 是否已被链上确认
     #[serde(default)]
     pub chain_confirmed: bool,
+
+    /// 锁仓价值信息 (由同步服务根据金库余额和代币USD价格定期刷新)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tvl_info: Option<TvlInfo>,
 }
 
 /// 代币属性
@@ -241,6 +245,24 @@ pub struct VaultInfo {
     pub token_vault_1: String,
 }
 
+/// 锁仓价值(TVL)信息
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct TvlInfo {
+    /// 代币0金库余额 (已按精度换算)
+    pub amount_0: f64,
+    /// 代币1金库余额 (已按精度换算)
+    pub amount_1: f64,
+    /// 代币0的USD价格 (无法定价时为空)
+    pub price_usd_0: Option<f64>,
+    /// 代币1的USD价格 (无法定价时为空)
+    pub price_usd_1: Option<f64>,
+    /// 锁仓价值 (USD)，两侧代币均无法定价时为0
+    pub tvl: f64,
+    /// 更新时间戳
+    #[serde(with = "mongodb::bson::serde_helpers::u64_as_f64")]
+    pub updated_at: u64,
+}
+
 /// 扩展地址信息
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ExtensionInfo {
@@ -372,7 +394,7 @@ pub struct PoolListRequest {
     #[serde(rename = "poolType")]
     pub pool_type: Option<String>,
 
-    /// 排序字段 (default, created_at, price, open_time)
+    /// 排序字段 (default, created_at, price, open_time, tvl)
     #[serde(rename = "poolSortField")]
     pub pool_sort_field: Option<String>,
 
@@ -408,6 +430,16 @@ pub struct PoolListRequest {
 
     /// 按多个池子地址查询 (用逗号分隔的地址列表)
     pub ids: Option<String>,
+
+    /// 最小锁仓价值 (USD)
+    #[serde(rename = "minTvl")]
+    #[validate(range(min = 0.0))]
+    pub min_tvl: Option<f64>,
+
+    /// 最大锁仓价值 (USD)
+    #[serde(rename = "maxTvl")]
+    #[validate(range(min = 0.0))]
+    pub max_tvl: Option<f64>,
 }
 
 impl Default for PoolListRequest {
@@ -424,6 +456,8 @@ impl Default for PoolListRequest {
             mint1: None,
             mint2: None,
             ids: None,
+            min_tvl: None,
+            max_tvl: None,
         }
     }
 }
@@ -603,6 +637,7 @@ mod tests {
             pool_type: PoolType::Concentrated,
             data_source: DataSource::ApiCreated,
             chain_confirmed: false,
+            tvl_info: None,
         };
 
         assert_eq!(pool.pool_type, PoolType::Concentrated);
@@ -106,6 +106,10 @@ impl ClmmPoolRepository {
                 .build(),
             // 数据来源索引
             IndexModel::builder().keys(doc! { "data_source": 1 }).build(),
+            // 锁仓价值索引 (用于按TVL排序和过滤)
+            IndexModel::builder().keys(doc! { "tvl_info.tvl": -1 }).build(),
+            // TVL更新时间索引 (用于查找TVL过期的池子)
+            IndexModel::builder().keys(doc! { "tvl_info.updated_at": 1 }).build(),
         ];
 
         self.collection.create_indexes(indexes, None).await?;
@@ -440,6 +444,41 @@ impl ClmmPoolRepository {
         Ok(result.deleted_count > 0)
     }
 
+    /// 根据链上事件签名查找池子（交易回滚时使用）
+    pub async fn find_by_event_signature(&self, signature: &str) -> AppResult<Vec<ClmmPool>> {
+        let filter = doc! { "event_signature": signature };
+        let mut cursor = self.collection.find(filter, None).await?;
+        let mut pools = Vec::new();
+
+        while cursor.advance().await? {
+            pools.push(cursor.deserialize_current()?);
+        }
+
+        Ok(pools)
+    }
+
+    /// 撤销池子的链上确认（确认交易未最终确认时，API创建的池子恢复为待确认状态）
+    pub async fn revert_chain_confirmation(&self, pool_address: &str, signature: &str) -> AppResult<bool> {
+        let filter = doc! { "pool_address": pool_address, "event_signature": signature };
+        let update = doc! {
+            "$set": {
+                "status": "Pending",
+                "chain_confirmed": false,
+                "data_source": "api",
+                "updated_at": chrono::Utc::now().timestamp(),
+            },
+            "$unset": {
+                "event_signature": "",
+                "event_updated_slot": "",
+                "event_confirmed_at": "",
+                "event_updated_at": "",
+            }
+        };
+
+        let result = self.collection.update_one(filter, update, None).await?;
+        Ok(result.modified_count > 0)
+    }
+
     /// 增强的池子查询接口，支持分页、过滤和排序
     pub async fn query_pools_with_pagination(&self, params: &PoolListRequest) -> AppResult<PoolListResponse> {
         let mut filter = Document::new();
@@ -535,6 +574,18 @@ impl ClmmPoolRepository {
             }
         }
 
+        // 锁仓价值范围过滤
+        if params.min_tvl.is_some() || params.max_tvl.is_some() {
+            let mut tvl_range = Document::new();
+            if let Some(min_tvl) = params.min_tvl {
+                tvl_range.insert("$gte", min_tvl);
+            }
+            if let Some(max_tvl) = params.max_tvl {
+                tvl_range.insert("$lte", max_tvl);
+            }
+            filter.insert("tvl_info.tvl", tvl_range);
+        }
+
         // 获取总数用于分页
         let total_count = self.collection.count_documents(filter.clone(), None).await?;
 
@@ -544,6 +595,7 @@ impl ClmmPoolRepository {
             "created_at" => "api_created_at",
             "price" => "price_info.initial_price",
             "open_time" => "open_time",
+            "tvl" => "tvl_info.tvl",
             _ => "api_created_at", // 默认排序字段
         };
 
@@ -729,6 +781,75 @@ impl ClmmPoolRepository {
         Ok(pools)
     }
 
+    /// 查询TVL需要刷新的活跃池子（从未计算过或更新时间早于`stale_before`），最久未更新的优先
+    pub async fn find_pools_need_tvl_refresh(&self, stale_before: u64, limit: i64) -> AppResult<Vec<ClmmPool>> {
+        let filter = doc! {
+            "status": mongodb::bson::to_bson(&PoolStatus::Active)?,
+            "$or": [
+                { "tvl_info": { "$exists": false } },
+                { "tvl_info.updated_at": { "$lt": stale_before as f64 } }
+            ]
+        };
+
+        let options = FindOptions::builder()
+            .limit(limit)
+            .sort(doc! { "tvl_info.updated_at": 1 })
+            .build();
+
+        let mut cursor = self.collection.find(filter, options).await?;
+        let mut pools = Vec::new();
+
+        while cursor.advance().await? {
+            pools.push(cursor.deserialize_current()?);
+        }
+
+        Ok(pools)
+    }
+
+    /// 更新池子的锁仓价值信息
+    pub async fn update_tvl_info(&self, pool_address: &str, tvl_info: &TvlInfo) -> AppResult<bool> {
+        let filter = doc! { "pool_address": pool_address };
+        let update = doc! {
+            "$set": {
+                "tvl_info": mongodb::bson::to_bson(tvl_info)?,
+            }
+        };
+
+        let result = self.collection.update_one(filter, update, None).await?;
+        Ok(result.matched_count > 0)
+    }
+
+    /// 查询全部活跃池子
+    pub async fn find_active_pools(&self) -> AppResult<Vec<ClmmPool>> {
+        let filter = doc! { "status": mongodb::bson::to_bson(&PoolStatus::Active)? };
+
+        let mut cursor = self.collection.find(filter, None).await?;
+        let mut pools = Vec::new();
+
+        while cursor.advance().await? {
+            pools.push(cursor.deserialize_current()?);
+        }
+
+        Ok(pools)
+    }
+
+    /// 按地址批量查询池子（不区分状态）
+    pub async fn find_by_pool_addresses(&self, pool_addresses: &[String]) -> AppResult<Vec<ClmmPool>> {
+        if pool_addresses.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let filter = doc! { "pool_address": { "$in": pool_addresses } };
+        let mut cursor = self.collection.find(filter, None).await?;
+        let mut pools = Vec::new();
+
+        while cursor.advance().await? {
+            pools.push(cursor.deserialize_current()?);
+        }
+
+        Ok(pools)
+    }
+
     /// 插入池子
     pub async fn insert_pool(&self, pool: ClmmPool) -> AppResult<()> {
         self.collection.insert_one(pool, None).await?;
@@ -821,6 +942,7 @@ mod tests {
                 pool_type: PoolType::Concentrated,
                 data_source: DataSource::ApiCreated,
                 chain_confirmed: false,
+                tvl_info: None,
             },
             ClmmPool {
                 id: None,
@@ -886,6 +1008,7 @@ mod tests {
                 pool_type: PoolType::Standard,
                 data_source: DataSource::ApiCreated,
                 chain_confirmed: false,
+                tvl_info: None,
             },
             ClmmPool {
                 id: None,
@@ -951,6 +1074,7 @@ mod tests {
                 pool_type: PoolType::Concentrated,
                 data_source: DataSource::ApiCreated,
                 chain_confirmed: false,
+                tvl_info: None,
             },
         ]
     }
@@ -1180,6 +1304,66 @@ mod tests {
         db.drop(None).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_query_pools_with_tvl_filter_and_sorting() {
+        let db = setup_test_db().await;
+        let collection = db.collection::<ClmmPool>("clmm_pools");
+        let repository = ClmmPoolRepository::new(collection.clone());
+
+        // Insert test data
+        let test_pools = create_test_pools();
+        collection.insert_many(&test_pools, None).await.unwrap();
+
+        let now = Utc::now().timestamp() as u64;
+        for (pool_address, tvl) in [
+            ("pool1111111111111111111111111111111", 5_000.0),
+            ("pool2222222222222222222222222222222", 120_000.0),
+        ] {
+            let tvl_info = TvlInfo {
+                amount_0: 1.0,
+                amount_1: 1.0,
+                price_usd_0: Some(1.0),
+                price_usd_1: Some(1.0),
+                tvl,
+                updated_at: now,
+            };
+            assert!(repository.update_tvl_info(pool_address, &tvl_info).await.unwrap());
+        }
+
+        // Test TVL descending sort: pools without TVL come last
+        let params = PoolListRequest {
+            pool_sort_field: Some("tvl".to_string()),
+            ..Default::default()
+        };
+
+        let result = repository.query_pools_with_pagination(&params).await.unwrap();
+
+        assert_eq!(result.pools.len(), 3);
+        assert_eq!(result.pools[0].pool_address, "pool2222222222222222222222222222222");
+        assert_eq!(result.pools[1].pool_address, "pool1111111111111111111111111111111");
+        assert!(result.pools[2].tvl_info.is_none());
+
+        // Test TVL range filter
+        let params = PoolListRequest {
+            min_tvl: Some(10_000.0),
+            ..Default::default()
+        };
+
+        let result = repository.query_pools_with_pagination(&params).await.unwrap();
+
+        assert_eq!(result.pools.len(), 1);
+        assert_eq!(result.pools[0].pool_address, "pool2222222222222222222222222222222");
+
+        // Only active pools with stale TVL need refresh
+        let result = repository.find_pools_need_tvl_refresh(now, 10).await.unwrap();
+        assert!(result.is_empty());
+        let result = repository.find_pools_need_tvl_refresh(now + 1, 10).await.unwrap();
+        assert_eq!(result.len(), 2);
+
+        // Cleanup
+        db.drop(None).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_build_filter_summary() {
         let db = setup_test_db().await;
@@ -0,0 +1,215 @@
+use crate::events::event_identity::EventIdentity;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+/// CLMM交换事件数据模型（存储原始事件数据）
+///
+/// 这个模型对应CLMM程序的SwapEvent结构体：
+/// ```rust
+/// pub struct SwapEvent {
+///     pub pool_state: Pubkey,       // 池子状态地址
+///     pub sender: Pubkey,           // 交换发起者
+///     pub token_account_0: Pubkey,  // token_0账户
+///     pub token_account_1: Pubkey,  // token_1账户
+///     pub amount_0: u64,            // token_0数量
+///     pub transfer_fee_0: u64,      // token_0转账费
+///     pub amount_1: u64,            // token_1数量
+///     pub transfer_fee_1: u64,      // token_1转账费
+///     pub zero_for_one: bool,       // 是否token_0换token_1
+///     pub sqrt_price_x64: u128,     // 交换后的sqrt价格
+///     pub liquidity: u128,          // 交换后的流动性
+///     pub tick: i32,                // 交换后的tick
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClmmSwapEventModel {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    // 用户和池子信息
+    /// 池子地址
+    pub pool_id: String,
+    /// 交换发起者钱包地址
+    pub sender: String,
+    /// token_0账户地址
+    pub token_account_0: String,
+    /// token_1账户地址
+    pub token_account_1: String,
+
+    // 交换数量
+    /// token_0数量
+    pub amount_0: u64,
+    /// token_0转账费
+    pub transfer_fee_0: u64,
+    /// token_1数量
+    pub amount_1: u64,
+    /// token_1转账费
+    pub transfer_fee_1: u64,
+
+    // 交换方向
+    /// 是否token_0换token_1
+    pub zero_for_one: bool,
+
+    // 交换后的池子状态（u128以字符串存储，避免精度丢失）
+    /// 交换后的sqrt价格(x64)
+    pub sqrt_price_x64: String,
+    /// 交换后的流动性
+    pub liquidity: String,
+    /// 交换后的tick
+    pub tick: i32,
+
+    // 交易元信息
+    /// 交易签名（唯一标识）
+    pub signature: String,
+    /// 区块高度
+    pub slot: u64,
+    #[serde(flatten)]
+    pub identity: EventIdentity,
+    /// 区块时间戳
+    pub block_time: Option<i64>,
+
+    // 记录时间
+    /// 事件创建时间
+    pub created_at: DateTime<Utc>,
+}
+
+impl ClmmSwapEventModel {
+    /// 验证事件数据是否有效
+    pub fn validate(&self) -> Result<(), String> {
+        // 验证池子地址
+        if self.pool_id.is_empty() {
+            return Err("池子地址不能为空".to_string());
+        }
+
+        // 验证发起者地址
+        if self.sender.is_empty() {
+            return Err("交换发起者地址不能为空".to_string());
+        }
+
+        // 验证交换数量
+        if self.amount_0 == 0 && self.amount_1 == 0 {
+            return Err("token_0和token_1数量不能同时为0".to_string());
+        }
+
+        // 验证sqrt价格
+        if self.sqrt_price_x64.parse::<u128>().is_err() {
+            return Err(format!("无效的sqrt价格: {}", self.sqrt_price_x64));
+        }
+
+        // 验证交易签名
+        if self.signature.is_empty() {
+            return Err("交易签名不能为空".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// 获取输入数量（按交换方向）
+    pub fn get_input_amount(&self) -> u64 {
+        if self.zero_for_one {
+            self.amount_0
+        } else {
+            self.amount_1
+        }
+    }
+
+    /// 获取输出数量（按交换方向）
+    pub fn get_output_amount(&self) -> u64 {
+        if self.zero_for_one {
+            self.amount_1
+        } else {
+            self.amount_0
+        }
+    }
+
+    /// 根据sqrt_price_x64计算原始价格（token_1/token_0，未按精度调整）
+    pub fn get_raw_price(&self) -> f64 {
+        let sqrt_price = self.sqrt_price_x64.parse::<u128>().unwrap_or(0) as f64 / (1u128 << 64) as f64;
+        sqrt_price * sqrt_price
+    }
+
+    /// 获取交换方向描述
+    pub fn get_swap_direction(&self) -> &'static str {
+        if self.zero_for_one {
+            "token0_to_token1"
+        } else {
+            "token1_to_token0"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_clmm_swap_event() -> ClmmSwapEventModel {
+        ClmmSwapEventModel {
+            id: None,
+            pool_id: "test_pool_id".to_string(),
+            sender: "test_sender".to_string(),
+            token_account_0: "token_account_0".to_string(),
+            token_account_1: "token_account_1".to_string(),
+            amount_0: 100000,
+            transfer_fee_0: 10,
+            amount_1: 200000,
+            transfer_fee_1: 20,
+            zero_for_one: true,
+            sqrt_price_x64: (1u128 << 64).to_string(),
+            liquidity: "5000000".to_string(),
+            tick: 0,
+            signature: "test_signature".to_string(),
+            slot: 12345,
+            identity: EventIdentity::default(),
+            block_time: Some(1234567890),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_validate_success() {
+        let event = create_test_clmm_swap_event();
+        assert!(event.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_empty_pool_id() {
+        let mut event = create_test_clmm_swap_event();
+        event.pool_id = String::new();
+        assert!(event.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_zero_amounts() {
+        let mut event = create_test_clmm_swap_event();
+        event.amount_0 = 0;
+        event.amount_1 = 0;
+        assert!(event.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_invalid_sqrt_price() {
+        let mut event = create_test_clmm_swap_event();
+        event.sqrt_price_x64 = "not_a_number".to_string();
+        assert!(event.validate().is_err());
+    }
+
+    #[test]
+    fn test_input_output_amount_by_direction() {
+        let mut event = create_test_clmm_swap_event();
+        assert_eq!(event.get_input_amount(), 100000);
+        assert_eq!(event.get_output_amount(), 200000);
+        assert_eq!(event.get_swap_direction(), "token0_to_token1");
+
+        event.zero_for_one = false;
+        assert_eq!(event.get_input_amount(), 200000);
+        assert_eq!(event.get_output_amount(), 100000);
+        assert_eq!(event.get_swap_direction(), "token1_to_token0");
+    }
+
+    #[test]
+    fn test_get_raw_price() {
+        let event = create_test_clmm_swap_event();
+        assert!((event.get_raw_price() - 1.0).abs() < 1e-9);
+    }
+}
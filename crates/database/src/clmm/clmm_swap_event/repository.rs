@@ -0,0 +1,204 @@
+use crate::clmm::clmm_swap_event::model::ClmmSwapEventModel;
+use crate::events::event_identity::{drop_legacy_unique_index, event_identity_filter, event_identity_index};
+use anyhow::Result;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, Document},
+    options::{FindOptions, IndexOptions},
+    Collection, IndexModel,
+};
+use tracing::{debug, error, info, warn};
+
+/// ClmmSwapEvent仓储接口
+#[derive(Clone, Debug)]
+pub struct ClmmSwapEventRepository {
+    collection: Collection<ClmmSwapEventModel>,
+}
+
+impl ClmmSwapEventRepository {
+    /// 创建新的ClmmSwapEvent仓储
+    pub fn new(collection: Collection<ClmmSwapEventModel>) -> Self {
+        Self { collection }
+    }
+
+    /// 初始化数据库索引
+    pub async fn init_indexes(&self) -> Result<()> {
+        info!("🔧 初始化ClmmSwapEvent集合索引...");
+
+        let indexes = vec![
+            // 事件唯一标识索引（同一交易中的多个事件各记录一次）
+            event_identity_index(),
+            // 池子交换历史查询索引（交易历史/价格序列）
+            IndexModel::builder()
+                .keys(doc! { "pool_id": 1, "slot": -1 })
+                .options(IndexOptions::builder().name("idx_pool_id_slot".to_string()).build())
+                .build(),
+            // 用户交换历史查询索引
+            IndexModel::builder()
+                .keys(doc! { "sender": 1, "created_at": -1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("idx_sender_created_at".to_string())
+                        .build(),
+                )
+                .build(),
+            // 区块高度查询索引
+            IndexModel::builder()
+                .keys(doc! { "slot": -1 })
+                .options(IndexOptions::builder().name("idx_slot".to_string()).build())
+                .build(),
+            // 出块时间范围查询索引（滚动统计按出块时间筛选）
+            IndexModel::builder()
+                .keys(doc! { "block_time": -1 })
+                .options(IndexOptions::builder().name("idx_block_time".to_string()).build())
+                .build(),
+        ];
+
+        // 按signature唯一的旧索引会拒绝同一交易中的多个事件
+        drop_legacy_unique_index(&self.collection, "idx_signature_unique").await?;
+
+        match self.collection.create_indexes(indexes, None).await {
+            Ok(_) => {
+                info!("✅ ClmmSwapEvent索引创建成功");
+                Ok(())
+            }
+            Err(e) => {
+                error!("❌ ClmmSwapEvent索引创建失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 插入单个CLMM交换事件
+    pub async fn insert(&self, mut event: ClmmSwapEventModel) -> Result<ClmmSwapEventModel> {
+        event.created_at = Utc::now();
+
+        // 验证数据
+        if let Err(e) = event.validate() {
+            error!("❌ ClmmSwapEvent数据验证失败: {}", e);
+            return Err(anyhow::anyhow!("数据验证失败: {}", e));
+        }
+
+        match self.collection.insert_one(&event, None).await {
+            Ok(result) => {
+                info!(
+                    "✅ CLMM交换事件插入成功: signature={}, pool={}",
+                    event.signature, event.pool_id
+                );
+                if let Some(id) = result.inserted_id.as_object_id() {
+                    event.id = Some(id);
+                }
+                Ok(event)
+            }
+            Err(e) => {
+                if e.to_string().contains("duplicate key") {
+                    warn!("⚠️ CLMM交换事件已存在，signature重复: {}", event.signature);
+                    return Err(anyhow::anyhow!(
+                        "CLMM交换事件已存在，signature重复: {}",
+                        event.signature
+                    ));
+                }
+                error!("❌ CLMM交换事件插入失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 根据signature查找CLMM交换事件
+    pub async fn find_by_signature(&self, signature: &str) -> Result<Option<ClmmSwapEventModel>> {
+        let filter = doc! { "signature": signature };
+
+        match self.collection.find_one(filter, None).await {
+            Ok(result) => {
+                if result.is_some() {
+                    debug!("✅ 根据signature查找CLMM交换事件成功: {}", signature);
+                } else {
+                    debug!("📭 根据signature未找到CLMM交换事件: {}", signature);
+                }
+                Ok(result)
+            }
+            Err(e) => {
+                error!("❌ 根据signature查找CLMM交换事件失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 根据事件唯一标识检查事件是否存在（防重）
+    pub async fn exists_by_identity(
+        &self,
+        signature: &str,
+        instruction_index: u32,
+        event_ordinal: u32,
+    ) -> Result<bool> {
+        let filter = event_identity_filter(signature, instruction_index, event_ordinal);
+        let count = self.collection.count_documents(filter, None).await?;
+        Ok(count > 0)
+    }
+
+    /// 根据池子查找CLMM交换事件（按slot倒序）
+    pub async fn find_by_pool(&self, pool_id: &str, limit: Option<i64>) -> Result<Vec<ClmmSwapEventModel>> {
+        let filter = doc! { "pool_id": pool_id };
+        let options = FindOptions::builder()
+            .sort(doc! { "slot": -1 })
+            .limit(limit.unwrap_or(100))
+            .build();
+
+        match self.collection.find(filter, options).await {
+            Ok(cursor) => {
+                let events: Vec<ClmmSwapEventModel> = cursor.try_collect().await?;
+                debug!("✅ 根据pool_id查找CLMM交换事件成功，查询到{}条记录", events.len());
+                Ok(events)
+            }
+            Err(e) => {
+                error!("❌ 根据pool_id查找CLMM交换事件失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 根据用户查找CLMM交换事件
+    pub async fn find_by_sender(&self, sender: &str, limit: Option<i64>) -> Result<Vec<ClmmSwapEventModel>> {
+        let filter = doc! { "sender": sender };
+        let options = FindOptions::builder()
+            .sort(doc! { "created_at": -1 })
+            .limit(limit.unwrap_or(100))
+            .build();
+
+        match self.collection.find(filter, options).await {
+            Ok(cursor) => {
+                let events: Vec<ClmmSwapEventModel> = cursor.try_collect().await?;
+                debug!("✅ 根据sender查找CLMM交换事件成功，查询到{}条记录", events.len());
+                Ok(events)
+            }
+            Err(e) => {
+                error!("❌ 根据sender查找CLMM交换事件失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 根据过滤条件查找CLMM交换事件
+    pub async fn find_with_filter(&self, filter: Document, options: FindOptions) -> Result<Vec<ClmmSwapEventModel>> {
+        let cursor = self.collection.find(filter, options).await?;
+        let events: Vec<ClmmSwapEventModel> = cursor.try_collect().await?;
+
+        debug!("✅ 带过滤条件查询CLMM交换事件成功，查询到{}条记录", events.len());
+        Ok(events)
+    }
+
+    /// 统计CLMM交换事件数量
+    pub async fn count_with_filter(&self, filter: Document) -> Result<u64> {
+        match self.collection.count_documents(filter, None).await {
+            Ok(count) => {
+                debug!("✅ 统计CLMM交换事件数量成功: {}", count);
+                Ok(count)
+            }
+            Err(e) => {
+                error!("❌ 统计CLMM交换事件数量失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+}
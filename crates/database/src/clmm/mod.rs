@@ -1,6 +1,9 @@
 pub mod clmm_config;
 pub mod clmm_pool;
+pub mod clmm_swap_event;
 pub mod position;
+pub mod position_event;
+pub mod protocol_fee_event;
 pub mod refer;
 pub mod reward;
 pub mod token_info;
\ No newline at end of file
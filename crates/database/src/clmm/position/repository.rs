@@ -2,7 +2,7 @@ use crate::Database;
 use async_trait::async_trait;
 use futures::stream::StreamExt;
 use mongodb::{
-    bson::{doc, Document},
+    bson::{doc, Bson, Document},
     options::{FindOptions, IndexOptions},
     results::{InsertOneResult, UpdateResult},
     IndexModel,
@@ -61,12 +61,46 @@ pub trait PositionRepositoryTrait {
         operation_type: &str,
     ) -> AppResult<UpdateResult>;
 
+    /// 以读取到的仓位为期望值原子地应用一次增减流动性（比较并交换）
+    ///
+    /// 仅当仓位的当前流动性和累计增减值仍与`expected`一致时才更新，同时维护累计增加/减少的流动性；
+    /// 返回更新后的流动性，返回None说明仓位已被并发修改，调用方应重新读取后重试
+    async fn compare_and_update_liquidity(
+        &self,
+        expected: &Position,
+        liquidity_change: u128,
+        is_increase: bool,
+        amount_0_change: u64,
+        amount_1_change: u64,
+        operation_type: &str,
+    ) -> AppResult<Option<u128>>;
+
     /// 更新手续费信息
     async fn update_fees(&self, position_key: &str, fees_0: u64, fees_1: u64) -> AppResult<UpdateResult>;
 
     /// 关闭仓位
     async fn close_position(&self, position_key: &str) -> AppResult<UpdateResult>;
 
+    /// 根据链上开仓事件插入仓位（已存在则不覆盖）
+    async fn upsert_from_chain_event(&self, position: Position) -> AppResult<UpdateResult>;
+
+    /// 记录已领取的手续费（累加已赚取手续费并清零未领取手续费）
+    async fn record_collected_fees(&self, position_key: &str, fees_0: u64, fees_1: u64) -> AppResult<UpdateResult>;
+
+    /// 撤销一次流动性/手续费变更（交易未最终确认时回滚）
+    async fn revert_position_change(
+        &self,
+        position_key: &str,
+        new_liquidity: &str,
+        total_liquidity_added: &str,
+        total_liquidity_removed: &str,
+        amount_deltas: (i64, i64),
+        fee_deltas: (i64, i64),
+    ) -> AppResult<UpdateResult>;
+
+    /// 删除由指定开仓交易建立的仓位（开仓交易未最终确认时回滚）
+    async fn delete_by_initial_signature(&self, position_key: &str, signature: &str) -> AppResult<bool>;
+
     /// 标记仓位为已同步
     async fn mark_synced(&self, position_key: &str) -> AppResult<UpdateResult>;
 
@@ -289,6 +323,71 @@ impl PositionRepositoryTrait for Database {
         Ok(result)
     }
 
+    async fn compare_and_update_liquidity(
+        &self,
+        expected: &Position,
+        liquidity_change: u128,
+        is_increase: bool,
+        amount_0_change: u64,
+        amount_1_change: u64,
+        operation_type: &str,
+    ) -> AppResult<Option<u128>> {
+        let current = parse_liquidity(&expected.current_liquidity);
+        let total_added = parse_liquidity(&expected.total_liquidity_added);
+        let total_removed = parse_liquidity(&expected.total_liquidity_removed);
+
+        let (new_liquidity, total_added, total_removed, amount_0, amount_1) = if is_increase {
+            (
+                current.saturating_add(liquidity_change),
+                total_added.saturating_add(liquidity_change),
+                total_removed,
+                amount_0_change as i64,
+                amount_1_change as i64,
+            )
+        } else {
+            (
+                current.saturating_sub(liquidity_change),
+                total_added,
+                total_removed.saturating_add(liquidity_change),
+                -(amount_0_change as i64),
+                -(amount_1_change as i64),
+            )
+        };
+
+        let mut set_doc = doc! {
+            "current_liquidity": new_liquidity.to_string(),
+            "total_liquidity_added": total_added.to_string(),
+            "total_liquidity_removed": total_removed.to_string(),
+            "last_operation_type": operation_type,
+            "updated_at": chrono::Utc::now().timestamp() as f64
+        };
+
+        // 减少流动性后归零时关闭仓位
+        if !is_increase && new_liquidity == 0 {
+            set_doc.insert("status", "Closed");
+            set_doc.insert("is_active", false);
+        }
+
+        // 以读取时的流动性字段作为条件，期间被其他写入修改过则不会命中
+        let filter = doc! {
+            "position_key": &expected.position_key,
+            "current_liquidity": liquidity_field_condition(&expected.current_liquidity),
+            "total_liquidity_added": liquidity_field_condition(&expected.total_liquidity_added),
+            "total_liquidity_removed": liquidity_field_condition(&expected.total_liquidity_removed)
+        };
+        let update = doc! {
+            "$set": set_doc,
+            "$inc": {
+                "total_operations": 1,
+                "current_amount_0": amount_0,
+                "current_amount_1": amount_1
+            }
+        };
+
+        let result = self.positions.update_one(filter, update, None).await?;
+        Ok((result.matched_count > 0).then_some(new_liquidity))
+    }
+
     async fn update_fees(&self, position_key: &str, fees_0: u64, fees_1: u64) -> AppResult<UpdateResult> {
         let filter = doc! { "position_key": position_key };
         let update = doc! {
@@ -323,6 +422,82 @@ impl PositionRepositoryTrait for Database {
         Ok(result)
     }
 
+    async fn upsert_from_chain_event(&self, position: Position) -> AppResult<UpdateResult> {
+        let filter = doc! { "position_key": &position.position_key };
+        let update = doc! { "$setOnInsert": mongodb::bson::to_bson(&position)? };
+        let options = mongodb::options::UpdateOptions::builder().upsert(true).build();
+
+        let result = self.positions.update_one(filter, update, options).await?;
+        Ok(result)
+    }
+
+    async fn record_collected_fees(&self, position_key: &str, fees_0: u64, fees_1: u64) -> AppResult<UpdateResult> {
+        let filter = doc! { "position_key": position_key };
+        let update = doc! {
+            "$inc": {
+                "fees_earned_0": fees_0 as i64,
+                "fees_earned_1": fees_1 as i64,
+                "total_operations": 1
+            },
+            "$set": {
+                "unclaimed_fees_0": 0_i64,
+                "unclaimed_fees_1": 0_i64,
+                "last_operation_type": "collect_fee",
+                "updated_at": chrono::Utc::now().timestamp() as f64
+            }
+        };
+
+        let result = self.positions.update_one(filter, update, None).await?;
+        Ok(result)
+    }
+
+    async fn revert_position_change(
+        &self,
+        position_key: &str,
+        new_liquidity: &str,
+        total_liquidity_added: &str,
+        total_liquidity_removed: &str,
+        amount_deltas: (i64, i64),
+        fee_deltas: (i64, i64),
+    ) -> AppResult<UpdateResult> {
+        let mut set_doc = doc! {
+            "current_liquidity": new_liquidity,
+            "total_liquidity_added": total_liquidity_added,
+            "total_liquidity_removed": total_liquidity_removed,
+            "last_operation_type": "rollback",
+            "updated_at": chrono::Utc::now().timestamp() as f64
+        };
+
+        // 撤销后仍有流动性的仓位恢复为活跃状态
+        if new_liquidity != "0" {
+            set_doc.insert("status", "Active");
+            set_doc.insert("is_active", true);
+        }
+
+        let filter = doc! { "position_key": position_key };
+        let update = doc! {
+            "$set": set_doc,
+            "$inc": {
+                "current_amount_0": amount_deltas.0,
+                "current_amount_1": amount_deltas.1,
+                "fees_earned_0": fee_deltas.0,
+                "fees_earned_1": fee_deltas.1
+            }
+        };
+
+        let result = self.positions.update_one(filter, update, None).await?;
+        Ok(result)
+    }
+
+    async fn delete_by_initial_signature(&self, position_key: &str, signature: &str) -> AppResult<bool> {
+        let filter = doc! {
+            "position_key": position_key,
+            "metadata.initial_transaction_signature": signature
+        };
+        let result = self.positions.delete_one(filter, None).await?;
+        Ok(result.deleted_count > 0)
+    }
+
     async fn mark_synced(&self, position_key: &str) -> AppResult<UpdateResult> {
         let now = chrono::Utc::now().timestamp() as u64;
         let filter = doc! { "position_key": position_key };
@@ -521,6 +696,20 @@ impl PositionRepositoryTrait for Database {
     }
 }
 
+/// 解析字符串形式的流动性，空值或非法值按0处理
+fn parse_liquidity(value: &str) -> u128 {
+    value.parse::<u128>().unwrap_or(0)
+}
+
+/// 构造流动性字段的比较条件，空值同时匹配字段缺失的旧文档
+fn liquidity_field_condition(value: &str) -> Bson {
+    if value.is_empty() {
+        Bson::Document(doc! { "$in": ["", Bson::Null] })
+    } else {
+        Bson::String(value.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -572,6 +761,16 @@ mod tests {
         assert_eq!(stats.unique_users, 25);
     }
 
+    #[test]
+    fn test_liquidity_field_condition() {
+        assert_eq!(liquidity_field_condition("1000"), Bson::String("1000".to_string()));
+        // 旧文档可能缺少累计字段，空值需要同时匹配缺失字段
+        let condition = liquidity_field_condition("");
+        let values = condition.as_document().unwrap().get_array("$in").unwrap();
+        assert_eq!(values, &vec![Bson::String(String::new()), Bson::Null]);
+        assert_eq!(parse_liquidity(""), 0);
+    }
+
     #[test]
     fn test_index_field_names() {
         // 验证索引字段名与模型字段名一致
@@ -0,0 +1,167 @@
+use crate::events::event_identity::EventIdentity;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+/// CLMM仓位事件类型
+///
+/// 对应CLMM程序states/personal_position.rs中的仓位生命周期事件
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClmmPositionEventType {
+    /// 开仓（CreatePersonalPositionEvent）
+    CreatePosition,
+    /// 增加流动性（IncreaseLiquidityEvent）
+    IncreaseLiquidity,
+    /// 减少流动性（DecreaseLiquidityEvent）
+    DecreaseLiquidity,
+    /// 领取手续费（CollectPersonalFeeEvent）
+    CollectPersonalFee,
+}
+
+impl ClmmPositionEventType {
+    /// 获取存储用的事件类型字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClmmPositionEventType::CreatePosition => "create_position",
+            ClmmPositionEventType::IncreaseLiquidity => "increase_liquidity",
+            ClmmPositionEventType::DecreaseLiquidity => "decrease_liquidity",
+            ClmmPositionEventType::CollectPersonalFee => "collect_personal_fee",
+        }
+    }
+}
+
+/// CLMM仓位事件流水模型
+///
+/// 每条链上仓位事件只记录一次，用于保证Position文档的增量更新幂等，
+/// 同时作为回填服务判断签名是否已处理的依据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClmmPositionEvent {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    /// 事件类型
+    pub event_type: ClmmPositionEventType,
+
+    /// 仓位地址（personal position PDA）
+    pub position_key: String,
+
+    /// 仓位NFT mint地址
+    pub position_nft_mint: String,
+
+    /// 池子地址（仅开仓事件携带）
+    pub pool_id: Option<String>,
+
+    /// 流动性变化量（u128以字符串存储）
+    pub liquidity: String,
+
+    /// token_0数量（存入/取出/领取）
+    pub amount_0: u64,
+
+    /// token_1数量（存入/取出/领取）
+    pub amount_1: u64,
+
+    /// token_0手续费
+    pub fee_amount_0: u64,
+
+    /// token_1手续费
+    pub fee_amount_1: u64,
+
+    /// 交易签名
+    pub signature: String,
+
+    /// 区块高度
+    pub slot: u64,
+
+    /// 事件在交易中的位置
+    #[serde(flatten)]
+    pub identity: EventIdentity,
+
+    /// 区块时间戳（旧记录为空，按记录创建时间统计）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_time: Option<i64>,
+
+    /// 记录创建时间
+    pub created_at: DateTime<Utc>,
+}
+
+impl ClmmPositionEvent {
+    /// 验证事件数据是否有效
+    pub fn validate(&self) -> Result<(), String> {
+        if self.position_key.is_empty() {
+            return Err("仓位地址不能为空".to_string());
+        }
+
+        if self.position_nft_mint.is_empty() {
+            return Err("仓位NFT mint地址不能为空".to_string());
+        }
+
+        if self.liquidity.parse::<u128>().is_err() {
+            return Err(format!("无效的流动性数值: {}", self.liquidity));
+        }
+
+        if self.signature.is_empty() {
+            return Err("交易签名不能为空".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_position_event() -> ClmmPositionEvent {
+        ClmmPositionEvent {
+            id: None,
+            event_type: ClmmPositionEventType::IncreaseLiquidity,
+            position_key: "test_position_key".to_string(),
+            position_nft_mint: "test_nft_mint".to_string(),
+            pool_id: None,
+            liquidity: "1000000".to_string(),
+            amount_0: 1000,
+            amount_1: 2000,
+            fee_amount_0: 0,
+            fee_amount_1: 0,
+            signature: "test_signature".to_string(),
+            slot: 12345,
+            identity: EventIdentity::default(),
+            block_time: Some(1234567890),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_validate_success() {
+        let event = create_test_position_event();
+        assert!(event.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_invalid_liquidity() {
+        let mut event = create_test_position_event();
+        event.liquidity = "abc".to_string();
+        assert!(event.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_empty_position_key() {
+        let mut event = create_test_position_event();
+        event.position_key = String::new();
+        assert!(event.validate().is_err());
+    }
+
+    #[test]
+    fn test_event_type_serialization() {
+        assert_eq!(ClmmPositionEventType::CreatePosition.as_str(), "create_position");
+        assert_eq!(
+            serde_json::to_string(&ClmmPositionEventType::DecreaseLiquidity).unwrap(),
+            "\"decrease_liquidity\""
+        );
+        assert_eq!(
+            serde_json::to_string(&ClmmPositionEventType::CollectPersonalFee).unwrap(),
+            format!("\"{}\"", ClmmPositionEventType::CollectPersonalFee.as_str())
+        );
+    }
+}
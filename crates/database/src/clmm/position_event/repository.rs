@@ -0,0 +1,169 @@
+use crate::clmm::position_event::model::{ClmmPositionEvent, ClmmPositionEventType};
+use crate::events::event_identity::{drop_legacy_unique_index, event_identity_filter, event_identity_index};
+use anyhow::Result;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId},
+    options::{FindOptions, IndexOptions},
+    Collection, IndexModel,
+};
+use tracing::{debug, error, info};
+
+/// ClmmPositionEvent仓储接口
+#[derive(Clone, Debug)]
+pub struct ClmmPositionEventRepository {
+    collection: Collection<ClmmPositionEvent>,
+}
+
+impl ClmmPositionEventRepository {
+    /// 创建新的ClmmPositionEvent仓储
+    pub fn new(collection: Collection<ClmmPositionEvent>) -> Self {
+        Self { collection }
+    }
+
+    /// 初始化数据库索引
+    pub async fn init_indexes(&self) -> Result<()> {
+        info!("🔧 初始化ClmmPositionEvent集合索引...");
+
+        let indexes = vec![
+            // 事件唯一标识索引（同一交易中的多个事件各记录一次）
+            event_identity_index(),
+            // 仓位事件历史查询索引
+            IndexModel::builder()
+                .keys(doc! { "position_key": 1, "slot": -1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("idx_position_key_slot".to_string())
+                        .build(),
+                )
+                .build(),
+            // 回填查询索引
+            IndexModel::builder()
+                .keys(doc! { "event_type": 1, "slot": 1 })
+                .options(IndexOptions::builder().name("idx_event_type_slot".to_string()).build())
+                .build(),
+        ];
+
+        // 旧的唯一索引已被事件唯一标识索引取代
+        drop_legacy_unique_index(&self.collection, "idx_signature_event_type_nft_unique").await?;
+
+        match self.collection.create_indexes(indexes, None).await {
+            Ok(_) => {
+                info!("✅ ClmmPositionEvent索引创建成功");
+                Ok(())
+            }
+            Err(e) => {
+                error!("❌ ClmmPositionEvent索引创建失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 插入仓位事件（已存在时返回None，保证幂等）
+    pub async fn insert_if_absent(&self, mut event: ClmmPositionEvent) -> Result<Option<ObjectId>> {
+        event.created_at = Utc::now();
+
+        if let Err(e) = event.validate() {
+            error!("❌ ClmmPositionEvent数据验证失败: {}", e);
+            return Err(anyhow::anyhow!("数据验证失败: {}", e));
+        }
+
+        // 同一事件（或引入标识之前按签名写入的旧记录）已存在时跳过
+        if let Some((instruction_index, event_ordinal)) = event.identity.position() {
+            let filter = event_identity_filter(&event.signature, instruction_index, event_ordinal);
+            if self.collection.count_documents(filter, None).await? > 0 {
+                debug!(
+                    "ℹ️ 仓位事件已存在，跳过: type={}, signature={}",
+                    event.event_type.as_str(),
+                    event.signature
+                );
+                return Ok(None);
+            }
+        }
+
+        match self.collection.insert_one(&event, None).await {
+            Ok(result) => {
+                debug!(
+                    "✅ 仓位事件插入成功: type={}, position={}, signature={}",
+                    event.event_type.as_str(),
+                    event.position_key,
+                    event.signature
+                );
+                Ok(result.inserted_id.as_object_id())
+            }
+            Err(e) => {
+                if e.to_string().contains("duplicate key") {
+                    debug!(
+                        "ℹ️ 仓位事件已存在，跳过: type={}, signature={}",
+                        event.event_type.as_str(),
+                        event.signature
+                    );
+                    return Ok(None);
+                }
+                error!("❌ 仓位事件插入失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 删除仓位事件（仓位更新失败时撤销流水，以便重试）
+    pub async fn delete_by_id(&self, id: &ObjectId) -> Result<bool> {
+        let result = self.collection.delete_one(doc! { "_id": id }, None).await?;
+        Ok(result.deleted_count > 0)
+    }
+
+    /// 根据签名和事件类型查找仓位事件
+    pub async fn find_by_signature(
+        &self,
+        signature: &str,
+        event_type: ClmmPositionEventType,
+    ) -> Result<Vec<ClmmPositionEvent>> {
+        let filter = doc! { "signature": signature, "event_type": event_type.as_str() };
+        let cursor = self.collection.find(filter, None).await?;
+        let events: Vec<ClmmPositionEvent> = cursor.try_collect().await?;
+        Ok(events)
+    }
+
+    /// 根据签名查找所有类型的仓位事件（按写入顺序倒序，便于逆序撤销）
+    pub async fn find_all_by_signature(&self, signature: &str) -> Result<Vec<ClmmPositionEvent>> {
+        let options = FindOptions::builder().sort(doc! { "_id": -1 }).build();
+        let cursor = self.collection.find(doc! { "signature": signature }, options).await?;
+        let events: Vec<ClmmPositionEvent> = cursor.try_collect().await?;
+        Ok(events)
+    }
+
+    /// 根据仓位地址查找全部事件流水（按链上顺序正序，用于重算仓位）
+    pub async fn find_all_by_position_key(&self, position_key: &str) -> Result<Vec<ClmmPositionEvent>> {
+        let options = FindOptions::builder()
+            .sort(doc! { "slot": 1, "instruction_index": 1, "event_ordinal": 1 })
+            .build();
+        let cursor = self
+            .collection
+            .find(doc! { "position_key": position_key }, options)
+            .await?;
+        let events: Vec<ClmmPositionEvent> = cursor.try_collect().await?;
+        Ok(events)
+    }
+
+    /// 根据仓位地址查找事件历史（按slot倒序）
+    pub async fn find_by_position_key(&self, position_key: &str, limit: Option<i64>) -> Result<Vec<ClmmPositionEvent>> {
+        let filter = doc! { "position_key": position_key };
+        let options = FindOptions::builder()
+            .sort(doc! { "slot": -1 })
+            .limit(limit.unwrap_or(100))
+            .build();
+
+        match self.collection.find(filter, options).await {
+            Ok(cursor) => {
+                let events: Vec<ClmmPositionEvent> = cursor.try_collect().await?;
+                debug!("✅ 根据position_key查找仓位事件成功，查询到{}条记录", events.len());
+                Ok(events)
+            }
+            Err(e) => {
+                error!("❌ 根据position_key查找仓位事件失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+}
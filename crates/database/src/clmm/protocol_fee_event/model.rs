@@ -0,0 +1,156 @@
+use crate::events::event_identity::EventIdentity;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+/// CLMM协议费用领取类型
+///
+/// 链上collect_protocol_fee与collect_fund_fee两条指令都会发出CollectProtocolFeeEvent，
+/// 通过指令日志区分
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClmmProtocolFeeType {
+    /// 协议费（collect_protocol_fee）
+    Protocol,
+    /// 基金费（collect_fund_fee）
+    Fund,
+}
+
+impl ClmmProtocolFeeType {
+    /// 获取存储用的费用类型字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClmmProtocolFeeType::Protocol => "protocol",
+            ClmmProtocolFeeType::Fund => "fund",
+        }
+    }
+
+    /// 从字符串解析费用类型
+    pub fn from_str_opt(value: &str) -> Option<Self> {
+        match value {
+            "protocol" => Some(ClmmProtocolFeeType::Protocol),
+            "fund" => Some(ClmmProtocolFeeType::Fund),
+            _ => None,
+        }
+    }
+}
+
+/// CLMM协议费用领取流水模型
+///
+/// 每条链上CollectProtocolFeeEvent记录一次，用于协议/基金费用的对账统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClmmProtocolFeeEvent {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    /// 池子地址
+    pub pool_id: String,
+
+    /// 费用类型
+    pub fee_type: ClmmProtocolFeeType,
+
+    /// 接收token_0的账户
+    pub recipient_token_account_0: String,
+
+    /// 接收token_1的账户
+    pub recipient_token_account_1: String,
+
+    /// 领取的token_0数量
+    pub amount_0: u64,
+
+    /// 领取的token_1数量
+    pub amount_1: u64,
+
+    /// 交易签名
+    pub signature: String,
+
+    /// 区块高度
+    pub slot: u64,
+
+    /// 事件在交易中的位置
+    #[serde(flatten)]
+    pub identity: EventIdentity,
+
+    /// 记录创建时间
+    pub created_at: DateTime<Utc>,
+}
+
+impl ClmmProtocolFeeEvent {
+    /// 验证事件数据是否有效
+    pub fn validate(&self) -> Result<(), String> {
+        if self.pool_id.is_empty() {
+            return Err("池子地址不能为空".to_string());
+        }
+
+        if self.signature.is_empty() {
+            return Err("交易签名不能为空".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// 池子协议费用累计统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClmmPoolProtocolFeeTotals {
+    /// 池子地址
+    pub pool_id: String,
+    /// 累计领取的协议费token_0
+    pub protocol_amount_0: u64,
+    /// 累计领取的协议费token_1
+    pub protocol_amount_1: u64,
+    /// 累计领取的基金费token_0
+    pub fund_amount_0: u64,
+    /// 累计领取的基金费token_1
+    pub fund_amount_1: u64,
+    /// 领取次数
+    pub collect_count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_fee_event() -> ClmmProtocolFeeEvent {
+        ClmmProtocolFeeEvent {
+            id: None,
+            pool_id: "test_pool".to_string(),
+            fee_type: ClmmProtocolFeeType::Protocol,
+            recipient_token_account_0: "recipient_0".to_string(),
+            recipient_token_account_1: "recipient_1".to_string(),
+            amount_0: 1000,
+            amount_1: 0,
+            signature: "test_signature".to_string(),
+            slot: 12345,
+            identity: EventIdentity::default(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_validate_success() {
+        let event = create_test_fee_event();
+        assert!(event.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_empty_signature() {
+        let mut event = create_test_fee_event();
+        event.signature = String::new();
+        assert!(event.validate().is_err());
+    }
+
+    #[test]
+    fn test_fee_type_round_trip() {
+        assert_eq!(ClmmProtocolFeeType::Fund.as_str(), "fund");
+        assert_eq!(
+            ClmmProtocolFeeType::from_str_opt("protocol"),
+            Some(ClmmProtocolFeeType::Protocol)
+        );
+        assert_eq!(ClmmProtocolFeeType::from_str_opt("unknown"), None);
+        assert_eq!(
+            serde_json::to_string(&ClmmProtocolFeeType::Fund).unwrap(),
+            format!("\"{}\"", ClmmProtocolFeeType::Fund.as_str())
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use crate::clmm::protocol_fee_event::model::{ClmmPoolProtocolFeeTotals, ClmmProtocolFeeEvent, ClmmProtocolFeeType};
+use crate::events::event_identity::{drop_legacy_unique_index, event_identity_filter, event_identity_index};
+use anyhow::Result;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId},
+    options::{FindOptions, IndexOptions},
+    Collection, IndexModel,
+};
+use tracing::{debug, error, info};
+
+/// ClmmProtocolFeeEvent仓储接口
+#[derive(Clone, Debug)]
+pub struct ClmmProtocolFeeEventRepository {
+    collection: Collection<ClmmProtocolFeeEvent>,
+}
+
+impl ClmmProtocolFeeEventRepository {
+    /// 创建新的ClmmProtocolFeeEvent仓储
+    pub fn new(collection: Collection<ClmmProtocolFeeEvent>) -> Self {
+        Self { collection }
+    }
+
+    /// 初始化数据库索引
+    pub async fn init_indexes(&self) -> Result<()> {
+        info!("🔧 初始化ClmmProtocolFeeEvent集合索引...");
+
+        let indexes = vec![
+            // 事件唯一标识索引（同一交易中的多个事件各记录一次）
+            event_identity_index(),
+            // 池子领取历史查询索引
+            IndexModel::builder()
+                .keys(doc! { "pool_id": 1, "slot": -1 })
+                .options(IndexOptions::builder().name("idx_pool_id_slot".to_string()).build())
+                .build(),
+            // 回填查询索引
+            IndexModel::builder()
+                .keys(doc! { "slot": 1 })
+                .options(IndexOptions::builder().name("idx_slot".to_string()).build())
+                .build(),
+        ];
+
+        // 旧的唯一索引已被事件唯一标识索引取代
+        drop_legacy_unique_index(&self.collection, "idx_signature_pool_fee_type_unique").await?;
+
+        match self.collection.create_indexes(indexes, None).await {
+            Ok(_) => {
+                info!("✅ ClmmProtocolFeeEvent索引创建成功");
+                Ok(())
+            }
+            Err(e) => {
+                error!("❌ ClmmProtocolFeeEvent索引创建失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 插入协议费用领取事件（已存在时返回None，保证幂等）
+    pub async fn insert_if_absent(&self, mut event: ClmmProtocolFeeEvent) -> Result<Option<ObjectId>> {
+        event.created_at = Utc::now();
+
+        if let Err(e) = event.validate() {
+            error!("❌ ClmmProtocolFeeEvent数据验证失败: {}", e);
+            return Err(anyhow::anyhow!("数据验证失败: {}", e));
+        }
+
+        // 同一事件（或引入标识之前按签名写入的旧记录）已存在时跳过
+        if let Some((instruction_index, event_ordinal)) = event.identity.position() {
+            let filter = event_identity_filter(&event.signature, instruction_index, event_ordinal);
+            if self.collection.count_documents(filter, None).await? > 0 {
+                debug!("ℹ️ 协议费用领取事件已存在，跳过: signature={}", event.signature);
+                return Ok(None);
+            }
+        }
+
+        match self.collection.insert_one(&event, None).await {
+            Ok(result) => {
+                debug!(
+                    "✅ 协议费用领取事件插入成功: type={}, pool={}, signature={}",
+                    event.fee_type.as_str(),
+                    event.pool_id,
+                    event.signature
+                );
+                Ok(result.inserted_id.as_object_id())
+            }
+            Err(e) => {
+                if e.to_string().contains("duplicate key") {
+                    debug!("ℹ️ 协议费用领取事件已存在，跳过: signature={}", event.signature);
+                    return Ok(None);
+                }
+                error!("❌ 协议费用领取事件插入失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 根据签名查找协议费用领取事件
+    pub async fn find_by_signature(&self, signature: &str) -> Result<Vec<ClmmProtocolFeeEvent>> {
+        let cursor = self.collection.find(doc! { "signature": signature }, None).await?;
+        let events: Vec<ClmmProtocolFeeEvent> = cursor.try_collect().await?;
+        Ok(events)
+    }
+
+    /// 根据池子地址查找领取历史（按slot倒序）
+    pub async fn find_by_pool(&self, pool_id: &str, limit: Option<i64>) -> Result<Vec<ClmmProtocolFeeEvent>> {
+        let filter = doc! { "pool_id": pool_id };
+        let options = FindOptions::builder()
+            .sort(doc! { "slot": -1 })
+            .limit(limit.unwrap_or(100))
+            .build();
+
+        match self.collection.find(filter, options).await {
+            Ok(cursor) => {
+                let events: Vec<ClmmProtocolFeeEvent> = cursor.try_collect().await?;
+                debug!("✅ 根据pool_id查找协议费用领取事件成功，查询到{}条记录", events.len());
+                Ok(events)
+            }
+            Err(e) => {
+                error!("❌ 根据pool_id查找协议费用领取事件失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 统计池子累计领取的协议费和基金费
+    pub async fn get_pool_fee_totals(&self, pool_id: &str) -> Result<ClmmPoolProtocolFeeTotals> {
+        let pipeline = vec![
+            doc! { "$match": { "pool_id": pool_id } },
+            doc! {
+                "$group": {
+                    "_id": "$fee_type",
+                    "amount_0": { "$sum": "$amount_0" },
+                    "amount_1": { "$sum": "$amount_1" },
+                    "count": { "$sum": 1 }
+                }
+            },
+        ];
+
+        let mut cursor = self.collection.aggregate(pipeline, None).await?;
+
+        let mut totals = ClmmPoolProtocolFeeTotals {
+            pool_id: pool_id.to_string(),
+            ..Default::default()
+        };
+        while let Some(doc) = cursor.try_next().await? {
+            let amount_0 = doc.get_i64("amount_0").unwrap_or(0) as u64;
+            let amount_1 = doc.get_i64("amount_1").unwrap_or(0) as u64;
+            totals.collect_count += doc.get_i32("count").unwrap_or(0) as u64;
+
+            match doc.get_str("_id").ok().and_then(ClmmProtocolFeeType::from_str_opt) {
+                Some(ClmmProtocolFeeType::Protocol) => {
+                    totals.protocol_amount_0 += amount_0;
+                    totals.protocol_amount_1 += amount_1;
+                }
+                Some(ClmmProtocolFeeType::Fund) => {
+                    totals.fund_amount_0 += amount_0;
+                    totals.fund_amount_1 += amount_1;
+                }
+                None => {}
+            }
+        }
+
+        Ok(totals)
+    }
+}
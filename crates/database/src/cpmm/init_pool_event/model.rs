@@ -1,3 +1,4 @@
+use crate::events::event_identity::EventIdentity;
 use chrono::{DateTime, Utc};
 use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
@@ -32,6 +33,8 @@ pub struct InitPoolEvent {
     // 交易信息
     pub signature: String,
     pub slot: u64,
+    #[serde(flatten)]
+    pub identity: EventIdentity,
     pub block_time: Option<i64>,
 
     // 时间戳
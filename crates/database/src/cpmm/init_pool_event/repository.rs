@@ -1,5 +1,6 @@
 use crate::cpmm::init_pool_event::model::InitPoolEvent;
 use crate::cpmm::init_pool_event::model::UserPoolStats;
+use crate::events::event_identity::{drop_legacy_unique_index, event_identity_index};
 use anyhow::Result;
 use chrono::Utc;
 use futures::stream::TryStreamExt;
@@ -33,16 +34,8 @@ impl InitPoolEventRepository {
                         .build(),
                 )
                 .build(),
-            // signature唯一索引（防重）
-            IndexModel::builder()
-                .keys(doc! { "signature": 1 })
-                .options(
-                    IndexOptions::builder()
-                        .unique(true)
-                        .name("idx_signature_unique".to_string())
-                        .build(),
-                )
-                .build(),
+            // 事件唯一标识索引（同一交易中的多个事件各记录一次）
+            event_identity_index(),
             // 用户创建的池子查询索引
             IndexModel::builder()
                 .keys(doc! { "pool_creator": 1, "created_at": -1 })
@@ -100,6 +93,9 @@ impl InitPoolEventRepository {
                 .build(),
         ];
 
+        // 按signature唯一的旧索引会拒绝同一交易中的多个事件
+        drop_legacy_unique_index(&self.collection, "idx_signature_unique").await?;
+
         match self.collection.create_indexes(indexes, None).await {
             Ok(_) => {
                 info!("✅ InitPoolEvent索引创建成功");
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use super::super::model::InitPoolEvent;
+    use crate::events::event_identity::EventIdentity;
     use chrono::Utc;
 
     /// 创建测试用的InitPoolEvent
@@ -24,6 +25,7 @@ mod tests {
             token_1_decimals: 6,
             signature: signature.to_string(),
             slot: 100000,
+            identity: EventIdentity::default(),
             block_time: Some(1700000000),
             created_at: Utc::now(),
         }
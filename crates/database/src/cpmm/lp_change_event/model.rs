@@ -1,3 +1,4 @@
+use crate::events::event_identity::EventIdentity;
 use chrono::{DateTime, Utc};
 use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
@@ -46,6 +47,8 @@ pub struct LpChangeEvent {
     // 交易信息
     pub signature: String,
     pub slot: u64,
+    #[serde(flatten)]
+    pub identity: EventIdentity,
     pub block_time: Option<i64>,
 
     // 时间戳
@@ -180,6 +183,7 @@ mod tests {
             token_1_decimals: 9,
             signature: "test_signature".to_string(),
             slot: 12345,
+            identity: EventIdentity::default(),
             block_time: Some(1234567890),
             created_at: Utc::now(),
         }
@@ -1,4 +1,5 @@
 use crate::cpmm::lp_change_event::model::LpChangeEvent;
+use crate::events::event_identity::{drop_legacy_unique_index, event_identity_filter, event_identity_index};
 use anyhow::Result;
 use chrono::Utc;
 use futures::stream::TryStreamExt;
@@ -24,16 +25,8 @@ impl LpChangeEventRepository {
     /// 初始化数据库索引
     pub async fn init_indexes(&self) -> Result<()> {
         let indexes = vec![
-            // signature唯一索引（防重）
-            IndexModel::builder()
-                .keys(doc! { "signature": 1 })
-                .options(
-                    IndexOptions::builder()
-                        .unique(true)
-                        .name("idx_signature_unique".to_string())
-                        .build(),
-                )
-                .build(),
+            // 事件唯一标识索引（同一交易中的多个事件各记录一次）
+            event_identity_index(),
             // 用户查询索引
             IndexModel::builder()
                 .keys(doc! { "user_wallet": 1, "created_at": -1 })
@@ -78,6 +71,9 @@ impl LpChangeEventRepository {
                 .build(),
         ];
 
+        // 按signature唯一的旧索引会拒绝同一交易中的多个事件
+        drop_legacy_unique_index(&self.collection, "idx_signature_unique").await?;
+
         match self.collection.create_indexes(indexes, None).await {
             Ok(_result) => {
                 info!("✅ LpChangeEvent索引初始化完成");
@@ -162,6 +158,18 @@ impl LpChangeEventRepository {
         }
     }
 
+    /// 根据事件唯一标识检查事件是否存在（防重）
+    pub async fn exists_by_identity(
+        &self,
+        signature: &str,
+        instruction_index: u32,
+        event_ordinal: u32,
+    ) -> Result<bool> {
+        let filter = event_identity_filter(signature, instruction_index, event_ordinal);
+        let count = self.collection.count_documents(filter, None).await?;
+        Ok(count > 0)
+    }
+
     /// 带过滤条件的分页查询
     pub async fn find_with_filter(&self, filter: Document, options: FindOptions) -> Result<Vec<LpChangeEvent>> {
         match self.collection.find(filter.clone(), options).await {
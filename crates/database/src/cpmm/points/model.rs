@@ -156,6 +156,27 @@ impl UserPointsSummary {
         self.record_update_time = Utc::now();
     }
 
+    /// 回滚交易积分（交易未最终确认时扣回，最低为0）
+    pub fn rollback_transaction_points(&mut self, points: u64) {
+        self.points_from_transaction = self.points_from_transaction.saturating_sub(points);
+        self.record_update_from = "rollback".to_string();
+        self.record_update_time = Utc::now();
+    }
+
+    /// 回滚NFT被领取积分（扣回300积分，最低为0）
+    pub fn rollback_nft_claimed_points(&mut self) {
+        self.points_from_nft_claimed = self.points_from_nft_claimed.saturating_sub(300);
+        self.record_update_from = "rollback".to_string();
+        self.record_update_time = Utc::now();
+    }
+
+    /// 回滚领取NFT积分（清零）
+    pub fn rollback_claim_nft_points(&mut self) {
+        self.point_from_claim_nft = 0;
+        self.record_update_from = "rollback".to_string();
+        self.record_update_time = Utc::now();
+    }
+
     /// 计算用户总积分
     pub fn total_points(&self) -> u64 {
         self.points_from_transaction
@@ -1,7 +1,8 @@
 use anyhow::Result;
 use futures::stream::TryStreamExt;
 use mongodb::{
-    bson::{doc, DateTime as BsonDateTime},
+    bson::{doc, DateTime as BsonDateTime, Document},
+    options::{FindOneAndUpdateOptions, ReturnDocument},
     Collection, IndexModel,
 };
 use tracing::{error, info, warn};
@@ -232,6 +233,67 @@ impl UserPointsRepository {
         Ok(())
     }
 
+    /// 回滚SwapEvent带来的交易积分（交易未最终确认时调用）
+    ///
+    /// 扣回该笔交易获得的积分，最低扣至0；用户不存在时忽略。
+    /// 使用管道更新在服务端原子地扣减，不会覆盖并发写入的积分
+    pub async fn rollback_swap_points(&self, user_wallet: &str, points: u64) -> Result<()> {
+        let filter = doc! { "userWallet": user_wallet };
+        let pipeline = rollback_points_pipeline("pointsFromTransaction", points as i64);
+
+        match self
+            .collection
+            .find_one_and_update(filter, pipeline, return_updated())
+            .await?
+        {
+            Some(user) => info!(
+                "↩️ 交易积分已回滚: user={}, 扣回={}, 当前交易积分={}",
+                user_wallet, points, user.points_from_transaction
+            ),
+            None => warn!("⚠️ 回滚交易积分时未找到用户: user={}", user_wallet),
+        }
+        Ok(())
+    }
+
+    /// 回滚ClaimNFTEvent带来的积分（交易未最终确认时调用）
+    ///
+    /// 业务逻辑：
+    /// - upper用户：扣回300积分（最低为0）
+    /// - claimer用户：仅在没有其他领取记录时清零领取NFT积分
+    pub async fn rollback_claim_nft_points(&self, claimer: &str, upper: &str, reset_claimer: bool) -> Result<()> {
+        let upper_filter = doc! { "userWallet": upper };
+        let pipeline = rollback_points_pipeline("pointsFromNftClaimed", 300);
+        if let Some(user) = self
+            .collection
+            .find_one_and_update(upper_filter, pipeline, return_updated())
+            .await?
+        {
+            info!(
+                "↩️ Upper积分已回滚: upper={}, 当前NFT被领取积分={}",
+                upper, user.points_from_nft_claimed
+            );
+        }
+
+        if !reset_claimer {
+            return Ok(());
+        }
+
+        let claimer_filter = doc! { "userWallet": claimer };
+        let update = doc! {
+            "$set": {
+                "pointFromClaimNft": 0i64,
+                "recordUpdateFrom": "rollback",
+                "recordUpdateTime": BsonDateTime::now()
+            }
+        };
+        let result = self.collection.update_one(claimer_filter, update, None).await?;
+        if result.matched_count > 0 {
+            info!("↩️ Claimer领取NFT积分已清零: claimer={}", claimer);
+        }
+
+        Ok(())
+    }
+
     /// 根据用户钱包地址获取积分记录
     pub async fn get_by_wallet(&self, user_wallet: &str) -> Result<Option<UserPointsSummary>> {
         let filter = doc! { "userWallet": user_wallet };
@@ -552,6 +614,25 @@ impl UserPointsRepository {
     }
 }
 
+/// 构造原子扣回积分的管道更新：字段减去`points`，最低为0
+fn rollback_points_pipeline(field: &str, points: i64) -> Vec<Document> {
+    let mut set_doc = Document::new();
+    set_doc.insert(
+        field,
+        doc! { "$max": [0i64, { "$subtract": [{ "$ifNull": [format!("${}", field), 0i64] }, points] }] },
+    );
+    set_doc.insert("recordUpdateFrom", "rollback");
+    set_doc.insert("recordUpdateTime", BsonDateTime::now());
+    vec![doc! { "$set": set_doc }]
+}
+
+/// 返回更新后文档的findOneAndUpdate选项
+fn return_updated() -> FindOneAndUpdateOptions {
+    FindOneAndUpdateOptions::builder()
+        .return_document(ReturnDocument::After)
+        .build()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1001,4 +1082,51 @@ mod tests {
 
         println!("✅ 完整业务流程测试通过");
     }
+
+    /// 集成测试：回滚积分为原子扣减，最低为0
+    #[tokio::test]
+    async fn test_rollback_points_floor_at_zero() {
+        let collection = setup_test_db("test_rollback_floor").await;
+        let repo = UserPointsRepository::new(collection.clone());
+
+        let wallet = "rollback_user";
+        repo.upsert_from_swap_event(wallet).await.unwrap();
+        repo.upsert_from_claim_nft_event("some_claimer", wallet).await.unwrap();
+
+        // 扣回10后剩190，再扣回500时最低为0
+        repo.rollback_swap_points(wallet, 10).await.unwrap();
+        let user = collection
+            .find_one(doc! { "userWallet": wallet }, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(user.points_from_transaction, 190);
+        assert_eq!(user.record_update_from, "rollback");
+
+        repo.rollback_swap_points(wallet, 500).await.unwrap();
+        repo.rollback_claim_nft_points("some_claimer", wallet, true)
+            .await
+            .unwrap();
+        repo.rollback_claim_nft_points("some_claimer", wallet, true)
+            .await
+            .unwrap();
+
+        let user = collection
+            .find_one(doc! { "userWallet": wallet }, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(user.points_from_transaction, 0);
+        assert_eq!(user.points_from_nft_claimed, 0);
+
+        let claimer = collection
+            .find_one(doc! { "userWallet": "some_claimer" }, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(claimer.point_from_claim_nft, 0);
+
+        // 不存在的用户直接忽略
+        repo.rollback_swap_points("missing_user", 10).await.unwrap();
+    }
 }
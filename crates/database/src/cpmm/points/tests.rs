@@ -237,4 +237,31 @@ mod points_tests {
 
         println!("✅ 测试通过: edge_cases");
     }
+
+    #[test]
+    fn test_rollback_points() {
+        let mut user = UserPointsSummary::new_from_first_swap("wallet_rollback_1".to_string());
+        user.update_transaction_points();
+        user.update_nft_claimed_points();
+        user.update_claim_nft_points();
+        assert_eq!(user.total_points(), 710);
+
+        // 回滚一笔后续交易（10积分）
+        user.rollback_transaction_points(10);
+        assert_eq!(user.points_from_transaction, 200);
+        assert_eq!(user.record_update_from, "rollback");
+
+        // 回滚NFT被领取和领取NFT积分
+        user.rollback_nft_claimed_points();
+        user.rollback_claim_nft_points();
+        assert_eq!(user.points_from_nft_claimed, 0);
+        assert_eq!(user.point_from_claim_nft, 0);
+
+        // 扣回的积分不会低于0
+        user.rollback_transaction_points(500);
+        user.rollback_nft_claimed_points();
+        assert_eq!(user.total_points(), 0);
+
+        println!("✅ 测试通过: rollback_points");
+    }
 }
@@ -191,6 +191,28 @@ impl UserTransactionPointsDetailRepository {
         }
     }
 
+    /// 删除交易积分记录（交易未最终确认时回滚），返回被删除的记录
+    pub async fn delete_by_wallet_and_signature(
+        &self,
+        user_wallet: &str,
+        signature: &str,
+    ) -> Result<Option<UserTransactionPointsDetail>> {
+        let filter = doc! {
+            "userWallet": user_wallet,
+            "signature": signature
+        };
+        match self.collection.find_one_and_delete(filter, None).await {
+            Ok(detail) => Ok(detail),
+            Err(e) => {
+                error!(
+                    "❌ 删除交易积分记录失败: user={}, signature={} - {}",
+                    user_wallet, signature, e
+                );
+                Err(e.into())
+            }
+        }
+    }
+
     /// 获取用户所有交易记录
     pub async fn get_by_wallet(&self, user_wallet: &str) -> Result<Vec<UserTransactionPointsDetail>> {
         let filter = doc! { "userWallet": user_wallet };
@@ -1,3 +1,4 @@
+use crate::events::event_identity::EventIdentity;
 use chrono::{DateTime, Utc};
 use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
@@ -73,6 +74,8 @@ pub struct SwapEventModel {
     pub signature: String,
     /// 区块高度
     pub slot: u64,
+    #[serde(flatten)]
+    pub identity: EventIdentity,
     /// 区块时间戳
     pub block_time: Option<i64>,
 
@@ -204,6 +207,7 @@ mod tests {
             creator_fee_on_input: true,
             signature: "test_signature".to_string(),
             slot: 12345,
+            identity: EventIdentity::default(),
             block_time: Some(1234567890),
             created_at: Utc::now(),
         }
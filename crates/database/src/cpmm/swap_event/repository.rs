@@ -1,4 +1,5 @@
 use crate::cpmm::swap_event::model::{PoolSwapStats, SwapEventModel, UserSwapStats};
+use crate::events::event_identity::{drop_legacy_unique_index, event_identity_filter, event_identity_index};
 use anyhow::Result;
 use chrono::Utc;
 use futures::stream::TryStreamExt;
@@ -26,16 +27,8 @@ impl SwapEventRepository {
         info!("🔧 初始化SwapEvent集合索引...");
 
         let indexes = vec![
-            // signature唯一索引（确保一个交易只记录一次）
-            IndexModel::builder()
-                .keys(doc! { "signature": 1 })
-                .options(
-                    IndexOptions::builder()
-                        .unique(true)
-                        .name("idx_signature_unique".to_string())
-                        .build(),
-                )
-                .build(),
+            // 事件唯一标识索引（同一交易中的多个事件各记录一次）
+            event_identity_index(),
             // 用户交换历史查询索引
             IndexModel::builder()
                 .keys(doc! {
@@ -101,6 +94,11 @@ impl SwapEventRepository {
                 .keys(doc! { "created_at": -1 })
                 .options(IndexOptions::builder().name("idx_created_at".to_string()).build())
                 .build(),
+            // 出块时间范围查询索引（滚动统计按出块时间筛选）
+            IndexModel::builder()
+                .keys(doc! { "block_time": -1 })
+                .options(IndexOptions::builder().name("idx_block_time".to_string()).build())
+                .build(),
             // 交换方向查询索引
             IndexModel::builder()
                 .keys(doc! { "base_input": 1, "created_at": -1 })
@@ -112,6 +110,9 @@ impl SwapEventRepository {
                 .build(),
         ];
 
+        // 按signature唯一的旧索引会拒绝同一交易中的多个事件
+        drop_legacy_unique_index(&self.collection, "idx_signature_unique").await?;
+
         match self.collection.create_indexes(indexes, None).await {
             Ok(_) => {
                 info!("✅ SwapEvent索引创建成功");
@@ -196,6 +197,18 @@ impl SwapEventRepository {
         }
     }
 
+    /// 根据事件唯一标识检查事件是否存在（防重）
+    pub async fn exists_by_identity(
+        &self,
+        signature: &str,
+        instruction_index: u32,
+        event_ordinal: u32,
+    ) -> Result<bool> {
+        let filter = event_identity_filter(signature, instruction_index, event_ordinal);
+        let count = self.collection.count_documents(filter, None).await?;
+        Ok(count > 0)
+    }
+
     /// 根据用户查找交换事件
     pub async fn find_by_payer(&self, payer: &str, limit: Option<i64>) -> Result<Vec<SwapEventModel>> {
         let filter = doc! { "payer": payer };
@@ -515,6 +528,16 @@ impl SwapEventRepository {
         }
     }
 
+    /// 删除指定交易内的全部交换事件，返回删除数量
+    pub async fn delete_by_signature(&self, signature: &str) -> Result<u64> {
+        let result = self
+            .collection
+            .delete_many(doc! { "signature": signature }, None)
+            .await?;
+        info!("✅ 删除交易内的交换事件: signature={}, count={}", signature, result.deleted_count);
+        Ok(result.deleted_count)
+    }
+
     /// 根据ID删除交换事件
     pub async fn delete_by_id(&self, id: &ObjectId) -> Result<bool> {
         let filter = doc! { "_id": id };
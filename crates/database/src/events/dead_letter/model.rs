@@ -0,0 +1,193 @@
+use crate::events::event_scanner::model::{bson_datetime, bson_datetime_option};
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// 死信事件状态
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeadLetterStatus {
+    /// 等待处理
+    Pending,
+    /// 已请求重放，等待监听器重新提交
+    ReplayRequested,
+    /// 已重新提交到批量写入器
+    Replayed,
+    /// 已人工丢弃
+    Discarded,
+}
+
+impl DeadLetterStatus {
+    /// 获取存储用的状态字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeadLetterStatus::Pending => "pending",
+            DeadLetterStatus::ReplayRequested => "replay_requested",
+            DeadLetterStatus::Replayed => "replayed",
+            DeadLetterStatus::Discarded => "discarded",
+        }
+    }
+}
+
+impl std::str::FromStr for DeadLetterStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(DeadLetterStatus::Pending),
+            "replay_requested" => Ok(DeadLetterStatus::ReplayRequested),
+            "replayed" => Ok(DeadLetterStatus::Replayed),
+            "discarded" => Ok(DeadLetterStatus::Discarded),
+            _ => Err(format!("未知的死信状态: {}", s)),
+        }
+    }
+}
+
+/// 死信事件
+///
+/// 批量写入器放弃重试（不可重试的错误或达到最大重试次数）的事件写入死信集合，
+/// 保留原始事件负载，问题修复后可通过管理接口请求重放
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeadLetterEvent {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    /// 写入失败的批次ID
+    pub batch_id: String,
+
+    /// 事件类型
+    pub event_type: String,
+
+    /// 交易签名
+    pub signature: String,
+
+    /// 区块高度
+    pub slot: u64,
+
+    /// 最后一次写入失败的错误信息
+    pub error: String,
+
+    /// 写入尝试次数
+    pub attempts: u32,
+
+    /// 原始事件负载（ParsedEvent的JSON序列化）
+    pub payload: String,
+
+    /// 处理状态
+    pub status: DeadLetterStatus,
+
+    /// 重放次数
+    #[serde(default)]
+    pub replay_count: u32,
+
+    /// 最后一次重放的错误信息
+    #[serde(default)]
+    pub replay_error: Option<String>,
+
+    /// 写入死信集合的时间
+    #[serde(with = "bson_datetime")]
+    pub created_at: DateTime<Utc>,
+
+    /// 状态更新时间
+    #[serde(with = "bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+
+    /// 最后一次重放时间
+    #[serde(with = "bson_datetime_option", default)]
+    pub replayed_at: Option<DateTime<Utc>>,
+}
+
+impl DeadLetterEvent {
+    /// 创建新的死信事件
+    pub fn new(
+        batch_id: String,
+        event_type: String,
+        signature: String,
+        slot: u64,
+        error: String,
+        attempts: u32,
+        payload: String,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            batch_id,
+            event_type,
+            signature,
+            slot,
+            error,
+            attempts,
+            payload,
+            status: DeadLetterStatus::Pending,
+            replay_count: 0,
+            replay_error: None,
+            created_at: now,
+            updated_at: now,
+            replayed_at: None,
+        }
+    }
+}
+
+/// 死信事件查询条件
+#[derive(Debug, Clone, Default)]
+pub struct DeadLetterQuery {
+    pub status: Option<DeadLetterStatus>,
+    pub event_type: Option<String>,
+    pub signature: Option<String>,
+    pub page: u64,
+    pub page_size: u64,
+}
+
+/// 死信事件状态统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct DeadLetterStats {
+    /// 等待处理的事件数
+    pub pending: u64,
+    /// 等待重放的事件数
+    pub replay_requested: u64,
+    /// 已重放的事件数
+    pub replayed: u64,
+    /// 已丢弃的事件数
+    pub discarded: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            DeadLetterStatus::Pending,
+            DeadLetterStatus::ReplayRequested,
+            DeadLetterStatus::Replayed,
+            DeadLetterStatus::Discarded,
+        ] {
+            assert_eq!(DeadLetterStatus::from_str(status.as_str()).unwrap(), status);
+            assert_eq!(
+                serde_json::to_string(&status).unwrap(),
+                format!("\"{}\"", status.as_str())
+            );
+        }
+        assert!(DeadLetterStatus::from_str("unknown").is_err());
+    }
+
+    #[test]
+    fn test_new_dead_letter_event() {
+        let event = DeadLetterEvent::new(
+            "batch-1".to_string(),
+            "swap".to_string(),
+            "sig".to_string(),
+            100,
+            "持久化错误".to_string(),
+            4,
+            "{}".to_string(),
+        );
+
+        assert_eq!(event.status, DeadLetterStatus::Pending);
+        assert_eq!(event.replay_count, 0);
+        assert!(event.replayed_at.is_none());
+    }
+}
@@ -0,0 +1,253 @@
+use crate::events::dead_letter::model::{DeadLetterEvent, DeadLetterQuery, DeadLetterStats, DeadLetterStatus};
+use anyhow::Result;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, DateTime as BsonDateTime, Document},
+    options::{FindOptions, IndexOptions},
+    Collection, IndexModel,
+};
+use tracing::{error, info, warn};
+
+/// DeadLetterEvent仓储接口
+#[derive(Clone, Debug)]
+pub struct DeadLetterRepository {
+    collection: Collection<DeadLetterEvent>,
+}
+
+impl DeadLetterRepository {
+    /// 创建新的DeadLetterEvent仓储
+    pub fn new(collection: Collection<DeadLetterEvent>) -> Self {
+        Self { collection }
+    }
+
+    /// 初始化数据库索引
+    pub async fn init_indexes(&self) -> Result<()> {
+        info!("🔧 初始化DeadLetterEvent集合索引...");
+
+        let indexes = vec![
+            // 按状态和时间查询
+            IndexModel::builder()
+                .keys(doc! { "status": 1, "created_at": -1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("idx_status_created_at".to_string())
+                        .build(),
+                )
+                .build(),
+            // 按事件类型查询
+            IndexModel::builder()
+                .keys(doc! { "event_type": 1, "created_at": -1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("idx_event_type_created_at".to_string())
+                        .build(),
+                )
+                .build(),
+            // 按签名查询
+            IndexModel::builder()
+                .keys(doc! { "signature": 1 })
+                .options(IndexOptions::builder().name("idx_signature".to_string()).build())
+                .build(),
+        ];
+
+        match self.collection.create_indexes(indexes, None).await {
+            Ok(_) => {
+                info!("✅ DeadLetterEvent索引创建成功");
+                Ok(())
+            }
+            Err(e) => {
+                error!("❌ DeadLetterEvent索引创建失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 批量写入死信事件
+    pub async fn insert_many(&self, events: &[DeadLetterEvent]) -> Result<u64> {
+        if events.is_empty() {
+            return Ok(0);
+        }
+
+        let result = self.collection.insert_many(events, None).await?;
+        warn!("☠️ {}个事件已写入死信集合", result.inserted_ids.len());
+        Ok(result.inserted_ids.len() as u64)
+    }
+
+    /// 分页查询死信事件（按写入时间倒序），返回(事件列表, 总数)
+    pub async fn find_with_query(&self, query: &DeadLetterQuery) -> Result<(Vec<DeadLetterEvent>, u64)> {
+        let filter = Self::build_filter(query);
+        let page = query.page.max(1);
+        let page_size = query.page_size.clamp(1, 100);
+
+        let total = self.collection.count_documents(filter.clone(), None).await?;
+        let options = FindOptions::builder()
+            .sort(doc! { "created_at": -1 })
+            .skip((page - 1) * page_size)
+            .limit(page_size as i64)
+            .build();
+
+        let cursor = self.collection.find(filter, options).await?;
+        let events: Vec<DeadLetterEvent> = cursor.try_collect().await?;
+        Ok((events, total))
+    }
+
+    /// 根据ID查找死信事件
+    pub async fn find_by_id(&self, id: &ObjectId) -> Result<Option<DeadLetterEvent>> {
+        let event = self.collection.find_one(doc! { "_id": id }, None).await?;
+        Ok(event)
+    }
+
+    /// 请求重放（仅对等待处理的事件生效），返回更新数
+    pub async fn request_replay(&self, ids: &[ObjectId]) -> Result<u64> {
+        self.update_status(ids, &[DeadLetterStatus::Pending], DeadLetterStatus::ReplayRequested)
+            .await
+    }
+
+    /// 丢弃事件（已重放的事件不可丢弃），返回更新数
+    pub async fn discard(&self, ids: &[ObjectId]) -> Result<u64> {
+        self.update_status(
+            ids,
+            &[DeadLetterStatus::Pending, DeadLetterStatus::ReplayRequested],
+            DeadLetterStatus::Discarded,
+        )
+        .await
+    }
+
+    /// 查询等待重放的事件（按写入时间升序，保持原始顺序）
+    pub async fn find_replay_requested(&self, limit: i64) -> Result<Vec<DeadLetterEvent>> {
+        let filter = doc! { "status": DeadLetterStatus::ReplayRequested.as_str() };
+        let options = FindOptions::builder()
+            .sort(doc! { "created_at": 1 })
+            .limit(limit)
+            .build();
+
+        let cursor = self.collection.find(filter, options).await?;
+        let events: Vec<DeadLetterEvent> = cursor.try_collect().await?;
+        Ok(events)
+    }
+
+    /// 标记为已重放
+    pub async fn mark_replayed(&self, ids: &[ObjectId]) -> Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let now = BsonDateTime::from_millis(Utc::now().timestamp_millis());
+        let filter = doc! {
+            "_id": { "$in": ids },
+            "status": DeadLetterStatus::ReplayRequested.as_str(),
+        };
+        let update = doc! {
+            "$set": {
+                "status": DeadLetterStatus::Replayed.as_str(),
+                "replay_error": null,
+                "replayed_at": now,
+                "updated_at": now,
+            },
+            "$inc": { "replay_count": 1 }
+        };
+
+        let result = self.collection.update_many(filter, update, None).await?;
+        Ok(result.modified_count)
+    }
+
+    /// 记录重放失败（恢复为等待处理，保留错误信息）
+    pub async fn mark_replay_failed(&self, id: &ObjectId, replay_error: &str) -> Result<bool> {
+        let now = BsonDateTime::from_millis(Utc::now().timestamp_millis());
+        let filter = doc! {
+            "_id": id,
+            "status": DeadLetterStatus::ReplayRequested.as_str(),
+        };
+        let update = doc! {
+            "$set": {
+                "status": DeadLetterStatus::Pending.as_str(),
+                "replay_error": replay_error,
+                "replayed_at": now,
+                "updated_at": now,
+            },
+            "$inc": { "replay_count": 1 }
+        };
+
+        let result = self.collection.update_one(filter, update, None).await?;
+        Ok(result.modified_count > 0)
+    }
+
+    /// 统计各状态的事件数
+    pub async fn get_stats(&self) -> Result<DeadLetterStats> {
+        let pipeline = vec![doc! { "$group": { "_id": "$status", "count": { "$sum": 1 } } }];
+        let mut cursor = self.collection.aggregate(pipeline, None).await?;
+
+        let mut stats = DeadLetterStats::default();
+        while let Some(doc) = cursor.try_next().await? {
+            let count = doc.get_i32("count").unwrap_or(0) as u64;
+            match doc.get_str("_id").unwrap_or_default() {
+                "pending" => stats.pending = count,
+                "replay_requested" => stats.replay_requested = count,
+                "replayed" => stats.replayed = count,
+                "discarded" => stats.discarded = count,
+                _ => {}
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// 批量更新状态
+    async fn update_status(&self, ids: &[ObjectId], from: &[DeadLetterStatus], to: DeadLetterStatus) -> Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let from: Vec<&str> = from.iter().map(|status| status.as_str()).collect();
+        let filter = doc! {
+            "_id": { "$in": ids },
+            "status": { "$in": from },
+        };
+        let update = doc! {
+            "$set": {
+                "status": to.as_str(),
+                "updated_at": BsonDateTime::from_millis(Utc::now().timestamp_millis()),
+            }
+        };
+
+        let result = self.collection.update_many(filter, update, None).await?;
+        info!("📝 {}个死信事件状态已更新为{}", result.modified_count, to.as_str());
+        Ok(result.modified_count)
+    }
+
+    /// 构建查询条件
+    fn build_filter(query: &DeadLetterQuery) -> Document {
+        let mut filter = doc! {};
+        if let Some(status) = &query.status {
+            filter.insert("status", status.as_str());
+        }
+        if let Some(event_type) = &query.event_type {
+            filter.insert("event_type", event_type);
+        }
+        if let Some(signature) = &query.signature {
+            filter.insert("signature", signature);
+        }
+        filter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_filter() {
+        let filter = DeadLetterRepository::build_filter(&DeadLetterQuery::default());
+        assert!(filter.is_empty());
+
+        let filter = DeadLetterRepository::build_filter(&DeadLetterQuery {
+            status: Some(DeadLetterStatus::Pending),
+            event_type: Some("swap".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(filter.get_str("status").unwrap(), "pending");
+        assert_eq!(filter.get_str("event_type").unwrap(), "swap");
+        assert!(filter.get("signature").is_none());
+    }
+}
@@ -0,0 +1,160 @@
+use mongodb::{
+    bson::{doc, Document},
+    error::{ErrorKind, Result},
+    options::IndexOptions,
+    Collection, IndexModel,
+};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// 事件唯一标识索引名称
+pub const EVENT_IDENTITY_INDEX_NAME: &str = "idx_event_identity_unique";
+
+/// MongoDB错误码：集合不存在
+const NAMESPACE_NOT_FOUND_CODE: i32 = 26;
+/// MongoDB错误码：索引不存在
+const INDEX_NOT_FOUND_CODE: i32 = 27;
+
+/// 事件在交易中的位置：顶层指令索引 + 指令内事件序号
+///
+/// 以instruction_index、event_ordinal两个扁平字段存储在各事件模型中，与交易签名共同组成事件唯一标识。
+/// 引入标识之前写入的旧记录和API写入的记录没有这两个字段，反序列化后为空
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventIdentity {
+    /// 事件所在的顶层指令索引
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instruction_index: Option<u32>,
+
+    /// 事件在该指令中的序号
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_ordinal: Option<u32>,
+}
+
+impl EventIdentity {
+    /// 创建解析得到的事件位置
+    pub fn new(instruction_index: u32, event_ordinal: u32) -> Self {
+        Self {
+            instruction_index: Some(instruction_index),
+            event_ordinal: Some(event_ordinal),
+        }
+    }
+
+    /// 位置完整时返回（顶层指令索引，指令内事件序号）
+    pub fn position(&self) -> Option<(u32, u32)> {
+        self.instruction_index.zip(self.event_ordinal)
+    }
+}
+
+/// 事件唯一标识索引：交易签名 + 顶层指令索引 + 指令内事件序号
+///
+/// 只约束带标识的记录，引入标识之前写入的旧记录和API写入的记录不参与唯一性检查
+pub fn event_identity_index() -> IndexModel {
+    IndexModel::builder()
+        .keys(doc! { "signature": 1, "instruction_index": 1, "event_ordinal": 1 })
+        .options(
+            IndexOptions::builder()
+                .unique(true)
+                .name(EVENT_IDENTITY_INDEX_NAME.to_string())
+                .partial_filter_expression(doc! { "instruction_index": { "$exists": true } })
+                .build(),
+        )
+        .build()
+}
+
+/// 查询同一事件是否已存储的过滤条件
+///
+/// 匹配标识完全相同的记录，或同一交易中没有标识的旧记录（旧版本按签名去重，视为已处理）
+pub fn event_identity_filter(signature: &str, instruction_index: u32, event_ordinal: u32) -> Document {
+    doc! {
+        "signature": signature,
+        "$or": [
+            { "instruction_index": instruction_index, "event_ordinal": event_ordinal },
+            { "instruction_index": { "$exists": false } },
+        ],
+    }
+}
+
+/// 删除已被事件唯一标识索引取代的旧唯一索引（索引或集合不存在时忽略）
+pub async fn drop_legacy_unique_index<T>(collection: &Collection<T>, index_name: &str) -> Result<()>
+where
+    T: Send + Sync,
+{
+    match collection.drop_index(index_name, None).await {
+        Ok(()) => {
+            info!("🧹 已删除旧唯一索引: {}.{}", collection.name(), index_name);
+            Ok(())
+        }
+        Err(e) => match e.kind.as_ref() {
+            ErrorKind::Command(command_error)
+                if matches!(command_error.code, NAMESPACE_NOT_FOUND_CODE | INDEX_NOT_FOUND_CODE) =>
+            {
+                Ok(())
+            }
+            _ => Err(e),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_identity_index() {
+        let index = event_identity_index();
+        assert_eq!(
+            index.keys,
+            doc! { "signature": 1, "instruction_index": 1, "event_ordinal": 1 }
+        );
+
+        let options = index.options.unwrap();
+        assert_eq!(options.unique, Some(true));
+        assert_eq!(options.name.as_deref(), Some(EVENT_IDENTITY_INDEX_NAME));
+        assert_eq!(
+            options.partial_filter_expression,
+            Some(doc! { "instruction_index": { "$exists": true } })
+        );
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct IdentifiedEvent {
+        signature: String,
+        #[serde(flatten)]
+        identity: EventIdentity,
+    }
+
+    #[test]
+    fn test_event_identity_flattened_fields() {
+        let event = IdentifiedEvent {
+            signature: "sig".to_string(),
+            identity: EventIdentity::new(2, 1),
+        };
+        let document = mongodb::bson::to_document(&event).unwrap();
+        assert_eq!(document.get_str("signature").unwrap(), "sig");
+        assert!(document.contains_key("instruction_index"));
+        assert!(document.contains_key("event_ordinal"));
+
+        let decoded: IdentifiedEvent = mongodb::bson::from_document(document).unwrap();
+        assert_eq!(decoded.identity.position(), Some((2, 1)));
+
+        // 没有标识的旧记录
+        let legacy: IdentifiedEvent = mongodb::bson::from_document(doc! { "signature": "sig" }).unwrap();
+        assert_eq!(legacy.identity, EventIdentity::default());
+        assert_eq!(legacy.identity.position(), None);
+
+        let document = mongodb::bson::to_document(&legacy).unwrap();
+        assert!(!document.contains_key("instruction_index"));
+    }
+
+    #[test]
+    fn test_event_identity_filter() {
+        let filter = event_identity_filter("sig", 2, 1);
+        assert_eq!(filter.get_str("signature").unwrap(), "sig");
+
+        let branches = filter.get_array("$or").unwrap();
+        assert_eq!(branches.len(), 2);
+        let identity = branches[0].as_document().unwrap();
+        assert_eq!(identity.get_i64("instruction_index").unwrap(), 2);
+        assert_eq!(identity.get_i64("event_ordinal").unwrap(), 1);
+    }
+}
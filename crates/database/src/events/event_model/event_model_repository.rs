@@ -3,8 +3,12 @@ use super::{
         ClmmPoolEventRepository, DepositEventRepository, LaunchEventRepository, NftClaimEventRepository,
         RewardDistributionEventRepository,
     },
-    ClmmPoolEvent, DepositEvent, LaunchEvent, NftClaimEvent, RewardDistributionEvent, TokenCreationEvent,
+    ClmmPoolEvent, DepositEvent, LaunchEvent, NftClaimEvent, NftMintEvent, ReferralEstablishedEvent,
+    RewardDistributionEvent, TokenCreationEvent,
 };
+use crate::clmm::clmm_swap_event::model::ClmmSwapEventModel;
+use crate::clmm::position_event::model::{ClmmPositionEvent, ClmmPositionEventType};
+use crate::clmm::protocol_fee_event::model::ClmmProtocolFeeEvent;
 use crate::cpmm::{
     init_pool_event::model::InitPoolEvent,
     lp_change_event::model::LpChangeEvent,
@@ -168,6 +172,42 @@ impl EventModelRepository {
         }
     }
 
+    /// 获取最老的NftMintEvent签名 (用于回填服务)
+    pub async fn get_oldest_nft_mint_event(&self) -> AppResult<Option<NftMintEvent>> {
+        let options = FindOptions::builder().sort(doc! { "slot": 1, "signature": 1 }).limit(1).build();
+
+        let mut cursor = self
+            .database
+            .collection::<NftMintEvent>("NftMintEvent")
+            .find(doc! {}, options)
+            .await?;
+
+        if cursor.advance().await? {
+            let event = cursor.deserialize_current()?;
+            Ok(Some(event))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// 获取最老的ReferralEstablishedEvent签名 (用于回填服务)
+    pub async fn get_oldest_referral_established_event(&self) -> AppResult<Option<ReferralEstablishedEvent>> {
+        let options = FindOptions::builder().sort(doc! { "slot": 1, "signature": 1 }).limit(1).build();
+
+        let mut cursor = self
+            .database
+            .collection::<ReferralEstablishedEvent>("ReferralEstablishedEvent")
+            .find(doc! {}, options)
+            .await?;
+
+        if cursor.advance().await? {
+            let event = cursor.deserialize_current()?;
+            Ok(Some(event))
+        } else {
+            Ok(None)
+        }
+    }
+
     /// 获取最老的ClmmPoolEvent签名 (用于回填服务)
     pub async fn get_oldest_clmm_pool_event(&self) -> AppResult<Option<ClmmPoolEvent>> {
         let options = FindOptions::builder().sort(doc! { "slot": 1, "signature": 1 }).limit(1).build();
@@ -260,6 +300,64 @@ impl EventModelRepository {
         }
     }
 
+    /// 获取最老的ClmmSwapEvent签名 (用于回填服务)
+    pub async fn get_oldest_clmm_swap_event(&self) -> AppResult<Option<ClmmSwapEventModel>> {
+        let options = FindOptions::builder().sort(doc! { "slot": 1, "signature": 1 }).limit(1).build();
+
+        let mut cursor = self
+            .database
+            .collection::<ClmmSwapEventModel>("ClmmSwapEvent")
+            .find(doc! {}, options)
+            .await?;
+
+        if cursor.advance().await? {
+            let event = cursor.deserialize_current()?;
+            Ok(Some(event))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// 获取指定类型最老的ClmmPositionEvent签名 (用于回填服务)
+    pub async fn get_oldest_clmm_position_event(
+        &self,
+        event_type: ClmmPositionEventType,
+    ) -> AppResult<Option<ClmmPositionEvent>> {
+        let filter = doc! { "event_type": event_type.as_str() };
+        let options = FindOptions::builder().sort(doc! { "slot": 1, "signature": 1 }).limit(1).build();
+
+        let mut cursor = self
+            .database
+            .collection::<ClmmPositionEvent>("ClmmPositionEvent")
+            .find(filter, options)
+            .await?;
+
+        if cursor.advance().await? {
+            let event = cursor.deserialize_current()?;
+            Ok(Some(event))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// 获取最老的ClmmProtocolFeeEvent签名 (用于回填服务)
+    pub async fn get_oldest_clmm_protocol_fee_event(&self) -> AppResult<Option<ClmmProtocolFeeEvent>> {
+        let options = FindOptions::builder().sort(doc! { "slot": 1, "signature": 1 }).limit(1).build();
+
+        let mut cursor = self
+            .database
+            .collection::<ClmmProtocolFeeEvent>("ClmmProtocolFeeEvent")
+            .find(doc! {}, options)
+            .await?;
+
+        if cursor.advance().await? {
+            let event = cursor.deserialize_current()?;
+            Ok(Some(event))
+        } else {
+            Ok(None)
+        }
+    }
+
     /// 查询数据库中已存在的签名集合 (用于回填服务去重)
     pub async fn get_existing_signatures(&self, signatures: &[String]) -> AppResult<Vec<String>> {
         if signatures.is_empty() {
@@ -357,6 +455,19 @@ impl EventModelRepository {
                 }
             }
 
+            // 检查ClmmSwapEvent集合
+            if let Ok(mut cursor) = self
+                .database
+                .collection::<ClmmSwapEventModel>("ClmmSwapEvent")
+                .find(filter.clone(), None)
+                .await
+            {
+                while cursor.advance().await? {
+                    let event = cursor.deserialize_current()?;
+                    all_found.push(event.signature);
+                }
+            }
+
             existing_signatures.extend(all_found);
         }
 
@@ -377,6 +488,7 @@ impl EventModelRepository {
             "RewardDistributionEvent",
             "DepositEvent",
             "SwapEvent",
+            "ClmmSwapEvent",
         ];
 
         for collection_name in &collections {
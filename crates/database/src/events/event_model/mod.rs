@@ -1,6 +1,7 @@
 pub mod repository;
 pub mod event_model_repository;
 
+use crate::events::event_identity::EventIdentity;
 use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
 
@@ -70,6 +71,10 @@ pub struct ClmmPoolEvent {
     #[serde(serialize_with = "crate::serde_helpers::serialize_u64_as_number")]
     pub slot: u64,
 
+    /// 事件在交易中的位置
+    #[serde(flatten)]
+    pub identity: EventIdentity,
+
     /// 处理时间
     #[serde(serialize_with = "crate::serde_helpers::serialize_i64_as_number")]
     pub processed_at: i64,
@@ -153,6 +158,10 @@ pub struct NftClaimEvent {
     #[serde(serialize_with = "crate::serde_helpers::serialize_u64_as_number")]
     pub slot: u64,
 
+    /// 事件在交易中的位置
+    #[serde(flatten)]
+    pub identity: EventIdentity,
+
     /// 处理时间
     #[serde(serialize_with = "crate::serde_helpers::serialize_i64_as_number")]
     pub processed_at: i64,
@@ -256,6 +265,10 @@ pub struct RewardDistributionEvent {
     #[serde(serialize_with = "crate::serde_helpers::serialize_u64_as_number")]
     pub slot: u64,
 
+    /// 事件在交易中的位置
+    #[serde(flatten)]
+    pub identity: EventIdentity,
+
     /// 处理时间
     #[serde(serialize_with = "crate::serde_helpers::serialize_i64_as_number")]
     pub processed_at: i64,
@@ -337,6 +350,18 @@ pub struct LaunchEvent {
     pub migration_error: Option<String>,
     /// 迁移重试次数
     pub migration_retry_count: u32,
+    /// 下次自动迁移尝试时间（重试退避，为空表示可立即执行）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub migration_next_attempt_at: Option<i64>,
+    /// 迁移任务占用截止时间（防止多个服务实例重复执行同一迁移）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub migration_locked_until: Option<i64>,
+    /// 最近一次发送的迁移交易签名（确认前写入，领取时据此核对是否已上链）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub migration_tx_signature: Option<String>,
+    /// 最近一次迁移交易的目标池子地址（与迁移交易签名一同写入）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub migration_pending_pool_address: Option<String>,
     
     // 统计分析字段
     /// 流动性总价值（USD估算）
@@ -354,6 +379,8 @@ pub struct LaunchEvent {
     /// 区块高度
     #[serde(serialize_with = "crate::serde_helpers::serialize_u64_as_number")]
     pub slot: u64,
+    #[serde(flatten)]
+    pub identity: EventIdentity,
     /// 处理时间
     #[serde(serialize_with = "crate::serde_helpers::serialize_i64_as_number")]
     pub processed_at: i64,
@@ -407,6 +434,8 @@ pub struct TokenCreationEvent {
     /// 区块高度
     #[serde(serialize_with = "crate::serde_helpers::serialize_u64_as_number")]
     pub slot: u64,
+    #[serde(flatten)]
+    pub identity: EventIdentity,
     /// 创建时间（Unix 时间戳）
     #[serde(serialize_with = "crate::serde_helpers::serialize_i64_as_number")]
     pub created_at: i64,
@@ -470,6 +499,8 @@ pub struct DepositEvent {
     /// 区块高度
     #[serde(serialize_with = "crate::serde_helpers::serialize_u64_as_number")]
     pub slot: u64,
+    #[serde(flatten)]
+    pub identity: EventIdentity,
     /// 存款时间戳
     #[serde(serialize_with = "crate::serde_helpers::serialize_i64_as_number")]
     pub deposited_at: i64,
@@ -480,3 +511,91 @@ pub struct DepositEvent {
     #[serde(serialize_with = "crate::serde_helpers::serialize_i64_as_number")]
     pub updated_at: i64,
 }
+
+/// NFT铸造事件模型（推荐合约MintNFTEvent）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NftMintEvent {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    /// 铸造者地址
+    pub minter: String,
+
+    /// NFT的mint地址
+    pub nft_mint: String,
+
+    /// 本次铸造数量
+    #[serde(serialize_with = "crate::serde_helpers::serialize_u64_as_number")]
+    pub amount: u64,
+
+    /// 铸造者累计铸造总数
+    #[serde(serialize_with = "crate::serde_helpers::serialize_u64_as_number")]
+    pub total_mint: u64,
+
+    /// 剩余可被领取数量
+    #[serde(serialize_with = "crate::serde_helpers::serialize_u64_as_number")]
+    pub remain_mint: u64,
+
+    /// NFT存放的池子账户
+    pub nft_pool_account: String,
+
+    /// 铸造时间戳
+    #[serde(serialize_with = "crate::serde_helpers::serialize_i64_as_number")]
+    pub minted_at: i64,
+
+    /// 交易签名
+    pub signature: String,
+
+    /// 区块高度
+    #[serde(serialize_with = "crate::serde_helpers::serialize_u64_as_number")]
+    pub slot: u64,
+
+    /// 事件在交易中的位置
+    #[serde(flatten)]
+    pub identity: EventIdentity,
+
+    /// 处理时间
+    #[serde(serialize_with = "crate::serde_helpers::serialize_i64_as_number")]
+    pub processed_at: i64,
+
+    #[serde(serialize_with = "crate::serde_helpers::serialize_i64_as_number")]
+    pub updated_at: i64,
+}
+
+/// 推荐关系建立事件模型（推荐合约ReferralEstablishedEvent）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferralEstablishedEvent {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    /// 下级用户地址
+    pub user: String,
+
+    /// 上级用户地址
+    pub upper: String,
+
+    /// 相关NFT的mint地址
+    pub nft_mint: String,
+
+    /// 关系建立时间戳
+    #[serde(serialize_with = "crate::serde_helpers::serialize_i64_as_number")]
+    pub established_at: i64,
+
+    /// 交易签名
+    pub signature: String,
+
+    /// 区块高度
+    #[serde(serialize_with = "crate::serde_helpers::serialize_u64_as_number")]
+    pub slot: u64,
+
+    /// 事件在交易中的位置
+    #[serde(flatten)]
+    pub identity: EventIdentity,
+
+    /// 处理时间
+    #[serde(serialize_with = "crate::serde_helpers::serialize_i64_as_number")]
+    pub processed_at: i64,
+
+    #[serde(serialize_with = "crate::serde_helpers::serialize_i64_as_number")]
+    pub updated_at: i64,
+}
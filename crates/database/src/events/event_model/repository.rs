@@ -1,11 +1,12 @@
+use crate::events::event_identity::{drop_legacy_unique_index, event_identity_filter, event_identity_index};
 use crate::events::event_model::{
-    ClmmPoolEvent, DepositEvent, LaunchEvent, MigrationStatus, NftClaimEvent, RewardDistributionEvent,
-    TokenCreationEvent,
+    ClmmPoolEvent, DepositEvent, LaunchEvent, MigrationStatus, NftClaimEvent, NftMintEvent, ReferralEstablishedEvent,
+    RewardDistributionEvent, TokenCreationEvent,
 };
 use chrono::Utc;
 use futures_util::TryStreamExt;
-use mongodb::bson::doc;
-use mongodb::options::IndexOptions;
+use mongodb::bson::{doc, oid::ObjectId};
+use mongodb::options::{FindOneAndUpdateOptions, FindOptions, IndexOptions, ReturnDocument};
 use mongodb::{Collection, IndexModel};
 use tracing::info;
 use utils::AppResult;
@@ -46,7 +47,9 @@ impl ClmmPoolEventRepository {
             })
             .build();
 
-        let indexes = vec![pool_signature_index, created_at_index, creator_index, token_pair_index];
+        let mut indexes = vec![pool_signature_index, created_at_index, creator_index, token_pair_index];
+        // 事件唯一标识索引（同一交易中的多个事件各记录一次）
+        indexes.push(event_identity_index());
 
         self.collection.create_indexes(indexes, None).await?;
         info!("✅ ClmmPoolEvent数据库索引初始化完成");
@@ -211,7 +214,7 @@ impl NftClaimEventRepository {
         // 创建奖励倍率索引
         let reward_multiplier_index = IndexModel::builder().keys(doc! { "reward_multiplier": 1 }).build();
 
-        let indexes = vec![
+        let mut indexes = vec![
             nft_signature_index,
             claimer_index,
             claimed_at_index,
@@ -226,6 +229,8 @@ impl NftClaimEventRepository {
             token_mint_index,
             reward_multiplier_index,
         ];
+        // 事件唯一标识索引（同一交易中的多个事件各记录一次）
+        indexes.push(event_identity_index());
 
         self.collection.create_indexes(indexes, None).await?;
 
@@ -262,6 +267,32 @@ impl NftClaimEventRepository {
         Ok(events)
     }
 
+    /// 根据交易签名查找领取事件
+    pub async fn find_by_signature(&self, signature: &str) -> AppResult<Vec<NftClaimEvent>> {
+        let filter = doc! { "signature": signature };
+        let cursor = self.collection.find(filter, None).await?;
+
+        let events: Vec<NftClaimEvent> = cursor.try_collect().await?;
+
+        Ok(events)
+    }
+
+    /// 统计领取者在其他交易中的领取次数
+    pub async fn count_by_claimer_excluding_signature(&self, claimer: &str, signature: &str) -> AppResult<u64> {
+        let filter = doc! { "claimer": claimer, "signature": { "$ne": signature } };
+        let count = self.collection.count_documents(filter, None).await?;
+        Ok(count)
+    }
+
+    /// 根据交易签名删除领取事件（交易未最终确认时回滚）
+    pub async fn delete_by_signature(&self, signature: &str) -> AppResult<u64> {
+        let result = self
+            .collection
+            .delete_many(doc! { "signature": signature }, None)
+            .await?;
+        Ok(result.deleted_count)
+    }
+
     /// 获取NFT领取统计
     pub async fn get_nft_claim_stats(&self) -> AppResult<NftClaimStats> {
         // 统计总领取次数
@@ -582,7 +613,7 @@ impl RewardDistributionEventRepository {
             })
             .build();
 
-        let indexes = vec![
+        let mut indexes = vec![
             distribution_signature_index,
             recipient_index,
             distributed_at_index,
@@ -604,6 +635,8 @@ impl RewardDistributionEventRepository {
             reward_source_index,
             recipient_distributed_at_index,
         ];
+        // 事件唯一标识索引（同一交易中的多个事件各记录一次）
+        indexes.push(event_identity_index());
 
         self.collection.create_indexes(indexes, None).await?;
 
@@ -749,11 +782,8 @@ impl LaunchEventRepository {
 
     /// 初始化索引
     pub async fn init_indexes(&self) -> AppResult<()> {
-        // 唯一索引：签名（防止重复）
-        let signature_index = IndexModel::builder()
-            .keys(doc! { "signature": 1 })
-            .options(IndexOptions::builder().unique(true).build())
-            .build();
+        // 唯一索引：事件唯一标识（防止重复处理）
+        let identity_index = event_identity_index();
 
         // 用户钱包索引（支持用户历史查询）
         let user_wallet_index = IndexModel::builder().keys(doc! { "user_wallet": 1 }).build();
@@ -776,7 +806,7 @@ impl LaunchEventRepository {
             .build();
 
         let indexes = vec![
-            signature_index,
+            identity_index,
             user_wallet_index,
             meme_token_index,
             launched_at_index,
@@ -784,6 +814,9 @@ impl LaunchEventRepository {
             status_time_index,
         ];
 
+        // 按签名唯一的旧索引会拒绝同一交易中的多个事件
+        drop_legacy_unique_index(&self.collection, "signature_1").await?;
+
         self.collection.create_indexes(indexes, None).await?;
         info!("✅ LaunchEvent数据库索引初始化完成");
         Ok(())
@@ -805,6 +838,18 @@ impl LaunchEventRepository {
         Ok(result)
     }
 
+    /// 根据事件唯一标识检查事件是否存在（防重复）
+    pub async fn exists_by_identity(
+        &self,
+        signature: &str,
+        instruction_index: u32,
+        event_ordinal: u32,
+    ) -> AppResult<bool> {
+        let filter = event_identity_filter(signature, instruction_index, event_ordinal);
+        let count = self.collection.count_documents(filter, None).await?;
+        Ok(count > 0)
+    }
+
     /// 更新迁移状态
     pub async fn update_migration_status(
         &self,
@@ -868,17 +913,156 @@ impl LaunchEventRepository {
         Ok(events)
     }
 
-    /// 查找需要重试的失败事件
+    /// 查找需要人工重试的失败事件（自动重试已耗尽或已放弃）
     pub async fn find_failed_migrations_for_retry(&self) -> AppResult<Vec<LaunchEvent>> {
+        let filter = doc! { "migration_status": "failed" };
+        let options = FindOptions::builder().sort(doc! { "updated_at": -1 }).build();
+        let cursor = self.collection.find(filter, options).await?;
+
+        let events: Vec<LaunchEvent> = cursor.try_collect().await?;
+
+        Ok(events)
+    }
+
+    /// 领取一个到期的待迁移事件
+    ///
+    /// 原子地为事件设置占用截止时间，避免多个服务实例同时执行同一迁移；
+    /// 占用到期后（如进程崩溃）事件会被重新领取。
+    /// 重新领取的事件可能已记录了发送过的迁移交易签名，调用方应先核对该交易再决定是否重发
+    pub async fn claim_due_migration(&self, now: i64, lease_secs: i64) -> AppResult<Option<LaunchEvent>> {
+        let filter = doc! {
+            "migration_status": { "$in": ["pending", "retrying"] },
+            "migration_next_attempt_at": { "$not": { "$gt": now } },
+            "migration_locked_until": { "$not": { "$gt": now } },
+        };
+        let update = doc! {
+            "$set": {
+                "migration_locked_until": now + lease_secs,
+                "updated_at": now,
+            }
+        };
+        let options = FindOneAndUpdateOptions::builder()
+            .sort(doc! { "launched_at": 1 })
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let event = self.collection.find_one_and_update(filter, update, options).await?;
+        Ok(event)
+    }
+
+    /// 在发送确认前记录已签名的迁移交易，返回是否更新
+    ///
+    /// 只更新仍处于待迁移状态的事件，执行期间已被放弃的迁移不会再发送交易
+    pub async fn record_migration_sent(
+        &self,
+        id: &ObjectId,
+        tx_signature: &str,
+        pool_address: &str,
+    ) -> AppResult<bool> {
+        let filter = doc! {
+            "_id": id,
+            "migration_status": { "$in": ["pending", "retrying"] },
+        };
+        let update = doc! {
+            "$set": {
+                "migration_tx_signature": tx_signature,
+                "migration_pending_pool_address": pool_address,
+                "updated_at": Utc::now().timestamp(),
+            }
+        };
+
+        let result = self.collection.update_one(filter, update, None).await?;
+        Ok(result.modified_count > 0)
+    }
+
+    /// 记录迁移成功并释放占用
+    pub async fn record_migration_success(&self, id: &ObjectId, pool_address: &str) -> AppResult<bool> {
+        let now = Utc::now().timestamp();
+        let update = doc! {
+            "$set": {
+                "migration_status": "success",
+                "migrated_pool_address": pool_address,
+                "migration_completed_at": now,
+                "migration_error": null,
+                "migration_next_attempt_at": null,
+                "migration_locked_until": null,
+                "updated_at": now,
+            }
+        };
+
+        let result = self.collection.update_one(doc! { "_id": id }, update, None).await?;
+        Ok(result.modified_count > 0)
+    }
+
+    /// 记录迁移失败并释放占用
+    ///
+    /// next_attempt_at不为空时进入重试状态，否则标记为失败等待人工处理；
+    /// 迁移执行期间已被放弃的事件不会被改回重试状态
+    pub async fn record_migration_failure(
+        &self,
+        id: &ObjectId,
+        error: &str,
+        next_attempt_at: Option<i64>,
+    ) -> AppResult<bool> {
+        let now = Utc::now().timestamp();
+        let status = if next_attempt_at.is_some() { "retrying" } else { "failed" };
+        let filter = doc! {
+            "_id": id,
+            "migration_status": { "$in": ["pending", "retrying"] },
+        };
+        let update = doc! {
+            "$set": {
+                "migration_status": status,
+                "migration_error": error,
+                "migration_next_attempt_at": next_attempt_at,
+                "migration_locked_until": null,
+                "updated_at": now,
+            },
+            "$inc": { "migration_retry_count": 1 }
+        };
+
+        let result = self.collection.update_one(filter, update, None).await?;
+        Ok(result.modified_count > 0)
+    }
+
+    /// 将失败的迁移重新放入队列（重置重试次数），返回是否更新
+    ///
+    /// 保留占用截止时间，执行中被放弃的迁移需等占用到期后才会被重新领取
+    pub async fn requeue_failed_migration(&self, signature: &str) -> AppResult<bool> {
         let filter = doc! {
+            "signature": signature,
             "migration_status": "failed",
-            "migration_retry_count": { "$lt": 3 } // 最多重试3次
         };
-        let cursor = self.collection.find(filter, None).await?;
+        let update = doc! {
+            "$set": {
+                "migration_status": "pending",
+                "migration_retry_count": 0,
+                "migration_next_attempt_at": null,
+                "updated_at": Utc::now().timestamp(),
+            }
+        };
 
-        let events: Vec<LaunchEvent> = cursor.try_collect().await?;
+        let result = self.collection.update_one(filter, update, None).await?;
+        Ok(result.modified_count > 0)
+    }
 
-        Ok(events)
+    /// 放弃尚未完成的迁移（标记为失败并停止自动重试），返回是否更新
+    pub async fn abandon_migration(&self, signature: &str, reason: &str) -> AppResult<bool> {
+        let filter = doc! {
+            "signature": signature,
+            "migration_status": { "$in": ["pending", "retrying"] },
+        };
+        let update = doc! {
+            "$set": {
+                "migration_status": "failed",
+                "migration_error": reason,
+                "migration_next_attempt_at": null,
+                "updated_at": Utc::now().timestamp(),
+            }
+        };
+
+        let result = self.collection.update_one(filter, update, None).await?;
+        Ok(result.modified_count > 0)
     }
 
     /// 统计总Launch数量
@@ -944,6 +1128,7 @@ impl LaunchEventRepository {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::events::event_identity::EventIdentity;
     use crate::events::event_model::PairType;
     use chrono::Utc;
 
@@ -968,12 +1153,17 @@ mod tests {
             migration_completed_at: None,
             migration_error: None,
             migration_retry_count: 0,
+            migration_next_attempt_at: None,
+            migration_locked_until: None,
+            migration_tx_signature: None,
+            migration_pending_pool_address: None,
             total_liquidity_usd: 1000.0,
             pair_type: "MemeToUsdc".to_string(),
             price_range_width_percent: 300.0,
             is_high_value_launch: true,
             signature: "test_signature_123".to_string(),
             slot: 12345,
+            identity: EventIdentity::default(),
             processed_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
         }
@@ -1072,11 +1262,8 @@ impl DepositEventRepository {
 
     /// 初始化数据库索引
     pub async fn init_indexes(&self) -> AppResult<()> {
-        // 1. 唯一索引：防止重复处理
-        let signature_index = IndexModel::builder()
-            .keys(doc! { "signature": 1 })
-            .options(IndexOptions::builder().unique(true).build())
-            .build();
+        // 1. 唯一索引：事件唯一标识（防止重复处理）
+        let identity_index = event_identity_index();
 
         // 2. 用户历史查询优化
         let user_deposited_at_index = IndexModel::builder()
@@ -1135,7 +1322,7 @@ impl DepositEventRepository {
             .build();
 
         let indexes = vec![
-            signature_index,
+            identity_index,
             user_deposited_at_index,
             token_deposited_at_index,
             project_deposited_at_index,
@@ -1149,6 +1336,9 @@ impl DepositEventRepository {
             project_user_deposited_index,
         ];
 
+        // 按签名唯一的旧索引会拒绝同一交易中的多个事件
+        drop_legacy_unique_index(&self.collection, "signature_1").await?;
+
         self.collection.create_indexes(indexes, None).await?;
         info!("✅ DepositEvent数据库索引初始化完成");
         Ok(())
@@ -1339,6 +1529,18 @@ impl DepositEventRepository {
         self.collection.find_one(filter, None).await.map_err(Into::into)
     }
 
+    /// 根据事件唯一标识检查事件是否存在（防重复）
+    pub async fn exists_by_identity(
+        &self,
+        signature: &str,
+        instruction_index: u32,
+        event_ordinal: u32,
+    ) -> AppResult<bool> {
+        let filter = event_identity_filter(signature, instruction_index, event_ordinal);
+        let count = self.collection.count_documents(filter, None).await?;
+        Ok(count > 0)
+    }
+
     /// 检查事件是否存在（防重复）
     pub async fn exists_by_signature(&self, signature: &str) -> AppResult<bool> {
         let filter = doc! { "signature": signature };
@@ -1404,11 +1606,8 @@ impl TokenCreationEventRepository {
 
     /// 初始化数据库索引
     pub async fn init_indexes(&self) -> AppResult<()> {
-        // 1. 唯一索引：防止重复处理
-        let signature_index = IndexModel::builder()
-            .keys(doc! { "signature": 1 })
-            .options(IndexOptions::builder().unique(true).build())
-            .build();
+        // 1. 唯一索引：事件唯一标识（防止重复处理）
+        let identity_index = event_identity_index();
 
         // 2. 代币地址索引（最重要的查询维度）
         let mint_address_index = IndexModel::builder().keys(doc! { "mint_address": 1 }).build();
@@ -1464,7 +1663,7 @@ impl TokenCreationEventRepository {
             .build();
 
         let indexes = vec![
-            signature_index,
+            identity_index,
             mint_address_index,
             creator_created_at_index,
             project_created_at_index,
@@ -1479,6 +1678,9 @@ impl TokenCreationEventRepository {
             whitelist_created_at_index,
         ];
 
+        // 按签名唯一的旧索引会拒绝同一交易中的多个事件
+        drop_legacy_unique_index(&self.collection, "signature_1").await?;
+
         self.collection.create_indexes(indexes, None).await?;
         info!("✅ TokenCreationEvent数据库索引初始化完成");
         Ok(())
@@ -1526,6 +1728,18 @@ impl TokenCreationEventRepository {
         self.collection.find_one(filter, None).await.map_err(Into::into)
     }
 
+    /// 根据事件唯一标识检查事件是否存在（防重复）
+    pub async fn exists_by_identity(
+        &self,
+        signature: &str,
+        instruction_index: u32,
+        event_ordinal: u32,
+    ) -> AppResult<bool> {
+        let filter = event_identity_filter(signature, instruction_index, event_ordinal);
+        let count = self.collection.count_documents(filter, None).await?;
+        Ok(count > 0)
+    }
+
     /// 检查事件是否存在（防重复）
     pub async fn exists_by_signature(&self, signature: &str) -> AppResult<bool> {
         let filter = doc! { "signature": signature };
@@ -1712,9 +1926,257 @@ pub struct CreatorDistribution {
     pub total_supply: u64,
 }
 
+/// NFT铸造事件仓库
+#[derive(Debug, Clone)]
+pub struct NftMintEventRepository {
+    collection: Collection<NftMintEvent>,
+}
+
+impl NftMintEventRepository {
+    pub fn new(collection: Collection<NftMintEvent>) -> Self {
+        Self { collection }
+    }
+
+    /// 初始化索引
+    pub async fn init_indexes(&self) -> AppResult<()> {
+        // 唯一索引：事件唯一标识（防止重复处理）
+        let identity_index = event_identity_index();
+
+        // 创建复合索引：铸造者 + 区块高度（查询铸造者最新供应量）
+        let minter_slot_index = IndexModel::builder()
+            .keys(doc! {
+                "minter": 1,
+                "slot": -1
+            })
+            .build();
+
+        // 创建时间戳索引
+        let minted_at_index = IndexModel::builder().keys(doc! { "minted_at": -1 }).build();
+
+        let indexes = vec![identity_index, minter_slot_index, minted_at_index];
+
+        // 按签名唯一的旧索引会拒绝同一交易中的多个事件
+        drop_legacy_unique_index(&self.collection, "signature_1").await?;
+
+        self.collection.create_indexes(indexes, None).await?;
+
+        info!("✅ NftMintEvent数据库索引初始化完成");
+        Ok(())
+    }
+
+    /// 插入NFT铸造事件
+    pub async fn insert_nft_mint_event(&self, mut event: NftMintEvent) -> AppResult<String> {
+        event.updated_at = Utc::now().timestamp();
+
+        let result = self.collection.insert_one(event, None).await?;
+
+        Ok(result.inserted_id.as_object_id().unwrap().to_hex())
+    }
+
+    /// 根据签名查找事件
+    pub async fn find_by_signature(&self, signature: &str) -> AppResult<Option<NftMintEvent>> {
+        let filter = doc! { "signature": signature };
+        let result = self.collection.find_one(filter, None).await?;
+        Ok(result)
+    }
+
+    /// 根据事件唯一标识检查事件是否存在（防重复）
+    pub async fn exists_by_identity(
+        &self,
+        signature: &str,
+        instruction_index: u32,
+        event_ordinal: u32,
+    ) -> AppResult<bool> {
+        let filter = event_identity_filter(signature, instruction_index, event_ordinal);
+        let count = self.collection.count_documents(filter, None).await?;
+        Ok(count > 0)
+    }
+
+    /// 根据铸造者查找所有铸造事件
+    pub async fn find_by_minter(&self, minter: &str) -> AppResult<Vec<NftMintEvent>> {
+        let filter = doc! { "minter": minter };
+        let cursor = self.collection.find(filter, None).await?;
+
+        let events: Vec<NftMintEvent> = cursor.try_collect().await?;
+
+        Ok(events)
+    }
+
+    /// 获取铸造者当前的NFT供应量（以最新一条铸造事件为准）
+    pub async fn get_minter_supply(&self, minter: &str) -> AppResult<Option<MinterNftSupply>> {
+        let options = mongodb::options::FindOneOptions::builder()
+            .sort(doc! { "slot": -1 })
+            .build();
+        let latest = self.collection.find_one(doc! { "minter": minter }, options).await?;
+
+        Ok(latest.map(|event| MinterNftSupply {
+            minter: event.minter,
+            total_mint: event.total_mint,
+            remain_mint: event.remain_mint,
+            last_minted_at: event.minted_at,
+        }))
+    }
+
+    /// 获取NFT铸造统计
+    pub async fn get_nft_mint_stats(&self) -> AppResult<NftMintStats> {
+        // 统计总铸造次数
+        let total_mint_events = self.collection.count_documents(doc! {}, None).await? as u64;
+
+        // 统计铸造总量和铸造人数
+        let pipeline = vec![
+            doc! {
+                "$group": {
+                    "_id": "$minter",
+                    "amount": { "$sum": "$amount" }
+                }
+            },
+            doc! {
+                "$group": {
+                    "_id": null,
+                    "total_minted": { "$sum": "$amount" },
+                    "unique_minters": { "$sum": 1 }
+                }
+            },
+        ];
+
+        let mut cursor = self.collection.aggregate(pipeline, None).await?;
+
+        let mut total_minted = 0u64;
+        let mut unique_minters = 0u64;
+        if let Some(doc) = cursor.try_next().await? {
+            total_minted = doc.get_i64("total_minted").unwrap_or(0) as u64;
+            unique_minters = doc.get_i32("unique_minters").unwrap_or(0) as u64;
+        }
+
+        Ok(NftMintStats {
+            total_mint_events,
+            total_minted,
+            unique_minters,
+        })
+    }
+}
+
+/// 推荐关系建立事件仓库
+#[derive(Debug, Clone)]
+pub struct ReferralEstablishedEventRepository {
+    collection: Collection<ReferralEstablishedEvent>,
+}
+
+impl ReferralEstablishedEventRepository {
+    pub fn new(collection: Collection<ReferralEstablishedEvent>) -> Self {
+        Self { collection }
+    }
+
+    /// 初始化索引
+    pub async fn init_indexes(&self) -> AppResult<()> {
+        // 创建复合索引：下级用户 + 签名（唯一）
+        let user_signature_index = IndexModel::builder()
+            .keys(doc! {
+                "user": 1,
+                "signature": 1
+            })
+            .options(IndexOptions::builder().unique(true).build())
+            .build();
+
+        // 创建上级用户索引
+        let upper_index = IndexModel::builder().keys(doc! { "upper": 1 }).build();
+
+        // 创建签名索引（回填检查）
+        let signature_index = IndexModel::builder().keys(doc! { "signature": 1 }).build();
+
+        // 创建时间戳索引
+        let established_at_index = IndexModel::builder().keys(doc! { "established_at": -1 }).build();
+
+        let mut indexes = vec![user_signature_index, upper_index, signature_index, established_at_index];
+        // 事件唯一标识索引（同一交易中的多个事件各记录一次）
+        indexes.push(event_identity_index());
+
+        self.collection.create_indexes(indexes, None).await?;
+
+        info!("✅ ReferralEstablishedEvent数据库索引初始化完成");
+        Ok(())
+    }
+
+    /// 插入推荐关系建立事件
+    pub async fn insert_referral_established_event(&self, mut event: ReferralEstablishedEvent) -> AppResult<String> {
+        event.updated_at = Utc::now().timestamp();
+
+        let result = self.collection.insert_one(event, None).await?;
+
+        Ok(result.inserted_id.as_object_id().unwrap().to_hex())
+    }
+
+    /// 根据签名查找事件
+    pub async fn find_by_signature(&self, signature: &str) -> AppResult<Option<ReferralEstablishedEvent>> {
+        let filter = doc! { "signature": signature };
+        let result = self.collection.find_one(filter, None).await?;
+        Ok(result)
+    }
+
+    /// 根据事件唯一标识检查事件是否存在（防重复）
+    pub async fn exists_by_identity(
+        &self,
+        signature: &str,
+        instruction_index: u32,
+        event_ordinal: u32,
+    ) -> AppResult<bool> {
+        let filter = event_identity_filter(signature, instruction_index, event_ordinal);
+        let count = self.collection.count_documents(filter, None).await?;
+        Ok(count > 0)
+    }
+
+    /// 根据下级用户查找推荐关系事件
+    pub async fn find_by_user(&self, user: &str) -> AppResult<Option<ReferralEstablishedEvent>> {
+        let filter = doc! { "user": user };
+        let result = self.collection.find_one(filter, None).await?;
+        Ok(result)
+    }
+
+    /// 根据上级用户查找所有下级
+    pub async fn find_by_upper(&self, upper: &str) -> AppResult<Vec<ReferralEstablishedEvent>> {
+        let filter = doc! { "upper": upper };
+        let cursor = self.collection.find(filter, None).await?;
+
+        let events: Vec<ReferralEstablishedEvent> = cursor.try_collect().await?;
+
+        Ok(events)
+    }
+
+    /// 统计上级用户的下级数量
+    pub async fn count_by_upper(&self, upper: &str) -> AppResult<u64> {
+        let count = self.collection.count_documents(doc! { "upper": upper }, None).await?;
+        Ok(count)
+    }
+}
+
+/// NFT铸造统计
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct NftMintStats {
+    /// 铸造事件总数
+    pub total_mint_events: u64,
+    /// NFT铸造总量
+    pub total_minted: u64,
+    /// 铸造人数
+    pub unique_minters: u64,
+}
+
+/// 铸造者NFT供应量
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MinterNftSupply {
+    /// 铸造者地址
+    pub minter: String,
+    /// 累计铸造总数
+    pub total_mint: u64,
+    /// 剩余可被领取数量
+    pub remain_mint: u64,
+    /// 最近铸造时间
+    pub last_minted_at: i64,
+}
+
 #[cfg(test)]
 mod deposit_tests {
     use super::*;
+    use crate::events::event_identity::EventIdentity;
     use crate::events::event_model::DepositEvent;
     use chrono::Utc;
 
@@ -1748,6 +2210,7 @@ mod deposit_tests {
             // 区块链标准字段
             signature: signature.to_string(),
             slot: 12345,
+            identity: EventIdentity::default(),
             processed_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
         }
@@ -1896,6 +2359,7 @@ mod deposit_tests {
 #[cfg(test)]
 mod nft_claim_stats_tests {
     use super::*;
+    use crate::events::event_identity::EventIdentity;
     use crate::events::event_model::NftClaimEvent;
     use chrono::Utc;
 
@@ -1931,6 +2395,7 @@ mod nft_claim_stats_tests {
             estimated_usd_value: 100.0,
             signature: signature.to_string(),
             slot: 12345,
+            identity: EventIdentity::default(),
             processed_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
         }
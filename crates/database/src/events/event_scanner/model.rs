@@ -75,6 +75,8 @@ pub enum ScanStatus {
     Failed,
     /// 已取消
     Cancelled,
+    /// 部分完成（超出单次上限或有交易失败，未覆盖的部分已交由后续回填）
+    Partial,
 }
 // MongoDB BSON DateTime 序列化辅助模块
 pub(crate) mod bson_datetime {
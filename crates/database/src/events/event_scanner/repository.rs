@@ -277,7 +277,7 @@ impl ScanRecordRepository {
         // 如果是完成或失败状态，设置完成时间
         if matches!(
             status,
-            ScanStatus::Completed | ScanStatus::Failed | ScanStatus::Cancelled
+            ScanStatus::Completed | ScanStatus::Failed | ScanStatus::Cancelled | ScanStatus::Partial
         ) {
             update_doc.insert("completed_at", now_bson);
         }
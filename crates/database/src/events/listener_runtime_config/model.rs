@@ -0,0 +1,136 @@
+use crate::events::event_scanner::model::bson_datetime;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::{collections::HashSet, str::FromStr};
+use utoipa::ToSchema;
+
+/// 事件监听器运行时配置的文档键（集合中只有一份配置）
+pub const LISTENER_RUNTIME_CONFIG_KEY: &str = "event_listener";
+
+/// 单个事件类型的运行时回填配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct RuntimeBackfillEventConfig {
+    /// 事件类型名称（与回填处理器注册的名称一致）
+    pub event_type: String,
+    /// 目标程序ID
+    pub program_id: String,
+    /// 是否启用该事件类型的回填
+    pub enabled: bool,
+    /// 检查间隔（秒），为空则使用默认值
+    #[serde(default)]
+    pub check_interval_secs: Option<u64>,
+}
+
+/// 事件监听器运行时配置
+///
+/// 由管理接口写入，监听器定期读取；版本号变化时在运行时调整订阅程序、
+/// 解析器启用状态和回填任务，不需要重启服务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListenerRuntimeConfig {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    /// 配置文档键
+    pub config_key: String,
+
+    /// 订阅的程序ID列表
+    pub program_ids: Vec<String>,
+
+    /// 禁用的事件类型（解析器的事件类型，如swap、clmm_swap）
+    #[serde(default)]
+    pub disabled_event_types: Vec<String>,
+
+    /// 回填事件配置列表
+    #[serde(default)]
+    pub backfill_events: Vec<RuntimeBackfillEventConfig>,
+
+    /// 配置版本，每次修改递增
+    pub version: u64,
+
+    /// 最后修改人
+    #[serde(default)]
+    pub updated_by: Option<String>,
+
+    /// 最后修改时间
+    #[serde(with = "bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ListenerRuntimeConfig {
+    /// 验证配置是否有效
+    pub fn validate(&self) -> Result<(), String> {
+        validate_runtime_config(&self.program_ids, &self.backfill_events)
+    }
+}
+
+/// 验证运行时配置的程序列表和回填配置
+pub fn validate_runtime_config(
+    program_ids: &[String],
+    backfill_events: &[RuntimeBackfillEventConfig],
+) -> Result<(), String> {
+    if program_ids.is_empty() {
+        return Err("程序ID列表不能为空".to_string());
+    }
+
+    let mut seen = HashSet::new();
+    for program_id in program_ids {
+        Pubkey::from_str(program_id).map_err(|e| format!("无效的程序ID: {} - {}", program_id, e))?;
+        if !seen.insert(program_id) {
+            return Err(format!("程序ID重复: {}", program_id));
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for event in backfill_events {
+        if event.event_type.is_empty() {
+            return Err("回填事件类型不能为空".to_string());
+        }
+        Pubkey::from_str(&event.program_id).map_err(|e| format!("无效的回填程序ID: {} - {}", event.program_id, e))?;
+        if event.check_interval_secs == Some(0) {
+            return Err(format!("回填事件{}的检查间隔必须大于0", event.event_type));
+        }
+        if !seen.insert((&event.event_type, &event.program_id)) {
+            return Err(format!("回填事件配置重复: {} ({})", event.event_type, event.program_id));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM_ID: &str = "FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX";
+
+    fn backfill_event(event_type: &str) -> RuntimeBackfillEventConfig {
+        RuntimeBackfillEventConfig {
+            event_type: event_type.to_string(),
+            program_id: PROGRAM_ID.to_string(),
+            enabled: true,
+            check_interval_secs: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_runtime_config() {
+        let program_ids = vec![PROGRAM_ID.to_string()];
+        assert!(validate_runtime_config(&program_ids, &[backfill_event("SwapEvent")]).is_ok());
+
+        assert!(validate_runtime_config(&[], &[]).is_err());
+        assert!(validate_runtime_config(&["invalid".to_string()], &[]).is_err());
+        assert!(validate_runtime_config(&[PROGRAM_ID.to_string(), PROGRAM_ID.to_string()], &[]).is_err());
+        assert!(validate_runtime_config(&program_ids, &[backfill_event("")]).is_err());
+        assert!(validate_runtime_config(
+            &program_ids,
+            &[backfill_event("SwapEvent"), backfill_event("SwapEvent")]
+        )
+        .is_err());
+
+        let mut zero_interval = backfill_event("SwapEvent");
+        zero_interval.check_interval_secs = Some(0);
+        assert!(validate_runtime_config(&program_ids, &[zero_interval]).is_err());
+    }
+}
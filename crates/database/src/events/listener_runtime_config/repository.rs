@@ -0,0 +1,96 @@
+use crate::events::listener_runtime_config::model::{
+    ListenerRuntimeConfig, RuntimeBackfillEventConfig, LISTENER_RUNTIME_CONFIG_KEY,
+};
+use anyhow::Result;
+use chrono::Utc;
+use mongodb::{
+    bson::{doc, to_bson, DateTime as BsonDateTime},
+    options::{FindOneAndUpdateOptions, IndexOptions, ReturnDocument},
+    Collection, IndexModel,
+};
+use tracing::{error, info};
+
+/// ListenerRuntimeConfig仓储接口
+#[derive(Clone, Debug)]
+pub struct ListenerRuntimeConfigRepository {
+    collection: Collection<ListenerRuntimeConfig>,
+}
+
+impl ListenerRuntimeConfigRepository {
+    /// 创建新的ListenerRuntimeConfig仓储
+    pub fn new(collection: Collection<ListenerRuntimeConfig>) -> Self {
+        Self { collection }
+    }
+
+    /// 初始化数据库索引
+    pub async fn init_indexes(&self) -> Result<()> {
+        info!("🔧 初始化ListenerRuntimeConfig集合索引...");
+
+        let indexes = vec![
+            // 配置键唯一索引
+            IndexModel::builder()
+                .keys(doc! { "config_key": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .unique(true)
+                        .name("idx_config_key_unique".to_string())
+                        .build(),
+                )
+                .build(),
+        ];
+
+        match self.collection.create_indexes(indexes, None).await {
+            Ok(_) => {
+                info!("✅ ListenerRuntimeConfig索引创建成功");
+                Ok(())
+            }
+            Err(e) => {
+                error!("❌ ListenerRuntimeConfig索引创建失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 获取当前运行时配置（未配置时返回None）
+    pub async fn get(&self) -> Result<Option<ListenerRuntimeConfig>> {
+        let filter = doc! { "config_key": LISTENER_RUNTIME_CONFIG_KEY };
+        Ok(self.collection.find_one(filter, None).await?)
+    }
+
+    /// 保存运行时配置，版本号递增
+    pub async fn save(
+        &self,
+        program_ids: Vec<String>,
+        disabled_event_types: Vec<String>,
+        backfill_events: Vec<RuntimeBackfillEventConfig>,
+        updated_by: Option<String>,
+    ) -> Result<ListenerRuntimeConfig> {
+        let filter = doc! { "config_key": LISTENER_RUNTIME_CONFIG_KEY };
+        let update = doc! {
+            "$set": {
+                "program_ids": program_ids,
+                "disabled_event_types": disabled_event_types,
+                "backfill_events": to_bson(&backfill_events)?,
+                "updated_by": updated_by,
+                "updated_at": BsonDateTime::from_chrono(Utc::now()),
+            },
+            "$inc": { "version": 1_i64 },
+        };
+        let options = FindOneAndUpdateOptions::builder()
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .build();
+
+        match self.collection.find_one_and_update(filter, update, options).await {
+            Ok(Some(config)) => {
+                info!("✅ 监听器运行时配置已保存: version={}", config.version);
+                Ok(config)
+            }
+            Ok(None) => Err(anyhow::anyhow!("保存监听器运行时配置后未返回文档")),
+            Err(e) => {
+                error!("❌ 保存监听器运行时配置失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+}
@@ -1,2 +1,9 @@
+pub mod dead_letter;
+pub mod event_identity;
 pub mod event_model;
-pub mod event_scanner;
\ No newline at end of file
+pub mod event_scanner;
+pub mod listener_runtime_config;
+pub mod provisional_transaction;
+pub mod raw_event;
+pub mod webhook_delivery;
+pub mod webhook_subscription;
\ No newline at end of file
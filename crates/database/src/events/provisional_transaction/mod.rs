@@ -0,0 +1,5 @@
+pub mod model;
+pub mod repository;
+
+pub use model::*;
+pub use repository::*;
@@ -0,0 +1,140 @@
+use crate::events::event_scanner::model::{bson_datetime, bson_datetime_option};
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+/// 临时交易的最终确认状态
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProvisionalStatus {
+    /// 已按非finalized承诺级别写入，等待最终确认
+    Provisional,
+    /// 已在finalized承诺级别确认
+    Finalized,
+    /// 交易未能最终确认（被分叉丢弃），派生数据已回滚
+    RolledBack,
+}
+
+impl ProvisionalStatus {
+    /// 获取存储用的状态字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProvisionalStatus::Provisional => "provisional",
+            ProvisionalStatus::Finalized => "finalized",
+            ProvisionalStatus::RolledBack => "rolled_back",
+        }
+    }
+}
+
+/// 临时交易记录
+///
+/// 监听器在confirmed承诺级别写入事件后登记一条记录，作为事件的临时标记；
+/// 后台最终确认任务在finalized承诺级别复查签名，确认后标记为Finalized，
+/// 超时仍未最终确认的交易回滚其写入的事件及派生数据后标记为RolledBack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvisionalTransaction {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    /// 交易签名
+    pub signature: String,
+
+    /// 区块高度
+    pub slot: u64,
+
+    /// 写入时的承诺级别
+    pub commitment: String,
+
+    /// 该交易写入的事件类型列表
+    pub event_types: Vec<String>,
+
+    /// 最终确认状态
+    pub status: ProvisionalStatus,
+
+    /// 已检查次数
+    pub check_attempts: u32,
+
+    /// 首次写入时间
+    #[serde(with = "bson_datetime")]
+    pub first_seen_at: DateTime<Utc>,
+
+    /// 最后检查时间
+    #[serde(with = "bson_datetime_option", default)]
+    pub last_checked_at: Option<DateTime<Utc>>,
+
+    /// 状态确定时间（Finalized或RolledBack）
+    #[serde(with = "bson_datetime_option", default)]
+    pub resolved_at: Option<DateTime<Utc>>,
+
+    /// 回滚摘要（仅RolledBack时存在）
+    pub rollback_summary: Option<String>,
+}
+
+impl ProvisionalTransaction {
+    /// 创建新的临时交易记录
+    pub fn new(signature: String, slot: u64, commitment: String, event_types: Vec<String>) -> Self {
+        Self {
+            id: None,
+            signature,
+            slot,
+            commitment,
+            event_types,
+            status: ProvisionalStatus::Provisional,
+            check_attempts: 0,
+            first_seen_at: Utc::now(),
+            last_checked_at: None,
+            resolved_at: None,
+            rollback_summary: None,
+        }
+    }
+
+    /// 距首次写入经过的秒数
+    pub fn age_secs(&self) -> i64 {
+        (Utc::now() - self.first_seen_at).num_seconds()
+    }
+}
+
+/// 临时交易状态统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProvisionalTransactionStats {
+    /// 等待最终确认的交易数
+    pub provisional: u64,
+    /// 已最终确认的交易数
+    pub finalized: u64,
+    /// 已回滚的交易数
+    pub rolled_back: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_provisional_transaction() {
+        let record = ProvisionalTransaction::new(
+            "test_signature".to_string(),
+            12345,
+            "confirmed".to_string(),
+            vec!["swap".to_string()],
+        );
+
+        assert_eq!(record.status, ProvisionalStatus::Provisional);
+        assert_eq!(record.check_attempts, 0);
+        assert!(record.last_checked_at.is_none());
+        assert!(record.age_secs() <= 1);
+    }
+
+    #[test]
+    fn test_status_serialization() {
+        for status in [
+            ProvisionalStatus::Provisional,
+            ProvisionalStatus::Finalized,
+            ProvisionalStatus::RolledBack,
+        ] {
+            assert_eq!(
+                serde_json::to_string(&status).unwrap(),
+                format!("\"{}\"", status.as_str())
+            );
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use crate::events::provisional_transaction::model::{
+    ProvisionalStatus, ProvisionalTransaction, ProvisionalTransactionStats,
+};
+use anyhow::Result;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, DateTime as BsonDateTime},
+    options::{FindOptions, IndexOptions, UpdateOptions},
+    Collection, IndexModel,
+};
+use tracing::{debug, error, info};
+
+/// ProvisionalTransaction仓储接口
+#[derive(Clone, Debug)]
+pub struct ProvisionalTransactionRepository {
+    collection: Collection<ProvisionalTransaction>,
+}
+
+impl ProvisionalTransactionRepository {
+    /// 创建新的ProvisionalTransaction仓储
+    pub fn new(collection: Collection<ProvisionalTransaction>) -> Self {
+        Self { collection }
+    }
+
+    /// 初始化数据库索引
+    pub async fn init_indexes(&self) -> Result<()> {
+        info!("🔧 初始化ProvisionalTransaction集合索引...");
+
+        let indexes = vec![
+            // 签名唯一索引
+            IndexModel::builder()
+                .keys(doc! { "signature": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .unique(true)
+                        .name("idx_signature_unique".to_string())
+                        .build(),
+                )
+                .build(),
+            // 待确认交易查询索引
+            IndexModel::builder()
+                .keys(doc! { "status": 1, "slot": 1 })
+                .options(IndexOptions::builder().name("idx_status_slot".to_string()).build())
+                .build(),
+        ];
+
+        match self.collection.create_indexes(indexes, None).await {
+            Ok(_) => {
+                info!("✅ ProvisionalTransaction索引创建成功");
+                Ok(())
+            }
+            Err(e) => {
+                error!("❌ ProvisionalTransaction索引创建失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 登记临时交易（已存在时合并事件类型，不影响已确定的状态）
+    pub async fn upsert_provisional(&self, record: &ProvisionalTransaction) -> Result<()> {
+        let filter = doc! { "signature": &record.signature };
+        let update = doc! {
+            "$setOnInsert": {
+                "slot": record.slot as i64,
+                "commitment": &record.commitment,
+                "status": ProvisionalStatus::Provisional.as_str(),
+                "check_attempts": 0_i32,
+                "first_seen_at": BsonDateTime::from_millis(record.first_seen_at.timestamp_millis()),
+            },
+            "$addToSet": {
+                "event_types": { "$each": &record.event_types }
+            }
+        };
+        let options = UpdateOptions::builder().upsert(true).build();
+
+        self.collection.update_one(filter, update, options).await?;
+        debug!("📝 临时交易已登记: signature={}", record.signature);
+        Ok(())
+    }
+
+    /// 查询等待最终确认的交易（按slot升序，先检查最早的交易）
+    pub async fn find_pending(&self, limit: i64) -> Result<Vec<ProvisionalTransaction>> {
+        let filter = doc! { "status": ProvisionalStatus::Provisional.as_str() };
+        let options = FindOptions::builder().sort(doc! { "slot": 1 }).limit(limit).build();
+
+        let cursor = self.collection.find(filter, options).await?;
+        let records: Vec<ProvisionalTransaction> = cursor.try_collect().await?;
+        Ok(records)
+    }
+
+    /// 根据签名查找临时交易记录
+    pub async fn find_by_signature(&self, signature: &str) -> Result<Option<ProvisionalTransaction>> {
+        let record = self.collection.find_one(doc! { "signature": signature }, None).await?;
+        Ok(record)
+    }
+
+    /// 记录一次最终确认检查
+    pub async fn record_check(&self, signatures: &[String]) -> Result<u64> {
+        if signatures.is_empty() {
+            return Ok(0);
+        }
+
+        let filter = doc! {
+            "signature": { "$in": signatures },
+            "status": ProvisionalStatus::Provisional.as_str(),
+        };
+        let update = doc! {
+            "$inc": { "check_attempts": 1 },
+            "$set": { "last_checked_at": BsonDateTime::from_millis(Utc::now().timestamp_millis()) }
+        };
+
+        let result = self.collection.update_many(filter, update, None).await?;
+        Ok(result.modified_count)
+    }
+
+    /// 批量标记为已最终确认
+    pub async fn mark_finalized(&self, signatures: &[String]) -> Result<u64> {
+        if signatures.is_empty() {
+            return Ok(0);
+        }
+
+        let filter = doc! {
+            "signature": { "$in": signatures },
+            "status": ProvisionalStatus::Provisional.as_str(),
+        };
+        let update = doc! {
+            "$set": {
+                "status": ProvisionalStatus::Finalized.as_str(),
+                "resolved_at": BsonDateTime::from_millis(Utc::now().timestamp_millis()),
+            }
+        };
+
+        let result = self.collection.update_many(filter, update, None).await?;
+        debug!("✅ {}笔交易已标记为最终确认", result.modified_count);
+        Ok(result.modified_count)
+    }
+
+    /// 标记为已回滚并记录回滚摘要
+    pub async fn mark_rolled_back(&self, signature: &str, summary: &str) -> Result<bool> {
+        let filter = doc! {
+            "signature": signature,
+            "status": ProvisionalStatus::Provisional.as_str(),
+        };
+        let update = doc! {
+            "$set": {
+                "status": ProvisionalStatus::RolledBack.as_str(),
+                "resolved_at": BsonDateTime::from_millis(Utc::now().timestamp_millis()),
+                "rollback_summary": summary,
+            }
+        };
+
+        let result = self.collection.update_one(filter, update, None).await?;
+        Ok(result.modified_count > 0)
+    }
+
+    /// 统计各状态的交易数
+    pub async fn get_stats(&self) -> Result<ProvisionalTransactionStats> {
+        let pipeline = vec![doc! { "$group": { "_id": "$status", "count": { "$sum": 1 } } }];
+        let mut cursor = self.collection.aggregate(pipeline, None).await?;
+
+        let mut stats = ProvisionalTransactionStats::default();
+        while let Some(doc) = cursor.try_next().await? {
+            let count = doc.get_i32("count").unwrap_or(0) as u64;
+            match doc.get_str("_id").unwrap_or_default() {
+                "provisional" => stats.provisional = count,
+                "finalized" => stats.finalized = count,
+                "rolled_back" => stats.rolled_back = count,
+                _ => {}
+            }
+        }
+
+        Ok(stats)
+    }
+}
@@ -0,0 +1,96 @@
+use crate::events::event_identity::EventIdentity;
+use crate::events::event_scanner::model::bson_datetime;
+use chrono::{DateTime, Utc};
+use mongodb::bson::{oid::ObjectId, Document};
+use serde::{Deserialize, Serialize};
+
+/// IDL通用解析的原始事件
+///
+/// 没有专用解析器的事件按Anchor IDL动态解码后写入该集合，字段名与IDL保持一致；
+/// 超出i64范围的u64以及u128/i128以十进制字符串存储
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawEvent {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    /// 发出事件的程序ID
+    pub program_id: String,
+
+    /// IDL中的事件名称
+    pub event_name: String,
+
+    /// 事件discriminator（十六进制）
+    pub discriminator: String,
+
+    /// 事件原始数据的SHA-256（十六进制），同一交易内区分同名事件
+    pub data_hash: String,
+
+    /// 解码后的事件字段
+    pub data: Document,
+
+    /// 交易签名
+    pub signature: String,
+
+    /// 区块高度
+    pub slot: u64,
+
+    /// 事件在交易中的位置
+    #[serde(flatten)]
+    pub identity: EventIdentity,
+
+    /// 记录创建时间
+    #[serde(with = "bson_datetime")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl RawEvent {
+    /// 验证事件数据是否有效
+    pub fn validate(&self) -> Result<(), String> {
+        if self.program_id.is_empty() {
+            return Err("程序ID不能为空".to_string());
+        }
+
+        if self.event_name.is_empty() {
+            return Err("事件名称不能为空".to_string());
+        }
+
+        if self.signature.is_empty() {
+            return Err("交易签名不能为空".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::doc;
+
+    fn create_test_raw_event() -> RawEvent {
+        RawEvent {
+            id: None,
+            program_id: "program".to_string(),
+            event_name: "PriceUpdated".to_string(),
+            discriminator: "0102030405060708".to_string(),
+            data_hash: "hash".to_string(),
+            data: doc! { "price": 100_i64 },
+            signature: "test_signature".to_string(),
+            slot: 12345,
+            identity: EventIdentity::default(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_validate_success() {
+        assert!(create_test_raw_event().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_empty_event_name() {
+        let mut event = create_test_raw_event();
+        event.event_name = String::new();
+        assert!(event.validate().is_err());
+    }
+}
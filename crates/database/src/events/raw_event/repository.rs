@@ -0,0 +1,125 @@
+use crate::events::event_identity::{drop_legacy_unique_index, event_identity_filter, event_identity_index};
+use crate::events::raw_event::model::RawEvent;
+use anyhow::Result;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId},
+    options::{FindOptions, IndexOptions},
+    Collection, IndexModel,
+};
+use tracing::{debug, error, info};
+
+/// RawEvent仓储接口
+#[derive(Clone, Debug)]
+pub struct RawEventRepository {
+    collection: Collection<RawEvent>,
+}
+
+impl RawEventRepository {
+    /// 创建新的RawEvent仓储
+    pub fn new(collection: Collection<RawEvent>) -> Self {
+        Self { collection }
+    }
+
+    /// 初始化数据库索引
+    pub async fn init_indexes(&self) -> Result<()> {
+        info!("🔧 初始化RawEvent集合索引...");
+
+        let indexes = vec![
+            // 事件唯一标识索引（同一交易中的多个事件各记录一次）
+            event_identity_index(),
+            // 按程序和事件名称查询历史
+            IndexModel::builder()
+                .keys(doc! { "program_id": 1, "event_name": 1, "slot": -1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("idx_program_event_name_slot".to_string())
+                        .build(),
+                )
+                .build(),
+            // 回填查询索引
+            IndexModel::builder()
+                .keys(doc! { "slot": 1 })
+                .options(IndexOptions::builder().name("idx_slot".to_string()).build())
+                .build(),
+        ];
+
+        // 旧的唯一索引已被事件唯一标识索引取代
+        drop_legacy_unique_index(&self.collection, "idx_signature_data_hash_unique").await?;
+
+        match self.collection.create_indexes(indexes, None).await {
+            Ok(_) => {
+                info!("✅ RawEvent索引创建成功");
+                Ok(())
+            }
+            Err(e) => {
+                error!("❌ RawEvent索引创建失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 插入原始事件（已存在时返回None，保证幂等）
+    pub async fn insert_if_absent(&self, mut event: RawEvent) -> Result<Option<ObjectId>> {
+        event.created_at = Utc::now();
+
+        if let Err(e) = event.validate() {
+            error!("❌ RawEvent数据验证失败: {}", e);
+            return Err(anyhow::anyhow!("数据验证失败: {}", e));
+        }
+
+        // 同一事件（或引入标识之前按签名写入的旧记录）已存在时跳过
+        if let Some((instruction_index, event_ordinal)) = event.identity.position() {
+            let filter = event_identity_filter(&event.signature, instruction_index, event_ordinal);
+            if self.collection.count_documents(filter, None).await? > 0 {
+                debug!("ℹ️ 原始事件已存在，跳过: signature={}", event.signature);
+                return Ok(None);
+            }
+        }
+
+        match self.collection.insert_one(&event, None).await {
+            Ok(result) => {
+                debug!(
+                    "✅ 原始事件插入成功: event={}, program={}, signature={}",
+                    event.event_name, event.program_id, event.signature
+                );
+                Ok(result.inserted_id.as_object_id())
+            }
+            Err(e) => {
+                if e.to_string().contains("duplicate key") {
+                    debug!("ℹ️ 原始事件已存在，跳过: signature={}", event.signature);
+                    return Ok(None);
+                }
+                error!("❌ 原始事件插入失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 根据签名查找原始事件
+    pub async fn find_by_signature(&self, signature: &str) -> Result<Vec<RawEvent>> {
+        let cursor = self.collection.find(doc! { "signature": signature }, None).await?;
+        let events: Vec<RawEvent> = cursor.try_collect().await?;
+        Ok(events)
+    }
+
+    /// 根据程序和事件名称查找事件历史（按slot倒序）
+    pub async fn find_by_event_name(
+        &self,
+        program_id: &str,
+        event_name: &str,
+        limit: Option<i64>,
+    ) -> Result<Vec<RawEvent>> {
+        let filter = doc! { "program_id": program_id, "event_name": event_name };
+        let options = FindOptions::builder()
+            .sort(doc! { "slot": -1 })
+            .limit(limit.unwrap_or(100))
+            .build();
+
+        let cursor = self.collection.find(filter, options).await?;
+        let events: Vec<RawEvent> = cursor.try_collect().await?;
+        debug!("✅ 查询到{}条{}原始事件", events.len(), event_name);
+        Ok(events)
+    }
+}
@@ -0,0 +1,162 @@
+use crate::events::event_scanner::model::{bson_datetime, bson_datetime_option};
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// webhook投递状态
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    /// 等待投递（包括等待重试）
+    Pending,
+    /// 投递成功
+    Delivered,
+    /// 重试耗尽或订阅已停用，放弃投递
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    /// 获取存储用的状态字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookDeliveryStatus::Pending => "pending",
+            WebhookDeliveryStatus::Delivered => "delivered",
+            WebhookDeliveryStatus::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for WebhookDeliveryStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(WebhookDeliveryStatus::Pending),
+            "delivered" => Ok(WebhookDeliveryStatus::Delivered),
+            "failed" => Ok(WebhookDeliveryStatus::Failed),
+            _ => Err(format!("未知的webhook投递状态: {}", s)),
+        }
+    }
+}
+
+/// webhook投递记录
+///
+/// 监听器持久化事件后为每个匹配的订阅生成一条记录，投递任务按next_attempt_at
+/// 发送并记录每次尝试的结果，同时作为投递日志供管理接口查询
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDelivery {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    /// 所属订阅ID
+    pub subscription_id: ObjectId,
+
+    /// 订阅所有者
+    pub owner: String,
+
+    /// 事件类型
+    pub event_type: String,
+
+    /// 交易签名
+    pub signature: String,
+
+    /// 区块高度
+    pub slot: u64,
+
+    /// 推送的事件负载（ParsedEvent的JSON序列化）
+    pub payload: String,
+
+    /// 投递状态
+    pub status: WebhookDeliveryStatus,
+
+    /// 已尝试次数
+    pub attempts: u32,
+
+    /// 最后一次响应的HTTP状态码
+    #[serde(default)]
+    pub last_status_code: Option<u16>,
+
+    /// 最后一次失败的错误信息
+    #[serde(default)]
+    pub last_error: Option<String>,
+
+    /// 下次尝试时间
+    #[serde(with = "bson_datetime")]
+    pub next_attempt_at: DateTime<Utc>,
+
+    /// 创建时间
+    #[serde(with = "bson_datetime")]
+    pub created_at: DateTime<Utc>,
+
+    /// 状态更新时间
+    #[serde(with = "bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+
+    /// 投递成功时间
+    #[serde(with = "bson_datetime_option", default)]
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl WebhookDelivery {
+    /// 创建新的待投递记录
+    pub fn new(
+        subscription_id: ObjectId,
+        owner: String,
+        event_type: String,
+        signature: String,
+        slot: u64,
+        payload: String,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            subscription_id,
+            owner,
+            event_type,
+            signature,
+            slot,
+            payload,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            last_status_code: None,
+            last_error: None,
+            next_attempt_at: now,
+            created_at: now,
+            updated_at: now,
+            delivered_at: None,
+        }
+    }
+}
+
+/// webhook投递记录查询条件
+#[derive(Debug, Clone, Default)]
+pub struct WebhookDeliveryQuery {
+    pub subscription_id: Option<ObjectId>,
+    pub owner: Option<String>,
+    pub status: Option<WebhookDeliveryStatus>,
+    pub page: u64,
+    pub page_size: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            WebhookDeliveryStatus::Pending,
+            WebhookDeliveryStatus::Delivered,
+            WebhookDeliveryStatus::Failed,
+        ] {
+            assert_eq!(WebhookDeliveryStatus::from_str(status.as_str()).unwrap(), status);
+            assert_eq!(
+                serde_json::to_string(&status).unwrap(),
+                format!("\"{}\"", status.as_str())
+            );
+        }
+        assert!(WebhookDeliveryStatus::from_str("unknown").is_err());
+    }
+}
@@ -0,0 +1,239 @@
+use crate::events::webhook_delivery::model::{WebhookDelivery, WebhookDeliveryQuery, WebhookDeliveryStatus};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, DateTime as BsonDateTime, Document},
+    options::{FindOptions, IndexOptions},
+    Collection, IndexModel,
+};
+use tracing::{error, info};
+
+/// WebhookDelivery仓储接口
+#[derive(Clone, Debug)]
+pub struct WebhookDeliveryRepository {
+    collection: Collection<WebhookDelivery>,
+}
+
+impl WebhookDeliveryRepository {
+    /// 创建新的WebhookDelivery仓储
+    pub fn new(collection: Collection<WebhookDelivery>) -> Self {
+        Self { collection }
+    }
+
+    /// 初始化数据库索引
+    pub async fn init_indexes(&self) -> Result<()> {
+        info!("🔧 初始化WebhookDelivery集合索引...");
+
+        let indexes = vec![
+            // 投递任务查询到期的记录
+            IndexModel::builder()
+                .keys(doc! { "status": 1, "next_attempt_at": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("idx_status_next_attempt_at".to_string())
+                        .build(),
+                )
+                .build(),
+            // 按订阅查询投递日志
+            IndexModel::builder()
+                .keys(doc! { "subscription_id": 1, "created_at": -1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("idx_subscription_created_at".to_string())
+                        .build(),
+                )
+                .build(),
+            // 按所有者查询投递日志
+            IndexModel::builder()
+                .keys(doc! { "owner": 1, "created_at": -1 })
+                .options(IndexOptions::builder().name("idx_owner_created_at".to_string()).build())
+                .build(),
+        ];
+
+        match self.collection.create_indexes(indexes, None).await {
+            Ok(_) => {
+                info!("✅ WebhookDelivery索引创建成功");
+                Ok(())
+            }
+            Err(e) => {
+                error!("❌ WebhookDelivery索引创建失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 批量写入待投递记录
+    pub async fn insert_many(&self, deliveries: &[WebhookDelivery]) -> Result<u64> {
+        if deliveries.is_empty() {
+            return Ok(0);
+        }
+
+        let result = self.collection.insert_many(deliveries, None).await?;
+        Ok(result.inserted_ids.len() as u64)
+    }
+
+    /// 查询已到期的待投递记录（按下次尝试时间升序）
+    pub async fn find_due(&self, limit: i64) -> Result<Vec<WebhookDelivery>> {
+        let filter = doc! {
+            "status": WebhookDeliveryStatus::Pending.as_str(),
+            "next_attempt_at": { "$lte": BsonDateTime::from_millis(Utc::now().timestamp_millis()) },
+        };
+        let options = FindOptions::builder()
+            .sort(doc! { "next_attempt_at": 1 })
+            .limit(limit)
+            .build();
+
+        let cursor = self.collection.find(filter, options).await?;
+        let deliveries: Vec<WebhookDelivery> = cursor.try_collect().await?;
+        Ok(deliveries)
+    }
+
+    /// 分页查询投递记录（按创建时间倒序），返回(记录列表, 总数)
+    pub async fn find_with_query(&self, query: &WebhookDeliveryQuery) -> Result<(Vec<WebhookDelivery>, u64)> {
+        let filter = Self::build_filter(query);
+        let page = query.page.max(1);
+        let page_size = query.page_size.clamp(1, 100);
+
+        let total = self.collection.count_documents(filter.clone(), None).await?;
+        let options = FindOptions::builder()
+            .sort(doc! { "created_at": -1 })
+            .skip((page - 1) * page_size)
+            .limit(page_size as i64)
+            .build();
+
+        let cursor = self.collection.find(filter, options).await?;
+        let deliveries: Vec<WebhookDelivery> = cursor.try_collect().await?;
+        Ok((deliveries, total))
+    }
+
+    /// 根据ID查找投递记录
+    pub async fn find_by_id(&self, id: &ObjectId) -> Result<Option<WebhookDelivery>> {
+        let delivery = self.collection.find_one(doc! { "_id": id }, None).await?;
+        Ok(delivery)
+    }
+
+    /// 标记投递成功
+    pub async fn mark_delivered(&self, id: &ObjectId, status_code: u16) -> Result<bool> {
+        let now = BsonDateTime::from_millis(Utc::now().timestamp_millis());
+        let update = doc! {
+            "$set": {
+                "status": WebhookDeliveryStatus::Delivered.as_str(),
+                "last_status_code": status_code as i32,
+                "last_error": null,
+                "delivered_at": now,
+                "updated_at": now,
+            },
+            "$inc": { "attempts": 1 }
+        };
+
+        let result = self.collection.update_one(doc! { "_id": id }, update, None).await?;
+        Ok(result.modified_count > 0)
+    }
+
+    /// 记录投递失败
+    ///
+    /// next_attempt_at为空表示放弃重试，记录标记为失败
+    pub async fn mark_attempt_failed(
+        &self,
+        id: &ObjectId,
+        status_code: Option<u16>,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<bool> {
+        let now = BsonDateTime::from_millis(Utc::now().timestamp_millis());
+        let mut set = doc! {
+            "last_status_code": status_code.map(|code| code as i32),
+            "last_error": error,
+            "updated_at": now,
+        };
+        match next_attempt_at {
+            Some(next_attempt_at) => {
+                set.insert("status", WebhookDeliveryStatus::Pending.as_str());
+                set.insert(
+                    "next_attempt_at",
+                    BsonDateTime::from_millis(next_attempt_at.timestamp_millis()),
+                );
+            }
+            None => {
+                set.insert("status", WebhookDeliveryStatus::Failed.as_str());
+            }
+        }
+        let update = doc! { "$set": set, "$inc": { "attempts": 1 } };
+
+        let result = self.collection.update_one(doc! { "_id": id }, update, None).await?;
+        Ok(result.modified_count > 0)
+    }
+
+    /// 重新投递失败的记录（重置为立即投递，保留尝试次数），owner不为空时只处理其所属记录，返回更新数
+    pub async fn request_redelivery(&self, ids: &[ObjectId], owner: Option<&str>) -> Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let now = BsonDateTime::from_millis(Utc::now().timestamp_millis());
+        let mut filter = doc! {
+            "_id": { "$in": ids },
+            "status": WebhookDeliveryStatus::Failed.as_str(),
+        };
+        if let Some(owner) = owner {
+            filter.insert("owner", owner);
+        }
+        let update = doc! {
+            "$set": {
+                "status": WebhookDeliveryStatus::Pending.as_str(),
+                "next_attempt_at": now,
+                "updated_at": now,
+            }
+        };
+
+        let result = self.collection.update_many(filter, update, None).await?;
+        info!("🔁 {}个webhook投递记录已重新排队", result.modified_count);
+        Ok(result.modified_count)
+    }
+
+    /// 删除订阅的所有投递记录，返回删除数
+    pub async fn delete_by_subscription(&self, subscription_id: &ObjectId) -> Result<u64> {
+        let result = self
+            .collection
+            .delete_many(doc! { "subscription_id": subscription_id }, None)
+            .await?;
+        Ok(result.deleted_count)
+    }
+
+    /// 构建查询条件
+    fn build_filter(query: &WebhookDeliveryQuery) -> Document {
+        let mut filter = doc! {};
+        if let Some(subscription_id) = &query.subscription_id {
+            filter.insert("subscription_id", subscription_id);
+        }
+        if let Some(owner) = &query.owner {
+            filter.insert("owner", owner);
+        }
+        if let Some(status) = &query.status {
+            filter.insert("status", status.as_str());
+        }
+        filter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_filter() {
+        let filter = WebhookDeliveryRepository::build_filter(&WebhookDeliveryQuery::default());
+        assert!(filter.is_empty());
+
+        let subscription_id = ObjectId::new();
+        let filter = WebhookDeliveryRepository::build_filter(&WebhookDeliveryQuery {
+            subscription_id: Some(subscription_id),
+            status: Some(WebhookDeliveryStatus::Failed),
+            ..Default::default()
+        });
+        assert_eq!(filter.get_object_id("subscription_id").unwrap(), subscription_id);
+        assert_eq!(filter.get_str("status").unwrap(), "failed");
+        assert!(filter.get("owner").is_none());
+    }
+}
@@ -0,0 +1,307 @@
+use crate::events::event_scanner::model::bson_datetime;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use url::{Host, Url};
+use utoipa::ToSchema;
+
+/// webhook订阅的事件过滤条件
+///
+/// 各条件之间为且关系，未设置的条件不参与过滤
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct WebhookFilter {
+    /// 事件类型（如swap、launch、init_pool），为空表示全部类型
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    /// 池子地址
+    #[serde(default)]
+    pub pool_address: Option<String>,
+    /// 代币mint地址
+    #[serde(default)]
+    pub mint: Option<String>,
+    /// 钱包地址
+    #[serde(default)]
+    pub wallet: Option<String>,
+}
+
+impl WebhookFilter {
+    /// 检查事件是否满足过滤条件
+    pub fn matches(&self, event_type: &str, pool_address: Option<&str>, mints: &[&str], wallets: &[&str]) -> bool {
+        if !self.event_types.is_empty() && !self.event_types.iter().any(|t| t == event_type) {
+            return false;
+        }
+        if let Some(pool) = &self.pool_address {
+            if pool_address != Some(pool.as_str()) {
+                return false;
+            }
+        }
+        if let Some(mint) = &self.mint {
+            if !mints.contains(&mint.as_str()) {
+                return false;
+            }
+        }
+        if let Some(wallet) = &self.wallet {
+            if !wallets.contains(&wallet.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// webhook订阅
+///
+/// 合作方注册的事件推送地址，监听器持久化事件后按过滤条件生成投递记录，
+/// 请求体使用订阅密钥做HMAC-SHA256签名
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSubscription {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    /// 订阅所有者（创建者用户ID）
+    pub owner: String,
+
+    /// 接收推送的URL
+    pub url: String,
+
+    /// HMAC签名密钥
+    pub secret: String,
+
+    /// 事件过滤条件
+    #[serde(default)]
+    pub filter: WebhookFilter,
+
+    /// 是否启用
+    pub active: bool,
+
+    /// 备注
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// 创建时间
+    #[serde(with = "bson_datetime")]
+    pub created_at: DateTime<Utc>,
+
+    /// 更新时间
+    #[serde(with = "bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    /// 创建新的webhook订阅
+    pub fn new(owner: String, url: String, secret: String, filter: WebhookFilter, description: Option<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            owner,
+            url,
+            secret,
+            filter,
+            active: true,
+            description,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// 验证webhook推送地址
+///
+/// 只做格式和字面量主机的检查：协议必须为http(s)，主机不能是localhost或内网、回环、链路本地等保留地址。
+/// 域名需要再经过[`resolve_webhook_url`]解析后才能确认实际指向的地址
+pub fn validate_webhook_url(url: &str) -> Result<(), String> {
+    parse_webhook_url(url).map(|_| ())
+}
+
+/// 解析webhook地址的主机，并确认所有解析结果都不是内网或保留地址
+///
+/// 返回主机名和解析出的地址，投递时应固定连接这些地址，避免DNS在校验后被改指向内网
+pub async fn resolve_webhook_url(url: &str) -> Result<(String, Vec<SocketAddr>), String> {
+    let (host, port) = parse_webhook_url(url)?;
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .map_err(|e| format!("webhook地址无法解析: {} ({})", host, e))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("webhook地址无法解析: {}", host));
+    }
+    if let Some(addr) = addrs.iter().find(|addr| is_forbidden_webhook_ip(addr.ip())) {
+        return Err(format!("webhook地址不能指向内网或保留地址: {} -> {}", host, addr.ip()));
+    }
+
+    Ok((host, addrs))
+}
+
+/// 解析webhook地址，返回主机和端口
+fn parse_webhook_url(url: &str) -> Result<(String, u16), String> {
+    if url.len() > 2048 {
+        return Err("webhook地址过长".to_string());
+    }
+
+    let parsed = Url::parse(url).map_err(|e| format!("webhook地址格式无效: {} ({})", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("webhook地址必须以http://或https://开头: {}", url));
+    }
+    let port = parsed
+        .port_or_known_default()
+        .ok_or_else(|| format!("webhook地址缺少端口: {}", url))?;
+
+    let host = match parsed.host() {
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            if domain == "localhost" || domain.ends_with(".localhost") {
+                return Err(format!("webhook地址不能指向本机: {}", url));
+            }
+            domain
+        }
+        Some(Host::Ipv4(ip)) => check_literal_ip(IpAddr::V4(ip), url)?,
+        Some(Host::Ipv6(ip)) => check_literal_ip(IpAddr::V6(ip), url)?,
+        None => return Err(format!("webhook地址缺少主机: {}", url)),
+    };
+
+    Ok((host, port))
+}
+
+fn check_literal_ip(ip: IpAddr, url: &str) -> Result<String, String> {
+    if is_forbidden_webhook_ip(ip) {
+        return Err(format!("webhook地址不能指向内网或保留地址: {}", url));
+    }
+    Ok(ip.to_string())
+}
+
+/// 判断地址是否为webhook禁止访问的地址
+///
+/// 包括回环、私有网段、链路本地（含169.254.169.254元数据服务）、运营商共享网段、
+/// 未指定、广播、组播和其他保留网段；IPv4映射、NAT64和6to4的IPv6地址按其内嵌的IPv4地址判断
+pub fn is_forbidden_webhook_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, _, _] = ip.octets();
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b))
+                || (a == 198 && (b == 18 || b == 19))
+                || a >= 240
+        }
+        IpAddr::V6(ip) => {
+            if let Some(v4) = embedded_ipv4(ip) {
+                return is_forbidden_webhook_ip(IpAddr::V4(v4));
+            }
+            let first = ip.segments()[0];
+            ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80
+        }
+    }
+}
+
+/// 提取IPv6地址内嵌的IPv4地址
+///
+/// 支持IPv4映射（::ffff:0:0/96）、NAT64（64:ff9b::/96，取低32位）和6to4（2002::/16，取第16~48位）
+fn embedded_ipv4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    if let Some(v4) = ip.to_ipv4_mapped() {
+        return Some(v4);
+    }
+
+    let segments = ip.segments();
+    let [a, b, c, d] = match segments {
+        [0x64, 0xff9b, 0, 0, 0, 0, high, low] => [high >> 8, high & 0xff, low >> 8, low & 0xff],
+        [0x2002, high, low, ..] => [high >> 8, high & 0xff, low >> 8, low & 0xff],
+        _ => return None,
+    };
+    Some(Ipv4Addr::new(a as u8, b as u8, c as u8, d as u8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_filter_matches() {
+        let all = WebhookFilter::default();
+        assert!(all.matches("swap", None, &[], &[]));
+
+        let filter = WebhookFilter {
+            event_types: vec!["swap".to_string(), "clmm_swap".to_string()],
+            pool_address: Some("pool".to_string()),
+            mint: Some("mint".to_string()),
+            wallet: None,
+        };
+        assert!(filter.matches("swap", Some("pool"), &["mint", "other"], &["wallet"]));
+        assert!(!filter.matches("launch", Some("pool"), &["mint"], &[]));
+        assert!(!filter.matches("swap", Some("other_pool"), &["mint"], &[]));
+        assert!(!filter.matches("swap", None, &["mint"], &[]));
+        assert!(!filter.matches("swap", Some("pool"), &["other"], &[]));
+
+        let filter = WebhookFilter {
+            wallet: Some("wallet".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.matches("launch", None, &[], &["creator", "wallet"]));
+        assert!(!filter.matches("launch", None, &[], &["creator"]));
+    }
+
+    #[test]
+    fn test_validate_webhook_url() {
+        assert!(validate_webhook_url("https://partner.example.com/hooks").is_ok());
+        assert!(validate_webhook_url("http://93.184.216.34:8080/hooks").is_ok());
+        assert!(validate_webhook_url("ftp://partner.example.com").is_err());
+        assert!(validate_webhook_url("partner.example.com").is_err());
+
+        // 本机、内网和元数据服务地址
+        assert!(validate_webhook_url("http://localhost:8080").is_err());
+        assert!(validate_webhook_url("http://api.localhost.").is_err());
+        assert!(validate_webhook_url("http://127.0.0.1/hooks").is_err());
+        assert!(validate_webhook_url("http://127.1/hooks").is_err());
+        assert!(validate_webhook_url("http://2130706433/hooks").is_err());
+        assert!(validate_webhook_url("http://10.0.0.5/hooks").is_err());
+        assert!(validate_webhook_url("http://192.168.1.1/hooks").is_err());
+        assert!(validate_webhook_url("http://169.254.169.254/latest/meta-data").is_err());
+        assert!(validate_webhook_url("http://user@169.254.169.254/").is_err());
+        assert!(validate_webhook_url("http://[::1]:8080").is_err());
+        assert!(validate_webhook_url("http://[fd00::1]/hooks").is_err());
+        assert!(validate_webhook_url("http://[::ffff:10.0.0.1]/hooks").is_err());
+        assert!(validate_webhook_url("http://[64:ff9b::a9fe:a9fe]/hooks").is_err());
+        assert!(validate_webhook_url("http://[2002:7f00:1::]/hooks").is_err());
+    }
+
+    #[test]
+    fn test_forbidden_ipv6_with_embedded_ipv4() {
+        let forbidden = |ip: &str| is_forbidden_webhook_ip(ip.parse().unwrap());
+
+        // NAT64：64:ff9b::/96，低32位为IPv4地址
+        assert!(forbidden("64:ff9b::7f00:1"));
+        assert!(forbidden("64:ff9b::10.0.0.5"));
+        assert!(forbidden("64:ff9b::169.254.169.254"));
+        assert!(!forbidden("64:ff9b::93.184.216.34"));
+
+        // 6to4：2002::/16，第16~48位为IPv4地址
+        assert!(forbidden("2002:7f00:1::"));
+        assert!(forbidden("2002:c0a8:101::1"));
+        assert!(forbidden("2002:a9fe:a9fe:1::1"));
+        assert!(!forbidden("2002:5db8:d822::1"));
+
+        // 普通公网IPv6地址
+        assert!(!forbidden("2606:2800:220:1:248:1893:25c8:1946"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_webhook_url_rejects_private_targets() {
+        assert!(resolve_webhook_url("http://127.0.0.1:8080/hooks").await.is_err());
+        assert!(resolve_webhook_url("http://169.254.169.254/").await.is_err());
+
+        let (host, addrs) = resolve_webhook_url("https://93.184.216.34/hooks").await.unwrap();
+        assert_eq!(host, "93.184.216.34");
+        assert_eq!(addrs, vec!["93.184.216.34:443".parse::<SocketAddr>().unwrap()]);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::events::webhook_subscription::model::{WebhookFilter, WebhookSubscription};
+use anyhow::Result;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, to_bson, DateTime as BsonDateTime, Document},
+    options::{FindOptions, IndexOptions},
+    Collection, IndexModel,
+};
+use tracing::{error, info};
+
+/// WebhookSubscription仓储接口
+#[derive(Clone, Debug)]
+pub struct WebhookSubscriptionRepository {
+    collection: Collection<WebhookSubscription>,
+}
+
+impl WebhookSubscriptionRepository {
+    /// 创建新的WebhookSubscription仓储
+    pub fn new(collection: Collection<WebhookSubscription>) -> Self {
+        Self { collection }
+    }
+
+    /// 初始化数据库索引
+    pub async fn init_indexes(&self) -> Result<()> {
+        info!("🔧 初始化WebhookSubscription集合索引...");
+
+        let indexes = vec![
+            // 按所有者查询
+            IndexModel::builder()
+                .keys(doc! { "owner": 1, "created_at": -1 })
+                .options(IndexOptions::builder().name("idx_owner_created_at".to_string()).build())
+                .build(),
+            // 监听器加载启用的订阅
+            IndexModel::builder()
+                .keys(doc! { "active": 1 })
+                .options(IndexOptions::builder().name("idx_active".to_string()).build())
+                .build(),
+        ];
+
+        match self.collection.create_indexes(indexes, None).await {
+            Ok(_) => {
+                info!("✅ WebhookSubscription索引创建成功");
+                Ok(())
+            }
+            Err(e) => {
+                error!("❌ WebhookSubscription索引创建失败: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 创建订阅，返回带ID的订阅
+    pub async fn insert(&self, mut subscription: WebhookSubscription) -> Result<WebhookSubscription> {
+        let result = self.collection.insert_one(&subscription, None).await?;
+        subscription.id = result.inserted_id.as_object_id();
+        info!("🔔 已创建webhook订阅: {} -> {}", subscription.owner, subscription.url);
+        Ok(subscription)
+    }
+
+    /// 根据ID查找订阅
+    pub async fn find_by_id(&self, id: &ObjectId) -> Result<Option<WebhookSubscription>> {
+        let subscription = self.collection.find_one(doc! { "_id": id }, None).await?;
+        Ok(subscription)
+    }
+
+    /// 根据ID列表批量查找订阅
+    pub async fn find_by_ids(&self, ids: &[ObjectId]) -> Result<Vec<WebhookSubscription>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let cursor = self.collection.find(doc! { "_id": { "$in": ids } }, None).await?;
+        let subscriptions: Vec<WebhookSubscription> = cursor.try_collect().await?;
+        Ok(subscriptions)
+    }
+
+    /// 查询所有启用的订阅
+    pub async fn find_active(&self) -> Result<Vec<WebhookSubscription>> {
+        let cursor = self.collection.find(doc! { "active": true }, None).await?;
+        let subscriptions: Vec<WebhookSubscription> = cursor.try_collect().await?;
+        Ok(subscriptions)
+    }
+
+    /// 查询订阅列表（按创建时间倒序），owner为空时返回全部
+    pub async fn list(&self, owner: Option<&str>) -> Result<Vec<WebhookSubscription>> {
+        let filter = Self::build_owner_filter(owner);
+        let options = FindOptions::builder().sort(doc! { "created_at": -1 }).build();
+
+        let cursor = self.collection.find(filter, options).await?;
+        let subscriptions: Vec<WebhookSubscription> = cursor.try_collect().await?;
+        Ok(subscriptions)
+    }
+
+    /// 更新订阅的推送地址、过滤条件、启用状态和备注，返回是否更新
+    pub async fn update(
+        &self,
+        id: &ObjectId,
+        url: &str,
+        filter: &WebhookFilter,
+        active: bool,
+        description: Option<&str>,
+    ) -> Result<bool> {
+        let update = doc! {
+            "$set": {
+                "url": url,
+                "filter": to_bson(filter)?,
+                "active": active,
+                "description": description,
+                "updated_at": BsonDateTime::from_millis(Utc::now().timestamp_millis()),
+            }
+        };
+
+        let result = self.collection.update_one(doc! { "_id": id }, update, None).await?;
+        Ok(result.matched_count > 0)
+    }
+
+    /// 更换签名密钥，返回是否更新
+    pub async fn rotate_secret(&self, id: &ObjectId, secret: &str) -> Result<bool> {
+        let update = doc! {
+            "$set": {
+                "secret": secret,
+                "updated_at": BsonDateTime::from_millis(Utc::now().timestamp_millis()),
+            }
+        };
+
+        let result = self.collection.update_one(doc! { "_id": id }, update, None).await?;
+        Ok(result.matched_count > 0)
+    }
+
+    /// 删除订阅，返回是否删除
+    pub async fn delete(&self, id: &ObjectId) -> Result<bool> {
+        let result = self.collection.delete_one(doc! { "_id": id }, None).await?;
+        if result.deleted_count > 0 {
+            info!("🗑️ 已删除webhook订阅: {}", id);
+        }
+        Ok(result.deleted_count > 0)
+    }
+
+    /// 构建所有者过滤条件
+    fn build_owner_filter(owner: Option<&str>) -> Document {
+        match owner {
+            Some(owner) => doc! { "owner": owner },
+            None => doc! {},
+        }
+    }
+}
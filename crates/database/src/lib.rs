@@ -7,14 +7,18 @@
 //
 //////////////////////////////////////////////////////////////////////
 
+use analytics::{candle, pool_stats, protocol_metrics};
 use auth::permission_config;
-use clmm::{clmm_config, clmm_pool, position, refer, reward, token_info};
+use clmm::{
+    clmm_config, clmm_pool, clmm_swap_event, position, position_event, protocol_fee_event, refer, reward, token_info,
+};
 use cpmm::{cpmm_config, init_pool_event, lp_change_event, points, swap_event};
 use mongodb::{Client, Collection};
 use std::sync::Arc;
 use tracing::{error, info};
 use utils::{AppConfig, AppResult};
 
+pub mod analytics;
 pub mod auth;
 pub mod clmm;
 pub mod cpmm;
@@ -38,6 +42,8 @@ pub struct Database {
     // 事件模型集合
     pub clmm_pool_events: Collection<event_model::ClmmPoolEvent>,
     pub nft_claim_events: Collection<event_model::NftClaimEvent>,
+    pub nft_mint_events: Collection<event_model::NftMintEvent>,
+    pub referral_established_events: Collection<event_model::ReferralEstablishedEvent>,
     pub reward_distribution_events: Collection<event_model::RewardDistributionEvent>,
     pub launch_events: Collection<event_model::LaunchEvent>,
     pub deposit_events: Collection<event_model::DepositEvent>,
@@ -48,13 +54,37 @@ pub struct Database {
     pub init_pool_events: Collection<init_pool_event::model::InitPoolEvent>,
     // CPMM交换事件集合
     pub swap_events: Collection<swap_event::model::SwapEventModel>,
+    // CLMM交换事件集合
+    pub clmm_swap_events: Collection<clmm_swap_event::model::ClmmSwapEventModel>,
+    // CLMM仓位事件流水集合
+    pub clmm_position_events: Collection<position_event::model::ClmmPositionEvent>,
+    // CLMM协议费用领取流水集合
+    pub clmm_protocol_fee_events: Collection<protocol_fee_event::model::ClmmProtocolFeeEvent>,
     // 事件扫描器集合
     pub event_scanner_checkpoints: Collection<event_scanner::model::EventScannerCheckpoints>,
     pub scan_records: Collection<event_scanner::model::ScanRecords>,
+    // 临时交易（等待最终确认）集合
+    pub provisional_transactions: Collection<provisional_transaction::model::ProvisionalTransaction>,
+    // 死信事件集合
+    pub dead_letter_events: Collection<dead_letter::model::DeadLetterEvent>,
+    // IDL通用解析的原始事件集合
+    pub raw_events: Collection<raw_event::model::RawEvent>,
+    // 事件监听器运行时配置集合
+    pub listener_runtime_configs: Collection<listener_runtime_config::model::ListenerRuntimeConfig>,
+    // webhook订阅集合
+    pub webhook_subscriptions: Collection<webhook_subscription::model::WebhookSubscription>,
+    // webhook投递记录集合
+    pub webhook_deliveries: Collection<webhook_delivery::model::WebhookDelivery>,
     // 用户积分集合
     pub user_points: Collection<points::model::UserPointsSummary>,
     // 用户交易积分详情集合
     pub user_transaction_points_detail: Collection<points::transaction_detail_model::UserTransactionPointsDetail>,
+    // 池子K线集合
+    pub pool_candles: Collection<candle::model::PoolCandle>,
+    // 池子滚动统计集合
+    pub pool_stats: Collection<pool_stats::model::PoolStatsSnapshot>,
+    // 协议每日指标集合
+    pub protocol_metrics: Collection<protocol_metrics::model::ProtocolDailyMetrics>,
     // 仓库层
     pub clmm_pool_repository: clmm_pool::repository::ClmmPoolRepository,
     pub cpmm_config_repository: cpmm_config::repository::CpmmConfigRepository,
@@ -65,6 +95,8 @@ pub struct Database {
     // 事件仓库
     pub clmm_pool_event_repository: event_model::repository::ClmmPoolEventRepository,
     pub nft_claim_event_repository: event_model::repository::NftClaimEventRepository,
+    pub nft_mint_event_repository: event_model::repository::NftMintEventRepository,
+    pub referral_established_event_repository: event_model::repository::ReferralEstablishedEventRepository,
     pub reward_distribution_event_repository: event_model::repository::RewardDistributionEventRepository,
     pub launch_event_repository: event_model::repository::LaunchEventRepository,
     pub deposit_event_repository: event_model::repository::DepositEventRepository,
@@ -75,13 +107,37 @@ pub struct Database {
     pub init_pool_event_repository: init_pool_event::repository::InitPoolEventRepository,
     // 交换事件仓库
     pub swap_event_repository: swap_event::repository::SwapEventRepository,
+    // CLMM交换事件仓库
+    pub clmm_swap_event_repository: clmm_swap_event::repository::ClmmSwapEventRepository,
+    // CLMM仓位事件流水仓库
+    pub clmm_position_event_repository: position_event::repository::ClmmPositionEventRepository,
+    // CLMM协议费用领取流水仓库
+    pub clmm_protocol_fee_event_repository: protocol_fee_event::repository::ClmmProtocolFeeEventRepository,
     // 事件扫描器仓库
     pub event_scanner_checkpoint_repository: event_scanner::repository::EventScannerCheckpointRepository,
     pub scan_record_repository: event_scanner::repository::ScanRecordRepository,
+    // 临时交易仓库
+    pub provisional_transaction_repository: provisional_transaction::repository::ProvisionalTransactionRepository,
+    // 死信事件仓库
+    pub dead_letter_repository: dead_letter::repository::DeadLetterRepository,
+    // 原始事件仓库
+    pub raw_event_repository: raw_event::repository::RawEventRepository,
+    // 事件监听器运行时配置仓库
+    pub listener_runtime_config_repository: listener_runtime_config::repository::ListenerRuntimeConfigRepository,
+    // webhook订阅仓库
+    pub webhook_subscription_repository: webhook_subscription::repository::WebhookSubscriptionRepository,
+    // webhook投递记录仓库
+    pub webhook_delivery_repository: webhook_delivery::repository::WebhookDeliveryRepository,
     // 用户积分仓库
     pub user_points_repository: points::repository::UserPointsRepository,
     // 用户交易积分详情仓库
     pub user_transaction_points_detail_repository: points::transaction_detail_repository::UserTransactionPointsDetailRepository,
+    // 池子K线仓库
+    pub pool_candle_repository: candle::repository::PoolCandleRepository,
+    // 池子滚动统计仓库
+    pub pool_stats_repository: pool_stats::repository::PoolStatsRepository,
+    // 协议每日指标仓库
+    pub protocol_metrics_repository: protocol_metrics::repository::ProtocolMetricsRepository,
 }
 
 impl Database {
@@ -103,6 +159,8 @@ impl Database {
         // 事件集合
         let clmm_pool_events = db.collection("ClmmPoolEvent");
         let nft_claim_events = db.collection("NftClaimEvent");
+        let nft_mint_events = db.collection("NftMintEvent");
+        let referral_established_events = db.collection("ReferralEstablishedEvent");
         let reward_distribution_events = db.collection("RewardDistributionEvent");
         let launch_events = db.collection("LaunchEvent");
         let deposit_events = db.collection("DepositEvent");
@@ -113,13 +171,37 @@ impl Database {
         let init_pool_events = db.collection("InitPoolEvent");
         // 交换事件集合
         let swap_events = db.collection("SwapEvent");
+        // CLMM交换事件集合
+        let clmm_swap_events = db.collection("ClmmSwapEvent");
+        // CLMM仓位事件流水集合
+        let clmm_position_events = db.collection("ClmmPositionEvent");
+        // CLMM协议费用领取流水集合
+        let clmm_protocol_fee_events = db.collection("ClmmProtocolFeeEvent");
         // 事件扫描器集合
         let event_scanner_checkpoints = db.collection("EventScannerCheckpoints");
         let scan_records = db.collection("ScanRecords");
+        // 临时交易集合
+        let provisional_transactions = db.collection("ProvisionalTransaction");
+        // 死信事件集合
+        let dead_letter_events = db.collection("DeadLetterEvent");
+        // IDL通用解析的原始事件集合
+        let raw_events = db.collection("RawEvent");
+        // 事件监听器运行时配置集合
+        let listener_runtime_configs = db.collection("ListenerRuntimeConfig");
+        // webhook订阅集合
+        let webhook_subscriptions = db.collection("WebhookSubscription");
+        // webhook投递记录集合
+        let webhook_deliveries = db.collection("WebhookDelivery");
         // 用户积分集合
         let user_points = db.collection("UserPointsSummary");
         // 用户交易积分详情集合
         let user_transaction_points_detail = db.collection("UserTransactionPointsDetail");
+        // 池子K线集合
+        let pool_candles = db.collection("PoolCandle");
+        // 池子滚动统计集合
+        let pool_stats = db.collection("PoolStats");
+        // 协议每日指标集合
+        let protocol_metrics = db.collection("ProtocolMetrics");
 
         // 初始化仓库层
         let clmm_pool_repository = clmm_pool::repository::ClmmPoolRepository::new(clmm_pools.clone());
@@ -136,6 +218,9 @@ impl Database {
             event_model::repository::ClmmPoolEventRepository::new(clmm_pool_events.clone());
         let nft_claim_event_repository =
             event_model::repository::NftClaimEventRepository::new(nft_claim_events.clone());
+        let nft_mint_event_repository = event_model::repository::NftMintEventRepository::new(nft_mint_events.clone());
+        let referral_established_event_repository =
+            event_model::repository::ReferralEstablishedEventRepository::new(referral_established_events.clone());
         let reward_distribution_event_repository =
             event_model::repository::RewardDistributionEventRepository::new(reward_distribution_events.clone());
         let launch_event_repository = event_model::repository::LaunchEventRepository::new(launch_events.clone());
@@ -150,10 +235,37 @@ impl Database {
             init_pool_event::repository::InitPoolEventRepository::new(init_pool_events.clone());
         // 交换事件仓库
         let swap_event_repository = swap_event::repository::SwapEventRepository::new(swap_events.clone());
+        // CLMM交换事件仓库
+        let clmm_swap_event_repository =
+            clmm_swap_event::repository::ClmmSwapEventRepository::new(clmm_swap_events.clone());
+        // CLMM仓位事件流水仓库
+        let clmm_position_event_repository =
+            position_event::repository::ClmmPositionEventRepository::new(clmm_position_events.clone());
+        // CLMM协议费用领取流水仓库
+        let clmm_protocol_fee_event_repository =
+            protocol_fee_event::repository::ClmmProtocolFeeEventRepository::new(clmm_protocol_fee_events.clone());
         // 事件扫描器仓库
         let event_scanner_checkpoint_repository =
             event_scanner::repository::EventScannerCheckpointRepository::new(event_scanner_checkpoints.clone());
         let scan_record_repository = event_scanner::repository::ScanRecordRepository::new(scan_records.clone());
+        // 临时交易仓库
+        let provisional_transaction_repository =
+            provisional_transaction::repository::ProvisionalTransactionRepository::new(
+                provisional_transactions.clone(),
+            );
+        // 死信事件仓库
+        let dead_letter_repository = dead_letter::repository::DeadLetterRepository::new(dead_letter_events.clone());
+        // 原始事件仓库
+        let raw_event_repository = raw_event::repository::RawEventRepository::new(raw_events.clone());
+        // 事件监听器运行时配置仓库
+        let listener_runtime_config_repository =
+            listener_runtime_config::repository::ListenerRuntimeConfigRepository::new(listener_runtime_configs.clone());
+        // webhook订阅仓库
+        let webhook_subscription_repository =
+            webhook_subscription::repository::WebhookSubscriptionRepository::new(webhook_subscriptions.clone());
+        // webhook投递记录仓库
+        let webhook_delivery_repository =
+            webhook_delivery::repository::WebhookDeliveryRepository::new(webhook_deliveries.clone());
         // 用户积分仓库
         let user_points_repository = points::repository::UserPointsRepository::new(user_points.clone());
         // 用户交易积分详情仓库
@@ -161,6 +273,13 @@ impl Database {
             points::transaction_detail_repository::UserTransactionPointsDetailRepository::new(
                 user_transaction_points_detail.clone(),
             );
+        // 池子K线仓库
+        let pool_candle_repository = candle::repository::PoolCandleRepository::new(pool_candles.clone());
+        // 池子滚动统计仓库
+        let pool_stats_repository = pool_stats::repository::PoolStatsRepository::new(pool_stats.clone());
+        // 协议每日指标仓库
+        let protocol_metrics_repository =
+            protocol_metrics::repository::ProtocolMetricsRepository::new(protocol_metrics.clone());
 
         info!("🧱 database({:#}) connected.", &config.mongo_db);
 
@@ -178,6 +297,8 @@ impl Database {
             token_infos,
             clmm_pool_events,
             nft_claim_events,
+            nft_mint_events,
+            referral_established_events,
             reward_distribution_events,
             launch_events,
             deposit_events,
@@ -185,10 +306,22 @@ impl Database {
             lp_change_events,
             init_pool_events,
             swap_events,
+            clmm_swap_events,
+            clmm_position_events,
+            clmm_protocol_fee_events,
             event_scanner_checkpoints,
             scan_records,
+            provisional_transactions,
+            dead_letter_events,
+            raw_events,
+            listener_runtime_configs,
+            webhook_subscriptions,
+            webhook_deliveries,
             user_points,
             user_transaction_points_detail,
+            pool_candles,
+            pool_stats,
+            protocol_metrics,
             clmm_pool_repository,
             cpmm_config_repository,
             global_permission_repository,
@@ -197,6 +330,8 @@ impl Database {
             token_info_repository,
             clmm_pool_event_repository,
             nft_claim_event_repository,
+            nft_mint_event_repository,
+            referral_established_event_repository,
             reward_distribution_event_repository,
             launch_event_repository,
             deposit_event_repository,
@@ -204,10 +339,22 @@ impl Database {
             lp_change_event_repository,
             init_pool_event_repository,
             swap_event_repository,
+            clmm_swap_event_repository,
+            clmm_position_event_repository,
+            clmm_protocol_fee_event_repository,
             event_scanner_checkpoint_repository,
             scan_record_repository,
+            provisional_transaction_repository,
+            dead_letter_repository,
+            raw_event_repository,
+            listener_runtime_config_repository,
+            webhook_subscription_repository,
+            webhook_delivery_repository,
             user_points_repository,
             user_transaction_points_detail_repository,
+            pool_candle_repository,
+            pool_stats_repository,
+            protocol_metrics_repository,
         })
     }
 
@@ -228,6 +375,8 @@ impl Database {
         // 初始化事件索引
         let _result = self.clmm_pool_event_repository.init_indexes().await;
         let _result = self.nft_claim_event_repository.init_indexes().await;
+        let _result = self.nft_mint_event_repository.init_indexes().await;
+        let _result = self.referral_established_event_repository.init_indexes().await;
         let _result = self.reward_distribution_event_repository.init_indexes().await;
         let _result = self.launch_event_repository.init_indexes().await;
         let _result = self.deposit_event_repository.init_indexes().await;
@@ -242,16 +391,50 @@ impl Database {
         // 初始化交换事件索引
         let _result = self.swap_event_repository.init_indexes().await;
 
+        // 初始化CLMM交换事件索引
+        let _result = self.clmm_swap_event_repository.init_indexes().await;
+
+        // 初始化CLMM仓位事件流水索引
+        let _result = self.clmm_position_event_repository.init_indexes().await;
+
+        // 初始化CLMM协议费用领取流水索引
+        let _result = self.clmm_protocol_fee_event_repository.init_indexes().await;
+
         // 初始化事件扫描器索引
         let _result = self.event_scanner_checkpoint_repository.init_indexes().await;
         let _result = self.scan_record_repository.init_indexes().await;
 
+        // 初始化临时交易索引
+        let _result = self.provisional_transaction_repository.init_indexes().await;
+
+        // 初始化死信事件索引
+        let _result = self.dead_letter_repository.init_indexes().await;
+
+        // 初始化原始事件索引
+        let _result = self.raw_event_repository.init_indexes().await;
+
+        // 初始化事件监听器运行时配置索引
+        let _result = self.listener_runtime_config_repository.init_indexes().await;
+
+        // 初始化webhook订阅与投递记录索引
+        let _result = self.webhook_subscription_repository.init_indexes().await;
+        let _result = self.webhook_delivery_repository.init_indexes().await;
+
         // 初始化用户积分索引
         let _result = self.user_points_repository.init_indexes().await;
 
         // 初始化用户交易积分详情索引
         let _result = self.user_transaction_points_detail_repository.init_indexes().await;
 
+        // 初始化池子K线索引
+        let _result = self.pool_candle_repository.init_indexes().await;
+
+        // 初始化池子滚动统计索引
+        let _result = self.pool_stats_repository.init_indexes().await;
+
+        // 初始化协议每日指标索引
+        let _result = self.protocol_metrics_repository.init_indexes().await;
+
         info!("✅ 权限配置和事件索引初始化完成");
         Ok(())
     }
@@ -498,4 +681,7 @@ pub use clmm::token_info::{model as token_info_model, repository as token_info_r
 
 // Export all from event_scanner with aliases to avoid conflicts
 pub use events::event_scanner::{model as event_scanner_model, repository as event_scanner_repository};
-use events::{event_model, event_scanner};
+use events::{
+    dead_letter, event_model, event_scanner, listener_runtime_config, provisional_transaction, raw_event,
+    webhook_delivery, webhook_subscription,
+};
@@ -0,0 +1,103 @@
+use crate::auth::{require_admin, AuthUser};
+use crate::dtos::solana::clmm::pool::candle::{PoolCandleQuery, PoolCandlesResponse};
+use crate::dtos::solana::common::ApiResponse;
+use crate::services::solana::clmm::candle::CandleService;
+use crate::services::Services;
+use axum::{
+    extract::{Extension, Path, Query},
+    middleware,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use database::analytics::candle::CandleRebuildSummary;
+use tracing::info;
+use utils::AppResult;
+use validator::Validate;
+
+pub struct CandleController;
+
+impl CandleController {
+    pub fn routes() -> Router {
+        Router::new()
+            .route("/:id/candles", get(get_pool_candles))
+            .merge(Self::admin_routes())
+    }
+
+    /// K线维护路由（需要管理员权限）
+    fn admin_routes() -> Router {
+        Router::new()
+            .route("/:id/candles/rebuild", post(rebuild_pool_candles))
+            .layer(middleware::from_fn(require_admin))
+    }
+}
+
+/// 获取池子K线
+///
+/// 返回CPMM或CLMM池子的OHLCV K线，价格为交换后的池子现价，已按代币精度换算。
+/// 默认以token_0为基础代币（价格为每个token_0值多少token_1），可通过base_mint或quote_mint切换方向。
+/// 指定from时从起始时间向后返回，否则返回截止时间（默认当前）之前最近的limit条。
+#[utoipa::path(
+    get,
+    path = "/api/v1/solana/pools/{id}/candles",
+    params(
+        ("id" = String, Path, description = "池子地址"),
+        PoolCandleQuery
+    ),
+    responses(
+        (status = 200, description = "查询成功", body = ApiResponse<PoolCandlesResponse>),
+        (status = 400, description = "请求参数错误"),
+        (status = 404, description = "池子不存在"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "Solana流动性"
+)]
+pub async fn get_pool_candles(
+    Extension(services): Extension<Services>,
+    Path(pool_id): Path<String>,
+    Query(params): Query<PoolCandleQuery>,
+) -> AppResult<Json<ApiResponse<PoolCandlesResponse>>> {
+    info!(
+        "📈 查询池子K线: pool={}, interval={}, from={:?}, to={:?}",
+        pool_id, params.interval, params.from, params.to
+    );
+    params.validate()?;
+
+    let service = CandleService::new(services.database.clone());
+    let response = service.get_pool_candles(&pool_id, &params).await?;
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+/// 管理员功能：从交换历史重建池子K线
+///
+/// 删除池子现有K线后按全部CPMM/CLMM交换记录重新聚合，用于修复增量更新失败或补录历史数据后的K线
+#[utoipa::path(
+    post,
+    path = "/api/v1/solana/pools/{id}/candles/rebuild",
+    params(
+        ("id" = String, Path, description = "池子地址")
+    ),
+    responses(
+        (status = 200, description = "重建成功", body = ApiResponse<CandleRebuildSummary>),
+        (status = 403, description = "权限不足"),
+        (status = 404, description = "池子不存在"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "管理员功能",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn rebuild_pool_candles(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Path(pool_id): Path<String>,
+) -> AppResult<Json<ApiResponse<CandleRebuildSummary>>> {
+    info!("🔄 管理员重建池子K线: pool={} (操作员: {})", pool_id, user.user_id);
+
+    let service = CandleService::new(services.database.clone());
+    let summary = service.rebuild_pool_candles(&pool_id).await?;
+
+    Ok(Json(ApiResponse::success(summary)))
+}
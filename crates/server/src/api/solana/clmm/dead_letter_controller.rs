@@ -0,0 +1,298 @@
+use crate::auth::{require_admin, AuthUser};
+use crate::dtos::solana::clmm::events::reward_distribution::EventPaginatedResponse;
+use crate::dtos::solana::common::{default_page, default_page_size, ApiResponse};
+use crate::services::solana::clmm::event::DeadLetterService;
+use crate::services::Services;
+use axum::{
+    extract::{Extension, Path, Query},
+    middleware,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use database::events::dead_letter::{DeadLetterEvent, DeadLetterStats, DeadLetterStatus};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use utils::AppResult;
+use utoipa::{IntoParams, ToSchema};
+
+/// 死信事件查询参数
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct DeadLetterListQuery {
+    /// 页码（从1开始）
+    #[serde(default = "default_page")]
+    pub page: u64,
+    /// 每页条数（最大100）
+    #[serde(default = "default_page_size")]
+    pub page_size: u64,
+    /// 状态过滤（pending/replay_requested/replayed/discarded）
+    pub status: Option<String>,
+    /// 事件类型过滤
+    pub event_type: Option<String>,
+    /// 交易签名过滤
+    pub signature: Option<String>,
+}
+
+/// 死信事件批量操作请求
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DeadLetterIdsRequest {
+    /// 死信事件ID列表（最多100个）
+    pub ids: Vec<String>,
+}
+
+/// 死信事件批量操作结果
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeadLetterOperationResponse {
+    /// 请求操作的事件数
+    pub requested: u64,
+    /// 实际更新的事件数（状态不匹配的事件会被跳过）
+    pub updated: u64,
+}
+
+/// 死信事件响应
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeadLetterEventResponse {
+    pub id: String,
+    pub batch_id: String,
+    pub event_type: String,
+    pub signature: String,
+    pub slot: u64,
+    pub error: String,
+    pub attempts: u32,
+    pub status: DeadLetterStatus,
+    pub replay_count: u32,
+    pub replay_error: Option<String>,
+    /// 原始事件负载
+    pub payload: serde_json::Value,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub replayed_at: Option<i64>,
+}
+
+impl From<DeadLetterEvent> for DeadLetterEventResponse {
+    fn from(event: DeadLetterEvent) -> Self {
+        // 负载无法解析时按原始字符串返回，便于排查
+        let payload = serde_json::from_str(&event.payload).unwrap_or(serde_json::Value::String(event.payload));
+        Self {
+            id: event.id.map(|id| id.to_hex()).unwrap_or_default(),
+            batch_id: event.batch_id,
+            event_type: event.event_type,
+            signature: event.signature,
+            slot: event.slot,
+            error: event.error,
+            attempts: event.attempts,
+            status: event.status,
+            replay_count: event.replay_count,
+            replay_error: event.replay_error,
+            payload,
+            created_at: event.created_at.timestamp(),
+            updated_at: event.updated_at.timestamp(),
+            replayed_at: event.replayed_at.map(|t| t.timestamp()),
+        }
+    }
+}
+
+pub struct DeadLetterController;
+
+impl DeadLetterController {
+    pub fn routes() -> Router {
+        Router::new()
+            .route("/", get(list_dead_letters))
+            .route("/stats", get(get_dead_letter_stats))
+            .route("/replay", post(replay_dead_letters))
+            .route("/discard", post(discard_dead_letters))
+            .route("/:id", get(get_dead_letter))
+            .layer(middleware::from_fn(require_admin))
+    }
+}
+
+/// 管理员功能：查询死信事件列表
+///
+/// 批量写入器放弃重试的事件会写入死信集合，按写入时间倒序返回
+#[utoipa::path(
+    get,
+    path = "/api/v1/solana/events/dead-letters",
+    params(DeadLetterListQuery),
+    responses(
+        (status = 200, description = "查询成功", body = ApiResponse<EventPaginatedResponse<DeadLetterEventResponse>>),
+        (status = 400, description = "请求参数错误"),
+        (status = 403, description = "权限不足"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "管理员功能",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn list_dead_letters(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Query(params): Query<DeadLetterListQuery>,
+) -> AppResult<Json<ApiResponse<EventPaginatedResponse<DeadLetterEventResponse>>>> {
+    info!("🔍 管理员查询死信事件列表 (操作员: {})", user.user_id);
+
+    if let Some(status) = &params.status {
+        status
+            .parse::<DeadLetterStatus>()
+            .map_err(utils::AppError::BadRequest)?;
+    }
+
+    let service = DeadLetterService::new(services.database.clone());
+    let result = service
+        .list(
+            params.status,
+            params.event_type,
+            params.signature,
+            params.page,
+            params.page_size,
+        )
+        .await?;
+
+    Ok(Json(ApiResponse::success(EventPaginatedResponse {
+        items: result.items.into_iter().map(Into::into).collect(),
+        total: result.total,
+        page: result.page,
+        page_size: result.page_size,
+        total_pages: result.total_pages,
+    })))
+}
+
+/// 管理员功能：获取死信事件统计
+#[utoipa::path(
+    get,
+    path = "/api/v1/solana/events/dead-letters/stats",
+    responses(
+        (status = 200, description = "查询成功", body = ApiResponse<DeadLetterStats>),
+        (status = 403, description = "权限不足"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "管理员功能",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn get_dead_letter_stats(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+) -> AppResult<Json<ApiResponse<DeadLetterStats>>> {
+    info!("📊 管理员查询死信事件统计 (操作员: {})", user.user_id);
+
+    let service = DeadLetterService::new(services.database.clone());
+    let stats = service.stats().await?;
+
+    Ok(Json(ApiResponse::success(stats)))
+}
+
+/// 管理员功能：查询单个死信事件
+#[utoipa::path(
+    get,
+    path = "/api/v1/solana/events/dead-letters/{id}",
+    params(
+        ("id" = String, Path, description = "死信事件ID")
+    ),
+    responses(
+        (status = 200, description = "查询成功", body = ApiResponse<DeadLetterEventResponse>),
+        (status = 400, description = "ID格式错误"),
+        (status = 404, description = "死信事件不存在"),
+        (status = 403, description = "权限不足"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "管理员功能",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn get_dead_letter(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<Json<ApiResponse<DeadLetterEventResponse>>> {
+    info!("🔍 管理员查询死信事件: {} (操作员: {})", id, user.user_id);
+
+    let id = DeadLetterService::parse_id(&id).map_err(|e| utils::AppError::BadRequest(e.to_string()))?;
+    let service = DeadLetterService::new(services.database.clone());
+    let event = service
+        .get(&id)
+        .await?
+        .ok_or_else(|| utils::AppError::NotFound("死信事件不存在".to_string()))?;
+
+    Ok(Json(ApiResponse::success(event.into())))
+}
+
+/// 管理员功能：请求重放死信事件
+///
+/// 仅将等待处理的事件标记为已请求重放，由监听器进程重新提交到批量写入器
+#[utoipa::path(
+    post,
+    path = "/api/v1/solana/events/dead-letters/replay",
+    request_body = DeadLetterIdsRequest,
+    responses(
+        (status = 200, description = "重放请求已提交", body = ApiResponse<DeadLetterOperationResponse>),
+        (status = 400, description = "ID格式错误"),
+        (status = 403, description = "权限不足"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "管理员功能",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn replay_dead_letters(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<DeadLetterIdsRequest>,
+) -> AppResult<Json<ApiResponse<DeadLetterOperationResponse>>> {
+    warn!(
+        "📮 管理员请求重放{}个死信事件 (操作员: {})",
+        request.ids.len(),
+        user.user_id
+    );
+
+    let ids = DeadLetterService::parse_ids(&request.ids).map_err(|e| utils::AppError::BadRequest(e.to_string()))?;
+    let service = DeadLetterService::new(services.database.clone());
+    let updated = service.request_replay(&ids).await?;
+
+    Ok(Json(ApiResponse::success(DeadLetterOperationResponse {
+        requested: request.ids.len() as u64,
+        updated,
+    })))
+}
+
+/// 管理员功能：丢弃死信事件
+///
+/// 已重放的事件不可丢弃
+#[utoipa::path(
+    post,
+    path = "/api/v1/solana/events/dead-letters/discard",
+    request_body = DeadLetterIdsRequest,
+    responses(
+        (status = 200, description = "丢弃成功", body = ApiResponse<DeadLetterOperationResponse>),
+        (status = 400, description = "ID格式错误"),
+        (status = 403, description = "权限不足"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "管理员功能",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn discard_dead_letters(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<DeadLetterIdsRequest>,
+) -> AppResult<Json<ApiResponse<DeadLetterOperationResponse>>> {
+    warn!(
+        "🗑️ 管理员丢弃{}个死信事件 (危险操作，操作员: {})",
+        request.ids.len(),
+        user.user_id
+    );
+
+    let ids = DeadLetterService::parse_ids(&request.ids).map_err(|e| utils::AppError::BadRequest(e.to_string()))?;
+    let service = DeadLetterService::new(services.database.clone());
+    let updated = service.discard(&ids).await?;
+
+    Ok(Json(ApiResponse::success(DeadLetterOperationResponse {
+        requested: request.ids.len() as u64,
+        updated,
+    })))
+}
@@ -1,13 +1,17 @@
+use crate::auth::{require_admin, AuthUser};
 use crate::services::Services;
 use axum::{
     extract::{Extension, Path},
+    middleware,
     response::Json,
-    routing::get,
+    routing::{get, post},
     Router,
 };
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 use utils::AppError;
-use crate::dtos::solana::clmm::events::launch_event::{LaunchEventResponse, LaunchEventStatsResponse};
+use crate::dtos::solana::clmm::events::launch_event::{
+    AbandonLaunchMigrationRequest, LaunchEventResponse, LaunchEventStatsResponse,
+};
 
 /// LaunchEvent控制器
 pub struct LaunchEventController;
@@ -20,6 +24,15 @@ impl LaunchEventController {
             .route("/stats", get(get_launch_event_stats))
             .route("/pending", get(get_pending_migrations))
             .route("/failed-retry", get(get_failed_migrations_for_retry))
+            .merge(Self::admin_routes())
+    }
+
+    /// 迁移管理路由（需要管理员权限）
+    fn admin_routes() -> Router {
+        Router::new()
+            .route("/:signature/retry", post(retry_migration))
+            .route("/:signature/abandon", post(abandon_migration))
+            .layer(middleware::from_fn(require_admin))
     }
 }
 
@@ -134,7 +147,7 @@ pub async fn get_pending_migrations(
 
 /// 获取需要重试的失败Launch事件列表
 ///
-/// 获取所有迁移失败但还可以重试的Launch事件列表。
+/// 获取所有已停止自动重试的失败Launch事件列表，可通过重试接口重新加入迁移队列。
 #[utoipa::path(
     get,
     path = "/api/v1/solana/events/launch/failed-retry",
@@ -168,3 +181,101 @@ pub async fn get_failed_migrations_for_retry(
         }
     }
 
+/// 管理员功能：重试失败的Launch迁移
+///
+/// 将状态为failed的Launch事件重新加入迁移队列，尝试次数清零后由迁移任务重新执行。
+#[utoipa::path(
+    post,
+    path = "/api/v1/solana/events/launch/{signature}/retry",
+    params(
+        ("signature" = String, Path, description = "交易签名")
+    ),
+    responses(
+        (status = 200, description = "已重新加入迁移队列", body = LaunchEventResponse),
+        (status = 400, description = "事件当前状态不允许重试"),
+        (status = 403, description = "权限不足"),
+        (status = 404, description = "未找到指定签名的Launch事件"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "LaunchEvent",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn retry_migration(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Path(signature): Path<String>,
+) -> Result<Json<LaunchEventResponse>, AppError> {
+    info!("🔄 管理员重试Launch迁移: signature={} (操作员: {})", signature, user.user_id);
+
+    let requeued = services.launch_event.retry_migration(&signature).await?;
+    let event = find_launch_event(&services, &signature).await?;
+    if !requeued {
+        warn!("Launch迁移无法重试: signature={}, status={}", signature, event.migration_status);
+        return Err(AppError::BadRequest(format!(
+            "Launch事件当前状态为{}，只有failed状态可以重试",
+            event.migration_status
+        )));
+    }
+
+    Ok(Json(event))
+}
+
+/// 管理员功能：放弃Launch迁移
+///
+/// 停止对pending/retrying状态的Launch事件的自动迁移，事件将标记为failed并记录放弃原因。
+#[utoipa::path(
+    post,
+    path = "/api/v1/solana/events/launch/{signature}/abandon",
+    params(
+        ("signature" = String, Path, description = "交易签名")
+    ),
+    request_body = AbandonLaunchMigrationRequest,
+    responses(
+        (status = 200, description = "已放弃迁移", body = LaunchEventResponse),
+        (status = 400, description = "事件当前状态不允许放弃"),
+        (status = 403, description = "权限不足"),
+        (status = 404, description = "未找到指定签名的Launch事件"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "LaunchEvent",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn abandon_migration(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Path(signature): Path<String>,
+    Json(request): Json<AbandonLaunchMigrationRequest>,
+) -> Result<Json<LaunchEventResponse>, AppError> {
+    info!("🛑 管理员放弃Launch迁移: signature={} (操作员: {})", signature, user.user_id);
+
+    let reason = request
+        .reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty())
+        .unwrap_or_else(|| format!("管理员{}手动放弃迁移", user.user_id));
+
+    let abandoned = services.launch_event.abandon_migration(&signature, &reason).await?;
+    let event = find_launch_event(&services, &signature).await?;
+    if !abandoned {
+        warn!("Launch迁移无法放弃: signature={}, status={}", signature, event.migration_status);
+        return Err(AppError::BadRequest(format!(
+            "Launch事件当前状态为{}，只有pending/retrying状态可以放弃",
+            event.migration_status
+        )));
+    }
+
+    Ok(Json(event))
+}
+
+/// 查询Launch事件，不存在时返回NotFound
+async fn find_launch_event(services: &Services, signature: &str) -> Result<LaunchEventResponse, AppError> {
+    services
+        .launch_event
+        .get_launch_event_by_signature(signature)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("未找到签名为 {} 的Launch事件", signature)))
+}
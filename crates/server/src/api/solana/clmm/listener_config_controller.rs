@@ -0,0 +1,134 @@
+use crate::auth::{require_admin, AuthUser};
+use crate::dtos::solana::common::ApiResponse;
+use crate::services::solana::clmm::event::ListenerConfigService;
+use crate::services::Services;
+use axum::{extract::Extension, middleware, response::Json, routing::get, Router};
+use database::events::listener_runtime_config::{
+    validate_runtime_config, ListenerRuntimeConfig, RuntimeBackfillEventConfig,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use utils::AppResult;
+use utoipa::ToSchema;
+
+/// 更新监听器运行时配置请求
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateListenerConfigRequest {
+    /// 订阅的程序ID列表（不能为空）
+    pub program_ids: Vec<String>,
+    /// 禁用的解析器事件类型（如swap、clmm_swap、lp_change）
+    #[serde(default)]
+    pub disabled_event_types: Vec<String>,
+    /// 回填事件配置列表（回填功能启用时生效）
+    #[serde(default)]
+    pub backfill_events: Vec<RuntimeBackfillEventConfig>,
+}
+
+/// 监听器运行时配置响应
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListenerConfigResponse {
+    pub program_ids: Vec<String>,
+    pub disabled_event_types: Vec<String>,
+    pub backfill_events: Vec<RuntimeBackfillEventConfig>,
+    /// 配置版本，监听器检测到版本变化后应用
+    pub version: u64,
+    pub updated_by: Option<String>,
+    pub updated_at: i64,
+}
+
+impl From<ListenerRuntimeConfig> for ListenerConfigResponse {
+    fn from(config: ListenerRuntimeConfig) -> Self {
+        Self {
+            program_ids: config.program_ids,
+            disabled_event_types: config.disabled_event_types,
+            backfill_events: config.backfill_events,
+            version: config.version,
+            updated_by: config.updated_by,
+            updated_at: config.updated_at.timestamp(),
+        }
+    }
+}
+
+pub struct ListenerConfigController;
+
+impl ListenerConfigController {
+    pub fn routes() -> Router {
+        Router::new()
+            .route("/", get(get_listener_config).put(update_listener_config))
+            .layer(middleware::from_fn(require_admin))
+    }
+}
+
+/// 管理员功能：查询监听器运行时配置
+///
+/// 未配置时返回空，监听器使用启动配置文件中的设置
+#[utoipa::path(
+    get,
+    path = "/api/v1/solana/events/listener-config",
+    responses(
+        (status = 200, description = "查询成功", body = ApiResponse<Option<ListenerConfigResponse>>),
+        (status = 403, description = "权限不足"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "管理员功能",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn get_listener_config(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+) -> AppResult<Json<ApiResponse<Option<ListenerConfigResponse>>>> {
+    info!("🔍 管理员查询监听器运行时配置 (操作员: {})", user.user_id);
+
+    let service = ListenerConfigService::new(services.database.clone());
+    let config = service.get().await?;
+
+    Ok(Json(ApiResponse::success(config.map(Into::into))))
+}
+
+/// 管理员功能：更新监听器运行时配置
+///
+/// 整体替换订阅程序列表、禁用的事件类型和回填事件配置，监听器在下一个检查周期内应用，无需重启
+#[utoipa::path(
+    put,
+    path = "/api/v1/solana/events/listener-config",
+    request_body = UpdateListenerConfigRequest,
+    responses(
+        (status = 200, description = "更新成功", body = ApiResponse<ListenerConfigResponse>),
+        (status = 400, description = "配置无效"),
+        (status = 403, description = "权限不足"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "管理员功能",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn update_listener_config(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<UpdateListenerConfigRequest>,
+) -> AppResult<Json<ApiResponse<ListenerConfigResponse>>> {
+    warn!(
+        "🔧 管理员更新监听器运行时配置: {}个程序, {}个禁用事件类型, {}个回填事件 (操作员: {})",
+        request.program_ids.len(),
+        request.disabled_event_types.len(),
+        request.backfill_events.len(),
+        user.user_id
+    );
+
+    validate_runtime_config(&request.program_ids, &request.backfill_events).map_err(utils::AppError::BadRequest)?;
+
+    let service = ListenerConfigService::new(services.database.clone());
+    let config = service
+        .update(
+            request.program_ids,
+            request.disabled_event_types,
+            request.backfill_events,
+            &user.user_id,
+        )
+        .await?;
+
+    Ok(Json(ApiResponse::success(config.into())))
+}
@@ -1,37 +1,47 @@
+pub mod candle_controller;
 pub mod clmm_config_controller;
 pub mod clmm_pool_create;
 pub mod clmm_pool_query;
+pub mod dead_letter_controller;
 pub mod deposit_event_controller;
 pub mod event_controller;
 pub mod launch_event_controller;
 pub mod launch_migration_controller;
 pub mod liquidity_line_controller;
+pub mod listener_config_controller;
 pub mod nft_controller;
 pub mod position_controller;
+pub mod protocol_metrics_controller;
 pub mod referral_controller;
 pub mod static_config_controller;
 pub mod swap_controller;
 pub mod swap_v2_controller;
 pub mod swap_v3_controller;
 pub mod token_controller;
+pub mod webhook_controller;
 pub mod refer_controller;
 pub mod reward_controller;
 
+pub use candle_controller::*;
 pub use clmm_config_controller::*;
 pub use clmm_pool_create::*;
 pub use clmm_pool_query::*;
+pub use dead_letter_controller::*;
 pub use deposit_event_controller::*;
 pub use event_controller::*;
 pub use launch_event_controller::*;
 pub use launch_migration_controller::*;
 pub use liquidity_line_controller::*;
+pub use listener_config_controller::*;
 pub use nft_controller::*;
 pub use position_controller::*;
+pub use protocol_metrics_controller::*;
 pub use referral_controller::*;
 pub use static_config_controller::*;
 pub use swap_controller::*;
 pub use swap_v2_controller::*;
 pub use swap_v3_controller::*;
 pub use token_controller::*;
+pub use webhook_controller::*;
 pub use refer_controller::*;
 pub use reward_controller::*;
\ No newline at end of file
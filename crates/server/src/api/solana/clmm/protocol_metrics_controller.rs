@@ -0,0 +1,138 @@
+use crate::auth::{require_admin, AuthUser};
+use crate::dtos::solana::clmm::analytics::protocol_metrics::{
+    ProtocolMetricsQuery, ProtocolMetricsRebuildRequest, ProtocolMetricsRebuildResponse, ProtocolMetricsResponse,
+};
+use crate::dtos::solana::common::ApiResponse;
+use crate::services::solana::clmm::protocol_metrics::ProtocolMetricsService;
+use crate::services::Services;
+use axum::{
+    extract::{Extension, Query},
+    middleware,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use tracing::info;
+use utils::AppResult;
+use validator::Validate;
+
+pub struct ProtocolMetricsController;
+
+impl ProtocolMetricsController {
+    pub fn routes() -> Router {
+        Router::new()
+            .route("/daily", get(get_protocol_daily_metrics))
+            .merge(Self::admin_routes())
+    }
+
+    /// 指标维护路由（需要管理员权限）
+    fn admin_routes() -> Router {
+        Router::new()
+            .route("/rebuild", post(rebuild_protocol_metrics))
+            .layer(middleware::from_fn(require_admin))
+    }
+}
+
+/// 获取协议每日指标
+///
+/// 返回日期范围内按UTC自然日聚合的协议指标时间序列：TVL、交易量、手续费、成交笔数、活跃交易者、
+/// 新建池子、新建仓位、代币发射和推荐领取，每天附带CLMM/CPMM分项。
+/// 快照由后台任务定期生成，尚未生成快照的日期不返回。
+///
+/// # 响应示例
+///
+/// ```json
+/// {
+///   "id": "5d3b7c1e-8a3f-4d2b-9a61-2f0e4c8b7a10",
+///   "success": true,
+///   "data": {
+///     "start": "2024-01-01",
+///     "end": "2024-01-02",
+///     "summary": {
+///       "tvl": 1250000.0,
+///       "volume": 830000.0,
+///       "fees": 2075.0,
+///       "trade_count": 5120,
+///       "new_pools": 7,
+///       "new_positions": 96,
+///       "launches": 3,
+///       "referral_claims": 41
+///     },
+///     "series": [
+///       {
+///         "date": "2024-01-01",
+///         "time": 1704067200,
+///         "tvl": 1200000.0,
+///         "volume": 400000.0,
+///         "fees": 1000.0,
+///         "trade_count": 2480,
+///         "active_traders": 615,
+///         "new_pools": 3,
+///         "new_positions": 40,
+///         "launches": 1,
+///         "referral_claims": 20,
+///         "clmm": { "tvl": 900000.0, "volume": 300000.0, "fees": 750.0, "trade_count": 1800, "active_traders": 480, "new_pools": 2, "new_positions": 31 },
+///         "cpmm": { "tvl": 300000.0, "volume": 100000.0, "fees": 250.0, "trade_count": 680, "active_traders": 190, "new_pools": 1, "new_positions": 9 }
+///       }
+///     ]
+///   }
+/// }
+/// ```
+#[utoipa::path(
+    get,
+    path = "/api/v1/solana/main/metrics/daily",
+    params(ProtocolMetricsQuery),
+    responses(
+        (status = 200, description = "查询成功", body = ApiResponse<ProtocolMetricsResponse>),
+        (status = 400, description = "请求参数错误"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "系统配置"
+)]
+pub async fn get_protocol_daily_metrics(
+    Extension(services): Extension<Services>,
+    Query(params): Query<ProtocolMetricsQuery>,
+) -> AppResult<Json<ApiResponse<ProtocolMetricsResponse>>> {
+    info!("📊 查询协议每日指标: start={:?}, end={:?}", params.start, params.end);
+
+    let service = ProtocolMetricsService::new(services.database.clone(), services.price.clone());
+    let response = service.get_daily_metrics(&params).await?;
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+/// 管理员功能：重建协议每日指标
+///
+/// 按事件集合重新聚合指定日期范围的指标，用于补录历史数据或修复快照。
+/// TVL无法回溯，历史日期保留已有快照中的TVL，今天的TVL取池子当前值
+#[utoipa::path(
+    post,
+    path = "/api/v1/solana/main/metrics/rebuild",
+    request_body = ProtocolMetricsRebuildRequest,
+    responses(
+        (status = 200, description = "重建成功", body = ApiResponse<ProtocolMetricsRebuildResponse>),
+        (status = 400, description = "请求参数错误"),
+        (status = 403, description = "权限不足"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "管理员功能",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn rebuild_protocol_metrics(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<ProtocolMetricsRebuildRequest>,
+) -> AppResult<Json<ApiResponse<ProtocolMetricsRebuildResponse>>> {
+    info!(
+        "🔄 管理员重建协议每日指标: {} ~ {} (操作员: {})",
+        request.start, request.end, user.user_id
+    );
+    request.validate()?;
+
+    let service = ProtocolMetricsService::new(services.database.clone(), services.price.clone());
+    let response = service.rebuild(&request).await?;
+
+    Ok(Json(ApiResponse::success(response)))
+}
@@ -790,6 +790,9 @@ pub async fn delete_token(
 ///
 /// 根据提供的代币mint地址列表查询价格
 ///
+/// 价格由本平台池子推导：稳定币(USDC/USDT)固定为1，其他代币沿流动性最深的池子路径定价，
+/// 流动性低于阈值的池子不参与定价。无法定价的代币返回价格"0"，置信度和来源为none
+///
 /// # 查询参数
 ///
 /// - mints: 代币mint地址列表，用逗号分隔
@@ -804,7 +807,11 @@ pub async fn delete_token(
 ///     "data": [
 ///       {
 ///         "mint": "So11111111111111111111111111111111111111112",
-///         "price": "0"
+///         "price": "150.23",
+///         "confidence": "high",
+///         "source": "pool",
+///         "liquidity": 1502300.0,
+///         "route": ["8sLbNZoA1cfnvMJLPfp98ZLAnFSYCFApfJKMbiXNLwxj"]
 ///       }
 ///     ]
 ///   }
@@ -817,26 +824,55 @@ pub async fn delete_token(
         ("mints" = String, Query, description = "代币mint地址列表，用逗号分隔")
     ),
     responses(
-        (status = 200, description = "代币价格查询成功", body = ApiResponse<MintPriceResponse>)
+        (status = 200, description = "代币价格查询成功", body = ApiResponse<MintPriceResponse>),
+        (status = 500, description = "服务器内部错误")
     ),
     tag = "代币信息"
 )]
-pub async fn get_mint_price(Query(params): Query<MintPriceQuery>) -> Json<ApiResponse<MintPriceResponse>> {
+pub async fn get_mint_price(
+    Extension(services): Extension<Services>,
+    Query(params): Query<MintPriceQuery>,
+) -> AppResult<Json<ApiResponse<MintPriceResponse>>> {
     info!("💰 获取代币价格，mints: {}", params.mints);
 
-    let mint_addresses: Vec<&str> = params.mints.split(',').collect();
+    let mint_addresses: Vec<String> = params
+        .mints
+        .split(',')
+        .map(|mint| mint.trim().to_string())
+        .filter(|mint| !mint.is_empty())
+        .collect();
 
-    let mut price_data = Vec::new();
-    for mint in mint_addresses {
-        price_data.push(PriceData {
-            mint: mint.to_string(),
-            price: "0".to_string(), // 按照文档要求，全部返回0
-        });
-    }
+    let prices = services.price.get_prices(&mint_addresses).await?;
+
+    let price_data = mint_addresses
+        .into_iter()
+        .map(|mint| match prices.get(&mint) {
+            Some(price) => PriceData {
+                price: price.price_usd.to_string(),
+                confidence: price.confidence.as_str().to_string(),
+                source: price.source.as_str().to_string(),
+                liquidity: if price.liquidity_usd.is_finite() {
+                    price.liquidity_usd
+                } else {
+                    0.0
+                },
+                route: price.route.clone(),
+                mint,
+            },
+            None => PriceData {
+                mint,
+                price: "0".to_string(),
+                confidence: "none".to_string(),
+                source: "none".to_string(),
+                liquidity: 0.0,
+                route: Vec::new(),
+            },
+        })
+        .collect();
 
     let response = MintPriceResponse { data: price_data };
 
-    Json(ApiResponse::success(response))
+    Ok(Json(ApiResponse::success(response)))
 }
 
 /// 代币 ID 查询参数
@@ -0,0 +1,510 @@
+use crate::auth::AuthUser;
+use crate::dtos::solana::clmm::events::reward_distribution::EventPaginatedResponse;
+use crate::dtos::solana::common::{default_page, default_page_size, ApiResponse};
+use crate::services::solana::clmm::event::WebhookService;
+use crate::services::Services;
+use axum::{
+    extract::{Extension, Path, Query},
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use database::events::webhook_delivery::{WebhookDelivery, WebhookDeliveryStatus};
+use database::events::webhook_subscription::{resolve_webhook_url, WebhookFilter, WebhookSubscription};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use utils::AppResult;
+use utoipa::{IntoParams, ToSchema};
+
+/// 创建/更新webhook订阅请求
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WebhookSubscriptionRequest {
+    /// 接收推送的URL（http://或https://，不能指向本机、内网或保留地址）
+    pub url: String,
+    /// 事件过滤条件，未设置时推送全部事件
+    #[serde(default)]
+    pub filter: WebhookFilter,
+    /// 是否启用（仅更新时生效，默认启用）
+    #[serde(default = "default_active")]
+    pub active: bool,
+    /// 备注
+    pub description: Option<String>,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+/// webhook订阅列表查询参数
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct WebhookSubscriptionListQuery {
+    /// 所有者过滤（仅管理员可用，普通用户只能查看自己的订阅）
+    pub owner: Option<String>,
+}
+
+/// webhook投递记录查询参数
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct WebhookDeliveryListQuery {
+    /// 页码（从1开始）
+    #[serde(default = "default_page")]
+    pub page: u64,
+    /// 每页条数（最大100）
+    #[serde(default = "default_page_size")]
+    pub page_size: u64,
+    /// 订阅ID过滤
+    pub subscription_id: Option<String>,
+    /// 状态过滤（pending/delivered/failed）
+    pub status: Option<String>,
+}
+
+/// webhook投递记录批量操作请求
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WebhookDeliveryIdsRequest {
+    /// 投递记录ID列表（最多100个）
+    pub ids: Vec<String>,
+}
+
+/// webhook投递记录批量操作结果
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDeliveryOperationResponse {
+    /// 请求操作的记录数
+    pub requested: u64,
+    /// 实际更新的记录数（非失败状态或不属于当前用户的记录会被跳过）
+    pub updated: u64,
+}
+
+/// webhook订阅响应（不包含签名密钥）
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookSubscriptionResponse {
+    pub id: String,
+    pub owner: String,
+    pub url: String,
+    pub filter: WebhookFilter,
+    pub active: bool,
+    pub description: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl From<WebhookSubscription> for WebhookSubscriptionResponse {
+    fn from(subscription: WebhookSubscription) -> Self {
+        Self {
+            id: subscription.id.map(|id| id.to_hex()).unwrap_or_default(),
+            owner: subscription.owner,
+            url: subscription.url,
+            filter: subscription.filter,
+            active: subscription.active,
+            description: subscription.description,
+            created_at: subscription.created_at.timestamp(),
+            updated_at: subscription.updated_at.timestamp(),
+        }
+    }
+}
+
+/// webhook签名密钥响应（仅在创建订阅和更换密钥时返回）
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookSecretResponse {
+    pub subscription: WebhookSubscriptionResponse,
+    /// HMAC-SHA256签名密钥，请妥善保存
+    pub secret: String,
+}
+
+/// webhook投递记录响应
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDeliveryResponse {
+    pub id: String,
+    pub subscription_id: String,
+    pub owner: String,
+    pub event_type: String,
+    pub signature: String,
+    pub slot: u64,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    pub last_status_code: Option<u16>,
+    pub last_error: Option<String>,
+    /// 推送的事件负载
+    pub payload: serde_json::Value,
+    pub next_attempt_at: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub delivered_at: Option<i64>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(delivery: WebhookDelivery) -> Self {
+        // 负载无法解析时按原始字符串返回，便于排查
+        let payload = serde_json::from_str(&delivery.payload).unwrap_or(serde_json::Value::String(delivery.payload));
+        Self {
+            id: delivery.id.map(|id| id.to_hex()).unwrap_or_default(),
+            subscription_id: delivery.subscription_id.to_hex(),
+            owner: delivery.owner,
+            event_type: delivery.event_type,
+            signature: delivery.signature,
+            slot: delivery.slot,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            last_status_code: delivery.last_status_code,
+            last_error: delivery.last_error,
+            payload,
+            next_attempt_at: delivery.next_attempt_at.timestamp(),
+            created_at: delivery.created_at.timestamp(),
+            updated_at: delivery.updated_at.timestamp(),
+            delivered_at: delivery.delivered_at.map(|t| t.timestamp()),
+        }
+    }
+}
+
+/// 普通用户只能访问自己的订阅，管理员可访问全部
+fn owner_scope(user: &AuthUser) -> Option<&str> {
+    if user.is_admin() {
+        None
+    } else {
+        Some(user.user_id.as_str())
+    }
+}
+
+pub struct WebhookController;
+
+impl WebhookController {
+    pub fn routes() -> Router {
+        Router::new()
+            .route("/", get(list_webhooks).post(create_webhook))
+            .route("/deliveries", get(list_webhook_deliveries))
+            .route("/deliveries/redeliver", post(redeliver_webhook_deliveries))
+            .route("/:id", get(get_webhook).put(update_webhook).delete(delete_webhook))
+            .route("/:id/rotate-secret", post(rotate_webhook_secret))
+    }
+}
+
+/// 注册webhook订阅
+///
+/// 订阅的事件被监听器持久化后推送到指定URL，请求体使用返回的密钥做HMAC-SHA256签名，
+/// 签名位于X-Coinfair-Signature请求头，签名内容为"<X-Coinfair-Timestamp>.<请求体>"
+#[utoipa::path(
+    post,
+    path = "/api/v1/solana/webhooks",
+    request_body = WebhookSubscriptionRequest,
+    responses(
+        (status = 200, description = "注册成功", body = ApiResponse<WebhookSecretResponse>),
+        (status = 400, description = "请求参数错误"),
+        (status = 401, description = "未认证"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "Webhook订阅",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn create_webhook(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<WebhookSubscriptionRequest>,
+) -> AppResult<Json<ApiResponse<WebhookSecretResponse>>> {
+    info!("🔔 用户{}注册webhook订阅: {}", user.user_id, request.url);
+
+    resolve_webhook_url(&request.url)
+        .await
+        .map_err(utils::AppError::BadRequest)?;
+
+    let service = WebhookService::new(services.database.clone());
+    let mut subscription = service
+        .create(&user.user_id, request.url, request.filter, request.description)
+        .await?;
+    let secret = std::mem::take(&mut subscription.secret);
+
+    Ok(Json(ApiResponse::success(WebhookSecretResponse {
+        subscription: subscription.into(),
+        secret,
+    })))
+}
+
+/// 查询webhook订阅列表
+///
+/// 普通用户返回自己的订阅，管理员返回全部订阅（可按所有者过滤）
+#[utoipa::path(
+    get,
+    path = "/api/v1/solana/webhooks",
+    params(WebhookSubscriptionListQuery),
+    responses(
+        (status = 200, description = "查询成功", body = ApiResponse<Vec<WebhookSubscriptionResponse>>),
+        (status = 401, description = "未认证"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "Webhook订阅",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn list_webhooks(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Query(params): Query<WebhookSubscriptionListQuery>,
+) -> AppResult<Json<ApiResponse<Vec<WebhookSubscriptionResponse>>>> {
+    info!("🔍 用户{}查询webhook订阅列表", user.user_id);
+
+    let owner = owner_scope(&user).or(params.owner.as_deref());
+    let service = WebhookService::new(services.database.clone());
+    let subscriptions = service.list(owner).await?;
+
+    Ok(Json(ApiResponse::success(
+        subscriptions.into_iter().map(Into::into).collect(),
+    )))
+}
+
+/// 查询单个webhook订阅
+#[utoipa::path(
+    get,
+    path = "/api/v1/solana/webhooks/{id}",
+    params(
+        ("id" = String, Path, description = "订阅ID")
+    ),
+    responses(
+        (status = 200, description = "查询成功", body = ApiResponse<WebhookSubscriptionResponse>),
+        (status = 400, description = "ID格式错误"),
+        (status = 404, description = "订阅不存在"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "Webhook订阅",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn get_webhook(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<Json<ApiResponse<WebhookSubscriptionResponse>>> {
+    info!("🔍 用户{}查询webhook订阅: {}", user.user_id, id);
+
+    let id = WebhookService::parse_id(&id).map_err(|e| utils::AppError::BadRequest(e.to_string()))?;
+    let service = WebhookService::new(services.database.clone());
+    let subscription = service
+        .get(&id, owner_scope(&user))
+        .await?
+        .ok_or_else(|| utils::AppError::NotFound("webhook订阅不存在".to_string()))?;
+
+    Ok(Json(ApiResponse::success(subscription.into())))
+}
+
+/// 更新webhook订阅
+///
+/// 整体替换推送地址、过滤条件、启用状态和备注，监听器在订阅缓存刷新后生效
+#[utoipa::path(
+    put,
+    path = "/api/v1/solana/webhooks/{id}",
+    params(
+        ("id" = String, Path, description = "订阅ID")
+    ),
+    request_body = WebhookSubscriptionRequest,
+    responses(
+        (status = 200, description = "更新成功", body = ApiResponse<WebhookSubscriptionResponse>),
+        (status = 400, description = "请求参数错误"),
+        (status = 404, description = "订阅不存在"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "Webhook订阅",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn update_webhook(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(request): Json<WebhookSubscriptionRequest>,
+) -> AppResult<Json<ApiResponse<WebhookSubscriptionResponse>>> {
+    info!("🔧 用户{}更新webhook订阅: {}", user.user_id, id);
+
+    let id = WebhookService::parse_id(&id).map_err(|e| utils::AppError::BadRequest(e.to_string()))?;
+    resolve_webhook_url(&request.url)
+        .await
+        .map_err(utils::AppError::BadRequest)?;
+
+    let service = WebhookService::new(services.database.clone());
+    let subscription = service
+        .update(
+            &id,
+            owner_scope(&user),
+            request.url,
+            request.filter,
+            request.active,
+            request.description,
+        )
+        .await?
+        .ok_or_else(|| utils::AppError::NotFound("webhook订阅不存在".to_string()))?;
+
+    Ok(Json(ApiResponse::success(subscription.into())))
+}
+
+/// 删除webhook订阅
+///
+/// 同时删除订阅的投递记录
+#[utoipa::path(
+    delete,
+    path = "/api/v1/solana/webhooks/{id}",
+    params(
+        ("id" = String, Path, description = "订阅ID")
+    ),
+    responses(
+        (status = 200, description = "删除成功", body = ApiResponse<bool>),
+        (status = 400, description = "ID格式错误"),
+        (status = 404, description = "订阅不存在"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "Webhook订阅",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn delete_webhook(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<Json<ApiResponse<bool>>> {
+    warn!("🗑️ 用户{}删除webhook订阅: {}", user.user_id, id);
+
+    let id = WebhookService::parse_id(&id).map_err(|e| utils::AppError::BadRequest(e.to_string()))?;
+    let service = WebhookService::new(services.database.clone());
+    if !service.delete(&id, owner_scope(&user)).await? {
+        return Err(utils::AppError::NotFound("webhook订阅不存在".to_string()));
+    }
+
+    Ok(Json(ApiResponse::success(true)))
+}
+
+/// 更换webhook签名密钥
+///
+/// 旧密钥立即失效，尚未投递的记录将使用新密钥签名
+#[utoipa::path(
+    post,
+    path = "/api/v1/solana/webhooks/{id}/rotate-secret",
+    params(
+        ("id" = String, Path, description = "订阅ID")
+    ),
+    responses(
+        (status = 200, description = "更换成功", body = ApiResponse<WebhookSecretResponse>),
+        (status = 400, description = "ID格式错误"),
+        (status = 404, description = "订阅不存在"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "Webhook订阅",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn rotate_webhook_secret(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<Json<ApiResponse<WebhookSecretResponse>>> {
+    warn!("🔑 用户{}更换webhook订阅密钥: {}", user.user_id, id);
+
+    let id = WebhookService::parse_id(&id).map_err(|e| utils::AppError::BadRequest(e.to_string()))?;
+    let owner = owner_scope(&user);
+    let service = WebhookService::new(services.database.clone());
+    let secret = service
+        .rotate_secret(&id, owner)
+        .await?
+        .ok_or_else(|| utils::AppError::NotFound("webhook订阅不存在".to_string()))?;
+    let subscription = service
+        .get(&id, owner)
+        .await?
+        .ok_or_else(|| utils::AppError::NotFound("webhook订阅不存在".to_string()))?;
+
+    Ok(Json(ApiResponse::success(WebhookSecretResponse {
+        subscription: subscription.into(),
+        secret,
+    })))
+}
+
+/// 查询webhook投递记录
+///
+/// 按创建时间倒序返回，普通用户只能查看自己订阅的投递记录
+#[utoipa::path(
+    get,
+    path = "/api/v1/solana/webhooks/deliveries",
+    params(WebhookDeliveryListQuery),
+    responses(
+        (status = 200, description = "查询成功", body = ApiResponse<EventPaginatedResponse<WebhookDeliveryResponse>>),
+        (status = 400, description = "请求参数错误"),
+        (status = 401, description = "未认证"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "Webhook订阅",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn list_webhook_deliveries(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Query(params): Query<WebhookDeliveryListQuery>,
+) -> AppResult<Json<ApiResponse<EventPaginatedResponse<WebhookDeliveryResponse>>>> {
+    info!("🔍 用户{}查询webhook投递记录", user.user_id);
+
+    if let Some(status) = &params.status {
+        status
+            .parse::<WebhookDeliveryStatus>()
+            .map_err(utils::AppError::BadRequest)?;
+    }
+    let subscription_id = params
+        .subscription_id
+        .as_deref()
+        .map(WebhookService::parse_id)
+        .transpose()
+        .map_err(|e| utils::AppError::BadRequest(e.to_string()))?;
+
+    let service = WebhookService::new(services.database.clone());
+    let result = service
+        .list_deliveries(
+            owner_scope(&user),
+            subscription_id,
+            params.status,
+            params.page,
+            params.page_size,
+        )
+        .await?;
+
+    Ok(Json(ApiResponse::success(EventPaginatedResponse {
+        items: result.items.into_iter().map(Into::into).collect(),
+        total: result.total,
+        page: result.page,
+        page_size: result.page_size,
+        total_pages: result.total_pages,
+    })))
+}
+
+/// 重新投递失败的webhook记录
+///
+/// 仅处理已放弃投递的记录，由监听器投递任务在下一轮立即发送
+#[utoipa::path(
+    post,
+    path = "/api/v1/solana/webhooks/deliveries/redeliver",
+    request_body = WebhookDeliveryIdsRequest,
+    responses(
+        (status = 200, description = "重新投递请求已提交", body = ApiResponse<WebhookDeliveryOperationResponse>),
+        (status = 400, description = "ID格式错误"),
+        (status = 401, description = "未认证"),
+        (status = 500, description = "服务器内部错误")
+    ),
+    tag = "Webhook订阅",
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn redeliver_webhook_deliveries(
+    Extension(services): Extension<Services>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<WebhookDeliveryIdsRequest>,
+) -> AppResult<Json<ApiResponse<WebhookDeliveryOperationResponse>>> {
+    info!("🔁 用户{}请求重新投递{}条webhook记录", user.user_id, request.ids.len());
+
+    let ids = WebhookService::parse_ids(&request.ids).map_err(|e| utils::AppError::BadRequest(e.to_string()))?;
+    let service = WebhookService::new(services.database.clone());
+    let updated = service.redeliver(&ids, owner_scope(&user)).await?;
+
+    Ok(Json(ApiResponse::success(WebhookDeliveryOperationResponse {
+        requested: request.ids.len() as u64,
+        updated,
+    })))
+}
@@ -5,10 +5,11 @@ pub mod statics;
 use crate::{api::solana::cpmm::NftClaimStatsController, auth::SolanaMiddlewareBuilder};
 use axum::{middleware, Extension, Router};
 use clmm::{
-    clmm_config_controller, clmm_pool_create, clmm_pool_query, deposit_event_controller, event_controller,
-    launch_event_controller, launch_migration_controller, liquidity_line_controller, nft_controller,
-    position_controller, referral_controller, static_config_controller, swap_controller, swap_v2_controller,
-    swap_v3_controller, token_controller,
+    candle_controller, clmm_config_controller, clmm_pool_create, clmm_pool_query, dead_letter_controller,
+    deposit_event_controller, event_controller, launch_event_controller, launch_migration_controller,
+    liquidity_line_controller, listener_config_controller, nft_controller, position_controller,
+    protocol_metrics_controller, referral_controller, static_config_controller, swap_controller, swap_v2_controller,
+    swap_v3_controller, token_controller, webhook_controller,
 };
 use cpmm::{
     cpmm_config_controller, cpmm_swap_controller, deposit_controller, init_pool_event_controller,
@@ -38,6 +39,8 @@ impl SolanaController {
             .nest("/pool", Self::pool_management_routes())
             // 流动性管理路由 - 存款、提款等操作
             .nest("/liquidity", Self::liquidity_management_routes())
+            // webhook订阅路由 - 使用强制权限检查
+            .nest("/webhooks", Self::webhook_routes())
     }
 
     /// 公开信息路由 - 版本、配置等基础信息
@@ -53,6 +56,11 @@ impl SolanaController {
             .route("/info", axum::routing::get(static_config_controller::get_info))
             .nest("/clmm-config", clmm_config_controller::ClmmConfigController::routes())
             .nest("/cpmm-config", cpmm_config_controller::CpmmConfigController::routes())
+            // 协议每日指标
+            .nest(
+                "/metrics",
+                protocol_metrics_controller::ProtocolMetricsController::routes(),
+            )
             .layer(middleware::from_fn(Self::apply_solana_optional_auth))
     }
 
@@ -82,6 +90,8 @@ impl SolanaController {
             )
             // pools/line路由 - 流动性线图
             .nest("/line", liquidity_line_controller::LiquidityLineController::routes())
+            // pools/{id}/candles路由 - K线
+            .merge(candle_controller::CandleController::routes())
             .layer(middleware::from_fn(Self::apply_solana_optional_auth))
     }
 
@@ -102,6 +112,13 @@ impl SolanaController {
             .nest("/cpmm", points_controller::points_routes())
             // NFT统计路由
             .nest("/cpmm/nft", NftClaimStatsController::routes())
+            // 死信事件管理路由（仅管理员）
+            .nest("/dead-letters", dead_letter_controller::DeadLetterController::routes())
+            // 监听器运行时配置路由（仅管理员）
+            .nest(
+                "/listener-config",
+                listener_config_controller::ListenerConfigController::routes(),
+            )
             //应用可选权限检查中间件
             .layer(middleware::from_fn(Self::apply_solana_optional_auth))
     }
@@ -157,6 +174,11 @@ impl SolanaController {
             .layer(middleware::from_fn(Self::apply_solana_auth))
     }
 
+    /// webhook订阅路由 - 合作方管理自己的事件推送订阅，管理员可管理全部订阅
+    fn webhook_routes() -> Router {
+        webhook_controller::WebhookController::routes().layer(middleware::from_fn(Self::apply_solana_auth))
+    }
+
     /// 应用Solana权限检查中间件（强制认证）
     async fn apply_solana_auth(
         Extension(solana_middleware): Extension<Arc<SolanaMiddlewareBuilder>>,
@@ -1,7 +1,16 @@
-use crate::{router::AppRouter, services::Services};
+use crate::{
+    router::AppRouter,
+    services::{
+        solana::clmm::launch_event::{LaunchMigrationWorker, LaunchMigrationWorkerConfig},
+        solana::clmm::pool_stats::{PoolStatsService, PoolStatsWorker, PoolStatsWorkerConfig},
+        solana::clmm::protocol_metrics::{ProtocolMetricsService, ProtocolMetricsWorker, ProtocolMetricsWorkerConfig},
+        Services,
+    },
+};
 use anyhow::Context;
 use axum::serve;
 use database::Database;
+use solana_client::rpc_client::RpcClient;
 use std::{net::SocketAddr, sync::Arc};
 use tokio::signal;
 use tracing::info;
@@ -24,6 +33,38 @@ impl ApplicationServer {
         // 构建一个内置了多种"集合"对应的底层数据库操作的Database
         let db = Database::new(config.clone()).await?;
         let services = Services::new(db);
+
+        // 启动Launch迁移任务（需显式启用，会使用服务端私钥发送交易）
+        let migration_config = LaunchMigrationWorkerConfig::from_env();
+        if migration_config.enabled {
+            let worker =
+                LaunchMigrationWorker::new(services.database.clone(), services.solana.clone(), migration_config);
+            tokio::spawn(async move { worker.start().await });
+        } else {
+            info!("⏸️ Launch迁移任务未启用 (LAUNCH_MIGRATION_WORKER_ENABLED)");
+        }
+
+        // 启动池子统计刷新任务（交易量、手续费、APR）
+        let pool_stats_config = PoolStatsWorkerConfig::from_env();
+        if pool_stats_config.enabled {
+            let rpc_client = Arc::new(RpcClient::new(config.rpc_url.clone()));
+            let service = PoolStatsService::new(services.database.clone(), rpc_client, services.price.clone());
+            let worker = PoolStatsWorker::new(service, pool_stats_config);
+            tokio::spawn(async move { worker.start().await });
+        } else {
+            info!("⏸️ 池子统计刷新任务未启用 (POOL_STATS_WORKER_ENABLED)");
+        }
+
+        // 启动协议指标快照任务（每日TVL、交易量、活跃交易者等）
+        let protocol_metrics_config = ProtocolMetricsWorkerConfig::from_env();
+        if protocol_metrics_config.enabled {
+            let service = ProtocolMetricsService::new(services.database.clone(), services.price.clone());
+            let worker = ProtocolMetricsWorker::new(service, protocol_metrics_config);
+            tokio::spawn(async move { worker.start().await });
+        } else {
+            info!("⏸️ 协议指标快照任务未启用 (PROTOCOL_METRICS_WORKER_ENABLED)");
+        }
+
         let router = AppRouter::new(services);
 
         info!("🟢 Server: CoinFair-Solana-Backend Has Launched On {local_addr} 🚀");
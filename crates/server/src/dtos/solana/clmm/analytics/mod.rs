@@ -0,0 +1 @@
+pub(crate) mod protocol_metrics;
@@ -0,0 +1,157 @@
+use database::analytics::protocol_metrics::{ProgramDailyMetrics, ProtocolDailyMetrics};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+// ========================= 协议指标相关DTO =========================
+
+/// 协议每日指标查询参数
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
+pub struct ProtocolMetricsQuery {
+    /// 起始日期（UTC，YYYY-MM-DD，可选，包含）；默认截止日期前29天
+    pub start: Option<String>,
+
+    /// 截止日期（UTC，YYYY-MM-DD，可选，包含）；默认今天
+    pub end: Option<String>,
+}
+
+/// 协议每日指标重建请求
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ProtocolMetricsRebuildRequest {
+    /// 起始日期（UTC，YYYY-MM-DD，包含）
+    #[validate(length(equal = 10))]
+    pub start: String,
+
+    /// 截止日期（UTC，YYYY-MM-DD，包含）
+    #[validate(length(equal = 10))]
+    pub end: String,
+}
+
+/// 协议每日指标重建结果
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProtocolMetricsRebuildResponse {
+    /// 起始日期
+    pub start: String,
+    /// 截止日期
+    pub end: String,
+    /// 重建的天数
+    pub days: u64,
+}
+
+/// 单个程序(CLMM/CPMM)的每日指标
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct ProgramMetricsData {
+    /// 锁仓价值（USD）
+    pub tvl: f64,
+    /// 交易量（USD）
+    pub volume: f64,
+    /// 手续费（USD）
+    pub fees: f64,
+    /// 成交笔数
+    pub trade_count: u64,
+    /// 活跃交易者数量
+    pub active_traders: u64,
+    /// 新建池子数量
+    pub new_pools: u64,
+    /// 新建仓位数量
+    pub new_positions: u64,
+}
+
+impl From<&ProgramDailyMetrics> for ProgramMetricsData {
+    fn from(metrics: &ProgramDailyMetrics) -> Self {
+        Self {
+            tvl: metrics.tvl,
+            volume: metrics.volume,
+            fees: metrics.fees,
+            trade_count: metrics.trade_count,
+            active_traders: metrics.active_traders,
+            new_pools: metrics.new_pools,
+            new_positions: metrics.new_positions,
+        }
+    }
+}
+
+/// 协议单日指标
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct ProtocolDailyMetricsData {
+    /// 日期（UTC，YYYY-MM-DD）
+    pub date: String,
+    /// 当天起始时间（Unix秒）
+    pub time: i64,
+    /// 锁仓价值合计（USD）
+    pub tvl: f64,
+    /// 交易量合计（USD）
+    pub volume: f64,
+    /// 手续费合计（USD）
+    pub fees: f64,
+    /// 成交笔数合计
+    pub trade_count: u64,
+    /// 活跃交易者数量（CLMM和CPMM去重）
+    pub active_traders: u64,
+    /// 新建池子数量
+    pub new_pools: u64,
+    /// 新建仓位数量
+    pub new_positions: u64,
+    /// 代币发射数量
+    pub launches: u64,
+    /// 推荐领取数量
+    pub referral_claims: u64,
+    /// CLMM指标
+    pub clmm: ProgramMetricsData,
+    /// CPMM指标
+    pub cpmm: ProgramMetricsData,
+}
+
+impl From<&ProtocolDailyMetrics> for ProtocolDailyMetricsData {
+    fn from(metrics: &ProtocolDailyMetrics) -> Self {
+        Self {
+            date: metrics.date.clone(),
+            time: metrics.day_start,
+            tvl: metrics.tvl,
+            volume: metrics.volume,
+            fees: metrics.fees,
+            trade_count: metrics.trade_count,
+            active_traders: metrics.active_traders,
+            new_pools: metrics.new_pools,
+            new_positions: metrics.new_positions,
+            launches: metrics.launches,
+            referral_claims: metrics.referral_claims,
+            clmm: ProgramMetricsData::from(&metrics.clmm),
+            cpmm: ProgramMetricsData::from(&metrics.cpmm),
+        }
+    }
+}
+
+/// 区间汇总（流量指标为区间之和，TVL为区间内最后一天的值）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct ProtocolMetricsSummary {
+    /// 最后一天的锁仓价值（USD）
+    pub tvl: f64,
+    /// 交易量（USD）
+    pub volume: f64,
+    /// 手续费（USD）
+    pub fees: f64,
+    /// 成交笔数
+    pub trade_count: u64,
+    /// 新建池子数量
+    pub new_pools: u64,
+    /// 新建仓位数量
+    pub new_positions: u64,
+    /// 代币发射数量
+    pub launches: u64,
+    /// 推荐领取数量
+    pub referral_claims: u64,
+}
+
+/// 协议每日指标响应
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProtocolMetricsResponse {
+    /// 起始日期
+    pub start: String,
+    /// 截止日期
+    pub end: String,
+    /// 区间汇总
+    pub summary: ProtocolMetricsSummary,
+    /// 每日指标（按日期升序，尚未生成快照的日期不返回）
+    pub series: Vec<ProtocolDailyMetricsData>,
+}
@@ -698,6 +698,7 @@ impl From<CreateDepositEventRequest> for database::events::event_model::DepositE
             actual_total_raised,
             signature: request.signature,
             slot: request.slot,
+            identity: database::events::event_identity::EventIdentity::default(),
             deposited_at: request.deposited_at,
             processed_at: now,
             updated_at: now,
@@ -43,6 +43,8 @@ pub struct LaunchEventResponse {
     pub migration_error: Option<String>,
     /// 迁移重试次数
     pub migration_retry_count: u32,
+    /// 下次自动重试时间（仅retrying状态有值）
+    pub migration_next_attempt_at: Option<i64>,
     /// 流动性总价值（USD）
     pub total_liquidity_usd: f64,
     /// 代币对类型
@@ -78,6 +80,13 @@ pub struct LaunchEventStatsResponse {
     pub retrying_count: u64,
 }
 
+/// 放弃Launch迁移请求DTO
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AbandonLaunchMigrationRequest {
+    /// 放弃原因
+    pub reason: Option<String>,
+}
+
 /// 数据转换：从LaunchEvent到LaunchEventResponse
 impl From<LaunchEvent> for LaunchEventResponse {
     fn from(event: LaunchEvent) -> Self {
@@ -101,6 +110,7 @@ impl From<LaunchEvent> for LaunchEventResponse {
             migration_completed_at: event.migration_completed_at,
             migration_error: event.migration_error,
             migration_retry_count: event.migration_retry_count,
+            migration_next_attempt_at: event.migration_next_attempt_at,
             total_liquidity_usd: event.total_liquidity_usd,
             pair_type: event.pair_type,
             price_range_width_percent: event.price_range_width_percent,
@@ -1,3 +1,4 @@
+pub(crate) mod analytics;
 pub(crate) mod events;
 pub(crate) mod launch;
 pub(crate) mod nft;
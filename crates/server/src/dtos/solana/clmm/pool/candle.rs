@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+// ========================= K线相关DTO =========================
+
+/// K线查询默认周期
+pub fn default_candle_interval() -> String {
+    "1h".to_string()
+}
+
+/// K线查询默认条数
+pub fn default_candle_limit() -> i64 {
+    300
+}
+
+/// 池子K线查询参数
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams, Validate)]
+pub struct PoolCandleQuery {
+    /// K线周期（1m/5m/15m/1h/4h/1d，默认1h）
+    #[serde(default = "default_candle_interval")]
+    pub interval: String,
+
+    /// 起始时间（Unix秒，可选）；未指定时返回截止时间之前最近的K线
+    pub from: Option<i64>,
+
+    /// 截止时间（Unix秒，可选，包含）
+    pub to: Option<i64>,
+
+    /// 最大返回条数（默认300，最大1000）
+    #[serde(default = "default_candle_limit")]
+    #[validate(range(min = 1, max = 1000))]
+    pub limit: i64,
+
+    /// 基础代币mint（可选）；价格以"每个基础代币值多少计价代币"表示，默认池子的token_0
+    pub base_mint: Option<String>,
+
+    /// 计价代币mint（可选），与base_mint二选一即可，同时指定时必须与base_mint组成该池子的代币对
+    pub quote_mint: Option<String>,
+}
+
+/// 单根K线（价格和成交量已按代币精度换算）
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct CandleData {
+    /// 开盘时间（Unix秒）
+    pub time: i64,
+    /// 开盘价
+    pub open: f64,
+    /// 最高价
+    pub high: f64,
+    /// 最低价
+    pub low: f64,
+    /// 收盘价
+    pub close: f64,
+    /// 基础代币成交量
+    pub base_volume: f64,
+    /// 计价代币成交量
+    pub quote_volume: f64,
+    /// 成交笔数
+    pub trade_count: u64,
+}
+
+/// 池子K线响应
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PoolCandlesResponse {
+    /// 池子地址
+    pub pool_id: String,
+    /// K线周期
+    pub interval: String,
+    /// 基础代币mint
+    pub base_mint: String,
+    /// 计价代币mint
+    pub quote_mint: String,
+    /// K线列表（按开盘时间升序）
+    pub candles: Vec<CandleData>,
+}
@@ -1,3 +1,4 @@
+pub(crate) mod candle;
 pub(crate) mod creation;
 pub(crate) mod info;
 pub(crate) mod liquidity_line;
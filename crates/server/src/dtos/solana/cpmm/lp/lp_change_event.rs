@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use database::cpmm::lp_change_event::model::LpChangeEvent;
+use database::events::event_identity::EventIdentity;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
@@ -175,6 +176,7 @@ impl CreateLpChangeEventRequest {
             token_1_decimals: self.token_1_decimals,
             signature: self.signature,
             slot: self.slot,
+            identity: EventIdentity::default(),
             block_time: self.block_time,
             created_at: Utc::now(), // 创建时间在Repository中设置
         }
@@ -384,6 +386,7 @@ mod tests {
             token_1_decimals: 9,
             signature: "test_signature".to_string(),
             slot: 12345,
+            identity: EventIdentity::default(),
             block_time: Some(1234567890),
             created_at: Utc::now(),
         }
@@ -5,6 +5,7 @@ use validator::Validate;
 
 use crate::dtos::solana::common::validate_pubkey;
 use database::cpmm::init_pool_event::model::InitPoolEvent;
+use database::events::event_identity::EventIdentity;
 
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct CreateInitPoolEventRequest {
@@ -165,6 +166,7 @@ impl From<CreateInitPoolEventRequest> for InitPoolEvent {
             token_1_decimals: request.token_1_decimals,
             signature: request.signature,
             slot: request.slot,
+            identity: EventIdentity::default(),
             block_time: request.block_time,
             created_at: Utc::now(),
         }
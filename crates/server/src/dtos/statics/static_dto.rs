@@ -298,8 +298,20 @@ pub struct PriceData {
     /// 代币mint地址
     pub mint: String,
 
-    /// 价格
+    /// USD价格，无法定价时为"0"
     pub price: String,
+
+    /// 置信度: high/medium/low，无法定价时为none
+    pub confidence: String,
+
+    /// 价格来源: stablecoin(稳定币锚定)/pool(池子路径推导)，无法定价时为none
+    pub source: String,
+
+    /// 定价路径上最浅一跳的流动性（USD）
+    pub liquidity: f64,
+
+    /// 定价经过的池子地址
+    pub route: Vec<String>,
 }
 
 /// 代币价格响应
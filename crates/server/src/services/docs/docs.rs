@@ -123,11 +123,19 @@ use utoipa::OpenApi;
         crate::api::solana::clmm::nft_controller::claim_nft_and_send_transaction,
         // Liquidity Line endpoints
         crate::api::solana::clmm::liquidity_line_controller::get_pool_liquidity_line,
+        // Candle endpoints
+        crate::api::solana::clmm::candle_controller::get_pool_candles,
+        crate::api::solana::clmm::candle_controller::rebuild_pool_candles,
+        // Protocol Metrics endpoints
+        crate::api::solana::clmm::protocol_metrics_controller::get_protocol_daily_metrics,
+        crate::api::solana::clmm::protocol_metrics_controller::rebuild_protocol_metrics,
         // Launch Event endpoints
         crate::api::solana::clmm::launch_event_controller::get_launch_event_by_signature,
         crate::api::solana::clmm::launch_event_controller::get_launch_event_stats,
         crate::api::solana::clmm::launch_event_controller::get_pending_migrations,
         crate::api::solana::clmm::launch_event_controller::get_failed_migrations_for_retry,
+        crate::api::solana::clmm::launch_event_controller::retry_migration,
+        crate::api::solana::clmm::launch_event_controller::abandon_migration,
         // Static Price endpoint
         crate::api::solana::statics::static_controller::get_tokens_by_ids,
     ),
@@ -291,9 +299,27 @@ use utoipa::OpenApi;
             crate::dtos::solana::clmm::pool::liquidity_line::PoolLiquidityLineData,
             crate::dtos::solana::clmm::pool::liquidity_line::LiquidityLinePoint,
             crate::dtos::solana::common::ApiResponse<crate::dtos::solana::clmm::pool::liquidity_line::PoolLiquidityLineData>,
+            // Candle DTOs
+            crate::dtos::solana::clmm::pool::candle::PoolCandleQuery,
+            crate::dtos::solana::clmm::pool::candle::CandleData,
+            crate::dtos::solana::clmm::pool::candle::PoolCandlesResponse,
+            crate::dtos::solana::common::ApiResponse<crate::dtos::solana::clmm::pool::candle::PoolCandlesResponse>,
+            database::analytics::candle::CandleRebuildSummary,
+            crate::dtos::solana::common::ApiResponse<database::analytics::candle::CandleRebuildSummary>,
+            // Protocol Metrics DTOs
+            crate::dtos::solana::clmm::analytics::protocol_metrics::ProtocolMetricsQuery,
+            crate::dtos::solana::clmm::analytics::protocol_metrics::ProtocolMetricsRebuildRequest,
+            crate::dtos::solana::clmm::analytics::protocol_metrics::ProtocolMetricsRebuildResponse,
+            crate::dtos::solana::clmm::analytics::protocol_metrics::ProgramMetricsData,
+            crate::dtos::solana::clmm::analytics::protocol_metrics::ProtocolDailyMetricsData,
+            crate::dtos::solana::clmm::analytics::protocol_metrics::ProtocolMetricsSummary,
+            crate::dtos::solana::clmm::analytics::protocol_metrics::ProtocolMetricsResponse,
+            crate::dtos::solana::common::ApiResponse<crate::dtos::solana::clmm::analytics::protocol_metrics::ProtocolMetricsResponse>,
+            crate::dtos::solana::common::ApiResponse<crate::dtos::solana::clmm::analytics::protocol_metrics::ProtocolMetricsRebuildResponse>,
             // Launch Event DTOs
             crate::dtos::solana::clmm::events::launch_event::LaunchEventResponse,
             crate::dtos::solana::clmm::events::launch_event::LaunchEventStatsResponse,
+            crate::dtos::solana::clmm::events::launch_event::AbandonLaunchMigrationRequest,
         )
     ),
     tags(
@@ -26,6 +26,7 @@ use user::user_service::{DynUserService, UserService};
 use self::solana::auth::solana_permission_service::{DynSolanaPermissionService, SolanaPermissionService};
 use self::solana::clmm::refer::refer_service::{DynReferService, ReferService};
 use self::solana::clmm::reward::reward_service::{DynRewardService, RewardService};
+use self::solana::clmm::price::{PriceService, PriceServiceConfig};
 use self::solana::clmm::token::token_service::TokenService;
 
 #[derive(Clone)]
@@ -37,6 +38,7 @@ pub struct Services {
     pub solana_permission: DynSolanaPermissionService,
     pub token: Arc<TokenService>,
     pub launch_event: Arc<LaunchEventService>,
+    pub price: Arc<PriceService>,
     pub database: Arc<Database>,
 }
 
@@ -65,8 +67,11 @@ impl Services {
                 let refer = Arc::new(ReferService::new(database.clone())) as DynReferService;
                 let reward = Arc::new(RewardService::new(database.clone())) as DynRewardService;
 
+                // 创建价格服务（所有请求共享价格缓存）
+                let price = Arc::new(PriceService::new(database.clone(), PriceServiceConfig::from_env()));
+
                 // 创建带数据库的SolanaService
-                let solana = match SolanaService::with_database(db.clone()) {
+                let solana = match SolanaService::with_database(db.clone(), price.clone()) {
                     Ok(service) => Arc::new(service) as DynSolanaService,
                     Err(e) => {
                         tracing::warn!("Failed to create SolanaService with database: {}, using default", e);
@@ -92,6 +97,7 @@ impl Services {
                     solana_permission,
                     token,
                     launch_event,
+                    price,
                     database,
                 };
 
@@ -117,8 +123,11 @@ impl Services {
         let refer = Arc::new(ReferService::new(database.clone())) as DynReferService;
         let reward = Arc::new(RewardService::new(database.clone())) as DynRewardService;
 
+        // 创建价格服务（所有请求共享价格缓存）
+        let price = Arc::new(PriceService::new(database.clone(), PriceServiceConfig::from_env()));
+
         // 创建带数据库的SolanaService
-        let solana = Arc::new(SolanaService::with_database(db)?) as DynSolanaService;
+        let solana = Arc::new(SolanaService::with_database(db, price.clone())?) as DynSolanaService;
 
         // 创建权限服务
         let solana_permission =
@@ -140,6 +149,7 @@ impl Services {
             solana_permission,
             token,
             launch_event,
+            price,
             database,
         })
     }
@@ -0,0 +1,254 @@
+use crate::dtos::solana::clmm::pool::candle::{CandleData, PoolCandleQuery, PoolCandlesResponse};
+use database::analytics::candle::{CandleInterval, CandleRebuildSummary, PoolCandle};
+use database::Database;
+use std::sync::Arc;
+use tracing::info;
+use utils::{AppError, AppResult};
+
+/// 池子代币对信息（token_0/token_1按池子中的顺序）
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolTokenPair {
+    pub mint_0: String,
+    pub mint_1: String,
+    pub decimals_0: u8,
+    pub decimals_1: u8,
+}
+
+/// K线服务 - 查询按代币精度和基础/计价方向换算后的池子K线，以及从交换历史重建K线
+///
+/// K线由事件监听器在写入新交换时增量维护，存储的是未按精度调整的token_1/token_0价格
+pub struct CandleService {
+    database: Arc<Database>,
+}
+
+impl CandleService {
+    /// 创建新的K线服务实例
+    pub fn new(database: Arc<Database>) -> Self {
+        Self { database }
+    }
+
+    /// 查询池子K线
+    pub async fn get_pool_candles(&self, pool_id: &str, query: &PoolCandleQuery) -> AppResult<PoolCandlesResponse> {
+        let interval = query.interval.parse::<CandleInterval>().map_err(AppError::BadRequest)?;
+        if let (Some(from), Some(to)) = (query.from, query.to) {
+            if from > to {
+                return Err(AppError::BadRequest("起始时间不能晚于截止时间".to_string()));
+            }
+        }
+
+        let pair = self.resolve_token_pair(pool_id).await?;
+        let invert = resolve_orientation(&pair, query.base_mint.as_deref(), query.quote_mint.as_deref())
+            .map_err(AppError::BadRequest)?;
+        let (base_mint, quote_mint) = if invert {
+            (pair.mint_1.clone(), pair.mint_0.clone())
+        } else {
+            (pair.mint_0.clone(), pair.mint_1.clone())
+        };
+
+        let candles = self
+            .database
+            .pool_candle_repository
+            .find_candles(pool_id, interval, query.from, query.to, query.limit.clamp(1, 1000))
+            .await?;
+
+        info!(
+            "📈 查询池子K线: pool={}, interval={}, base={}, count={}",
+            pool_id,
+            interval,
+            base_mint,
+            candles.len()
+        );
+
+        Ok(PoolCandlesResponse {
+            pool_id: pool_id.to_string(),
+            interval: interval.to_string(),
+            base_mint,
+            quote_mint,
+            candles: candles
+                .iter()
+                .map(|candle| to_candle_data(candle, &pair, invert))
+                .collect(),
+        })
+    }
+
+    /// 从交换历史重建池子的全部K线
+    pub async fn rebuild_pool_candles(&self, pool_id: &str) -> AppResult<CandleRebuildSummary> {
+        // 确认池子存在，避免为任意地址写入空结果
+        self.resolve_token_pair(pool_id).await?;
+
+        let summary = self.database.rebuild_pool_candles(pool_id, None).await?;
+        info!(
+            "✅ 池子K线重建完成: pool={}, trades={}, candles={}",
+            pool_id, summary.trades, summary.candles
+        );
+        Ok(summary)
+    }
+
+    /// 获取池子的代币对和精度，优先取池子表，CPMM池子缺失时取池子初始化事件
+    async fn resolve_token_pair(&self, pool_id: &str) -> AppResult<PoolTokenPair> {
+        if let Some(pool) = self.database.clmm_pool_repository.find_by_pool_address(pool_id).await? {
+            return Ok(PoolTokenPair {
+                mint_0: pool.mint0.mint_address,
+                mint_1: pool.mint1.mint_address,
+                decimals_0: pool.mint0.decimals,
+                decimals_1: pool.mint1.decimals,
+            });
+        }
+
+        match self
+            .database
+            .init_pool_event_repository
+            .find_by_pool_id(pool_id)
+            .await?
+        {
+            Some(event) => Ok(PoolTokenPair {
+                mint_0: event.token_0_mint,
+                mint_1: event.token_1_mint,
+                decimals_0: event.token_0_decimals,
+                decimals_1: event.token_1_decimals,
+            }),
+            None => Err(AppError::NotFound(format!("池子不存在: {}", pool_id))),
+        }
+    }
+}
+
+/// 根据请求的基础/计价代币确定价格方向，返回是否需要取倒数（以token_1为基础代币）
+pub fn resolve_orientation(
+    pair: &PoolTokenPair,
+    base_mint: Option<&str>,
+    quote_mint: Option<&str>,
+) -> Result<bool, String> {
+    let is_pool_mint = |mint: &str| mint == pair.mint_0 || mint == pair.mint_1;
+    for mint in [base_mint, quote_mint].into_iter().flatten() {
+        if !is_pool_mint(mint) {
+            return Err(format!("代币{}不属于该池子", mint));
+        }
+    }
+
+    match (base_mint, quote_mint) {
+        (Some(base), Some(quote)) if base == quote => Err("基础代币和计价代币不能相同".to_string()),
+        (Some(base), _) => Ok(base == pair.mint_1),
+        (None, Some(quote)) => Ok(quote == pair.mint_0),
+        (None, None) => Ok(false),
+    }
+}
+
+/// 将存储的K线换算为展示用K线
+///
+/// 价格按 10^(decimals_0 - decimals_1) 调整精度，取倒数时最高价和最低价互换
+pub fn to_candle_data(candle: &PoolCandle, pair: &PoolTokenPair, invert: bool) -> CandleData {
+    let scale = 10f64.powi(pair.decimals_0 as i32 - pair.decimals_1 as i32);
+    let volume_0 = candle.volume_0 / 10f64.powi(pair.decimals_0 as i32);
+    let volume_1 = candle.volume_1 / 10f64.powi(pair.decimals_1 as i32);
+    let price = |raw: f64| raw * scale;
+
+    if invert {
+        let inverse = |raw: f64| {
+            let price = price(raw);
+            if price > 0.0 {
+                1.0 / price
+            } else {
+                0.0
+            }
+        };
+        CandleData {
+            time: candle.open_time,
+            open: inverse(candle.open),
+            high: inverse(candle.low),
+            low: inverse(candle.high),
+            close: inverse(candle.close),
+            base_volume: volume_1,
+            quote_volume: volume_0,
+            trade_count: candle.trade_count,
+        }
+    } else {
+        CandleData {
+            time: candle.open_time,
+            open: price(candle.open),
+            high: price(candle.high),
+            low: price(candle.low),
+            close: price(candle.close),
+            base_volume: volume_0,
+            quote_volume: volume_1,
+            trade_count: candle.trade_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn create_pair() -> PoolTokenPair {
+        PoolTokenPair {
+            mint_0: "So11111111111111111111111111111111111111112".to_string(),
+            mint_1: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+            decimals_0: 9,
+            decimals_1: 6,
+        }
+    }
+
+    fn create_candle() -> PoolCandle {
+        PoolCandle {
+            id: None,
+            pool_id: "pool".to_string(),
+            interval: CandleInterval::OneHour,
+            open_time: 3_600,
+            // 原始价格0.1 / 0.2 / 0.05 / 0.125 对应 100 / 200 / 50 / 125 USDC每SOL
+            open: 0.1,
+            high: 0.2,
+            low: 0.05,
+            close: 0.125,
+            volume_0: 2_000_000_000.0,
+            volume_1: 300_000_000.0,
+            trade_count: 3,
+            open_slot: 1,
+            open_instruction_index: 0,
+            open_event_ordinal: 0,
+            close_slot: 3,
+            close_instruction_index: 0,
+            close_event_ordinal: 0,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_orientation() {
+        let pair = create_pair();
+        assert_eq!(resolve_orientation(&pair, None, None), Ok(false));
+        assert_eq!(resolve_orientation(&pair, Some(pair.mint_0.as_str()), None), Ok(false));
+        assert_eq!(resolve_orientation(&pair, Some(pair.mint_1.as_str()), None), Ok(true));
+        assert_eq!(resolve_orientation(&pair, None, Some(pair.mint_0.as_str())), Ok(true));
+        assert_eq!(
+            resolve_orientation(&pair, Some(pair.mint_1.as_str()), Some(pair.mint_0.as_str())),
+            Ok(true)
+        );
+        assert!(resolve_orientation(&pair, Some(pair.mint_0.as_str()), Some(pair.mint_0.as_str())).is_err());
+        assert!(resolve_orientation(&pair, Some("unknown_mint"), None).is_err());
+    }
+
+    #[test]
+    fn test_to_candle_data_scales_and_inverts() {
+        let pair = create_pair();
+        let candle = create_candle();
+
+        let data = to_candle_data(&candle, &pair, false);
+        assert!((data.open - 100.0).abs() < 1e-9);
+        assert!((data.high - 200.0).abs() < 1e-9);
+        assert!((data.low - 50.0).abs() < 1e-9);
+        assert!((data.close - 125.0).abs() < 1e-9);
+        assert!((data.base_volume - 2.0).abs() < 1e-9);
+        assert!((data.quote_volume - 300.0).abs() < 1e-9);
+
+        // 以token_1为基础代币：价格取倒数，最高价来自原最低价
+        let data = to_candle_data(&candle, &pair, true);
+        assert!((data.open - 0.01).abs() < 1e-12);
+        assert!((data.high - 0.02).abs() < 1e-12);
+        assert!((data.low - 0.005).abs() < 1e-12);
+        assert!((data.close - 0.008).abs() < 1e-12);
+        assert!((data.base_volume - 300.0).abs() < 1e-9);
+        assert!((data.quote_volume - 2.0).abs() < 1e-9);
+        assert_eq!(data.trade_count, 3);
+    }
+}
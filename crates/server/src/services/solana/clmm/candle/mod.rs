@@ -0,0 +1,3 @@
+pub mod candle_service;
+
+pub use candle_service::CandleService;
@@ -0,0 +1,113 @@
+use crate::services::solana::clmm::event::event_service::PaginatedResponse;
+use anyhow::{anyhow, Result};
+use database::events::dead_letter::{DeadLetterEvent, DeadLetterQuery, DeadLetterStats, DeadLetterStatus};
+use database::Database;
+use mongodb::bson::oid::ObjectId;
+use std::sync::Arc;
+use tracing::info;
+
+/// 死信事件服务 - 处理写入失败事件的查询、重放请求和丢弃
+///
+/// 服务端只负责变更死信事件状态，实际重放由监听器进程中的DeadLetterReplayer完成
+pub struct DeadLetterService {
+    database: Arc<Database>,
+}
+
+impl DeadLetterService {
+    /// 创建新的死信事件服务实例
+    pub fn new(database: Arc<Database>) -> Self {
+        Self { database }
+    }
+
+    /// 分页查询死信事件
+    pub async fn list(
+        &self,
+        status: Option<String>,
+        event_type: Option<String>,
+        signature: Option<String>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<PaginatedResponse<DeadLetterEvent>> {
+        info!("🔍 查询死信事件列表");
+
+        let status = match status {
+            Some(status) => Some(status.parse::<DeadLetterStatus>().map_err(|e| anyhow!(e))?),
+            None => None,
+        };
+        let page = page.max(1);
+        let page_size = page_size.clamp(1, 100);
+        let query = DeadLetterQuery {
+            status,
+            event_type,
+            signature,
+            page,
+            page_size,
+        };
+
+        let (items, total) = self.database.dead_letter_repository.find_with_query(&query).await?;
+        let total_pages = if total == 0 {
+            0
+        } else {
+            (total + page_size - 1) / page_size
+        };
+
+        Ok(PaginatedResponse {
+            items,
+            total,
+            page,
+            page_size,
+            total_pages,
+        })
+    }
+
+    /// 根据ID查询死信事件
+    pub async fn get(&self, id: &ObjectId) -> Result<Option<DeadLetterEvent>> {
+        self.database.dead_letter_repository.find_by_id(id).await
+    }
+
+    /// 获取各状态的死信事件统计
+    pub async fn stats(&self) -> Result<DeadLetterStats> {
+        self.database.dead_letter_repository.get_stats().await
+    }
+
+    /// 请求重放死信事件，返回实际更新数
+    pub async fn request_replay(&self, ids: &[ObjectId]) -> Result<u64> {
+        self.database.dead_letter_repository.request_replay(ids).await
+    }
+
+    /// 丢弃死信事件，返回实际更新数
+    pub async fn discard(&self, ids: &[ObjectId]) -> Result<u64> {
+        self.database.dead_letter_repository.discard(ids).await
+    }
+
+    /// 解析死信事件ID
+    pub fn parse_id(id: &str) -> Result<ObjectId> {
+        ObjectId::parse_str(id).map_err(|_| anyhow!("无效的死信事件ID: {}", id))
+    }
+
+    /// 解析批量操作的死信事件ID列表（1-100个）
+    pub fn parse_ids(ids: &[String]) -> Result<Vec<ObjectId>> {
+        if ids.is_empty() {
+            return Err(anyhow!("死信事件ID列表不能为空"));
+        }
+        if ids.len() > 100 {
+            return Err(anyhow!("单次最多操作100个死信事件"));
+        }
+        ids.iter().map(|id| Self::parse_id(id)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ids() {
+        let valid = ObjectId::new().to_hex();
+        assert_eq!(DeadLetterService::parse_ids(&[valid.clone()]).unwrap().len(), 1);
+
+        assert!(DeadLetterService::parse_ids(&[]).is_err());
+        assert!(DeadLetterService::parse_ids(&[valid, "not-an-id".to_string()]).is_err());
+        assert!(DeadLetterService::parse_ids(&vec![ObjectId::new().to_hex(); 101]).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+use anyhow::Result;
+use database::events::listener_runtime_config::{ListenerRuntimeConfig, RuntimeBackfillEventConfig};
+use database::Database;
+use std::sync::Arc;
+use tracing::info;
+
+/// 事件监听器运行时配置服务 - 处理订阅程序、解析器启用状态和回填配置的查询与修改
+///
+/// 服务端只负责写入配置文档，监听器进程中的RuntimeConfigWatcher检测到版本变化后在运行时生效
+pub struct ListenerConfigService {
+    database: Arc<Database>,
+}
+
+impl ListenerConfigService {
+    /// 创建新的监听器配置服务实例
+    pub fn new(database: Arc<Database>) -> Self {
+        Self { database }
+    }
+
+    /// 获取当前运行时配置（未配置时返回None，监听器使用启动配置）
+    pub async fn get(&self) -> Result<Option<ListenerRuntimeConfig>> {
+        info!("🔍 查询监听器运行时配置");
+        self.database.listener_runtime_config_repository.get().await
+    }
+
+    /// 保存运行时配置，返回递增版本后的配置
+    pub async fn update(
+        &self,
+        program_ids: Vec<String>,
+        disabled_event_types: Vec<String>,
+        backfill_events: Vec<RuntimeBackfillEventConfig>,
+        updated_by: &str,
+    ) -> Result<ListenerRuntimeConfig> {
+        info!(
+            "🔧 更新监听器运行时配置: {}个程序 (操作员: {})",
+            program_ids.len(),
+            updated_by
+        );
+        self.database
+            .listener_runtime_config_repository
+            .save(
+                program_ids,
+                disabled_event_types,
+                backfill_events,
+                Some(updated_by.to_string()),
+            )
+            .await
+    }
+}
@@ -1,8 +1,14 @@
 pub mod event_service;
 pub mod deposit_service;
+pub mod dead_letter_service;
+pub mod listener_config_service;
+pub mod webhook_service;
 #[cfg(test)]
 pub mod event_tests;
 
 pub use event_service::EventService;
 pub use deposit_service::DepositEventService;
+pub use dead_letter_service::DeadLetterService;
+pub use listener_config_service::ListenerConfigService;
+pub use webhook_service::WebhookService;
 
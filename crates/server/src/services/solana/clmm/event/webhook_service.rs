@@ -0,0 +1,265 @@
+use crate::services::solana::clmm::event::event_service::PaginatedResponse;
+use anyhow::{anyhow, Result};
+use database::events::webhook_delivery::{WebhookDelivery, WebhookDeliveryQuery, WebhookDeliveryStatus};
+use database::events::webhook_subscription::{WebhookFilter, WebhookSubscription};
+use database::Database;
+use mongodb::bson::oid::ObjectId;
+use rand::{distributions::Alphanumeric, Rng};
+use std::sync::Arc;
+use tracing::info;
+
+/// 签名密钥长度（不含前缀）
+const SECRET_LENGTH: usize = 40;
+
+/// webhook订阅服务 - 处理订阅的注册、修改、删除和投递记录查询
+///
+/// 服务端只负责维护订阅与投递记录，事件匹配和实际投递由监听器进程完成。
+/// owner为None表示管理员视角，可访问所有订阅；推送地址由调用方预先校验
+pub struct WebhookService {
+    database: Arc<Database>,
+}
+
+impl WebhookService {
+    /// 创建新的webhook订阅服务实例
+    pub fn new(database: Arc<Database>) -> Self {
+        Self { database }
+    }
+
+    /// 注册新订阅，返回包含签名密钥的订阅
+    pub async fn create(
+        &self,
+        owner: &str,
+        url: String,
+        filter: WebhookFilter,
+        description: Option<String>,
+    ) -> Result<WebhookSubscription> {
+        info!("🔔 注册webhook订阅: {} -> {}", owner, url);
+
+        let subscription = WebhookSubscription::new(
+            owner.to_string(),
+            url,
+            generate_secret(),
+            normalize_filter(filter),
+            description,
+        );
+        self.database.webhook_subscription_repository.insert(subscription).await
+    }
+
+    /// 查询订阅列表
+    pub async fn list(&self, owner: Option<&str>) -> Result<Vec<WebhookSubscription>> {
+        info!("🔍 查询webhook订阅列表");
+        self.database.webhook_subscription_repository.list(owner).await
+    }
+
+    /// 查询订阅（不属于owner的订阅按不存在处理）
+    pub async fn get(&self, id: &ObjectId, owner: Option<&str>) -> Result<Option<WebhookSubscription>> {
+        let subscription = self.database.webhook_subscription_repository.find_by_id(id).await?;
+        Ok(subscription.filter(|subscription| Self::is_accessible(subscription, owner)))
+    }
+
+    /// 更新订阅，返回更新后的订阅
+    pub async fn update(
+        &self,
+        id: &ObjectId,
+        owner: Option<&str>,
+        url: String,
+        filter: WebhookFilter,
+        active: bool,
+        description: Option<String>,
+    ) -> Result<Option<WebhookSubscription>> {
+        if self.get(id, owner).await?.is_none() {
+            return Ok(None);
+        }
+
+        self.database
+            .webhook_subscription_repository
+            .update(id, &url, &normalize_filter(filter), active, description.as_deref())
+            .await?;
+        info!("🔧 已更新webhook订阅: {}", id);
+        self.get(id, owner).await
+    }
+
+    /// 更换签名密钥，返回新密钥
+    pub async fn rotate_secret(&self, id: &ObjectId, owner: Option<&str>) -> Result<Option<String>> {
+        if self.get(id, owner).await?.is_none() {
+            return Ok(None);
+        }
+
+        let secret = generate_secret();
+        self.database
+            .webhook_subscription_repository
+            .rotate_secret(id, &secret)
+            .await?;
+        info!("🔑 已更换webhook订阅密钥: {}", id);
+        Ok(Some(secret))
+    }
+
+    /// 删除订阅及其投递记录，返回是否删除
+    pub async fn delete(&self, id: &ObjectId, owner: Option<&str>) -> Result<bool> {
+        if self.get(id, owner).await?.is_none() {
+            return Ok(false);
+        }
+
+        let deleted = self.database.webhook_subscription_repository.delete(id).await?;
+        if deleted {
+            let deliveries = self
+                .database
+                .webhook_delivery_repository
+                .delete_by_subscription(id)
+                .await?;
+            info!("🗑️ 已删除webhook订阅{}的{}条投递记录", id, deliveries);
+        }
+        Ok(deleted)
+    }
+
+    /// 分页查询投递记录
+    pub async fn list_deliveries(
+        &self,
+        owner: Option<&str>,
+        subscription_id: Option<ObjectId>,
+        status: Option<String>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<PaginatedResponse<WebhookDelivery>> {
+        info!("🔍 查询webhook投递记录");
+
+        let status = match status {
+            Some(status) => Some(status.parse::<WebhookDeliveryStatus>().map_err(|e| anyhow!(e))?),
+            None => None,
+        };
+        let page = page.max(1);
+        let page_size = page_size.clamp(1, 100);
+        let query = WebhookDeliveryQuery {
+            subscription_id,
+            owner: owner.map(str::to_string),
+            status,
+            page,
+            page_size,
+        };
+
+        let (items, total) = self
+            .database
+            .webhook_delivery_repository
+            .find_with_query(&query)
+            .await?;
+        let total_pages = if total == 0 {
+            0
+        } else {
+            (total + page_size - 1) / page_size
+        };
+
+        Ok(PaginatedResponse {
+            items,
+            total,
+            page,
+            page_size,
+            total_pages,
+        })
+    }
+
+    /// 重新投递失败的记录，返回实际更新数
+    pub async fn redeliver(&self, ids: &[ObjectId], owner: Option<&str>) -> Result<u64> {
+        self.database
+            .webhook_delivery_repository
+            .request_redelivery(ids, owner)
+            .await
+    }
+
+    /// 解析ID
+    pub fn parse_id(id: &str) -> Result<ObjectId> {
+        ObjectId::parse_str(id).map_err(|_| anyhow!("无效的ID: {}", id))
+    }
+
+    /// 解析批量操作的投递记录ID列表（1-100个）
+    pub fn parse_ids(ids: &[String]) -> Result<Vec<ObjectId>> {
+        if ids.is_empty() {
+            return Err(anyhow!("投递记录ID列表不能为空"));
+        }
+        if ids.len() > 100 {
+            return Err(anyhow!("单次最多操作100条投递记录"));
+        }
+        ids.iter().map(|id| Self::parse_id(id)).collect()
+    }
+
+    /// 检查订阅是否可被owner访问
+    fn is_accessible(subscription: &WebhookSubscription, owner: Option<&str>) -> bool {
+        owner.map_or(true, |owner| subscription.owner == owner)
+    }
+}
+
+/// 生成签名密钥
+fn generate_secret() -> String {
+    let secret: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(SECRET_LENGTH)
+        .map(char::from)
+        .collect();
+    format!("whsec_{}", secret)
+}
+
+/// 规范化过滤条件（去除空白条件和重复的事件类型）
+fn normalize_filter(filter: WebhookFilter) -> WebhookFilter {
+    let non_empty = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+    let mut event_types: Vec<String> = filter
+        .event_types
+        .into_iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    event_types.sort();
+    event_types.dedup();
+
+    WebhookFilter {
+        event_types,
+        pool_address: non_empty(filter.pool_address),
+        mint: non_empty(filter.mint),
+        wallet: non_empty(filter.wallet),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_secret() {
+        let secret = generate_secret();
+        assert!(secret.starts_with("whsec_"));
+        assert_eq!(secret.len(), "whsec_".len() + SECRET_LENGTH);
+        assert_ne!(secret, generate_secret());
+    }
+
+    #[test]
+    fn test_normalize_filter() {
+        let filter = normalize_filter(WebhookFilter {
+            event_types: vec![
+                " swap".to_string(),
+                "launch".to_string(),
+                "swap".to_string(),
+                "".to_string(),
+            ],
+            pool_address: Some("  ".to_string()),
+            mint: Some(" mint ".to_string()),
+            wallet: None,
+        });
+        assert_eq!(filter.event_types, vec!["launch".to_string(), "swap".to_string()]);
+        assert_eq!(filter.pool_address, None);
+        assert_eq!(filter.mint.as_deref(), Some("mint"));
+        assert_eq!(filter.wallet, None);
+    }
+
+    #[test]
+    fn test_is_accessible() {
+        let subscription = WebhookSubscription::new(
+            "partner".to_string(),
+            "https://partner.example.com/hooks".to_string(),
+            generate_secret(),
+            WebhookFilter::default(),
+            None,
+        );
+        assert!(WebhookService::is_accessible(&subscription, None));
+        assert!(WebhookService::is_accessible(&subscription, Some("partner")));
+        assert!(!WebhookService::is_accessible(&subscription, Some("other")));
+    }
+}
@@ -140,11 +140,32 @@ impl LaunchEventService {
 
         Ok(response_events)
     }
+
+    /// 将失败的Launch事件重新加入迁移队列（重置尝试次数）
+    pub async fn retry_migration(&self, signature: &str) -> AppResult<bool> {
+        info!("🔄 手动重试Launch迁移: signature={}", signature);
+
+        self.launch_event_repository
+            .requeue_failed_migration(signature)
+            .await
+            .map_err(|e| AppError::InternalServerErrorWithContext(format!("重新排队Launch迁移失败: {}", e)))
+    }
+
+    /// 放弃尚未完成的Launch迁移，事件将标记为失败
+    pub async fn abandon_migration(&self, signature: &str, reason: &str) -> AppResult<bool> {
+        info!("🛑 放弃Launch迁移: signature={}, reason={}", signature, reason);
+
+        self.launch_event_repository
+            .abandon_migration(signature, reason)
+            .await
+            .map_err(|e| AppError::InternalServerErrorWithContext(format!("放弃Launch迁移失败: {}", e)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use database::events::event_identity::EventIdentity;
     use database::events::event_model::LaunchEvent;
     use chrono::Utc;
 
@@ -169,12 +190,17 @@ mod tests {
             migration_completed_at: None,
             migration_error: None,
             migration_retry_count: 0,
+            migration_next_attempt_at: None,
+            migration_locked_until: None,
+            migration_tx_signature: None,
+            migration_pending_pool_address: None,
             total_liquidity_usd: 1000.0,
             pair_type: "MemeToUsdc".to_string(),
             price_range_width_percent: 300.0,
             is_high_value_launch: true,
             signature: "test_signature_123".to_string(),
             slot: 12345,
+            identity: EventIdentity::default(),
             processed_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
         }
@@ -0,0 +1,283 @@
+use crate::dtos::solana::clmm::launch::LaunchMigrationRequest;
+use crate::services::solana::DynSolanaService;
+use chrono::Utc;
+use database::events::event_model::LaunchEvent;
+use database::Database;
+use mongodb::bson::oid::ObjectId;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// Launch迁移任务配置
+#[derive(Debug, Clone)]
+pub struct LaunchMigrationWorkerConfig {
+    /// 是否启用自动迁移（会使用服务端私钥签名并发送交易）
+    pub enabled: bool,
+    /// 检查待迁移事件的间隔（秒）
+    pub poll_interval_secs: u64,
+    /// 单个事件的最大自动尝试次数，达到后标记为失败等待人工处理
+    pub max_attempts: u32,
+    /// 初始重试间隔（秒），按指数退避
+    pub retry_base_delay_secs: u64,
+    /// 重试间隔上限（秒）
+    pub retry_max_delay_secs: u64,
+    /// 领取事件后的占用时长（秒），应大于一次迁移交易的确认时间
+    pub lease_secs: u64,
+}
+
+impl Default for LaunchMigrationWorkerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            poll_interval_secs: 10,
+            max_attempts: 5,
+            retry_base_delay_secs: 30,
+            retry_max_delay_secs: 1800,
+            lease_secs: 300,
+        }
+    }
+}
+
+impl LaunchMigrationWorkerConfig {
+    /// 从环境变量加载配置
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: std::env::var("LAUNCH_MIGRATION_WORKER_ENABLED")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.enabled),
+            poll_interval_secs: std::env::var("LAUNCH_MIGRATION_POLL_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.poll_interval_secs),
+            max_attempts: std::env::var("LAUNCH_MIGRATION_MAX_ATTEMPTS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.max_attempts),
+            retry_base_delay_secs: std::env::var("LAUNCH_MIGRATION_RETRY_BASE_DELAY_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.retry_base_delay_secs),
+            retry_max_delay_secs: std::env::var("LAUNCH_MIGRATION_RETRY_MAX_DELAY_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.retry_max_delay_secs),
+            lease_secs: std::env::var("LAUNCH_MIGRATION_LEASE_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.lease_secs),
+        }
+    }
+
+    /// 计算第attempts次失败后的重试间隔（指数退避，有上限）
+    pub fn retry_delay_secs(&self, attempts: u32) -> u64 {
+        let factor = 2_u64.saturating_pow(attempts.saturating_sub(1));
+        self.retry_base_delay_secs
+            .saturating_mul(factor)
+            .min(self.retry_max_delay_secs)
+    }
+}
+
+/// Launch迁移任务
+///
+/// 定期领取状态为pending/retrying且已到期的LaunchEvent，调用
+/// LaunchMigrationService::launch_event_and_send_transaction完成池子创建和流动性注入，
+/// 成功后记录池子地址；失败时按指数退避安排重试，达到最大次数后标记为failed，
+/// 可通过管理接口重新排队或放弃。
+/// 交易签名在确认前写入事件，领取到已发送过交易的事件时先核对该交易和目标池子，
+/// 已上链则直接记为成功，不再重复发送
+pub struct LaunchMigrationWorker {
+    database: Arc<Database>,
+    solana: DynSolanaService,
+    config: LaunchMigrationWorkerConfig,
+}
+
+impl LaunchMigrationWorker {
+    /// 创建新的Launch迁移任务
+    pub fn new(database: Arc<Database>, solana: DynSolanaService, config: LaunchMigrationWorkerConfig) -> Self {
+        Self {
+            database,
+            solana,
+            config,
+        }
+    }
+
+    /// 启动迁移循环
+    pub async fn start(&self) {
+        info!(
+            "🚀 启动Launch迁移任务: 检查间隔{}秒, 最大尝试{}次",
+            self.config.poll_interval_secs, self.config.max_attempts
+        );
+
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.poll_interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+
+            match self.run_once().await {
+                Ok(0) => {}
+                Ok(count) => info!("✅ 本轮处理{}个Launch迁移", count),
+                Err(e) => error!("❌ Launch迁移任务执行失败: {}", e),
+            }
+        }
+    }
+
+    /// 处理所有已到期的待迁移事件，返回处理数量
+    pub async fn run_once(&self) -> anyhow::Result<usize> {
+        let mut processed = 0;
+
+        loop {
+            let now = Utc::now().timestamp();
+            let event = self
+                .database
+                .launch_event_repository
+                .claim_due_migration(now, self.config.lease_secs as i64)
+                .await?;
+
+            match event {
+                Some(event) => {
+                    self.migrate(event).await?;
+                    processed += 1;
+                }
+                None => return Ok(processed),
+            }
+        }
+    }
+
+    /// 执行单个事件的迁移并记录结果
+    async fn migrate(&self, event: LaunchEvent) -> anyhow::Result<()> {
+        let id = event
+            .id
+            .ok_or_else(|| anyhow::anyhow!("Launch事件缺少ID: {}", event.signature))?;
+        let attempts = event.migration_retry_count + 1;
+
+        info!(
+            "🚀 执行Launch迁移: signature={}, meme={}, 第{}次尝试",
+            event.signature, event.meme_token_mint, attempts
+        );
+
+        if let (Some(tx_signature), Some(pool_address)) = (
+            event.migration_tx_signature.as_deref(),
+            event.migration_pending_pool_address.as_deref(),
+        ) {
+            match self.solana.is_launch_migration_landed(tx_signature, pool_address).await {
+                Ok(true) => {
+                    info!(
+                        "✅ 上次发送的迁移交易已上链: signature={}, pool={}, tx={}",
+                        event.signature, pool_address, tx_signature
+                    );
+                    self.database
+                        .launch_event_repository
+                        .record_migration_success(&id, pool_address)
+                        .await?;
+                    return Ok(());
+                }
+                Ok(false) => info!(
+                    "🔄 上次发送的迁移交易未上链，重新发送: signature={}, tx={}",
+                    event.signature, tx_signature
+                ),
+                Err(e) => {
+                    // 无法确认上次交易的结果时不重新发送，避免重复迁移
+                    let e = anyhow::anyhow!("核对迁移交易{}失败: {}", tx_signature, e);
+                    return self.record_failure(&id, &event, attempts, e).await;
+                }
+            }
+        }
+
+        let request = build_migration_request(&event);
+        match self
+            .solana
+            .launch_event_migration_and_send_transaction(request, &id)
+            .await
+        {
+            Ok(response) => {
+                info!(
+                    "✅ Launch迁移成功: signature={}, pool={}, tx={}",
+                    event.signature, response.pool_address, response.signature
+                );
+                self.database
+                    .launch_event_repository
+                    .record_migration_success(&id, &response.pool_address)
+                    .await?;
+            }
+            Err(e) => self.record_failure(&id, &event, attempts, e).await?,
+        }
+
+        Ok(())
+    }
+
+    /// 记录第attempts次迁移失败，未达到最大次数时安排重试
+    async fn record_failure(
+        &self,
+        id: &ObjectId,
+        event: &LaunchEvent,
+        attempts: u32,
+        e: anyhow::Error,
+    ) -> anyhow::Result<()> {
+        let next_attempt_at = if attempts < self.config.max_attempts {
+            Some(Utc::now().timestamp() + self.config.retry_delay_secs(attempts) as i64)
+        } else {
+            None
+        };
+
+        match next_attempt_at {
+            Some(next_attempt_at) => warn!(
+                "🔄 Launch迁移第{}次失败，{}后重试: signature={}, error={}",
+                attempts, next_attempt_at, event.signature, e
+            ),
+            None => error!(
+                "❌ Launch迁移已尝试{}次，放弃自动重试: signature={}, error={}",
+                attempts, event.signature, e
+            ),
+        }
+
+        self.database
+            .launch_event_repository
+            .record_migration_failure(id, &e.to_string(), next_attempt_at)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// 根据Launch事件构建迁移请求
+fn build_migration_request(event: &LaunchEvent) -> LaunchMigrationRequest {
+    LaunchMigrationRequest {
+        meme_token_mint: event.meme_token_mint.clone(),
+        base_token_mint: event.base_token_mint.clone(),
+        user_wallet: event.user_wallet.clone(),
+        config_index: event.config_index,
+        initial_price: event.initial_price,
+        open_time: event.open_time,
+        tick_lower_price: event.tick_lower_price,
+        tick_upper_price: event.tick_upper_price,
+        meme_token_amount: event.meme_token_amount,
+        base_token_amount: event.base_token_amount,
+        max_slippage_percent: event.max_slippage_percent,
+        with_metadata: Some(event.with_metadata),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_secs() {
+        let config = LaunchMigrationWorkerConfig {
+            retry_base_delay_secs: 30,
+            retry_max_delay_secs: 600,
+            ..Default::default()
+        };
+        assert_eq!(config.retry_delay_secs(1), 30);
+        assert_eq!(config.retry_delay_secs(2), 60);
+        assert_eq!(config.retry_delay_secs(4), 240);
+        assert_eq!(config.retry_delay_secs(10), 600);
+        assert_eq!(config.retry_delay_secs(100), 600);
+    }
+
+    #[test]
+    fn test_worker_disabled_by_default() {
+        assert!(!LaunchMigrationWorkerConfig::default().enabled);
+    }
+}
@@ -1,5 +1,7 @@
 //! LaunchEvent相关服务模块
 
 pub mod launch_event_service;
+pub mod launch_migration_worker;
 
-pub use launch_event_service::LaunchEventService;
\ No newline at end of file
+pub use launch_event_service::LaunchEventService;
+pub use launch_migration_worker::{LaunchMigrationWorker, LaunchMigrationWorkerConfig};
@@ -18,14 +18,15 @@ use ::utils::solana::{ConfigManager, PoolInstructionBuilder, PositionInstruction
 use anyhow::Result;
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
-use mongodb::bson::doc;
+use mongodb::bson::{doc, oid::ObjectId};
+use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::instruction::AccountMeta;
 use solana_sdk::program_pack::Pack;
 use solana_sdk::{
     instruction::Instruction,
     message::Message,
     pubkey::Pubkey,
-    signature::{Keypair, Signer},
+    signature::{Keypair, Signature, Signer},
     transaction::Transaction,
 };
 use spl_token::state::Mint;
@@ -129,8 +130,76 @@ impl LaunchMigrationService {
     ) -> Result<LaunchMigrationAndSendTransactionResponse> {
         info!("🚀 开始发射迁移并发送交易");
 
+        let (transaction, addresses) = self.build_signed_migration_transaction(&request).await?;
+        self.send_migration_transaction(&request, &transaction, addresses).await
+    }
+
+    /// 为Launch事件构建并发送发射迁移交易
+    ///
+    /// 交易签名后、发送确认前先把交易签名和池子地址写入Launch事件，
+    /// 确认阶段超时或进程崩溃时，下次领取可据此核对交易是否已上链，避免重复迁移
+    pub async fn launch_event_and_send_transaction(
+        &self,
+        request: LaunchMigrationRequest,
+        launch_event_id: &ObjectId,
+    ) -> Result<LaunchMigrationAndSendTransactionResponse> {
+        info!("🚀 开始为Launch事件发射迁移并发送交易: {}", launch_event_id);
+
+        let (transaction, addresses) = self.build_signed_migration_transaction(&request).await?;
+        let tx_signature = transaction.signatures[0].to_string();
+
+        let recorded = self
+            .database
+            .launch_event_repository
+            .record_migration_sent(launch_event_id, &tx_signature, &addresses.pool_address)
+            .await?;
+        if !recorded {
+            return Err(anyhow::anyhow!("Launch事件已不在待迁移状态，取消发送: {}", launch_event_id));
+        }
+        info!("📝 已记录迁移交易签名: {}", tx_signature);
+
+        self.send_migration_transaction(&request, &transaction, addresses).await
+    }
+
+    /// 核对已发送的迁移交易是否已生效
+    ///
+    /// 交易已确认成功，或迁移目标池子账户已存在时返回true
+    pub async fn is_migration_landed(&self, tx_signature: &str, pool_address: &str) -> Result<bool> {
+        let signature = Signature::from_str(tx_signature)?;
+        let statuses = self
+            .shared
+            .rpc_client
+            .get_signature_statuses_with_history(&[signature])?
+            .value;
+
+        if let Some(status) = statuses.into_iter().flatten().next() {
+            if status.err.is_none() && status.satisfies_commitment(CommitmentConfig::confirmed()) {
+                info!("✅ 迁移交易已确认: {}", tx_signature);
+                return Ok(true);
+            }
+        }
+
+        let pool_pubkey = Pubkey::from_str(pool_address)?;
+        let pool_account = self
+            .shared
+            .rpc_client
+            .get_account_with_commitment(&pool_pubkey, CommitmentConfig::confirmed())?
+            .value;
+        if pool_account.is_some() {
+            info!("✅ 迁移目标池子已存在: {}", pool_address);
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    /// 构建并签名发射迁移交易
+    async fn build_signed_migration_transaction(
+        &self,
+        request: &LaunchMigrationRequest,
+    ) -> Result<(Transaction, MigrationAddresses)> {
         // 1. 参数验证
-        self.validate_migration_request(&request)?;
+        self.validate_migration_request(request)?;
 
         // 2. 获取私钥
         let private_key = self
@@ -145,9 +214,9 @@ impl LaunchMigrationService {
 
         // 3. 构建指令（这次需要同时返回nft_mint_keypair）
         let (instructions, addresses, nft_mint_keypair) =
-            self.build_migration_instructions_with_keypair(&request).await?;
+            self.build_migration_instructions_with_keypair(request).await?;
 
-        // 4. 构建并发送交易
+        // 4. 构建并签名交易
         let recent_blockhash = self.shared.rpc_client.get_latest_blockhash()?;
 
         let transaction = Transaction::new_signed_with_payer(
@@ -157,8 +226,18 @@ impl LaunchMigrationService {
             recent_blockhash,
         );
 
+        Ok((transaction, addresses))
+    }
+
+    /// 发送并确认已签名的发射迁移交易
+    async fn send_migration_transaction(
+        &self,
+        request: &LaunchMigrationRequest,
+        transaction: &Transaction,
+        addresses: MigrationAddresses,
+    ) -> Result<LaunchMigrationAndSendTransactionResponse> {
         // 5. 发送交易
-        let signature = self.shared.rpc_client.send_and_confirm_transaction(&transaction)?;
+        let signature = self.shared.rpc_client.send_and_confirm_transaction(transaction)?;
         info!("✅ 发射迁移交易发送成功，签名: {}", signature);
 
         // 6. 构建响应
@@ -189,7 +268,7 @@ impl LaunchMigrationService {
         };
 
         // 异步持久化Launch Migration记录（发送交易版本）
-        self.persist_launch_migration_with_transaction(&request, &response)
+        self.persist_launch_migration_with_transaction(request, &response)
             .await;
 
         Ok(response)
@@ -322,6 +401,7 @@ impl LaunchMigrationService {
             pool_type: PoolType::Concentrated,
             data_source: DataSource::ApiCreated, // 标识为API创建
             chain_confirmed: false,
+            tvl_info: None,
         };
 
         // 插入数据库
@@ -468,6 +548,7 @@ impl LaunchMigrationService {
             pool_type: PoolType::Concentrated,
             data_source: DataSource::ApiCreated, // 标识为API创建
             chain_confirmed: false,
+            tvl_info: None,
         };
 
         // 插入数据库
@@ -1,12 +1,16 @@
+pub mod candle;
 pub mod config;
 pub mod event;
 pub mod launch_event;
 pub mod pool;
+pub mod pool_stats;
 pub mod launch_migration;
 pub mod liquidity;
 pub mod liquidity_line;
 pub mod nft;
 pub mod position;
+pub mod price;
+pub mod protocol_metrics;
 pub mod referral;
 pub mod swap;
 pub mod token;
@@ -14,15 +18,19 @@ pub mod refer;
 pub mod reward;
 pub mod transform;
 
+pub use candle::*;
 pub use config::*;
 pub use event::*;
 pub use launch_event::*;
 pub use pool::*;
+pub use pool_stats::*;
 pub use launch_migration::*;
 pub use liquidity::*;
 pub use liquidity_line::*;
 pub use nft::*;
 pub use position::*;
+pub use price::*;
+pub use protocol_metrics::*;
 pub use referral::*;
 pub use swap::*;
 pub use token::*;
@@ -218,6 +218,7 @@ impl ChainPoolLoader {
             pool_type: PoolType::Concentrated, // 当前只支持CLMM池
             data_source: database::clmm::clmm_pool::DataSource::ChainEvent,
             chain_confirmed: true, // 从链上加载的池子已确认
+            tvl_info: None,
         };
 
         debug!("✅ 池子信息加载完成: {}", pool_address);
@@ -340,6 +340,7 @@ mod integration_tests {
             pool_type: database::clmm::clmm_pool::model::PoolType::Concentrated,
             data_source: database::clmm::clmm_pool::DataSource::ApiCreated,
             chain_confirmed: false,
+            tvl_info: None,
         };
 
         // 测试数据存储和查询
@@ -391,6 +392,7 @@ mod integration_tests {
             max_retries: 2,
             retry_interval: 5,
             auto_sync_enabled: false, // 测试中禁用自动同步
+            tvl_refresh_interval: 300,
         };
 
         let sync_service = ClmmPoolSyncService::new(env.shared_context, env.storage_service, Some(sync_config));
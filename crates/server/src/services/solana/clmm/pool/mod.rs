@@ -5,6 +5,7 @@ pub mod error_handler;
 pub mod pool_service;
 pub mod storage;
 pub mod sync;
+pub mod tvl;
 
 #[cfg(test)]
 mod pool_tests;
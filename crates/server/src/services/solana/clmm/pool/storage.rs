@@ -7,7 +7,7 @@ use crate::dtos::solana::clmm::pool::creation::{
 };
 use database::clmm::clmm_pool::{
     ClmmPool, ClmmPoolRepository, DataSource, ExtensionInfo, PoolStatus, PriceInfo, SyncStatus, TokenInfo,
-    TransactionInfo, TransactionStatus, VaultInfo,
+    TransactionInfo, TransactionStatus, TvlInfo, VaultInfo,
 };
 use mongodb::Collection;
 use tracing::{debug, error, info, warn};
@@ -183,6 +183,7 @@ impl ClmmPoolStorageService {
             // 新增状态字段
             data_source: DataSource::ApiCreated,
             chain_confirmed: false,
+            tvl_info: None,
         };
 
         // 使用upsert操作
@@ -310,6 +311,7 @@ impl ClmmPoolStorageService {
             // 状态字段
             data_source: DataSource::ApiCreated,
             chain_confirmed: true, // 交易已发送并确认
+            tvl_info: None,
         };
 
         // 使用upsert操作
@@ -435,6 +437,21 @@ impl ClmmPoolStorageService {
         self.repository.update_sync_status(pool_address, sync_status).await
     }
 
+    /// 获取全部活跃池子
+    pub async fn get_active_pools(&self) -> AppResult<Vec<ClmmPool>> {
+        self.repository.find_active_pools().await
+    }
+
+    /// 获取TVL需要刷新的池子列表
+    pub async fn get_pools_need_tvl_refresh(&self, stale_before: u64, limit: i64) -> AppResult<Vec<ClmmPool>> {
+        self.repository.find_pools_need_tvl_refresh(stale_before, limit).await
+    }
+
+    /// 更新池子的锁仓价值信息
+    pub async fn update_tvl_info(&self, pool_address: &str, tvl_info: &TvlInfo) -> AppResult<bool> {
+        self.repository.update_tvl_info(pool_address, tvl_info).await
+    }
+
     /// 批量标记池子需要同步
     pub async fn mark_pools_for_sync(&self, pool_addresses: &[String]) -> AppResult<u64> {
         if pool_addresses.is_empty() {
@@ -4,9 +4,14 @@
 
 use super::super::super::shared::SharedContext;
 use super::storage::ClmmPoolStorageService;
+use super::tvl::{build_tvl_info, fetch_multiple_accounts, resolve_vault_addresses, PoolQuote};
+use crate::services::solana::clmm::price::price_graph::{derive_prices, PoolEdge};
+use crate::services::solana::clmm::price::{PriceGraphConfig, PriceServiceConfig};
 use database::clmm::clmm_pool::{ClmmPool, SyncStatus};
 use solana_sdk::{program_pack::Pack, pubkey::Pubkey};
 use spl_token::state::Mint;
+use spl_token_2022::{extension::PodStateWithExtensions, pod::PodAccount};
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -27,6 +32,8 @@ pub struct SyncConfig {
     pub retry_interval: u64,
     /// 是否启用自动同步
     pub auto_sync_enabled: bool,
+    /// TVL刷新间隔 (秒)
+    pub tvl_refresh_interval: u64,
 }
 
 impl Default for SyncConfig {
@@ -52,6 +59,10 @@ impl Default for SyncConfig {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(true),
+            tvl_refresh_interval: std::env::var("CLMM_TVL_REFRESH_INTERVAL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300), // 5分钟
         }
     }
 }
@@ -76,6 +87,9 @@ impl SyncConfig {
         if self.retry_interval < 1 {
             return Err("重试间隔不能小于1秒".to_string());
         }
+        if self.tvl_refresh_interval < 10 {
+            return Err("TVL刷新间隔不能小于10秒".to_string());
+        }
         Ok(())
     }
 }
@@ -85,6 +99,8 @@ pub struct ClmmPoolSyncService {
     shared: Arc<SharedContext>,
     storage: ClmmPoolStorageService,
     config: SyncConfig,
+    /// TVL定价参数，与价格服务一致
+    price_graph: PriceGraphConfig,
     metaplex_service: tokio::sync::Mutex<MetaplexService>,
 }
 
@@ -97,6 +113,7 @@ impl ClmmPoolSyncService {
             shared,
             storage,
             config: config.unwrap_or_default(),
+            price_graph: PriceServiceConfig::from_env().graph,
             metaplex_service: tokio::sync::Mutex::new(metaplex_service),
         }
     }
@@ -127,7 +144,133 @@ impl ClmmPoolSyncService {
                     error!("❌ 批次同步失败: {}", e);
                 }
             }
+
+            match self.refresh_pools_tvl().await {
+                Ok(refreshed_count) => {
+                    if refreshed_count > 0 {
+                        info!("💰 TVL刷新完成，更新了 {} 个池子", refreshed_count);
+                    }
+                }
+                Err(e) => {
+                    error!("❌ TVL刷新失败: {}", e);
+                }
+            }
+        }
+    }
+
+    /// 刷新TVL已过期的池子
+    ///
+    /// 读取金库余额，通过定价图推导代币USD价格后计算TVL并写回池子记录。
+    /// 定价图同时使用其他活跃池子上次记录的余额，使非稳定币交易对也能定价
+    pub async fn refresh_pools_tvl(&self) -> AppResult<u64> {
+        let now = chrono::Utc::now().timestamp() as u64;
+        let stale_before = now.saturating_sub(self.config.tvl_refresh_interval);
+
+        let pools = self
+            .storage
+            .get_pools_need_tvl_refresh(stale_before, self.config.batch_size)
+            .await?;
+        if pools.is_empty() {
+            return Ok(0);
+        }
+
+        info!("💰 开始刷新 {} 个池子的TVL", pools.len());
+
+        // 1. 批量获取金库余额
+        let balances = self.batch_fetch_vault_balances(&pools).await?;
+
+        // 2. 构造定价图：本批次使用最新余额，其余活跃池子使用上次记录的余额
+        let mut edges: Vec<PoolEdge> = pools
+            .iter()
+            .filter_map(|pool| {
+                let (amount_0, amount_1) = balances.get(&pool.pool_address)?;
+                Some(PoolEdge {
+                    pool_id: pool.pool_address.clone(),
+                    quote: PoolQuote::new(pool, *amount_0, *amount_1)?,
+                })
+            })
+            .collect();
+
+        let batch_addresses: HashSet<&str> = pools.iter().map(|pool| pool.pool_address.as_str()).collect();
+        match self.storage.get_active_pools().await {
+            Ok(active_pools) => edges.extend(
+                active_pools
+                    .iter()
+                    .filter(|pool| !batch_addresses.contains(pool.pool_address.as_str()))
+                    .filter_map(PoolEdge::from_pool),
+            ),
+            Err(e) => warn!("⚠️ 加载活跃池子失败，仅使用本批次池子定价: {}", e),
+        }
+
+        let prices = derive_prices(&edges, &self.price_graph);
+
+        // 3. 计算并保存TVL
+        let mut refreshed_count = 0u64;
+        for pool in &pools {
+            let Some((amount_0, amount_1)) = balances.get(&pool.pool_address) else {
+                continue;
+            };
+
+            let tvl_info = build_tvl_info(pool, *amount_0, *amount_1, &prices, now);
+            debug!(
+                "💰 池子TVL: {} - tvl={:.2}, amount_0={}, amount_1={}",
+                pool.pool_address, tvl_info.tvl, tvl_info.amount_0, tvl_info.amount_1
+            );
+
+            match self.storage.update_tvl_info(&pool.pool_address, &tvl_info).await {
+                Ok(true) => refreshed_count += 1,
+                Ok(false) => warn!("⚠️ 更新TVL失败，池子不存在: {}", pool.pool_address),
+                Err(e) => error!("❌ 更新TVL失败: {} - {}", pool.pool_address, e),
+            }
+        }
+
+        Ok(refreshed_count)
+    }
+
+    /// 批量获取池子金库余额（已按精度换算），返回 池子地址 -> (代币0余额, 代币1余额)
+    ///
+    /// 代币精度尚未同步的池子跳过；金库账户不存在的池子余额记为0
+    async fn batch_fetch_vault_balances(&self, pools: &[ClmmPool]) -> AppResult<HashMap<String, (f64, f64)>> {
+        let mut vault_pools = Vec::new();
+        let mut vault_pubkeys = Vec::new();
+
+        for pool in pools {
+            if pool.mint0.owner.is_empty() || pool.mint1.owner.is_empty() {
+                debug!("⏭️ 池子代币信息尚未同步，跳过TVL计算: {}", pool.pool_address);
+                continue;
+            }
+
+            match resolve_vault_addresses(pool) {
+                Ok((vault_0, vault_1)) => {
+                    vault_pools.push(pool);
+                    vault_pubkeys.push(vault_0);
+                    vault_pubkeys.push(vault_1);
+                }
+                Err(e) => warn!("⚠️ 无法确定池子金库地址: {} - {}", pool.pool_address, e),
+            }
+        }
+
+        let accounts = fetch_multiple_accounts(&self.shared.rpc_client, vault_pubkeys)
+            .await
+            .map_err(|e| anyhow::anyhow!("批量获取金库账户失败: {}", e))?;
+
+        let vault_amount = |index: usize| -> u64 {
+            accounts[index]
+                .as_ref()
+                .and_then(|account| PodStateWithExtensions::<PodAccount>::unpack(&account.data).ok())
+                .map(|vault| vault.base.amount.into())
+                .unwrap_or(0)
+        };
+
+        let mut balances = HashMap::new();
+        for (i, pool) in vault_pools.into_iter().enumerate() {
+            let amount_0 = vault_amount(i * 2) as f64 / 10f64.powi(pool.mint0.decimals as i32);
+            let amount_1 = vault_amount(i * 2 + 1) as f64 / 10f64.powi(pool.mint1.decimals as i32);
+            balances.insert(pool.pool_address.clone(), (amount_0, amount_1));
         }
+
+        debug!("📦 批量获取了 {} 个池子的金库余额", balances.len());
+        Ok(balances)
     }
 
     /// 批量同步池子数据
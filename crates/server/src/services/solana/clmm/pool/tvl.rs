@@ -0,0 +1,144 @@
+//! 池子锁仓价值(TVL)计算
+//!
+//! TVL = 金库余额 × 代币USD价格。代币USD价格由定价图(price_graph)推导，
+//! 与代币价格接口使用同一套锚点、路径选择和流动性阈值
+
+use crate::services::solana::clmm::price::DerivedPrice;
+use database::clmm::clmm_pool::{ClmmPool, PoolType, TvlInfo};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use utils::{AppResult, ConfigManager, USDC_MINT_CONFIG, USDC_MINT_STANDARD, USDT_MINT};
+
+/// 作为USD价格锚点的稳定币
+pub const STABLE_MINTS: [&str; 3] = [USDC_MINT_STANDARD, USDC_MINT_CONFIG, USDT_MINT];
+
+/// 单次getMultipleAccounts请求的最大账户数
+pub const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+/// 用于价格推导的池子报价
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolQuote {
+    /// 代币0 mint地址
+    pub mint_0: String,
+    /// 代币1 mint地址
+    pub mint_1: String,
+    /// 每个代币0值多少代币1 (已按精度换算)
+    pub price: f64,
+    /// 代币0金库余额 (已按精度换算)
+    pub amount_0: f64,
+    /// 代币1金库余额 (已按精度换算)
+    pub amount_1: f64,
+}
+
+impl PoolQuote {
+    /// 根据池子和金库余额构造报价，无法确定池子价格时返回None
+    pub fn new(pool: &ClmmPool, amount_0: f64, amount_1: f64) -> Option<Self> {
+        let price = pool_price(pool, amount_0, amount_1)?;
+        Some(Self {
+            mint_0: pool.mint0.mint_address.clone(),
+            mint_1: pool.mint1.mint_address.clone(),
+            price,
+            amount_0,
+            amount_1,
+        })
+    }
+
+    /// 使用池子上次计算的金库余额构造报价（用于本批次未刷新的池子）
+    pub fn from_stored(pool: &ClmmPool) -> Option<Self> {
+        let tvl_info = pool.tvl_info.as_ref()?;
+        Self::new(pool, tvl_info.amount_0, tvl_info.amount_1)
+    }
+}
+
+/// 计算池子价格（每个代币0值多少代币1，已按精度换算）
+///
+/// 标准池按金库余额之比计算；集中流动性池的金库余额不反映价格，使用池子记录的当前价格
+pub fn pool_price(pool: &ClmmPool, amount_0: f64, amount_1: f64) -> Option<f64> {
+    let price = match pool.pool_type {
+        PoolType::Standard => {
+            if amount_0 <= 0.0 || amount_1 <= 0.0 {
+                return None;
+            }
+            amount_1 / amount_0
+        }
+        PoolType::Concentrated => pool.price_info.current_price.unwrap_or(pool.price_info.initial_price),
+    };
+
+    if price.is_finite() && price > 0.0 {
+        Some(price)
+    } else {
+        None
+    }
+}
+
+/// 根据金库余额和代币USD价格生成TVL信息，无法定价的一侧不计入TVL
+pub fn build_tvl_info(
+    pool: &ClmmPool,
+    amount_0: f64,
+    amount_1: f64,
+    prices: &HashMap<String, DerivedPrice>,
+    updated_at: u64,
+) -> TvlInfo {
+    let price_usd_0 = prices.get(&pool.mint0.mint_address).map(|price| price.price_usd);
+    let price_usd_1 = prices.get(&pool.mint1.mint_address).map(|price| price.price_usd);
+    let tvl = amount_0 * price_usd_0.unwrap_or(0.0) + amount_1 * price_usd_1.unwrap_or(0.0);
+
+    TvlInfo {
+        amount_0,
+        amount_1,
+        price_usd_0,
+        price_usd_1,
+        tvl,
+        updated_at,
+    }
+}
+
+/// 分批读取账户，阻塞的RPC请求放到blocking线程池执行，避免占用异步运行时的工作线程
+pub async fn fetch_multiple_accounts(
+    rpc_client: &Arc<RpcClient>,
+    pubkeys: Vec<Pubkey>,
+) -> anyhow::Result<Vec<Option<Account>>> {
+    let rpc_client = Arc::clone(rpc_client);
+    tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<Option<Account>>> {
+        let mut accounts = Vec::with_capacity(pubkeys.len());
+        for chunk in pubkeys.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+            accounts.extend(rpc_client.get_multiple_accounts(chunk)?);
+        }
+        Ok(accounts)
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("异步任务执行失败: {}", e))?
+}
+
+/// 获取池子的两个金库地址，池子记录中缺失时按程序PDA推导
+pub fn resolve_vault_addresses(pool: &ClmmPool) -> AppResult<(Pubkey, Pubkey)> {
+    let pool_pubkey = Pubkey::from_str(&pool.pool_address).map_err(|e| anyhow::anyhow!("无效的池子地址: {}", e))?;
+    let program_id = match pool.pool_type {
+        PoolType::Concentrated => ConfigManager::get_raydium_program_id()?,
+        PoolType::Standard => ConfigManager::get_cpmm_program_id()?,
+    };
+
+    let resolve = |vault: &str, mint: &str| -> AppResult<Pubkey> {
+        if !vault.is_empty() {
+            return Ok(Pubkey::from_str(vault).map_err(|e| anyhow::anyhow!("无效的金库地址: {}", e))?);
+        }
+        let mint_pubkey = Pubkey::from_str(mint).map_err(|e| anyhow::anyhow!("无效的mint地址: {}", e))?;
+        let (vault_pubkey, _) = Pubkey::find_program_address(
+            &[
+                "pool_vault".as_bytes(),
+                pool_pubkey.to_bytes().as_ref(),
+                mint_pubkey.to_bytes().as_ref(),
+            ],
+            &program_id,
+        );
+        Ok(vault_pubkey)
+    };
+
+    Ok((
+        resolve(&pool.vault_info.token_vault_0, &pool.mint0.mint_address)?,
+        resolve(&pool.vault_info.token_vault_1, &pool.mint1.mint_address)?,
+    ))
+}
@@ -0,0 +1,7 @@
+//! 池子滚动统计（交易量、手续费、APR）相关服务模块
+
+pub mod pool_stats_service;
+pub mod pool_stats_worker;
+
+pub use pool_stats_service::PoolStatsService;
+pub use pool_stats_worker::{PoolStatsWorker, PoolStatsWorkerConfig};
@@ -0,0 +1,506 @@
+use crate::dtos::solana::clmm::pool::listing::{PeriodStats, PoolInfo};
+use crate::services::solana::clmm::pool::tvl::fetch_multiple_accounts;
+use crate::services::solana::clmm::price::PriceService;
+use crate::services::solana::shared::SolanaUtils;
+use chrono::Utc;
+use database::analytics::pool_stats::{annualized_rate, PoolPeriodStats, PoolStatsSnapshot, StatsPeriod, SwapVolume};
+use database::clmm::clmm_pool::{ClmmPool, PoolType};
+use database::Database;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::{extension::PodStateWithExtensions, pod::PodMint};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use utils::AppResult;
+
+/// 一年的秒数（奖励按每秒发放量年化）
+const SECONDS_PER_YEAR: f64 = 31_536_000.0;
+
+/// CLMM交易费率的精度（trade_fee_rate以百万分之一为单位）
+const FEE_RATE_DENOMINATOR: f64 = 1_000_000.0;
+
+/// 池子统计服务 - 计算并提供池子的滚动交易量、手续费和APR
+///
+/// 24h/7d/30d交易量和手续费来自已索引的交换事件，价格区间来自K线，
+/// 手续费APR按周期手续费相对当前TVL年化，CLMM奖励APR按链上奖励的每秒发放量年化，奖励代币价格取自价格服务。
+/// 计算结果物化到PoolStats集合，池子列表查询时直接读取
+pub struct PoolStatsService {
+    database: Arc<Database>,
+    rpc_client: Arc<RpcClient>,
+    price_service: Arc<PriceService>,
+}
+
+impl PoolStatsService {
+    /// 创建新的池子统计服务实例
+    pub fn new(database: Arc<Database>, rpc_client: Arc<RpcClient>, price_service: Arc<PriceService>) -> Self {
+        Self {
+            database,
+            rpc_client,
+            price_service,
+        }
+    }
+
+    /// 重新计算全部活跃池子的统计快照，返回写入数量
+    pub async fn refresh_all(&self) -> AppResult<u64> {
+        let pools = self.database.clmm_pool_repository.find_active_pools().await?;
+        if pools.is_empty() {
+            return Ok(0);
+        }
+
+        let now = Utc::now();
+        let to = now.timestamp();
+
+        let mut volumes = HashMap::new();
+        let mut price_ranges = HashMap::new();
+        for period in StatsPeriod::ALL {
+            let from = to - period.seconds();
+            volumes.insert(period, self.database.aggregate_swap_volumes(from, to).await?);
+            price_ranges.insert(
+                period,
+                self.database
+                    .pool_candle_repository
+                    .find_price_ranges(period.candle_interval(), from)
+                    .await?,
+            );
+        }
+
+        let fee_rates = load_clmm_fee_rates(&self.database, &pools).await;
+        let reward_aprs = match self.load_reward_aprs(&pools, to as u64).await {
+            Ok(reward_aprs) => reward_aprs,
+            Err(e) => {
+                warn!("⚠️ 计算CLMM奖励APR失败，本轮奖励APR按0处理: {}", e);
+                HashMap::new()
+            }
+        };
+
+        let snapshots: Vec<PoolStatsSnapshot> = pools
+            .iter()
+            .map(|pool| {
+                let fee_rate = fee_rates.get(&pool.amm_config_address).copied();
+                let reward_apr = reward_aprs.get(&pool.pool_address).map_or(&[][..], Vec::as_slice);
+                let period_stats = |period: StatsPeriod| {
+                    build_period_stats(
+                        pool,
+                        period,
+                        volumes[&period].get(&pool.pool_address),
+                        fee_rate,
+                        price_ranges[&period].get(&pool.pool_address).copied(),
+                        reward_apr,
+                    )
+                };
+
+                PoolStatsSnapshot {
+                    id: None,
+                    pool_id: pool.pool_address.clone(),
+                    tvl: pool.tvl_info.as_ref().map_or(0.0, |tvl_info| tvl_info.tvl),
+                    day: period_stats(StatsPeriod::Day),
+                    week: period_stats(StatsPeriod::Week),
+                    month: period_stats(StatsPeriod::Month),
+                    updated_at: now,
+                }
+            })
+            .collect();
+
+        let written = self.database.pool_stats_repository.upsert_snapshots(&snapshots).await?;
+        info!(
+            "✅ 池子统计刷新完成: pools={}, traded_24h={}, rewarded={}",
+            written,
+            volumes[&StatsPeriod::Day].len(),
+            reward_aprs.len()
+        );
+        Ok(written)
+    }
+
+    /// 用统计快照填充池子列表的日/周/月统计，尚无快照的池子保持原值
+    pub async fn fill_pool_period_stats(&self, pools: &mut [PoolInfo]) -> AppResult<()> {
+        let pool_ids: Vec<String> = pools.iter().map(|pool| pool.id.clone()).collect();
+        let snapshots: HashMap<String, PoolStatsSnapshot> = self
+            .database
+            .pool_stats_repository
+            .find_by_pool_ids(&pool_ids)
+            .await?
+            .into_iter()
+            .map(|snapshot| (snapshot.pool_id.clone(), snapshot))
+            .collect();
+
+        for pool in pools.iter_mut() {
+            if let Some(snapshot) = snapshots.get(&pool.id) {
+                pool.day = Some(to_period_stats(&snapshot.day));
+                pool.week = Some(to_period_stats(&snapshot.week));
+                pool.month = Some(to_period_stats(&snapshot.month));
+            }
+        }
+
+        debug!("📊 填充池子统计: pools={}, snapshots={}", pools.len(), snapshots.len());
+        Ok(())
+    }
+
+    /// 根据链上奖励参数计算CLMM池子各奖励的APR，返回 池子地址 -> 各奖励APR
+    async fn load_reward_aprs(&self, pools: &[ClmmPool], now: u64) -> AppResult<HashMap<String, Vec<f64>>> {
+        let reward_pools: Vec<(&ClmmPool, Pubkey, f64)> = pools
+            .iter()
+            .filter(|pool| matches!(pool.pool_type, PoolType::Concentrated))
+            .filter_map(|pool| {
+                let tvl = pool.tvl_info.as_ref()?.tvl;
+                let pubkey = Pubkey::from_str(&pool.pool_address).ok()?;
+                (tvl > 0.0).then_some((pool, pubkey, tvl))
+            })
+            .collect();
+        if reward_pools.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        // 1. 批量读取池子状态，收集已初始化的奖励
+        let pool_pubkeys: Vec<Pubkey> = reward_pools.iter().map(|(_, pubkey, _)| *pubkey).collect();
+        let pool_accounts = fetch_multiple_accounts(&self.rpc_client, pool_pubkeys)
+            .await
+            .map_err(|e| anyhow::anyhow!("批量获取池子账户失败: {}", e))?;
+
+        let mut pool_rewards = Vec::new();
+        for ((pool, _, tvl), account) in reward_pools.iter().zip(pool_accounts) {
+            let Some(account) = account else {
+                continue;
+            };
+            match SolanaUtils::deserialize_anchor_account::<raydium_amm_v3::states::PoolState>(&account) {
+                Ok(pool_state) => {
+                    let rewards: Vec<_> = pool_state
+                        .reward_infos
+                        .iter()
+                        .filter(|reward| reward.initialized())
+                        .copied()
+                        .collect();
+                    if !rewards.is_empty() {
+                        pool_rewards.push((*pool, *tvl, rewards));
+                    }
+                }
+                Err(e) => warn!("⚠️ 解析池子状态失败: {} - {}", pool.pool_address, e),
+            }
+        }
+        if pool_rewards.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        // 2. 批量读取奖励代币精度
+        let reward_mints: Vec<Pubkey> = pool_rewards
+            .iter()
+            .flat_map(|(_, _, rewards)| rewards.iter().map(|reward| reward.token_mint))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let mint_accounts = fetch_multiple_accounts(&self.rpc_client, reward_mints.clone())
+            .await
+            .map_err(|e| anyhow::anyhow!("批量获取奖励代币账户失败: {}", e))?;
+        let mut mint_decimals = HashMap::new();
+        for (mint, account) in reward_mints.iter().zip(mint_accounts) {
+            if let Some(mint_state) = account
+                .as_ref()
+                .and_then(|account| PodStateWithExtensions::<PodMint>::unpack(&account.data).ok())
+            {
+                mint_decimals.insert(*mint, mint_state.base.decimals);
+            }
+        }
+
+        // 3. 奖励代币USD价格取自价格服务
+        let reward_mint_addresses: Vec<String> = reward_mints.iter().map(Pubkey::to_string).collect();
+        let usd_prices = self.price_service.get_prices(&reward_mint_addresses).await?;
+
+        let mut reward_aprs = HashMap::new();
+        for (pool, tvl, rewards) in pool_rewards {
+            let aprs = rewards
+                .iter()
+                .map(|reward| {
+                    // 奖励信息为packed结构，先按值复制字段
+                    let token_mint = reward.token_mint;
+                    let price_usd = usd_prices.get(&token_mint.to_string()).map(|price| price.price_usd);
+                    match (mint_decimals.get(&token_mint), price_usd) {
+                        (Some(decimals), Some(price_usd)) => calculate_reward_apr(
+                            reward.emissions_per_second_x64,
+                            *decimals,
+                            price_usd,
+                            tvl,
+                            (reward.open_time, reward.end_time),
+                            now,
+                        ),
+                        _ => 0.0,
+                    }
+                })
+                .collect();
+            reward_aprs.insert(pool.pool_address.clone(), aprs);
+        }
+
+        Ok(reward_aprs)
+    }
+}
+
+/// 加载CLMM池子的交易费率，返回 配置地址 -> 费率（小数），加载失败时返回空表
+pub async fn load_clmm_fee_rates(database: &Database, pools: &[ClmmPool]) -> HashMap<String, f64> {
+    let config_addresses: Vec<String> = pools
+        .iter()
+        .filter(|pool| matches!(pool.pool_type, PoolType::Concentrated))
+        .map(|pool| pool.amm_config_address.clone())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    match database
+        .clmm_config_repository
+        .get_configs_by_addresses_batch(&config_addresses)
+        .await
+    {
+        Ok(configs) => configs
+            .into_iter()
+            .map(|config| (config.config_id, config.trade_fee_rate as f64 / FEE_RATE_DENOMINATOR))
+            .collect(),
+        Err(e) => {
+            warn!("⚠️ 加载CLMM配置费率失败，本轮CLMM手续费按0处理: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
+/// 计算池子单个周期的统计
+///
+/// 交易量和手续费按池子TVL信息中的代币USD价格折算；价格区间按 10^(decimals_0 - decimals_1) 调整精度
+pub fn build_period_stats(
+    pool: &ClmmPool,
+    period: StatsPeriod,
+    volume: Option<&SwapVolume>,
+    fee_rate: Option<f64>,
+    price_range: Option<(f64, f64)>,
+    reward_apr: &[f64],
+) -> PoolPeriodStats {
+    let volume = volume.cloned().unwrap_or_default();
+    let tvl_info = pool.tvl_info.as_ref();
+    let tvl = tvl_info.map_or(0.0, |tvl_info| tvl_info.tvl);
+    let price_usd_0 = tvl_info.and_then(|tvl_info| tvl_info.price_usd_0);
+    let price_usd_1 = tvl_info.and_then(|tvl_info| tvl_info.price_usd_1);
+
+    let volume_1 = volume.volume_1 / 10f64.powi(pool.mint1.decimals as i32);
+    let (volume_usd, volume_fee) = price_swap_volume(pool, &volume, fee_rate, price_usd_0, price_usd_1);
+    let fee_apr = annualized_rate(volume_fee, tvl, period.days());
+
+    let price_scale = 10f64.powi(pool.mint0.decimals as i32 - pool.mint1.decimals as i32);
+    let (price_min, price_max) = price_range.map_or((0.0, 0.0), |(low, high)| (low * price_scale, high * price_scale));
+
+    PoolPeriodStats {
+        volume: volume_usd,
+        volume_quote: volume_1,
+        volume_fee,
+        apr: fee_apr + reward_apr.iter().sum::<f64>(),
+        fee_apr,
+        reward_apr: reward_apr.to_vec(),
+        price_min,
+        price_max,
+        trade_count: volume.trade_count,
+    }
+}
+
+/// 按代币USD价格折算池子的交换量，返回 (交易量USD, 手续费USD)
+///
+/// 交易量优先按token_0的USD价格计，无价格时按token_1；CLMM手续费按输入量乘交易费率，
+/// CPMM手续费取事件记录值
+pub fn price_swap_volume(
+    pool: &ClmmPool,
+    volume: &SwapVolume,
+    fee_rate: Option<f64>,
+    price_usd_0: Option<f64>,
+    price_usd_1: Option<f64>,
+) -> (f64, f64) {
+    let unit_0 = 10f64.powi(pool.mint0.decimals as i32);
+    let unit_1 = 10f64.powi(pool.mint1.decimals as i32);
+
+    let (fee_0, fee_1) = match (&pool.pool_type, fee_rate) {
+        (PoolType::Concentrated, Some(rate)) => (volume.input_0 * rate, volume.input_1 * rate),
+        (PoolType::Concentrated, None) => (0.0, 0.0),
+        (PoolType::Standard, _) => (volume.fee_0, volume.fee_1),
+    };
+
+    let volume_usd = match (price_usd_0, price_usd_1) {
+        (Some(price_0), _) => volume.volume_0 / unit_0 * price_0,
+        (None, Some(price_1)) => volume.volume_1 / unit_1 * price_1,
+        (None, None) => 0.0,
+    };
+    let volume_fee = fee_0 / unit_0 * price_usd_0.unwrap_or(0.0) + fee_1 / unit_1 * price_usd_1.unwrap_or(0.0);
+
+    (volume_usd, volume_fee)
+}
+
+/// 计算单个奖励的APR（百分比），奖励未开始或已结束时为0
+///
+/// 每秒发放量为Q64.64定点数，按奖励代币精度换算后年化，再乘以USD价格除以TVL
+pub fn calculate_reward_apr(
+    emissions_per_second_x64: u128,
+    decimals: u8,
+    price_usd: f64,
+    tvl: f64,
+    (open_time, end_time): (u64, u64),
+    now: u64,
+) -> f64 {
+    if tvl <= 0.0 || now < open_time || now >= end_time {
+        return 0.0;
+    }
+
+    let emissions_per_second = emissions_per_second_x64 as f64 / 2f64.powi(64) / 10f64.powi(decimals as i32);
+    emissions_per_second * SECONDS_PER_YEAR * price_usd / tvl * 100.0
+}
+
+/// 将统计快照转换为池子列表的周期统计
+fn to_period_stats(stats: &PoolPeriodStats) -> PeriodStats {
+    PeriodStats {
+        volume: stats.volume,
+        volume_quote: stats.volume_quote,
+        volume_fee: stats.volume_fee,
+        apr: stats.apr,
+        fee_apr: stats.fee_apr,
+        price_min: stats.price_min,
+        price_max: stats.price_max,
+        reward_apr: stats.reward_apr.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database::clmm::clmm_pool::model::{
+        DataSource, ExtensionInfo, PoolStatus, PriceInfo, SyncStatus, TokenInfo, TvlInfo, VaultInfo,
+    };
+
+    fn create_token(mint_address: &str, decimals: u8) -> TokenInfo {
+        TokenInfo {
+            mint_address: mint_address.to_string(),
+            decimals,
+            owner: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+            symbol: None,
+            name: None,
+            log_uri: None,
+            description: None,
+            external_url: None,
+            tags: None,
+            attributes: None,
+        }
+    }
+
+    /// SOL/USDC池子: 1 SOL = 150 USDC，TVL 300000 USD
+    fn create_pool(pool_type: PoolType) -> ClmmPool {
+        ClmmPool {
+            id: None,
+            pool_address: "test_pool_address".to_string(),
+            amm_config_address: "test_config_address".to_string(),
+            config_index: 0,
+            mint0: create_token("So11111111111111111111111111111111111111112", 9),
+            mint1: create_token("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", 6),
+            price_info: PriceInfo {
+                initial_price: 150.0,
+                sqrt_price_x64: "0".to_string(),
+                initial_tick: 0,
+                current_price: Some(150.0),
+                current_tick: None,
+            },
+            vault_info: VaultInfo {
+                token_vault_0: String::new(),
+                token_vault_1: String::new(),
+            },
+            extension_info: ExtensionInfo {
+                observation_address: String::new(),
+                tickarray_bitmap_extension: String::new(),
+            },
+            creator_wallet: "test_creator".to_string(),
+            open_time: 0,
+            api_created_at: 0,
+            api_created_slot: None,
+            updated_at: 0,
+            event_signature: None,
+            event_updated_slot: None,
+            event_confirmed_at: None,
+            event_updated_at: None,
+            transaction_info: None,
+            status: PoolStatus::Active,
+            sync_status: SyncStatus {
+                last_sync_at: 0,
+                sync_version: 1,
+                needs_sync: false,
+                sync_error: None,
+            },
+            pool_type,
+            data_source: DataSource::ChainEvent,
+            chain_confirmed: true,
+            tvl_info: Some(TvlInfo {
+                amount_0: 1_000.0,
+                amount_1: 150_000.0,
+                price_usd_0: Some(150.0),
+                price_usd_1: Some(1.0),
+                tvl: 300_000.0,
+                updated_at: 0,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_build_period_stats_clmm_fee_from_rate() {
+        let pool = create_pool(PoolType::Concentrated);
+        // 10 SOL输入换出1500 USDC，再以1500 USDC换回10 SOL
+        let volume = SwapVolume {
+            volume_0: 20_000_000_000.0,
+            volume_1: 3_000_000_000.0,
+            input_0: 10_000_000_000.0,
+            input_1: 1_500_000_000.0,
+            trade_count: 2,
+            ..Default::default()
+        };
+
+        let stats = build_period_stats(
+            &pool,
+            StatsPeriod::Day,
+            Some(&volume),
+            Some(0.0025),
+            Some((0.14, 0.16)),
+            &[10.0],
+        );
+
+        assert!((stats.volume - 3_000.0).abs() < 1e-6);
+        assert!((stats.volume_quote - 3_000.0).abs() < 1e-6);
+        // 手续费: 10 SOL × 0.25% × 150 + 1500 USDC × 0.25% = 7.5 USD
+        assert!((stats.volume_fee - 7.5).abs() < 1e-9);
+        assert!((stats.fee_apr - 7.5 / 300_000.0 * 365.0 * 100.0).abs() < 1e-9);
+        assert!((stats.apr - (stats.fee_apr + 10.0)).abs() < 1e-9);
+        assert!((stats.price_min - 140.0).abs() < 1e-6);
+        assert!((stats.price_max - 160.0).abs() < 1e-6);
+        assert_eq!(stats.trade_count, 2);
+    }
+
+    #[test]
+    fn test_build_period_stats_cpmm_fee_from_events() {
+        let pool = create_pool(PoolType::Standard);
+        let volume = SwapVolume {
+            volume_0: 7_000_000_000.0,
+            volume_1: 1_050_000_000.0,
+            input_1: 1_050_000_000.0,
+            fee_1: 2_625_000.0,
+            trade_count: 1,
+            ..Default::default()
+        };
+
+        let stats = build_period_stats(&pool, StatsPeriod::Week, Some(&volume), Some(0.01), None, &[]);
+        // CPMM忽略配置费率，使用事件记录的2.625 USDC手续费
+        assert!((stats.volume_fee - 2.625).abs() < 1e-9);
+        assert!((stats.fee_apr - 2.625 / 300_000.0 * 365.0 / 7.0 * 100.0).abs() < 1e-9);
+        assert_eq!(stats.apr, stats.fee_apr);
+        assert_eq!(stats.price_min, 0.0);
+
+        let empty = build_period_stats(&pool, StatsPeriod::Month, None, None, None, &[]);
+        assert_eq!(empty, PoolPeriodStats::default());
+    }
+
+    #[test]
+    fn test_calculate_reward_apr() {
+        // 每秒发放1个奖励代币（精度6），价格0.1 USD，TVL 31536 USD => 年化10000%
+        let emissions = 1_000_000u128 << 64;
+        let apr = calculate_reward_apr(emissions, 6, 0.1, 31_536.0, (100, 200), 150);
+        assert!((apr - 10_000.0).abs() < 1e-6);
+
+        assert_eq!(calculate_reward_apr(emissions, 6, 0.1, 31_536.0, (100, 200), 50), 0.0);
+        assert_eq!(calculate_reward_apr(emissions, 6, 0.1, 31_536.0, (100, 200), 200), 0.0);
+    }
+}
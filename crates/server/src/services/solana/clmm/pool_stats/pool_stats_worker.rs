@@ -0,0 +1,72 @@
+use super::pool_stats_service::PoolStatsService;
+use std::time::Duration;
+use tracing::{error, info};
+
+/// 池子统计刷新任务配置
+#[derive(Debug, Clone)]
+pub struct PoolStatsWorkerConfig {
+    /// 是否启用定期刷新
+    pub enabled: bool,
+    /// 刷新间隔（秒）
+    pub refresh_interval_secs: u64,
+}
+
+impl Default for PoolStatsWorkerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            refresh_interval_secs: 300,
+        }
+    }
+}
+
+impl PoolStatsWorkerConfig {
+    /// 从环境变量加载配置
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: std::env::var("POOL_STATS_WORKER_ENABLED")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.enabled),
+            refresh_interval_secs: std::env::var("POOL_STATS_REFRESH_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.refresh_interval_secs),
+        }
+    }
+}
+
+/// 池子统计刷新任务
+///
+/// 定期重新计算全部活跃池子的24h/7d/30d交易量、手续费和APR并写入PoolStats集合
+pub struct PoolStatsWorker {
+    service: PoolStatsService,
+    config: PoolStatsWorkerConfig,
+}
+
+impl PoolStatsWorker {
+    /// 创建新的池子统计刷新任务
+    pub fn new(service: PoolStatsService, config: PoolStatsWorkerConfig) -> Self {
+        Self { service, config }
+    }
+
+    /// 启动刷新循环
+    pub async fn start(&self) {
+        info!(
+            "🚀 启动池子统计刷新任务: 刷新间隔{}秒",
+            self.config.refresh_interval_secs
+        );
+
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.refresh_interval_secs.max(10)));
+        loop {
+            interval.tick().await;
+
+            match self.service.refresh_all().await {
+                Ok(0) => {}
+                Ok(count) => info!("✅ 本轮刷新{}个池子统计", count),
+                Err(e) => error!("❌ 池子统计刷新失败: {}", e),
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//! 代币USD价格相关服务模块
+
+pub mod price_graph;
+pub mod price_service;
+
+pub use price_graph::{DerivedPrice, PriceConfidence, PriceGraphConfig, PriceSource};
+pub use price_service::{PriceService, PriceServiceConfig};
@@ -0,0 +1,293 @@
+//! 基于池子图的代币USD定价
+//!
+//! 以池子为边、代币为节点建图，稳定币(USDC/USDT)价格固定为1作为锚点。
+//! 对每个代币选择"最浅一跳流动性最大"的路径定价（最宽路径），SOL通常经由稳定币池子定价后
+//! 成为其他代币的主要中间锚点。每一跳的流动性按已定价一侧的金库USD价值计算，
+//! 低于阈值的池子不参与定价，避免通过浅池操纵价格
+
+use crate::services::solana::clmm::pool::tvl::{PoolQuote, STABLE_MINTS};
+use database::clmm::clmm_pool::ClmmPool;
+use std::collections::HashMap;
+
+/// 价格来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceSource {
+    /// 稳定币锚定价格
+    Stablecoin,
+    /// 沿池子路径推导
+    Pool,
+}
+
+impl PriceSource {
+    /// 来源字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriceSource::Stablecoin => "stablecoin",
+            PriceSource::Pool => "pool",
+        }
+    }
+}
+
+/// 价格置信度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PriceConfidence {
+    Low,
+    Medium,
+    High,
+}
+
+impl PriceConfidence {
+    /// 置信度字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriceConfidence::Low => "low",
+            PriceConfidence::Medium => "medium",
+            PriceConfidence::High => "high",
+        }
+    }
+}
+
+/// 定价参数
+#[derive(Debug, Clone)]
+pub struct PriceGraphConfig {
+    /// 参与定价的池子在已定价一侧的最小流动性（USD）
+    pub min_liquidity_usd: f64,
+    /// 路径最浅一跳达到该流动性（USD）且跳数不超过2时视为高置信度
+    pub high_confidence_liquidity_usd: f64,
+    /// 从锚点出发的最大跳数
+    pub max_hops: usize,
+}
+
+impl Default for PriceGraphConfig {
+    fn default() -> Self {
+        Self {
+            min_liquidity_usd: 1_000.0,
+            high_confidence_liquidity_usd: 100_000.0,
+            max_hops: 3,
+        }
+    }
+}
+
+/// 推导出的代币USD价格
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivedPrice {
+    /// USD价格（按精度换算后的单个代币）
+    pub price_usd: f64,
+    /// 价格来源
+    pub source: PriceSource,
+    /// 置信度
+    pub confidence: PriceConfidence,
+    /// 路径上最浅一跳的流动性（USD），稳定币为无穷大
+    pub liquidity_usd: f64,
+    /// 从锚点出发经过的池子地址
+    pub route: Vec<String>,
+}
+
+/// 定价图中的一条边（池子）
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolEdge {
+    /// 池子地址
+    pub pool_id: String,
+    /// 池子报价
+    pub quote: PoolQuote,
+}
+
+impl PoolEdge {
+    /// 根据池子上次计算的金库余额构造边，缺少余额或价格时返回None
+    pub fn from_pool(pool: &ClmmPool) -> Option<Self> {
+        Some(Self {
+            pool_id: pool.pool_address.clone(),
+            quote: PoolQuote::from_stored(pool)?,
+        })
+    }
+}
+
+/// 沿最宽路径推导各代币的USD价格
+///
+/// 每轮从候选中确定最浅一跳流动性最大的代币（瓶颈最短路），保证每个代币使用的路径
+/// 在满足跳数限制的前提下流动性最深
+pub fn derive_prices(edges: &[PoolEdge], config: &PriceGraphConfig) -> HashMap<String, DerivedPrice> {
+    let mut adjacency: HashMap<&str, Vec<&PoolEdge>> = HashMap::new();
+    for edge in edges {
+        adjacency.entry(edge.quote.mint_0.as_str()).or_default().push(edge);
+        adjacency.entry(edge.quote.mint_1.as_str()).or_default().push(edge);
+    }
+
+    let mut settled: HashMap<String, DerivedPrice> = STABLE_MINTS
+        .iter()
+        .map(|mint| {
+            (
+                mint.to_string(),
+                DerivedPrice {
+                    price_usd: 1.0,
+                    source: PriceSource::Stablecoin,
+                    confidence: PriceConfidence::High,
+                    liquidity_usd: f64::INFINITY,
+                    route: Vec::new(),
+                },
+            )
+        })
+        .collect();
+    let mut frontier: Vec<String> = settled.keys().cloned().collect();
+    let mut candidates: HashMap<String, DerivedPrice> = HashMap::new();
+
+    loop {
+        // 1. 从新确定价格的代币向外扩展候选
+        for mint in frontier.drain(..) {
+            let from = settled[&mint].clone();
+            if from.route.len() >= config.max_hops {
+                continue;
+            }
+
+            for edge in adjacency.get(mint.as_str()).into_iter().flatten() {
+                let quote = &edge.quote;
+                let (target_mint, target_price, depth) = if quote.mint_0 == mint {
+                    (
+                        &quote.mint_1,
+                        from.price_usd / quote.price,
+                        quote.amount_0 * from.price_usd,
+                    )
+                } else {
+                    (
+                        &quote.mint_0,
+                        from.price_usd * quote.price,
+                        quote.amount_1 * from.price_usd,
+                    )
+                };
+                if settled.contains_key(target_mint)
+                    || depth < config.min_liquidity_usd
+                    || !target_price.is_finite()
+                    || target_price <= 0.0
+                {
+                    continue;
+                }
+
+                let liquidity_usd = from.liquidity_usd.min(depth);
+                let is_better = candidates
+                    .get(target_mint)
+                    .map_or(true, |current| liquidity_usd > current.liquidity_usd);
+                if is_better {
+                    let mut route = from.route.clone();
+                    route.push(edge.pool_id.clone());
+                    candidates.insert(
+                        target_mint.clone(),
+                        DerivedPrice {
+                            price_usd: target_price,
+                            source: PriceSource::Pool,
+                            confidence: confidence_for(liquidity_usd, route.len(), config),
+                            liquidity_usd,
+                            route,
+                        },
+                    );
+                }
+            }
+        }
+
+        // 2. 确定候选中路径最深的代币
+        let Some(best_mint) = candidates
+            .iter()
+            .max_by(|a, b| a.1.liquidity_usd.total_cmp(&b.1.liquidity_usd))
+            .map(|(mint, _)| mint.clone())
+        else {
+            break;
+        };
+        let price = candidates.remove(&best_mint).expect("候选代币必然存在");
+        settled.insert(best_mint.clone(), price);
+        frontier.push(best_mint);
+    }
+
+    settled
+}
+
+/// 根据路径最浅一跳的流动性和跳数评估置信度
+fn confidence_for(liquidity_usd: f64, hops: usize, config: &PriceGraphConfig) -> PriceConfidence {
+    if liquidity_usd >= config.high_confidence_liquidity_usd && hops <= 2 {
+        PriceConfidence::High
+    } else if liquidity_usd >= config.min_liquidity_usd * 10.0 {
+        PriceConfidence::Medium
+    } else {
+        PriceConfidence::Low
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::{SOL_MINT, USDC_MINT_STANDARD, USDT_MINT};
+
+    const TOKEN_MINT: &str = "4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R";
+
+    fn edge(pool_id: &str, mint_0: &str, mint_1: &str, price: f64, amount_0: f64, amount_1: f64) -> PoolEdge {
+        PoolEdge {
+            pool_id: pool_id.to_string(),
+            quote: PoolQuote {
+                mint_0: mint_0.to_string(),
+                mint_1: mint_1.to_string(),
+                price,
+                amount_0,
+                amount_1,
+            },
+        }
+    }
+
+    #[test]
+    fn test_derive_prices_through_sol() {
+        let edges = vec![
+            // SOL/USDC: 1 SOL = 150 USDC，USDC一侧150万
+            edge("sol_usdc", SOL_MINT, USDC_MINT_STANDARD, 150.0, 10_000.0, 1_500_000.0),
+            // TOKEN/SOL: 1 TOKEN = 0.01 SOL，SOL一侧500 SOL
+            edge("token_sol", TOKEN_MINT, SOL_MINT, 0.01, 50_000.0, 500.0),
+        ];
+
+        let prices = derive_prices(&edges, &PriceGraphConfig::default());
+        assert_eq!(prices[USDC_MINT_STANDARD].source, PriceSource::Stablecoin);
+
+        let sol = &prices[SOL_MINT];
+        assert!((sol.price_usd - 150.0).abs() < 1e-9);
+        assert_eq!(sol.route, vec!["sol_usdc".to_string()]);
+        assert_eq!(sol.confidence, PriceConfidence::High);
+
+        let token = &prices[TOKEN_MINT];
+        assert!((token.price_usd - 1.5).abs() < 1e-9);
+        assert_eq!(token.route, vec!["sol_usdc".to_string(), "token_sol".to_string()]);
+        // 瓶颈为SOL一侧的 500 × 150 = 75000 USD
+        assert!((token.liquidity_usd - 75_000.0).abs() < 1e-6);
+        assert_eq!(token.confidence, PriceConfidence::Medium);
+    }
+
+    #[test]
+    fn test_derive_prices_prefers_deepest_path_and_skips_shallow_pools() {
+        let edges = vec![
+            edge("sol_usdc", SOL_MINT, USDC_MINT_STANDARD, 150.0, 10_000.0, 1_500_000.0),
+            // 浅池直连USDT：USDT一侧仅500 USD，低于阈值且报价被操纵为10
+            edge("token_usdt", TOKEN_MINT, USDT_MINT, 10.0, 50.0, 500.0),
+            // 经SOL的深池
+            edge("token_sol", TOKEN_MINT, SOL_MINT, 0.01, 50_000.0, 500.0),
+        ];
+
+        let prices = derive_prices(&edges, &PriceGraphConfig::default());
+        let token = &prices[TOKEN_MINT];
+        assert!((token.price_usd - 1.5).abs() < 1e-9);
+        assert_eq!(token.route.last().map(String::as_str), Some("token_sol"));
+
+        // 只有浅池时无法定价
+        let prices = derive_prices(&edges[1..2], &PriceGraphConfig::default());
+        assert!(!prices.contains_key(TOKEN_MINT));
+    }
+
+    #[test]
+    fn test_derive_prices_respects_max_hops() {
+        let edges = vec![
+            edge("sol_usdc", SOL_MINT, USDC_MINT_STANDARD, 150.0, 10_000.0, 1_500_000.0),
+            edge("token_sol", TOKEN_MINT, SOL_MINT, 0.01, 50_000.0, 500.0),
+        ];
+        let config = PriceGraphConfig {
+            max_hops: 1,
+            ..Default::default()
+        };
+
+        let prices = derive_prices(&edges, &config);
+        assert!(prices.contains_key(SOL_MINT));
+        assert!(!prices.contains_key(TOKEN_MINT));
+    }
+}
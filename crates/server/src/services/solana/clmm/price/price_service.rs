@@ -0,0 +1,139 @@
+use super::price_graph::{derive_prices, DerivedPrice, PoolEdge, PriceGraphConfig};
+use database::Database;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::info;
+use utils::{AppResult, SOL_MINT};
+
+/// 价格服务配置
+#[derive(Debug, Clone)]
+pub struct PriceServiceConfig {
+    /// 定价参数
+    pub graph: PriceGraphConfig,
+    /// 价格缓存有效期（秒）
+    pub cache_ttl_secs: u64,
+}
+
+impl Default for PriceServiceConfig {
+    fn default() -> Self {
+        Self {
+            graph: PriceGraphConfig::default(),
+            cache_ttl_secs: 60,
+        }
+    }
+}
+
+impl PriceServiceConfig {
+    /// 从环境变量加载配置
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            graph: PriceGraphConfig {
+                min_liquidity_usd: std::env::var("PRICE_MIN_LIQUIDITY_USD")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(defaults.graph.min_liquidity_usd),
+                high_confidence_liquidity_usd: std::env::var("PRICE_HIGH_CONFIDENCE_LIQUIDITY_USD")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(defaults.graph.high_confidence_liquidity_usd),
+                max_hops: std::env::var("PRICE_MAX_HOPS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(defaults.graph.max_hops),
+            },
+            cache_ttl_secs: std::env::var("PRICE_CACHE_TTL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.cache_ttl_secs),
+        }
+    }
+}
+
+/// 缓存的全量价格
+struct PriceCache {
+    prices: HashMap<String, DerivedPrice>,
+    built_at: Instant,
+}
+
+/// 代币价格服务 - 根据本平台CLMM/CPMM池子推导代币USD价格
+///
+/// 池子报价和金库余额取自池子同步服务定期刷新的TVL信息，全量价格在有效期内缓存，
+/// 过期后由下一次查询重新构建
+pub struct PriceService {
+    database: Arc<Database>,
+    config: PriceServiceConfig,
+    cache: RwLock<Option<PriceCache>>,
+}
+
+impl PriceService {
+    /// 创建新的价格服务实例
+    pub fn new(database: Arc<Database>, config: PriceServiceConfig) -> Self {
+        Self {
+            database,
+            config,
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// 查询指定代币的价格，无法定价的代币不在结果中
+    pub async fn get_prices(&self, mints: &[String]) -> AppResult<HashMap<String, DerivedPrice>> {
+        let ttl = Duration::from_secs(self.config.cache_ttl_secs);
+        let lookup = |cache: &PriceCache| {
+            mints
+                .iter()
+                .filter_map(|mint| cache.prices.get(mint).map(|price| (mint.clone(), price.clone())))
+                .collect()
+        };
+
+        if let Some(cache) = self.cache.read().await.as_ref() {
+            if cache.built_at.elapsed() < ttl {
+                return Ok(lookup(cache));
+            }
+        }
+
+        let mut guard = self.cache.write().await;
+        // 等待写锁期间可能已被其他请求重建
+        if let Some(cache) = guard.as_ref() {
+            if cache.built_at.elapsed() < ttl {
+                return Ok(lookup(cache));
+            }
+        }
+
+        let cache = PriceCache {
+            prices: self.build_prices().await?,
+            built_at: Instant::now(),
+        };
+        let result = lookup(&cache);
+        *guard = Some(cache);
+        Ok(result)
+    }
+
+    /// 查询SOL的USD价格
+    pub async fn get_sol_price(&self) -> AppResult<Option<f64>> {
+        let prices = self.get_prices(&[SOL_MINT.to_string()]).await?;
+        Ok(prices.get(SOL_MINT).map(|price| price.price_usd))
+    }
+
+    /// 按价格服务的定价参数推导给定定价图的价格（如按历史收盘价构造的定价图）
+    pub fn derive(&self, edges: &[PoolEdge]) -> HashMap<String, DerivedPrice> {
+        derive_prices(edges, &self.config.graph)
+    }
+
+    /// 根据全部活跃池子重新推导价格
+    async fn build_prices(&self) -> AppResult<HashMap<String, DerivedPrice>> {
+        let pools = self.database.clmm_pool_repository.find_active_pools().await?;
+        let edges: Vec<PoolEdge> = pools.iter().filter_map(PoolEdge::from_pool).collect();
+        let prices = derive_prices(&edges, &self.config.graph);
+
+        info!(
+            "💰 代币价格已重建: pools={}, edges={}, priced={}",
+            pools.len(),
+            edges.len(),
+            prices.len()
+        );
+        Ok(prices)
+    }
+}
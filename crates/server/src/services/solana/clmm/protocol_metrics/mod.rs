@@ -0,0 +1,7 @@
+//! 协议级每日指标（TVL、交易量、手续费、活跃交易者等）相关服务模块
+
+pub mod protocol_metrics_service;
+pub mod protocol_metrics_worker;
+
+pub use protocol_metrics_service::ProtocolMetricsService;
+pub use protocol_metrics_worker::{ProtocolMetricsWorker, ProtocolMetricsWorkerConfig};
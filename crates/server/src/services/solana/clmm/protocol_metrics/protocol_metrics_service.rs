@@ -0,0 +1,376 @@
+use crate::dtos::solana::clmm::analytics::protocol_metrics::{
+    ProtocolDailyMetricsData, ProtocolMetricsQuery, ProtocolMetricsRebuildRequest, ProtocolMetricsRebuildResponse,
+    ProtocolMetricsResponse, ProtocolMetricsSummary,
+};
+use crate::services::solana::clmm::pool::tvl::PoolQuote;
+use crate::services::solana::clmm::pool_stats::pool_stats_service::{load_clmm_fee_rates, price_swap_volume};
+use crate::services::solana::clmm::price::price_graph::PoolEdge;
+use crate::services::solana::clmm::price::{DerivedPrice, PriceService};
+use chrono::{DateTime, NaiveDate, Utc};
+use database::analytics::candle::CandleInterval;
+use database::analytics::pool_stats::SwapVolume;
+use database::analytics::protocol_metrics::{
+    day_start_of, format_day, ProgramDailyMetrics, ProtocolDailyMetrics, SECONDS_PER_DAY,
+};
+use database::clmm::clmm_pool::{ClmmPool, PoolType};
+use database::Database;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::{debug, info};
+use utils::{AppError, AppResult};
+
+/// 默认查询天数
+const DEFAULT_QUERY_DAYS: i64 = 30;
+
+/// 单次查询或重建的最大天数
+const MAX_RANGE_DAYS: i64 = 366;
+
+/// 协议指标服务 - 生成并提供协议级每日指标时间序列
+///
+/// 交易量和手续费来自已索引的交换事件，按当天K线收盘价经定价图推导的代币USD价格折算；
+/// 交易者、新池子、新仓位、代币发射和推荐领取来自对应事件集合。
+/// TVL无法回溯，只在计算当天时写入当前值，重建历史日期时保留已有快照中的TVL
+pub struct ProtocolMetricsService {
+    database: Arc<Database>,
+    price_service: Arc<PriceService>,
+}
+
+impl ProtocolMetricsService {
+    /// 创建新的协议指标服务实例
+    pub fn new(database: Arc<Database>, price_service: Arc<PriceService>) -> Self {
+        Self {
+            database,
+            price_service,
+        }
+    }
+
+    /// 重新计算最近几天（含今天）的指标，返回写入天数
+    ///
+    /// 除今天外同时重算前几天，补上延迟入库的事件
+    pub async fn refresh_recent(&self, lookback_days: i64) -> AppResult<u64> {
+        let now = Utc::now();
+        let today = day_start_of(now.timestamp());
+        let first_day = today - (lookback_days.max(1) - 1) * SECONDS_PER_DAY;
+
+        self.build_days(first_day, today, now).await
+    }
+
+    /// 管理员功能：重建指定日期范围的指标
+    pub async fn rebuild(&self, request: &ProtocolMetricsRebuildRequest) -> AppResult<ProtocolMetricsRebuildResponse> {
+        let now = Utc::now();
+        let today = day_start_of(now.timestamp());
+        let (first_day, last_day) = resolve_range(Some(request.start.as_str()), Some(request.end.as_str()), today)?;
+
+        let days = self.build_days(first_day, last_day, now).await?;
+        info!(
+            "✅ 协议每日指标重建完成: {} ~ {}, days={}",
+            request.start, request.end, days
+        );
+
+        Ok(ProtocolMetricsRebuildResponse {
+            start: format_day(first_day),
+            end: format_day(last_day),
+            days,
+        })
+    }
+
+    /// 查询日期范围内的每日指标
+    pub async fn get_daily_metrics(&self, query: &ProtocolMetricsQuery) -> AppResult<ProtocolMetricsResponse> {
+        let today = day_start_of(Utc::now().timestamp());
+        let (first_day, last_day) = resolve_range(query.start.as_deref(), query.end.as_deref(), today)?;
+
+        let series: Vec<ProtocolDailyMetricsData> = self
+            .database
+            .protocol_metrics_repository
+            .find_range(first_day, last_day)
+            .await?
+            .iter()
+            .map(ProtocolDailyMetricsData::from)
+            .collect();
+
+        Ok(ProtocolMetricsResponse {
+            start: format_day(first_day),
+            end: format_day(last_day),
+            summary: summarize_series(&series),
+            series,
+        })
+    }
+
+    /// 逐日计算并写入`[first_day, last_day]`的指标，返回写入天数
+    async fn build_days(&self, first_day: i64, last_day: i64, now: DateTime<Utc>) -> AppResult<u64> {
+        let today = day_start_of(now.timestamp());
+        let last_day = last_day.min(today);
+
+        // 1. 汇总每天的交换量
+        let mut daily_volumes = Vec::new();
+        let mut day = first_day;
+        while day <= last_day {
+            let volumes = self.database.aggregate_swap_volumes(day, day + SECONDS_PER_DAY).await?;
+            daily_volumes.push((day, volumes));
+            day += SECONDS_PER_DAY;
+        }
+
+        // 2. 活跃池子之外补充有成交的其他池子，TVL只统计活跃池子
+        let active_pools = self.database.clmm_pool_repository.find_active_pools().await?;
+        let (current_clmm_tvl, current_cpmm_tvl) = sum_tvl(&active_pools);
+        let pools = self.with_traded_pools(active_pools, &daily_volumes).await?;
+        let fee_rates = load_clmm_fee_rates(&self.database, &pools).await;
+
+        let mut written = 0;
+        for (day, volumes) in daily_volumes {
+            let to = day + SECONDS_PER_DAY;
+            let activity = self.database.aggregate_daily_activity(day, to).await?;
+
+            // 3. 按当天收盘价推导代币USD价格
+            let closes = self
+                .database
+                .pool_candle_repository
+                .find_latest_closes(CandleInterval::OneDay, to)
+                .await?;
+            let prices = self.price_service.derive(&day_price_edges(&pools, &closes));
+
+            let (mut clmm, mut cpmm) = summarize_pool_volumes(&pools, &volumes, &fee_rates, &prices);
+            clmm.active_traders = activity.clmm_traders.len() as u64;
+            clmm.new_pools = activity.clmm_new_pools;
+            clmm.new_positions = activity.clmm_new_positions;
+            cpmm.active_traders = activity.cpmm_traders.len() as u64;
+            cpmm.new_pools = activity.cpmm_new_pools;
+            cpmm.new_positions = activity.cpmm_new_positions;
+
+            if day == today {
+                clmm.tvl = current_clmm_tvl;
+                cpmm.tvl = current_cpmm_tvl;
+            } else if let Some(existing) = self.database.protocol_metrics_repository.find_by_day(day).await? {
+                clmm.tvl = existing.clmm.tvl;
+                cpmm.tvl = existing.cpmm.tvl;
+            }
+
+            let metrics = ProtocolDailyMetrics::new(day, clmm, cpmm, &activity, now);
+            self.database.protocol_metrics_repository.upsert_day(&metrics).await?;
+            debug!(
+                "📊 协议每日指标: date={}, volume={:.2}, tvl={:.2}, traders={}",
+                metrics.date, metrics.volume, metrics.tvl, metrics.active_traders
+            );
+
+            written += 1;
+        }
+
+        Ok(written)
+    }
+
+    /// 在活跃池子之外补充加载区间内有成交的其他池子（如已暂停或关闭的池子）
+    async fn with_traded_pools(
+        &self,
+        mut pools: Vec<ClmmPool>,
+        daily_volumes: &[(i64, HashMap<String, SwapVolume>)],
+    ) -> AppResult<Vec<ClmmPool>> {
+        let known: HashSet<&str> = pools.iter().map(|pool| pool.pool_address.as_str()).collect();
+        let missing: Vec<String> = daily_volumes
+            .iter()
+            .flat_map(|(_, volumes)| volumes.keys())
+            .filter(|pool_id| !known.contains(pool_id.as_str()))
+            .cloned()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        if missing.is_empty() {
+            return Ok(pools);
+        }
+
+        let traded = self
+            .database
+            .clmm_pool_repository
+            .find_by_pool_addresses(&missing)
+            .await?;
+        debug!("📊 补充加载有成交的非活跃池子: {}/{}", traded.len(), missing.len());
+        pools.extend(traded);
+        Ok(pools)
+    }
+}
+
+/// 按K线收盘价构造定价图：报价取收盘价（按精度调整），流动性取池子最近记录的金库余额
+///
+/// 金库余额只用于选择定价路径和过滤浅池；截至当天尚无K线的池子不参与定价
+fn day_price_edges(pools: &[ClmmPool], closes: &HashMap<String, f64>) -> Vec<PoolEdge> {
+    pools
+        .iter()
+        .filter_map(|pool| {
+            let close = closes.get(&pool.pool_address)?;
+            let tvl_info = pool.tvl_info.as_ref()?;
+            let price = close * 10f64.powi(pool.mint0.decimals as i32 - pool.mint1.decimals as i32);
+            if !price.is_finite() || price <= 0.0 {
+                return None;
+            }
+
+            Some(PoolEdge {
+                pool_id: pool.pool_address.clone(),
+                quote: PoolQuote {
+                    mint_0: pool.mint0.mint_address.clone(),
+                    mint_1: pool.mint1.mint_address.clone(),
+                    price,
+                    amount_0: tvl_info.amount_0,
+                    amount_1: tvl_info.amount_1,
+                },
+            })
+        })
+        .collect()
+}
+
+/// 按程序汇总池子的USD交易量、手续费和成交笔数
+///
+/// 复用池子统计的折算规则（CLMM按费率计算手续费，CPMM取事件记录值），
+/// 池子记录缺失的交换量无法确定精度和程序类型，不计入
+pub fn summarize_pool_volumes(
+    pools: &[ClmmPool],
+    volumes: &HashMap<String, SwapVolume>,
+    fee_rates: &HashMap<String, f64>,
+    prices: &HashMap<String, DerivedPrice>,
+) -> (ProgramDailyMetrics, ProgramDailyMetrics) {
+    let mut clmm = ProgramDailyMetrics::default();
+    let mut cpmm = ProgramDailyMetrics::default();
+
+    for pool in pools {
+        let Some(volume) = volumes.get(&pool.pool_address) else {
+            continue;
+        };
+        let fee_rate = fee_rates.get(&pool.amm_config_address).copied();
+        let price_usd = |mint: &str| prices.get(mint).map(|price| price.price_usd);
+        let (volume_usd, volume_fee) = price_swap_volume(
+            pool,
+            volume,
+            fee_rate,
+            price_usd(&pool.mint0.mint_address),
+            price_usd(&pool.mint1.mint_address),
+        );
+
+        let program = match pool.pool_type {
+            PoolType::Concentrated => &mut clmm,
+            PoolType::Standard => &mut cpmm,
+        };
+        program.volume += volume_usd;
+        program.fees += volume_fee;
+        program.trade_count += volume.trade_count;
+    }
+
+    (clmm, cpmm)
+}
+
+/// 按程序汇总池子当前TVL，返回 (CLMM, CPMM)
+fn sum_tvl(pools: &[ClmmPool]) -> (f64, f64) {
+    pools.iter().fold((0.0, 0.0), |(clmm, cpmm), pool| {
+        let tvl = pool.tvl_info.as_ref().map_or(0.0, |tvl_info| tvl_info.tvl);
+        match pool.pool_type {
+            PoolType::Concentrated => (clmm + tvl, cpmm),
+            PoolType::Standard => (clmm, cpmm + tvl),
+        }
+    })
+}
+
+/// 解析日期范围，返回 (起始日, 截止日) 的起始时间
+///
+/// 截止日默认今天且不能晚于今天，起始日默认截止日前29天
+pub fn resolve_range(start: Option<&str>, end: Option<&str>, today: i64) -> AppResult<(i64, i64)> {
+    let last_day = match end {
+        Some(end) => parse_day(end)?,
+        None => today,
+    };
+    if last_day > today {
+        return Err(AppError::BadRequest("截止日期不能晚于今天".to_string()));
+    }
+
+    let first_day = match start {
+        Some(start) => parse_day(start)?,
+        None => last_day - (DEFAULT_QUERY_DAYS - 1) * SECONDS_PER_DAY,
+    };
+    if first_day > last_day {
+        return Err(AppError::BadRequest("起始日期不能晚于截止日期".to_string()));
+    }
+    if (last_day - first_day) / SECONDS_PER_DAY + 1 > MAX_RANGE_DAYS {
+        return Err(AppError::BadRequest(format!("日期范围不能超过{}天", MAX_RANGE_DAYS)));
+    }
+
+    Ok((first_day, last_day))
+}
+
+/// 解析YYYY-MM-DD日期为当天起始时间（UTC）
+fn parse_day(date: &str) -> AppResult<i64> {
+    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| AppError::BadRequest(format!("无效的日期: {}，应为YYYY-MM-DD", date)))?;
+    Ok(date.and_hms_opt(0, 0, 0).map_or(0, |time| time.and_utc().timestamp()))
+}
+
+/// 汇总区间内的流量指标，TVL取最后一天
+fn summarize_series(series: &[ProtocolDailyMetricsData]) -> ProtocolMetricsSummary {
+    let mut summary = series
+        .iter()
+        .fold(ProtocolMetricsSummary::default(), |mut summary, day| {
+            summary.volume += day.volume;
+            summary.fees += day.fees;
+            summary.trade_count += day.trade_count;
+            summary.new_pools += day.new_pools;
+            summary.new_positions += day.new_positions;
+            summary.launches += day.launches;
+            summary.referral_claims += day.referral_claims;
+            summary
+        });
+    summary.tvl = series.last().map_or(0.0, |day| day.tvl);
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::solana::clmm::analytics::protocol_metrics::ProgramMetricsData;
+
+    /// 2024-01-10 00:00:00 UTC
+    const TODAY: i64 = 1_704_844_800;
+
+    #[test]
+    fn test_resolve_range_defaults_and_limits() {
+        let (first_day, last_day) = resolve_range(None, None, TODAY).unwrap();
+        assert_eq!(last_day, TODAY);
+        assert_eq!(format_day(first_day), "2023-12-12");
+
+        let (first_day, last_day) = resolve_range(Some("2024-01-01"), Some("2024-01-05"), TODAY).unwrap();
+        assert_eq!(format_day(first_day), "2024-01-01");
+        assert_eq!(format_day(last_day), "2024-01-05");
+
+        assert!(resolve_range(Some("2024-01-05"), Some("2024-01-01"), TODAY).is_err());
+        assert!(resolve_range(None, Some("2024-01-11"), TODAY).is_err());
+        assert!(resolve_range(Some("2022-01-01"), None, TODAY).is_err());
+        assert!(resolve_range(Some("2024/01/01"), None, TODAY).is_err());
+    }
+
+    #[test]
+    fn test_summarize_series() {
+        let day = |date: &str, tvl: f64, volume: f64, launches: u64| ProtocolDailyMetricsData {
+            date: date.to_string(),
+            time: 0,
+            tvl,
+            volume,
+            fees: volume * 0.0025,
+            trade_count: 10,
+            active_traders: 3,
+            new_pools: 1,
+            new_positions: 2,
+            launches,
+            referral_claims: 1,
+            clmm: ProgramMetricsData::from(&ProgramDailyMetrics::default()),
+            cpmm: ProgramMetricsData::from(&ProgramDailyMetrics::default()),
+        };
+        let series = vec![
+            day("2024-01-01", 1_000.0, 400.0, 1),
+            day("2024-01-02", 1_200.0, 600.0, 2),
+        ];
+
+        let summary = summarize_series(&series);
+        assert_eq!(summary.tvl, 1_200.0);
+        assert_eq!(summary.volume, 1_000.0);
+        assert!((summary.fees - 2.5).abs() < 1e-9);
+        assert_eq!(summary.trade_count, 20);
+        assert_eq!(summary.new_positions, 4);
+        assert_eq!(summary.launches, 3);
+        assert_eq!(summary.referral_claims, 2);
+        assert_eq!(summarize_series(&[]), ProtocolMetricsSummary::default());
+    }
+}
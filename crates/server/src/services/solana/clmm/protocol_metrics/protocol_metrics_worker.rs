@@ -0,0 +1,80 @@
+use super::protocol_metrics_service::ProtocolMetricsService;
+use std::time::Duration;
+use tracing::{error, info};
+
+/// 协议指标快照任务配置
+#[derive(Debug, Clone)]
+pub struct ProtocolMetricsWorkerConfig {
+    /// 是否启用定期快照
+    pub enabled: bool,
+    /// 刷新间隔（秒）
+    pub refresh_interval_secs: u64,
+    /// 每轮重算的天数（含今天）
+    pub lookback_days: i64,
+}
+
+impl Default for ProtocolMetricsWorkerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            refresh_interval_secs: 900,
+            lookback_days: 2,
+        }
+    }
+}
+
+impl ProtocolMetricsWorkerConfig {
+    /// 从环境变量加载配置
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: std::env::var("PROTOCOL_METRICS_WORKER_ENABLED")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.enabled),
+            refresh_interval_secs: std::env::var("PROTOCOL_METRICS_REFRESH_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.refresh_interval_secs),
+            lookback_days: std::env::var("PROTOCOL_METRICS_LOOKBACK_DAYS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.lookback_days),
+        }
+    }
+}
+
+/// 协议指标快照任务
+///
+/// 定期重算最近几天的协议每日指标并写入ProtocolMetrics集合，
+/// 当天的快照随每轮刷新更新，跨天后前一天的快照在回看窗口内继续补全延迟事件
+pub struct ProtocolMetricsWorker {
+    service: ProtocolMetricsService,
+    config: ProtocolMetricsWorkerConfig,
+}
+
+impl ProtocolMetricsWorker {
+    /// 创建新的协议指标快照任务
+    pub fn new(service: ProtocolMetricsService, config: ProtocolMetricsWorkerConfig) -> Self {
+        Self { service, config }
+    }
+
+    /// 启动快照循环
+    pub async fn start(&self) {
+        info!(
+            "🚀 启动协议指标快照任务: 刷新间隔{}秒, 回看{}天",
+            self.config.refresh_interval_secs, self.config.lookback_days
+        );
+
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.refresh_interval_secs.max(60)));
+        loop {
+            interval.tick().await;
+
+            match self.service.refresh_recent(self.config.lookback_days).await {
+                Ok(0) => {}
+                Ok(days) => info!("✅ 本轮刷新{}天协议指标", days),
+                Err(e) => error!("❌ 协议指标快照失败: {}", e),
+            }
+        }
+    }
+}
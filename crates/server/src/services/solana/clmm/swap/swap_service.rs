@@ -1,5 +1,6 @@
 use crate::dtos::solana::clmm::swap::basic::{PriceQuoteRequest, PriceQuoteResponse, SwapRequest, SwapResponse};
 
+use crate::services::solana::clmm::price::PriceService;
 use crate::services::solana::clmm::referral::referral_service::ReferralAccount;
 use crate::services::solana::shared::{
     helpers::{ResponseBuilder, SolanaUtils},
@@ -32,12 +33,23 @@ use utils::{PDACalculator, PoolInfoManager};
 /// SwapService handles all swap-related operations
 pub struct SwapService {
     shared: Arc<SharedContext>,
+    /// 备用价格计算使用的代币价格服务
+    price_service: Option<Arc<PriceService>>,
 }
 
 impl SwapService {
     /// Create a new SwapService instance
     pub fn new(shared: Arc<SharedContext>) -> Self {
-        Self { shared }
+        Self {
+            shared,
+            price_service: None,
+        }
+    }
+
+    /// 注入代币价格服务（备用价格计算使用池子推导的SOL价格）
+    pub fn with_price_service(mut self, price_service: Arc<PriceService>) -> Self {
+        self.price_service = Some(price_service);
+        self
     }
 
     /// Execute token swap
@@ -637,8 +649,8 @@ impl SwapService {
         let to_type = TokenUtils::get_token_type(to_token);
 
         let estimated_output = match (from_type, to_type) {
-            (TokenType::Sol, TokenType::Usdc) => MathUtils::convert_sol_to_usdc(amount),
-            (TokenType::Usdc, TokenType::Sol) => MathUtils::convert_usdc_to_sol(amount),
+            (TokenType::Sol, TokenType::Usdc) => MathUtils::convert_sol_to_usdc(amount, self.get_sol_price().await?),
+            (TokenType::Usdc, TokenType::Sol) => MathUtils::convert_usdc_to_sol(amount, self.get_sol_price().await?),
             _ => return Err(anyhow::anyhow!("不支持的交换对: {} -> {}", from_token, to_token)),
         };
 
@@ -646,6 +658,18 @@ impl SwapService {
         Ok(estimated_output)
     }
 
+    /// 获取由池子推导的SOL价格（USD）
+    async fn get_sol_price(&self) -> Result<f64> {
+        let price_service = self
+            .price_service
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("价格服务未配置，无法估算SOL价格"))?;
+        price_service
+            .get_sol_price()
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("暂无可用的SOL价格"))
+    }
+
     /// Execute the actual swap operation
     pub async fn execute_swap(&self, request: SwapRequest) -> Result<SwapResponse> {
         info!("🔄 开始执行交换");
@@ -690,6 +690,12 @@ impl DataTransformService {
                 }),
         );
 
+        // 金库余额和TVL由池子同步服务定期刷新，尚未计算时为0
+        let (mint_amount_a, mint_amount_b, tvl) = pool
+            .tvl_info
+            .as_ref()
+            .map_or((0.0, 0.0, 0.0), |tvl_info| (tvl_info.amount_0, tvl_info.amount_1, tvl_info.tvl));
+
         let pool_info = PoolInfo {
             pool_type: match pool.pool_type {
                 PoolType::Concentrated => "Concentrated".to_string(),
@@ -702,14 +708,15 @@ impl DataTransformService {
             reward_default_pool_infos: self.get_reward_pool_type(&pool.pool_type),
             reward_default_infos: vec![], // 暂时为空，未来可以从链上获取
             price: pool.price_info.current_price.unwrap_or(pool.price_info.initial_price),
-            mint_amount_a: 0.0, // 暂时为空，需要从链上获取
-            mint_amount_b: 0.0, // 暂时为空，需要从链上获取
+            mint_amount_a,
+            mint_amount_b,
             fee_rate: self.calculate_fee_rate(pool.config_index),
             open_time: pool.open_time.to_string(),
-            tvl: 0.0,                            // 暂时为空，需要计算
-            day: Some(PeriodStats::default()),   // 暂时为空，需要从交易数据汇聚
-            week: Some(PeriodStats::default()),  // 暂时为空，需要从交易数据汇聚
-            month: Some(PeriodStats::default()), // 暂时为空，需要从交易数据汇聚
+            tvl,
+            // 交易量、手续费和APR由池子统计服务在列表查询时填充，尚无统计时为空
+            day: Some(PeriodStats::default()),
+            week: Some(PeriodStats::default()),
+            month: Some(PeriodStats::default()),
             pooltype: self.get_pool_tags(&pool),
             farm_upcoming_count: 0,
             farm_ongoing_count: 0,
@@ -1520,6 +1527,7 @@ mod tests {
             pool_type: PoolType::Concentrated,
             data_source: database::clmm::clmm_pool::DataSource::ApiCreated,
             chain_confirmed: false,
+            tvl_info: None,
         }
     }
 
@@ -497,6 +497,8 @@ mod tests {
             token_infos: mock_mongodb.collection("TokenInfo"),
             clmm_pool_events: mock_mongodb.collection("ClmmPoolEvent"),
             nft_claim_events: mock_mongodb.collection("NftClaimEvent"),
+            nft_mint_events: mock_mongodb.collection("NftMintEvent"),
+            referral_established_events: mock_mongodb.collection("ReferralEstablishedEvent"),
             reward_distribution_events: mock_mongodb.collection("RewardDistributionEvent"),
             launch_events: mock_mongodb.collection("LaunchEvent"),
             deposit_events: mock_mongodb.collection("DepositEvent"),
@@ -504,8 +506,17 @@ mod tests {
             lp_change_events: mock_mongodb.collection("LpChangeEvent"),
             init_pool_events: mock_mongodb.collection("InitPoolEvent"),
             swap_events: mock_mongodb.collection("SwapEvent"),
+            clmm_swap_events: mock_mongodb.collection("ClmmSwapEvent"),
+            clmm_position_events: mock_mongodb.collection("ClmmPositionEvent"),
+            clmm_protocol_fee_events: mock_mongodb.collection("ClmmProtocolFeeEvent"),
             event_scanner_checkpoints: mock_mongodb.collection("EventScannerCheckpoints"),
             scan_records: mock_mongodb.collection("ScanRecords"),
+            provisional_transactions: mock_mongodb.collection("ProvisionalTransaction"),
+            dead_letter_events: mock_mongodb.collection("DeadLetterEvent"),
+            raw_events: mock_mongodb.collection("RawEvent"),
+            listener_runtime_configs: mock_mongodb.collection("ListenerRuntimeConfig"),
+            webhook_subscriptions: mock_mongodb.collection("WebhookSubscription"),
+            webhook_deliveries: mock_mongodb.collection("WebhookDelivery"),
             clmm_pool_repository: database::clmm::clmm_pool::repository::ClmmPoolRepository::new(
                 mock_mongodb.collection("ClmmPool"),
             ),
@@ -533,6 +544,13 @@ mod tests {
             nft_claim_event_repository: database::events::event_model::repository::NftClaimEventRepository::new(
                 mock_mongodb.collection("NftClaimEvent"),
             ),
+            nft_mint_event_repository: database::events::event_model::repository::NftMintEventRepository::new(
+                mock_mongodb.collection("NftMintEvent"),
+            ),
+            referral_established_event_repository:
+                database::events::event_model::repository::ReferralEstablishedEventRepository::new(
+                    mock_mongodb.collection("ReferralEstablishedEvent"),
+                ),
             reward_distribution_event_repository:
                 database::events::event_model::repository::RewardDistributionEventRepository::new(
                     mock_mongodb.collection("RewardDistributionEvent"),
@@ -556,6 +574,17 @@ mod tests {
             swap_event_repository: database::cpmm::swap_event::repository::SwapEventRepository::new(
                 mock_mongodb.collection("SwapEvent"),
             ),
+            clmm_swap_event_repository: database::clmm::clmm_swap_event::repository::ClmmSwapEventRepository::new(
+                mock_mongodb.collection("ClmmSwapEvent"),
+            ),
+            clmm_position_event_repository:
+                database::clmm::position_event::repository::ClmmPositionEventRepository::new(
+                    mock_mongodb.collection("ClmmPositionEvent"),
+                ),
+            clmm_protocol_fee_event_repository:
+                database::clmm::protocol_fee_event::repository::ClmmProtocolFeeEventRepository::new(
+                    mock_mongodb.collection("ClmmProtocolFeeEvent"),
+                ),
             event_scanner_checkpoint_repository:
                 database::events::event_scanner::repository::EventScannerCheckpointRepository::new(
                     mock_mongodb.collection("EventScannerCheckpoints"),
@@ -563,6 +592,27 @@ mod tests {
             scan_record_repository: database::events::event_scanner::repository::ScanRecordRepository::new(
                 mock_mongodb.collection("ScanRecords"),
             ),
+            provisional_transaction_repository:
+                database::events::provisional_transaction::repository::ProvisionalTransactionRepository::new(
+                    mock_mongodb.collection("ProvisionalTransaction"),
+                ),
+            dead_letter_repository: database::events::dead_letter::repository::DeadLetterRepository::new(
+                mock_mongodb.collection("DeadLetterEvent"),
+            ),
+            raw_event_repository: database::events::raw_event::repository::RawEventRepository::new(
+                mock_mongodb.collection("RawEvent"),
+            ),
+            listener_runtime_config_repository:
+                database::events::listener_runtime_config::repository::ListenerRuntimeConfigRepository::new(
+                    mock_mongodb.collection("ListenerRuntimeConfig"),
+                ),
+            webhook_subscription_repository:
+                database::events::webhook_subscription::repository::WebhookSubscriptionRepository::new(
+                    mock_mongodb.collection("WebhookSubscription"),
+                ),
+            webhook_delivery_repository: database::events::webhook_delivery::repository::WebhookDeliveryRepository::new(
+                mock_mongodb.collection("WebhookDelivery"),
+            ),
             user_points: mock_mongodb.collection("UserPointsSummary"),
             user_points_repository: database::cpmm::points::repository::UserPointsRepository::new(
                 mock_mongodb.collection("UserPointsSummary"),
@@ -572,6 +622,19 @@ mod tests {
                 database::cpmm::points::transaction_detail_repository::UserTransactionPointsDetailRepository::new(
                     mock_mongodb.collection("UserTransactionPointsDetail"),
                 ),
+            pool_candles: mock_mongodb.collection("PoolCandle"),
+            pool_candle_repository: database::analytics::candle::repository::PoolCandleRepository::new(
+                mock_mongodb.collection("PoolCandle"),
+            ),
+            pool_stats: mock_mongodb.collection("PoolStats"),
+            pool_stats_repository: database::analytics::pool_stats::repository::PoolStatsRepository::new(
+                mock_mongodb.collection("PoolStats"),
+            ),
+            protocol_metrics: mock_mongodb.collection("ProtocolMetrics"),
+            protocol_metrics_repository:
+                database::analytics::protocol_metrics::repository::ProtocolMetricsRepository::new(
+                    mock_mongodb.collection("ProtocolMetrics"),
+                ),
         };
 
         LpChangeEventService::new(Arc::new(mock_database))
@@ -23,7 +23,9 @@ use crate::dtos::solana::cpmm::withdraw::{
 use crate::services::solana::clmm::launch_migration::LaunchMigrationService;
 use crate::services::solana::clmm::liquidity_line::LiquidityLineService;
 use crate::services::solana::clmm::nft::NftService;
+use crate::services::solana::clmm::pool_stats::PoolStatsService;
 use crate::services::solana::clmm::position::PositionService;
+use crate::services::solana::clmm::price::PriceService;
 use crate::services::solana::clmm::referral::ReferralService;
 use crate::services::solana::clmm::swap::SwapService;
 use crate::services::solana::cpmm::deposit::CpmmDepositService;
@@ -38,6 +40,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use database::clmm::clmm_pool::{PoolListRequest, PoolListResponse};
 use database::{ClmmPool, PoolQueryParams, PoolStats};
+use mongodb::bson::oid::ObjectId;
 use std::sync::Arc;
 
 use crate::dtos::solana::clmm::launch::{
@@ -105,6 +108,7 @@ pub struct SolanaService {
     cpmm_config_service: CpmmConfigService,
     liquidity_line_service: LiquidityLineService,
     points_service: PointsService,
+    pool_stats_service: PoolStatsService,
     pub launch_migration: LaunchMigrationService,
     pub nft: NftService,
     pub referral: ReferralService,
@@ -124,7 +128,7 @@ impl SolanaService {
     }
 
     /// Create a new SolanaService with database integration
-    pub fn with_database(database: database::Database) -> Result<Self> {
+    pub fn with_database(database: database::Database, price_service: Arc<PriceService>) -> Result<Self> {
         let shared_context = Arc::new(SharedContext::new()?);
         let config_service = ClmmConfigService::new(Arc::new(database.clone()), shared_context.rpc_client.clone());
         let config_service_arc = Arc::new(config_service);
@@ -151,7 +155,7 @@ impl SolanaService {
         let optimized_shared_context = Arc::new(optimized_shared_context);
 
         Ok(Self {
-            swap_service: SwapService::new(optimized_shared_context.clone()),
+            swap_service: SwapService::new(optimized_shared_context.clone()).with_price_service(price_service.clone()),
             cpmm_swap_service: CpmmSwapService::new(optimized_shared_context.clone()),
             cpmm_deposit_service: CpmmDepositService::new(optimized_shared_context.clone()),
             cpmm_withdraw_service: CpmmWithdrawService::new(optimized_shared_context.clone()),
@@ -201,6 +205,11 @@ impl SolanaService {
                 Arc::new(database.clone()),
             ),
             points_service: PointsService::new(Arc::new(database.clone())),
+            pool_stats_service: PoolStatsService::new(
+                Arc::new(database.clone()),
+                optimized_shared_context.rpc_client.clone(),
+                price_service,
+            ),
             launch_migration: LaunchMigrationService::new(optimized_shared_context.clone(), &database),
             nft: NftService::new(optimized_shared_context.clone()),
             referral: ReferralService::new(optimized_shared_context.clone()),
@@ -426,6 +435,12 @@ pub trait SolanaServiceTrait {
         &self,
         request: LaunchMigrationRequest,
     ) -> Result<LaunchMigrationAndSendTransactionResponse>;
+    async fn launch_event_migration_and_send_transaction(
+        &self,
+        request: LaunchMigrationRequest,
+        launch_event_id: &ObjectId,
+    ) -> Result<LaunchMigrationAndSendTransactionResponse>;
+    async fn is_launch_migration_landed(&self, tx_signature: &str, pool_address: &str) -> Result<bool>;
 
     // Launch Migration query operations
     async fn get_user_launch_history(&self, creator_wallet: &str, page: u64, limit: u64) -> Result<Vec<ClmmPool>>;
@@ -845,10 +860,21 @@ impl SolanaServiceTrait for SolanaService {
         let old_response = self.clmm_pool_service.query_pools_with_pagination(params).await?;
 
         // 使用共享的数据转换服务（包含持久化缓存）
-        let mut transform_service = self.shared_context.data_transform_service.lock().await;
-        let new_response = transform_service
-            .transform_pool_list_response(old_response, params)
-            .await?;
+        let mut new_response = {
+            let mut transform_service = self.shared_context.data_transform_service.lock().await;
+            transform_service
+                .transform_pool_list_response(old_response, params)
+                .await?
+        };
+
+        // 填充交易量、手续费和APR统计，统计不可用时不影响列表返回
+        if let Err(e) = self
+            .pool_stats_service
+            .fill_pool_period_stats(&mut new_response.data.data)
+            .await
+        {
+            warn!("⚠️ 填充池子统计失败: {}", e);
+        }
 
         Ok(new_response)
     }
@@ -858,10 +884,21 @@ impl SolanaServiceTrait for SolanaService {
         let old_response = self.clmm_pool_service.query_pools_with_pagination(params).await?;
 
         // 使用共享的数据转换服务（包含持久化缓存）
-        let mut transform_service = self.shared_context.data_transform_service.lock().await;
-        let new_response = transform_service
-            .transform_pool_list_response2(old_response, params)
-            .await?;
+        let mut new_response = {
+            let mut transform_service = self.shared_context.data_transform_service.lock().await;
+            transform_service
+                .transform_pool_list_response2(old_response, params)
+                .await?
+        };
+
+        // 填充交易量、手续费和APR统计，统计不可用时不影响列表返回
+        if let Err(e) = self
+            .pool_stats_service
+            .fill_pool_period_stats(&mut new_response.data)
+            .await
+        {
+            warn!("⚠️ 填充池子统计失败: {}", e);
+        }
 
         Ok(new_response)
     }
@@ -984,6 +1021,22 @@ impl SolanaServiceTrait for SolanaService {
         self.launch_migration.launch_and_send_transaction(request).await
     }
 
+    async fn launch_event_migration_and_send_transaction(
+        &self,
+        request: LaunchMigrationRequest,
+        launch_event_id: &ObjectId,
+    ) -> Result<LaunchMigrationAndSendTransactionResponse> {
+        self.launch_migration
+            .launch_event_and_send_transaction(request, launch_event_id)
+            .await
+    }
+
+    async fn is_launch_migration_landed(&self, tx_signature: &str, pool_address: &str) -> Result<bool> {
+        self.launch_migration
+            .is_migration_landed(tx_signature, pool_address)
+            .await
+    }
+
     // Launch Migration query operations - delegate to launch_migration service
     async fn get_user_launch_history(&self, creator_wallet: &str, page: u64, limit: u64) -> Result<Vec<ClmmPool>> {
         self.launch_migration
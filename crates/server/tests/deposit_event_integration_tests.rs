@@ -9,6 +9,7 @@ use std::sync::Arc;
 use tower::ServiceExt;
 
 use database::Database;
+use database::events::event_identity::EventIdentity;
 use database::events::event_model::DepositEvent;
 use server::services::Services;
 use server::api::solana::clmm::deposit_event_controller::DepositEventController;
@@ -326,6 +327,7 @@ mod database_integration_tests {
             actual_total_raised: 5.0,
             signature: format!("integration_test_signature_{}", Utc::now().timestamp_millis()),
             slot: 12345,
+            identity: EventIdentity::default(),
             processed_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
         };
@@ -0,0 +1,129 @@
+use clap::Parser;
+use solana_event_listener::{
+    config::EventListenerConfig,
+    recovery::{ReindexOptions, ReindexRange, Reindexer},
+};
+use solana_sdk::pubkey::Pubkey;
+use tracing::{error, info, warn};
+use tracing_subscriber::{EnvFilter, FmtSubscriber};
+
+/// 按slot或签名范围重建单个事件类型的索引
+///
+/// 示例：
+///   event-reindex --event-type SwapEvent --program-id <PROGRAM_ID> --start-slot 300000000 --end-slot 300100000
+///   event-reindex --event-type ReferralRewardEvent --program-id <PROGRAM_ID> --until <SIGNATURE> --wipe --yes
+#[derive(Debug, Parser)]
+#[command(name = "event-reindex")]
+struct Opts {
+    /// 事件类型（回填处理器注册名，如SwapEvent、ClmmSwapEvent、ReferralRewardEvent）
+    #[arg(long)]
+    event_type: String,
+    /// 发出事件的程序ID
+    #[arg(long)]
+    program_id: Pubkey,
+    /// 起始slot（包含）
+    #[arg(long)]
+    start_slot: Option<u64>,
+    /// 结束slot（包含）
+    #[arg(long)]
+    end_slot: Option<u64>,
+    /// 从该签名之前（更老）的交易开始扫描
+    #[arg(long)]
+    before: Option<String>,
+    /// 扫描到该签名为止（不包含）
+    #[arg(long)]
+    until: Option<String>,
+    /// 重建前清空目标集合中该事件类型的全部文档（范围外的历史事件也会被删除）
+    #[arg(long)]
+    wipe: bool,
+    /// 只获取和解析交易，不删除或写入任何数据
+    #[arg(long)]
+    dry_run: bool,
+    /// 每批处理的交易数
+    #[arg(long, default_value_t = 50)]
+    batch_size: usize,
+    /// 确认执行清空操作
+    #[arg(long)]
+    yes: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opts = Opts::parse();
+
+    // 加载环境配置文件（日志系统初始化后再报告失败）
+    let env_loaded = utils::config::EnvLoader::load_env_file();
+
+    // 初始化日志系统
+    let subscriber = FmtSubscriber::builder()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .with_target(false)
+        .finish();
+
+    tracing::subscriber::set_global_default(subscriber).expect("Failed to set tracing subscriber");
+
+    if let Err(e) = env_loaded {
+        warn!("⚠️ 加载环境配置文件失败: {}", e);
+    }
+
+    if opts.wipe && !opts.dry_run && !opts.yes {
+        error!("❌ --wipe会删除{}的全部历史文档，确认执行请追加--yes", opts.event_type);
+        std::process::exit(1);
+    }
+
+    let config = match EventListenerConfig::from_env().await {
+        Ok(config) => config,
+        Err(e) => {
+            error!("❌ 配置加载失败: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let options = ReindexOptions {
+        event_type: opts.event_type,
+        program_id: opts.program_id,
+        range: ReindexRange {
+            start_slot: opts.start_slot,
+            end_slot: opts.end_slot,
+            before_signature: opts.before,
+            until_signature: opts.until,
+        },
+        wipe: opts.wipe,
+        dry_run: opts.dry_run,
+        batch_size: opts.batch_size,
+    };
+
+    let reindexer = match Reindexer::new(&config, options).await {
+        Ok(reindexer) => reindexer,
+        Err(e) => {
+            error!("❌ 创建重建索引任务失败: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match reindexer.run().await {
+        Ok(summary) => {
+            info!(
+                "✅ 重建索引完成: 签名{}个，处理交易{}个，失败交易{}个，解析事件{}个，写入{}个，写入失败{}个，清理旧文档{}个，重建K线{}根，重算仓位{}个",
+                summary.signatures_scanned,
+                summary.transactions_processed,
+                summary.transactions_failed,
+                summary.events_parsed,
+                summary.events_written,
+                summary.events_failed,
+                summary.documents_deleted,
+                summary.candles_rebuilt,
+                summary.positions_rebuilt
+            );
+            if summary.transactions_failed > 0 || summary.events_failed > 0 {
+                std::process::exit(2);
+            }
+        }
+        Err(e) => {
+            error!("❌ 重建索引失败: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+pub mod runtime_config;
 pub mod settings;
 
+pub use runtime_config::RuntimeConfigWatcher;
 pub use settings::EventListenerConfig;
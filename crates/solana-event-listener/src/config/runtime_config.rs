@@ -0,0 +1,220 @@
+use crate::{
+    error::{EventListenerError, Result},
+    parser::EventParserRegistry,
+    persistence::EventStorage,
+    recovery::{BackfillEventConfig, BackfillManager},
+    subscriber::SubscriptionManager,
+};
+use database::events::listener_runtime_config::ListenerRuntimeConfig;
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::HashSet,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tracing::{error, info, warn};
+
+/// 检查运行时配置变化的间隔
+const RUNTIME_CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 从运行时配置文档解析出的监听器设置
+#[derive(Debug, Clone)]
+pub struct RuntimeListenerSettings {
+    /// 订阅的程序ID列表
+    pub program_ids: Vec<Pubkey>,
+    /// 禁用的解析器事件类型
+    pub disabled_event_types: HashSet<String>,
+    /// 回填事件配置列表
+    pub backfill_events: Vec<BackfillEventConfig>,
+}
+
+impl RuntimeListenerSettings {
+    /// 校验并转换运行时配置文档，未指定检查间隔的回填事件使用默认间隔
+    pub fn from_config(config: &ListenerRuntimeConfig, default_check_interval_secs: Option<u64>) -> Result<Self> {
+        config.validate().map_err(EventListenerError::Config)?;
+
+        let program_ids = config
+            .program_ids
+            .iter()
+            .map(|program_id| parse_pubkey(program_id))
+            .collect::<Result<Vec<_>>>()?;
+
+        let backfill_events = config
+            .backfill_events
+            .iter()
+            .map(|event| {
+                let program_id = parse_pubkey(&event.program_id)?;
+                let mut backfill_event =
+                    BackfillEventConfig::new(&event.event_type, program_id).with_enabled(event.enabled);
+                if let Some(interval) = event.check_interval_secs.or(default_check_interval_secs) {
+                    backfill_event = backfill_event.with_check_interval(interval);
+                }
+                Ok(backfill_event)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            program_ids,
+            disabled_event_types: config.disabled_event_types.iter().cloned().collect(),
+            backfill_events,
+        })
+    }
+}
+
+fn parse_pubkey(program_id: &str) -> Result<Pubkey> {
+    Pubkey::from_str(program_id)
+        .map_err(|e| EventListenerError::Config(format!("无效的程序ID: {} - {}", program_id, e)))
+}
+
+/// 运行时配置热更新任务
+///
+/// 管理接口只负责写入MongoDB中的配置文档，由监听器进程中的本任务定期读取，
+/// 版本号变化时调整订阅程序、解析器启用状态和回填任务，不需要重启服务
+pub struct RuntimeConfigWatcher {
+    event_storage: Arc<EventStorage>,
+    subscription_manager: Arc<SubscriptionManager>,
+    parser_registry: Arc<EventParserRegistry>,
+    backfill_manager: Option<Arc<BackfillManager>>,
+    default_check_interval_secs: Option<u64>,
+    /// 已应用的配置版本（0表示尚未应用）
+    applied_version: AtomicU64,
+}
+
+impl RuntimeConfigWatcher {
+    /// 创建新的运行时配置热更新任务
+    pub fn new(
+        event_storage: Arc<EventStorage>,
+        subscription_manager: Arc<SubscriptionManager>,
+        parser_registry: Arc<EventParserRegistry>,
+        backfill_manager: Option<Arc<BackfillManager>>,
+        default_check_interval_secs: Option<u64>,
+    ) -> Self {
+        Self {
+            event_storage,
+            subscription_manager,
+            parser_registry,
+            backfill_manager,
+            default_check_interval_secs,
+            applied_version: AtomicU64::new(0),
+        }
+    }
+
+    /// 启动配置检查循环
+    pub async fn start(&self) -> Result<()> {
+        info!(
+            "🔧 启动运行时配置热更新任务，检查间隔{:?}",
+            RUNTIME_CONFIG_POLL_INTERVAL
+        );
+
+        let mut interval = tokio::time::interval(RUNTIME_CONFIG_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.reload_once().await {
+                error!("❌ 运行时配置热更新失败: {}", e);
+            }
+        }
+    }
+
+    /// 检查一次配置，版本变化时应用，返回是否应用了新配置
+    pub async fn reload_once(&self) -> Result<bool> {
+        let config = match self.event_storage.get_listener_runtime_config().await? {
+            Some(config) => config,
+            None => return Ok(false),
+        };
+        if config.version == self.applied_version.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
+
+        // 无效配置只记录一次，等待管理员修正后的新版本
+        self.applied_version.store(config.version, Ordering::Relaxed);
+        let settings = match RuntimeListenerSettings::from_config(&config, self.default_check_interval_secs) {
+            Ok(settings) => settings,
+            Err(e) => {
+                warn!("⚠️ 忽略无效的运行时配置 version={}: {}", config.version, e);
+                return Ok(false);
+            }
+        };
+
+        info!(
+            "🔧 应用运行时配置 version={} (修改人: {})",
+            config.version,
+            config.updated_by.as_deref().unwrap_or("unknown")
+        );
+        self.apply(settings).await?;
+        Ok(true)
+    }
+
+    /// 应用运行时设置
+    async fn apply(&self, settings: RuntimeListenerSettings) -> Result<()> {
+        self.parser_registry
+            .set_disabled_event_types(settings.disabled_event_types);
+
+        if let Some(backfill_manager) = &self.backfill_manager {
+            backfill_manager.reconcile_event_configs(settings.backfill_events).await;
+        } else if !settings.backfill_events.is_empty() {
+            warn!("⚠️ 回填功能未启用，忽略运行时回填事件配置");
+        }
+
+        self.subscription_manager
+            .update_program_ids(settings.program_ids)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use database::events::listener_runtime_config::{RuntimeBackfillEventConfig, LISTENER_RUNTIME_CONFIG_KEY};
+
+    const PROGRAM_ID: &str = "FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX";
+
+    fn runtime_config(program_ids: Vec<String>) -> ListenerRuntimeConfig {
+        ListenerRuntimeConfig {
+            id: None,
+            config_key: LISTENER_RUNTIME_CONFIG_KEY.to_string(),
+            program_ids,
+            disabled_event_types: vec!["swap".to_string()],
+            backfill_events: vec![
+                RuntimeBackfillEventConfig {
+                    event_type: "SwapEvent".to_string(),
+                    program_id: PROGRAM_ID.to_string(),
+                    enabled: true,
+                    check_interval_secs: None,
+                },
+                RuntimeBackfillEventConfig {
+                    event_type: "LaunchEvent".to_string(),
+                    program_id: PROGRAM_ID.to_string(),
+                    enabled: false,
+                    check_interval_secs: Some(60),
+                },
+            ],
+            version: 1,
+            updated_by: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_runtime_settings_from_config() {
+        let config = runtime_config(vec![PROGRAM_ID.to_string()]);
+        let settings = RuntimeListenerSettings::from_config(&config, Some(300)).unwrap();
+
+        assert_eq!(settings.program_ids, vec![Pubkey::from_str(PROGRAM_ID).unwrap()]);
+        assert!(settings.disabled_event_types.contains("swap"));
+        assert_eq!(settings.backfill_events.len(), 2);
+        assert_eq!(settings.backfill_events[0].check_interval_secs, Some(300));
+        assert!(settings.backfill_events[0].enabled);
+        assert_eq!(settings.backfill_events[1].check_interval_secs, Some(60));
+        assert!(!settings.backfill_events[1].enabled);
+
+        let invalid = runtime_config(Vec::new());
+        assert!(RuntimeListenerSettings::from_config(&invalid, Some(300)).is_err());
+    }
+}
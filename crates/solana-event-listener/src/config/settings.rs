@@ -17,6 +17,17 @@ pub struct EventListenerConfig {
     pub monitoring: MonitoringConfig,
     /// 回填服务配置（可选）
     pub backfill: Option<BackfillConfig>,
+    /// 交易最终确认配置（可选，订阅承诺级别为finalized时无需启用）
+    pub finality: Option<FinalityConfig>,
+    /// IDL通用事件解析配置（可选）
+    #[serde(default)]
+    pub idl_events: Option<IdlEventsConfig>,
+    /// 事件摄取来源配置（可选，未配置时通过logsSubscribe实时订阅）
+    #[serde(default)]
+    pub ingestion: Option<IngestionConfig>,
+    /// 链上进度落后监控配置（可选，未配置时使用默认阈值）
+    #[serde(default)]
+    pub lag_monitor: Option<LagMonitorConfig>,
 }
 
 /// Solana网络配置
@@ -32,6 +43,37 @@ pub struct SolanaConfig {
     pub program_ids: Vec<Pubkey>,
     /// 签名者私钥 (可选，用于发送交易)
     pub private_key: Option<String>,
+    /// 多端点故障切换配置（rpc_url/ws_url作为主端点）
+    #[serde(default)]
+    pub failover: EndpointFailoverConfig,
+}
+
+/// 备用RPC/WebSocket端点配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcEndpointConfig {
+    /// 端点名称（用于日志和指标标签，避免暴露带密钥的URL）
+    pub name: String,
+    /// RPC URL
+    pub rpc_url: String,
+    /// WebSocket URL
+    pub ws_url: String,
+    /// 权重（越大越优先）
+    pub weight: u32,
+}
+
+/// 多端点故障切换配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointFailoverConfig {
+    /// 主端点权重
+    pub primary_weight: u32,
+    /// 备用端点列表
+    pub fallback_endpoints: Vec<RpcEndpointConfig>,
+    /// 连续失败多少次后将端点标记为不可用
+    pub failure_threshold: u32,
+    /// 不可用端点的冷却时间（秒），冷却结束后允许重新尝试
+    pub cooldown_secs: u64,
+    /// 检查是否可以切回更高权重端点的间隔（秒）
+    pub failback_check_interval_secs: u64,
 }
 
 /// 数据库配置
@@ -77,6 +119,8 @@ pub struct MonitoringConfig {
     pub enable_performance_monitoring: bool,
     /// 健康检查间隔（秒）
     pub health_check_interval_secs: u64,
+    /// 指标/状态HTTP服务监听地址（None表示不启动）
+    pub http_bind_addr: Option<String>,
 }
 
 /// 退避重连配置
@@ -105,8 +149,108 @@ pub struct BatchWriteConfig {
     pub max_wait_ms: u64,
     /// 缓冲区大小
     pub buffer_size: usize,
-    /// 并发写入线程数
+    /// 并发写入任务数，默认1；大于1时事件按仓位NFT/池子路由，同一键的事件由同一任务顺序写入
     pub concurrent_writers: usize,
+    /// 写入队列已满时的处理策略
+    pub overflow_policy: OverflowPolicy,
+    /// 溢出文件路径（仅spill策略使用），为空则默认./data/batch_writer_spill.jsonl
+    pub spill_path: Option<String>,
+    /// 事件输出目标
+    pub sinks: EventSinkConfig,
+}
+
+/// 事件输出目标配置
+///
+/// MongoDB为主存储，写入失败时按批量写入器的重试和死信流程处理；
+/// JSONL文件和webhook为附加输出，各自拥有独立的队列、重试和指标，失败不影响主存储
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventSinkConfig {
+    /// 是否写入MongoDB
+    pub mongo_enabled: bool,
+    /// 按小时滚动的JSONL文件输出（可选）
+    pub jsonl: Option<JsonlSinkConfig>,
+    /// HTTP webhook输出（可选）
+    pub webhook: Option<WebhookSinkConfig>,
+    /// 按合作方订阅推送事件（可选）
+    pub webhook_subscriptions: Option<WebhookSubscriptionConfig>,
+}
+
+/// JSONL文件输出配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonlSinkConfig {
+    /// 输出目录
+    pub directory: String,
+    /// 文件名前缀，文件名为<prefix>-<YYYYMMDDHH>.jsonl（UTC小时）
+    pub file_prefix: String,
+    /// 重试配置
+    pub retry: SinkRetryConfig,
+}
+
+/// Webhook输出配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSinkConfig {
+    /// 接收事件批次的URL（POST JSON）
+    pub url: String,
+    /// Bearer认证令牌（可选）
+    pub auth_token: Option<String>,
+    /// 请求超时（毫秒）
+    pub timeout_ms: u64,
+    /// 重试配置
+    pub retry: SinkRetryConfig,
+}
+
+/// webhook订阅投递配置
+///
+/// 订阅注册在MongoDB中，事件批次写入后为匹配的订阅生成投递记录，由投递任务签名发送并按指数退避重试
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSubscriptionConfig {
+    /// 订阅缓存刷新间隔（秒）
+    pub refresh_interval_secs: u64,
+    /// 请求超时（毫秒）
+    pub timeout_ms: u64,
+    /// 单条投递的最大尝试次数，达到后标记为失败
+    pub max_attempts: u32,
+    /// 初始重试间隔（秒），按指数退避
+    pub retry_base_delay_secs: u64,
+    /// 生成投递记录的队列与重试配置
+    pub retry: SinkRetryConfig,
+}
+
+/// 附加输出的队列与重试配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SinkRetryConfig {
+    /// 待写入批次队列容量，队列满时丢弃新批次
+    pub queue_size: usize,
+    /// 最大重试次数
+    pub max_retries: u32,
+    /// 初始重试间隔（毫秒），按指数退避
+    pub retry_delay_ms: u64,
+}
+
+/// 批量写入队列溢出策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// 阻塞提交方直到队列有空位（默认）
+    #[default]
+    Block,
+    /// 写入本地追加文件，启动时及队列空闲时重放
+    Spill,
+    /// 丢弃新事件并计入丢弃统计
+    Drop,
+}
+
+impl FromStr for OverflowPolicy {
+    type Err = EventListenerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "block" => Ok(OverflowPolicy::Block),
+            "spill" => Ok(OverflowPolicy::Spill),
+            "drop" => Ok(OverflowPolicy::Drop),
+            _ => Err(EventListenerError::Config(format!("未知的队列溢出策略: {}", s))),
+        }
+    }
 }
 
 /// 回填服务配置
@@ -118,6 +262,10 @@ pub struct BackfillConfig {
     pub events: Vec<BackfillEventConfigItem>,
     /// 默认检查周期间隔（秒）
     pub default_check_interval_secs: Option<u64>,
+    /// 是否在WebSocket重连后立即回填断线缺口，为空则默认启用
+    pub gap_backfill_enabled: Option<bool>,
+    /// 单次缺口回填最多处理的签名数，为空则默认5000
+    pub gap_backfill_max_signatures: Option<usize>,
 }
 
 /// 单个事件类型的回填配置
@@ -133,6 +281,100 @@ pub struct BackfillEventConfigItem {
     pub check_interval_secs: Option<u64>,
 }
 
+/// 交易最终确认配置
+///
+/// 监听器以confirmed级别写入的事件会被登记为临时记录，由后台任务在finalized级别复查，
+/// 超时仍未最终确认的交易视为被分叉丢弃，回滚其写入的事件和派生数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FinalityConfig {
+    /// 是否启用最终确认检查
+    pub enabled: bool,
+    /// 检查间隔（秒）
+    pub check_interval_secs: u64,
+    /// 单次检查的最大签名数（getSignatureStatuses单次最多256个）
+    pub batch_size: usize,
+    /// 写入后超过该时长仍查询不到交易状态则回滚（秒）
+    pub rollback_timeout_secs: u64,
+}
+
+/// IDL通用事件解析配置
+///
+/// 按Anchor IDL动态解码没有专用解析器的事件，写入RawEvent集合；
+/// 事件所属程序需同时出现在订阅程序列表中才会被实时监听
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdlEventsConfig {
+    /// IDL来源列表
+    pub sources: Vec<IdlSourceConfig>,
+}
+
+/// 单个程序的IDL来源
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdlSourceConfig {
+    /// 发出事件的程序ID
+    pub program_id: String,
+    /// Anchor IDL JSON文件路径
+    pub idl_path: String,
+    /// 只索引这些事件，为空则索引IDL中的全部事件
+    pub events: Vec<String>,
+}
+
+/// 事件摄取模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestionMode {
+    /// 通过WebSocket logsSubscribe实时订阅（默认）
+    #[default]
+    LogsSubscribe,
+    /// 从磁盘回放录制的日志和交易夹具
+    Replay,
+}
+
+impl FromStr for IngestionMode {
+    type Err = EventListenerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "logs_subscribe" | "websocket" => Ok(IngestionMode::LogsSubscribe),
+            "replay" => Ok(IngestionMode::Replay),
+            _ => Err(EventListenerError::Config(format!("未知的事件摄取模式: {}", s))),
+        }
+    }
+}
+
+/// 事件摄取来源配置
+///
+/// 回放模式下不连接WebSocket，按录制顺序把夹具中的日志送入解析和持久化流程；
+/// 设置录制路径后，实时订阅收到的日志和日志截断时获取的完整交易会追加写入夹具文件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IngestionConfig {
+    /// 摄取模式
+    #[serde(default)]
+    pub mode: IngestionMode,
+    /// 回放的夹具文件或目录（目录下的.jsonl文件按文件名顺序回放）
+    pub fixture_path: Option<String>,
+    /// 回放时相邻记录的间隔（毫秒），0表示不等待
+    #[serde(default)]
+    pub replay_interval_ms: u64,
+    /// 录制夹具的输出文件路径
+    pub record_path: Option<String>,
+}
+
+/// 链上进度落后监控配置
+///
+/// 定期查询链上最新slot，与各程序、各事件类型最新处理的slot和回填检查点比较；
+/// 任一指标超过阈值时健康状态降级为degraded，阈值为0表示不检查该项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LagMonitorConfig {
+    /// 查询链上最新slot的间隔（秒）
+    pub tip_poll_interval_secs: u64,
+    /// 实时处理位置落后链上最新slot的最大slot数
+    pub max_slot_lag: u64,
+    /// 出块到事件处理完成的最大延迟（秒）
+    pub max_processing_delay_secs: u64,
+    /// 回填检查点落后链上最新slot的最大slot数
+    pub max_backfill_slot_lag: u64,
+}
+
 impl EventListenerConfig {
     /// 从环境变量加载配置
     pub async fn from_env() -> Result<Self> {
@@ -148,6 +390,7 @@ impl EventListenerConfig {
             commitment: std::env::var("SOLANA_COMMITMENT").unwrap_or_else(|_| "confirmed".to_string()),
             program_ids: Self::parse_program_ids()?,
             private_key: std::env::var("PRIVATE_KEY").ok(),
+            failover: Self::load_endpoint_failover_config(),
         };
 
         // 加载数据库配置
@@ -229,9 +472,15 @@ impl EventListenerConfig {
                     .parse()
                     .unwrap_or(1000),
                 concurrent_writers: std::env::var("EVENT_BATCH_WRITE_CONCURRENT")
-                    .unwrap_or_else(|_| "4".to_string())
+                    .unwrap_or_else(|_| "1".to_string())
                     .parse()
-                    .unwrap_or(4),
+                    .unwrap_or(1),
+                overflow_policy: std::env::var("EVENT_BATCH_WRITE_OVERFLOW_POLICY")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or_default(),
+                spill_path: std::env::var("EVENT_BATCH_WRITE_SPILL_PATH").ok(),
+                sinks: Self::load_event_sink_config(),
             },
         };
 
@@ -249,6 +498,15 @@ impl EventListenerConfig {
                 .unwrap_or_else(|_| "30".to_string())
                 .parse()
                 .unwrap_or(30),
+            http_bind_addr: if std::env::var("EVENT_HTTP_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true)
+            {
+                Some(std::env::var("EVENT_HTTP_BIND_ADDR").unwrap_or_else(|_| "0.0.0.0:8090".to_string()))
+            } else {
+                None
+            },
         };
 
         // 加载回填配置（可选）
@@ -269,17 +527,66 @@ impl EventListenerConfig {
                 enabled: true,
                 events,
                 default_check_interval_secs,
+                gap_backfill_enabled: std::env::var("BACKFILL_GAP_ENABLED").ok().and_then(|s| s.parse().ok()),
+                gap_backfill_max_signatures: std::env::var("BACKFILL_GAP_MAX_SIGNATURES")
+                    .ok()
+                    .and_then(|s| s.parse().ok()),
             })
         } else {
             None
         };
 
+        // 加载交易最终确认配置（订阅承诺级别低于finalized时默认启用）
+        let finality = if solana.commitment != "finalized"
+            && std::env::var("FINALITY_TRACKING_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true)
+        {
+            let defaults = FinalityConfig::default();
+            Some(FinalityConfig {
+                enabled: true,
+                check_interval_secs: std::env::var("FINALITY_CHECK_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(defaults.check_interval_secs),
+                batch_size: std::env::var("FINALITY_BATCH_SIZE")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(defaults.batch_size),
+                rollback_timeout_secs: std::env::var("FINALITY_ROLLBACK_TIMEOUT_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(defaults.rollback_timeout_secs),
+            })
+        } else {
+            None
+        };
+
+        // 加载IDL通用事件解析配置（可选）
+        let idl_sources = Self::load_idl_source_configs();
+        let idl_events = if idl_sources.is_empty() {
+            None
+        } else {
+            Some(IdlEventsConfig { sources: idl_sources })
+        };
+
+        // 加载事件摄取来源配置（可选）
+        let ingestion = Self::load_ingestion_config()?;
+
+        // 加载链上进度落后监控配置（可选）
+        let lag_monitor = Self::load_lag_monitor_config();
+
         let config = Self {
             solana,
             database,
             listener,
             monitoring,
             backfill,
+            finality,
+            idl_events,
+            ingestion,
+            lag_monitor,
         };
 
         info!("✅ Event-Listener配置加载完成");
@@ -288,11 +595,145 @@ impl EventListenerConfig {
         }
         info!("🌐 RPC URL: {}", config.solana.rpc_url);
         info!("🔌 WebSocket URL: {}", config.solana.ws_url);
+        for endpoint in &config.solana.failover.fallback_endpoints {
+            info!("🛟 备用端点: {} (权重: {})", endpoint.name, endpoint.weight);
+        }
         info!("📊 数据库: {}", config.database.database_name);
 
         Ok(config)
     }
 
+    /// 加载事件输出目标配置
+    fn load_event_sink_config() -> EventSinkConfig {
+        let jsonl = std::env::var("EVENT_SINK_JSONL_DIR")
+            .ok()
+            .map(|directory| JsonlSinkConfig {
+                directory,
+                file_prefix: std::env::var("EVENT_SINK_JSONL_PREFIX").unwrap_or_else(|_| "events".to_string()),
+                retry: SinkRetryConfig::from_env("EVENT_SINK_JSONL"),
+            });
+
+        let webhook = std::env::var("EVENT_SINK_WEBHOOK_URL")
+            .ok()
+            .map(|url| WebhookSinkConfig {
+                url,
+                auth_token: std::env::var("EVENT_SINK_WEBHOOK_TOKEN").ok(),
+                timeout_ms: std::env::var("EVENT_SINK_WEBHOOK_TIMEOUT_MS")
+                    .unwrap_or_else(|_| "10000".to_string())
+                    .parse()
+                    .unwrap_or(10000),
+                retry: SinkRetryConfig::from_env("EVENT_SINK_WEBHOOK"),
+            });
+
+        let webhook_subscriptions = std::env::var("WEBHOOK_SUBSCRIPTIONS_ENABLED")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse()
+            .unwrap_or(true)
+            .then(|| {
+                let defaults = WebhookSubscriptionConfig::default();
+                WebhookSubscriptionConfig {
+                    refresh_interval_secs: std::env::var("WEBHOOK_SUBSCRIPTIONS_REFRESH_INTERVAL_SECS")
+                        .ok()
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or(defaults.refresh_interval_secs),
+                    timeout_ms: std::env::var("WEBHOOK_SUBSCRIPTIONS_TIMEOUT_MS")
+                        .ok()
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or(defaults.timeout_ms),
+                    max_attempts: std::env::var("WEBHOOK_SUBSCRIPTIONS_MAX_ATTEMPTS")
+                        .ok()
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or(defaults.max_attempts),
+                    retry_base_delay_secs: std::env::var("WEBHOOK_SUBSCRIPTIONS_RETRY_BASE_DELAY_SECS")
+                        .ok()
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or(defaults.retry_base_delay_secs),
+                    retry: SinkRetryConfig::from_env("WEBHOOK_SUBSCRIPTIONS"),
+                }
+            });
+
+        EventSinkConfig {
+            mongo_enabled: std::env::var("EVENT_SINK_MONGO_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            jsonl,
+            webhook,
+            webhook_subscriptions,
+        }
+    }
+
+    /// 加载多端点故障切换配置
+    fn load_endpoint_failover_config() -> EndpointFailoverConfig {
+        let defaults = EndpointFailoverConfig::default();
+        let mut fallback_endpoints = Vec::new();
+
+        // 支持通过环境变量配置多个备用端点
+        // 格式: RPC_FALLBACK_<INDEX>_URL=https://backup-rpc.example.com
+        //      RPC_FALLBACK_<INDEX>_WS_URL=wss://backup-rpc.example.com
+        //      RPC_FALLBACK_<INDEX>_NAME=backup
+        //      RPC_FALLBACK_<INDEX>_WEIGHT=50
+
+        for i in 1..=5 {
+            // 支持最多5个备用端点
+            let rpc_url_key = format!("RPC_FALLBACK_{}_URL", i);
+            let ws_url_key = format!("RPC_FALLBACK_{}_WS_URL", i);
+            let name_key = format!("RPC_FALLBACK_{}_NAME", i);
+            let weight_key = format!("RPC_FALLBACK_{}_WEIGHT", i);
+
+            if let Ok(rpc_url) = std::env::var(&rpc_url_key) {
+                let ws_url = std::env::var(&ws_url_key)
+                    .unwrap_or_else(|_| Self::_derive_ws_url(&rpc_url).unwrap_or_else(|_| rpc_url.clone()));
+                let name = std::env::var(&name_key).unwrap_or_else(|_| format!("fallback-{}", i));
+                let weight = std::env::var(&weight_key)
+                    .unwrap_or_else(|_| "50".to_string())
+                    .parse()
+                    .unwrap_or(50);
+
+                info!("📋 加载备用端点配置 {}: {} (权重: {})", i, name, weight);
+
+                fallback_endpoints.push(RpcEndpointConfig {
+                    name,
+                    rpc_url,
+                    ws_url,
+                    weight,
+                });
+            }
+        }
+
+        EndpointFailoverConfig {
+            primary_weight: std::env::var("RPC_PRIMARY_WEIGHT")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.primary_weight),
+            fallback_endpoints,
+            failure_threshold: std::env::var("RPC_FAILURE_THRESHOLD")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.failure_threshold),
+            cooldown_secs: std::env::var("RPC_FAILOVER_COOLDOWN_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.cooldown_secs),
+            failback_check_interval_secs: std::env::var("RPC_FAILBACK_CHECK_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.failback_check_interval_secs),
+        }
+    }
+
+    /// 获取所有端点（主端点在前，随后是备用端点）
+    pub fn get_all_endpoints(&self) -> Vec<RpcEndpointConfig> {
+        let mut endpoints = vec![RpcEndpointConfig {
+            name: "primary".to_string(),
+            rpc_url: self.solana.rpc_url.clone(),
+            ws_url: self.solana.ws_url.clone(),
+            weight: self.solana.failover.primary_weight,
+        }];
+        endpoints.extend(self.solana.failover.fallback_endpoints.iter().cloned());
+        endpoints
+    }
+
     /// 加载回填事件配置列表
     fn load_backfill_event_configs() -> Vec<BackfillEventConfigItem> {
         let mut configs = Vec::new();
@@ -346,6 +787,98 @@ impl EventListenerConfig {
         configs
     }
 
+    /// 加载IDL来源配置列表
+    /// 加载事件摄取来源配置，未设置任何相关环境变量时返回None
+    fn load_ingestion_config() -> Result<Option<IngestionConfig>> {
+        let mode = std::env::var("INGESTION_MODE").ok();
+        let fixture_path = std::env::var("INGESTION_FIXTURE_PATH").ok().filter(|s| !s.is_empty());
+        let record_path = std::env::var("INGESTION_RECORD_PATH").ok().filter(|s| !s.is_empty());
+
+        if mode.is_none() && fixture_path.is_none() && record_path.is_none() {
+            return Ok(None);
+        }
+
+        Ok(Some(IngestionConfig {
+            mode: match mode {
+                Some(mode) => mode.parse()?,
+                None => IngestionMode::default(),
+            },
+            fixture_path,
+            replay_interval_ms: std::env::var("INGESTION_REPLAY_INTERVAL_MS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0),
+            record_path,
+        }))
+    }
+
+    /// 加载链上进度落后监控配置，未设置任何相关环境变量时返回None
+    fn load_lag_monitor_config() -> Option<LagMonitorConfig> {
+        const KEYS: [&str; 4] = [
+            "LAG_TIP_POLL_INTERVAL_SECS",
+            "LAG_MAX_SLOT_LAG",
+            "LAG_MAX_PROCESSING_DELAY_SECS",
+            "LAG_MAX_BACKFILL_SLOT_LAG",
+        ];
+        if KEYS.iter().all(|key| std::env::var(key).is_err()) {
+            return None;
+        }
+
+        let defaults = LagMonitorConfig::default();
+        Some(LagMonitorConfig {
+            tip_poll_interval_secs: std::env::var("LAG_TIP_POLL_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.tip_poll_interval_secs),
+            max_slot_lag: std::env::var("LAG_MAX_SLOT_LAG")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.max_slot_lag),
+            max_processing_delay_secs: std::env::var("LAG_MAX_PROCESSING_DELAY_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.max_processing_delay_secs),
+            max_backfill_slot_lag: std::env::var("LAG_MAX_BACKFILL_SLOT_LAG")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.max_backfill_slot_lag),
+        })
+    }
+
+    fn load_idl_source_configs() -> Vec<IdlSourceConfig> {
+        let mut configs = Vec::new();
+
+        // 格式: IDL_SOURCE_<INDEX>_PROGRAM_ID=<PROGRAM_ID>
+        //      IDL_SOURCE_<INDEX>_PATH=./idl/program.json
+        //      IDL_SOURCE_<INDEX>_EVENTS=EventA,EventB（可选）
+        for i in 1..=10 {
+            let program_id = match std::env::var(format!("IDL_SOURCE_{}_PROGRAM_ID", i)) {
+                Ok(program_id) => program_id,
+                Err(_) => continue,
+            };
+            let idl_path = std::env::var(format!("IDL_SOURCE_{}_PATH", i)).unwrap_or_default();
+            let events = std::env::var(format!("IDL_SOURCE_{}_EVENTS", i))
+                .map(|events| {
+                    events
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            info!("📜 加载IDL来源配置 {}: {} ({})", i, program_id, idl_path);
+            configs.push(IdlSourceConfig {
+                program_id,
+                idl_path,
+                events,
+            });
+        }
+
+        configs
+    }
+
     /// 解析程序ID列表从环境变量
     fn parse_program_ids() -> Result<Vec<Pubkey>> {
         // 1. 优先使用新格式 SUBSCRIBED_PROGRAM_IDS（逗号分隔）
@@ -536,6 +1069,25 @@ impl EventListenerConfig {
             return Err(EventListenerError::Config("WebSocket URL必须以ws或wss开头".to_string()));
         }
 
+        // 验证备用端点
+        let mut endpoint_names = HashSet::new();
+        endpoint_names.insert("primary".to_string());
+        for endpoint in &self.solana.failover.fallback_endpoints {
+            if !endpoint.rpc_url.starts_with("http") || !endpoint.ws_url.starts_with("ws") {
+                return Err(EventListenerError::Config(format!(
+                    "备用端点 {} 的URL格式无效",
+                    endpoint.name
+                )));
+            }
+            if !endpoint_names.insert(endpoint.name.clone()) {
+                return Err(EventListenerError::Config(format!("端点名称重复: {}", endpoint.name)));
+            }
+        }
+
+        if self.solana.failover.failure_threshold == 0 {
+            return Err(EventListenerError::Config("端点失败阈值必须大于0".to_string()));
+        }
+
         // 验证程序ID列表
         if self.solana.program_ids.is_empty() {
             return Err(EventListenerError::Config("至少需要配置一个程序ID".to_string()));
@@ -562,6 +1114,91 @@ impl EventListenerConfig {
             return Err(EventListenerError::Config("批量写入大小必须大于0".to_string()));
         }
 
+        if self.listener.batch_write.buffer_size < self.listener.batch_write.batch_size {
+            return Err(EventListenerError::Config(
+                "批量写入缓冲区大小不能小于批量写入大小".to_string(),
+            ));
+        }
+
+        if self.listener.batch_write.concurrent_writers == 0 {
+            return Err(EventListenerError::Config("并发写入线程数必须大于0".to_string()));
+        }
+
+        // 验证事件输出配置
+        let sinks = &self.listener.batch_write.sinks;
+        if !sinks.mongo_enabled && sinks.jsonl.is_none() && sinks.webhook.is_none() {
+            return Err(EventListenerError::Config("至少需要启用一个事件输出目标".to_string()));
+        }
+        if let Some(jsonl) = &sinks.jsonl {
+            if jsonl.directory.is_empty() {
+                return Err(EventListenerError::Config("JSONL输出目录不能为空".to_string()));
+            }
+        }
+        if let Some(webhook) = &sinks.webhook {
+            if !webhook.url.starts_with("http://") && !webhook.url.starts_with("https://") {
+                return Err(EventListenerError::Config(format!(
+                    "无效的webhook URL: {}",
+                    webhook.url
+                )));
+            }
+        }
+        if let Some(subscriptions) = &sinks.webhook_subscriptions {
+            if subscriptions.max_attempts == 0 {
+                return Err(EventListenerError::Config(
+                    "webhook订阅最大投递次数必须大于0".to_string(),
+                ));
+            }
+            if subscriptions.refresh_interval_secs == 0 {
+                return Err(EventListenerError::Config(
+                    "webhook订阅缓存刷新间隔必须大于0".to_string(),
+                ));
+            }
+        }
+
+        // 验证最终确认配置
+        if let Some(finality) = &self.finality {
+            if finality.batch_size == 0 || finality.batch_size > 256 {
+                return Err(EventListenerError::Config(
+                    "最终确认检查批量大小必须在1到256之间".to_string(),
+                ));
+            }
+            if finality.rollback_timeout_secs == 0 {
+                return Err(EventListenerError::Config("最终确认回滚超时必须大于0".to_string()));
+            }
+        }
+
+        // 验证IDL通用事件解析配置
+        if let Some(idl_events) = &self.idl_events {
+            for source in &idl_events.sources {
+                Pubkey::from_str(&source.program_id).map_err(|e| {
+                    EventListenerError::Config(format!("解析IDL程序ID失败: {} - {}", source.program_id, e))
+                })?;
+                if source.idl_path.is_empty() {
+                    return Err(EventListenerError::Config(format!(
+                        "IDL来源 {} 未配置IDL文件路径",
+                        source.program_id
+                    )));
+                }
+            }
+        }
+
+        // 验证事件摄取来源配置
+        if let Some(ingestion) = &self.ingestion {
+            if ingestion.mode == IngestionMode::Replay && ingestion.fixture_path.is_none() {
+                return Err(EventListenerError::Config("回放模式必须配置夹具路径".to_string()));
+            }
+            if ingestion.mode == IngestionMode::Replay && ingestion.record_path.is_some() {
+                return Err(EventListenerError::Config("回放模式下不能同时录制夹具".to_string()));
+            }
+        }
+
+        // 验证链上进度落后监控配置
+        if let Some(lag_monitor) = &self.lag_monitor {
+            if lag_monitor.tip_poll_interval_secs == 0 {
+                return Err(EventListenerError::Config("链上slot查询间隔必须大于0".to_string()));
+            }
+        }
+
         // 验证连接池配置
         if self.database.max_connections <= self.database.min_connections {
             return Err(EventListenerError::Config("最大连接数必须大于最小连接数".to_string()));
@@ -584,13 +1221,104 @@ impl Default for BackoffConfig {
     }
 }
 
+impl Default for EndpointFailoverConfig {
+    fn default() -> Self {
+        Self {
+            primary_weight: 100,
+            fallback_endpoints: Vec::new(),
+            failure_threshold: 3,
+            cooldown_secs: 30,
+            failback_check_interval_secs: 60,
+        }
+    }
+}
+
+impl Default for FinalityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_interval_secs: 10,
+            batch_size: 100,
+            rollback_timeout_secs: 180,
+        }
+    }
+}
+
+impl Default for LagMonitorConfig {
+    fn default() -> Self {
+        Self {
+            tip_poll_interval_secs: 10,
+            max_slot_lag: 150,
+            max_processing_delay_secs: 120,
+            max_backfill_slot_lag: 9000,
+        }
+    }
+}
+
 impl Default for BatchWriteConfig {
     fn default() -> Self {
         Self {
             batch_size: 50,
             max_wait_ms: 5000,
             buffer_size: 1000,
-            concurrent_writers: 4,
+            concurrent_writers: 1,
+            overflow_policy: OverflowPolicy::Block,
+            spill_path: None,
+            sinks: EventSinkConfig::default(),
+        }
+    }
+}
+
+impl Default for EventSinkConfig {
+    fn default() -> Self {
+        Self {
+            mongo_enabled: true,
+            jsonl: None,
+            webhook: None,
+            webhook_subscriptions: None,
+        }
+    }
+}
+
+impl Default for WebhookSubscriptionConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval_secs: 30,
+            timeout_ms: 10000,
+            max_attempts: 8,
+            retry_base_delay_secs: 10,
+            retry: SinkRetryConfig::default(),
+        }
+    }
+}
+
+impl Default for SinkRetryConfig {
+    fn default() -> Self {
+        Self {
+            queue_size: 1000,
+            max_retries: 5,
+            retry_delay_ms: 1000,
+        }
+    }
+}
+
+impl SinkRetryConfig {
+    /// 从带前缀的环境变量加载（<PREFIX>_QUEUE_SIZE / _MAX_RETRIES / _RETRY_DELAY_MS）
+    fn from_env(prefix: &str) -> Self {
+        let defaults = Self::default();
+        Self {
+            queue_size: std::env::var(format!("{}_QUEUE_SIZE", prefix))
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.queue_size),
+            max_retries: std::env::var(format!("{}_MAX_RETRIES", prefix))
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.max_retries),
+            retry_delay_ms: std::env::var(format!("{}_RETRY_DELAY_MS", prefix))
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.retry_delay_ms),
         }
     }
 }
@@ -623,13 +1351,130 @@ mod tests {
         assert_eq!(config.simple_reconnect_interval_ms, 500);
     }
 
+    #[test]
+    fn test_endpoint_failover_config_default() {
+        let config = EndpointFailoverConfig::default();
+        assert_eq!(config.primary_weight, 100);
+        assert!(config.fallback_endpoints.is_empty());
+        assert_eq!(config.failure_threshold, 3);
+        assert_eq!(config.cooldown_secs, 30);
+        assert_eq!(config.failback_check_interval_secs, 60);
+    }
+
     #[test]
     fn test_batch_write_config_default() {
         let config = BatchWriteConfig::default();
         assert_eq!(config.batch_size, 50);
         assert_eq!(config.max_wait_ms, 5000);
         assert_eq!(config.buffer_size, 1000);
-        assert_eq!(config.concurrent_writers, 4);
+        assert_eq!(config.concurrent_writers, 1);
+        assert_eq!(config.overflow_policy, OverflowPolicy::Block);
+        assert!(config.spill_path.is_none());
+        assert!(config.sinks.mongo_enabled);
+        assert!(config.sinks.jsonl.is_none());
+        assert!(config.sinks.webhook.is_none());
+        assert!(config.sinks.webhook_subscriptions.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_event_sink_validation() {
+        let mut config = EventListenerConfig::from_env().await.unwrap();
+        config.listener.batch_write.sinks = EventSinkConfig {
+            mongo_enabled: false,
+            jsonl: None,
+            webhook: None,
+            webhook_subscriptions: None,
+        };
+        assert!(config.validate().is_err());
+
+        config.listener.batch_write.sinks.webhook = Some(WebhookSinkConfig {
+            url: "ftp://example.com/events".to_string(),
+            auth_token: None,
+            timeout_ms: 10000,
+            retry: SinkRetryConfig::default(),
+        });
+        assert!(config.validate().is_err());
+
+        config.listener.batch_write.sinks.webhook.as_mut().unwrap().url = "https://example.com/events".to_string();
+        assert!(config.validate().is_ok());
+
+        config.listener.batch_write.sinks.webhook_subscriptions = Some(WebhookSubscriptionConfig {
+            max_attempts: 0,
+            ..Default::default()
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_idl_events_validation() {
+        let mut config = EventListenerConfig::from_env().await.unwrap();
+        config.idl_events = Some(IdlEventsConfig {
+            sources: vec![IdlSourceConfig {
+                program_id: "invalid".to_string(),
+                idl_path: "./idl/program.json".to_string(),
+                events: Vec::new(),
+            }],
+        });
+        assert!(config.validate().is_err());
+
+        let source = &mut config.idl_events.as_mut().unwrap().sources[0];
+        source.program_id = "FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX".to_string();
+        source.idl_path = String::new();
+        assert!(config.validate().is_err());
+
+        config.idl_events.as_mut().unwrap().sources[0].idl_path = "./idl/program.json".to_string();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_overflow_policy_from_str() {
+        assert_eq!("block".parse::<OverflowPolicy>().unwrap(), OverflowPolicy::Block);
+        assert_eq!("Spill".parse::<OverflowPolicy>().unwrap(), OverflowPolicy::Spill);
+        assert_eq!("DROP".parse::<OverflowPolicy>().unwrap(), OverflowPolicy::Drop);
+        assert!("discard".parse::<OverflowPolicy>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ingestion_config_validation() {
+        assert_eq!("replay".parse::<IngestionMode>().unwrap(), IngestionMode::Replay);
+        assert_eq!(
+            "logs_subscribe".parse::<IngestionMode>().unwrap(),
+            IngestionMode::LogsSubscribe
+        );
+        assert!("grpc".parse::<IngestionMode>().is_err());
+
+        let mut config = EventListenerConfig::from_env().await.unwrap();
+        config.ingestion = Some(IngestionConfig {
+            mode: IngestionMode::Replay,
+            ..Default::default()
+        });
+        assert!(config.validate().is_err());
+
+        let ingestion = config.ingestion.as_mut().unwrap();
+        ingestion.fixture_path = Some("./fixtures/swap.jsonl".to_string());
+        ingestion.record_path = Some("./fixtures/recorded.jsonl".to_string());
+        assert!(config.validate().is_err());
+
+        config.ingestion.as_mut().unwrap().record_path = None;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_lag_monitor_config_default() {
+        let config = LagMonitorConfig::default();
+        assert_eq!(config.tip_poll_interval_secs, 10);
+        assert_eq!(config.max_slot_lag, 150);
+        assert_eq!(config.max_processing_delay_secs, 120);
+        assert_eq!(config.max_backfill_slot_lag, 9000);
+    }
+
+    #[test]
+    fn test_finality_config_default() {
+        let config = FinalityConfig::default();
+        assert!(config.enabled);
+        assert_eq!(config.check_interval_secs, 10);
+        assert_eq!(config.batch_size, 100);
+        assert_eq!(config.rollback_timeout_secs, 180);
     }
 
     #[tokio::test]
@@ -643,4 +1488,41 @@ mod tests {
         // 清理环境变量
         env::remove_var("RAYDIUM_PROGRAM_ID");
     }
+
+    #[tokio::test]
+    async fn test_fallback_endpoint_validation() {
+        let mut config = EventListenerConfig::from_env().await.unwrap();
+        config.solana.failover.fallback_endpoints = vec![RpcEndpointConfig {
+            name: "backup".to_string(),
+            rpc_url: "https://backup.example.com".to_string(),
+            ws_url: "wss://backup.example.com".to_string(),
+            weight: 50,
+        }];
+        assert!(config.validate().is_ok());
+
+        let endpoints = config.get_all_endpoints();
+        assert_eq!(endpoints.len(), 2);
+        assert_eq!(endpoints[0].name, "primary");
+        assert_eq!(endpoints[0].rpc_url, config.solana.rpc_url);
+
+        // 备用端点URL格式错误
+        config.solana.failover.fallback_endpoints[0].ws_url = "https://backup.example.com".to_string();
+        assert!(config.validate().is_err());
+
+        // 端点名称重复
+        config.solana.failover.fallback_endpoints[0].ws_url = "wss://backup.example.com".to_string();
+        config.solana.failover.fallback_endpoints[0].name = "primary".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_finality_config_validation() {
+        let mut config = EventListenerConfig::from_env().await.unwrap();
+        config.finality = Some(FinalityConfig::default());
+        assert!(config.validate().is_ok());
+
+        // getSignatureStatuses单次最多查询256个签名
+        config.finality.as_mut().unwrap().batch_size = 300;
+        assert!(config.validate().is_err());
+    }
 }
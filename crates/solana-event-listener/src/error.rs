@@ -18,6 +18,9 @@ pub enum EventListenerError {
     #[error("持久化错误: {0}")]
     Persistence(String),
 
+    #[error("前置事件尚未写入: {0}")]
+    OutOfOrder(String),
+
     #[error("检查点错误: {0}")]
     Checkpoint(String),
 
@@ -4,7 +4,6 @@ pub mod metrics;
 pub mod parser;
 pub mod persistence;
 pub mod recovery;
-pub mod services;
 pub mod subscriber;
 
 #[cfg(test)]
@@ -13,8 +12,14 @@ pub mod tests;
 pub use error::{EventListenerError, Result};
 
 use crate::{
-    config::EventListenerConfig, metrics::MetricsCollector, parser::EventParserRegistry, persistence::BatchWriter,
-    recovery::{BackfillManager, CheckpointPersistence, ScanRecordPersistence},
+    config::{EventListenerConfig, RuntimeConfigWatcher},
+    metrics::{MetricsCollector, MetricsHttpServer},
+    parser::EventParserRegistry,
+    persistence::{BatchWriter, WebhookDispatcher},
+    recovery::{
+        backfill_manager::compute_backfill_parser_keys, BackfillEventRegistry, BackfillManager, CheckpointPersistence,
+        CommitmentFinalizer, DeadLetterReplayer, ScanRecordPersistence,
+    },
     subscriber::SubscriptionManager,
 };
 use std::sync::Arc;
@@ -30,6 +35,10 @@ use utils::{MetaplexService, TokenMetadataProvider};
 /// - 批量持久化
 /// - 监控指标收集
 /// - 历史事件回填
+/// - 临时事件最终确认与回滚
+/// - 死信事件重放
+/// - 运行时配置热更新
+/// - webhook订阅投递
 #[derive(Clone)]
 pub struct EventListenerService {
     config: Arc<EventListenerConfig>,
@@ -38,6 +47,10 @@ pub struct EventListenerService {
     batch_writer: Arc<BatchWriter>,
     metrics: Arc<MetricsCollector>,
     backfill_manager: Option<Arc<BackfillManager>>,
+    commitment_finalizer: Option<Arc<CommitmentFinalizer>>,
+    dead_letter_replayer: Arc<DeadLetterReplayer>,
+    runtime_config_watcher: Arc<RuntimeConfigWatcher>,
+    webhook_dispatcher: Option<Arc<WebhookDispatcher>>,
 }
 
 impl EventListenerService {
@@ -50,6 +63,7 @@ impl EventListenerService {
         // 初始化各个组件
         let metrics = Arc::new(MetricsCollector::new(&config)?);
         let batch_writer = Arc::new(BatchWriter::new(&config).await?);
+        metrics.register_event_sinks(batch_writer.secondary_sinks()).await;
 
         // 创建 MetaplexService 作为代币元数据提供者
         let metadata_provider = match MetaplexService::new(None) {
@@ -72,18 +86,7 @@ impl EventListenerService {
             if backfill_config.enabled {
                 info!("🔄 预计算回填ParserKey配置...");
                 let event_configs = config.get_backfill_event_configs()?;
-                
-                let mut keys = std::collections::HashSet::new();
-                for event_config in &event_configs {
-                    if event_config.enabled {
-                        let discriminator = crate::parser::event_parser::calculate_event_discriminator(&event_config.event_type);
-                        let parser_key = crate::parser::event_parser::ParserKey::for_program(event_config.program_id, discriminator);
-                        keys.insert(parser_key);
-                        
-                        info!("🔑 预计算回填事件 {} 的ParserKey: program={}, discriminator={:?}", 
-                            event_config.event_type, event_config.program_id, discriminator);
-                    }
-                }
+                let keys = compute_backfill_parser_keys(&BackfillEventRegistry::new(), &event_configs);
                 info!("✅ 预计算完成，共 {} 个回填ParserKey", keys.len());
                 Some(keys)
             } else {
@@ -140,7 +143,8 @@ impl EventListenerService {
                     scan_record_persistence,
                     event_configs,
                     default_check_interval,
-                );
+                )?;
+                metrics.register_endpoint_pool(manager.endpoint_pool()).await;
                 
                 info!("✅ 回填管理器初始化完成");
                 Some(Arc::new(manager))
@@ -153,6 +157,44 @@ impl EventListenerService {
             None
         };
 
+        // 初始化最终确认任务（订阅承诺级别低于finalized时启用）
+        let commitment_finalizer = match &config.finality {
+            Some(finality_config) if finality_config.enabled && config.solana.commitment != "finalized" => {
+                info!("🔏 初始化最终确认任务...");
+                Some(Arc::new(CommitmentFinalizer::new(
+                    &config,
+                    batch_writer.event_storage(),
+                    Arc::clone(&metrics),
+                )?))
+            }
+            _ => {
+                info!("⚠️ 最终确认任务未启用");
+                None
+            }
+        };
+
+        let dead_letter_replayer = Arc::new(DeadLetterReplayer::new(Arc::clone(&batch_writer)));
+
+        let runtime_config_watcher = Arc::new(RuntimeConfigWatcher::new(
+            batch_writer.event_storage(),
+            Arc::clone(&subscription_manager),
+            Arc::clone(&parser_registry),
+            backfill_manager.clone(),
+            config.backfill.as_ref().and_then(|backfill| backfill.default_check_interval_secs),
+        ));
+
+        // 初始化webhook订阅投递任务（如果启用）
+        let webhook_dispatcher = match &config.listener.batch_write.sinks.webhook_subscriptions {
+            Some(subscriptions) => Some(Arc::new(WebhookDispatcher::new(
+                batch_writer.event_storage(),
+                subscriptions,
+            ))),
+            None => {
+                info!("⚠️ webhook订阅投递未启用");
+                None
+            }
+        };
+
         info!("✅ Event-Listener服务初始化完成");
 
         Ok(Self {
@@ -162,6 +204,10 @@ impl EventListenerService {
             batch_writer,
             metrics,
             backfill_manager,
+            commitment_finalizer,
+            dead_letter_replayer,
+            runtime_config_watcher,
+            webhook_dispatcher,
         })
     }
 
@@ -210,6 +256,74 @@ impl EventListenerService {
             None
         };
 
+        // 启动断线缺口回填（依赖回填管理器）
+        let gap_recovery_task = if let Some(backfill_manager) = &self.backfill_manager {
+            let manager = Arc::clone(backfill_manager);
+            let gap_tracker = self.subscription_manager.gap_tracker();
+            Some(tokio::spawn(async move {
+                if let Err(e) = manager.run_gap_recovery(gap_tracker).await {
+                    error!("断线缺口回填服务启动失败: {}", e);
+                }
+            }))
+        } else {
+            None
+        };
+
+        // 启动最终确认任务（如果启用）
+        let finalizer_task = if let Some(commitment_finalizer) = &self.commitment_finalizer {
+            let finalizer = Arc::clone(commitment_finalizer);
+            Some(tokio::spawn(async move {
+                if let Err(e) = finalizer.start().await {
+                    error!("最终确认任务启动失败: {}", e);
+                }
+            }))
+        } else {
+            None
+        };
+
+        let dead_letter_task = {
+            let replayer = Arc::clone(&self.dead_letter_replayer);
+            tokio::spawn(async move {
+                if let Err(e) = replayer.start().await {
+                    error!("死信事件重放任务启动失败: {}", e);
+                }
+            })
+        };
+
+        let runtime_config_task = {
+            let watcher = Arc::clone(&self.runtime_config_watcher);
+            tokio::spawn(async move {
+                if let Err(e) = watcher.start().await {
+                    error!("运行时配置热更新任务启动失败: {}", e);
+                }
+            })
+        };
+
+        // 启动webhook订阅投递任务（如果启用）
+        let webhook_task = self.webhook_dispatcher.as_ref().map(|dispatcher| {
+            let dispatcher = Arc::clone(dispatcher);
+            tokio::spawn(async move {
+                if let Err(e) = dispatcher.start().await {
+                    error!("webhook订阅投递任务启动失败: {}", e);
+                }
+            })
+        });
+
+        // 启动指标/状态HTTP服务（如果配置了监听地址）
+        let http_task = self.config.monitoring.http_bind_addr.clone().map(|bind_addr| {
+            let server = MetricsHttpServer::new(
+                Arc::clone(&self.metrics),
+                Arc::clone(&self.subscription_manager),
+                Arc::clone(&self.batch_writer),
+                self.backfill_manager.clone(),
+            );
+            tokio::spawn(async move {
+                if let Err(e) = server.serve(&bind_addr).await {
+                    error!("指标/状态HTTP服务启动失败: {}", e);
+                }
+            })
+        });
+
         info!("✅ Event-Listener服务启动完成");
 
         // 等待关闭信号
@@ -221,11 +335,25 @@ impl EventListenerService {
         subscription_task.abort();
         batch_writer_task.abort();
         metrics_task.abort();
+        dead_letter_task.abort();
+        runtime_config_task.abort();
         
         // 停止回填任务（如果存在）
         if let Some(task) = backfill_task {
             task.abort();
         }
+        if let Some(task) = gap_recovery_task {
+            task.abort();
+        }
+        if let Some(task) = finalizer_task {
+            task.abort();
+        }
+        if let Some(task) = webhook_task {
+            task.abort();
+        }
+        if let Some(task) = http_task {
+            task.abort();
+        }
 
         // 执行清理工作
         self.shutdown().await?;
@@ -263,6 +391,11 @@ impl EventListenerService {
 
     /// 执行优雅关闭
     async fn shutdown(&self) -> Result<()> {
+        // 停止回填任务
+        if let Some(backfill_manager) = &self.backfill_manager {
+            backfill_manager.stop().await;
+        }
+
         // 停止订阅
         if let Err(e) = self.subscription_manager.stop().await {
             warn!("停止订阅管理器时出错: {}", e);
@@ -312,6 +445,7 @@ impl EventListenerService {
             subscription_manager: subscription_healthy,
             batch_writer: batch_writer_healthy,
             backfill_manager: backfill_healthy,
+            lag: self.metrics.lag_tracker().report(),
             metrics: self.metrics.get_stats().await?,
         })
     }
@@ -324,5 +458,6 @@ pub struct HealthStatus {
     pub subscription_manager: bool,
     pub batch_writer: bool,
     pub backfill_manager: bool,
+    pub lag: crate::metrics::LagReport,
     pub metrics: crate::metrics::MetricsStats,
 }
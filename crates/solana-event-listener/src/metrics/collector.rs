@@ -1,4 +1,10 @@
-use crate::{config::EventListenerConfig, error::Result};
+use crate::{
+    config::EventListenerConfig,
+    error::Result,
+    metrics::lag_tracker::{ChainLagTracker, LagVerdict},
+    persistence::event_sink::SinkWorker,
+    subscriber::endpoint_pool::EndpointPool,
+};
 use std::{
     collections::HashMap,
     sync::{
@@ -93,6 +99,23 @@ pub struct MetricsCollector {
     websocket_latencies: Arc<RwLock<Vec<Duration>>>,
     batch_write_durations: Arc<RwLock<Vec<Duration>>>,
 
+    // 断线缺口回填指标
+    gap_backfills: Arc<AtomicU64>,
+    gap_signatures: Arc<AtomicU64>,
+    gap_backfilled_events: Arc<AtomicU64>,
+    last_gap_slots: Arc<AtomicU64>,
+    gap_recovery_durations: Arc<RwLock<Vec<Duration>>>,
+
+    // 最终确认检查指标
+    finalized_transactions: Arc<AtomicU64>,
+    rolled_back_transactions: Arc<AtomicU64>,
+    pending_finality_transactions: Arc<AtomicU64>,
+
+    // 日志截断回退解析指标
+    truncated_log_fallbacks: Arc<AtomicU64>,
+    truncated_log_fallback_failures: Arc<AtomicU64>,
+    truncated_log_recovered_events: Arc<AtomicU64>,
+
     // 系统指标
     start_time: Instant,
     last_metrics_report: Arc<RwLock<Option<Instant>>>,
@@ -100,6 +123,15 @@ pub struct MetricsCollector {
 
     // 自定义指标存储
     custom_metrics: Arc<RwLock<HashMap<String, MetricData>>>,
+
+    // 已注册的RPC/WebSocket端点池
+    endpoint_pools: Arc<RwLock<Vec<Arc<EndpointPool>>>>,
+
+    // 已注册的附加事件输出目标
+    event_sinks: Arc<RwLock<Vec<Arc<SinkWorker>>>>,
+
+    // 链上进度落后追踪
+    lag_tracker: Arc<ChainLagTracker>,
 }
 
 /// 指标统计信息
@@ -116,6 +148,17 @@ pub struct MetricsStats {
     pub avg_processing_duration_ms: f64,
     pub avg_websocket_latency_ms: f64,
     pub avg_batch_write_duration_ms: f64,
+    pub gap_backfills: u64,
+    pub gap_signatures: u64,
+    pub gap_backfilled_events: u64,
+    pub last_gap_slots: u64,
+    pub avg_gap_recovery_latency_ms: f64,
+    pub finalized_transactions: u64,
+    pub rolled_back_transactions: u64,
+    pub pending_finality_transactions: u64,
+    pub truncated_log_fallbacks: u64,
+    pub truncated_log_fallback_failures: u64,
+    pub truncated_log_recovered_events: u64,
     pub is_running: bool,
     #[serde(skip)]
     pub last_metrics_report: Option<Instant>,
@@ -178,10 +221,24 @@ impl MetricsCollector {
             processing_durations: Arc::new(RwLock::new(Vec::new())),
             websocket_latencies: Arc::new(RwLock::new(Vec::new())),
             batch_write_durations: Arc::new(RwLock::new(Vec::new())),
+            gap_backfills: Arc::new(AtomicU64::new(0)),
+            gap_signatures: Arc::new(AtomicU64::new(0)),
+            gap_backfilled_events: Arc::new(AtomicU64::new(0)),
+            last_gap_slots: Arc::new(AtomicU64::new(0)),
+            gap_recovery_durations: Arc::new(RwLock::new(Vec::new())),
+            finalized_transactions: Arc::new(AtomicU64::new(0)),
+            rolled_back_transactions: Arc::new(AtomicU64::new(0)),
+            pending_finality_transactions: Arc::new(AtomicU64::new(0)),
+            truncated_log_fallbacks: Arc::new(AtomicU64::new(0)),
+            truncated_log_fallback_failures: Arc::new(AtomicU64::new(0)),
+            truncated_log_recovered_events: Arc::new(AtomicU64::new(0)),
             start_time: Instant::now(),
             last_metrics_report: Arc::new(RwLock::new(None)),
             system_monitor: Arc::new(RwLock::new(system)),
             custom_metrics: Arc::new(RwLock::new(HashMap::new())),
+            endpoint_pools: Arc::new(RwLock::new(Vec::new())),
+            event_sinks: Arc::new(RwLock::new(Vec::new())),
+            lag_tracker: Arc::new(ChainLagTracker::new(config.lag_monitor.clone().unwrap_or_default())),
         })
     }
 
@@ -225,6 +282,26 @@ impl MetricsCollector {
         Ok(())
     }
 
+    /// 注册端点池，导出其健康状态指标
+    pub async fn register_endpoint_pool(&self, pool: Arc<EndpointPool>) {
+        info!("📊 注册{}端点池指标（{}个端点）", pool.role().as_str(), pool.len());
+        self.endpoint_pools.write().await.push(pool);
+    }
+
+    /// 注册附加事件输出目标，导出其写入、重试和队列指标
+    pub async fn register_event_sinks(&self, sinks: &[Arc<SinkWorker>]) {
+        if sinks.is_empty() {
+            return;
+        }
+        info!("📊 注册{}个事件输出目标指标", sinks.len());
+        self.event_sinks.write().await.extend(sinks.iter().cloned());
+    }
+
+    /// 获取链上进度落后追踪器
+    pub fn lag_tracker(&self) -> Arc<ChainLagTracker> {
+        Arc::clone(&self.lag_tracker)
+    }
+
     /// 记录事件处理成功 - 支持多程序标签
     pub async fn record_event_processed(&self) -> Result<()> {
         self.events_processed.fetch_add(1, Ordering::Relaxed);
@@ -307,6 +384,63 @@ impl MetricsCollector {
         Ok(())
     }
 
+    /// 记录一次断线缺口回填
+    pub async fn record_gap_backfill(
+        &self,
+        gap_signatures: u64,
+        gap_slots: u64,
+        backfilled_events: u64,
+        recovery_latency: Duration,
+    ) -> Result<()> {
+        self.gap_backfills.fetch_add(1, Ordering::Relaxed);
+        self.gap_signatures.fetch_add(gap_signatures, Ordering::Relaxed);
+        self.gap_backfilled_events
+            .fetch_add(backfilled_events, Ordering::Relaxed);
+        self.last_gap_slots.store(gap_slots, Ordering::Relaxed);
+
+        let mut durations = self.gap_recovery_durations.write().await;
+        durations.push(recovery_latency);
+
+        // 保持最近1000个样本
+        if durations.len() > 1000 {
+            durations.remove(0);
+        }
+
+        debug!(
+            "🩹 记录缺口回填: {}个签名, {}个slot, 回填{}个, 耗时{:?}",
+            gap_signatures, gap_slots, backfilled_events, recovery_latency
+        );
+        Ok(())
+    }
+
+    /// 记录一轮最终确认检查结果
+    pub fn record_finality_check(&self, finalized: u64, rolled_back: u64, pending: u64) {
+        self.finalized_transactions.fetch_add(finalized, Ordering::Relaxed);
+        self.rolled_back_transactions.fetch_add(rolled_back, Ordering::Relaxed);
+        self.pending_finality_transactions.store(pending, Ordering::Relaxed);
+
+        debug!(
+            "🔏 记录最终确认检查: 确认{}笔, 回滚{}笔, 待确认{}笔",
+            finalized, rolled_back, pending
+        );
+    }
+
+    /// 记录一次日志截断回退解析（从完整交易中解析事件）
+    pub fn record_truncated_log_fallback(&self, success: bool, recovered_events: u64) {
+        self.truncated_log_fallbacks.fetch_add(1, Ordering::Relaxed);
+        if success {
+            self.truncated_log_recovered_events
+                .fetch_add(recovered_events, Ordering::Relaxed);
+        } else {
+            self.truncated_log_fallback_failures.fetch_add(1, Ordering::Relaxed);
+        }
+
+        debug!(
+            "✂️ 记录日志截断回退解析: 成功={}, 补回{}个事件",
+            success, recovered_events
+        );
+    }
+
     /// 记录事件处理耗时
     pub async fn record_processing_duration(&self, duration: Duration) -> Result<()> {
         let mut durations = self.processing_durations.write().await;
@@ -588,6 +722,16 @@ impl MetricsCollector {
             }
         };
 
+        let avg_gap_recovery_latency_ms = {
+            let durations = self.gap_recovery_durations.read().await;
+            if durations.is_empty() {
+                0.0
+            } else {
+                let total: Duration = durations.iter().sum();
+                total.as_millis() as f64 / durations.len() as f64
+            }
+        };
+
         let custom_metrics_count = {
             let metrics = self.custom_metrics.read().await;
             metrics.len()
@@ -605,6 +749,17 @@ impl MetricsCollector {
             avg_processing_duration_ms,
             avg_websocket_latency_ms,
             avg_batch_write_duration_ms,
+            gap_backfills: self.gap_backfills.load(Ordering::Relaxed),
+            gap_signatures: self.gap_signatures.load(Ordering::Relaxed),
+            gap_backfilled_events: self.gap_backfilled_events.load(Ordering::Relaxed),
+            last_gap_slots: self.last_gap_slots.load(Ordering::Relaxed),
+            avg_gap_recovery_latency_ms,
+            finalized_transactions: self.finalized_transactions.load(Ordering::Relaxed),
+            rolled_back_transactions: self.rolled_back_transactions.load(Ordering::Relaxed),
+            pending_finality_transactions: self.pending_finality_transactions.load(Ordering::Relaxed),
+            truncated_log_fallbacks: self.truncated_log_fallbacks.load(Ordering::Relaxed),
+            truncated_log_fallback_failures: self.truncated_log_fallback_failures.load(Ordering::Relaxed),
+            truncated_log_recovered_events: self.truncated_log_recovered_events.load(Ordering::Relaxed),
             is_running: *self.is_running.read().await,
             last_metrics_report: *self.last_metrics_report.read().await,
             custom_metrics_count,
@@ -621,12 +776,27 @@ impl MetricsCollector {
         self.websocket_reconnections.store(0, Ordering::Relaxed);
         self.batch_writes.store(0, Ordering::Relaxed);
         self.checkpoint_saves.store(0, Ordering::Relaxed);
+        self.gap_backfills.store(0, Ordering::Relaxed);
+        self.gap_signatures.store(0, Ordering::Relaxed);
+        self.gap_backfilled_events.store(0, Ordering::Relaxed);
+        self.last_gap_slots.store(0, Ordering::Relaxed);
+        self.finalized_transactions.store(0, Ordering::Relaxed);
+        self.rolled_back_transactions.store(0, Ordering::Relaxed);
+        self.pending_finality_transactions.store(0, Ordering::Relaxed);
+        self.truncated_log_fallbacks.store(0, Ordering::Relaxed);
+        self.truncated_log_fallback_failures.store(0, Ordering::Relaxed);
+        self.truncated_log_recovered_events.store(0, Ordering::Relaxed);
 
         {
             let mut durations = self.processing_durations.write().await;
             durations.clear();
         }
 
+        {
+            let mut durations = self.gap_recovery_durations.write().await;
+            durations.clear();
+        }
+
         {
             let mut latencies = self.websocket_latencies.write().await;
             latencies.clear();
@@ -752,6 +922,86 @@ impl MetricsCollector {
             base_labels, stats.checkpoint_saves
         ));
 
+        // === 断线缺口回填指标 ===
+        output.push_str("# HELP gap_backfills_total Total number of gap backfills triggered by WebSocket reconnects\n");
+        output.push_str("# TYPE gap_backfills_total counter\n");
+        output.push_str(&format!(
+            "gap_backfills_total{{{}}} {}\n",
+            base_labels, stats.gap_backfills
+        ));
+
+        output.push_str("# HELP gap_signatures_total Total number of signatures found in reconnect gaps\n");
+        output.push_str("# TYPE gap_signatures_total counter\n");
+        output.push_str(&format!(
+            "gap_signatures_total{{{}}} {}\n",
+            base_labels, stats.gap_signatures
+        ));
+
+        output.push_str("# HELP gap_backfilled_events_total Total number of transactions recovered by gap backfill\n");
+        output.push_str("# TYPE gap_backfilled_events_total counter\n");
+        output.push_str(&format!(
+            "gap_backfilled_events_total{{{}}} {}\n",
+            base_labels, stats.gap_backfilled_events
+        ));
+
+        output.push_str("# HELP gap_last_size_slots Slot span of the most recent reconnect gap\n");
+        output.push_str("# TYPE gap_last_size_slots gauge\n");
+        output.push_str(&format!(
+            "gap_last_size_slots{{{}}} {}\n",
+            base_labels, stats.last_gap_slots
+        ));
+
+        output.push_str("# HELP gap_recovery_latency_ms Average time from reconnect to gap fully recovered\n");
+        output.push_str("# TYPE gap_recovery_latency_ms gauge\n");
+        output.push_str(&format!(
+            "gap_recovery_latency_ms{{{}}} {:.2}\n",
+            base_labels, stats.avg_gap_recovery_latency_ms
+        ));
+
+        // === 最终确认指标 ===
+        output.push_str("# HELP finalized_transactions_total Total number of transactions confirmed at finalized\n");
+        output.push_str("# TYPE finalized_transactions_total counter\n");
+        output.push_str(&format!(
+            "finalized_transactions_total{{{}}} {}\n",
+            base_labels, stats.finalized_transactions
+        ));
+
+        output.push_str("# HELP rolled_back_transactions_total Total number of transactions rolled back after failing to finalize\n");
+        output.push_str("# TYPE rolled_back_transactions_total counter\n");
+        output.push_str(&format!(
+            "rolled_back_transactions_total{{{}}} {}\n",
+            base_labels, stats.rolled_back_transactions
+        ));
+
+        output.push_str("# HELP pending_finality_transactions Number of transactions still awaiting finalization in the last check\n");
+        output.push_str("# TYPE pending_finality_transactions gauge\n");
+        output.push_str(&format!(
+            "pending_finality_transactions{{{}}} {}\n",
+            base_labels, stats.pending_finality_transactions
+        ));
+
+        // === 日志截断回退解析指标 ===
+        output.push_str("# HELP truncated_log_fallbacks_total Transactions re-parsed after log truncation\n");
+        output.push_str("# TYPE truncated_log_fallbacks_total counter\n");
+        output.push_str(&format!(
+            "truncated_log_fallbacks_total{{{}}} {}\n",
+            base_labels, stats.truncated_log_fallbacks
+        ));
+
+        output.push_str("# HELP truncated_log_fallback_failures_total Failed truncated log fallbacks\n");
+        output.push_str("# TYPE truncated_log_fallback_failures_total counter\n");
+        output.push_str(&format!(
+            "truncated_log_fallback_failures_total{{{}}} {}\n",
+            base_labels, stats.truncated_log_fallback_failures
+        ));
+
+        output.push_str("# HELP truncated_log_recovered_events_total Events recovered by truncated log fallback\n");
+        output.push_str("# TYPE truncated_log_recovered_events_total counter\n");
+        output.push_str(&format!(
+            "truncated_log_recovered_events_total{{{}}} {}\n",
+            base_labels, stats.truncated_log_recovered_events
+        ));
+
         // === 性能指标 ===
         output.push_str("# HELP processing_duration_ms Average event processing duration in milliseconds\n");
         output.push_str("# TYPE processing_duration_ms gauge\n");
@@ -799,6 +1049,185 @@ impl MetricsCollector {
             if stats.is_running { 1 } else { 0 }
         ));
 
+        // === RPC端点指标 ===
+        let endpoint_pools = self.endpoint_pools.read().await;
+        if !endpoint_pools.is_empty() {
+            output.push_str("# HELP solana_endpoint_health_score Health score of each Solana endpoint (0-1)\n");
+            output.push_str("# TYPE solana_endpoint_health_score gauge\n");
+            for pool in endpoint_pools.iter() {
+                for endpoint in pool.stats() {
+                    output.push_str(&format!(
+                        "solana_endpoint_health_score{{{},role=\"{}\",endpoint=\"{}\"}} {:.4}\n",
+                        base_labels, endpoint.role, endpoint.name, endpoint.health_score
+                    ));
+                }
+            }
+
+            output.push_str(
+                "# HELP solana_endpoint_active Whether the endpoint is currently in use (1=active, 0=standby)\n",
+            );
+            output.push_str("# TYPE solana_endpoint_active gauge\n");
+            for pool in endpoint_pools.iter() {
+                for endpoint in pool.stats() {
+                    output.push_str(&format!(
+                        "solana_endpoint_active{{{},role=\"{}\",endpoint=\"{}\"}} {}\n",
+                        base_labels,
+                        endpoint.role,
+                        endpoint.name,
+                        if endpoint.is_active { 1 } else { 0 }
+                    ));
+                }
+            }
+
+            output.push_str("# HELP solana_endpoint_requests_total Total requests per Solana endpoint by outcome\n");
+            output.push_str("# TYPE solana_endpoint_requests_total counter\n");
+            for pool in endpoint_pools.iter() {
+                for endpoint in pool.stats() {
+                    output.push_str(&format!(
+                        "solana_endpoint_requests_total{{{},role=\"{}\",endpoint=\"{}\",outcome=\"success\"}} {}\n",
+                        base_labels, endpoint.role, endpoint.name, endpoint.total_successes
+                    ));
+                    output.push_str(&format!(
+                        "solana_endpoint_requests_total{{{},role=\"{}\",endpoint=\"{}\",outcome=\"failure\"}} {}\n",
+                        base_labels, endpoint.role, endpoint.name, endpoint.total_failures
+                    ));
+                }
+            }
+
+            output.push_str("# HELP solana_endpoint_failovers_total Total number of endpoint switches\n");
+            output.push_str("# TYPE solana_endpoint_failovers_total counter\n");
+            for pool in endpoint_pools.iter() {
+                output.push_str(&format!(
+                    "solana_endpoint_failovers_total{{{},role=\"{}\"}} {}\n",
+                    base_labels,
+                    pool.role().as_str(),
+                    pool.failover_count()
+                ));
+            }
+        }
+        drop(endpoint_pools);
+
+        // === 事件输出目标指标 ===
+        let event_sinks = self.event_sinks.read().await;
+        if !event_sinks.is_empty() {
+            let sink_stats: Vec<_> = event_sinks.iter().map(|sink| sink.stats()).collect();
+
+            output.push_str("# HELP event_sink_events_total Total events per event sink by outcome\n");
+            output.push_str("# TYPE event_sink_events_total counter\n");
+            for stats in &sink_stats {
+                for (outcome, value) in [
+                    ("written", stats.events_written),
+                    ("failed", stats.events_failed),
+                    ("dropped", stats.events_dropped),
+                ] {
+                    output.push_str(&format!(
+                        "event_sink_events_total{{{},sink=\"{}\",outcome=\"{}\"}} {}\n",
+                        base_labels, stats.name, outcome, value
+                    ));
+                }
+            }
+
+            output.push_str("# HELP event_sink_retries_total Total write retries per event sink\n");
+            output.push_str("# TYPE event_sink_retries_total counter\n");
+            for stats in &sink_stats {
+                output.push_str(&format!(
+                    "event_sink_retries_total{{{},sink=\"{}\"}} {}\n",
+                    base_labels, stats.name, stats.retries
+                ));
+            }
+
+            output.push_str("# HELP event_sink_queued_batches Batches waiting in each event sink queue\n");
+            output.push_str("# TYPE event_sink_queued_batches gauge\n");
+            for stats in &sink_stats {
+                output.push_str(&format!(
+                    "event_sink_queued_batches{{{},sink=\"{}\"}} {}\n",
+                    base_labels, stats.name, stats.queued_batches
+                ));
+            }
+        }
+        drop(event_sinks);
+
+        // === 链上进度落后指标 ===
+        let lag_report = self.lag_tracker.report();
+        if let Some(chain_tip) = lag_report.chain_tip_slot {
+            output.push_str("# HELP chain_tip_slot Latest slot reported by the RPC node\n");
+            output.push_str("# TYPE chain_tip_slot gauge\n");
+            output.push_str(&format!("chain_tip_slot{{{}}} {}\n", base_labels, chain_tip));
+        }
+
+        if !lag_report.programs.is_empty() {
+            output.push_str("# HELP indexer_latest_processed_slot Latest slot processed per program and event type\n");
+            output.push_str("# TYPE indexer_latest_processed_slot gauge\n");
+            for program in &lag_report.programs {
+                output.push_str(&format!(
+                    "indexer_latest_processed_slot{{{},program_id=\"{}\",event_type=\"{}\"}} {}\n",
+                    base_labels, program.program_id, program.event_type, program.latest_slot
+                ));
+            }
+
+            output.push_str("# HELP indexer_slot_lag Slots between the chain tip and the latest processed slot\n");
+            output.push_str("# TYPE indexer_slot_lag gauge\n");
+            for program in &lag_report.programs {
+                if let Some(slot_lag) = program.slot_lag {
+                    output.push_str(&format!(
+                        "indexer_slot_lag{{{},program_id=\"{}\",event_type=\"{}\"}} {}\n",
+                        base_labels, program.program_id, program.event_type, slot_lag
+                    ));
+                }
+            }
+
+            output.push_str(
+                "# HELP indexer_processing_delay_seconds Seconds from block time to processing of the latest event\n",
+            );
+            output.push_str("# TYPE indexer_processing_delay_seconds gauge\n");
+            for program in &lag_report.programs {
+                if let Some(delay) = program.processing_delay_secs {
+                    output.push_str(&format!(
+                        "indexer_processing_delay_seconds{{{},program_id=\"{}\",event_type=\"{}\"}} {}\n",
+                        base_labels, program.program_id, program.event_type, delay
+                    ));
+                }
+            }
+        }
+
+        if !lag_report.backfill_checkpoints.is_empty() {
+            output.push_str("# HELP backfill_checkpoint_slot Slot of the latest backfill checkpoint\n");
+            output.push_str("# TYPE backfill_checkpoint_slot gauge\n");
+            for checkpoint in &lag_report.backfill_checkpoints {
+                output.push_str(&format!(
+                    "backfill_checkpoint_slot{{{},program_id=\"{}\",event_name=\"{}\"}} {}\n",
+                    base_labels, checkpoint.program_id, checkpoint.event_name, checkpoint.checkpoint_slot
+                ));
+            }
+
+            output.push_str(
+                "# HELP backfill_checkpoint_slot_lag Slots between the chain tip and the backfill checkpoint\n",
+            );
+            output.push_str("# TYPE backfill_checkpoint_slot_lag gauge\n");
+            for checkpoint in &lag_report.backfill_checkpoints {
+                if let Some(slot_lag) = checkpoint.slot_lag {
+                    output.push_str(&format!(
+                        "backfill_checkpoint_slot_lag{{{},program_id=\"{}\",event_name=\"{}\"}} {}\n",
+                        base_labels, checkpoint.program_id, checkpoint.event_name, slot_lag
+                    ));
+                }
+            }
+        }
+
+        output.push_str(
+            "# HELP indexer_lag_degraded Whether lag exceeds the configured thresholds (1=degraded, 0=healthy)\n",
+        );
+        output.push_str("# TYPE indexer_lag_degraded gauge\n");
+        output.push_str(&format!(
+            "indexer_lag_degraded{{{}}} {}\n",
+            base_labels,
+            if lag_report.verdict == LagVerdict::Degraded {
+                1
+            } else {
+                0
+            }
+        ));
+
         // === 自定义指标 ===
         output.push_str("# HELP custom_metrics_count Number of custom metrics registered\n");
         output.push_str("# TYPE custom_metrics_count gauge\n");
@@ -858,10 +1287,24 @@ impl Clone for MetricsCollector {
             processing_durations: Arc::clone(&self.processing_durations),
             websocket_latencies: Arc::clone(&self.websocket_latencies),
             batch_write_durations: Arc::clone(&self.batch_write_durations),
+            gap_backfills: Arc::clone(&self.gap_backfills),
+            gap_signatures: Arc::clone(&self.gap_signatures),
+            gap_backfilled_events: Arc::clone(&self.gap_backfilled_events),
+            last_gap_slots: Arc::clone(&self.last_gap_slots),
+            gap_recovery_durations: Arc::clone(&self.gap_recovery_durations),
+            finalized_transactions: Arc::clone(&self.finalized_transactions),
+            rolled_back_transactions: Arc::clone(&self.rolled_back_transactions),
+            pending_finality_transactions: Arc::clone(&self.pending_finality_transactions),
+            truncated_log_fallbacks: Arc::clone(&self.truncated_log_fallbacks),
+            truncated_log_fallback_failures: Arc::clone(&self.truncated_log_fallback_failures),
+            truncated_log_recovered_events: Arc::clone(&self.truncated_log_recovered_events),
             start_time: self.start_time,
             last_metrics_report: Arc::clone(&self.last_metrics_report),
             system_monitor: Arc::new(RwLock::new(system)),
             custom_metrics: Arc::clone(&self.custom_metrics),
+            endpoint_pools: Arc::clone(&self.endpoint_pools),
+            event_sinks: Arc::clone(&self.event_sinks),
+            lag_tracker: Arc::clone(&self.lag_tracker),
         }
     }
 }
@@ -878,6 +1321,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![solana_sdk::pubkey::Pubkey::new_unique()],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -899,8 +1343,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         }
     }
 
@@ -987,6 +1436,71 @@ mod tests {
         assert_eq!(stats.avg_processing_duration_ms, 150.0);
     }
 
+    #[tokio::test]
+    async fn test_record_gap_backfill() {
+        let config = create_test_config();
+        let collector = MetricsCollector::new(&config).unwrap();
+
+        collector
+            .record_gap_backfill(12, 40, 10, Duration::from_millis(300))
+            .await
+            .unwrap();
+        collector
+            .record_gap_backfill(3, 8, 3, Duration::from_millis(100))
+            .await
+            .unwrap();
+
+        let stats = collector.get_stats().await.unwrap();
+        assert_eq!(stats.gap_backfills, 2);
+        assert_eq!(stats.gap_signatures, 15);
+        assert_eq!(stats.gap_backfilled_events, 13);
+        assert_eq!(stats.last_gap_slots, 8);
+        assert_eq!(stats.avg_gap_recovery_latency_ms, 200.0);
+
+        let prometheus_output = collector.export_prometheus_metrics().await.unwrap();
+        assert!(prometheus_output.contains("gap_backfills_total{"));
+        assert!(prometheus_output.contains("gap_recovery_latency_ms{"));
+    }
+
+    #[tokio::test]
+    async fn test_record_finality_check() {
+        let config = create_test_config();
+        let collector = MetricsCollector::new(&config).unwrap();
+
+        collector.record_finality_check(5, 1, 3);
+        collector.record_finality_check(2, 0, 1);
+
+        let stats = collector.get_stats().await.unwrap();
+        assert_eq!(stats.finalized_transactions, 7);
+        assert_eq!(stats.rolled_back_transactions, 1);
+        assert_eq!(stats.pending_finality_transactions, 1);
+
+        let prometheus_output = collector.export_prometheus_metrics().await.unwrap();
+        assert!(prometheus_output.contains("rolled_back_transactions_total{"));
+        assert!(prometheus_output.contains("pending_finality_transactions{"));
+
+        collector.reset_metrics().await.unwrap();
+        assert_eq!(collector.get_stats().await.unwrap().finalized_transactions, 0);
+    }
+
+    #[tokio::test]
+    async fn test_record_truncated_log_fallback() {
+        let config = create_test_config();
+        let collector = MetricsCollector::new(&config).unwrap();
+
+        collector.record_truncated_log_fallback(true, 3);
+        collector.record_truncated_log_fallback(false, 0);
+
+        let stats = collector.get_stats().await.unwrap();
+        assert_eq!(stats.truncated_log_fallbacks, 2);
+        assert_eq!(stats.truncated_log_fallback_failures, 1);
+        assert_eq!(stats.truncated_log_recovered_events, 3);
+
+        let prometheus_output = collector.export_prometheus_metrics().await.unwrap();
+        assert!(prometheus_output.contains("truncated_log_fallbacks_total{"));
+        assert!(prometheus_output.contains("truncated_log_recovered_events_total{"));
+    }
+
     #[tokio::test]
     async fn test_custom_metrics() {
         let config = create_test_config();
@@ -1143,6 +1657,67 @@ mod tests {
         println!("✅ 增强的Prometheus导出包含 {} 行指标", lines.len());
     }
 
+    #[tokio::test]
+    async fn test_export_endpoint_metrics() {
+        use crate::subscriber::endpoint_pool::EndpointRole;
+
+        let config = create_test_config();
+        let collector = MetricsCollector::new(&config).unwrap();
+
+        let pool = Arc::new(EndpointPool::new(&config, EndpointRole::WebSocket).unwrap());
+        pool.record_success(0);
+        pool.record_failure(0, "timeout");
+        collector.register_endpoint_pool(pool).await;
+
+        let prometheus_output = collector.export_prometheus_metrics().await.unwrap();
+
+        assert!(prometheus_output.contains("solana_endpoint_health_score{"));
+        assert!(prometheus_output.contains("role=\"websocket\",endpoint=\"primary\",outcome=\"success\"} 1"));
+        assert!(prometheus_output.contains("role=\"websocket\",endpoint=\"primary\",outcome=\"failure\"} 1"));
+        assert!(prometheus_output.contains("solana_endpoint_active{"));
+        assert!(prometheus_output.contains("solana_endpoint_failovers_total{"));
+        // 指标中不应暴露端点URL
+        assert!(!prometheus_output.contains(&config.solana.rpc_url));
+    }
+
+    #[tokio::test]
+    async fn test_export_event_sink_metrics() {
+        use crate::{config::settings::SinkRetryConfig, persistence::jsonl_sink::JsonlFileSink};
+
+        let config = create_test_config();
+        let collector = MetricsCollector::new(&config).unwrap();
+
+        let sink = Arc::new(JsonlFileSink::new(std::env::temp_dir(), "metrics-test"));
+        let worker = Arc::new(SinkWorker::new(sink, &SinkRetryConfig::default()));
+        collector.register_event_sinks(&[worker]).await;
+
+        let prometheus_output = collector.export_prometheus_metrics().await.unwrap();
+
+        assert!(prometheus_output.contains("sink=\"jsonl\",outcome=\"written\"} 0"));
+        assert!(prometheus_output.contains("sink=\"jsonl\",outcome=\"dropped\"} 0"));
+        assert!(prometheus_output.contains("event_sink_retries_total{"));
+        assert!(prometheus_output.contains("event_sink_queued_batches{"));
+    }
+
+    #[tokio::test]
+    async fn test_export_lag_metrics() {
+        let config = create_test_config();
+        let collector = MetricsCollector::new(&config).unwrap();
+
+        let lag_tracker = collector.lag_tracker();
+        lag_tracker.record_processed("program_a", "clmm_swap", 1_000);
+        lag_tracker.record_backfill_checkpoint("program_a", "swapevent", 900);
+        lag_tracker.record_chain_tip(1_020);
+
+        let prometheus_output = collector.export_prometheus_metrics().await.unwrap();
+
+        assert!(prometheus_output.contains("chain_tip_slot{"));
+        assert!(prometheus_output.contains("program_id=\"program_a\",event_type=\"clmm_swap\"} 1000"));
+        assert!(prometheus_output.contains("program_id=\"program_a\",event_type=\"clmm_swap\"} 20"));
+        assert!(prometheus_output.contains("program_id=\"program_a\",event_name=\"swapevent\"} 120"));
+        assert!(prometheus_output.contains("indexer_lag_degraded{"));
+    }
+
     #[tokio::test]
     async fn test_is_healthy() {
         let config = create_test_config();
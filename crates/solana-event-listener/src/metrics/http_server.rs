@@ -0,0 +1,280 @@
+use crate::{
+    error::{EventListenerError, Result},
+    metrics::{LagReport, LagVerdict, MetricsCollector, MetricsStats},
+    persistence::{batch_writer::BatchWriterStats, BatchWriter},
+    recovery::{BackfillEventConfig, BackfillManager, ScanStatistics},
+    subscriber::{subscription_manager::SubscriptionStats, SubscriptionManager},
+};
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{error, info};
+
+/// Prometheus文本格式的Content-Type
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// 指标/状态HTTP服务
+///
+/// 提供Prometheus抓取的`/metrics`，以及订阅、批量写入、回填状态的JSON接口：
+/// - `GET /metrics`
+/// - `GET /health`（healthy/degraded/unhealthy，降级表示处理进度落后链上）
+/// - `GET /status`（汇总）
+/// - `GET /status/subscription`
+/// - `GET /status/batch-writer`
+/// - `GET /status/backfill`
+#[derive(Clone)]
+pub struct MetricsHttpServer {
+    metrics: Arc<MetricsCollector>,
+    subscription_manager: Arc<SubscriptionManager>,
+    batch_writer: Arc<BatchWriter>,
+    backfill_manager: Option<Arc<BackfillManager>>,
+}
+
+/// 健康结论
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthVerdict {
+    /// 组件正常且未落后链上
+    Healthy,
+    /// 组件正常但处理进度落后超过阈值
+    Degraded,
+    /// 组件异常
+    Unhealthy,
+}
+
+/// 健康检查响应
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthResponse {
+    pub healthy: bool,
+    pub status: HealthVerdict,
+    pub subscription_manager: bool,
+    pub batch_writer: bool,
+    pub lag: LagReport,
+}
+
+/// 回填事件配置状态
+#[derive(Debug, Clone, Serialize)]
+pub struct BackfillEventStatus {
+    pub event_type: String,
+    pub program_id: String,
+    pub enabled: bool,
+    pub check_interval_secs: Option<u64>,
+}
+
+/// 回填状态
+#[derive(Debug, Clone, Serialize)]
+pub struct BackfillStatus {
+    pub enabled: bool,
+    pub events: Vec<BackfillEventStatus>,
+    pub scan_statistics: Option<ScanStatistics>,
+}
+
+/// 汇总状态
+#[derive(Debug, Clone, Serialize)]
+pub struct ListenerStatus {
+    pub health: HealthResponse,
+    pub subscription: SubscriptionStats,
+    pub batch_writer: BatchWriterStats,
+    pub backfill: BackfillStatus,
+    pub metrics: MetricsStats,
+}
+
+impl MetricsHttpServer {
+    /// 创建指标/状态HTTP服务
+    pub fn new(
+        metrics: Arc<MetricsCollector>,
+        subscription_manager: Arc<SubscriptionManager>,
+        batch_writer: Arc<BatchWriter>,
+        backfill_manager: Option<Arc<BackfillManager>>,
+    ) -> Self {
+        Self {
+            metrics,
+            subscription_manager,
+            batch_writer,
+            backfill_manager,
+        }
+    }
+
+    /// 构建路由
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/metrics", get(prometheus_metrics))
+            .route("/health", get(health))
+            .route("/status", get(status))
+            .route("/status/subscription", get(subscription_status))
+            .route("/status/batch-writer", get(batch_writer_status))
+            .route("/status/backfill", get(backfill_status))
+            .with_state(self.clone())
+    }
+
+    /// 监听指定地址并提供服务，直到任务被取消
+    pub async fn serve(&self, bind_addr: &str) -> Result<()> {
+        let listener = tokio::net::TcpListener::bind(bind_addr).await?;
+        info!("📡 指标/状态HTTP服务已启动: http://{}", bind_addr);
+
+        axum::serve(listener, self.router())
+            .await
+            .map_err(EventListenerError::IO)
+    }
+
+    async fn health(&self) -> HealthResponse {
+        let subscription_manager = self.subscription_manager.is_healthy().await;
+        let batch_writer = self.batch_writer.is_healthy().await;
+        let healthy = subscription_manager && batch_writer;
+        let lag = self.metrics.lag_tracker().report();
+        HealthResponse {
+            healthy,
+            status: health_verdict(healthy, lag.verdict),
+            subscription_manager,
+            batch_writer,
+            lag,
+        }
+    }
+
+    async fn backfill(&self) -> Result<BackfillStatus> {
+        let manager = match &self.backfill_manager {
+            Some(manager) => manager,
+            None => {
+                return Ok(BackfillStatus {
+                    enabled: false,
+                    events: Vec::new(),
+                    scan_statistics: None,
+                })
+            }
+        };
+
+        Ok(BackfillStatus {
+            enabled: true,
+            events: backfill_event_statuses(&manager.get_event_configs()),
+            scan_statistics: Some(manager.scan_record_persistence().get_scan_statistics().await?),
+        })
+    }
+}
+
+/// 综合组件健康状态和落后监控结论
+fn health_verdict(components_healthy: bool, lag_verdict: LagVerdict) -> HealthVerdict {
+    match (components_healthy, lag_verdict) {
+        (false, _) => HealthVerdict::Unhealthy,
+        (true, LagVerdict::Degraded) => HealthVerdict::Degraded,
+        (true, LagVerdict::Healthy) => HealthVerdict::Healthy,
+    }
+}
+
+/// 转换回填事件配置为状态响应
+fn backfill_event_statuses(configs: &[BackfillEventConfig]) -> Vec<BackfillEventStatus> {
+    configs
+        .iter()
+        .map(|config| BackfillEventStatus {
+            event_type: config.event_type.clone(),
+            program_id: config.program_id.to_string(),
+            enabled: config.enabled,
+            check_interval_secs: config.check_interval_secs,
+        })
+        .collect()
+}
+
+/// 将内部错误转换为500响应
+fn internal_error(e: EventListenerError) -> Response {
+    error!("❌ 指标/状态接口处理失败: {}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(serde_json::json!({ "error": e.to_string() })),
+    )
+        .into_response()
+}
+
+async fn prometheus_metrics(State(server): State<MetricsHttpServer>) -> Response {
+    match server.metrics.export_prometheus_metrics().await {
+        Ok(body) => ([(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], body).into_response(),
+        Err(e) => internal_error(e),
+    }
+}
+
+async fn health(State(server): State<MetricsHttpServer>) -> Response {
+    let health = server.health().await;
+    // 降级时仍返回200，避免处理落后时被编排系统反复重启
+    let status = if health.status == HealthVerdict::Unhealthy {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    (status, Json(health)).into_response()
+}
+
+async fn status(State(server): State<MetricsHttpServer>) -> Response {
+    let metrics = match server.metrics.get_stats().await {
+        Ok(metrics) => metrics,
+        Err(e) => return internal_error(e),
+    };
+    let backfill = match server.backfill().await {
+        Ok(backfill) => backfill,
+        Err(e) => return internal_error(e),
+    };
+
+    Json(ListenerStatus {
+        health: server.health().await,
+        subscription: server.subscription_manager.get_stats().await,
+        batch_writer: server.batch_writer.get_stats().await,
+        backfill,
+        metrics,
+    })
+    .into_response()
+}
+
+async fn subscription_status(State(server): State<MetricsHttpServer>) -> Json<SubscriptionStats> {
+    Json(server.subscription_manager.get_stats().await)
+}
+
+async fn batch_writer_status(State(server): State<MetricsHttpServer>) -> Json<BatchWriterStats> {
+    Json(server.batch_writer.get_stats().await)
+}
+
+async fn backfill_status(State(server): State<MetricsHttpServer>) -> Response {
+    match server.backfill().await {
+        Ok(backfill) => Json(backfill).into_response(),
+        Err(e) => internal_error(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_backfill_event_statuses() {
+        let program_id = Pubkey::new_unique();
+        let configs = vec![
+            BackfillEventConfig::new("SwapEvent", program_id).with_check_interval(60),
+            BackfillEventConfig::new("LaunchEvent", program_id).with_enabled(false),
+        ];
+
+        let statuses = backfill_event_statuses(&configs);
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0].event_type, "SwapEvent");
+        assert_eq!(statuses[0].program_id, program_id.to_string());
+        assert_eq!(statuses[0].check_interval_secs, Some(60));
+        assert!(!statuses[1].enabled);
+
+        let json = serde_json::to_value(&statuses[1]).unwrap();
+        assert_eq!(json["event_type"], "LaunchEvent");
+        assert_eq!(json["check_interval_secs"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_health_verdict() {
+        assert_eq!(health_verdict(true, LagVerdict::Healthy), HealthVerdict::Healthy);
+        assert_eq!(health_verdict(true, LagVerdict::Degraded), HealthVerdict::Degraded);
+        assert_eq!(health_verdict(false, LagVerdict::Healthy), HealthVerdict::Unhealthy);
+        assert_eq!(
+            serde_json::to_value(HealthVerdict::Degraded).unwrap(),
+            serde_json::json!("degraded")
+        );
+    }
+}
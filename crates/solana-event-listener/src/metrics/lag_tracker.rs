@@ -0,0 +1,337 @@
+use crate::config::settings::LagMonitorConfig;
+use chrono::Utc;
+use dashmap::DashMap;
+use serde::Serialize;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+
+/// 单个程序、单个事件类型的实时处理位置
+#[derive(Debug, Clone)]
+struct ProcessedPosition {
+    slot: u64,
+    processed_at: i64,
+    block_time: Option<i64>,
+}
+
+/// 回填检查点位置
+#[derive(Debug, Clone)]
+struct CheckpointPosition {
+    slot: u64,
+    updated_at: i64,
+}
+
+/// 落后监控结论
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LagVerdict {
+    Healthy,
+    Degraded,
+}
+
+/// 程序、事件类型维度的处理进度
+#[derive(Debug, Clone, Serialize)]
+pub struct ProgramLag {
+    pub program_id: String,
+    pub event_type: String,
+    pub latest_slot: u64,
+    /// 落后链上最新slot的数量，尚未查询到链上slot时为None
+    pub slot_lag: Option<u64>,
+    /// 出块到处理完成的延迟（秒），尚未查询到出块时间时为None
+    pub processing_delay_secs: Option<i64>,
+    pub processed_at: i64,
+}
+
+/// 回填检查点进度
+#[derive(Debug, Clone, Serialize)]
+pub struct BackfillCheckpointLag {
+    pub program_id: String,
+    pub event_name: String,
+    pub checkpoint_slot: u64,
+    pub slot_lag: Option<u64>,
+    pub updated_at: i64,
+}
+
+/// 落后监控报告
+#[derive(Debug, Clone, Serialize)]
+pub struct LagReport {
+    pub verdict: LagVerdict,
+    /// 降级原因，健康时为空
+    pub reasons: Vec<String>,
+    pub chain_tip_slot: Option<u64>,
+    pub chain_tip_updated_at: Option<i64>,
+    /// 所有程序中最新处理的slot
+    pub latest_processed_slot: Option<u64>,
+    pub slot_lag: Option<u64>,
+    pub programs: Vec<ProgramLag>,
+    pub backfill_checkpoints: Vec<BackfillCheckpointLag>,
+}
+
+/// 链上进度落后追踪器
+///
+/// 记录各程序、各事件类型最新处理的slot和出块时间，以及各回填检查点的slot，
+/// 与定期查询的链上最新slot比较后给出健康结论。
+/// 单个程序可能长时间没有交易，因此slot落后只按所有程序中最新的处理位置判断，
+/// 出块延迟只看最近处理的事件
+pub struct ChainLagTracker {
+    config: LagMonitorConfig,
+    chain_tip_slot: AtomicU64,
+    chain_tip_updated_at: AtomicI64,
+    positions: DashMap<(String, String), ProcessedPosition>,
+    checkpoints: DashMap<(String, String), CheckpointPosition>,
+}
+
+impl ChainLagTracker {
+    /// 创建落后追踪器
+    pub fn new(config: LagMonitorConfig) -> Self {
+        Self {
+            config,
+            chain_tip_slot: AtomicU64::new(0),
+            chain_tip_updated_at: AtomicI64::new(0),
+            positions: DashMap::new(),
+            checkpoints: DashMap::new(),
+        }
+    }
+
+    /// 获取监控配置
+    pub fn config(&self) -> &LagMonitorConfig {
+        &self.config
+    }
+
+    /// 记录实时路径处理完成的事件位置，只会向前推进
+    pub fn record_processed(&self, program_id: &str, event_type: &str, slot: u64) {
+        let now = Utc::now().timestamp();
+        let mut entry = self
+            .positions
+            .entry((program_id.to_string(), event_type.to_string()))
+            .or_insert(ProcessedPosition {
+                slot,
+                processed_at: now,
+                block_time: None,
+            });
+
+        if slot > entry.slot {
+            entry.slot = slot;
+            entry.block_time = None;
+        }
+        if slot == entry.slot {
+            entry.processed_at = now;
+        }
+    }
+
+    /// 记录查询到的链上最新slot
+    pub fn record_chain_tip(&self, slot: u64) {
+        self.chain_tip_slot.fetch_max(slot, Ordering::Relaxed);
+        self.chain_tip_updated_at
+            .store(Utc::now().timestamp(), Ordering::Relaxed);
+    }
+
+    /// 获取链上最新slot，尚未查询时返回None
+    pub fn chain_tip(&self) -> Option<u64> {
+        match self.chain_tip_slot.load(Ordering::Relaxed) {
+            0 => None,
+            slot => Some(slot),
+        }
+    }
+
+    /// 获取尚未查询出块时间的slot列表（从新到旧）
+    pub fn slots_missing_block_time(&self) -> Vec<u64> {
+        let mut slots: Vec<u64> = self
+            .positions
+            .iter()
+            .filter(|entry| entry.block_time.is_none())
+            .map(|entry| entry.slot)
+            .collect();
+        slots.sort_unstable_by(|a, b| b.cmp(a));
+        slots.dedup();
+        slots
+    }
+
+    /// 记录slot的出块时间
+    pub fn record_block_time(&self, slot: u64, block_time: i64) {
+        for mut entry in self.positions.iter_mut() {
+            if entry.slot == slot && entry.block_time.is_none() {
+                entry.block_time = Some(block_time);
+            }
+        }
+    }
+
+    /// 记录回填检查点位置，只会向前推进
+    pub fn record_backfill_checkpoint(&self, program_id: &str, event_name: &str, slot: u64) {
+        let now = Utc::now().timestamp();
+        let mut entry = self
+            .checkpoints
+            .entry((program_id.to_string(), event_name.to_string()))
+            .or_insert(CheckpointPosition { slot, updated_at: now });
+
+        if slot >= entry.slot {
+            entry.slot = slot;
+            entry.updated_at = now;
+        }
+    }
+
+    /// 生成落后监控报告
+    pub fn report(&self) -> LagReport {
+        let chain_tip = self.chain_tip();
+        let chain_tip_updated_at = chain_tip.map(|_| self.chain_tip_updated_at.load(Ordering::Relaxed));
+
+        let mut programs: Vec<ProgramLag> = self
+            .positions
+            .iter()
+            .map(|entry| {
+                let (program_id, event_type) = entry.key();
+                ProgramLag {
+                    program_id: program_id.clone(),
+                    event_type: event_type.clone(),
+                    latest_slot: entry.slot,
+                    slot_lag: chain_tip.map(|tip| tip.saturating_sub(entry.slot)),
+                    processing_delay_secs: entry
+                        .block_time
+                        .map(|block_time| (entry.processed_at - block_time).max(0)),
+                    processed_at: entry.processed_at,
+                }
+            })
+            .collect();
+        programs.sort_by(|a, b| (&a.program_id, &a.event_type).cmp(&(&b.program_id, &b.event_type)));
+
+        let mut backfill_checkpoints: Vec<BackfillCheckpointLag> = self
+            .checkpoints
+            .iter()
+            .map(|entry| {
+                let (program_id, event_name) = entry.key();
+                BackfillCheckpointLag {
+                    program_id: program_id.clone(),
+                    event_name: event_name.clone(),
+                    checkpoint_slot: entry.slot,
+                    slot_lag: chain_tip.map(|tip| tip.saturating_sub(entry.slot)),
+                    updated_at: entry.updated_at,
+                }
+            })
+            .collect();
+        backfill_checkpoints.sort_by(|a, b| (&a.program_id, &a.event_name).cmp(&(&b.program_id, &b.event_name)));
+
+        let latest = programs
+            .iter()
+            .max_by_key(|program| (program.latest_slot, program.processed_at));
+        let latest_processed_slot = latest.map(|program| program.latest_slot);
+        let slot_lag = chain_tip
+            .zip(latest_processed_slot)
+            .map(|(tip, slot)| tip.saturating_sub(slot));
+
+        let mut reasons = Vec::new();
+        if let Some(slot_lag) = slot_lag {
+            if self.config.max_slot_lag > 0 && slot_lag > self.config.max_slot_lag {
+                reasons.push(format!(
+                    "实时处理位置落后链上{}个slot（阈值{}）",
+                    slot_lag, self.config.max_slot_lag
+                ));
+            }
+        }
+        if let Some(latest) = latest {
+            if let Some(delay) = latest.processing_delay_secs {
+                if self.config.max_processing_delay_secs > 0 && delay as u64 > self.config.max_processing_delay_secs {
+                    reasons.push(format!(
+                        "{}/{} 出块到处理延迟{}秒（阈值{}秒）",
+                        latest.program_id, latest.event_type, delay, self.config.max_processing_delay_secs
+                    ));
+                }
+            }
+        }
+        if self.config.max_backfill_slot_lag > 0 {
+            for checkpoint in &backfill_checkpoints {
+                if let Some(lag) = checkpoint
+                    .slot_lag
+                    .filter(|lag| *lag > self.config.max_backfill_slot_lag)
+                {
+                    reasons.push(format!(
+                        "回填检查点{}/{}落后链上{}个slot（阈值{}）",
+                        checkpoint.program_id, checkpoint.event_name, lag, self.config.max_backfill_slot_lag
+                    ));
+                }
+            }
+        }
+
+        LagReport {
+            verdict: if reasons.is_empty() {
+                LagVerdict::Healthy
+            } else {
+                LagVerdict::Degraded
+            },
+            reasons,
+            chain_tip_slot: chain_tip,
+            chain_tip_updated_at,
+            latest_processed_slot,
+            slot_lag,
+            programs,
+            backfill_checkpoints,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_tracker() -> ChainLagTracker {
+        ChainLagTracker::new(LagMonitorConfig {
+            tip_poll_interval_secs: 10,
+            max_slot_lag: 100,
+            max_processing_delay_secs: 60,
+            max_backfill_slot_lag: 1000,
+        })
+    }
+
+    #[test]
+    fn test_processed_position_only_advances() {
+        let tracker = create_tracker();
+        tracker.record_processed("program_a", "clmm_swap", 500);
+        tracker.record_processed("program_a", "clmm_swap", 400);
+        tracker.record_processed("program_a", "pool_creation", 450);
+
+        let report = tracker.report();
+        assert_eq!(report.programs.len(), 2);
+        assert_eq!(report.programs[0].event_type, "clmm_swap");
+        assert_eq!(report.programs[0].latest_slot, 500);
+        assert_eq!(report.latest_processed_slot, Some(500));
+        // 尚未查询链上slot时不判断落后
+        assert_eq!(report.slot_lag, None);
+        assert_eq!(report.verdict, LagVerdict::Healthy);
+        assert_eq!(tracker.slots_missing_block_time(), vec![500, 450]);
+    }
+
+    #[test]
+    fn test_verdict_degrades_past_thresholds() {
+        let tracker = create_tracker();
+        tracker.record_processed("program_a", "clmm_swap", 1_000);
+        tracker.record_chain_tip(1_050);
+        assert_eq!(tracker.report().verdict, LagVerdict::Healthy);
+
+        tracker.record_chain_tip(1_200);
+        let report = tracker.report();
+        assert_eq!(report.slot_lag, Some(200));
+        assert_eq!(report.verdict, LagVerdict::Degraded);
+        assert_eq!(report.reasons.len(), 1);
+
+        // 处理位置追上后恢复健康，出块延迟超过阈值再次降级
+        tracker.record_processed("program_a", "clmm_swap", 1_190);
+        assert_eq!(tracker.report().verdict, LagVerdict::Healthy);
+        tracker.record_block_time(1_190, Utc::now().timestamp() - 300);
+        let report = tracker.report();
+        assert!(report.programs[0].processing_delay_secs.unwrap() >= 300);
+        assert_eq!(report.verdict, LagVerdict::Degraded);
+    }
+
+    #[test]
+    fn test_backfill_checkpoint_lag() {
+        let tracker = create_tracker();
+        tracker.record_backfill_checkpoint("program_a", "swapevent", 5_000);
+        tracker.record_backfill_checkpoint("program_a", "swapevent", 4_000);
+        tracker.record_chain_tip(5_500);
+
+        let report = tracker.report();
+        assert_eq!(report.backfill_checkpoints[0].checkpoint_slot, 5_000);
+        assert_eq!(report.backfill_checkpoints[0].slot_lag, Some(500));
+        assert_eq!(report.verdict, LagVerdict::Healthy);
+
+        tracker.record_chain_tip(7_000);
+        assert_eq!(tracker.report().verdict, LagVerdict::Degraded);
+    }
+}
@@ -1,3 +1,7 @@
 pub mod collector;
+pub mod http_server;
+pub mod lag_tracker;
 
 pub use collector::{MetricsCollector, MetricsStats};
+pub use http_server::MetricsHttpServer;
+pub use lag_tracker::{ChainLagTracker, LagReport, LagVerdict};
@@ -0,0 +1,345 @@
+use crate::{
+    config::EventListenerConfig,
+    error::{EventListenerError, Result},
+    parser::{EventParser, ParsedEvent},
+};
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use raydium_amm_v3::states::{AMM_CONFIG_SEED, FEE_RATE_DENOMINATOR_VALUE};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use tracing::{debug, info, warn};
+
+/// 配置变更事件的原始数据结构（与CLMM合约states/config.rs保持一致）
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct ConfigChangeEvent {
+    pub index: u16,
+    pub owner: Pubkey,
+    pub protocol_fee_rate: u32,
+    pub trade_fee_rate: u32,
+    pub tick_spacing: u16,
+    pub fund_fee_rate: u32,
+    pub fund_owner: Pubkey,
+}
+
+/// CLMM配置变更事件数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClmmConfigChangeEventData {
+    /// 配置地址（AmmConfig PDA）
+    pub config_id: String,
+    /// 配置索引
+    pub index: u16,
+    /// 配置管理员
+    pub owner: String,
+    /// 协议费率
+    pub protocol_fee_rate: u32,
+    /// 交易费率
+    pub trade_fee_rate: u32,
+    /// tick间距
+    pub tick_spacing: u16,
+    /// 基金费率
+    pub fund_fee_rate: u32,
+    /// 基金费接收者
+    pub fund_owner: String,
+    /// 交易签名
+    pub signature: String,
+    /// 区块高度
+    pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
+    /// 处理时间
+    pub processed_at: String,
+}
+
+/// CLMM配置变更事件解析器（create_amm_config / update_amm_config）
+pub struct ClmmConfigChangeParser {
+    /// 事件的discriminator
+    discriminator: [u8; 8],
+    /// 目标程序ID，指定此解析器处理哪个程序的事件
+    target_program_id: Pubkey,
+}
+
+impl ClmmConfigChangeParser {
+    /// 创建新的CLMM配置变更事件解析器
+    pub fn new(_config: &EventListenerConfig, program_id: Pubkey) -> Result<Self> {
+        let discriminator = crate::parser::event_parser::calculate_event_discriminator("ConfigChangeEvent");
+
+        Ok(Self {
+            discriminator,
+            target_program_id: program_id,
+        })
+    }
+
+    /// 计算配置索引对应的AmmConfig地址
+    fn derive_config_id(&self, index: u16) -> Pubkey {
+        Pubkey::find_program_address(
+            &[AMM_CONFIG_SEED.as_bytes(), &index.to_be_bytes()],
+            &self.target_program_id,
+        )
+        .0
+    }
+
+    /// 从程序数据解析配置变更事件
+    fn parse_program_data(&self, data_str: &str) -> Result<ConfigChangeEvent> {
+        // Base64解码
+        let data = general_purpose::STANDARD
+            .decode(data_str)
+            .map_err(|e| EventListenerError::EventParsing(format!("Base64解码失败: {}", e)))?;
+
+        if data.len() < 8 {
+            return Err(EventListenerError::EventParsing(
+                "数据长度不足，无法包含discriminator".to_string(),
+            ));
+        }
+
+        // 验证discriminator
+        let discriminator = &data[0..8];
+        if discriminator != self.discriminator {
+            return Err(EventListenerError::DiscriminatorMismatch);
+        }
+
+        // Borsh反序列化事件数据
+        let event_data = &data[8..];
+        let event = ConfigChangeEvent::try_from_slice(event_data)
+            .map_err(|e| EventListenerError::EventParsing(format!("Borsh反序列化失败: {}", e)))?;
+
+        debug!(
+            "✅ 成功解析CLMM配置变更事件: index={}, trade_fee_rate={}, tick_spacing={}",
+            event.index, event.trade_fee_rate, event.tick_spacing
+        );
+        Ok(event)
+    }
+
+    /// 将原始事件转换为ParsedEvent
+    fn convert_to_parsed_event(&self, event: ConfigChangeEvent, signature: String, slot: u64) -> ParsedEvent {
+        ParsedEvent::ClmmConfigChange(ClmmConfigChangeEventData {
+            config_id: self.derive_config_id(event.index).to_string(),
+            index: event.index,
+            owner: event.owner.to_string(),
+            protocol_fee_rate: event.protocol_fee_rate,
+            trade_fee_rate: event.trade_fee_rate,
+            tick_spacing: event.tick_spacing,
+            fund_fee_rate: event.fund_fee_rate,
+            fund_owner: event.fund_owner.to_string(),
+            signature,
+            slot,
+            instruction_index: 0,
+            event_ordinal: 0,
+            processed_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// 验证配置变更事件数据
+    fn validate_config_change(&self, event: &ClmmConfigChangeEventData) -> Result<bool> {
+        // 验证tick间距
+        if event.tick_spacing == 0 {
+            warn!("❌ 无效的tick间距: {}", event.tick_spacing);
+            return Ok(false);
+        }
+
+        // 验证交易费率（合约要求小于分母）
+        if event.trade_fee_rate >= FEE_RATE_DENOMINATOR_VALUE {
+            warn!("❌ 交易费率超出范围: {}", event.trade_fee_rate);
+            return Ok(false);
+        }
+
+        // 协议费率与基金费率之和不能超过分母
+        if event.protocol_fee_rate as u64 + event.fund_fee_rate as u64 > FEE_RATE_DENOMINATOR_VALUE as u64 {
+            warn!(
+                "❌ 协议费率与基金费率之和超出范围: protocol={}, fund={}",
+                event.protocol_fee_rate, event.fund_fee_rate
+            );
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl EventParser for ClmmConfigChangeParser {
+    fn get_program_id(&self) -> Pubkey {
+        self.target_program_id
+    }
+
+    fn get_discriminator(&self) -> [u8; 8] {
+        self.discriminator
+    }
+
+    fn get_event_type(&self) -> &'static str {
+        "clmm_config_change"
+    }
+
+    fn supports_program(&self, program_id: &Pubkey) -> Option<bool> {
+        Some(*program_id == self.target_program_id)
+    }
+
+    async fn parse_from_logs(&self, logs: &[String], signature: &str, slot: u64) -> Result<Option<ParsedEvent>> {
+        for (index, log) in logs.iter().enumerate() {
+            if let Some(data_part) = log.strip_prefix("Program data: ") {
+                match self.parse_program_data(data_part) {
+                    Ok(event) => {
+                        info!(
+                            "⚙️ 第{}行发现CLMM配置变更事件: index={} trade_fee_rate={} protocol_fee_rate={} fund_fee_rate={}",
+                            index + 1,
+                            event.index,
+                            event.trade_fee_rate,
+                            event.protocol_fee_rate,
+                            event.fund_fee_rate
+                        );
+                        let parsed_event = self.convert_to_parsed_event(event, signature.to_string(), slot);
+                        return Ok(Some(parsed_event));
+                    }
+                    Err(EventListenerError::DiscriminatorMismatch) => {
+                        // Discriminator不匹配是正常情况，继续尝试下一条日志
+                        continue;
+                    }
+                    Err(e) => {
+                        warn!("⚠️ 第{}行CLMM配置变更事件解析失败: {}", index + 1, e);
+                        continue;
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    async fn validate_event(&self, event: &ParsedEvent) -> Result<bool> {
+        match event {
+            ParsedEvent::ClmmConfigChange(config_event) => self.validate_config_change(config_event),
+            _ => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::pubkey;
+
+    fn create_test_config() -> EventListenerConfig {
+        EventListenerConfig {
+            solana: crate::config::settings::SolanaConfig {
+                rpc_url: "https://api.devnet.solana.com".to_string(),
+                ws_url: "wss://api.devnet.solana.com".to_string(),
+                commitment: "confirmed".to_string(),
+                program_ids: vec![pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX")],
+                private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
+            },
+            database: crate::config::settings::DatabaseConfig {
+                uri: "mongodb://localhost:27017".to_string(),
+                database_name: "test".to_string(),
+                max_connections: 10,
+                min_connections: 2,
+            },
+            listener: crate::config::settings::ListenerConfig {
+                batch_size: 100,
+                sync_interval_secs: 30,
+                max_retries: 3,
+                retry_delay_ms: 1000,
+                signature_cache_size: 10000,
+                checkpoint_save_interval_secs: 60,
+                backoff: crate::config::settings::BackoffConfig::default(),
+                batch_write: crate::config::settings::BatchWriteConfig::default(),
+            },
+            monitoring: crate::config::settings::MonitoringConfig {
+                metrics_interval_secs: 60,
+                enable_performance_monitoring: true,
+                health_check_interval_secs: 30,
+                http_bind_addr: None,
+            },
+            backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
+        }
+    }
+
+    fn create_test_config_change_event() -> ConfigChangeEvent {
+        ConfigChangeEvent {
+            index: 3,
+            owner: Pubkey::new_unique(),
+            protocol_fee_rate: 120_000,
+            trade_fee_rate: 2_500,
+            tick_spacing: 60,
+            fund_fee_rate: 40_000,
+            fund_owner: Pubkey::new_unique(),
+        }
+    }
+
+    fn encode_program_data(event: &ConfigChangeEvent) -> String {
+        let mut data = crate::parser::event_parser::calculate_event_discriminator("ConfigChangeEvent").to_vec();
+        data.extend(borsh::to_vec(event).unwrap());
+        format!("Program data: {}", general_purpose::STANDARD.encode(data))
+    }
+
+    #[test]
+    fn test_clmm_config_change_parser_creation() {
+        let config = create_test_config();
+        let program_id = pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX");
+        let parser = ClmmConfigChangeParser::new(&config, program_id).unwrap();
+
+        assert_eq!(parser.get_event_type(), "clmm_config_change");
+        assert_eq!(parser.get_program_id(), program_id);
+        assert_eq!(
+            parser.get_discriminator(),
+            crate::parser::event_parser::calculate_event_discriminator("ConfigChangeEvent")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_config_change_event_from_logs() {
+        let config = create_test_config();
+        let program_id = pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX");
+        let parser = ClmmConfigChangeParser::new(&config, program_id).unwrap();
+        let event = create_test_config_change_event();
+
+        let logs = vec![
+            "Program log: Instruction: UpdateAmmConfig".to_string(),
+            encode_program_data(&event),
+        ];
+
+        match parser.parse_from_logs(&logs, "test_sig", 300).await.unwrap() {
+            Some(ParsedEvent::ClmmConfigChange(data)) => {
+                let expected_config_id =
+                    Pubkey::find_program_address(&[AMM_CONFIG_SEED.as_bytes(), &3u16.to_be_bytes()], &program_id).0;
+                assert_eq!(data.config_id, expected_config_id.to_string());
+                assert_eq!(data.index, 3);
+                assert_eq!(data.protocol_fee_rate, 120_000);
+                assert_eq!(data.trade_fee_rate, 2_500);
+                assert_eq!(data.tick_spacing, 60);
+                assert_eq!(data.fund_fee_rate, 40_000);
+                assert_eq!(data.slot, 300);
+
+                let parsed = ParsedEvent::ClmmConfigChange(data);
+                assert!(parser.validate_event(&parsed).await.unwrap());
+            }
+            other => panic!("期望ClmmConfigChange事件，实际: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_rejects_invalid_fee_rates() {
+        let config = create_test_config();
+        let parser =
+            ClmmConfigChangeParser::new(&config, pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX")).unwrap();
+
+        let mut event = create_test_config_change_event();
+        event.protocol_fee_rate = 900_000;
+        event.fund_fee_rate = 200_000;
+        let parsed = parser.convert_to_parsed_event(event, "test_sig".to_string(), 300);
+        assert!(!parser.validate_event(&parsed).await.unwrap());
+
+        let mut event = create_test_config_change_event();
+        event.tick_spacing = 0;
+        let parsed = parser.convert_to_parsed_event(event, "test_sig".to_string(), 300);
+        assert!(!parser.validate_event(&parsed).await.unwrap());
+    }
+}
@@ -0,0 +1,764 @@
+use crate::{
+    config::EventListenerConfig,
+    error::{EventListenerError, Result},
+    parser::{EventParser, ParsedEvent},
+};
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use raydium_amm_v3::states::{POSITION_SEED, REWARD_NUM};
+use serde::{Deserialize, Serialize};
+use solana_client::{rpc_client::RpcClient, rpc_config::RpcTransactionConfig};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::UiTransactionEncoding;
+use std::str::FromStr;
+use tracing::{debug, info, warn};
+
+/// 开仓事件的原始数据结构（与CLMM合约states/personal_position.rs保持一致）
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct CreatePersonalPositionEvent {
+    /// 池子状态地址
+    pub pool_state: Pubkey,
+    /// 开仓者
+    pub minter: Pubkey,
+    /// 仓位NFT持有者
+    pub nft_owner: Pubkey,
+    /// 下限tick
+    pub tick_lower_index: i32,
+    /// 上限tick
+    pub tick_upper_index: i32,
+    /// 铸造的流动性
+    pub liquidity: u128,
+    /// 存入的token_0数量
+    pub deposit_amount_0: u64,
+    /// 存入的token_1数量
+    pub deposit_amount_1: u64,
+    /// token_0转账费
+    pub deposit_amount_0_transfer_fee: u64,
+    /// token_1转账费
+    pub deposit_amount_1_transfer_fee: u64,
+}
+
+/// 增加流动性事件的原始数据结构
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct IncreaseLiquidityEvent {
+    /// 仓位NFT mint
+    pub position_nft_mint: Pubkey,
+    /// 增加的流动性
+    pub liquidity: u128,
+    /// 支付的token_0数量
+    pub amount_0: u64,
+    /// 支付的token_1数量
+    pub amount_1: u64,
+    /// token_0转账费
+    pub amount_0_transfer_fee: u64,
+    /// token_1转账费
+    pub amount_1_transfer_fee: u64,
+}
+
+/// 减少流动性事件的原始数据结构
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct DecreaseLiquidityEvent {
+    /// 仓位NFT mint
+    pub position_nft_mint: Pubkey,
+    /// 减少的流动性
+    pub liquidity: u128,
+    /// 取出的token_0数量
+    pub decrease_amount_0: u64,
+    /// 取出的token_1数量
+    pub decrease_amount_1: u64,
+    /// 领取的token_0手续费
+    pub fee_amount_0: u64,
+    /// 领取的token_1手续费
+    pub fee_amount_1: u64,
+    /// 领取的奖励数量
+    pub reward_amounts: [u64; REWARD_NUM],
+    /// token_0转账费
+    pub transfer_fee_0: u64,
+    /// token_1转账费
+    pub transfer_fee_1: u64,
+}
+
+/// 领取仓位手续费事件的原始数据结构
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct CollectPersonalFeeEvent {
+    /// 仓位NFT mint
+    pub position_nft_mint: Pubkey,
+    /// 接收token_0的账户
+    pub recipient_token_account_0: Pubkey,
+    /// 接收token_1的账户
+    pub recipient_token_account_1: Pubkey,
+    /// 领取的token_0数量
+    pub amount_0: u64,
+    /// 领取的token_1数量
+    pub amount_1: u64,
+}
+
+/// 开仓事件数据（用于事件系统传递）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClmmCreatePositionEventData {
+    /// 池子地址
+    pub pool_id: String,
+    /// 开仓者
+    pub minter: String,
+    /// 仓位NFT持有者
+    pub nft_owner: String,
+    /// 仓位NFT mint（事件本身不携带，从交易账户中解析，解析失败为None）
+    pub position_nft_mint: Option<String>,
+    /// 仓位地址（personal position PDA）
+    pub position_key: Option<String>,
+    /// 下限tick
+    pub tick_lower_index: i32,
+    /// 上限tick
+    pub tick_upper_index: i32,
+    /// 铸造的流动性
+    pub liquidity: u128,
+    /// 存入的token_0数量
+    pub deposit_amount_0: u64,
+    /// 存入的token_1数量
+    pub deposit_amount_1: u64,
+    /// token_0转账费
+    pub deposit_amount_0_transfer_fee: u64,
+    /// token_1转账费
+    pub deposit_amount_1_transfer_fee: u64,
+    /// 交易签名
+    pub signature: String,
+    /// 区块高度
+    pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
+    /// 出块时间（实时订阅的日志不携带，写入前按slot补齐）
+    #[serde(default)]
+    pub block_time: Option<i64>,
+    /// 处理时间
+    pub processed_at: String,
+}
+
+/// 增加流动性事件数据（用于事件系统传递）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClmmIncreaseLiquidityEventData {
+    /// 仓位NFT mint
+    pub position_nft_mint: String,
+    /// 仓位地址（personal position PDA）
+    pub position_key: String,
+    /// 增加的流动性
+    pub liquidity: u128,
+    /// 支付的token_0数量
+    pub amount_0: u64,
+    /// 支付的token_1数量
+    pub amount_1: u64,
+    /// token_0转账费
+    pub amount_0_transfer_fee: u64,
+    /// token_1转账费
+    pub amount_1_transfer_fee: u64,
+    /// 交易签名
+    pub signature: String,
+    /// 区块高度
+    pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
+    /// 出块时间（实时订阅的日志不携带，写入前按slot补齐）
+    #[serde(default)]
+    pub block_time: Option<i64>,
+    /// 处理时间
+    pub processed_at: String,
+}
+
+/// 减少流动性事件数据（用于事件系统传递）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClmmDecreaseLiquidityEventData {
+    /// 仓位NFT mint
+    pub position_nft_mint: String,
+    /// 仓位地址（personal position PDA）
+    pub position_key: String,
+    /// 减少的流动性
+    pub liquidity: u128,
+    /// 取出的token_0数量
+    pub decrease_amount_0: u64,
+    /// 取出的token_1数量
+    pub decrease_amount_1: u64,
+    /// 领取的token_0手续费
+    pub fee_amount_0: u64,
+    /// 领取的token_1手续费
+    pub fee_amount_1: u64,
+    /// 领取的奖励数量
+    pub reward_amounts: Vec<u64>,
+    /// token_0转账费
+    pub transfer_fee_0: u64,
+    /// token_1转账费
+    pub transfer_fee_1: u64,
+    /// 交易签名
+    pub signature: String,
+    /// 区块高度
+    pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
+    /// 出块时间（实时订阅的日志不携带，写入前按slot补齐）
+    #[serde(default)]
+    pub block_time: Option<i64>,
+    /// 处理时间
+    pub processed_at: String,
+}
+
+/// 领取仓位手续费事件数据（用于事件系统传递）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClmmCollectPersonalFeeEventData {
+    /// 仓位NFT mint
+    pub position_nft_mint: String,
+    /// 仓位地址（personal position PDA）
+    pub position_key: String,
+    /// 接收token_0的账户
+    pub recipient_token_account_0: String,
+    /// 接收token_1的账户
+    pub recipient_token_account_1: String,
+    /// 领取的token_0数量
+    pub amount_0: u64,
+    /// 领取的token_1数量
+    pub amount_1: u64,
+    /// 交易签名
+    pub signature: String,
+    /// 区块高度
+    pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
+    /// 出块时间（实时订阅的日志不携带，写入前按slot补齐）
+    #[serde(default)]
+    pub block_time: Option<i64>,
+    /// 处理时间
+    pub processed_at: String,
+}
+
+/// CLMM仓位事件种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClmmPositionEventKind {
+    /// CreatePersonalPositionEvent
+    CreatePosition,
+    /// IncreaseLiquidityEvent
+    IncreaseLiquidity,
+    /// DecreaseLiquidityEvent
+    DecreaseLiquidity,
+    /// CollectPersonalFeeEvent
+    CollectPersonalFee,
+}
+
+impl ClmmPositionEventKind {
+    /// 链上Anchor事件名称（用于计算discriminator）
+    pub fn anchor_event_name(&self) -> &'static str {
+        match self {
+            ClmmPositionEventKind::CreatePosition => "CreatePersonalPositionEvent",
+            ClmmPositionEventKind::IncreaseLiquidity => "IncreaseLiquidityEvent",
+            ClmmPositionEventKind::DecreaseLiquidity => "DecreaseLiquidityEvent",
+            ClmmPositionEventKind::CollectPersonalFee => "CollectPersonalFeeEvent",
+        }
+    }
+
+    /// 解析器事件类型名称
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            ClmmPositionEventKind::CreatePosition => "clmm_create_position",
+            ClmmPositionEventKind::IncreaseLiquidity => "clmm_increase_liquidity",
+            ClmmPositionEventKind::DecreaseLiquidity => "clmm_decrease_liquidity",
+            ClmmPositionEventKind::CollectPersonalFee => "clmm_collect_personal_fee",
+        }
+    }
+}
+
+/// 根据仓位NFT mint推导personal position地址
+pub fn derive_position_key(position_nft_mint: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[POSITION_SEED.as_bytes(), position_nft_mint.as_ref()], program_id).0
+}
+
+/// 从交易账户列表中找出仓位NFT mint及对应的personal position地址
+///
+/// 开仓交易同时包含NFT mint和由其推导的仓位PDA，两者同时出现即可确定
+pub fn find_position_in_account_keys(account_keys: &[Pubkey], program_id: &Pubkey) -> Option<(Pubkey, Pubkey)> {
+    account_keys.iter().find_map(|candidate| {
+        let position_key = derive_position_key(candidate, program_id);
+        account_keys
+            .contains(&position_key)
+            .then_some((*candidate, position_key))
+    })
+}
+
+/// CLMM仓位生命周期事件解析器
+///
+/// 同一结构按事件种类实例化，每个实例负责一个discriminator
+pub struct ClmmPositionParser {
+    /// 事件种类
+    kind: ClmmPositionEventKind,
+    /// 事件的discriminator
+    discriminator: [u8; 8],
+    /// 目标程序ID，指定此解析器处理哪个程序的事件
+    target_program_id: Pubkey,
+    /// RPC客户端，用于解析开仓交易中的仓位NFT mint
+    rpc_client: RpcClient,
+}
+
+impl ClmmPositionParser {
+    /// 创建新的CLMM仓位事件解析器
+    pub fn new(config: &EventListenerConfig, program_id: Pubkey, kind: ClmmPositionEventKind) -> Result<Self> {
+        let discriminator = crate::parser::event_parser::calculate_event_discriminator(kind.anchor_event_name());
+        let rpc_client = RpcClient::new(config.solana.rpc_url.clone());
+
+        Ok(Self {
+            kind,
+            discriminator,
+            target_program_id: program_id,
+            rpc_client,
+        })
+    }
+
+    /// 获取事件种类
+    pub fn kind(&self) -> ClmmPositionEventKind {
+        self.kind
+    }
+
+    /// 从程序数据解码事件
+    fn decode_program_data<T: BorshDeserialize>(&self, data_str: &str) -> Result<T> {
+        // Base64解码
+        let data = general_purpose::STANDARD
+            .decode(data_str)
+            .map_err(|e| EventListenerError::EventParsing(format!("Base64解码失败: {}", e)))?;
+
+        if data.len() < 8 {
+            return Err(EventListenerError::EventParsing(
+                "数据长度不足，无法包含discriminator".to_string(),
+            ));
+        }
+
+        // 验证discriminator
+        if data[0..8] != self.discriminator {
+            return Err(EventListenerError::DiscriminatorMismatch);
+        }
+
+        // Borsh反序列化事件数据
+        T::try_from_slice(&data[8..]).map_err(|e| EventListenerError::EventParsing(format!("Borsh反序列化失败: {}", e)))
+    }
+
+    /// 通过交易详情解析开仓的仓位NFT mint和仓位地址
+    fn resolve_created_position(&self, signature: &str) -> Option<(Pubkey, Pubkey)> {
+        let signature_obj = Signature::from_str(signature).ok()?;
+        let tx_config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+
+        let transaction = match self.rpc_client.get_transaction_with_config(&signature_obj, tx_config) {
+            Ok(transaction) => transaction,
+            Err(e) => {
+                warn!("⚠️ 获取开仓交易详情失败: {} - {}", signature, e);
+                return None;
+            }
+        };
+
+        let versioned_tx = transaction.transaction.transaction.decode()?;
+        find_position_in_account_keys(versioned_tx.message.static_account_keys(), &self.target_program_id)
+    }
+
+    /// 解析单条Program data为对应的ParsedEvent（验证失败返回None）
+    fn parse_program_data(&self, data_str: &str, signature: &str, slot: u64) -> Result<Option<ParsedEvent>> {
+        let processed_at = chrono::Utc::now().to_rfc3339();
+
+        match self.kind {
+            ClmmPositionEventKind::CreatePosition => {
+                let event: CreatePersonalPositionEvent = self.decode_program_data(data_str)?;
+                if event.pool_state == Pubkey::default() || event.tick_lower_index >= event.tick_upper_index {
+                    warn!("❌ 无效的开仓事件: pool={}", event.pool_state);
+                    return Ok(None);
+                }
+
+                let resolved = self.resolve_created_position(signature);
+                if resolved.is_none() {
+                    warn!("⚠️ 无法从交易中解析仓位NFT mint: {}", signature);
+                }
+
+                Ok(Some(ParsedEvent::ClmmCreatePosition(ClmmCreatePositionEventData {
+                    pool_id: event.pool_state.to_string(),
+                    minter: event.minter.to_string(),
+                    nft_owner: event.nft_owner.to_string(),
+                    position_nft_mint: resolved.map(|(nft_mint, _)| nft_mint.to_string()),
+                    position_key: resolved.map(|(_, position_key)| position_key.to_string()),
+                    tick_lower_index: event.tick_lower_index,
+                    tick_upper_index: event.tick_upper_index,
+                    liquidity: event.liquidity,
+                    deposit_amount_0: event.deposit_amount_0,
+                    deposit_amount_1: event.deposit_amount_1,
+                    deposit_amount_0_transfer_fee: event.deposit_amount_0_transfer_fee,
+                    deposit_amount_1_transfer_fee: event.deposit_amount_1_transfer_fee,
+                    signature: signature.to_string(),
+                    slot,
+                    instruction_index: 0,
+                    event_ordinal: 0,
+                    block_time: None,
+                    processed_at,
+                })))
+            }
+            ClmmPositionEventKind::IncreaseLiquidity => {
+                let event: IncreaseLiquidityEvent = self.decode_program_data(data_str)?;
+                if event.position_nft_mint == Pubkey::default() {
+                    warn!("❌ 无效的仓位NFT mint");
+                    return Ok(None);
+                }
+
+                Ok(Some(ParsedEvent::ClmmIncreaseLiquidity(
+                    ClmmIncreaseLiquidityEventData {
+                        position_nft_mint: event.position_nft_mint.to_string(),
+                        position_key: derive_position_key(&event.position_nft_mint, &self.target_program_id)
+                            .to_string(),
+                        liquidity: event.liquidity,
+                        amount_0: event.amount_0,
+                        amount_1: event.amount_1,
+                        amount_0_transfer_fee: event.amount_0_transfer_fee,
+                        amount_1_transfer_fee: event.amount_1_transfer_fee,
+                        signature: signature.to_string(),
+                        slot,
+                        instruction_index: 0,
+                        event_ordinal: 0,
+                        block_time: None,
+                        processed_at,
+                    },
+                )))
+            }
+            ClmmPositionEventKind::DecreaseLiquidity => {
+                let event: DecreaseLiquidityEvent = self.decode_program_data(data_str)?;
+                if event.position_nft_mint == Pubkey::default() {
+                    warn!("❌ 无效的仓位NFT mint");
+                    return Ok(None);
+                }
+
+                Ok(Some(ParsedEvent::ClmmDecreaseLiquidity(
+                    ClmmDecreaseLiquidityEventData {
+                        position_nft_mint: event.position_nft_mint.to_string(),
+                        position_key: derive_position_key(&event.position_nft_mint, &self.target_program_id)
+                            .to_string(),
+                        liquidity: event.liquidity,
+                        decrease_amount_0: event.decrease_amount_0,
+                        decrease_amount_1: event.decrease_amount_1,
+                        fee_amount_0: event.fee_amount_0,
+                        fee_amount_1: event.fee_amount_1,
+                        reward_amounts: event.reward_amounts.to_vec(),
+                        transfer_fee_0: event.transfer_fee_0,
+                        transfer_fee_1: event.transfer_fee_1,
+                        signature: signature.to_string(),
+                        slot,
+                        instruction_index: 0,
+                        event_ordinal: 0,
+                        block_time: None,
+                        processed_at,
+                    },
+                )))
+            }
+            ClmmPositionEventKind::CollectPersonalFee => {
+                let event: CollectPersonalFeeEvent = self.decode_program_data(data_str)?;
+                if event.position_nft_mint == Pubkey::default() {
+                    warn!("❌ 无效的仓位NFT mint");
+                    return Ok(None);
+                }
+
+                Ok(Some(ParsedEvent::ClmmCollectPersonalFee(
+                    ClmmCollectPersonalFeeEventData {
+                        position_nft_mint: event.position_nft_mint.to_string(),
+                        position_key: derive_position_key(&event.position_nft_mint, &self.target_program_id)
+                            .to_string(),
+                        recipient_token_account_0: event.recipient_token_account_0.to_string(),
+                        recipient_token_account_1: event.recipient_token_account_1.to_string(),
+                        amount_0: event.amount_0,
+                        amount_1: event.amount_1,
+                        signature: signature.to_string(),
+                        slot,
+                        instruction_index: 0,
+                        event_ordinal: 0,
+                        block_time: None,
+                        processed_at,
+                    },
+                )))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EventParser for ClmmPositionParser {
+    fn get_program_id(&self) -> Pubkey {
+        self.target_program_id
+    }
+
+    fn get_discriminator(&self) -> [u8; 8] {
+        self.discriminator
+    }
+
+    fn get_event_type(&self) -> &'static str {
+        self.kind.event_type()
+    }
+
+    fn supports_program(&self, program_id: &Pubkey) -> Option<bool> {
+        Some(*program_id == self.target_program_id)
+    }
+
+    async fn parse_from_logs(&self, logs: &[String], signature: &str, slot: u64) -> Result<Option<ParsedEvent>> {
+        for (index, log) in logs.iter().enumerate() {
+            if let Some(data_part) = log.strip_prefix("Program data: ") {
+                match self.parse_program_data(data_part, signature, slot) {
+                    Ok(Some(parsed_event)) => {
+                        info!(
+                            "📍 第{}行发现CLMM仓位事件: type={}, signature={}",
+                            index + 1,
+                            self.kind.event_type(),
+                            signature
+                        );
+                        return Ok(Some(parsed_event));
+                    }
+                    Ok(None) => continue,
+                    Err(EventListenerError::DiscriminatorMismatch) => {
+                        // Discriminator不匹配是正常情况，继续尝试下一条日志
+                        continue;
+                    }
+                    Err(e) => {
+                        debug!("⚠️ 第{}行CLMM仓位事件解析失败: {}", index + 1, e);
+                        continue;
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    async fn validate_event(&self, event: &ParsedEvent) -> Result<bool> {
+        let matches = matches!(
+            (self.kind, event),
+            (
+                ClmmPositionEventKind::CreatePosition,
+                ParsedEvent::ClmmCreatePosition(_)
+            ) | (
+                ClmmPositionEventKind::IncreaseLiquidity,
+                ParsedEvent::ClmmIncreaseLiquidity(_)
+            ) | (
+                ClmmPositionEventKind::DecreaseLiquidity,
+                ParsedEvent::ClmmDecreaseLiquidity(_)
+            ) | (
+                ClmmPositionEventKind::CollectPersonalFee,
+                ParsedEvent::ClmmCollectPersonalFee(_)
+            )
+        );
+        Ok(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_config() -> EventListenerConfig {
+        EventListenerConfig {
+            solana: crate::config::settings::SolanaConfig {
+                rpc_url: "http://127.0.0.1:1".to_string(),
+                ws_url: "ws://127.0.0.1:1".to_string(),
+                commitment: "confirmed".to_string(),
+                program_ids: vec![Pubkey::from_str("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX").unwrap()],
+                private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
+            },
+            database: crate::config::settings::DatabaseConfig {
+                uri: "mongodb://localhost:27017".to_string(),
+                database_name: "test".to_string(),
+                max_connections: 10,
+                min_connections: 2,
+            },
+            listener: crate::config::settings::ListenerConfig {
+                batch_size: 100,
+                sync_interval_secs: 30,
+                max_retries: 3,
+                retry_delay_ms: 1000,
+                signature_cache_size: 10000,
+                checkpoint_save_interval_secs: 60,
+                backoff: crate::config::settings::BackoffConfig::default(),
+                batch_write: crate::config::settings::BatchWriteConfig::default(),
+            },
+            monitoring: crate::config::settings::MonitoringConfig {
+                metrics_interval_secs: 60,
+                enable_performance_monitoring: true,
+                health_check_interval_secs: 30,
+                http_bind_addr: None,
+            },
+            backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
+        }
+    }
+
+    fn clmm_program_id() -> Pubkey {
+        Pubkey::from_str("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX").unwrap()
+    }
+
+    fn encode_program_data<T: BorshSerialize>(event_name: &str, event: &T) -> String {
+        let mut data = crate::parser::event_parser::calculate_event_discriminator(event_name).to_vec();
+        data.extend(borsh::to_vec(event).unwrap());
+        format!("Program data: {}", general_purpose::STANDARD.encode(data))
+    }
+
+    #[test]
+    fn test_parser_discriminators_and_types() {
+        let config = create_test_config();
+        let kinds = [
+            ClmmPositionEventKind::CreatePosition,
+            ClmmPositionEventKind::IncreaseLiquidity,
+            ClmmPositionEventKind::DecreaseLiquidity,
+            ClmmPositionEventKind::CollectPersonalFee,
+        ];
+
+        for kind in kinds {
+            let parser = ClmmPositionParser::new(&config, clmm_program_id(), kind).unwrap();
+            assert_eq!(parser.get_event_type(), kind.event_type());
+            assert_eq!(
+                parser.get_discriminator(),
+                crate::parser::event_parser::calculate_event_discriminator(kind.anchor_event_name())
+            );
+            assert_eq!(parser.supports_program(&clmm_program_id()), Some(true));
+            assert_eq!(parser.supports_program(&Pubkey::new_unique()), Some(false));
+        }
+    }
+
+    #[test]
+    fn test_find_position_in_account_keys() {
+        let program_id = clmm_program_id();
+        let nft_mint = Pubkey::new_unique();
+        let position_key = derive_position_key(&nft_mint, &program_id);
+        let account_keys = vec![Pubkey::new_unique(), position_key, Pubkey::new_unique(), nft_mint];
+
+        assert_eq!(
+            find_position_in_account_keys(&account_keys, &program_id),
+            Some((nft_mint, position_key))
+        );
+        assert_eq!(
+            find_position_in_account_keys(&[Pubkey::new_unique(), nft_mint], &program_id),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_increase_liquidity_event() {
+        let config = create_test_config();
+        let parser =
+            ClmmPositionParser::new(&config, clmm_program_id(), ClmmPositionEventKind::IncreaseLiquidity).unwrap();
+        let event = IncreaseLiquidityEvent {
+            position_nft_mint: Pubkey::new_unique(),
+            liquidity: 1_000_000,
+            amount_0: 500,
+            amount_1: 600,
+            amount_0_transfer_fee: 0,
+            amount_1_transfer_fee: 0,
+        };
+
+        let logs = vec![encode_program_data("IncreaseLiquidityEvent", &event)];
+        match parser.parse_from_logs(&logs, "test_sig", 100).await.unwrap() {
+            Some(ParsedEvent::ClmmIncreaseLiquidity(data)) => {
+                assert_eq!(data.position_nft_mint, event.position_nft_mint.to_string());
+                assert_eq!(
+                    data.position_key,
+                    derive_position_key(&event.position_nft_mint, &clmm_program_id()).to_string()
+                );
+                assert_eq!(data.liquidity, 1_000_000);
+                assert_eq!(data.amount_0, 500);
+                assert_eq!(data.amount_1, 600);
+            }
+            other => panic!("期望ClmmIncreaseLiquidity事件，实际: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parse_decrease_liquidity_event() {
+        let config = create_test_config();
+        let parser =
+            ClmmPositionParser::new(&config, clmm_program_id(), ClmmPositionEventKind::DecreaseLiquidity).unwrap();
+        let event = DecreaseLiquidityEvent {
+            position_nft_mint: Pubkey::new_unique(),
+            liquidity: 2_000,
+            decrease_amount_0: 10,
+            decrease_amount_1: 20,
+            fee_amount_0: 1,
+            fee_amount_1: 2,
+            reward_amounts: [3, 0, 0],
+            transfer_fee_0: 0,
+            transfer_fee_1: 0,
+        };
+
+        let logs = vec![encode_program_data("DecreaseLiquidityEvent", &event)];
+        match parser.parse_from_logs(&logs, "test_sig", 100).await.unwrap() {
+            Some(ParsedEvent::ClmmDecreaseLiquidity(data)) => {
+                assert_eq!(data.liquidity, 2_000);
+                assert_eq!(data.fee_amount_0, 1);
+                assert_eq!(data.fee_amount_1, 2);
+                assert_eq!(data.reward_amounts, vec![3, 0, 0]);
+            }
+            other => panic!("期望ClmmDecreaseLiquidity事件，实际: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parse_create_position_without_resolvable_transaction() {
+        let config = create_test_config();
+        let parser =
+            ClmmPositionParser::new(&config, clmm_program_id(), ClmmPositionEventKind::CreatePosition).unwrap();
+        let event = CreatePersonalPositionEvent {
+            pool_state: Pubkey::new_unique(),
+            minter: Pubkey::new_unique(),
+            nft_owner: Pubkey::new_unique(),
+            tick_lower_index: -100,
+            tick_upper_index: 100,
+            liquidity: 42,
+            deposit_amount_0: 1,
+            deposit_amount_1: 2,
+            deposit_amount_0_transfer_fee: 0,
+            deposit_amount_1_transfer_fee: 0,
+        };
+
+        // 无效签名无法查询交易，仓位NFT mint为None但事件仍被解析
+        let logs = vec![encode_program_data("CreatePersonalPositionEvent", &event)];
+        match parser.parse_from_logs(&logs, "invalid_sig", 100).await.unwrap() {
+            Some(ParsedEvent::ClmmCreatePosition(data)) => {
+                assert_eq!(data.pool_id, event.pool_state.to_string());
+                assert!(data.position_nft_mint.is_none());
+                assert!(data.position_key.is_none());
+                assert_eq!(data.liquidity, 42);
+            }
+            other => panic!("期望ClmmCreatePosition事件，实际: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parse_skips_other_discriminators() {
+        let config = create_test_config();
+        let parser =
+            ClmmPositionParser::new(&config, clmm_program_id(), ClmmPositionEventKind::CollectPersonalFee).unwrap();
+        let event = IncreaseLiquidityEvent {
+            position_nft_mint: Pubkey::new_unique(),
+            liquidity: 1,
+            amount_0: 1,
+            amount_1: 1,
+            amount_0_transfer_fee: 0,
+            amount_1_transfer_fee: 0,
+        };
+
+        let logs = vec![encode_program_data("IncreaseLiquidityEvent", &event)];
+        assert!(parser.parse_from_logs(&logs, "test_sig", 100).await.unwrap().is_none());
+    }
+}
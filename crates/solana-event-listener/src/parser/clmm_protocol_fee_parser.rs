@@ -0,0 +1,335 @@
+use crate::{
+    config::EventListenerConfig,
+    error::{EventListenerError, Result},
+    parser::{EventParser, ParsedEvent},
+};
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use tracing::{debug, info, warn};
+
+/// 领取基金费指令的日志前缀（collect_fund_fee与collect_protocol_fee发出同一个事件）
+const COLLECT_FUND_FEE_INSTRUCTION_LOG: &str = "Program log: Instruction: CollectFundFee";
+/// 领取协议费指令的日志前缀
+const COLLECT_PROTOCOL_FEE_INSTRUCTION_LOG: &str = "Program log: Instruction: CollectProtocolFee";
+
+/// 协议费领取事件的原始数据结构（与CLMM合约states/pool.rs保持一致）
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct CollectProtocolFeeEvent {
+    pub pool_state: Pubkey,
+    pub recipient_token_account_0: Pubkey,
+    pub recipient_token_account_1: Pubkey,
+    pub amount_0: u64,
+    pub amount_1: u64,
+}
+
+/// CLMM协议费领取事件数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClmmCollectProtocolFeeEventData {
+    /// 池子地址
+    pub pool_id: String,
+    /// 费用类型（protocol: 协议费, fund: 基金费）
+    pub fee_type: String,
+    /// 接收token_0的账户
+    pub recipient_token_account_0: String,
+    /// 接收token_1的账户
+    pub recipient_token_account_1: String,
+    /// 领取的token_0数量
+    pub amount_0: u64,
+    /// 领取的token_1数量
+    pub amount_1: u64,
+    /// 交易签名
+    pub signature: String,
+    /// 区块高度
+    pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
+    /// 处理时间
+    pub processed_at: String,
+}
+
+/// CLMM协议费领取事件解析器（collect_protocol_fee / collect_fund_fee）
+pub struct ClmmProtocolFeeParser {
+    /// 事件的discriminator
+    discriminator: [u8; 8],
+    /// 目标程序ID，指定此解析器处理哪个程序的事件
+    target_program_id: Pubkey,
+}
+
+impl ClmmProtocolFeeParser {
+    /// 创建新的CLMM协议费领取事件解析器
+    pub fn new(_config: &EventListenerConfig, program_id: Pubkey) -> Result<Self> {
+        let discriminator = crate::parser::event_parser::calculate_event_discriminator("CollectProtocolFeeEvent");
+
+        Ok(Self {
+            discriminator,
+            target_program_id: program_id,
+        })
+    }
+
+    /// 从程序数据解析协议费领取事件
+    fn parse_program_data(&self, data_str: &str) -> Result<CollectProtocolFeeEvent> {
+        // Base64解码
+        let data = general_purpose::STANDARD
+            .decode(data_str)
+            .map_err(|e| EventListenerError::EventParsing(format!("Base64解码失败: {}", e)))?;
+
+        if data.len() < 8 {
+            return Err(EventListenerError::EventParsing(
+                "数据长度不足，无法包含discriminator".to_string(),
+            ));
+        }
+
+        // 验证discriminator
+        let discriminator = &data[0..8];
+        if discriminator != self.discriminator {
+            return Err(EventListenerError::DiscriminatorMismatch);
+        }
+
+        // Borsh反序列化事件数据
+        let event_data = &data[8..];
+        let event = CollectProtocolFeeEvent::try_from_slice(event_data)
+            .map_err(|e| EventListenerError::EventParsing(format!("Borsh反序列化失败: {}", e)))?;
+
+        debug!(
+            "✅ 成功解析CLMM协议费领取事件: 池子={}, amount_0={}, amount_1={}",
+            event.pool_state, event.amount_0, event.amount_1
+        );
+        Ok(event)
+    }
+
+    /// 将原始事件转换为ParsedEvent
+    fn convert_to_parsed_event(
+        &self,
+        event: CollectProtocolFeeEvent,
+        fee_type: &str,
+        signature: String,
+        slot: u64,
+    ) -> ParsedEvent {
+        ParsedEvent::ClmmCollectProtocolFee(ClmmCollectProtocolFeeEventData {
+            pool_id: event.pool_state.to_string(),
+            fee_type: fee_type.to_string(),
+            recipient_token_account_0: event.recipient_token_account_0.to_string(),
+            recipient_token_account_1: event.recipient_token_account_1.to_string(),
+            amount_0: event.amount_0,
+            amount_1: event.amount_1,
+            signature,
+            slot,
+            instruction_index: 0,
+            event_ordinal: 0,
+            processed_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// 验证协议费领取事件数据
+    fn validate_collect_protocol_fee(&self, event: &ClmmCollectProtocolFeeEventData) -> Result<bool> {
+        // 验证池子地址
+        if event.pool_id == Pubkey::default().to_string() {
+            warn!("❌ 无效的池子地址");
+            return Ok(false);
+        }
+
+        // 验证费用类型
+        if event.fee_type != "protocol" && event.fee_type != "fund" {
+            warn!("❌ 无效的费用类型: {}", event.fee_type);
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl EventParser for ClmmProtocolFeeParser {
+    fn get_program_id(&self) -> Pubkey {
+        self.target_program_id
+    }
+
+    fn get_discriminator(&self) -> [u8; 8] {
+        self.discriminator
+    }
+
+    fn get_event_type(&self) -> &'static str {
+        "clmm_collect_protocol_fee"
+    }
+
+    fn supports_program(&self, program_id: &Pubkey) -> Option<bool> {
+        Some(*program_id == self.target_program_id)
+    }
+
+    async fn parse_from_logs(&self, logs: &[String], signature: &str, slot: u64) -> Result<Option<ParsedEvent>> {
+        // 事件之前最近一条指令日志决定费用类型，缺省视为协议费
+        let mut fee_type = "protocol";
+
+        for (index, log) in logs.iter().enumerate() {
+            if log.starts_with(COLLECT_FUND_FEE_INSTRUCTION_LOG) {
+                fee_type = "fund";
+                continue;
+            }
+            if log.starts_with(COLLECT_PROTOCOL_FEE_INSTRUCTION_LOG) {
+                fee_type = "protocol";
+                continue;
+            }
+
+            if let Some(data_part) = log.strip_prefix("Program data: ") {
+                match self.parse_program_data(data_part) {
+                    Ok(event) => {
+                        info!(
+                            "🏦 第{}行发现CLMM{}领取事件: 池子={} amount_0={} amount_1={}",
+                            index + 1,
+                            if fee_type == "fund" { "基金费" } else { "协议费" },
+                            event.pool_state,
+                            event.amount_0,
+                            event.amount_1
+                        );
+                        let parsed_event = self.convert_to_parsed_event(event, fee_type, signature.to_string(), slot);
+                        return Ok(Some(parsed_event));
+                    }
+                    Err(EventListenerError::DiscriminatorMismatch) => {
+                        // Discriminator不匹配是正常情况，继续尝试下一条日志
+                        continue;
+                    }
+                    Err(e) => {
+                        warn!("⚠️ 第{}行CLMM协议费领取事件解析失败: {}", index + 1, e);
+                        continue;
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    async fn validate_event(&self, event: &ParsedEvent) -> Result<bool> {
+        match event {
+            ParsedEvent::ClmmCollectProtocolFee(fee_event) => self.validate_collect_protocol_fee(fee_event),
+            _ => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::pubkey;
+
+    fn create_test_config() -> EventListenerConfig {
+        EventListenerConfig {
+            solana: crate::config::settings::SolanaConfig {
+                rpc_url: "https://api.devnet.solana.com".to_string(),
+                ws_url: "wss://api.devnet.solana.com".to_string(),
+                commitment: "confirmed".to_string(),
+                program_ids: vec![pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX")],
+                private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
+            },
+            database: crate::config::settings::DatabaseConfig {
+                uri: "mongodb://localhost:27017".to_string(),
+                database_name: "test".to_string(),
+                max_connections: 10,
+                min_connections: 2,
+            },
+            listener: crate::config::settings::ListenerConfig {
+                batch_size: 100,
+                sync_interval_secs: 30,
+                max_retries: 3,
+                retry_delay_ms: 1000,
+                signature_cache_size: 10000,
+                checkpoint_save_interval_secs: 60,
+                backoff: crate::config::settings::BackoffConfig::default(),
+                batch_write: crate::config::settings::BatchWriteConfig::default(),
+            },
+            monitoring: crate::config::settings::MonitoringConfig {
+                metrics_interval_secs: 60,
+                enable_performance_monitoring: true,
+                health_check_interval_secs: 30,
+                http_bind_addr: None,
+            },
+            backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
+        }
+    }
+
+    fn create_test_fee_event() -> CollectProtocolFeeEvent {
+        CollectProtocolFeeEvent {
+            pool_state: Pubkey::new_unique(),
+            recipient_token_account_0: Pubkey::new_unique(),
+            recipient_token_account_1: Pubkey::new_unique(),
+            amount_0: 1_000,
+            amount_1: 2_000,
+        }
+    }
+
+    fn encode_program_data(event: &CollectProtocolFeeEvent) -> String {
+        let mut data = crate::parser::event_parser::calculate_event_discriminator("CollectProtocolFeeEvent").to_vec();
+        data.extend(borsh::to_vec(event).unwrap());
+        format!("Program data: {}", general_purpose::STANDARD.encode(data))
+    }
+
+    #[test]
+    fn test_clmm_protocol_fee_parser_creation() {
+        let config = create_test_config();
+        let program_id = pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX");
+        let parser = ClmmProtocolFeeParser::new(&config, program_id).unwrap();
+
+        assert_eq!(parser.get_event_type(), "clmm_collect_protocol_fee");
+        assert_eq!(parser.get_program_id(), program_id);
+        assert_eq!(
+            parser.get_discriminator(),
+            crate::parser::event_parser::calculate_event_discriminator("CollectProtocolFeeEvent")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_collect_protocol_fee_from_logs() {
+        let config = create_test_config();
+        let parser =
+            ClmmProtocolFeeParser::new(&config, pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX")).unwrap();
+        let event = create_test_fee_event();
+
+        let logs = vec![
+            "Program log: Instruction: CollectProtocolFee".to_string(),
+            encode_program_data(&event),
+        ];
+
+        match parser.parse_from_logs(&logs, "test_sig", 400).await.unwrap() {
+            Some(ParsedEvent::ClmmCollectProtocolFee(data)) => {
+                assert_eq!(data.pool_id, event.pool_state.to_string());
+                assert_eq!(data.fee_type, "protocol");
+                assert_eq!(data.amount_0, 1_000);
+                assert_eq!(data.amount_1, 2_000);
+                assert_eq!(data.slot, 400);
+
+                let parsed = ParsedEvent::ClmmCollectProtocolFee(data);
+                assert!(parser.validate_event(&parsed).await.unwrap());
+            }
+            other => panic!("期望ClmmCollectProtocolFee事件，实际: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parse_collect_fund_fee_from_logs() {
+        let config = create_test_config();
+        let parser =
+            ClmmProtocolFeeParser::new(&config, pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX")).unwrap();
+        let event = create_test_fee_event();
+
+        let logs = vec![
+            "Program log: Instruction: CollectFundFee".to_string(),
+            encode_program_data(&event),
+        ];
+
+        match parser.parse_from_logs(&logs, "test_sig", 400).await.unwrap() {
+            Some(ParsedEvent::ClmmCollectProtocolFee(data)) => assert_eq!(data.fee_type, "fund"),
+            other => panic!("期望ClmmCollectProtocolFee事件，实际: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,398 @@
+use crate::{
+    config::EventListenerConfig,
+    error::{EventListenerError, Result},
+    parser::{EventParser, ParsedEvent},
+};
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use tracing::{debug, info, warn};
+
+/// CLMM交换事件的原始数据结构（与CLMM合约states/pool.rs中的SwapEvent保持一致）
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct ClmmSwapEvent {
+    /// 池子状态地址
+    pub pool_state: Pubkey,
+    /// 交换发起者
+    pub sender: Pubkey,
+    /// token_0账户（输入或输出取决于交换方向）
+    pub token_account_0: Pubkey,
+    /// token_1账户（输入或输出取决于交换方向）
+    pub token_account_1: Pubkey,
+    /// token_0数量
+    pub amount_0: u64,
+    /// token_0转账费
+    pub transfer_fee_0: u64,
+    /// token_1数量
+    pub amount_1: u64,
+    /// token_1转账费
+    pub transfer_fee_1: u64,
+    /// 是否token_0换token_1
+    pub zero_for_one: bool,
+    /// 交换后的sqrt价格(Q64.64)
+    pub sqrt_price_x64: u128,
+    /// 交换后的流动性
+    pub liquidity: u128,
+    /// 交换后的tick
+    pub tick: i32,
+}
+
+/// CLMM交换事件数据（用于事件系统传递）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClmmSwapEventData {
+    /// 池子地址
+    pub pool_id: String,
+    /// 交换发起者
+    pub sender: String,
+    /// token_0账户
+    pub token_account_0: String,
+    /// token_1账户
+    pub token_account_1: String,
+    /// token_0数量
+    pub amount_0: u64,
+    /// token_0转账费
+    pub transfer_fee_0: u64,
+    /// token_1数量
+    pub amount_1: u64,
+    /// token_1转账费
+    pub transfer_fee_1: u64,
+    /// 是否token_0换token_1
+    pub zero_for_one: bool,
+    /// 交换后的sqrt价格(Q64.64)
+    pub sqrt_price_x64: u128,
+    /// 交换后的流动性
+    pub liquidity: u128,
+    /// 交换后的tick
+    pub tick: i32,
+    /// 交易签名
+    pub signature: String,
+    /// 区块高度
+    pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
+    /// 出块时间（实时订阅的日志不携带，写入前按slot补齐）
+    #[serde(default)]
+    pub block_time: Option<i64>,
+    /// 处理时间
+    pub processed_at: String,
+}
+
+/// CLMM交换事件解析器
+///
+/// CLMM与CPMM的事件名称都是SwapEvent，discriminator相同，
+/// 依靠ParserKey中的program_id区分
+pub struct ClmmSwapParser {
+    /// 事件的discriminator
+    discriminator: [u8; 8],
+    /// 目标程序ID，指定此解析器处理哪个程序的事件
+    target_program_id: Pubkey,
+}
+
+impl ClmmSwapParser {
+    /// 创建新的CLMM交换事件解析器
+    pub fn new(_config: &EventListenerConfig, program_id: Pubkey) -> Result<Self> {
+        let discriminator = crate::parser::event_parser::calculate_event_discriminator("SwapEvent");
+
+        Ok(Self {
+            discriminator,
+            target_program_id: program_id,
+        })
+    }
+
+    /// 从程序数据解析CLMM交换事件
+    fn parse_program_data(&self, data_str: &str) -> Result<ClmmSwapEvent> {
+        // Base64解码
+        let data = general_purpose::STANDARD
+            .decode(data_str)
+            .map_err(|e| EventListenerError::EventParsing(format!("Base64解码失败: {}", e)))?;
+
+        if data.len() < 8 {
+            return Err(EventListenerError::EventParsing(
+                "数据长度不足，无法包含discriminator".to_string(),
+            ));
+        }
+
+        // 验证discriminator
+        let discriminator = &data[0..8];
+        if discriminator != self.discriminator {
+            return Err(EventListenerError::DiscriminatorMismatch);
+        }
+
+        // Borsh反序列化事件数据
+        let event_data = &data[8..];
+        let event = ClmmSwapEvent::try_from_slice(event_data)
+            .map_err(|e| EventListenerError::EventParsing(format!("Borsh反序列化失败: {}", e)))?;
+
+        debug!(
+            "✅ 成功解析CLMM交换事件: 池子={}, 发送者={}, amount_0={}, amount_1={}",
+            event.pool_state, event.sender, event.amount_0, event.amount_1
+        );
+        Ok(event)
+    }
+
+    /// 将原始事件转换为ClmmSwapEventData
+    fn convert_to_parsed_event(&self, event: ClmmSwapEvent, signature: String, slot: u64) -> ParsedEvent {
+        ParsedEvent::ClmmSwap(ClmmSwapEventData {
+            pool_id: event.pool_state.to_string(),
+            sender: event.sender.to_string(),
+            token_account_0: event.token_account_0.to_string(),
+            token_account_1: event.token_account_1.to_string(),
+            amount_0: event.amount_0,
+            transfer_fee_0: event.transfer_fee_0,
+            amount_1: event.amount_1,
+            transfer_fee_1: event.transfer_fee_1,
+            zero_for_one: event.zero_for_one,
+            sqrt_price_x64: event.sqrt_price_x64,
+            liquidity: event.liquidity,
+            tick: event.tick,
+            signature,
+            slot,
+            instruction_index: 0,
+            event_ordinal: 0,
+            block_time: None,
+            processed_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// 验证CLMM交换事件数据
+    fn validate_swap(&self, event: &ClmmSwapEvent) -> Result<bool> {
+        // 验证池子地址
+        if event.pool_state == Pubkey::default() {
+            warn!("❌ 无效的池子地址");
+            return Ok(false);
+        }
+
+        // 验证发起者地址
+        if event.sender == Pubkey::default() {
+            warn!("❌ 无效的交换发起者地址");
+            return Ok(false);
+        }
+
+        // 验证交换数量
+        if event.amount_0 == 0 && event.amount_1 == 0 {
+            warn!("❌ token_0和token_1数量不能同时为0");
+            return Ok(false);
+        }
+
+        // 验证sqrt价格
+        if event.sqrt_price_x64 == 0 {
+            warn!("❌ 无效的sqrt价格: 0");
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl EventParser for ClmmSwapParser {
+    fn get_program_id(&self) -> Pubkey {
+        self.target_program_id
+    }
+
+    fn get_discriminator(&self) -> [u8; 8] {
+        self.discriminator
+    }
+
+    fn get_event_type(&self) -> &'static str {
+        "clmm_swap"
+    }
+
+    fn supports_program(&self, program_id: &Pubkey) -> Option<bool> {
+        Some(*program_id == self.target_program_id)
+    }
+
+    async fn parse_from_logs(&self, logs: &[String], signature: &str, slot: u64) -> Result<Option<ParsedEvent>> {
+        for (index, log) in logs.iter().enumerate() {
+            if let Some(data_part) = log.strip_prefix("Program data: ") {
+                match self.parse_program_data(data_part) {
+                    Ok(event) => {
+                        info!(
+                            "💱 第{}行发现CLMM交换事件: 池子={}, 交换者={}, amount_0={}, amount_1={}, tick={}",
+                            index + 1,
+                            event.pool_state,
+                            event.sender,
+                            event.amount_0,
+                            event.amount_1,
+                            event.tick
+                        );
+
+                        if self.validate_swap(&event)? {
+                            let parsed_event = self.convert_to_parsed_event(event, signature.to_string(), slot);
+                            return Ok(Some(parsed_event));
+                        }
+                    }
+                    Err(EventListenerError::DiscriminatorMismatch) => {
+                        // Discriminator不匹配是正常情况，继续尝试下一条日志
+                        continue;
+                    }
+                    Err(e) => {
+                        debug!("⚠️ 第{}行CLMM交换事件解析失败: {}", index + 1, e);
+                        continue;
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    async fn validate_event(&self, event: &ParsedEvent) -> Result<bool> {
+        match event {
+            ParsedEvent::ClmmSwap(_) => Ok(true),
+            _ => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+    use std::str::FromStr;
+
+    fn create_test_config() -> EventListenerConfig {
+        EventListenerConfig {
+            solana: crate::config::settings::SolanaConfig {
+                rpc_url: "https://api.devnet.solana.com".to_string(),
+                ws_url: "wss://api.devnet.solana.com".to_string(),
+                commitment: "confirmed".to_string(),
+                program_ids: vec![Pubkey::from_str("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX").unwrap()],
+                private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
+            },
+            database: crate::config::settings::DatabaseConfig {
+                uri: "mongodb://localhost:27017".to_string(),
+                database_name: "test".to_string(),
+                max_connections: 10,
+                min_connections: 2,
+            },
+            listener: crate::config::settings::ListenerConfig {
+                batch_size: 100,
+                sync_interval_secs: 30,
+                max_retries: 3,
+                retry_delay_ms: 1000,
+                signature_cache_size: 10000,
+                checkpoint_save_interval_secs: 60,
+                backoff: crate::config::settings::BackoffConfig::default(),
+                batch_write: crate::config::settings::BatchWriteConfig::default(),
+            },
+            monitoring: crate::config::settings::MonitoringConfig {
+                metrics_interval_secs: 60,
+                enable_performance_monitoring: true,
+                health_check_interval_secs: 30,
+                http_bind_addr: None,
+            },
+            backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
+        }
+    }
+
+    fn create_test_clmm_swap_event() -> ClmmSwapEvent {
+        ClmmSwapEvent {
+            pool_state: Pubkey::new_unique(),
+            sender: Pubkey::new_unique(),
+            token_account_0: Pubkey::new_unique(),
+            token_account_1: Pubkey::new_unique(),
+            amount_0: 1_000_000,
+            transfer_fee_0: 0,
+            amount_1: 2_000_000,
+            transfer_fee_1: 0,
+            zero_for_one: true,
+            sqrt_price_x64: 1u128 << 64,
+            liquidity: 5_000_000_000,
+            tick: -12,
+        }
+    }
+
+    fn encode_program_data(event: &ClmmSwapEvent) -> String {
+        let mut data = crate::parser::event_parser::calculate_event_discriminator("SwapEvent").to_vec();
+        data.extend(borsh::to_vec(event).unwrap());
+        format!("Program data: {}", general_purpose::STANDARD.encode(data))
+    }
+
+    #[test]
+    fn test_clmm_swap_parser_creation() {
+        let config = create_test_config();
+        let parser = ClmmSwapParser::new(&config, Pubkey::new_unique()).unwrap();
+
+        assert_eq!(parser.get_event_type(), "clmm_swap");
+        assert_eq!(
+            parser.get_discriminator(),
+            crate::parser::event_parser::calculate_event_discriminator("SwapEvent")
+        );
+    }
+
+    #[test]
+    fn test_clmm_swap_parser_supports_program() {
+        let config = create_test_config();
+        let target_program = Pubkey::from_str("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX").unwrap();
+        let parser = ClmmSwapParser::new(&config, target_program).unwrap();
+
+        assert_eq!(parser.supports_program(&target_program), Some(true));
+        assert_eq!(parser.supports_program(&Pubkey::new_unique()), Some(false));
+    }
+
+    #[tokio::test]
+    async fn test_parse_from_logs_roundtrip() {
+        let config = create_test_config();
+        let parser = ClmmSwapParser::new(&config, Pubkey::new_unique()).unwrap();
+        let event = create_test_clmm_swap_event();
+
+        let logs = vec![
+            "Program FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX invoke [1]".to_string(),
+            encode_program_data(&event),
+        ];
+
+        let result = parser.parse_from_logs(&logs, "test_sig", 12345).await.unwrap();
+        match result {
+            Some(ParsedEvent::ClmmSwap(data)) => {
+                assert_eq!(data.pool_id, event.pool_state.to_string());
+                assert_eq!(data.sender, event.sender.to_string());
+                assert_eq!(data.amount_0, 1_000_000);
+                assert_eq!(data.amount_1, 2_000_000);
+                assert_eq!(data.sqrt_price_x64, 1u128 << 64);
+                assert_eq!(data.liquidity, 5_000_000_000);
+                assert_eq!(data.tick, -12);
+                assert_eq!(data.signature, "test_sig");
+                assert_eq!(data.slot, 12345);
+            }
+            other => panic!("期望ClmmSwap事件，实际: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parse_from_logs_rejects_zero_amounts() {
+        let config = create_test_config();
+        let parser = ClmmSwapParser::new(&config, Pubkey::new_unique()).unwrap();
+        let mut event = create_test_clmm_swap_event();
+        event.amount_0 = 0;
+        event.amount_1 = 0;
+
+        let logs = vec![encode_program_data(&event)];
+        let result = parser.parse_from_logs(&logs, "test_sig", 12345).await.unwrap();
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_parse_from_logs_no_program_data() {
+        let config = create_test_config();
+        let parser = ClmmSwapParser::new(&config, Pubkey::new_unique()).unwrap();
+
+        let logs = vec![
+            "Program 11111111111111111111111111111111 invoke [1]".to_string(),
+            "Program 11111111111111111111111111111111 success".to_string(),
+        ];
+
+        let result = parser.parse_from_logs(&logs, "test_sig", 12345).await.unwrap();
+        assert!(result.is_none());
+    }
+}
@@ -61,6 +61,10 @@ pub struct InitPoolEventData {
     // 交易信息
     pub signature: String,
     pub slot: u64,
+    #[serde(default)]
+    pub instruction_index: u32,
+    #[serde(default)]
+    pub event_ordinal: u32,
     pub processed_at: String,
 }
 
@@ -352,6 +356,8 @@ impl InitPoolParser {
 
             signature,
             slot,
+            instruction_index: 0,
+            event_ordinal: 0,
             processed_at: Utc::now().to_rfc3339(),
         };
 
@@ -555,6 +561,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -576,8 +583,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         }
     }
 
@@ -614,6 +626,8 @@ mod tests {
             signature: "3PGKKiYqS6KJNcvS5KvHTZMiKF7RPTJdGXHFDwMHhJf5tDn1Zj4BhM5XgRcvNsF2kL6pYzCH8qR7eB9J3VfGKdAt"
                 .to_string(),
             slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
             processed_at: chrono::Utc::now().to_rfc3339(),
         }
     }
@@ -819,6 +833,8 @@ mod tests {
             created_at: 1234567890,
             signature: "test_signature".to_string(),
             slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
             extensions: None,
             source: None,
         };
@@ -96,6 +96,10 @@ pub struct LpChangeEventData {
     // 交易信息
     pub signature: String,
     pub slot: u64,
+    #[serde(default)]
+    pub instruction_index: u32,
+    #[serde(default)]
+    pub event_ordinal: u32,
     pub processed_at: String,
 }
 
@@ -268,6 +272,8 @@ impl LpChangeParser {
             // 交易信息
             signature,
             slot,
+            instruction_index: 0,
+            event_ordinal: 0,
             processed_at: Utc::now().to_rfc3339(),
         };
 
@@ -446,6 +452,9 @@ mod tests {
                     max_wait_ms: 1000,
                     buffer_size: 100,
                     concurrent_writers: 1,
+                    overflow_policy: OverflowPolicy::Block,
+                    spill_path: None,
+                    sinks: EventSinkConfig::default(),
                 },
             },
             solana: SolanaConfig {
@@ -454,6 +463,7 @@ mod tests {
                 commitment: "finalized".to_string(),
                 program_ids: vec![],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -465,8 +475,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         }
     }
 
@@ -501,6 +516,8 @@ mod tests {
             token_1_decimals: 9,
             signature: "test_signature".to_string(),
             slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
             processed_at: Utc::now().to_rfc3339(),
         }
     }
@@ -85,6 +85,12 @@ pub struct DepositEventData {
     pub signature: String,
     /// 区块高度
     pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
     /// 存款时间戳
     pub deposited_at: i64,
     /// 处理时间
@@ -254,6 +260,8 @@ impl DepositEventParser {
             actual_total_raised,
             signature,
             slot,
+            instruction_index: 0,
+            event_ordinal: 0,
             deposited_at: chrono::Utc::now().timestamp(),
             processed_at: chrono::Utc::now().to_rfc3339(),
         })
@@ -730,6 +738,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![Pubkey::from_str("11111111111111111111111111111112").unwrap()],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -751,8 +760,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         }
     }
 
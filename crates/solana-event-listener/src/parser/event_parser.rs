@@ -1,17 +1,30 @@
+use crate::config::settings::IdlEventsConfig;
 use crate::config::EventListenerConfig;
 use crate::error::{EventListenerError, Result};
+use crate::parser::clmm_config_change_parser::ClmmConfigChangeEventData;
+use crate::parser::clmm_position_parser::{
+    ClmmCollectPersonalFeeEventData, ClmmCreatePositionEventData, ClmmDecreaseLiquidityEventData,
+    ClmmIncreaseLiquidityEventData,
+};
+use crate::parser::clmm_protocol_fee_parser::ClmmCollectProtocolFeeEventData;
+use crate::parser::clmm_swap_parser::ClmmSwapEventData;
 use crate::parser::cpmm_init_pool_parser::InitPoolEventData;
 use crate::parser::cpmm_lp_change_parser::LpChangeEventData;
 use crate::parser::deposit_event_parser::DepositEventData;
+use crate::parser::idl_event_parser::{build_idl_parsers, RawEventData};
 use crate::parser::launch_event_parser::LaunchEventData;
 use crate::parser::nft_claim_parser::NftClaimEventData;
+use crate::parser::nft_mint_parser::NftMintEventData;
 use crate::parser::pool_creation_parser::PoolCreatedEventData;
+use crate::parser::referral_established_parser::ReferralEstablishedEventData;
 use crate::parser::reward_distribution_parser::RewardDistributionEventData;
 use crate::parser::swap_parser::SwapEventData;
 use crate::parser::token_creation_parser::TokenCreationEventData;
+use crate::parser::transaction_fallback::InstructionEventData;
 use crate::parser::{
-    DepositEventParser, InitPoolParser, LaunchEventParser, LpChangeParser, NftClaimParser, PoolCreationParser,
-    RewardDistributionParser, SwapParser, TokenCreationParser,
+    ClmmConfigChangeParser, ClmmPositionEventKind, ClmmPositionParser, ClmmProtocolFeeParser, ClmmSwapParser,
+    DepositEventParser, InitPoolParser, LaunchEventParser, LpChangeParser, NftClaimParser, NftMintParser,
+    PoolCreationParser, ReferralEstablishedParser, RewardDistributionParser, SwapParser, TokenCreationParser,
 };
 use anchor_lang::pubkey;
 use async_trait::async_trait;
@@ -20,7 +33,7 @@ use sha2::{Digest, Sha256};
 use solana_sdk::pubkey::Pubkey;
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use tracing::info;
 use utils::TokenMetadataProvider;
 
@@ -108,6 +121,26 @@ pub enum ParsedEvent {
     LpChange(LpChangeEventData),
     /// 池子初始化事件
     InitPool(InitPoolEventData),
+    /// CLMM交换事件
+    ClmmSwap(ClmmSwapEventData),
+    /// CLMM开仓事件
+    ClmmCreatePosition(ClmmCreatePositionEventData),
+    /// CLMM增加流动性事件
+    ClmmIncreaseLiquidity(ClmmIncreaseLiquidityEventData),
+    /// CLMM减少流动性事件
+    ClmmDecreaseLiquidity(ClmmDecreaseLiquidityEventData),
+    /// CLMM领取仓位手续费事件
+    ClmmCollectPersonalFee(ClmmCollectPersonalFeeEventData),
+    /// 推荐NFT铸造事件
+    NftMint(NftMintEventData),
+    /// 推荐关系建立事件
+    ReferralEstablished(ReferralEstablishedEventData),
+    /// CLMM配置变更事件
+    ClmmConfigChange(ClmmConfigChangeEventData),
+    /// CLMM协议费/基金费领取事件
+    ClmmCollectProtocolFee(ClmmCollectProtocolFeeEventData),
+    /// 按IDL通用解析的原始事件
+    Raw(RawEventData),
 }
 
 impl ParsedEvent {
@@ -123,23 +156,239 @@ impl ParsedEvent {
             ParsedEvent::Deposit(_) => "deposit",
             ParsedEvent::LpChange(_) => "lp_change",
             ParsedEvent::InitPool(_) => "init_pool",
+            ParsedEvent::ClmmSwap(_) => "clmm_swap",
+            ParsedEvent::ClmmCreatePosition(_) => "clmm_create_position",
+            ParsedEvent::ClmmIncreaseLiquidity(_) => "clmm_increase_liquidity",
+            ParsedEvent::ClmmDecreaseLiquidity(_) => "clmm_decrease_liquidity",
+            ParsedEvent::ClmmCollectPersonalFee(_) => "clmm_collect_personal_fee",
+            ParsedEvent::NftMint(_) => "nft_mint",
+            ParsedEvent::ReferralEstablished(_) => "referral_established",
+            ParsedEvent::ClmmConfigChange(_) => "clmm_config_change",
+            ParsedEvent::ClmmCollectProtocolFee(_) => "clmm_collect_protocol_fee",
+            ParsedEvent::Raw(_) => "raw_event",
         }
     }
 
-    /// 获取事件的唯一标识符（用于去重）
-    pub fn get_unique_id(&self) -> String {
+    /// 获取事件所在交易的签名
+    pub fn signature(&self) -> &str {
+        match self {
+            ParsedEvent::TokenCreation(data) => &data.signature,
+            ParsedEvent::PoolCreation(data) => &data.signature,
+            ParsedEvent::NftClaim(data) => &data.signature,
+            ParsedEvent::RewardDistribution(data) => &data.signature,
+            ParsedEvent::Swap(data) => &data.signature,
+            ParsedEvent::Launch(data) => &data.signature,
+            ParsedEvent::Deposit(data) => &data.signature,
+            ParsedEvent::LpChange(data) => &data.signature,
+            ParsedEvent::InitPool(data) => &data.signature,
+            ParsedEvent::ClmmSwap(data) => &data.signature,
+            ParsedEvent::ClmmCreatePosition(data) => &data.signature,
+            ParsedEvent::ClmmIncreaseLiquidity(data) => &data.signature,
+            ParsedEvent::ClmmDecreaseLiquidity(data) => &data.signature,
+            ParsedEvent::ClmmCollectPersonalFee(data) => &data.signature,
+            ParsedEvent::NftMint(data) => &data.signature,
+            ParsedEvent::ReferralEstablished(data) => &data.signature,
+            ParsedEvent::ClmmConfigChange(data) => &data.signature,
+            ParsedEvent::ClmmCollectProtocolFee(data) => &data.signature,
+            ParsedEvent::Raw(data) => &data.signature,
+        }
+    }
+
+    /// 获取事件所在交易的slot
+    pub fn slot(&self) -> u64 {
+        match self {
+            ParsedEvent::TokenCreation(data) => data.slot,
+            ParsedEvent::PoolCreation(data) => data.slot,
+            ParsedEvent::NftClaim(data) => data.slot,
+            ParsedEvent::RewardDistribution(data) => data.slot,
+            ParsedEvent::Swap(data) => data.slot,
+            ParsedEvent::Launch(data) => data.slot,
+            ParsedEvent::Deposit(data) => data.slot,
+            ParsedEvent::LpChange(data) => data.slot,
+            ParsedEvent::InitPool(data) => data.slot,
+            ParsedEvent::ClmmSwap(data) => data.slot,
+            ParsedEvent::ClmmCreatePosition(data) => data.slot,
+            ParsedEvent::ClmmIncreaseLiquidity(data) => data.slot,
+            ParsedEvent::ClmmDecreaseLiquidity(data) => data.slot,
+            ParsedEvent::ClmmCollectPersonalFee(data) => data.slot,
+            ParsedEvent::NftMint(data) => data.slot,
+            ParsedEvent::ReferralEstablished(data) => data.slot,
+            ParsedEvent::ClmmConfigChange(data) => data.slot,
+            ParsedEvent::ClmmCollectProtocolFee(data) => data.slot,
+            ParsedEvent::Raw(data) => data.slot,
+        }
+    }
+
+    /// 获取事件关联的池子地址（用于webhook订阅过滤）
+    pub fn pool_address(&self) -> Option<&str> {
+        match self {
+            ParsedEvent::PoolCreation(data) => Some(data.pool_address.as_str()),
+            ParsedEvent::NftClaim(data) => data.pool_address.as_deref(),
+            ParsedEvent::Swap(data) => Some(data.pool_id.as_str()),
+            ParsedEvent::Deposit(data) => data.related_pool.as_deref(),
+            ParsedEvent::LpChange(data) => Some(data.pool_id.as_str()),
+            ParsedEvent::InitPool(data) => Some(data.pool_id.as_str()),
+            ParsedEvent::ClmmSwap(data) => Some(data.pool_id.as_str()),
+            ParsedEvent::ClmmCreatePosition(data) => Some(data.pool_id.as_str()),
+            ParsedEvent::ClmmCollectProtocolFee(data) => Some(data.pool_id.as_str()),
+            _ => None,
+        }
+    }
+
+    /// 获取事件涉及的代币mint地址（用于webhook订阅过滤）
+    pub fn mints(&self) -> Vec<&str> {
+        match self {
+            ParsedEvent::TokenCreation(data) => vec![data.mint_address.as_str()],
+            ParsedEvent::PoolCreation(data) => vec![data.token_a_mint.as_str(), data.token_b_mint.as_str()],
+            ParsedEvent::NftClaim(data) => vec![data.nft_mint.as_str(), data.token_mint.as_str()],
+            ParsedEvent::RewardDistribution(data) => vec![data.reward_token_mint.as_str()],
+            ParsedEvent::Swap(data) => vec![data.input_mint.as_str(), data.output_mint.as_str()],
+            ParsedEvent::Launch(data) => vec![data.meme_token_mint.as_str(), data.base_token_mint.as_str()],
+            ParsedEvent::Deposit(data) => vec![data.token_mint.as_str()],
+            ParsedEvent::LpChange(data) => vec![
+                data.lp_mint.as_str(),
+                data.token_0_mint.as_str(),
+                data.token_1_mint.as_str(),
+            ],
+            ParsedEvent::InitPool(data) => vec![
+                data.lp_mint.as_str(),
+                data.token_0_mint.as_str(),
+                data.token_1_mint.as_str(),
+            ],
+            ParsedEvent::ClmmCreatePosition(data) => data.position_nft_mint.as_deref().into_iter().collect(),
+            ParsedEvent::ClmmIncreaseLiquidity(data) => vec![data.position_nft_mint.as_str()],
+            ParsedEvent::ClmmDecreaseLiquidity(data) => vec![data.position_nft_mint.as_str()],
+            ParsedEvent::ClmmCollectPersonalFee(data) => vec![data.position_nft_mint.as_str()],
+            ParsedEvent::NftMint(data) => vec![data.nft_mint.as_str()],
+            ParsedEvent::ReferralEstablished(data) => vec![data.nft_mint.as_str()],
+            _ => Vec::new(),
+        }
+    }
+
+    /// 获取事件涉及的钱包地址（用于webhook订阅过滤）
+    pub fn wallets(&self) -> Vec<&str> {
+        match self {
+            ParsedEvent::TokenCreation(data) => vec![data.creator.as_str()],
+            ParsedEvent::PoolCreation(data) => vec![data.creator.as_str()],
+            ParsedEvent::NftClaim(data) => std::iter::once(data.claimer.as_str())
+                .chain(data.referrer.as_deref())
+                .collect(),
+            ParsedEvent::RewardDistribution(data) => std::iter::once(data.recipient.as_str())
+                .chain(data.referrer.as_deref())
+                .collect(),
+            ParsedEvent::Swap(data) => vec![data.payer.as_str()],
+            ParsedEvent::Launch(data) => vec![data.user_wallet.as_str()],
+            ParsedEvent::Deposit(data) => vec![data.user.as_str()],
+            ParsedEvent::LpChange(data) => vec![data.user_wallet.as_str()],
+            ParsedEvent::InitPool(data) => vec![data.pool_creator.as_str()],
+            ParsedEvent::ClmmSwap(data) => vec![data.sender.as_str()],
+            ParsedEvent::ClmmCreatePosition(data) => vec![data.minter.as_str(), data.nft_owner.as_str()],
+            ParsedEvent::NftMint(data) => vec![data.minter.as_str()],
+            ParsedEvent::ReferralEstablished(data) => vec![data.user.as_str(), data.upper.as_str()],
+            ParsedEvent::ClmmConfigChange(data) => vec![data.owner.as_str(), data.fund_owner.as_str()],
+            _ => Vec::new(),
+        }
+    }
+
+    /// 获取事件在交易中的位置（顶层指令索引, 指令内事件序号）
+    pub fn position(&self) -> (u32, u32) {
         match self {
-            ParsedEvent::TokenCreation(data) => data.mint_address.to_string(),
-            ParsedEvent::PoolCreation(data) => data.pool_address.to_string(),
-            ParsedEvent::NftClaim(data) => format!("{}_{}", data.nft_mint, data.signature),
-            ParsedEvent::RewardDistribution(data) => format!("{}_{}", data.distribution_id, data.signature),
-            ParsedEvent::Swap(data) => format!("{}_{}", data.pool_id, data.signature),
-            ParsedEvent::Launch(data) => format!("{}_{}", data.meme_token_mint, data.signature),
-            ParsedEvent::Deposit(data) => format!("{}_{}_{}", data.user, data.token_mint, data.signature),
-            ParsedEvent::LpChange(data) => data.signature.clone(), // 使用signature作为唯一标识
-            ParsedEvent::InitPool(data) => data.pool_id.clone(),   // 使用pool_id作为唯一标识
+            ParsedEvent::TokenCreation(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::PoolCreation(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::NftClaim(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::RewardDistribution(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::Swap(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::Launch(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::Deposit(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::LpChange(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::InitPool(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::ClmmSwap(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::ClmmCreatePosition(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::ClmmIncreaseLiquidity(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::ClmmDecreaseLiquidity(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::ClmmCollectPersonalFee(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::NftMint(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::ReferralEstablished(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::ClmmConfigChange(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::ClmmCollectProtocolFee(data) => (data.instruction_index, data.event_ordinal),
+            ParsedEvent::Raw(data) => (data.instruction_index, data.event_ordinal),
         }
     }
+
+    /// 设置事件在交易中的位置（由注册表按日志或指令顺序确定）
+    pub fn set_position(&mut self, instruction_index: u32, event_ordinal: u32) {
+        let (index, ordinal) = match self {
+            ParsedEvent::TokenCreation(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::PoolCreation(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::NftClaim(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::RewardDistribution(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::Swap(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::Launch(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::Deposit(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::LpChange(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::InitPool(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::ClmmSwap(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::ClmmCreatePosition(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::ClmmIncreaseLiquidity(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::ClmmDecreaseLiquidity(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::ClmmCollectPersonalFee(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::NftMint(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::ReferralEstablished(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::ClmmConfigChange(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::ClmmCollectProtocolFee(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+            ParsedEvent::Raw(data) => (&mut data.instruction_index, &mut data.event_ordinal),
+        };
+        *index = instruction_index;
+        *ordinal = event_ordinal;
+    }
+
+    /// 获取事件的出块时间（只有按出块时间统计的交换和仓位事件携带）
+    pub fn block_time(&self) -> Option<i64> {
+        match self {
+            ParsedEvent::Swap(data) => data.block_time,
+            ParsedEvent::ClmmSwap(data) => data.block_time,
+            ParsedEvent::ClmmCreatePosition(data) => data.block_time,
+            ParsedEvent::ClmmIncreaseLiquidity(data) => data.block_time,
+            ParsedEvent::ClmmDecreaseLiquidity(data) => data.block_time,
+            ParsedEvent::ClmmCollectPersonalFee(data) => data.block_time,
+            _ => None,
+        }
+    }
+
+    /// 事件是否还缺少出块时间
+    pub fn needs_block_time(&self) -> bool {
+        matches!(
+            self,
+            ParsedEvent::Swap(_)
+                | ParsedEvent::ClmmSwap(_)
+                | ParsedEvent::ClmmCreatePosition(_)
+                | ParsedEvent::ClmmIncreaseLiquidity(_)
+                | ParsedEvent::ClmmDecreaseLiquidity(_)
+                | ParsedEvent::ClmmCollectPersonalFee(_)
+        ) && self.block_time().is_none()
+    }
+
+    /// 设置事件的出块时间（不携带出块时间的事件类型忽略）
+    pub fn set_block_time(&mut self, block_time: i64) {
+        let field = match self {
+            ParsedEvent::Swap(data) => &mut data.block_time,
+            ParsedEvent::ClmmSwap(data) => &mut data.block_time,
+            ParsedEvent::ClmmCreatePosition(data) => &mut data.block_time,
+            ParsedEvent::ClmmIncreaseLiquidity(data) => &mut data.block_time,
+            ParsedEvent::ClmmDecreaseLiquidity(data) => &mut data.block_time,
+            ParsedEvent::ClmmCollectPersonalFee(data) => &mut data.block_time,
+            _ => return,
+        };
+        *field = Some(block_time);
+    }
+
+    /// 获取事件的唯一标识符（签名 + 指令索引 + 事件序号，用于去重）
+    ///
+    /// 同一交易中的多个事件（如多次存款、多跳交换）各自拥有独立标识
+    pub fn get_unique_id(&self) -> String {
+        let (instruction_index, event_ordinal) = self.position();
+        format!("{}_{}_{}", self.signature(), instruction_index, event_ordinal)
+    }
 }
 
 /// 事件解析器接口
@@ -180,8 +429,10 @@ pub trait EventParser: Send + Sync {
 pub struct EventParserRegistry {
     /// 使用复合键映射的解析器表
     parsers: HashMap<ParserKey, Box<dyn EventParser>>,
-    /// 回填服务配置的ParserKey集合（program_id + discriminator）
-    backfill_parser_keys: HashSet<ParserKey>,
+    /// 回填服务配置的ParserKey集合（program_id + discriminator），运行时配置变化时更新
+    backfill_parser_keys: RwLock<HashSet<ParserKey>>,
+    /// 运行时禁用的事件类型，对应解析器不再产出事件
+    disabled_event_types: RwLock<HashSet<String>>,
 }
 
 impl EventParserRegistry {
@@ -206,7 +457,8 @@ impl EventParserRegistry {
     ) -> Result<Self> {
         let mut registry = Self {
             parsers: HashMap::new(),
-            backfill_parser_keys: backfill_parser_keys.unwrap_or_default(),
+            backfill_parser_keys: RwLock::new(backfill_parser_keys.unwrap_or_default()),
+            disabled_event_types: RwLock::new(HashSet::new()),
         };
 
         // 交换事件解析器
@@ -230,6 +482,20 @@ impl EventParserRegistry {
         )?);
         registry.register_program_parser(nft_claim_parser)?;
 
+        // NFT铸造事件解析器
+        let nft_mint_parser = Box::new(NftMintParser::new(
+            config,
+            pubkey!("RefhMEwmB38AWzjySFcGiSYtRxrK6qy9DVpFJRTX9Ku"),
+        )?);
+        registry.register_program_parser(nft_mint_parser)?;
+
+        // 推荐关系建立事件解析器
+        let referral_established_parser = Box::new(ReferralEstablishedParser::new(
+            config,
+            pubkey!("RefhMEwmB38AWzjySFcGiSYtRxrK6qy9DVpFJRTX9Ku"),
+        )?);
+        registry.register_program_parser(referral_established_parser)?;
+
         // 奖励分发事件解析器
         let mut reward_distribution_parser = Box::new(RewardDistributionParser::new(
             config,
@@ -287,9 +553,81 @@ impl EventParserRegistry {
         )?);
         registry.register_program_parser(init_pool_parser)?;
 
+        // CLMM交换事件解析器 - 与CPMM的SwapEvent同名，按CLMM程序ID区分
+        let clmm_swap_parser = Box::new(ClmmSwapParser::new(
+            config,
+            pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX"),
+        )?);
+        registry.register_program_parser(clmm_swap_parser)?;
+
+        // CLMM仓位生命周期事件解析器 - 每种仓位事件一个实例
+        for kind in [
+            ClmmPositionEventKind::CreatePosition,
+            ClmmPositionEventKind::IncreaseLiquidity,
+            ClmmPositionEventKind::DecreaseLiquidity,
+            ClmmPositionEventKind::CollectPersonalFee,
+        ] {
+            let clmm_position_parser = Box::new(ClmmPositionParser::new(
+                config,
+                pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX"),
+                kind,
+            )?);
+            registry.register_program_parser(clmm_position_parser)?;
+        }
+
+        // CLMM配置变更事件解析器 - create_amm_config/update_amm_config
+        let clmm_config_change_parser = Box::new(ClmmConfigChangeParser::new(
+            config,
+            pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX"),
+        )?);
+        registry.register_program_parser(clmm_config_change_parser)?;
+
+        // CLMM协议费领取事件解析器 - collect_protocol_fee/collect_fund_fee
+        let clmm_protocol_fee_parser = Box::new(ClmmProtocolFeeParser::new(
+            config,
+            pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX"),
+        )?);
+        registry.register_program_parser(clmm_protocol_fee_parser)?;
+
+        // IDL通用事件解析器 - 放在最后注册，已有专用解析器的事件不会被覆盖
+        if let Some(idl_config) = &config.idl_events {
+            let program_ids = &config.solana.program_ids;
+            for source in &idl_config.sources {
+                if !program_ids.iter().any(|id| id.to_string() == source.program_id) {
+                    tracing::warn!("⚠️ IDL程序{}不在监听的程序列表中，其事件不会被订阅", source.program_id);
+                }
+            }
+            registry.register_idl_parsers(idl_config)?;
+        }
+
         Ok(registry)
     }
 
+    /// 注册IDL通用事件解析器，返回实际注册的数量
+    ///
+    /// 相同program_id + discriminator已有专用解析器时跳过，专用解析器优先
+    pub fn register_idl_parsers(&mut self, idl_config: &IdlEventsConfig) -> Result<usize> {
+        let mut registered = 0;
+
+        for parser in build_idl_parsers(idl_config)? {
+            let parser_key = ParserKey::for_program(parser.get_program_id(), parser.get_discriminator());
+            if self.parsers.contains_key(&parser_key) {
+                info!(
+                    "⏭️ 事件{}已有专用解析器，跳过IDL解析器 (程序: {})",
+                    parser.event_name(),
+                    parser.get_program_id()
+                );
+                continue;
+            }
+
+            self.register_program_parser(Box::new(parser))?;
+            registered += 1;
+        }
+
+        info!("📜 注册{}个IDL通用事件解析器", registered);
+        Ok(registered)
+    }
+
     /// 注册程序特定的事件解析器
     pub fn register_program_parser(&mut self, parser: Box<dyn EventParser>) -> Result<()> {
         let discriminator = parser.get_discriminator();
@@ -368,11 +706,21 @@ impl EventParserRegistry {
         let mut program_data_count = 0;
         let mut processed_count = 0;
         let mut skipped_count = 0;
+        // 事件位置：顶层指令以`invoke [1]`开始，内部调用发出的事件归属其顶层指令
+        let mut instruction_index: Option<u32> = None;
+        let mut event_ordinal = 0u32;
 
         // 处理所有程序数据日志
         for (index, log) in logs.iter().enumerate() {
+            if log.starts_with("Program ") && log.ends_with(" invoke [1]") {
+                instruction_index = Some(instruction_index.map_or(0, |i| i + 1));
+                event_ordinal = 0;
+            }
             if log.starts_with("Program data: ") {
                 program_data_count += 1;
+                // 未被解析的Program data也占用序号，保证新增解析器不改变已有事件的标识
+                let position = (instruction_index.unwrap_or(0), event_ordinal);
+                event_ordinal += 1;
                 if let Some(data_part) = log.strip_prefix("Program data: ") {
                     tracing::info!(
                         "📊 处理第{}个Program data (行{}, 数据: {})",
@@ -395,8 +743,9 @@ impl EventParserRegistry {
                         .try_parse_program_data_with_hint(data_part, signature, slot, specific_program_id, data_source)
                         .await?
                     {
-                        Some(event) => {
+                        Some(mut event) => {
                             tracing::info!("✅ 第{}个事件解析成功: {}", program_data_count, event.event_type());
+                            event.set_position(position.0, position.1);
                             processed_count += 1;
                             // 收集所有有效事件，不跳过任何一个
                             all_valid_events.push(event);
@@ -444,6 +793,29 @@ impl EventParserRegistry {
         Ok(all_valid_events)
     }
 
+    /// 解析从交易指令中提取的事件数据（日志被截断时的回退路径）
+    ///
+    /// 事件数据与`Program data:`日志格式一致，复用同一套解析器和数据源过滤规则
+    pub async fn parse_instruction_events(
+        &self,
+        events: &[InstructionEventData],
+        signature: &str,
+        slot: u64,
+        data_source: Option<EventDataSource>,
+    ) -> Result<Vec<ParsedEvent>> {
+        let mut parsed_events = Vec::new();
+        for event in events {
+            if let Some(mut parsed) = self
+                .try_parse_program_data_with_hint(&event.data, signature, slot, Some(event.program_id), data_source)
+                .await?
+            {
+                parsed.set_position(event.instruction_index, event.event_ordinal);
+                parsed_events.push(parsed);
+            }
+        }
+        Ok(parsed_events)
+    }
+
     /// 从日志中提取程序ID（解析用）
     /// 新策略：查找包含Program data的程序调用块，并验证是否在允许的程序列表中
     /// 注意：这个方法只返回第一个找到的程序ID，用于兼容性
@@ -537,21 +909,34 @@ impl EventParserRegistry {
     }
 
     /// 设置回填服务配置的ParserKey集合
-    pub fn set_backfill_parser_keys(&mut self, parser_keys: HashSet<ParserKey>) {
-        self.backfill_parser_keys = parser_keys;
-        tracing::info!("🔑 设置回填ParserKey集合: {} 个键", self.backfill_parser_keys.len());
-        for key in &self.backfill_parser_keys {
+    pub fn set_backfill_parser_keys(&self, parser_keys: HashSet<ParserKey>) {
+        tracing::info!("🔑 设置回填ParserKey集合: {} 个键", parser_keys.len());
+        for key in &parser_keys {
             tracing::info!(
                 "  - Program: {}, Discriminator: {:?}",
                 key.program_id,
                 key.discriminator
             );
         }
+        *self.backfill_parser_keys.write().unwrap() = parser_keys;
     }
 
     /// 获取回填服务配置的ParserKey集合
-    pub fn get_backfill_parser_keys(&self) -> &HashSet<ParserKey> {
-        &self.backfill_parser_keys
+    pub fn get_backfill_parser_keys(&self) -> HashSet<ParserKey> {
+        self.backfill_parser_keys.read().unwrap().clone()
+    }
+
+    /// 设置运行时禁用的事件类型
+    pub fn set_disabled_event_types(&self, event_types: HashSet<String>) {
+        if !event_types.is_empty() {
+            tracing::info!("⏸️ 禁用事件类型: {:?}", event_types);
+        }
+        *self.disabled_event_types.write().unwrap() = event_types;
+    }
+
+    /// 检查事件类型是否启用
+    pub fn is_event_type_enabled(&self, event_type: &str) -> bool {
+        !self.disabled_event_types.read().unwrap().contains(event_type)
     }
 
     /// 检查程序ID是否为系统程序（辅助验证用）
@@ -684,7 +1069,7 @@ impl EventParserRegistry {
             let allowed_by_data_source = match data_source {
                 Some(EventDataSource::BackfillService) => {
                     // 回填服务使用配置的ParserKey集合进行精确过滤
-                    let backfill_keys = self.get_backfill_parser_keys();
+                    let backfill_keys = self.backfill_parser_keys.read().unwrap();
                     let allowed = backfill_keys.contains(&parser_key)
                         || backfill_keys
                             .iter()
@@ -720,6 +1105,10 @@ impl EventParserRegistry {
         }
         // 使用智能解析器查找
         if let Some(parser) = self.find_best_parser(discriminator, program_id_hint) {
+            if !self.is_event_type_enabled(parser.get_event_type()) {
+                tracing::info!("⏸️ 事件类型已禁用，跳过: {} - {}", parser.get_event_type(), signature);
+                return Ok(None);
+            }
             tracing::info!(
                 "🔍 找到匹配的解析器: {} {} ({:?})",
                 parser.get_program_id(),
@@ -912,11 +1301,15 @@ mod tests {
             created_at: 1234567890,
             signature: "test_signature".to_string(),
             slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
             extensions: None,
             source: None,
         });
 
         assert_eq!(event.event_type(), "token_creation");
+        assert_eq!(event.signature(), "test_signature");
+        assert_eq!(event.slot(), 12345);
     }
 
     #[tokio::test]
@@ -928,6 +1321,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![Pubkey::new_unique()],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -949,8 +1343,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         };
 
         let registry = EventParserRegistry::new(&config).unwrap();
@@ -969,6 +1368,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![Pubkey::new_unique()],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -990,8 +1390,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         };
 
         let mut registry = EventParserRegistry::new(&config).unwrap();
@@ -1026,6 +1431,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![Pubkey::new_unique()],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -1047,14 +1453,20 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         };
 
         let registry = EventParserRegistry::new(&config).unwrap();
 
-        // 应该有9个解析器：swap、token_creation、pool_creation、nft_claim、reward_distribution、launch、deposit、lp_change、init_pool
-        assert_eq!(registry.parser_count(), 9);
+        // 应该有18个解析器：swap、token_creation、pool_creation、nft_claim、nft_mint、referral_established、reward_distribution、
+        // launch、deposit、lp_change、init_pool、clmm_swap、clmm_config_change、clmm_collect_protocol_fee，以及4个CLMM仓位事件解析器
+        assert_eq!(registry.parser_count(), 18);
 
         let parsers = registry.get_registered_parsers();
         let parser_types: Vec<String> = parsers.iter().map(|(name, _)| name.clone()).collect();
@@ -1068,8 +1480,17 @@ mod tests {
         assert!(parser_types.contains(&"deposit".to_string()));
         assert!(parser_types.contains(&"lp_change".to_string()));
         assert!(parser_types.contains(&"init_pool".to_string()));
-
-        // 注意：现在有9个解析器（新增了init_pool解析器）
+        assert!(parser_types.contains(&"clmm_swap".to_string()));
+        assert!(parser_types.contains(&"clmm_create_position".to_string()));
+        assert!(parser_types.contains(&"clmm_increase_liquidity".to_string()));
+        assert!(parser_types.contains(&"clmm_decrease_liquidity".to_string()));
+        assert!(parser_types.contains(&"clmm_collect_personal_fee".to_string()));
+        assert!(parser_types.contains(&"nft_mint".to_string()));
+        assert!(parser_types.contains(&"referral_established".to_string()));
+        assert!(parser_types.contains(&"clmm_config_change".to_string()));
+        assert!(parser_types.contains(&"clmm_collect_protocol_fee".to_string()));
+
+        // 注意：现在有18个解析器（新增了clmm_swap、CLMM仓位事件、CLMM管理事件及推荐合约事件解析器）
         println!("📊 解析器统计: 总数={}, 类型={:?}", parsers.len(), parser_types);
     }
 
@@ -1085,6 +1506,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![Pubkey::new_unique()],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -1106,11 +1528,16 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         };
 
-        let mut registry = EventParserRegistry::new(&config).unwrap();
+        let registry = EventParserRegistry::new(&config).unwrap();
 
         // 设置回填ParserKey集合（不同于WebSocket订阅的程序列表）
         let websocket_program = Pubkey::from_str("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX").unwrap();
@@ -1177,6 +1604,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![Pubkey::new_unique()],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -1198,8 +1626,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         };
 
         let registry = EventParserRegistry::new(&config).unwrap();
@@ -1251,6 +1684,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![Pubkey::new_unique()],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -1272,11 +1706,16 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         };
 
-        let mut registry = EventParserRegistry::new(&config).unwrap();
+        let registry = EventParserRegistry::new(&config).unwrap();
 
         // 设置测试用的回填ParserKey集合
         let test_program_id = Pubkey::from_str("AZxHQhxgjENmx8x9CQ8r86Eodo8Qg6H9wYiuRqbonaoH").unwrap();
@@ -1332,6 +1771,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -1353,8 +1793,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         };
 
         // 模拟回填服务的ParserKey集合
@@ -1426,6 +1871,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -1447,8 +1893,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         };
 
         let registry = EventParserRegistry::new(&config).unwrap();
@@ -1498,6 +1949,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -1519,8 +1971,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         };
 
         let default_program_id = config.get_cpmm_program_id().unwrap();
@@ -1555,6 +2012,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![Pubkey::new_unique()],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -1576,8 +2034,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         };
 
         let registry = EventParserRegistry::new(&config).unwrap();
@@ -1619,4 +2082,186 @@ mod tests {
 
         println!("✅ 多Program data程序ID提取测试通过");
     }
+
+    #[test]
+    fn test_register_idl_parsers_keeps_typed_parsers() {
+        let clmm_program = pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX");
+        let idl_path = std::env::temp_dir().join(format!("registry-idl-test-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &idl_path,
+            r#"{
+                "version": "0.1.0",
+                "name": "clmm",
+                "instructions": [],
+                "events": [
+                    {"name": "CollectProtocolFeeEvent", "fields": [
+                        {"name": "pool_state", "type": "publicKey", "index": false},
+                        {"name": "amount_0", "type": "u64", "index": false}
+                    ]},
+                    {"name": "CustomEvent", "fields": [
+                        {"name": "value", "type": "u64", "index": false}
+                    ]}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let config = crate::config::EventListenerConfig {
+            solana: crate::config::settings::SolanaConfig {
+                rpc_url: "https://api.devnet.solana.com".to_string(),
+                ws_url: "wss://api.devnet.solana.com".to_string(),
+                commitment: "confirmed".to_string(),
+                program_ids: vec![clmm_program],
+                private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
+            },
+            database: crate::config::settings::DatabaseConfig {
+                uri: "mongodb://localhost:27017".to_string(),
+                database_name: "test".to_string(),
+                max_connections: 10,
+                min_connections: 2,
+            },
+            listener: crate::config::settings::ListenerConfig {
+                batch_size: 100,
+                sync_interval_secs: 30,
+                max_retries: 3,
+                retry_delay_ms: 1000,
+                signature_cache_size: 10000,
+                checkpoint_save_interval_secs: 60,
+                backoff: crate::config::settings::BackoffConfig::default(),
+                batch_write: crate::config::settings::BatchWriteConfig::default(),
+            },
+            monitoring: crate::config::settings::MonitoringConfig {
+                metrics_interval_secs: 60,
+                enable_performance_monitoring: true,
+                health_check_interval_secs: 30,
+                http_bind_addr: None,
+            },
+            backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
+        };
+        let idl_config = IdlEventsConfig {
+            sources: vec![crate::config::settings::IdlSourceConfig {
+                program_id: clmm_program.to_string(),
+                idl_path: idl_path.to_string_lossy().to_string(),
+                events: vec![],
+            }],
+        };
+
+        let mut registry = EventParserRegistry::new(&config).unwrap();
+        let typed_count = registry.parser_count();
+
+        // CollectProtocolFeeEvent已有专用解析器，只注册CustomEvent
+        assert_eq!(registry.register_idl_parsers(&idl_config).unwrap(), 1);
+        assert_eq!(registry.parser_count(), typed_count + 1);
+
+        let protocol_fee_key =
+            ParserKey::for_program(clmm_program, calculate_event_discriminator("CollectProtocolFeeEvent"));
+        assert_eq!(
+            registry.parsers.get(&protocol_fee_key).unwrap().get_event_type(),
+            "clmm_collect_protocol_fee"
+        );
+        let custom_key = ParserKey::for_program(clmm_program, calculate_event_discriminator("CustomEvent"));
+        assert_eq!(registry.parsers.get(&custom_key).unwrap().get_event_type(), "raw_event");
+
+        let _ = std::fs::remove_file(&idl_path);
+    }
+
+    /// 创建只注册了IDL事件CustomEvent的注册表，返回注册表和IDL文件路径
+    fn create_custom_event_registry(program_id: Pubkey) -> (EventParserRegistry, std::path::PathBuf) {
+        let idl_path = std::env::temp_dir().join(format!("custom-idl-test-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &idl_path,
+            r#"{"version": "0.1.0", "name": "test", "instructions": [],
+                "events": [{"name": "CustomEvent", "fields": [{"name": "value", "type": "u64", "index": false}]}]}"#,
+        )
+        .unwrap();
+
+        let mut registry = EventParserRegistry {
+            parsers: HashMap::new(),
+            backfill_parser_keys: RwLock::new(HashSet::new()),
+            disabled_event_types: RwLock::new(HashSet::new()),
+        };
+        registry
+            .register_idl_parsers(&IdlEventsConfig {
+                sources: vec![crate::config::settings::IdlSourceConfig {
+                    program_id: program_id.to_string(),
+                    idl_path: idl_path.to_string_lossy().to_string(),
+                    events: vec![],
+                }],
+            })
+            .unwrap();
+
+        (registry, idl_path)
+    }
+
+    fn custom_event_program_data(value: u64) -> String {
+        use base64::{engine::general_purpose, Engine as _};
+
+        let mut data = calculate_event_discriminator("CustomEvent").to_vec();
+        data.extend_from_slice(&value.to_le_bytes());
+        format!("Program data: {}", general_purpose::STANDARD.encode(data))
+    }
+
+    #[tokio::test]
+    async fn test_parse_all_events_assigns_positions() {
+        let program_id = Pubkey::new_unique();
+        let (registry, idl_path) = create_custom_event_registry(program_id);
+
+        // 第一条指令发出两个同类事件，第二条指令发出一个
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            custom_event_program_data(1),
+            custom_event_program_data(2),
+            format!("Program {} success", program_id),
+            format!("Program {} invoke [1]", program_id),
+            custom_event_program_data(3),
+            format!("Program {} success", program_id),
+        ];
+
+        let events = registry
+            .parse_all_events_with_context(&logs, "multi_event_sig", 12345, &[program_id], None)
+            .await
+            .unwrap();
+        let positions: Vec<(u32, u32)> = events.iter().map(|event| event.position()).collect();
+        assert_eq!(positions, vec![(0, 0), (0, 1), (1, 0)]);
+
+        let unique_ids: HashSet<String> = events.iter().map(|event| event.get_unique_id()).collect();
+        assert_eq!(unique_ids.len(), 3);
+        assert!(unique_ids.contains("multi_event_sig_0_1"));
+
+        let _ = std::fs::remove_file(&idl_path);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_event_types_are_skipped() {
+        let program_id = Pubkey::new_unique();
+        let (registry, idl_path) = create_custom_event_registry(program_id);
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            custom_event_program_data(1),
+            format!("Program {} success", program_id),
+        ];
+
+        registry.set_disabled_event_types(HashSet::from(["raw_event".to_string()]));
+        assert!(!registry.is_event_type_enabled("raw_event"));
+        let events = registry
+            .parse_all_events_with_context(&logs, "disabled_sig", 12345, &[program_id], None)
+            .await
+            .unwrap();
+        assert!(events.is_empty());
+
+        // 重新启用后恢复解析
+        registry.set_disabled_event_types(HashSet::new());
+        let events = registry
+            .parse_all_events_with_context(&logs, "disabled_sig", 12345, &[program_id], None)
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1);
+
+        let _ = std::fs::remove_file(&idl_path);
+    }
 }
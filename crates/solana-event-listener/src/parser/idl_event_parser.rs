@@ -0,0 +1,867 @@
+use crate::{
+    config::settings::IdlEventsConfig,
+    error::{EventListenerError, Result},
+    parser::{event_parser::calculate_event_discriminator, EventParser, ParsedEvent},
+};
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use solana_sdk::pubkey::Pubkey;
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+use tracing::{debug, info, warn};
+
+/// 类型解码的最大嵌套深度（防止IDL中的递归类型导致栈溢出）
+const MAX_DECODE_DEPTH: usize = 32;
+
+/// 按IDL解析的原始事件数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawEventData {
+    /// 发出事件的程序ID
+    pub program_id: String,
+    /// IDL中的事件名称
+    pub event_name: String,
+    /// 事件discriminator（十六进制）
+    pub discriminator: String,
+    /// 事件原始数据（含discriminator）的SHA-256（十六进制）
+    pub data_hash: String,
+    /// 按IDL解码后的事件字段
+    pub data: Value,
+    /// 交易签名
+    pub signature: String,
+    /// 区块高度
+    pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
+    /// 处理时间
+    pub processed_at: String,
+}
+
+/// IDL字段类型
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdlType {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    F32,
+    U64,
+    I64,
+    F64,
+    U128,
+    I128,
+    String,
+    Bytes,
+    Pubkey,
+    Option(Box<IdlType>),
+    COption(Box<IdlType>),
+    Vec(Box<IdlType>),
+    Array(Box<IdlType>, usize),
+    Defined(String),
+}
+
+impl IdlType {
+    /// 从IDL JSON解析类型（兼容旧版IDL与Anchor 0.30+格式）
+    fn from_json(value: &Value) -> Result<Self> {
+        match value {
+            Value::String(name) => Self::primitive(name).ok_or_else(|| unsupported_type(value)),
+            Value::Object(map) => {
+                if let Some(inner) = map.get("option") {
+                    return Ok(IdlType::Option(Box::new(Self::from_json(inner)?)));
+                }
+                if let Some(inner) = map.get("coption") {
+                    return Ok(IdlType::COption(Box::new(Self::from_json(inner)?)));
+                }
+                if let Some(inner) = map.get("vec") {
+                    return Ok(IdlType::Vec(Box::new(Self::from_json(inner)?)));
+                }
+                if let Some(array) = map.get("array") {
+                    let (inner, len) = match array.as_array().map(|items| items.as_slice()) {
+                        Some([inner, len]) => (inner, len),
+                        _ => return Err(unsupported_type(value)),
+                    };
+                    let len = len.as_u64().ok_or_else(|| unsupported_type(value))?;
+                    return Ok(IdlType::Array(Box::new(Self::from_json(inner)?), len as usize));
+                }
+                if let Some(defined) = map.get("defined") {
+                    // 旧版IDL为字符串，0.30+为 {"name": "..."}
+                    let name = defined
+                        .as_str()
+                        .or_else(|| defined.get("name").and_then(Value::as_str))
+                        .ok_or_else(|| unsupported_type(value))?;
+                    return Ok(IdlType::Defined(name.to_string()));
+                }
+                Err(unsupported_type(value))
+            }
+            _ => Err(unsupported_type(value)),
+        }
+    }
+
+    fn primitive(name: &str) -> Option<Self> {
+        let ty = match name {
+            "bool" => IdlType::Bool,
+            "u8" => IdlType::U8,
+            "i8" => IdlType::I8,
+            "u16" => IdlType::U16,
+            "i16" => IdlType::I16,
+            "u32" => IdlType::U32,
+            "i32" => IdlType::I32,
+            "f32" => IdlType::F32,
+            "u64" => IdlType::U64,
+            "i64" => IdlType::I64,
+            "f64" => IdlType::F64,
+            "u128" => IdlType::U128,
+            "i128" => IdlType::I128,
+            "string" => IdlType::String,
+            "bytes" => IdlType::Bytes,
+            "publicKey" | "pubkey" => IdlType::Pubkey,
+            _ => return None,
+        };
+        Some(ty)
+    }
+}
+
+fn unsupported_type(value: &Value) -> EventListenerError {
+    EventListenerError::Config(format!("不支持的IDL类型: {}", value))
+}
+
+/// 具名字段
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdlField {
+    pub name: String,
+    pub ty: IdlType,
+}
+
+/// 结构体或枚举变体的字段
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdlFields {
+    Unit,
+    Named(Vec<IdlField>),
+    Tuple(Vec<IdlType>),
+}
+
+impl IdlFields {
+    fn from_json(value: Option<&Value>) -> Result<Self> {
+        let items = match value.and_then(Value::as_array) {
+            Some(items) if !items.is_empty() => items,
+            _ => return Ok(IdlFields::Unit),
+        };
+
+        // 具名字段为 {"name", "type"}，元组字段直接是类型
+        if items[0].get("name").is_some() {
+            let fields = items
+                .iter()
+                .map(|item| {
+                    let name = item
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .ok_or_else(|| EventListenerError::Config(format!("IDL字段缺少名称: {}", item)))?;
+                    let ty = item
+                        .get("type")
+                        .ok_or_else(|| EventListenerError::Config(format!("IDL字段缺少类型: {}", item)))?;
+                    Ok(IdlField {
+                        name: name.to_string(),
+                        ty: IdlType::from_json(ty)?,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(IdlFields::Named(fields))
+        } else {
+            let types = items.iter().map(IdlType::from_json).collect::<Result<Vec<_>>>()?;
+            Ok(IdlFields::Tuple(types))
+        }
+    }
+}
+
+/// IDL自定义类型定义
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdlTypeDef {
+    Struct(IdlFields),
+    Enum(Vec<(String, IdlFields)>),
+    Alias(IdlType),
+}
+
+impl IdlTypeDef {
+    fn from_json(value: &Value) -> Result<Self> {
+        let kind = value.get("kind").and_then(Value::as_str).unwrap_or_default();
+        match kind {
+            "struct" => Ok(IdlTypeDef::Struct(IdlFields::from_json(value.get("fields"))?)),
+            "enum" => {
+                let variants = value
+                    .get("variants")
+                    .and_then(Value::as_array)
+                    .map(|variants| variants.as_slice())
+                    .unwrap_or_default();
+                let variants = variants
+                    .iter()
+                    .map(|variant| {
+                        let name = variant
+                            .get("name")
+                            .and_then(Value::as_str)
+                            .ok_or_else(|| EventListenerError::Config(format!("IDL枚举变体缺少名称: {}", variant)))?;
+                        Ok((name.to_string(), IdlFields::from_json(variant.get("fields"))?))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(IdlTypeDef::Enum(variants))
+            }
+            "alias" | "type" => {
+                let ty = value
+                    .get("value")
+                    .ok_or_else(|| EventListenerError::Config(format!("IDL别名缺少类型: {}", value)))?;
+                Ok(IdlTypeDef::Alias(IdlType::from_json(ty)?))
+            }
+            _ => Err(EventListenerError::Config(format!("不支持的IDL类型定义: {}", kind))),
+        }
+    }
+}
+
+/// IDL中的事件定义
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdlEventDef {
+    pub name: String,
+    pub discriminator: [u8; 8],
+    pub fields: Vec<IdlField>,
+}
+
+/// 解析后的Anchor IDL（只保留事件解码需要的部分）
+#[derive(Debug, Clone)]
+pub struct AnchorIdl {
+    pub events: Vec<IdlEventDef>,
+    pub types: Arc<HashMap<String, IdlTypeDef>>,
+}
+
+impl AnchorIdl {
+    /// 从文件加载IDL
+    pub fn load(path: &str) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| EventListenerError::Config(format!("读取IDL文件失败: {} - {}", path, e)))?;
+        Self::from_json(&json)
+    }
+
+    /// 从JSON解析IDL
+    ///
+    /// 旧版IDL的事件直接携带fields，discriminator按`event:<Name>`计算；
+    /// Anchor 0.30+的事件携带discriminator，字段定义在同名的types条目中
+    pub fn from_json(json: &str) -> Result<Self> {
+        let idl: Value = serde_json::from_str(json)?;
+
+        let mut types = HashMap::new();
+        for type_def in idl.get("types").and_then(Value::as_array).into_iter().flatten() {
+            let name = type_def
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| EventListenerError::Config(format!("IDL类型缺少名称: {}", type_def)))?;
+            let ty = type_def.get("type").unwrap_or(type_def);
+            types.insert(name.to_string(), IdlTypeDef::from_json(ty)?);
+        }
+
+        let mut events = Vec::new();
+        for event in idl.get("events").and_then(Value::as_array).into_iter().flatten() {
+            let name = event
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| EventListenerError::Config(format!("IDL事件缺少名称: {}", event)))?;
+
+            let discriminator = match event.get("discriminator").and_then(Value::as_array) {
+                Some(bytes) => bytes
+                    .iter()
+                    .map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok()))
+                    .collect::<Option<Vec<u8>>>()
+                    .and_then(|bytes| <[u8; 8]>::try_from(bytes).ok())
+                    .ok_or_else(|| EventListenerError::Config(format!("IDL事件{}的discriminator无效", name)))?,
+                None => calculate_event_discriminator(name),
+            };
+
+            let fields = match event.get("fields") {
+                Some(fields) => IdlFields::from_json(Some(fields))?,
+                None => match types.get(name) {
+                    Some(IdlTypeDef::Struct(fields)) => fields.clone(),
+                    _ => return Err(EventListenerError::Config(format!("IDL事件{}缺少字段定义", name))),
+                },
+            };
+            let fields = match fields {
+                IdlFields::Named(fields) => fields,
+                IdlFields::Unit => Vec::new(),
+                IdlFields::Tuple(_) => {
+                    return Err(EventListenerError::Config(format!("IDL事件{}不支持元组字段", name)));
+                }
+            };
+
+            events.push(IdlEventDef {
+                name: name.to_string(),
+                discriminator,
+                fields,
+            });
+        }
+
+        Ok(Self {
+            events,
+            types: Arc::new(types),
+        })
+    }
+}
+
+/// Borsh数据读取器
+struct BorshReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> BorshReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.data.len() - self.offset
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.remaining() < len {
+            return Err(EventListenerError::EventParsing(format!(
+                "事件数据长度不足: 需要{}字节，剩余{}字节",
+                len,
+                self.remaining()
+            )));
+        }
+        let bytes = &self.data[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut buf = [0u8; N];
+        buf.copy_from_slice(self.read_bytes(N)?);
+        Ok(buf)
+    }
+
+    fn read_len(&mut self) -> Result<usize> {
+        Ok(u32::from_le_bytes(self.read_array()?) as usize)
+    }
+}
+
+/// 按IDL类型把Borsh数据解码为JSON
+struct IdlDecoder<'a> {
+    types: &'a HashMap<String, IdlTypeDef>,
+}
+
+impl IdlDecoder<'_> {
+    fn decode_value(&self, ty: &IdlType, reader: &mut BorshReader, depth: usize) -> Result<Value> {
+        if depth > MAX_DECODE_DEPTH {
+            return Err(EventListenerError::EventParsing("IDL类型嵌套过深".to_string()));
+        }
+
+        let value = match ty {
+            IdlType::Bool => match reader.read_array::<1>()?[0] {
+                0 => Value::Bool(false),
+                1 => Value::Bool(true),
+                v => return Err(EventListenerError::EventParsing(format!("无效的bool值: {}", v))),
+            },
+            IdlType::U8 => Value::from(u8::from_le_bytes(reader.read_array()?)),
+            IdlType::I8 => Value::from(i8::from_le_bytes(reader.read_array()?)),
+            IdlType::U16 => Value::from(u16::from_le_bytes(reader.read_array()?)),
+            IdlType::I16 => Value::from(i16::from_le_bytes(reader.read_array()?)),
+            IdlType::U32 => Value::from(u32::from_le_bytes(reader.read_array()?)),
+            IdlType::I32 => Value::from(i32::from_le_bytes(reader.read_array()?)),
+            IdlType::F32 => Value::from(f32::from_le_bytes(reader.read_array()?) as f64),
+            IdlType::F64 => Value::from(f64::from_le_bytes(reader.read_array()?)),
+            IdlType::I64 => Value::from(i64::from_le_bytes(reader.read_array()?)),
+            // 超出i64范围的u64和所有128位整数以字符串保存，避免存储时溢出
+            IdlType::U64 => {
+                let v = u64::from_le_bytes(reader.read_array()?);
+                if v <= i64::MAX as u64 {
+                    Value::from(v)
+                } else {
+                    Value::String(v.to_string())
+                }
+            }
+            IdlType::U128 => Value::String(u128::from_le_bytes(reader.read_array()?).to_string()),
+            IdlType::I128 => Value::String(i128::from_le_bytes(reader.read_array()?).to_string()),
+            IdlType::String => {
+                let len = reader.read_len()?;
+                let bytes = reader.read_bytes(len)?;
+                let s = std::str::from_utf8(bytes)
+                    .map_err(|e| EventListenerError::EventParsing(format!("无效的UTF-8字符串: {}", e)))?;
+                Value::String(s.to_string())
+            }
+            IdlType::Bytes => {
+                let len = reader.read_len()?;
+                Value::String(general_purpose::STANDARD.encode(reader.read_bytes(len)?))
+            }
+            IdlType::Pubkey => Value::String(Pubkey::new_from_array(reader.read_array()?).to_string()),
+            IdlType::Option(inner) => match reader.read_array::<1>()?[0] {
+                0 => Value::Null,
+                1 => self.decode_value(inner, reader, depth + 1)?,
+                tag => return Err(EventListenerError::EventParsing(format!("无效的Option标记: {}", tag))),
+            },
+            IdlType::COption(inner) => match u32::from_le_bytes(reader.read_array()?) {
+                0 => Value::Null,
+                1 => self.decode_value(inner, reader, depth + 1)?,
+                tag => return Err(EventListenerError::EventParsing(format!("无效的COption标记: {}", tag))),
+            },
+            IdlType::Vec(inner) => {
+                let len = reader.read_len()?;
+                // 每个元素至少占1字节，长度超过剩余数据说明数据与IDL不匹配
+                if len > reader.remaining() {
+                    return Err(EventListenerError::EventParsing(format!(
+                        "Vec长度{}超过剩余数据{}字节",
+                        len,
+                        reader.remaining()
+                    )));
+                }
+                self.decode_sequence(inner, len, reader, depth)?
+            }
+            IdlType::Array(inner, len) => self.decode_sequence(inner, *len, reader, depth)?,
+            IdlType::Defined(name) => self.decode_defined(name, reader, depth + 1)?,
+        };
+
+        Ok(value)
+    }
+
+    fn decode_sequence(&self, ty: &IdlType, len: usize, reader: &mut BorshReader, depth: usize) -> Result<Value> {
+        let items = (0..len)
+            .map(|_| self.decode_value(ty, reader, depth + 1))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Value::Array(items))
+    }
+
+    fn decode_named_fields(&self, fields: &[IdlField], reader: &mut BorshReader, depth: usize) -> Result<Value> {
+        let mut map = Map::new();
+        for field in fields {
+            map.insert(field.name.clone(), self.decode_value(&field.ty, reader, depth + 1)?);
+        }
+        Ok(Value::Object(map))
+    }
+
+    fn decode_fields(&self, fields: &IdlFields, reader: &mut BorshReader, depth: usize) -> Result<Value> {
+        match fields {
+            IdlFields::Unit => Ok(Value::Object(Map::new())),
+            IdlFields::Named(fields) => self.decode_named_fields(fields, reader, depth),
+            IdlFields::Tuple(types) => {
+                let items = types
+                    .iter()
+                    .map(|ty| self.decode_value(ty, reader, depth + 1))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Value::Array(items))
+            }
+        }
+    }
+
+    fn decode_defined(&self, name: &str, reader: &mut BorshReader, depth: usize) -> Result<Value> {
+        let type_def = self
+            .types
+            .get(name)
+            .ok_or_else(|| EventListenerError::EventParsing(format!("IDL中未定义类型: {}", name)))?;
+
+        match type_def {
+            IdlTypeDef::Struct(fields) => self.decode_fields(fields, reader, depth),
+            IdlTypeDef::Alias(ty) => self.decode_value(ty, reader, depth),
+            IdlTypeDef::Enum(variants) => {
+                let index = reader.read_array::<1>()?[0] as usize;
+                let (variant_name, fields) = variants
+                    .get(index)
+                    .ok_or_else(|| EventListenerError::EventParsing(format!("枚举{}的变体索引{}无效", name, index)))?;
+                // 无字段变体解码为变体名，其余解码为 {"变体名": 字段}
+                if *fields == IdlFields::Unit {
+                    return Ok(Value::String(variant_name.clone()));
+                }
+                let mut map = Map::new();
+                map.insert(variant_name.clone(), self.decode_fields(fields, reader, depth)?);
+                Ok(Value::Object(map))
+            }
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 基于Anchor IDL的通用事件解析器
+///
+/// 每个实例负责一个程序的一个事件，解码结果作为原始事件存入通用集合，
+/// 新事件只需配置IDL即可被索引，业务逻辑仍由专用解析器处理
+pub struct IdlEventParser {
+    program_id: Pubkey,
+    event: IdlEventDef,
+    types: Arc<HashMap<String, IdlTypeDef>>,
+}
+
+impl IdlEventParser {
+    pub fn new(program_id: Pubkey, event: IdlEventDef, types: Arc<HashMap<String, IdlTypeDef>>) -> Self {
+        Self {
+            program_id,
+            event,
+            types,
+        }
+    }
+
+    /// IDL中的事件名称
+    pub fn event_name(&self) -> &str {
+        &self.event.name
+    }
+
+    /// 解码事件数据（discriminator + Borsh数据）
+    pub fn decode_event_data(&self, data: &[u8]) -> Result<Value> {
+        if data.len() < 8 {
+            return Err(EventListenerError::EventParsing(
+                "数据长度不足，无法包含discriminator".to_string(),
+            ));
+        }
+        if data[0..8] != self.event.discriminator {
+            return Err(EventListenerError::DiscriminatorMismatch);
+        }
+
+        let mut reader = BorshReader::new(&data[8..]);
+        let decoder = IdlDecoder { types: &self.types };
+        let value = decoder.decode_named_fields(&self.event.fields, &mut reader, 0)?;
+        if reader.remaining() > 0 {
+            return Err(EventListenerError::EventParsing(format!(
+                "IDL与事件{}数据不匹配: 剩余{}字节未解码",
+                self.event.name,
+                reader.remaining()
+            )));
+        }
+
+        Ok(value)
+    }
+
+    /// 从程序数据解析原始事件
+    fn parse_program_data(&self, data_str: &str, signature: &str, slot: u64) -> Result<ParsedEvent> {
+        let data = general_purpose::STANDARD
+            .decode(data_str)
+            .map_err(|e| EventListenerError::EventParsing(format!("Base64解码失败: {}", e)))?;
+        let decoded = self.decode_event_data(&data)?;
+
+        Ok(ParsedEvent::Raw(RawEventData {
+            program_id: self.program_id.to_string(),
+            event_name: self.event.name.clone(),
+            discriminator: to_hex(&self.event.discriminator),
+            data_hash: to_hex(&Sha256::digest(&data)),
+            data: decoded,
+            signature: signature.to_string(),
+            slot,
+            instruction_index: 0,
+            event_ordinal: 0,
+            processed_at: chrono::Utc::now().to_rfc3339(),
+        }))
+    }
+}
+
+#[async_trait]
+impl EventParser for IdlEventParser {
+    fn get_program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    fn get_discriminator(&self) -> [u8; 8] {
+        self.event.discriminator
+    }
+
+    fn get_event_type(&self) -> &'static str {
+        "raw_event"
+    }
+
+    fn supports_program(&self, program_id: &Pubkey) -> Option<bool> {
+        Some(*program_id == self.program_id)
+    }
+
+    async fn parse_from_logs(&self, logs: &[String], signature: &str, slot: u64) -> Result<Option<ParsedEvent>> {
+        for (index, log) in logs.iter().enumerate() {
+            if let Some(data_part) = log.strip_prefix("Program data: ") {
+                match self.parse_program_data(data_part, signature, slot) {
+                    Ok(event) => {
+                        debug!("📜 第{}行按IDL解析到{}事件", index + 1, self.event.name);
+                        return Ok(Some(event));
+                    }
+                    Err(EventListenerError::DiscriminatorMismatch) => continue,
+                    Err(e) => {
+                        warn!("⚠️ 第{}行{}事件IDL解码失败: {}", index + 1, self.event.name, e);
+                        continue;
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn validate_event(&self, event: &ParsedEvent) -> Result<bool> {
+        match event {
+            ParsedEvent::Raw(data) => Ok(data.program_id == self.program_id.to_string()
+                && data.event_name == self.event.name
+                && data.data.is_object()),
+            _ => Ok(false),
+        }
+    }
+}
+
+/// 根据配置加载IDL并创建通用事件解析器
+pub fn build_idl_parsers(config: &IdlEventsConfig) -> Result<Vec<IdlEventParser>> {
+    let mut parsers = Vec::new();
+
+    for source in &config.sources {
+        let program_id = Pubkey::from_str(&source.program_id)
+            .map_err(|e| EventListenerError::Config(format!("无效的IDL程序ID {}: {}", source.program_id, e)))?;
+        let idl = AnchorIdl::load(&source.idl_path)?;
+
+        for name in &source.events {
+            if !idl.events.iter().any(|event| &event.name == name) {
+                return Err(EventListenerError::Config(format!(
+                    "IDL {} 中不存在事件 {}",
+                    source.idl_path, name
+                )));
+            }
+        }
+
+        let before = parsers.len();
+        for event in idl.events {
+            if !source.events.is_empty() && !source.events.contains(&event.name) {
+                continue;
+            }
+            parsers.push(IdlEventParser::new(program_id, event, idl.types.clone()));
+        }
+        info!(
+            "📜 从IDL {} 加载{}个事件定义 (程序: {})",
+            source.idl_path,
+            parsers.len() - before,
+            program_id
+        );
+    }
+
+    Ok(parsers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::IdlSourceConfig;
+    use borsh::BorshSerialize;
+
+    #[derive(BorshSerialize)]
+    struct TestInner {
+        level: u8,
+        active: bool,
+    }
+
+    #[derive(BorshSerialize)]
+    enum TestSide {
+        Buy,
+        Sell { amount: u64 },
+    }
+
+    #[derive(BorshSerialize)]
+    struct TestEvent {
+        owner: Pubkey,
+        amount: u64,
+        huge: u64,
+        liquidity: u128,
+        label: String,
+        maybe: Option<u8>,
+        values: Vec<u16>,
+        inner: TestInner,
+        side: TestSide,
+        last_side: TestSide,
+    }
+
+    const LEGACY_IDL: &str = r#"{
+        "version": "0.1.0",
+        "name": "test_program",
+        "instructions": [],
+        "types": [
+            {"name": "Inner", "type": {"kind": "struct", "fields": [
+                {"name": "level", "type": "u8"},
+                {"name": "active", "type": "bool"}
+            ]}},
+            {"name": "Side", "type": {"kind": "enum", "variants": [
+                {"name": "Buy"},
+                {"name": "Sell", "fields": [{"name": "amount", "type": "u64"}]}
+            ]}}
+        ],
+        "events": [
+            {"name": "TestEvent", "fields": [
+                {"name": "owner", "type": "publicKey", "index": false},
+                {"name": "amount", "type": "u64", "index": false},
+                {"name": "huge", "type": "u64", "index": false},
+                {"name": "liquidity", "type": "u128", "index": false},
+                {"name": "label", "type": "string", "index": false},
+                {"name": "maybe", "type": {"option": "u8"}, "index": false},
+                {"name": "values", "type": {"vec": "u16"}, "index": false},
+                {"name": "inner", "type": {"defined": "Inner"}, "index": false},
+                {"name": "side", "type": {"defined": "Side"}, "index": false},
+                {"name": "lastSide", "type": {"defined": "Side"}, "index": false}
+            ]}
+        ]
+    }"#;
+
+    fn encode_event(discriminator: [u8; 8], event: &TestEvent) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        event.serialize(&mut data).unwrap();
+        data
+    }
+
+    fn create_test_event(owner: Pubkey) -> TestEvent {
+        TestEvent {
+            owner,
+            amount: 1_000_000,
+            huge: u64::MAX,
+            liquidity: u128::MAX,
+            label: "hello".to_string(),
+            maybe: None,
+            values: vec![1, 2, 3],
+            inner: TestInner { level: 7, active: true },
+            side: TestSide::Buy,
+            last_side: TestSide::Sell { amount: 42 },
+        }
+    }
+
+    fn create_parser(idl_json: &str, program_id: Pubkey) -> IdlEventParser {
+        let idl = AnchorIdl::from_json(idl_json).unwrap();
+        let event = idl.events[0].clone();
+        IdlEventParser::new(program_id, event, idl.types.clone())
+    }
+
+    #[test]
+    fn test_legacy_idl_decoding() {
+        let owner = Pubkey::new_unique();
+        let parser = create_parser(LEGACY_IDL, Pubkey::new_unique());
+        assert_eq!(parser.get_discriminator(), calculate_event_discriminator("TestEvent"));
+
+        let data = encode_event(parser.get_discriminator(), &create_test_event(owner));
+        let decoded = parser.decode_event_data(&data).unwrap();
+
+        assert_eq!(decoded["owner"], owner.to_string());
+        assert_eq!(decoded["amount"], 1_000_000);
+        assert_eq!(decoded["huge"], u64::MAX.to_string());
+        assert_eq!(decoded["liquidity"], u128::MAX.to_string());
+        assert_eq!(decoded["label"], "hello");
+        assert!(decoded["maybe"].is_null());
+        assert_eq!(decoded["values"], serde_json::json!([1, 2, 3]));
+        assert_eq!(decoded["inner"], serde_json::json!({"level": 7, "active": true}));
+        assert_eq!(decoded["side"], "Buy");
+        assert_eq!(decoded["lastSide"], serde_json::json!({"Sell": {"amount": 42}}));
+    }
+
+    #[test]
+    fn test_new_format_idl_decoding() {
+        let idl_json = r#"{
+            "address": "11111111111111111111111111111111",
+            "metadata": {"name": "test_program", "version": "0.1.0", "spec": "0.1.0"},
+            "instructions": [],
+            "events": [{"name": "PriceUpdated", "discriminator": [1, 2, 3, 4, 5, 6, 7, 8]}],
+            "types": [
+                {"name": "PriceUpdated", "type": {"kind": "struct", "fields": [
+                    {"name": "pool", "type": "pubkey"},
+                    {"name": "prices", "type": {"array": ["u32", 2]}},
+                    {"name": "owner", "type": {"coption": "pubkey"}},
+                    {"name": "pair", "type": {"defined": {"name": "Pair"}}}
+                ]}},
+                {"name": "Pair", "type": {"kind": "struct", "fields": ["u8", "i64"]}}
+            ]
+        }"#;
+        let parser = create_parser(idl_json, Pubkey::new_unique());
+        assert_eq!(parser.get_discriminator(), [1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let pool = Pubkey::new_unique();
+        let mut data = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        data.extend_from_slice(pool.as_ref());
+        data.extend_from_slice(&10u32.to_le_bytes());
+        data.extend_from_slice(&20u32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.push(3);
+        data.extend_from_slice(&(-5i64).to_le_bytes());
+
+        let decoded = parser.decode_event_data(&data).unwrap();
+        assert_eq!(decoded["pool"], pool.to_string());
+        assert_eq!(decoded["prices"], serde_json::json!([10, 20]));
+        assert!(decoded["owner"].is_null());
+        assert_eq!(decoded["pair"], serde_json::json!([3, -5]));
+    }
+
+    #[test]
+    fn test_decode_rejects_mismatched_data() {
+        let parser = create_parser(LEGACY_IDL, Pubkey::new_unique());
+        let mut data = encode_event(parser.get_discriminator(), &create_test_event(Pubkey::new_unique()));
+
+        // 多余字节说明IDL与数据不匹配
+        data.push(0);
+        assert!(matches!(
+            parser.decode_event_data(&data),
+            Err(EventListenerError::EventParsing(_))
+        ));
+
+        // 截断数据
+        data.truncate(20);
+        assert!(parser.decode_event_data(&data).is_err());
+
+        data[0] ^= 0xff;
+        assert!(matches!(
+            parser.decode_event_data(&data),
+            Err(EventListenerError::DiscriminatorMismatch)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_parse_from_logs() {
+        let program_id = Pubkey::new_unique();
+        let parser = create_parser(LEGACY_IDL, program_id);
+        let data = encode_event(parser.get_discriminator(), &create_test_event(Pubkey::new_unique()));
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            "Program data: AAAAAAAAAAA=".to_string(),
+            format!("Program data: {}", general_purpose::STANDARD.encode(&data)),
+        ];
+
+        let event = parser.parse_from_logs(&logs, "test_signature", 12345).await.unwrap();
+        match &event {
+            Some(ParsedEvent::Raw(raw)) => {
+                assert_eq!(raw.program_id, program_id.to_string());
+                assert_eq!(raw.event_name, "TestEvent");
+                assert_eq!(raw.discriminator, to_hex(&parser.get_discriminator()));
+                assert_eq!(raw.data_hash, to_hex(&Sha256::digest(&data)));
+                assert_eq!(raw.signature, "test_signature");
+                assert_eq!(raw.slot, 12345);
+            }
+            _ => panic!("期望解析出原始事件"),
+        }
+        assert!(parser.validate_event(event.as_ref().unwrap()).await.unwrap());
+
+        let result = parser
+            .parse_from_logs(&["Program data: AAAAAAAAAAA=".to_string()], "sig", 1)
+            .await
+            .unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_build_idl_parsers_filters_events() {
+        let idl_path = std::env::temp_dir().join(format!("idl-test-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(&idl_path, LEGACY_IDL).unwrap();
+        let program_id = Pubkey::new_unique();
+
+        let mut config = IdlEventsConfig {
+            sources: vec![IdlSourceConfig {
+                program_id: program_id.to_string(),
+                idl_path: idl_path.to_string_lossy().to_string(),
+                events: vec!["TestEvent".to_string()],
+            }],
+        };
+        let parsers = build_idl_parsers(&config).unwrap();
+        assert_eq!(parsers.len(), 1);
+        assert_eq!(parsers[0].event_name(), "TestEvent");
+        assert_eq!(parsers[0].get_program_id(), program_id);
+
+        config.sources[0].events = vec!["MissingEvent".to_string()];
+        assert!(matches!(build_idl_parsers(&config), Err(EventListenerError::Config(_))));
+
+        let _ = std::fs::remove_file(&idl_path);
+    }
+}
@@ -2,7 +2,6 @@ use crate::{
     config::EventListenerConfig,
     error::{EventListenerError, Result},
     parser::{EventParser, ParsedEvent},
-    services::MigrationClient,
 };
 use async_trait::async_trait;
 use base64::{engine::general_purpose, Engine as _};
@@ -11,7 +10,6 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
-use std::sync::Arc;
 use tracing::{debug, info, warn};
 
 /// LaunchEvent的原始数据结构（与链上合约保持一致）
@@ -74,6 +72,12 @@ pub struct LaunchEventData {
     pub signature: String,
     /// 区块高度
     pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
     /// 处理时间
     pub processed_at: String,
 }
@@ -87,8 +91,6 @@ pub struct LaunchEventParser {
     target_program_id: Pubkey,
     /// RPC客户端
     rpc_client: RpcClient,
-    /// 迁移服务客户端
-    migration_client: Arc<MigrationClient>,
 }
 
 impl LaunchEventParser {
@@ -99,13 +101,6 @@ impl LaunchEventParser {
         // 创建RPC客户端
         let rpc_client = RpcClient::new(config.solana.rpc_url.clone());
 
-        // 创建迁移服务客户端
-        // 使用环境变量或配置中的后端服务URL
-        let migration_service_url =
-            std::env::var("MIGRATION_SERVICE_URL").unwrap_or_else(|_| "http://localhost:8765".to_string());
-
-        let migration_client = Arc::new(MigrationClient::new(migration_service_url));
-
         info!(
             "✅ 创建LaunchEventParser: 程序ID={}, discriminator={:?}",
             program_id, discriminator
@@ -115,7 +110,6 @@ impl LaunchEventParser {
             discriminator,
             target_program_id: program_id,
             rpc_client,
-            migration_client,
         })
     }
 
@@ -168,6 +162,8 @@ impl LaunchEventParser {
             with_metadata: event.with_metadata.unwrap_or(false),
             signature,
             slot,
+            instruction_index: 0,
+            event_ordinal: 0,
             processed_at: Utc::now().to_rfc3339(),
         };
 
@@ -287,6 +283,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![Pubkey::new_unique()],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -308,8 +305,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         }
     }
 
@@ -370,6 +372,8 @@ mod tests {
             with_metadata: true,
             signature: "test_sig".to_string(),
             slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
             processed_at: Utc::now().to_rfc3339(),
         };
 
@@ -404,6 +408,8 @@ mod tests {
             with_metadata: true,
             signature: "test_sig".to_string(),
             slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
             processed_at: Utc::now().to_rfc3339(),
         };
 
@@ -1,21 +1,36 @@
+pub mod clmm_config_change_parser;
+pub mod clmm_position_parser;
+pub mod clmm_protocol_fee_parser;
+pub mod clmm_swap_parser;
 pub mod cpmm_init_pool_parser;
 pub mod cpmm_lp_change_parser;
 pub mod deposit_event_parser;
 pub mod event_parser;
+pub mod idl_event_parser;
 pub mod launch_event_parser;
 pub mod nft_claim_parser;
+pub mod nft_mint_parser;
 pub mod pool_creation_parser;
+pub mod referral_established_parser;
 pub mod reward_distribution_parser;
 pub mod swap_parser;
 pub mod token_creation_parser;
+pub mod transaction_fallback;
 
+pub use clmm_config_change_parser::ClmmConfigChangeParser;
+pub use clmm_position_parser::{ClmmPositionEventKind, ClmmPositionParser};
+pub use clmm_protocol_fee_parser::ClmmProtocolFeeParser;
+pub use clmm_swap_parser::ClmmSwapParser;
 pub use cpmm_init_pool_parser::InitPoolParser;
 pub use cpmm_lp_change_parser::LpChangeParser;
 pub use deposit_event_parser::DepositEventParser;
 pub use event_parser::{EventDataSource, EventParser, EventParserRegistry, ParsedEvent};
+pub use idl_event_parser::{IdlEventParser, RawEventData};
 pub use launch_event_parser::LaunchEventParser;
 pub use nft_claim_parser::NftClaimParser;
+pub use nft_mint_parser::NftMintParser;
 pub use pool_creation_parser::PoolCreationParser;
+pub use referral_established_parser::ReferralEstablishedParser;
 pub use reward_distribution_parser::RewardDistributionParser;
 pub use swap_parser::SwapParser;
 pub use token_creation_parser::TokenCreationParser;
@@ -70,6 +70,12 @@ pub struct NftClaimEventData {
     pub signature: String,
     /// 区块高度
     pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
     /// 处理时间
     pub processed_at: String,
 }
@@ -242,6 +248,8 @@ impl NftClaimParser {
             claimed_at: event.timestamp,
             signature,
             slot,
+            instruction_index: 0,
+            event_ordinal: 0,
             processed_at: chrono::Utc::now().to_rfc3339(),
         })
     }
@@ -399,6 +407,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX")],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -420,8 +429,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         }
     }
 
@@ -589,6 +603,8 @@ mod tests {
             claimed_at: chrono::Utc::now().timestamp(),
             signature: "test_sig".to_string(),
             slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
             processed_at: chrono::Utc::now().to_rfc3339(),
         };
 
@@ -667,6 +683,8 @@ mod tests {
             claimed_at: chrono::Utc::now().timestamp(),
             signature: "test_sig".to_string(),
             slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
             processed_at: chrono::Utc::now().to_rfc3339(),
         });
 
@@ -0,0 +1,343 @@
+use crate::{
+    config::EventListenerConfig,
+    error::{EventListenerError, Result},
+    parser::{EventParser, ParsedEvent},
+};
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use tracing::{debug, info, warn};
+
+/// 推荐合约NFT铸造事件的原始数据结构（与referral程序mint_nft.rs保持一致）
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct MintNFTEvent {
+    pub minter: Pubkey,           // 铸造者地址
+    pub nft_mint: Pubkey,         // NFT mint 地址
+    pub amount: u64,              // 铸造数量
+    pub total_mint: u64,          // 用户累计铸造总数
+    pub remain_mint: u64,         // 剩余可claim数量
+    pub nft_pool_account: Pubkey, // NFT存放的池子账户
+    pub timestamp: i64,           // 铸造时间戳
+}
+
+/// NFT铸造事件数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NftMintEventData {
+    /// 铸造者地址
+    pub minter: String,
+    /// NFT的mint地址
+    pub nft_mint: String,
+    /// 本次铸造数量
+    pub amount: u64,
+    /// 铸造者累计铸造总数
+    pub total_mint: u64,
+    /// 剩余可被领取数量
+    pub remain_mint: u64,
+    /// NFT存放的池子账户
+    pub nft_pool_account: String,
+    /// 铸造时间戳
+    pub minted_at: i64,
+    /// 交易签名
+    pub signature: String,
+    /// 区块高度
+    pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
+    /// 处理时间
+    pub processed_at: String,
+}
+
+/// NFT铸造事件解析器
+pub struct NftMintParser {
+    /// 事件的discriminator
+    discriminator: [u8; 8],
+    /// 目标程序ID，指定此解析器处理哪个程序的事件
+    target_program_id: Pubkey,
+}
+
+impl NftMintParser {
+    /// 创建新的NFT铸造事件解析器
+    pub fn new(_config: &EventListenerConfig, program_id: Pubkey) -> Result<Self> {
+        let discriminator = crate::parser::event_parser::calculate_event_discriminator("MintNFTEvent");
+
+        Ok(Self {
+            discriminator,
+            target_program_id: program_id,
+        })
+    }
+
+    /// 从程序数据解析NFT铸造事件
+    fn parse_program_data(&self, data_str: &str) -> Result<MintNFTEvent> {
+        // Base64解码
+        let data = general_purpose::STANDARD
+            .decode(data_str)
+            .map_err(|e| EventListenerError::EventParsing(format!("Base64解码失败: {}", e)))?;
+
+        if data.len() < 8 {
+            return Err(EventListenerError::EventParsing(
+                "数据长度不足，无法包含discriminator".to_string(),
+            ));
+        }
+
+        // 验证discriminator
+        let discriminator = &data[0..8];
+        if discriminator != self.discriminator {
+            return Err(EventListenerError::DiscriminatorMismatch);
+        }
+
+        // Borsh反序列化事件数据
+        let event_data = &data[8..];
+        let event = MintNFTEvent::try_from_slice(event_data)
+            .map_err(|e| EventListenerError::EventParsing(format!("Borsh反序列化失败: {}", e)))?;
+
+        debug!(
+            "✅ 成功解析NFT铸造事件: NFT={}, 铸造者={}, 数量={}",
+            event.nft_mint, event.minter, event.amount
+        );
+        Ok(event)
+    }
+
+    /// 将原始事件转换为ParsedEvent
+    fn convert_to_parsed_event(&self, event: MintNFTEvent, signature: String, slot: u64) -> ParsedEvent {
+        ParsedEvent::NftMint(NftMintEventData {
+            minter: event.minter.to_string(),
+            nft_mint: event.nft_mint.to_string(),
+            amount: event.amount,
+            total_mint: event.total_mint,
+            remain_mint: event.remain_mint,
+            nft_pool_account: event.nft_pool_account.to_string(),
+            minted_at: event.timestamp,
+            signature,
+            slot,
+            instruction_index: 0,
+            event_ordinal: 0,
+            processed_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// 验证NFT铸造事件数据
+    fn validate_nft_mint(&self, event: &NftMintEventData) -> Result<bool> {
+        // 验证铸造者地址
+        if event.minter == Pubkey::default().to_string() {
+            warn!("❌ 无效的铸造者地址");
+            return Ok(false);
+        }
+
+        // 验证NFT地址
+        if event.nft_mint == Pubkey::default().to_string() {
+            warn!("❌ 无效的NFT地址");
+            return Ok(false);
+        }
+
+        // 合约要求铸造数量大于0
+        if event.amount == 0 {
+            warn!("❌ 铸造数量不能为0");
+            return Ok(false);
+        }
+
+        // 累计铸造总数不能小于本次铸造数量
+        if event.total_mint < event.amount {
+            warn!(
+                "❌ 累计铸造总数不能小于本次铸造数量: total={}, current={}",
+                event.total_mint, event.amount
+            );
+            return Ok(false);
+        }
+
+        // 剩余可领取数量不能超过累计铸造总数
+        if event.remain_mint > event.total_mint {
+            warn!(
+                "❌ 剩余可领取数量超过累计铸造总数: remain={}, total={}",
+                event.remain_mint, event.total_mint
+            );
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl EventParser for NftMintParser {
+    fn get_program_id(&self) -> Pubkey {
+        self.target_program_id
+    }
+
+    fn get_discriminator(&self) -> [u8; 8] {
+        self.discriminator
+    }
+
+    fn get_event_type(&self) -> &'static str {
+        "nft_mint"
+    }
+
+    fn supports_program(&self, program_id: &Pubkey) -> Option<bool> {
+        Some(*program_id == self.target_program_id)
+    }
+
+    async fn parse_from_logs(&self, logs: &[String], signature: &str, slot: u64) -> Result<Option<ParsedEvent>> {
+        for (index, log) in logs.iter().enumerate() {
+            if let Some(data_part) = log.strip_prefix("Program data: ") {
+                match self.parse_program_data(data_part) {
+                    Ok(event) => {
+                        info!(
+                            "🖼️ 第{}行发现NFT铸造事件: {} 铸造 {} 个 (累计: {} 剩余: {})",
+                            index + 1,
+                            event.minter,
+                            event.amount,
+                            event.total_mint,
+                            event.remain_mint
+                        );
+                        let parsed_event = self.convert_to_parsed_event(event, signature.to_string(), slot);
+                        return Ok(Some(parsed_event));
+                    }
+                    Err(EventListenerError::DiscriminatorMismatch) => {
+                        // Discriminator不匹配是正常情况，继续尝试下一条日志
+                        continue;
+                    }
+                    Err(e) => {
+                        warn!("⚠️ 第{}行NFT铸造事件解析失败: {}", index + 1, e);
+                        continue;
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    async fn validate_event(&self, event: &ParsedEvent) -> Result<bool> {
+        match event {
+            ParsedEvent::NftMint(mint_event) => self.validate_nft_mint(mint_event),
+            _ => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::pubkey;
+
+    fn create_test_config() -> EventListenerConfig {
+        EventListenerConfig {
+            solana: crate::config::settings::SolanaConfig {
+                rpc_url: "https://api.devnet.solana.com".to_string(),
+                ws_url: "wss://api.devnet.solana.com".to_string(),
+                commitment: "confirmed".to_string(),
+                program_ids: vec![pubkey!("RefhMEwmB38AWzjySFcGiSYtRxrK6qy9DVpFJRTX9Ku")],
+                private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
+            },
+            database: crate::config::settings::DatabaseConfig {
+                uri: "mongodb://localhost:27017".to_string(),
+                database_name: "test".to_string(),
+                max_connections: 10,
+                min_connections: 2,
+            },
+            listener: crate::config::settings::ListenerConfig {
+                batch_size: 100,
+                sync_interval_secs: 30,
+                max_retries: 3,
+                retry_delay_ms: 1000,
+                signature_cache_size: 10000,
+                checkpoint_save_interval_secs: 60,
+                backoff: crate::config::settings::BackoffConfig::default(),
+                batch_write: crate::config::settings::BatchWriteConfig::default(),
+            },
+            monitoring: crate::config::settings::MonitoringConfig {
+                metrics_interval_secs: 60,
+                enable_performance_monitoring: true,
+                health_check_interval_secs: 30,
+                http_bind_addr: None,
+            },
+            backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
+        }
+    }
+
+    fn create_test_mint_event() -> MintNFTEvent {
+        MintNFTEvent {
+            minter: Pubkey::new_unique(),
+            nft_mint: Pubkey::new_unique(),
+            amount: 5,
+            total_mint: 10,
+            remain_mint: 8,
+            nft_pool_account: Pubkey::new_unique(),
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    fn encode_program_data(event: &MintNFTEvent) -> String {
+        let mut data = crate::parser::event_parser::calculate_event_discriminator("MintNFTEvent").to_vec();
+        data.extend(borsh::to_vec(event).unwrap());
+        format!("Program data: {}", general_purpose::STANDARD.encode(data))
+    }
+
+    #[test]
+    fn test_nft_mint_parser_creation() {
+        let config = create_test_config();
+        let program_id = pubkey!("RefhMEwmB38AWzjySFcGiSYtRxrK6qy9DVpFJRTX9Ku");
+        let parser = NftMintParser::new(&config, program_id).unwrap();
+
+        assert_eq!(parser.get_event_type(), "nft_mint");
+        assert_eq!(parser.get_program_id(), program_id);
+        assert_eq!(
+            parser.get_discriminator(),
+            crate::parser::event_parser::calculate_event_discriminator("MintNFTEvent")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_nft_mint_event_from_logs() {
+        let config = create_test_config();
+        let parser = NftMintParser::new(&config, pubkey!("RefhMEwmB38AWzjySFcGiSYtRxrK6qy9DVpFJRTX9Ku")).unwrap();
+        let event = create_test_mint_event();
+
+        let logs = vec![
+            "Program RefhMEwmB38AWzjySFcGiSYtRxrK6qy9DVpFJRTX9Ku invoke [1]".to_string(),
+            encode_program_data(&event),
+        ];
+
+        match parser.parse_from_logs(&logs, "test_sig", 100).await.unwrap() {
+            Some(ParsedEvent::NftMint(data)) => {
+                assert_eq!(data.minter, event.minter.to_string());
+                assert_eq!(data.nft_mint, event.nft_mint.to_string());
+                assert_eq!(data.amount, 5);
+                assert_eq!(data.total_mint, 10);
+                assert_eq!(data.remain_mint, 8);
+                assert_eq!(data.minted_at, 1_700_000_000);
+                assert_eq!(data.signature, "test_sig");
+                assert_eq!(data.slot, 100);
+
+                let parsed = ParsedEvent::NftMint(data);
+                assert!(parser.validate_event(&parsed).await.unwrap());
+            }
+            other => panic!("期望NftMint事件，实际: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_nft_mint_rejects_inconsistent_supply() {
+        let config = create_test_config();
+        let parser = NftMintParser::new(&config, pubkey!("RefhMEwmB38AWzjySFcGiSYtRxrK6qy9DVpFJRTX9Ku")).unwrap();
+
+        let mut event = create_test_mint_event();
+        event.remain_mint = 20;
+        let parsed = parser.convert_to_parsed_event(event, "test_sig".to_string(), 100);
+        assert!(!parser.validate_event(&parsed).await.unwrap());
+
+        let mut event = create_test_mint_event();
+        event.amount = 0;
+        let parsed = parser.convert_to_parsed_event(event, "test_sig".to_string(), 100);
+        assert!(!parser.validate_event(&parsed).await.unwrap());
+    }
+}
@@ -0,0 +1,304 @@
+use crate::{
+    config::EventListenerConfig,
+    error::{EventListenerError, Result},
+    parser::{EventParser, ParsedEvent},
+};
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use tracing::{debug, info, warn};
+
+/// 推荐关系建立事件的原始数据结构（与referral程序claim_nft.rs保持一致）
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct ReferralEstablishedEvent {
+    pub user: Pubkey,     // 下级用户
+    pub upper: Pubkey,    // 上级用户
+    pub nft_mint: Pubkey, // 相关NFT mint地址
+    pub timestamp: i64,   // 建立关系时间戳
+}
+
+/// 推荐关系建立事件数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferralEstablishedEventData {
+    /// 下级用户地址
+    pub user: String,
+    /// 上级用户地址
+    pub upper: String,
+    /// 相关NFT的mint地址
+    pub nft_mint: String,
+    /// 关系建立时间戳
+    pub established_at: i64,
+    /// 交易签名
+    pub signature: String,
+    /// 区块高度
+    pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
+    /// 处理时间
+    pub processed_at: String,
+}
+
+/// 推荐关系建立事件解析器
+pub struct ReferralEstablishedParser {
+    /// 事件的discriminator
+    discriminator: [u8; 8],
+    /// 目标程序ID，指定此解析器处理哪个程序的事件
+    target_program_id: Pubkey,
+}
+
+impl ReferralEstablishedParser {
+    /// 创建新的推荐关系建立事件解析器
+    pub fn new(_config: &EventListenerConfig, program_id: Pubkey) -> Result<Self> {
+        let discriminator = crate::parser::event_parser::calculate_event_discriminator("ReferralEstablishedEvent");
+
+        Ok(Self {
+            discriminator,
+            target_program_id: program_id,
+        })
+    }
+
+    /// 从程序数据解析推荐关系建立事件
+    fn parse_program_data(&self, data_str: &str) -> Result<ReferralEstablishedEvent> {
+        // Base64解码
+        let data = general_purpose::STANDARD
+            .decode(data_str)
+            .map_err(|e| EventListenerError::EventParsing(format!("Base64解码失败: {}", e)))?;
+
+        if data.len() < 8 {
+            return Err(EventListenerError::EventParsing(
+                "数据长度不足，无法包含discriminator".to_string(),
+            ));
+        }
+
+        // 验证discriminator
+        let discriminator = &data[0..8];
+        if discriminator != self.discriminator {
+            return Err(EventListenerError::DiscriminatorMismatch);
+        }
+
+        // Borsh反序列化事件数据
+        let event_data = &data[8..];
+        let event = ReferralEstablishedEvent::try_from_slice(event_data)
+            .map_err(|e| EventListenerError::EventParsing(format!("Borsh反序列化失败: {}", e)))?;
+
+        debug!(
+            "✅ 成功解析推荐关系建立事件: 下级={}, 上级={}, NFT={}",
+            event.user, event.upper, event.nft_mint
+        );
+        Ok(event)
+    }
+
+    /// 将原始事件转换为ParsedEvent
+    fn convert_to_parsed_event(&self, event: ReferralEstablishedEvent, signature: String, slot: u64) -> ParsedEvent {
+        ParsedEvent::ReferralEstablished(ReferralEstablishedEventData {
+            user: event.user.to_string(),
+            upper: event.upper.to_string(),
+            nft_mint: event.nft_mint.to_string(),
+            established_at: event.timestamp,
+            signature,
+            slot,
+            instruction_index: 0,
+            event_ordinal: 0,
+            processed_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// 验证推荐关系建立事件数据
+    fn validate_referral_established(&self, event: &ReferralEstablishedEventData) -> Result<bool> {
+        // 验证下级用户地址
+        if event.user == Pubkey::default().to_string() {
+            warn!("❌ 无效的下级用户地址");
+            return Ok(false);
+        }
+
+        // 验证上级用户地址
+        if event.upper == Pubkey::default().to_string() {
+            warn!("❌ 无效的上级用户地址");
+            return Ok(false);
+        }
+
+        // 验证上级不能是自己
+        if event.user == event.upper {
+            warn!("❌ 上级不能是自己: {}", event.user);
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl EventParser for ReferralEstablishedParser {
+    fn get_program_id(&self) -> Pubkey {
+        self.target_program_id
+    }
+
+    fn get_discriminator(&self) -> [u8; 8] {
+        self.discriminator
+    }
+
+    fn get_event_type(&self) -> &'static str {
+        "referral_established"
+    }
+
+    fn supports_program(&self, program_id: &Pubkey) -> Option<bool> {
+        Some(*program_id == self.target_program_id)
+    }
+
+    async fn parse_from_logs(&self, logs: &[String], signature: &str, slot: u64) -> Result<Option<ParsedEvent>> {
+        for (index, log) in logs.iter().enumerate() {
+            if let Some(data_part) = log.strip_prefix("Program data: ") {
+                match self.parse_program_data(data_part) {
+                    Ok(event) => {
+                        info!(
+                            "🤝 第{}行发现推荐关系建立事件: {} 上级 {} (nft mint: {})",
+                            index + 1,
+                            event.user,
+                            event.upper,
+                            event.nft_mint
+                        );
+                        let parsed_event = self.convert_to_parsed_event(event, signature.to_string(), slot);
+                        return Ok(Some(parsed_event));
+                    }
+                    Err(EventListenerError::DiscriminatorMismatch) => {
+                        // Discriminator不匹配是正常情况，继续尝试下一条日志
+                        continue;
+                    }
+                    Err(e) => {
+                        warn!("⚠️ 第{}行推荐关系建立事件解析失败: {}", index + 1, e);
+                        continue;
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    async fn validate_event(&self, event: &ParsedEvent) -> Result<bool> {
+        match event {
+            ParsedEvent::ReferralEstablished(referral_event) => self.validate_referral_established(referral_event),
+            _ => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::pubkey;
+
+    fn create_test_config() -> EventListenerConfig {
+        EventListenerConfig {
+            solana: crate::config::settings::SolanaConfig {
+                rpc_url: "https://api.devnet.solana.com".to_string(),
+                ws_url: "wss://api.devnet.solana.com".to_string(),
+                commitment: "confirmed".to_string(),
+                program_ids: vec![pubkey!("RefhMEwmB38AWzjySFcGiSYtRxrK6qy9DVpFJRTX9Ku")],
+                private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
+            },
+            database: crate::config::settings::DatabaseConfig {
+                uri: "mongodb://localhost:27017".to_string(),
+                database_name: "test".to_string(),
+                max_connections: 10,
+                min_connections: 2,
+            },
+            listener: crate::config::settings::ListenerConfig {
+                batch_size: 100,
+                sync_interval_secs: 30,
+                max_retries: 3,
+                retry_delay_ms: 1000,
+                signature_cache_size: 10000,
+                checkpoint_save_interval_secs: 60,
+                backoff: crate::config::settings::BackoffConfig::default(),
+                batch_write: crate::config::settings::BatchWriteConfig::default(),
+            },
+            monitoring: crate::config::settings::MonitoringConfig {
+                metrics_interval_secs: 60,
+                enable_performance_monitoring: true,
+                health_check_interval_secs: 30,
+                http_bind_addr: None,
+            },
+            backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
+        }
+    }
+
+    fn encode_program_data(event: &ReferralEstablishedEvent) -> String {
+        let mut data = crate::parser::event_parser::calculate_event_discriminator("ReferralEstablishedEvent").to_vec();
+        data.extend(borsh::to_vec(event).unwrap());
+        format!("Program data: {}", general_purpose::STANDARD.encode(data))
+    }
+
+    #[test]
+    fn test_referral_established_parser_creation() {
+        let config = create_test_config();
+        let program_id = pubkey!("RefhMEwmB38AWzjySFcGiSYtRxrK6qy9DVpFJRTX9Ku");
+        let parser = ReferralEstablishedParser::new(&config, program_id).unwrap();
+
+        assert_eq!(parser.get_event_type(), "referral_established");
+        assert_eq!(parser.get_program_id(), program_id);
+        assert_eq!(
+            parser.get_discriminator(),
+            crate::parser::event_parser::calculate_event_discriminator("ReferralEstablishedEvent")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_referral_established_event_from_logs() {
+        let config = create_test_config();
+        let parser =
+            ReferralEstablishedParser::new(&config, pubkey!("RefhMEwmB38AWzjySFcGiSYtRxrK6qy9DVpFJRTX9Ku")).unwrap();
+        let event = ReferralEstablishedEvent {
+            user: Pubkey::new_unique(),
+            upper: Pubkey::new_unique(),
+            nft_mint: Pubkey::new_unique(),
+            timestamp: 1_700_000_000,
+        };
+
+        let logs = vec![
+            "Program log: Instruction: ClaimNft".to_string(),
+            encode_program_data(&event),
+        ];
+
+        match parser.parse_from_logs(&logs, "test_sig", 200).await.unwrap() {
+            Some(ParsedEvent::ReferralEstablished(data)) => {
+                assert_eq!(data.user, event.user.to_string());
+                assert_eq!(data.upper, event.upper.to_string());
+                assert_eq!(data.nft_mint, event.nft_mint.to_string());
+                assert_eq!(data.established_at, 1_700_000_000);
+                assert_eq!(data.slot, 200);
+
+                let parsed = ParsedEvent::ReferralEstablished(data);
+                assert!(parser.validate_event(&parsed).await.unwrap());
+            }
+            other => panic!("期望ReferralEstablished事件，实际: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_rejects_self_referral() {
+        let config = create_test_config();
+        let parser =
+            ReferralEstablishedParser::new(&config, pubkey!("RefhMEwmB38AWzjySFcGiSYtRxrK6qy9DVpFJRTX9Ku")).unwrap();
+        let user = Pubkey::new_unique();
+        let event = ReferralEstablishedEvent {
+            user,
+            upper: user,
+            nft_mint: Pubkey::new_unique(),
+            timestamp: 1_700_000_000,
+        };
+
+        let parsed = parser.convert_to_parsed_event(event, "test_sig".to_string(), 200);
+        assert!(!parser.validate_event(&parsed).await.unwrap());
+    }
+}
@@ -108,6 +108,12 @@ pub struct RewardDistributionEventData {
     pub signature: String,
     /// 区块高度
     pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
     /// 处理时间
     pub processed_at: String,
 }
@@ -327,6 +333,8 @@ impl RewardDistributionParser {
             distributed_at: event.timestamp, // timestamp对应distributed_at
             signature,
             slot,
+            instruction_index: 0,
+            event_ordinal: 0,
             processed_at: chrono::Utc::now().to_rfc3339(),
         })
     }
@@ -887,6 +895,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![Pubkey::from_str("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX").unwrap()],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -908,8 +917,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         }
     }
 
@@ -1081,6 +1095,8 @@ mod tests {
             distributed_at: chrono::Utc::now().timestamp(),
             signature: "test_sig".to_string(),
             slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
             processed_at: chrono::Utc::now().to_rfc3339(),
         };
 
@@ -1177,6 +1193,8 @@ mod tests {
             distributed_at: chrono::Utc::now().timestamp(),
             signature: "test_sig".to_string(),
             slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
             processed_at: chrono::Utc::now().to_rfc3339(),
         });
 
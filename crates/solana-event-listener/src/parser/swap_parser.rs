@@ -79,6 +79,15 @@ pub struct SwapEventData {
     pub signature: String,
     /// 区块高度
     pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
+    /// 出块时间（实时订阅的日志不携带，写入前按slot补齐）
+    #[serde(default)]
+    pub block_time: Option<i64>,
     /// 处理时间
     pub processed_at: String,
 }
@@ -152,6 +161,9 @@ impl SwapParser {
             creator_fee_on_input: event.creator_fee_on_input,
             signature,
             slot,
+            instruction_index: 0,
+            event_ordinal: 0,
+            block_time: None,
             processed_at: chrono::Utc::now().to_rfc3339(),
         })
     }
@@ -264,6 +276,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![Pubkey::from_str("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX").unwrap()],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -285,8 +298,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         }
     }
 
@@ -429,6 +447,9 @@ mod tests {
             creator_fee_on_input: true,
             signature: "test_sig".to_string(),
             slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
+            block_time: None,
             processed_at: chrono::Utc::now().to_rfc3339(),
         });
 
@@ -450,6 +471,8 @@ mod tests {
             created_at: 1234567890,
             signature: "test_sig".to_string(),
             slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
             extensions: None,
             source: None,
         });
@@ -68,6 +68,12 @@ pub struct TokenCreationEventData {
     pub signature: String,
     /// 区块高度
     pub slot: u64,
+    /// 事件所在的顶层指令索引
+    #[serde(default)]
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    #[serde(default)]
+    pub event_ordinal: u32,
     /// 扩展信息 (可选)
     pub extensions: Option<serde_json::Value>,
     /// 数据来源 (可选，默认为external_push)
@@ -207,6 +213,8 @@ impl TokenCreationParser {
             created_at: event.created_at,
             signature,
             slot,
+            instruction_index: 0,
+            event_ordinal: 0,
             extensions: Some(extensions),
             source: Some(DataSource::OnchainSync),
         }))
@@ -351,6 +359,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![pubkey!("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX")],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -372,8 +381,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         }
     }
 
@@ -449,6 +463,8 @@ mod tests {
             created_at: 1234567890,
             signature: "test_sig".to_string(),
             slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
             extensions: None,
             source: None,
         };
@@ -0,0 +1,343 @@
+use crate::{
+    error::{EventListenerError, Result},
+    parser::{EventDataSource, EventParserRegistry, ParsedEvent},
+    subscriber::endpoint_pool::EndpointPool,
+};
+use base64::{engine::general_purpose, Engine as _};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction,
+    UiCompiledInstruction, UiInstruction, UiMessage, UiTransactionEncoding,
+};
+use std::{collections::HashMap, str::FromStr};
+use tracing::{debug, info};
+
+/// Anchor emit_cpi事件指令标签（EVENT_IX_TAG = 0x1d9acb512ea545e4 的小端字节）
+pub const EVENT_IX_TAG_LE: [u8; 8] = 0x1d9acb512ea545e4u64.to_le_bytes();
+
+/// Solana日志超出长度限制时写入的截断标记
+const LOG_TRUNCATED_MARKER: &str = "Log truncated";
+
+/// 从交易指令中提取的事件数据
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionEventData {
+    /// 发出事件的程序ID
+    pub program_id: Pubkey,
+    /// Base64编码的事件数据（discriminator + borsh数据），与`Program data:`日志格式一致
+    pub data: String,
+    /// 事件所在的顶层指令索引
+    pub instruction_index: u32,
+    /// 事件在该指令中的序号
+    pub event_ordinal: u32,
+}
+
+/// 检查交易日志是否被截断
+pub fn is_log_truncated(logs: &[String]) -> bool {
+    logs.iter().any(|log| log.starts_with(LOG_TRUNCATED_MARKER))
+}
+
+/// 获取完整交易（JSON编码，包含内部指令）
+pub fn fetch_transaction(
+    endpoint_pool: &EndpointPool,
+    signature: &str,
+) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+    let signature_obj =
+        Signature::from_str(signature).map_err(|e| EventListenerError::SolanaRpc(format!("解析签名失败: {}", e)))?;
+    let tx_config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Json),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+
+    endpoint_pool
+        .call("get_transaction", |client| {
+            client.get_transaction_with_config(&signature_obj, tx_config)
+        })
+        .map_err(|e| EventListenerError::SolanaRpc(format!("获取交易详情失败: {}", e)))
+}
+
+/// 从交易的顶层指令和内部指令中提取目标程序通过emit_cpi发出的事件
+///
+/// 按指令执行顺序返回：每条顶层指令之后紧跟其内部指令
+pub fn extract_instruction_events(
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+    programs: &[Pubkey],
+) -> Vec<InstructionEventData> {
+    let message = match &transaction.transaction.transaction {
+        EncodedTransaction::Json(ui_transaction) => match &ui_transaction.message {
+            UiMessage::Raw(message) => message,
+            UiMessage::Parsed(_) => return Vec::new(),
+        },
+        _ => return Vec::new(),
+    };
+
+    // 账户列表：静态账户 + 地址查找表加载的可写/只读账户
+    let mut account_keys = message.account_keys.clone();
+    let mut inner_by_index: HashMap<u8, Vec<&UiCompiledInstruction>> = HashMap::new();
+    if let Some(meta) = &transaction.transaction.meta {
+        if let OptionSerializer::Some(addresses) = &meta.loaded_addresses {
+            account_keys.extend(addresses.writable.iter().cloned());
+            account_keys.extend(addresses.readonly.iter().cloned());
+        }
+        if let OptionSerializer::Some(inner_instructions) = &meta.inner_instructions {
+            for inner in inner_instructions {
+                let instructions = inner.instructions.iter().filter_map(|instruction| match instruction {
+                    UiInstruction::Compiled(compiled) => Some(compiled),
+                    UiInstruction::Parsed(_) => None,
+                });
+                inner_by_index.entry(inner.index).or_default().extend(instructions);
+            }
+        }
+    }
+
+    let mut events = Vec::new();
+    for (index, instruction) in message.instructions.iter().enumerate() {
+        let inner = inner_by_index.get(&(index as u8)).into_iter().flatten().copied();
+        let mut event_ordinal = 0u32;
+        for compiled in std::iter::once(instruction).chain(inner) {
+            let program_id = match account_keys
+                .get(compiled.program_id_index as usize)
+                .and_then(|key| Pubkey::from_str(key).ok())
+            {
+                Some(program_id) => program_id,
+                None => continue,
+            };
+            if !programs.contains(&program_id) {
+                continue;
+            }
+            if let Some(data) = decode_event_instruction(&compiled.data) {
+                events.push(InstructionEventData {
+                    program_id,
+                    data: general_purpose::STANDARD.encode(data),
+                    instruction_index: index as u32,
+                    event_ordinal,
+                });
+                event_ordinal += 1;
+            }
+        }
+    }
+
+    events
+}
+
+/// 解码emit_cpi事件指令数据，返回去掉指令标签后的事件数据
+fn decode_event_instruction(data_b58: &str) -> Option<Vec<u8>> {
+    let data = bs58::decode(data_b58).into_vec().ok()?;
+    if data.len() < 16 || data[..8] != EVENT_IX_TAG_LE {
+        return None;
+    }
+    Some(data[8..].to_vec())
+}
+
+/// 合并日志解析事件与指令解析事件
+///
+/// 日志截断只会丢失末尾的事件，日志中已解析出的同类型事件对应指令事件的前几个，
+/// 因此每种事件类型只追加超出日志数量的指令事件，返回(合并结果, 补回的事件数)
+pub fn merge_recovered_events(
+    log_events: Vec<ParsedEvent>,
+    instruction_events: Vec<ParsedEvent>,
+) -> (Vec<ParsedEvent>, u64) {
+    let mut seen_in_logs: HashMap<&'static str, usize> = HashMap::new();
+    for event in &log_events {
+        *seen_in_logs.entry(event.event_type()).or_default() += 1;
+    }
+
+    let mut merged = log_events;
+    let mut recovered = 0u64;
+    for event in instruction_events {
+        match seen_in_logs.get_mut(event.event_type()) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => {
+                merged.push(event);
+                recovered += 1;
+            }
+        }
+    }
+
+    (merged, recovered)
+}
+
+/// 日志被截断时从完整交易中补回事件，返回(合并后的事件, 补回的事件数)
+pub async fn recover_truncated_events(
+    parser_registry: &EventParserRegistry,
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+    log_events: Vec<ParsedEvent>,
+    signature: &str,
+    slot: u64,
+    programs: &[Pubkey],
+    data_source: Option<EventDataSource>,
+) -> Result<(Vec<ParsedEvent>, u64)> {
+    let instruction_data = extract_instruction_events(transaction, programs);
+    debug!("✂️ 交易{}中发现{}条事件指令", signature, instruction_data.len());
+
+    let instruction_events = parser_registry
+        .parse_instruction_events(&instruction_data, signature, slot, data_source)
+        .await?;
+    let (merged, recovered) = merge_recovered_events(log_events, instruction_events);
+
+    info!(
+        "✂️ 日志截断回退解析完成: {} -> 补回{}个事件，共{}个事件",
+        signature,
+        recovered,
+        merged.len()
+    );
+    Ok((merged, recovered))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::token_creation_parser::TokenCreationEventData;
+    use solana_sdk::{message::MessageHeader, transaction::TransactionVersion};
+    use solana_transaction_status::{
+        EncodedTransactionWithStatusMeta, UiInnerInstructions, UiLoadedAddresses, UiRawMessage, UiTransaction,
+        UiTransactionStatusMeta,
+    };
+
+    fn compiled(program_id_index: u8, data: &[u8]) -> UiCompiledInstruction {
+        UiCompiledInstruction {
+            program_id_index,
+            accounts: vec![],
+            data: bs58::encode(data).into_string(),
+            stack_height: None,
+        }
+    }
+
+    fn event_ix(payload: &[u8]) -> Vec<u8> {
+        let mut data = EVENT_IX_TAG_LE.to_vec();
+        data.extend_from_slice(payload);
+        data
+    }
+
+    fn build_transaction(
+        account_keys: Vec<String>,
+        instructions: Vec<UiCompiledInstruction>,
+        inner_instructions: Vec<UiInnerInstructions>,
+        loaded_readonly: Vec<String>,
+    ) -> EncodedConfirmedTransactionWithStatusMeta {
+        let meta: UiTransactionStatusMeta = serde_json::from_value(serde_json::json!({
+            "err": null,
+            "status": { "Ok": null },
+            "fee": 5000,
+            "preBalances": [],
+            "postBalances": [],
+        }))
+        .unwrap();
+        let meta = UiTransactionStatusMeta {
+            inner_instructions: OptionSerializer::Some(inner_instructions),
+            loaded_addresses: OptionSerializer::Some(UiLoadedAddresses {
+                writable: vec![],
+                readonly: loaded_readonly,
+            }),
+            ..meta
+        };
+
+        EncodedConfirmedTransactionWithStatusMeta {
+            slot: 100,
+            transaction: EncodedTransactionWithStatusMeta {
+                transaction: EncodedTransaction::Json(UiTransaction {
+                    signatures: vec!["test_signature".to_string()],
+                    message: UiMessage::Raw(UiRawMessage {
+                        header: MessageHeader::default(),
+                        account_keys,
+                        recent_blockhash: String::new(),
+                        instructions,
+                        address_table_lookups: None,
+                    }),
+                }),
+                meta: Some(meta),
+                version: Some(TransactionVersion::LEGACY),
+            },
+            block_time: None,
+        }
+    }
+
+    fn token_event(signature: &str) -> ParsedEvent {
+        ParsedEvent::TokenCreation(TokenCreationEventData {
+            project_config: "project_config".to_string(),
+            mint_address: "mint_address".to_string(),
+            name: "Test Token".to_string(),
+            symbol: "TEST".to_string(),
+            metadata_uri: String::new(),
+            logo_uri: String::new(),
+            decimals: 9,
+            supply: 1000000,
+            creator: "creator".to_string(),
+            has_whitelist: false,
+            whitelist_deadline: 0,
+            created_at: 1234567890,
+            signature: signature.to_string(),
+            slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
+            extensions: None,
+            source: None,
+        })
+    }
+
+    #[test]
+    fn test_is_log_truncated() {
+        let logs = vec![
+            "Program 11111111111111111111111111111111 invoke [1]".to_string(),
+            "Log truncated".to_string(),
+        ];
+        assert!(is_log_truncated(&logs));
+        assert!(!is_log_truncated(&logs[..1]));
+    }
+
+    #[test]
+    fn test_extract_instruction_events() {
+        let payer = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+        // 目标程序通过地址查找表加载，验证账户索引包含加载的地址
+        let account_keys = vec![payer.to_string(), other_program.to_string()];
+        let program_index = 2;
+
+        let transaction = build_transaction(
+            account_keys,
+            vec![compiled(program_index, &[1, 2, 3]), compiled(1, &event_ix(&[9; 8]))],
+            vec![
+                UiInnerInstructions {
+                    index: 0,
+                    instructions: vec![
+                        UiInstruction::Compiled(compiled(program_index, &event_ix(&[7; 10]))),
+                        // 不带事件标签的自调用不是事件
+                        UiInstruction::Compiled(compiled(program_index, &[4; 20])),
+                    ],
+                },
+                UiInnerInstructions {
+                    index: 1,
+                    instructions: vec![UiInstruction::Compiled(compiled(program_index, &event_ix(&[8; 12])))],
+                },
+            ],
+            vec![program.to_string()],
+        );
+
+        let events = extract_instruction_events(&transaction, &[program]);
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.program_id == program));
+        assert_eq!(events[0].data, general_purpose::STANDARD.encode([7; 10]));
+        assert_eq!(events[1].data, general_purpose::STANDARD.encode([8; 12]));
+        assert_eq!((events[0].instruction_index, events[0].event_ordinal), (0, 0));
+        assert_eq!((events[1].instruction_index, events[1].event_ordinal), (1, 0));
+
+        // 非目标程序的事件指令被忽略
+        assert!(extract_instruction_events(&transaction, &[payer]).is_empty());
+    }
+
+    #[test]
+    fn test_merge_recovered_events() {
+        let log_events = vec![token_event("sig")];
+        let instruction_events = vec![token_event("sig"), token_event("sig")];
+
+        let (merged, recovered) = merge_recovered_events(log_events, instruction_events);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(recovered, 1);
+
+        let (merged, recovered) = merge_recovered_events(vec![token_event("sig")], vec![]);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(recovered, 0);
+    }
+}
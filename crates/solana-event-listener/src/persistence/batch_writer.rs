@@ -1,11 +1,17 @@
 use crate::{
-    config::EventListenerConfig,
+    config::{settings::OverflowPolicy, EventListenerConfig},
     error::{EventListenerError, Result},
     parser::ParsedEvent,
-    persistence::EventStorage,
+    persistence::{
+        event_sink::{build_secondary_sinks, EventSink, MongoEventSink, SinkStats, SinkWorker},
+        event_storage::{BatchWriteOutcome, FailedEvent},
+        spill_file::{SpillFile, DEFAULT_SPILL_PATH},
+        EventStorage,
+    },
 };
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{hash_map::DefaultHasher, HashMap, HashSet, VecDeque},
+    hash::{Hash, Hasher},
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
@@ -13,11 +19,13 @@ use std::{
     time::{Duration, Instant},
 };
 use tokio::{
-    sync::{mpsc, Mutex, RwLock},
+    sync::{
+        mpsc::{self, error::TrySendError},
+        Mutex, RwLock,
+    },
     time::{interval, timeout},
 };
 use tracing::{debug, error, info, warn};
-use uuid::Uuid;
 
 /// 批量写入器
 ///
@@ -26,31 +34,52 @@ use uuid::Uuid;
 /// - 定期或达到阈值时批量写入数据库
 /// - 提供写入性能监控
 /// - 处理写入失败和重试
+///
+/// 提交通道和缓冲区容量均为buffer_size，数据库写入变慢时按overflow_policy
+/// 阻塞提交方、溢出到本地文件或丢弃新事件，避免内存无限增长
+///
+/// 每个批次先写入主存储（MongoDB，可关闭），成功后分发到JSONL、webhook等附加输出目标，
+/// 附加输出目标各自排队和重试，失败不影响主存储写入
+///
+/// 并发写入时每个写入任务独占一个缓冲区，事件按排序键（仓位NFT、池子、配置）路由，
+/// 同一排序键的事件始终由同一任务按提交顺序写入，失败批次也只回到该任务的缓冲区前部
 pub struct BatchWriter {
     config: Arc<EventListenerConfig>,
     event_storage: Arc<EventStorage>,
 
+    // 事件输出目标
+    primary_sink: Option<Arc<dyn EventSink>>,
+    secondary_sinks: Arc<Vec<Arc<SinkWorker>>>,
+
     // 批量写入配置
     batch_size: usize,
     max_wait_duration: Duration,
     buffer_size: usize,
+    concurrent_writers: usize,
+    overflow_policy: OverflowPolicy,
 
     // 运行状态
     is_running: Arc<AtomicBool>,
 
-    // 事件缓冲区
-    event_buffer: Arc<Mutex<VecDeque<ParsedEvent>>>,
+    // 事件缓冲区（每个写入任务一个）
+    event_buffers: Arc<Vec<Mutex<VecDeque<ParsedEvent>>>>,
 
     // 统计信息
     events_queued: Arc<AtomicU64>,
     events_written: Arc<AtomicU64>,
     events_failed: Arc<AtomicU64>,
+    events_dead_lettered: Arc<AtomicU64>,
+    events_dropped: Arc<AtomicU64>,
+    events_spilled: Arc<AtomicU64>,
     batches_written: Arc<AtomicU64>,
     last_write_time: Arc<RwLock<Option<Instant>>>,
 
-    // 事件提交通道
-    event_sender: mpsc::UnboundedSender<ParsedEvent>,
-    event_receiver: Arc<Mutex<mpsc::UnboundedReceiver<ParsedEvent>>>,
+    // 事件提交通道（有界）
+    event_sender: mpsc::Sender<ParsedEvent>,
+    event_receiver: Arc<Mutex<mpsc::Receiver<ParsedEvent>>>,
+
+    // 队列溢出文件
+    spill_file: Arc<SpillFile>,
 
     // 重试管理 (测试可见)
     #[cfg(test)]
@@ -69,12 +98,17 @@ pub struct BatchWriterStats {
     pub events_queued: u64,
     pub events_written: u64,
     pub events_failed: u64,
+    pub events_dead_lettered: u64,
+    pub events_dropped: u64,
+    pub events_spilled: u64,
+    pub spill_pending: u64,
     pub batches_written: u64,
     pub buffer_size: usize,
     #[serde(skip)]
     pub last_write_time: Option<Instant>,
     pub success_rate: f64,
     pub average_batch_size: f64,
+    pub sinks: Vec<SinkStats>,
 }
 
 impl BatchWriter {
@@ -85,14 +119,30 @@ impl BatchWriter {
 
         let batch_size = config.listener.batch_write.batch_size;
         let max_wait_duration = Duration::from_millis(config.listener.batch_write.max_wait_ms);
-        let buffer_size = config.listener.batch_write.buffer_size;
+        let buffer_size = config.listener.batch_write.buffer_size.max(1);
+        let concurrent_writers = config.listener.batch_write.concurrent_writers.max(1);
+        let overflow_policy = config.listener.batch_write.overflow_policy;
+        let spill_path = config
+            .listener
+            .batch_write
+            .spill_path
+            .clone()
+            .unwrap_or_else(|| DEFAULT_SPILL_PATH.to_string());
+
+        let primary_sink: Option<Arc<dyn EventSink>> = if config.listener.batch_write.sinks.mongo_enabled {
+            Some(Arc::new(MongoEventSink::new(Arc::clone(&event_storage))))
+        } else {
+            warn!("⚠️ MongoDB事件输出已关闭，事件只写入附加输出目标");
+            None
+        };
+        let secondary_sinks = build_secondary_sinks(&config.listener.batch_write.sinks, &event_storage)?;
 
-        let (event_sender, event_receiver) = mpsc::unbounded_channel::<ParsedEvent>();
+        let (event_sender, event_receiver) = mpsc::channel::<ParsedEvent>(buffer_size);
         let event_receiver = Arc::new(Mutex::new(event_receiver));
 
         info!(
-            "🔧 初始化批量写入器，batch_size: {}, max_wait: {:?}, buffer_size: {}",
-            batch_size, max_wait_duration, buffer_size
+            "🔧 初始化批量写入器，batch_size: {}, max_wait: {:?}, buffer_size: {}, 并发写入: {}, 溢出策略: {:?}",
+            batch_size, max_wait_duration, buffer_size, concurrent_writers, overflow_policy
         );
 
         let max_retries = config.listener.max_retries;
@@ -100,18 +150,30 @@ impl BatchWriter {
         Ok(Self {
             config,
             event_storage,
+            primary_sink,
+            secondary_sinks: Arc::new(secondary_sinks),
             batch_size,
             max_wait_duration,
             buffer_size,
+            concurrent_writers,
+            overflow_policy,
             is_running: Arc::new(AtomicBool::new(false)),
-            event_buffer: Arc::new(Mutex::new(VecDeque::with_capacity(buffer_size))),
+            event_buffers: Arc::new(
+                (0..concurrent_writers)
+                    .map(|_| Mutex::new(VecDeque::with_capacity(buffer_size)))
+                    .collect(),
+            ),
             events_queued: Arc::new(AtomicU64::new(0)),
             events_written: Arc::new(AtomicU64::new(0)),
             events_failed: Arc::new(AtomicU64::new(0)),
+            events_dead_lettered: Arc::new(AtomicU64::new(0)),
+            events_dropped: Arc::new(AtomicU64::new(0)),
+            events_spilled: Arc::new(AtomicU64::new(0)),
             batches_written: Arc::new(AtomicU64::new(0)),
             last_write_time: Arc::new(RwLock::new(None)),
             event_sender,
             event_receiver,
+            spill_file: Arc::new(SpillFile::new(spill_path)),
             retry_counts: Arc::new(Mutex::new(HashMap::new())),
             max_retries,
         })
@@ -124,6 +186,11 @@ impl BatchWriter {
             return Ok(());
         }
 
+        // 上次运行遗留的溢出事件早于本次的实时事件，重放完成前实时事件继续写入溢出文件
+        if self.spill_file.resume_backlog().await {
+            warn!("📂 发现遗留的溢出事件，重放完成前实时事件暂存到溢出文件");
+        }
+
         self.is_running.store(true, Ordering::Relaxed);
         info!("🚀 启动批量写入处理");
 
@@ -135,23 +202,50 @@ impl BatchWriter {
             })
         };
 
-        // 启动批量写入任务
-        let batch_write_task = {
+        // 启动批量写入任务（按concurrent_writers并发写入）
+        let batch_write_tasks: Vec<_> = (0..self.concurrent_writers)
+            .map(|writer_id| {
+                let writer = self.clone();
+                tokio::spawn(async move {
+                    writer.batch_write_loop(writer_id).await;
+                })
+            })
+            .collect();
+
+        // 启动溢出文件重放任务（启动时重放上次运行遗留的溢出事件）
+        let spill_replay_task = {
             let writer = self.clone();
             tokio::spawn(async move {
-                writer.batch_write_loop().await;
+                writer.spill_replay_loop().await;
             })
         };
 
+        // 启动附加输出目标写入任务
+        let sink_tasks: Vec<_> = self
+            .secondary_sinks
+            .iter()
+            .map(|worker| {
+                let worker = Arc::clone(worker);
+                let is_running = Arc::clone(&self.is_running);
+                tokio::spawn(async move {
+                    worker.run(is_running).await;
+                })
+            })
+            .collect();
+
         // 等待任务完成
         tokio::select! {
             _ = collection_task => {
                 warn!("事件收集任务完成");
             }
-            _ = batch_write_task => {
+            _ = futures::future::join_all(batch_write_tasks) => {
                 warn!("批量写入任务完成");
             }
         }
+        spill_replay_task.abort();
+        for task in sink_tasks {
+            task.abort();
+        }
 
         Ok(())
     }
@@ -161,10 +255,29 @@ impl BatchWriter {
         info!("🛑 停止批量写入器");
         self.is_running.store(false, Ordering::Relaxed);
 
-        // 刷新剩余的事件
-        self.flush().await?;
+        // 将通道中尚未收集的事件移入缓冲区
+        {
+            let mut receiver = self.event_receiver.lock().await;
+            while let Ok(event) = receiver.try_recv() {
+                self.buffer_event(event).await;
+            }
+        }
 
-        Ok(())
+        // 刷新剩余的事件，失败时溢出到本地文件以便下次启动重放
+        let flush_result = self.flush().await;
+        if flush_result.is_err() && self.overflow_policy == OverflowPolicy::Spill {
+            for buffer in self.event_buffers.iter() {
+                let remaining: Vec<ParsedEvent> = buffer.lock().await.drain(..).collect();
+                self.spill_events(remaining).await;
+            }
+        }
+
+        // 写出附加输出目标队列中剩余的批次
+        for worker in self.secondary_sinks.iter() {
+            worker.drain().await;
+        }
+
+        flush_result
     }
 
     /// 提交事件到批量写入队列
@@ -173,17 +286,13 @@ impl BatchWriter {
             return Err(EventListenerError::Persistence("批量写入器未运行".to_string()));
         }
 
-        self.event_sender
-            .send(event)
-            .map_err(|_| EventListenerError::Persistence("事件提交失败：通道已关闭".to_string()))?;
-
-        self.events_queued.fetch_add(1, Ordering::Relaxed);
-        Ok(())
+        self.enqueue_events(vec![event]).await
     }
 
     /// 批量提交多个事件到写入队列
     ///
-    /// 这个方法比多次调用 submit_event 更高效，因为它减少了通道操作的开销
+    /// 这个方法比多次调用 submit_event 更高效，因为它减少了通道操作的开销；
+    /// 队列已满时剩余事件按溢出策略整体处理（溢出文件一次写入）
     ///
     /// # 参数
     /// * `events` - 要提交的事件向量
@@ -203,18 +312,172 @@ impl BatchWriter {
         }
 
         let event_count = events.len();
+        self.enqueue_events(events).await?;
 
-        // 批量发送所有事件
-        for event in events {
-            self.event_sender
-                .send(event)
-                .map_err(|_| EventListenerError::Persistence("批量事件提交失败：通道已关闭".to_string()))?;
+        debug!("📦 批量提交{}个事件到写入队列", event_count);
+        Ok(())
+    }
+
+    /// 按溢出策略将事件放入有界队列
+    ///
+    /// 溢出积压未写完时暂停实时入队，事件追加到溢出文件尾部，保证不会超越先溢出的事件
+    async fn enqueue_events(&self, events: Vec<ParsedEvent>) -> Result<()> {
+        if self.spill_file.is_backlogged() {
+            debug!("📂 溢出积压未写完，{}个事件追加到溢出文件", events.len());
+            self.spill_events(events).await;
+            return Ok(());
         }
 
-        // 更新统计计数器
-        self.events_queued.fetch_add(event_count as u64, Ordering::Relaxed);
+        let mut events: VecDeque<ParsedEvent> = events.into();
+        let mut queued = 0u64;
+
+        while let Some(event) = events.pop_front() {
+            let result = match self.overflow_policy {
+                OverflowPolicy::Block => self
+                    .event_sender
+                    .send(event)
+                    .await
+                    .map_err(|e| TrySendError::Closed(e.0)),
+                OverflowPolicy::Spill | OverflowPolicy::Drop => self.event_sender.try_send(event),
+            };
 
-        debug!("📦 批量提交{}个事件到写入队列", event_count);
+            match result {
+                Ok(()) => queued += 1,
+                Err(TrySendError::Full(event)) => {
+                    let overflow: Vec<ParsedEvent> = std::iter::once(event).chain(events).collect();
+                    self.events_queued.fetch_add(queued, Ordering::Relaxed);
+                    self.handle_overflow(overflow).await;
+                    return Ok(());
+                }
+                Err(TrySendError::Closed(_)) => {
+                    self.events_queued.fetch_add(queued, Ordering::Relaxed);
+                    return Err(EventListenerError::Persistence("事件提交失败：通道已关闭".to_string()));
+                }
+            }
+        }
+
+        self.events_queued.fetch_add(queued, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// 处理队列已满时无法入队的事件
+    async fn handle_overflow(&self, events: Vec<ParsedEvent>) {
+        match self.overflow_policy {
+            OverflowPolicy::Spill => {
+                warn!(
+                    "📂 写入队列已满，{}个事件溢出到文件: {}",
+                    events.len(),
+                    self.spill_file.path().display()
+                );
+                self.spill_events(events).await
+            }
+            _ => {
+                self.events_dropped.fetch_add(events.len() as u64, Ordering::Relaxed);
+                warn!(
+                    "⚠️ 写入队列已满，丢弃{}个事件（累计丢弃: {}）",
+                    events.len(),
+                    self.events_dropped.load(Ordering::Relaxed)
+                );
+            }
+        }
+    }
+
+    /// 将事件写入溢出文件，写入失败时计入丢弃统计
+    async fn spill_events(&self, events: Vec<ParsedEvent>) {
+        if events.is_empty() {
+            return;
+        }
+
+        match self.spill_file.append(&events).await {
+            Ok(()) => {
+                self.events_spilled.fetch_add(events.len() as u64, Ordering::Relaxed);
+            }
+            Err(e) => {
+                self.events_dropped.fetch_add(events.len() as u64, Ordering::Relaxed);
+                error!("❌ 写入溢出文件失败，{}个事件丢失: {}", events.len(), e);
+            }
+        }
+    }
+
+    /// 溢出文件重放循环
+    ///
+    /// 启动时立即重放遗留的溢出事件；spill策略下持续运行。积压期间实时事件暂停入队，
+    /// 待队列和缓冲区中更早的事件全部写入后，按原顺序直接写入溢出事件，积压清空后恢复实时入队
+    async fn spill_replay_loop(&self) {
+        let mut check_interval = interval(Duration::from_secs(1));
+
+        while self.is_running.load(Ordering::Relaxed) {
+            check_interval.tick().await;
+
+            if !self.spill_file.is_backlogged() {
+                if self.overflow_policy != OverflowPolicy::Spill {
+                    break;
+                }
+                continue;
+            }
+
+            // 队列和缓冲区中的事件早于溢出事件，先等它们写完
+            if self.event_sender.capacity() < self.event_sender.max_capacity() || self.buffered_len().await > 0 {
+                continue;
+            }
+
+            match self.replay_spill_file().await {
+                Ok(()) => {
+                    if self.spill_file.try_finish_backlog().await {
+                        info!("📂 溢出积压已全部写入，恢复实时事件入队");
+                    }
+                }
+                Err(e) => error!("❌ 重放溢出文件失败: {}", e),
+            }
+        }
+    }
+
+    /// 将溢出文件中的事件按原顺序分批写入主存储
+    ///
+    /// 每个批次写入成功后才从重放文件中截掉，写入失败时保留剩余事件等待下一轮重放
+    async fn replay_spill_file(&self) -> Result<()> {
+        let events = self.spill_file.take_for_replay().await?;
+        let count = events.len();
+
+        let mut written = 0;
+        while written < count {
+            let end = std::cmp::min(written + self.batch_size.max(1), count);
+            self.write_replayed_batch(events[written..end].to_vec()).await?;
+            written = end;
+            self.spill_file.retain_for_replay(&events[written..]).await?;
+        }
+        self.spill_file.complete_replay().await?;
+
+        if count > 0 {
+            info!("📂 已重放{}个溢出事件", count);
+        }
+        Ok(())
+    }
+
+    /// 写入一批重放的溢出事件，单个事件的失败与实时批次一样重新排队或转入死信集合
+    async fn write_replayed_batch(&self, batch: Vec<ParsedEvent>) -> Result<()> {
+        let outcome = match &self.primary_sink {
+            Some(sink) => sink.write_batch_outcome(&batch).await?,
+            None => BatchWriteOutcome {
+                written: batch.len() as u64,
+                failed: Vec::new(),
+            },
+        };
+
+        self.events_written.fetch_add(outcome.written, Ordering::Relaxed);
+        self.batches_written.fetch_add(1, Ordering::Relaxed);
+        *self.last_write_time.write().await = Some(Instant::now());
+
+        let batch = if outcome.failed.is_empty() {
+            batch
+        } else {
+            let batch_id = batch_id_for(&batch);
+            self.handle_failed_events(&batch_id, outcome.failed, batch).await
+        };
+
+        for worker in self.secondary_sinks.iter() {
+            worker.enqueue(batch.clone());
+        }
         Ok(())
     }
 
@@ -225,20 +488,17 @@ impl BatchWriter {
         let mut receiver = self.event_receiver.lock().await;
 
         while self.is_running.load(Ordering::Relaxed) {
+            // 缓冲区已满时暂停接收，由有界通道向提交方施加背压
+            let buffer_full = self.buffered_len().await >= self.buffer_size;
+            if buffer_full {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                continue;
+            }
+
             match timeout(Duration::from_millis(100), receiver.recv()).await {
                 Ok(Some(event)) => {
-                    // 将事件添加到缓冲区
-                    {
-                        let mut buffer = self.event_buffer.lock().await;
-
-                        // 检查缓冲区容量
-                        if buffer.len() >= self.buffer_size {
-                            warn!("⚠️ 事件缓冲区已满，丢弃最旧的事件");
-                            buffer.pop_front();
-                        }
-
-                        buffer.push_back(event);
-                    }
+                    // 将事件添加到其排序键对应写入任务的缓冲区
+                    self.buffer_event(event).await;
 
                     debug!("📦 事件已添加到缓冲区");
                 }
@@ -256,9 +516,24 @@ impl BatchWriter {
         info!("📥 事件收集循环已停止");
     }
 
+    /// 将事件放入其排序键对应写入任务的缓冲区
+    async fn buffer_event(&self, event: ParsedEvent) {
+        let writer_id = writer_index(&event, self.event_buffers.len());
+        self.event_buffers[writer_id].lock().await.push_back(event);
+    }
+
+    /// 所有缓冲区中的事件总数
+    async fn buffered_len(&self) -> usize {
+        let mut total = 0;
+        for buffer in self.event_buffers.iter() {
+            total += buffer.lock().await.len();
+        }
+        total
+    }
+
     /// 批量写入循环
-    async fn batch_write_loop(&self) {
-        info!("💾 启动批量写入循环");
+    async fn batch_write_loop(&self, writer_id: usize) {
+        info!("💾 启动批量写入循环 #{}", writer_id);
         info!(
             "📊 批量配置 - batch_size: {}, max_wait: {:?}",
             self.batch_size, self.max_wait_duration
@@ -275,10 +550,7 @@ impl BatchWriter {
             write_interval.tick().await;
 
             // 检查是否需要写入
-            let buffer_size = {
-                let buffer = self.event_buffer.lock().await;
-                buffer.len()
-            };
+            let buffer_size = self.event_buffers[writer_id].lock().await.len();
 
             if buffer_size == 0 {
                 continue;
@@ -297,7 +569,7 @@ impl BatchWriter {
                     buffer_size, self.batch_size, time_since_last_write, self.max_wait_duration
                 );
 
-                if let Err(e) = self.write_batch().await {
+                if let Err(e) = self.write_batch(writer_id).await {
                     error!("❌ 批量写入失败: {}", e);
                 } else {
                     // 成功写入后重置时间
@@ -306,13 +578,13 @@ impl BatchWriter {
             }
         }
 
-        info!("💾 批量写入循环已停止");
+        info!("💾 批量写入循环 #{} 已停止", writer_id);
     }
 
-    /// 执行批量写入
-    async fn write_batch(&self) -> Result<()> {
+    /// 从指定写入任务的缓冲区取出一个批次执行写入
+    async fn write_batch(&self, writer_id: usize) -> Result<()> {
         let batch = {
-            let mut buffer = self.event_buffer.lock().await;
+            let mut buffer = self.event_buffers[writer_id].lock().await;
             if buffer.is_empty() {
                 return Ok(());
             }
@@ -337,12 +609,24 @@ impl BatchWriter {
         let batch_size = batch.len();
         info!("📦 批量写入开始 - 事件数量: {}", batch_size);
 
+        // 重新排队的批次保持原有顺序，按批次内容生成ID以跨重试累计次数
+        let batch_id = batch_id_for(&batch);
+
         let start_time = Instant::now();
 
-        // 执行批量写入
-        match self.event_storage.write_batch(&batch).await {
-            Ok(written_count) => {
+        // 执行批量写入（主存储关闭时直接分发到附加输出目标）
+        let result = match &self.primary_sink {
+            Some(sink) => sink.write_batch_outcome(&batch).await,
+            None => Ok(BatchWriteOutcome {
+                written: batch_size as u64,
+                failed: Vec::new(),
+            }),
+        };
+
+        match result {
+            Ok(outcome) => {
                 let duration = start_time.elapsed();
+                let written_count = outcome.written;
 
                 // 更新统计信息
                 self.events_written.fetch_add(written_count, Ordering::Relaxed);
@@ -352,6 +636,19 @@ impl BatchWriter {
                     *last_write = Some(Instant::now());
                 }
 
+                self.retry_counts.lock().await.remove(&batch_id);
+
+                // 写入失败的单个事件重新排队或转入死信集合，写入成功前不分发到附加输出目标
+                let batch = if outcome.failed.is_empty() {
+                    batch
+                } else {
+                    self.handle_failed_events(&batch_id, outcome.failed, batch).await
+                };
+
+                for worker in self.secondary_sinks.iter() {
+                    worker.enqueue(batch.clone());
+                }
+
                 info!(
                     "✅ 批量写入完成，写入: {}/{} 事件，耗时: {:?}",
                     written_count, batch_size, duration
@@ -363,8 +660,7 @@ impl BatchWriter {
 
                 error!("❌ 批量写入失败: {}", e);
 
-                // 生成批次ID用于重试跟踪
-                let batch_id = Uuid::new_v4().to_string();
+                let attempts = self.retry_counts.lock().await.get(&batch_id).copied().unwrap_or(0) + 1;
 
                 // 将失败的事件重新加入缓冲区（可选择性重试）
                 if self.should_retry_batch_internal(&batch, &e, &batch_id).await {
@@ -378,9 +674,10 @@ impl BatchWriter {
 
                     tokio::time::sleep(Duration::from_millis(delay)).await;
 
-                    self.requeue_batch(batch).await;
+                    self.requeue_batch(writer_id, batch).await;
                 } else {
-                    warn!("🚫 批次重试已放弃，丢弃 {} 个事件", batch.len());
+                    warn!("🚫 批次重试已放弃，{} 个事件转入死信集合", batch.len());
+                    self.dead_letter_batch(&batch, &e, &batch_id, attempts).await;
                 }
 
                 return Err(e);
@@ -390,6 +687,94 @@ impl BatchWriter {
         Ok(())
     }
 
+    /// 将放弃重试的批次写入死信集合
+    async fn dead_letter_batch(
+        &self,
+        batch: &[ParsedEvent],
+        error: &EventListenerError,
+        batch_id: &str,
+        attempts: u32,
+    ) {
+        match self
+            .event_storage
+            .write_dead_letters(batch, &error.to_string(), batch_id, attempts)
+            .await
+        {
+            Ok(count) => {
+                self.events_dead_lettered.fetch_add(count, Ordering::Relaxed);
+            }
+            Err(e) => {
+                error!(
+                    "❌ 写入死信集合失败，{} 个事件丢失: batch_id={} - {}",
+                    batch.len(),
+                    batch_id,
+                    e
+                );
+            }
+        }
+    }
+
+    /// 处理批次中写入失败的单个事件，返回其余事件
+    ///
+    /// 前置事件尚未写入（如增减流动性先于开仓到达）的事件放回缓冲区尾部，等同一排序键的后续事件写入后重试，
+    /// 重试耗尽后与其他失败事件一样逐个转入死信集合（各自记录失败原因）
+    async fn handle_failed_events(
+        &self,
+        batch_id: &str,
+        failed: Vec<FailedEvent>,
+        batch: Vec<ParsedEvent>,
+    ) -> Vec<ParsedEvent> {
+        let failed_ids: HashSet<String> = failed.iter().map(|f| f.event.get_unique_id()).collect();
+
+        for failed_event in failed {
+            let retry_key = format!("event:{}", failed_event.event.get_unique_id());
+            let attempts = {
+                let mut retry_counts = self.retry_counts.lock().await;
+                let attempts = retry_counts.get(&retry_key).copied().unwrap_or(0) + 1;
+                let retryable = matches!(failed_event.error, EventListenerError::OutOfOrder(_));
+                if retryable && attempts <= self.max_retries {
+                    retry_counts.insert(retry_key, attempts);
+                    None
+                } else {
+                    retry_counts.remove(&retry_key);
+                    Some(attempts)
+                }
+            };
+
+            match attempts {
+                None => {
+                    debug!(
+                        "🔄 事件{}等待前置事件写入，放回缓冲区尾部: {}",
+                        failed_event.event.get_unique_id(),
+                        failed_event.error
+                    );
+                    let writer_id = writer_index(&failed_event.event, self.event_buffers.len());
+                    self.event_buffers[writer_id].lock().await.push_back(failed_event.event);
+                }
+                Some(attempts) => {
+                    self.events_failed.fetch_add(1, Ordering::Relaxed);
+                    warn!(
+                        "🚫 事件{}写入失败，转入死信集合: {}",
+                        failed_event.event.get_unique_id(),
+                        failed_event.error
+                    );
+                    self.dead_letter_batch(
+                        std::slice::from_ref(&failed_event.event),
+                        &failed_event.error,
+                        batch_id,
+                        attempts,
+                    )
+                    .await;
+                }
+            }
+        }
+
+        batch
+            .into_iter()
+            .filter(|event| !failed_ids.contains(&event.get_unique_id()))
+            .collect()
+    }
+
     /// 判断是否应该重试批量写入
     async fn should_retry_batch_internal(
         &self,
@@ -405,6 +790,7 @@ impl BatchWriter {
             EventListenerError::SolanaRpc(_) => true,           // Solana RPC错误可重试
             EventListenerError::Network(_) => true,             // 网络错误可重试
             EventListenerError::Persistence(_) => true,         // 持久化错误可重试
+            EventListenerError::OutOfOrder(_) => true,          // 前置事件写入后可重试
             EventListenerError::EventParsing(_) => false,       // 解析错误不重试
             EventListenerError::DiscriminatorMismatch => false, // Discriminator不匹配不重试
             EventListenerError::Config(_) => false,             // 配置错误不重试
@@ -452,17 +838,16 @@ impl BatchWriter {
     }
 
     /// 将批量事件重新加入队列
-    async fn requeue_batch(&self, batch: Vec<ParsedEvent>) {
+    ///
+    /// 批次回到原写入任务的缓冲区，该任务退避期间不会写入同一排序键的后续事件
+    async fn requeue_batch(&self, writer_id: usize, batch: Vec<ParsedEvent>) {
         warn!("🔄 重新排队 {} 个失败的事件", batch.len());
 
-        let mut buffer = self.event_buffer.lock().await;
+        let mut buffer = self.event_buffers[writer_id].lock().await;
 
         // 将失败的事件添加到缓冲区前部（优先处理）
+        // 重新排队的事件本就来自缓冲区，不受容量限制（最多超出一个批次），避免丢失
         for event in batch.into_iter().rev() {
-            if buffer.len() >= self.buffer_size {
-                // 如果缓冲区满了，丢弃最旧的事件
-                buffer.pop_back();
-            }
             buffer.push_front(event);
         }
     }
@@ -471,55 +856,59 @@ impl BatchWriter {
     pub async fn flush(&self) -> Result<()> {
         info!("🚿 刷新批量写入缓冲区");
 
-        let mut attempts = 0;
         const MAX_ATTEMPTS: u32 = 3;
 
-        while attempts < MAX_ATTEMPTS {
-            let buffer_size = {
-                let buffer = self.event_buffer.lock().await;
-                buffer.len()
-            };
+        // 逐个缓冲区刷新，保持每个写入任务内的事件顺序
+        for writer_id in 0..self.event_buffers.len() {
+            let mut attempts = 0;
 
-            if buffer_size == 0 {
-                info!("✅ 缓冲区已清空");
-                break;
-            }
-
-            info!(
-                "💾 刷新剩余 {} 个事件 (尝试 {}/{})",
-                buffer_size,
-                attempts + 1,
-                MAX_ATTEMPTS
-            );
+            while attempts < MAX_ATTEMPTS {
+                let buffer_size = self.event_buffers[writer_id].lock().await.len();
 
-            match self.write_batch().await {
-                Ok(()) => {
-                    info!("✅ 刷新批量写入成功");
+                if buffer_size == 0 {
+                    break;
                 }
-                Err(e) => {
-                    error!("❌ 刷新批量写入失败: {}", e);
-                    attempts += 1;
 
-                    if attempts >= MAX_ATTEMPTS {
-                        return Err(e);
+                info!(
+                    "💾 刷新缓冲区 #{} 剩余 {} 个事件 (尝试 {}/{})",
+                    writer_id,
+                    buffer_size,
+                    attempts + 1,
+                    MAX_ATTEMPTS
+                );
+
+                match self.write_batch(writer_id).await {
+                    Ok(()) => {
+                        info!("✅ 刷新批量写入成功");
                     }
+                    Err(e) => {
+                        error!("❌ 刷新批量写入失败: {}", e);
+                        attempts += 1;
 
-                    // 等待一段时间再重试
-                    tokio::time::sleep(Duration::from_millis(1000)).await;
+                        if attempts >= MAX_ATTEMPTS {
+                            return Err(e);
+                        }
+
+                        // 等待一段时间再重试
+                        tokio::time::sleep(Duration::from_millis(1000)).await;
+                    }
                 }
             }
         }
 
+        info!("✅ 缓冲区已清空");
         Ok(())
     }
 
+    /// 获取事件存储（供最终确认任务回滚交易使用）
+    pub fn event_storage(&self) -> Arc<EventStorage> {
+        Arc::clone(&self.event_storage)
+    }
+
     /// 检查批量写入器是否健康
     pub async fn is_healthy(&self) -> bool {
         let is_running = self.is_running.load(Ordering::Relaxed);
-        let buffer_size = {
-            let buffer = self.event_buffer.lock().await;
-            buffer.len()
-        };
+        let buffer_size = self.buffered_len().await;
 
         // 检查是否运行正常且缓冲区未过载
         is_running && buffer_size < self.buffer_size
@@ -544,29 +933,44 @@ impl BatchWriter {
             0.0
         };
 
-        let buffer_size = {
-            let buffer = self.event_buffer.lock().await;
-            buffer.len()
-        };
+        let buffer_size = self.buffered_len().await;
 
         BatchWriterStats {
             is_running: self.is_running.load(Ordering::Relaxed),
             events_queued: self.events_queued.load(Ordering::Relaxed),
             events_written,
             events_failed,
+            events_dead_lettered: self.events_dead_lettered.load(Ordering::Relaxed),
+            events_dropped: self.events_dropped.load(Ordering::Relaxed),
+            events_spilled: self.events_spilled.load(Ordering::Relaxed),
+            spill_pending: self.spill_file.pending(),
             batches_written,
             buffer_size,
             last_write_time: *self.last_write_time.read().await,
             success_rate,
             average_batch_size,
+            sinks: self.sink_stats(),
         }
     }
 
+    /// 附加输出目标写入任务（供指标注册使用）
+    pub fn secondary_sinks(&self) -> &[Arc<SinkWorker>] {
+        &self.secondary_sinks
+    }
+
+    /// 获取附加输出目标统计信息
+    pub fn sink_stats(&self) -> Vec<SinkStats> {
+        self.secondary_sinks.iter().map(|worker| worker.stats()).collect()
+    }
+
     /// 重置统计信息
     pub async fn reset_stats(&self) {
         self.events_queued.store(0, Ordering::Relaxed);
         self.events_written.store(0, Ordering::Relaxed);
         self.events_failed.store(0, Ordering::Relaxed);
+        self.events_dead_lettered.store(0, Ordering::Relaxed);
+        self.events_dropped.store(0, Ordering::Relaxed);
+        self.events_spilled.store(0, Ordering::Relaxed);
         self.batches_written.store(0, Ordering::Relaxed);
         {
             let mut last_write = self.last_write_time.write().await;
@@ -576,23 +980,75 @@ impl BatchWriter {
     }
 }
 
+/// 事件的排序键：同一排序键的事件修改同一份状态，必须按提交顺序写入
+///
+/// 仓位事件按仓位NFT，池子相关事件按池子，配置变更按配置；
+/// 其余事件（积分、推荐、发射等）跨用户修改状态，返回None统一由第一个写入任务处理
+fn ordering_key(event: &ParsedEvent) -> Option<&str> {
+    match event {
+        ParsedEvent::ClmmCreatePosition(data) => data.position_nft_mint.as_deref().or(data.position_key.as_deref()),
+        ParsedEvent::ClmmIncreaseLiquidity(data) => Some(&data.position_nft_mint),
+        ParsedEvent::ClmmDecreaseLiquidity(data) => Some(&data.position_nft_mint),
+        ParsedEvent::ClmmCollectPersonalFee(data) => Some(&data.position_nft_mint),
+        ParsedEvent::ClmmSwap(data) => Some(&data.pool_id),
+        ParsedEvent::ClmmCollectProtocolFee(data) => Some(&data.pool_id),
+        ParsedEvent::Swap(data) => Some(&data.pool_id),
+        ParsedEvent::LpChange(data) => Some(&data.pool_id),
+        ParsedEvent::InitPool(data) => Some(&data.pool_id),
+        ParsedEvent::ClmmConfigChange(data) => Some(&data.config_id),
+        _ => None,
+    }
+}
+
+/// 计算事件应由哪个写入任务处理
+fn writer_index(event: &ParsedEvent, writers: usize) -> usize {
+    if writers <= 1 {
+        return 0;
+    }
+
+    match ordering_key(event) {
+        Some(key) => {
+            let mut hasher = DefaultHasher::new();
+            key.hash(&mut hasher);
+            (hasher.finish() % writers as u64) as usize
+        }
+        None => 0,
+    }
+}
+
+/// 根据批次内容生成批次ID（首尾签名和事件数）
+fn batch_id_for(batch: &[ParsedEvent]) -> String {
+    match (batch.first(), batch.last()) {
+        (Some(first), Some(last)) => format!("{}..{}#{}", first.signature(), last.signature(), batch.len()),
+        _ => "empty".to_string(),
+    }
+}
+
 impl Clone for BatchWriter {
     fn clone(&self) -> Self {
         Self {
             config: Arc::clone(&self.config),
             event_storage: Arc::clone(&self.event_storage),
+            primary_sink: self.primary_sink.clone(),
+            secondary_sinks: Arc::clone(&self.secondary_sinks),
             batch_size: self.batch_size,
             max_wait_duration: self.max_wait_duration,
             buffer_size: self.buffer_size,
+            concurrent_writers: self.concurrent_writers,
+            overflow_policy: self.overflow_policy,
             is_running: Arc::clone(&self.is_running),
-            event_buffer: Arc::clone(&self.event_buffer),
+            event_buffers: Arc::clone(&self.event_buffers),
             events_queued: Arc::clone(&self.events_queued),
             events_written: Arc::clone(&self.events_written),
             events_failed: Arc::clone(&self.events_failed),
+            events_dead_lettered: Arc::clone(&self.events_dead_lettered),
+            events_dropped: Arc::clone(&self.events_dropped),
+            events_spilled: Arc::clone(&self.events_spilled),
             batches_written: Arc::clone(&self.batches_written),
             last_write_time: Arc::clone(&self.last_write_time),
             event_sender: self.event_sender.clone(),
             event_receiver: Arc::clone(&self.event_receiver),
+            spill_file: Arc::clone(&self.spill_file),
             retry_counts: Arc::clone(&self.retry_counts),
             max_retries: self.max_retries,
         }
@@ -613,6 +1069,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![solana_sdk::pubkey::Pubkey::new_unique()],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -633,14 +1090,22 @@ mod tests {
                     max_wait_ms: 1000,
                     buffer_size: 100,
                     concurrent_writers: 2,
+                    overflow_policy: crate::config::settings::OverflowPolicy::Block,
+                    spill_path: None,
+                    sinks: crate::config::settings::EventSinkConfig::default(),
                 },
             },
             monitoring: crate::config::settings::MonitoringConfig {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         }
     }
 
@@ -660,6 +1125,8 @@ mod tests {
             created_at: 1234567890,
             signature: "test_signature".to_string(),
             slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
             extensions: None,
             source: None,
         })
@@ -778,14 +1245,152 @@ mod tests {
         let test_events = vec![create_test_event(), create_test_event(), create_test_event()];
 
         // 重新排队事件
-        writer.requeue_batch(test_events.clone()).await;
+        writer.requeue_batch(1, test_events.clone()).await;
+
+        // 验证事件回到原写入任务的缓冲区
+        assert_eq!(writer.event_buffers[1].lock().await.len(), test_events.len());
+        assert!(writer.event_buffers[0].lock().await.is_empty());
+        assert_eq!(writer.buffered_len().await, test_events.len());
+    }
+
+    #[tokio::test]
+    async fn test_out_of_order_event_requeued_to_back() {
+        let config = create_test_config();
+        let writer = BatchWriter::new(&config).await.unwrap();
 
-        // 验证事件已添加到缓冲区
-        let buffer_size = {
-            let buffer = writer.event_buffer.lock().await;
-            buffer.len()
+        let mut pending = create_test_event();
+        pending.set_position(0, 1);
+        let written = create_test_event();
+        let writer_id = writer_index(&pending, writer.event_buffers.len());
+        writer.event_buffers[writer_id]
+            .lock()
+            .await
+            .push_back(create_test_event());
+
+        let failed = vec![FailedEvent::new(
+            pending.clone(),
+            EventListenerError::OutOfOrder("仓位尚未建立".to_string()),
+        )];
+        let delivered = writer
+            .handle_failed_events("test-batch", failed, vec![written, pending.clone()])
+            .await;
+
+        // 失败事件不分发到附加输出目标，并排在缓冲区已有事件之后等待重试
+        assert_eq!(delivered.len(), 1);
+        let buffer = writer.event_buffers[writer_id].lock().await;
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer[1].get_unique_id(), pending.get_unique_id());
+        let retry_key = format!("event:{}", pending.get_unique_id());
+        assert_eq!(writer.retry_counts.lock().await.get(&retry_key).copied(), Some(1));
+    }
+
+    #[test]
+    fn test_writer_index_keeps_same_key_on_one_writer() {
+        let swap = |pool_id: &str, signature: &str| {
+            ParsedEvent::Swap(crate::parser::swap_parser::SwapEventData {
+                payer: Pubkey::new_unique().to_string(),
+                pool_id: pool_id.to_string(),
+                input_vault_before: 0,
+                output_vault_before: 0,
+                input_amount: 100,
+                output_amount: 99,
+                input_transfer_fee: 0,
+                output_transfer_fee: 0,
+                base_input: true,
+                input_mint: Pubkey::new_unique().to_string(),
+                output_mint: Pubkey::new_unique().to_string(),
+                trade_fee: 1,
+                creator_fee: 0,
+                creator_fee_on_input: true,
+                signature: signature.to_string(),
+                slot: 1,
+                instruction_index: 0,
+                event_ordinal: 0,
+                block_time: None,
+                processed_at: "2024-01-01T00:00:00Z".to_string(),
+            })
         };
-        assert_eq!(buffer_size, test_events.len());
+
+        // 同一池子的事件无论签名如何都路由到同一写入任务
+        let first = writer_index(&swap("pool_a", "sig_1"), 4);
+        assert_eq!(writer_index(&swap("pool_a", "sig_2"), 4), first);
+        assert!(first < 4);
+
+        // 没有排序键的事件和单写入任务都落在第一个写入任务
+        assert_eq!(writer_index(&create_test_event(), 4), 0);
+        assert_eq!(writer_index(&swap("pool_b", "sig_3"), 1), 0);
+    }
+
+    #[tokio::test]
+    async fn test_overflow_drop_policy() {
+        let mut config = create_test_config();
+        config.listener.batch_write.buffer_size = 2;
+        config.listener.batch_write.overflow_policy = OverflowPolicy::Drop;
+        let writer = BatchWriter::new(&config).await.unwrap();
+        writer.is_running.store(true, Ordering::Relaxed);
+
+        // 未启动收集循环时通道容量即为上限，超出部分被丢弃
+        let events = (0..5).map(|_| create_test_event()).collect();
+        writer.submit_events(events).await.unwrap();
+
+        let stats = writer.get_stats().await;
+        assert_eq!(stats.events_queued, 2);
+        assert_eq!(stats.events_dropped, 3);
+        assert_eq!(stats.events_spilled, 0);
+    }
+
+    #[tokio::test]
+    async fn test_overflow_spill_policy() {
+        let spill_dir = std::env::temp_dir().join(format!("batch-writer-spill-{}", uuid::Uuid::new_v4()));
+        let mut config = create_test_config();
+        config.listener.batch_write.buffer_size = 2;
+        config.listener.batch_write.overflow_policy = OverflowPolicy::Spill;
+        config.listener.batch_write.spill_path = Some(spill_dir.join("spill.jsonl").to_string_lossy().to_string());
+        config.listener.batch_write.sinks.mongo_enabled = false;
+        let writer = BatchWriter::new(&config).await.unwrap();
+        writer.is_running.store(true, Ordering::Relaxed);
+
+        let events = (0..5).map(|_| create_test_event()).collect();
+        writer.submit_events(events).await.unwrap();
+
+        let stats = writer.get_stats().await;
+        assert_eq!(stats.events_queued, 2);
+        assert_eq!(stats.events_spilled, 3);
+        assert_eq!(stats.spill_pending, 3);
+        assert_eq!(stats.events_dropped, 0);
+
+        // 积压未写完时后到的事件追加到溢出文件，不会超越已溢出的事件
+        writer.submit_event(create_test_event()).await.unwrap();
+        let stats = writer.get_stats().await;
+        assert_eq!(stats.events_queued, 2);
+        assert_eq!(stats.events_spilled, 4);
+
+        // 溢出事件直接写入后才删除重放文件并恢复实时入队
+        writer.replay_spill_file().await.unwrap();
+        assert_eq!(writer.get_stats().await.events_written, 4);
+        assert!(!writer.spill_file.has_pending().await);
+        assert!(writer.spill_file.try_finish_backlog().await);
+
+        writer.submit_event(create_test_event()).await.unwrap();
+        assert_eq!(writer.get_stats().await.events_queued, 3);
+
+        let _ = tokio::fs::remove_dir_all(spill_dir).await;
+    }
+
+    #[test]
+    fn test_batch_id_stable_across_requeue() {
+        let first = create_test_event();
+        let mut last = create_test_event();
+        if let ParsedEvent::TokenCreation(data) = &mut last {
+            data.signature = "last_signature".to_string();
+        }
+
+        let batch = vec![first.clone(), last.clone()];
+        // 重新排队后组成的相同批次得到相同ID，重试次数才能累计
+        assert_eq!(batch_id_for(&batch), batch_id_for(&batch.clone()));
+        assert_eq!(batch_id_for(&batch), "test_signature..last_signature#2");
+        assert_ne!(batch_id_for(&batch), batch_id_for(&[first, last.clone(), last]));
+        assert_eq!(batch_id_for(&[]), "empty");
     }
 
     #[tokio::test]
@@ -0,0 +1,179 @@
+use crate::{
+    error::{EventListenerError, Result},
+    parser::ParsedEvent,
+    subscriber::endpoint_pool::EndpointPool,
+};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+use tracing::debug;
+
+/// slot出块时间缓存的最大条目数
+const BLOCK_TIME_CACHE_CAPACITY: usize = 4096;
+
+/// 出块时间补齐器
+///
+/// 实时订阅的日志只带slot不带出块时间，写入前按slot向RPC查询并写回事件；
+/// 回填和重建索引从完整交易中直接取得出块时间，不会触发查询。
+/// 查询结果按slot缓存，同一slot内的多个事件只查询一次
+pub struct BlockTimeResolver {
+    endpoint_pool: Arc<EndpointPool>,
+    cache: Mutex<BTreeMap<u64, i64>>,
+}
+
+impl BlockTimeResolver {
+    /// 创建出块时间补齐器
+    pub fn new(endpoint_pool: Arc<EndpointPool>) -> Self {
+        Self {
+            endpoint_pool,
+            cache: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// 为缺少出块时间的事件补齐出块时间
+    ///
+    /// 所有事件都已携带出块时间时不复制；任一slot查询失败返回错误，由调用方整批重试
+    pub async fn fill<'a>(&self, events: &'a [ParsedEvent]) -> Result<Cow<'a, [ParsedEvent]>> {
+        if !events.iter().any(ParsedEvent::needs_block_time) {
+            self.remember(events);
+            return Ok(Cow::Borrowed(events));
+        }
+
+        let mut filled = events.to_vec();
+        for event in filled.iter_mut().filter(|event| event.needs_block_time()) {
+            let block_time = self.resolve(event.slot()).await?;
+            event.set_block_time(block_time);
+        }
+
+        Ok(Cow::Owned(filled))
+    }
+
+    /// 查询slot的出块时间（优先使用缓存）
+    async fn resolve(&self, slot: u64) -> Result<i64> {
+        if let Some(block_time) = self.cached(slot) {
+            return Ok(block_time);
+        }
+
+        let endpoint_pool = Arc::clone(&self.endpoint_pool);
+        let block_time = tokio::task::spawn_blocking(move || {
+            endpoint_pool
+                .call("get_block_time", |client| client.get_block_time(slot))
+                .map_err(|e| EventListenerError::SolanaRpc(format!("获取slot {}出块时间失败: {}", slot, e)))
+        })
+        .await
+        .map_err(|e| EventListenerError::Unknown(format!("异步任务执行失败: {}", e)))??;
+
+        debug!("🕒 补齐slot {}出块时间: {}", slot, block_time);
+        self.insert(slot, block_time);
+        Ok(block_time)
+    }
+
+    /// 记录事件自带的出块时间，供同一slot的实时事件复用
+    fn remember(&self, events: &[ParsedEvent]) {
+        for event in events {
+            if let Some(block_time) = event.block_time() {
+                self.insert(event.slot(), block_time);
+            }
+        }
+    }
+
+    fn cached(&self, slot: u64) -> Option<i64> {
+        self.cache.lock().unwrap().get(&slot).copied()
+    }
+
+    fn insert(&self, slot: u64, block_time: i64) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.insert(slot, block_time);
+        // 超出容量时淘汰最早的slot
+        while cache.len() > BLOCK_TIME_CACHE_CAPACITY {
+            cache.pop_first();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::settings::RpcEndpointConfig, parser::swap_parser::SwapEventData,
+        subscriber::endpoint_pool::EndpointRole,
+    };
+    use std::time::Duration;
+
+    fn create_test_resolver() -> BlockTimeResolver {
+        let endpoint = RpcEndpointConfig {
+            name: "primary".to_string(),
+            rpc_url: "http://127.0.0.1:1".to_string(),
+            ws_url: "ws://127.0.0.1:1".to_string(),
+            weight: 100,
+        };
+        let pool = EndpointPool::with_endpoints(vec![endpoint], EndpointRole::Rpc, 3, Duration::from_secs(30)).unwrap();
+        BlockTimeResolver::new(Arc::new(pool))
+    }
+
+    fn create_swap_event(slot: u64, block_time: Option<i64>) -> ParsedEvent {
+        ParsedEvent::Swap(SwapEventData {
+            payer: "payer".to_string(),
+            pool_id: "pool".to_string(),
+            input_vault_before: 0,
+            output_vault_before: 0,
+            input_amount: 1,
+            output_amount: 1,
+            input_transfer_fee: 0,
+            output_transfer_fee: 0,
+            base_input: true,
+            input_mint: "mint_a".to_string(),
+            output_mint: "mint_b".to_string(),
+            trade_fee: 0,
+            creator_fee: 0,
+            creator_fee_on_input: true,
+            signature: format!("sig_{}", slot),
+            slot,
+            instruction_index: 0,
+            event_ordinal: 0,
+            block_time,
+            processed_at: "2024-01-01T00:00:00Z".to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_fill_borrows_when_block_time_present() {
+        let resolver = create_test_resolver();
+        let events = vec![create_swap_event(100, Some(1_700_000_000))];
+
+        let filled = resolver.fill(&events).await.unwrap();
+
+        assert!(matches!(filled, Cow::Borrowed(_)));
+        assert_eq!(resolver.cached(100), Some(1_700_000_000));
+    }
+
+    #[tokio::test]
+    async fn test_fill_uses_cached_slot_block_time() {
+        let resolver = create_test_resolver();
+        resolver.insert(200, 1_700_000_100);
+        let events = vec![
+            create_swap_event(200, None),
+            create_swap_event(200, Some(1_700_000_100)),
+        ];
+
+        let filled = resolver.fill(&events).await.unwrap();
+
+        assert!(filled.iter().all(|event| event.block_time() == Some(1_700_000_100)));
+    }
+
+    #[test]
+    fn test_cache_evicts_oldest_slot() {
+        let resolver = create_test_resolver();
+        for slot in 0..=BLOCK_TIME_CACHE_CAPACITY as u64 {
+            resolver.insert(slot, slot as i64);
+        }
+
+        assert_eq!(resolver.cached(0), None);
+        assert_eq!(
+            resolver.cached(BLOCK_TIME_CACHE_CAPACITY as u64),
+            Some(BLOCK_TIME_CACHE_CAPACITY as i64)
+        );
+    }
+}
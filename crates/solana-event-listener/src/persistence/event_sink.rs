@@ -0,0 +1,386 @@
+use crate::{
+    config::settings::{EventSinkConfig, SinkRetryConfig},
+    error::Result,
+    parser::ParsedEvent,
+    persistence::{
+        event_storage::BatchWriteOutcome, jsonl_sink::JsonlFileSink, webhook_sink::WebhookSink,
+        webhook_subscription_sink::WebhookSubscriptionSink, EventStorage,
+    },
+};
+use async_trait::async_trait;
+use serde::Serialize;
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{
+    sync::{
+        mpsc::{self, error::TrySendError},
+        Mutex,
+    },
+    time::timeout,
+};
+use tracing::{error, info, warn};
+
+/// 事件输出目标
+///
+/// BatchWriter将每个批次分发到所有已启用的输出目标
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    /// 输出目标名称（用于日志和指标标签）
+    fn name(&self) -> &str;
+
+    /// 写入一批事件，返回实际写入的事件数
+    async fn write_batch(&self, events: &[ParsedEvent]) -> Result<u64>;
+
+    /// 写入一批事件，返回写入数和写入失败的单个事件
+    ///
+    /// 默认整批成功或整批失败，能区分单个事件失败的输出目标应覆盖此方法
+    async fn write_batch_outcome(&self, events: &[ParsedEvent]) -> Result<BatchWriteOutcome> {
+        let written = self.write_batch(events).await?;
+        Ok(BatchWriteOutcome {
+            written,
+            failed: Vec::new(),
+        })
+    }
+}
+
+/// MongoDB输出目标
+pub struct MongoEventSink {
+    event_storage: Arc<EventStorage>,
+}
+
+impl MongoEventSink {
+    pub fn new(event_storage: Arc<EventStorage>) -> Self {
+        Self { event_storage }
+    }
+}
+
+#[async_trait]
+impl EventSink for MongoEventSink {
+    fn name(&self) -> &str {
+        "mongo"
+    }
+
+    async fn write_batch(&self, events: &[ParsedEvent]) -> Result<u64> {
+        let outcome = self.write_batch_outcome(events).await?;
+        for failed in &outcome.failed {
+            error!(
+                "❌ 事件写入MongoDB失败: {} - {}",
+                failed.event.get_unique_id(),
+                failed.error
+            );
+        }
+        Ok(outcome.written)
+    }
+
+    async fn write_batch_outcome(&self, events: &[ParsedEvent]) -> Result<BatchWriteOutcome> {
+        self.event_storage.write_batch(events).await
+    }
+}
+
+/// 附加输出目标统计
+#[derive(Debug, Clone, Serialize)]
+pub struct SinkStats {
+    pub name: String,
+    pub events_written: u64,
+    pub events_failed: u64,
+    pub events_dropped: u64,
+    pub retries: u64,
+    pub queued_batches: usize,
+}
+
+/// 附加输出目标的独立写入任务
+///
+/// 每个附加输出目标拥有独立的有界批次队列、重试和统计，
+/// 输出变慢或不可用时只丢弃本目标的批次，不阻塞主存储写入
+pub struct SinkWorker {
+    sink: Arc<dyn EventSink>,
+    queue_size: usize,
+    max_retries: u32,
+    retry_delay: Duration,
+
+    sender: mpsc::Sender<Vec<ParsedEvent>>,
+    receiver: Mutex<mpsc::Receiver<Vec<ParsedEvent>>>,
+
+    events_written: AtomicU64,
+    events_failed: AtomicU64,
+    events_dropped: AtomicU64,
+    retries: AtomicU64,
+}
+
+impl SinkWorker {
+    /// 创建输出目标写入任务
+    pub fn new(sink: Arc<dyn EventSink>, retry: &SinkRetryConfig) -> Self {
+        let queue_size = retry.queue_size.max(1);
+        let (sender, receiver) = mpsc::channel(queue_size);
+
+        Self {
+            sink,
+            queue_size,
+            max_retries: retry.max_retries,
+            retry_delay: Duration::from_millis(retry.retry_delay_ms),
+            sender,
+            receiver: Mutex::new(receiver),
+            events_written: AtomicU64::new(0),
+            events_failed: AtomicU64::new(0),
+            events_dropped: AtomicU64::new(0),
+            retries: AtomicU64::new(0),
+        }
+    }
+
+    /// 输出目标名称
+    pub fn name(&self) -> &str {
+        self.sink.name()
+    }
+
+    /// 提交批次（不阻塞），队列已满时丢弃
+    pub fn enqueue(&self, events: Vec<ParsedEvent>) {
+        if events.is_empty() {
+            return;
+        }
+
+        match self.sender.try_send(events) {
+            Ok(()) => {}
+            Err(TrySendError::Full(events)) | Err(TrySendError::Closed(events)) => {
+                self.events_dropped.fetch_add(events.len() as u64, Ordering::Relaxed);
+                warn!("⚠️ 输出目标{}队列已满，丢弃{}个事件", self.name(), events.len());
+            }
+        }
+    }
+
+    /// 运行写入循环，直到is_running被清除
+    ///
+    /// 每次等待最多1秒后释放队列锁，保证停止时drain能取到剩余批次
+    pub async fn run(&self, is_running: Arc<AtomicBool>) {
+        info!("📤 启动事件输出目标: {}", self.name());
+
+        while is_running.load(Ordering::Relaxed) {
+            let batch = {
+                let mut receiver = self.receiver.lock().await;
+                timeout(Duration::from_secs(1), receiver.recv()).await
+            };
+            match batch {
+                Ok(Some(batch)) => self.write_with_retry(&batch).await,
+                Ok(None) => break,
+                Err(_) => continue,
+            }
+        }
+
+        info!("📤 事件输出目标{}已停止", self.name());
+    }
+
+    /// 写入队列中剩余的批次（关闭时调用）
+    pub async fn drain(&self) {
+        let mut receiver = self.receiver.lock().await;
+        while let Ok(batch) = receiver.try_recv() {
+            self.write_with_retry(&batch).await;
+        }
+    }
+
+    /// 带指数退避重试的写入，重试耗尽后计入失败统计
+    async fn write_with_retry(&self, batch: &[ParsedEvent]) {
+        let mut attempt = 0u32;
+        loop {
+            match self.sink.write_batch(batch).await {
+                Ok(written) => {
+                    self.events_written.fetch_add(written, Ordering::Relaxed);
+                    return;
+                }
+                Err(e) if attempt < self.max_retries => {
+                    let delay = std::cmp::min(
+                        self.retry_delay.saturating_mul(2_u32.saturating_pow(attempt)),
+                        Duration::from_secs(30),
+                    );
+                    attempt += 1;
+                    self.retries.fetch_add(1, Ordering::Relaxed);
+                    warn!(
+                        "🔄 输出目标{}写入失败，{:?}后第{}次重试: {}",
+                        self.name(),
+                        delay,
+                        attempt,
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => {
+                    self.events_failed.fetch_add(batch.len() as u64, Ordering::Relaxed);
+                    error!(
+                        "❌ 输出目标{}重试{}次后仍写入失败，放弃{}个事件: {}",
+                        self.name(),
+                        attempt,
+                        batch.len(),
+                        e
+                    );
+                    return;
+                }
+            }
+        }
+    }
+
+    /// 获取统计信息
+    pub fn stats(&self) -> SinkStats {
+        SinkStats {
+            name: self.name().to_string(),
+            events_written: self.events_written.load(Ordering::Relaxed),
+            events_failed: self.events_failed.load(Ordering::Relaxed),
+            events_dropped: self.events_dropped.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            queued_batches: self.queue_size - self.sender.capacity(),
+        }
+    }
+}
+
+/// 根据配置创建附加输出目标（MongoDB作为主存储由BatchWriter直接管理）
+pub fn build_secondary_sinks(
+    config: &EventSinkConfig,
+    event_storage: &Arc<EventStorage>,
+) -> Result<Vec<Arc<SinkWorker>>> {
+    let mut workers = Vec::new();
+
+    if let Some(jsonl) = &config.jsonl {
+        info!("📁 启用JSONL事件输出: {}", jsonl.directory);
+        let sink = Arc::new(JsonlFileSink::new(&jsonl.directory, &jsonl.file_prefix));
+        workers.push(Arc::new(SinkWorker::new(sink, &jsonl.retry)));
+    }
+
+    if let Some(webhook) = &config.webhook {
+        info!("🌐 启用webhook事件输出");
+        let sink = Arc::new(WebhookSink::new(
+            webhook.url.clone(),
+            webhook.auth_token.clone(),
+            Duration::from_millis(webhook.timeout_ms),
+        )?);
+        workers.push(Arc::new(SinkWorker::new(sink, &webhook.retry)));
+    }
+
+    if let Some(subscriptions) = &config.webhook_subscriptions {
+        info!("🔔 启用webhook订阅投递");
+        let sink = Arc::new(WebhookSubscriptionSink::new(
+            Arc::clone(event_storage),
+            Duration::from_secs(subscriptions.refresh_interval_secs),
+        ));
+        workers.push(Arc::new(SinkWorker::new(sink, &subscriptions.retry)));
+    }
+
+    Ok(workers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::EventListenerError, parser::token_creation_parser::TokenCreationEventData};
+    use std::sync::atomic::AtomicU32;
+
+    /// 前若干次写入失败的测试输出目标
+    struct FlakySink {
+        failures_left: AtomicU32,
+        written: AtomicU64,
+    }
+
+    #[async_trait]
+    impl EventSink for FlakySink {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn write_batch(&self, events: &[ParsedEvent]) -> Result<u64> {
+            if self.failures_left.load(Ordering::Relaxed) > 0 {
+                self.failures_left.fetch_sub(1, Ordering::Relaxed);
+                return Err(EventListenerError::Network("connection refused".to_string()));
+            }
+            self.written.fetch_add(events.len() as u64, Ordering::Relaxed);
+            Ok(events.len() as u64)
+        }
+    }
+
+    fn create_test_event(signature: &str) -> ParsedEvent {
+        ParsedEvent::TokenCreation(TokenCreationEventData {
+            project_config: "project_config".to_string(),
+            mint_address: "mint_address".to_string(),
+            name: "Test Token".to_string(),
+            symbol: "TEST".to_string(),
+            metadata_uri: String::new(),
+            logo_uri: String::new(),
+            decimals: 9,
+            supply: 1000000,
+            creator: "creator".to_string(),
+            has_whitelist: false,
+            whitelist_deadline: 0,
+            created_at: 1234567890,
+            signature: signature.to_string(),
+            slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
+            extensions: None,
+            source: None,
+        })
+    }
+
+    fn retry_config(queue_size: usize, max_retries: u32) -> SinkRetryConfig {
+        SinkRetryConfig {
+            queue_size,
+            max_retries,
+            retry_delay_ms: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sink_worker_retries_until_success() {
+        let sink = Arc::new(FlakySink {
+            failures_left: AtomicU32::new(2),
+            written: AtomicU64::new(0),
+        });
+        let worker = SinkWorker::new(sink.clone(), &retry_config(10, 3));
+
+        worker.enqueue(vec![create_test_event("sig_1"), create_test_event("sig_2")]);
+        assert_eq!(worker.stats().queued_batches, 1);
+        worker.drain().await;
+
+        let stats = worker.stats();
+        assert_eq!(stats.events_written, 2);
+        assert_eq!(stats.retries, 2);
+        assert_eq!(stats.events_failed, 0);
+        assert_eq!(stats.queued_batches, 0);
+        assert_eq!(sink.written.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sink_worker_gives_up_and_drops() {
+        let sink = Arc::new(FlakySink {
+            failures_left: AtomicU32::new(10),
+            written: AtomicU64::new(0),
+        });
+        let worker = SinkWorker::new(sink, &retry_config(1, 1));
+
+        worker.enqueue(vec![create_test_event("sig_1")]);
+        // 队列容量为1，第二个批次被丢弃
+        worker.enqueue(vec![create_test_event("sig_2"), create_test_event("sig_3")]);
+        worker.drain().await;
+
+        let stats = worker.stats();
+        assert_eq!(stats.name, "flaky");
+        assert_eq!(stats.events_written, 0);
+        assert_eq!(stats.events_failed, 1);
+        assert_eq!(stats.events_dropped, 2);
+        assert_eq!(stats.retries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_default_write_batch_outcome_is_all_or_nothing() {
+        let sink = FlakySink {
+            failures_left: AtomicU32::new(1),
+            written: AtomicU64::new(0),
+        };
+        let batch = vec![create_test_event("sig_1"), create_test_event("sig_2")];
+
+        assert!(sink.write_batch_outcome(&batch).await.is_err());
+
+        let outcome = sink.write_batch_outcome(&batch).await.unwrap();
+        assert_eq!(outcome.written, 2);
+        assert!(outcome.failed.is_empty());
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{error::Result, parser::ParsedEvent, persistence::event_sink::EventSink};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+use tokio::{fs, io::AsyncWriteExt, sync::Mutex};
+
+/// 按小时轮转的JSONL文件输出目标
+///
+/// 每个事件写为一行JSON，文件名为`<prefix>-<YYYYMMDDHH>.jsonl`（UTC写入时间），
+/// 每批写入后立即落盘，便于下游按小时拉取完整文件
+pub struct JsonlFileSink {
+    directory: PathBuf,
+    file_prefix: String,
+    write_lock: Mutex<()>,
+}
+
+impl JsonlFileSink {
+    pub fn new(directory: impl Into<PathBuf>, file_prefix: &str) -> Self {
+        Self {
+            directory: directory.into(),
+            file_prefix: file_prefix.to_string(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// 输出目录
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// 指定时间对应的输出文件路径
+    pub fn file_path_at(&self, now: DateTime<Utc>) -> PathBuf {
+        self.directory
+            .join(format!("{}-{}.jsonl", self.file_prefix, now.format("%Y%m%d%H")))
+    }
+
+    /// 以指定时间写入一批事件
+    pub async fn write_batch_at(&self, events: &[ParsedEvent], now: DateTime<Utc>) -> Result<u64> {
+        if events.is_empty() {
+            return Ok(0);
+        }
+
+        let mut content = String::new();
+        for event in events {
+            content.push_str(&serde_json::to_string(event)?);
+            content.push('\n');
+        }
+
+        let _guard = self.write_lock.lock().await;
+        fs::create_dir_all(&self.directory).await?;
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.file_path_at(now))
+            .await?;
+        file.write_all(content.as_bytes()).await?;
+        file.sync_data().await?;
+
+        Ok(events.len() as u64)
+    }
+}
+
+#[async_trait]
+impl EventSink for JsonlFileSink {
+    fn name(&self) -> &str {
+        "jsonl"
+    }
+
+    async fn write_batch(&self, events: &[ParsedEvent]) -> Result<u64> {
+        self.write_batch_at(events, Utc::now()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::token_creation_parser::TokenCreationEventData;
+    use chrono::TimeZone;
+
+    fn create_test_event(signature: &str) -> ParsedEvent {
+        ParsedEvent::TokenCreation(TokenCreationEventData {
+            project_config: "project_config".to_string(),
+            mint_address: "mint_address".to_string(),
+            name: "Test Token".to_string(),
+            symbol: "TEST".to_string(),
+            metadata_uri: String::new(),
+            logo_uri: String::new(),
+            decimals: 9,
+            supply: 1000000,
+            creator: "creator".to_string(),
+            has_whitelist: false,
+            whitelist_deadline: 0,
+            created_at: 1234567890,
+            signature: signature.to_string(),
+            slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
+            extensions: None,
+            source: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_write_batch_rotates_hourly() {
+        let directory = std::env::temp_dir().join(format!("jsonl-sink-test-{}", uuid::Uuid::new_v4()));
+        let sink = JsonlFileSink::new(&directory, "events");
+        let first_hour = Utc.with_ymd_and_hms(2024, 5, 1, 10, 59, 0).unwrap();
+        let next_hour = Utc.with_ymd_and_hms(2024, 5, 1, 11, 0, 0).unwrap();
+
+        let written = sink
+            .write_batch_at(&[create_test_event("sig_1"), create_test_event("sig_2")], first_hour)
+            .await
+            .unwrap();
+        assert_eq!(written, 2);
+        sink.write_batch_at(&[create_test_event("sig_3")], next_hour)
+            .await
+            .unwrap();
+        assert_eq!(sink.write_batch_at(&[], next_hour).await.unwrap(), 0);
+
+        let first_path = sink.file_path_at(first_hour);
+        assert!(first_path.ends_with("events-2024050110.jsonl"));
+        let first = fs::read_to_string(&first_path).await.unwrap();
+        let signatures: Vec<String> = first
+            .lines()
+            .map(|line| {
+                let event: ParsedEvent = serde_json::from_str(line).unwrap();
+                event.signature().to_string()
+            })
+            .collect();
+        assert_eq!(signatures, vec!["sig_1", "sig_2"]);
+
+        let next = fs::read_to_string(sink.file_path_at(next_hour)).await.unwrap();
+        assert_eq!(next.lines().count(), 1);
+
+        let _ = fs::remove_dir_all(sink.directory()).await;
+    }
+}
@@ -1,5 +1,15 @@
 pub mod batch_writer;
+pub mod block_time_resolver;
+pub mod event_sink;
 pub mod event_storage;
+pub mod jsonl_sink;
+pub mod spill_file;
+pub mod webhook_dispatcher;
+pub mod webhook_sink;
+pub mod webhook_subscription_sink;
 
 pub use batch_writer::BatchWriter;
-pub use event_storage::EventStorage;
+pub use block_time_resolver::BlockTimeResolver;
+pub use event_sink::{EventSink, SinkStats, SinkWorker};
+pub use event_storage::{EventStorage, WriteMode};
+pub use webhook_dispatcher::WebhookDispatcher;
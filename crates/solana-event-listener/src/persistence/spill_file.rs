@@ -0,0 +1,305 @@
+use crate::{
+    error::{EventListenerError, Result},
+    parser::ParsedEvent,
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+use tokio::{fs, io::AsyncWriteExt, sync::Mutex};
+use tracing::{info, warn};
+
+/// 默认溢出文件路径
+pub const DEFAULT_SPILL_PATH: &str = "./data/batch_writer_spill.jsonl";
+
+/// 批量写入队列的本地溢出文件
+///
+/// 队列已满时事件按JSON行追加写入文件并立即落盘；重放时先将文件改名为`.replay`
+/// 再读取，改名后新的溢出事件写入新文件，重放中途崩溃时下次启动会继续处理遗留的`.replay`文件
+///
+/// `.replay`文件只在事件写入成功后才截掉对应部分，全部写入后删除；
+/// 存在积压期间（backlog）新事件也应追加到溢出文件，避免后到的实时事件超越溢出事件
+pub struct SpillFile {
+    path: PathBuf,
+    replay_path: PathBuf,
+    write_lock: Mutex<()>,
+    pending: AtomicU64,
+    backlog: AtomicBool,
+}
+
+impl SpillFile {
+    /// 创建溢出文件句柄（不会立即创建文件）
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let mut replay_path = path.clone().into_os_string();
+        replay_path.push(".replay");
+
+        Self {
+            path,
+            replay_path: PathBuf::from(replay_path),
+            write_lock: Mutex::new(()),
+            pending: AtomicU64::new(0),
+            backlog: AtomicBool::new(false),
+        }
+    }
+
+    /// 溢出文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 本进程写入后尚未取出重放的事件数
+    pub fn pending(&self) -> u64 {
+        self.pending.load(Ordering::Relaxed)
+    }
+
+    /// 是否存在待重放的溢出事件（含上次运行遗留的文件）
+    pub async fn has_pending(&self) -> bool {
+        self.pending() > 0 || self.files_exist().await
+    }
+
+    /// 溢出文件或重放文件是否存在
+    async fn files_exist(&self) -> bool {
+        fs::try_exists(&self.path).await.unwrap_or(false) || fs::try_exists(&self.replay_path).await.unwrap_or(false)
+    }
+
+    /// 是否存在尚未写入成功的溢出积压
+    pub fn is_backlogged(&self) -> bool {
+        self.backlog.load(Ordering::Acquire)
+    }
+
+    /// 检查上次运行遗留的溢出文件，存在时进入积压状态
+    pub async fn resume_backlog(&self) -> bool {
+        let _guard = self.write_lock.lock().await;
+        let backlogged = self.files_exist().await;
+        self.backlog.store(backlogged, Ordering::Release);
+        backlogged
+    }
+
+    /// 溢出文件和重放文件均已处理完时退出积压状态
+    ///
+    /// 与追加写入互斥，保证退出积压后不会有事件遗留在文件中
+    pub async fn try_finish_backlog(&self) -> bool {
+        let _guard = self.write_lock.lock().await;
+        if self.files_exist().await {
+            return false;
+        }
+        self.backlog.store(false, Ordering::Release);
+        true
+    }
+
+    /// 追加事件到溢出文件
+    pub async fn append(&self, events: &[ParsedEvent]) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let mut content = String::new();
+        for event in events {
+            content.push_str(&serde_json::to_string(event)?);
+            content.push('\n');
+        }
+
+        let _guard = self.write_lock.lock().await;
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent).await?;
+            }
+        }
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(content.as_bytes()).await?;
+        file.sync_data().await?;
+
+        self.pending.fetch_add(events.len() as u64, Ordering::Relaxed);
+        self.backlog.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// 取出所有待重放的事件
+    ///
+    /// 返回的事件交由调用方按顺序写入，每写入成功一部分调用`retain_for_replay`截掉已写入的事件，
+    /// 全部写入后调用`complete_replay`删除重放文件；写入前崩溃不会丢失事件，但重启后会再次重放（事件写入按签名去重）
+    pub async fn take_for_replay(&self) -> Result<Vec<ParsedEvent>> {
+        {
+            let _guard = self.write_lock.lock().await;
+            // 上一轮的重放文件未完成时不覆盖，先处理遗留文件
+            if !fs::try_exists(&self.replay_path).await? {
+                if !fs::try_exists(&self.path).await? {
+                    return Ok(Vec::new());
+                }
+                fs::rename(&self.path, &self.replay_path).await?;
+                self.pending.store(0, Ordering::Relaxed);
+            }
+        }
+
+        let content = fs::read_to_string(&self.replay_path).await?;
+        let events = parse_spill_lines(&content);
+        if !events.is_empty() {
+            info!(
+                "📂 从溢出文件取出{}个待重放事件: {}",
+                events.len(),
+                self.replay_path.display()
+            );
+        }
+        Ok(events)
+    }
+
+    /// 用尚未写入的事件替换重放文件内容（先写临时文件再改名，中途崩溃不会丢失事件）
+    pub async fn retain_for_replay(&self, remaining: &[ParsedEvent]) -> Result<()> {
+        if remaining.is_empty() {
+            return self.complete_replay().await;
+        }
+
+        let mut content = String::new();
+        for event in remaining {
+            content.push_str(&serde_json::to_string(event)?);
+            content.push('\n');
+        }
+
+        let mut tmp_path = self.replay_path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        let mut file = fs::File::create(&tmp_path).await?;
+        file.write_all(content.as_bytes()).await?;
+        file.sync_data().await?;
+        fs::rename(&tmp_path, &self.replay_path).await?;
+        Ok(())
+    }
+
+    /// 重放完成后删除重放文件
+    pub async fn complete_replay(&self) -> Result<()> {
+        match fs::remove_file(&self.replay_path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(EventListenerError::IO(e)),
+        }
+    }
+}
+
+/// 解析溢出文件内容，跳过损坏的行（如崩溃时写了一半的最后一行）
+fn parse_spill_lines(content: &str) -> Vec<ParsedEvent> {
+    let mut events = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<ParsedEvent>(line) {
+            Ok(event) => events.push(event),
+            Err(e) => warn!("⚠️ 跳过无法解析的溢出事件: 第{}行 - {}", index + 1, e),
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::token_creation_parser::TokenCreationEventData;
+
+    fn create_test_event(signature: &str) -> ParsedEvent {
+        ParsedEvent::TokenCreation(TokenCreationEventData {
+            project_config: "project_config".to_string(),
+            mint_address: "mint_address".to_string(),
+            name: "Test Token".to_string(),
+            symbol: "TEST".to_string(),
+            metadata_uri: "https://example.com/metadata.json".to_string(),
+            logo_uri: "https://example.com/logo.png".to_string(),
+            decimals: 9,
+            supply: 1000000,
+            creator: "creator".to_string(),
+            has_whitelist: false,
+            whitelist_deadline: 0,
+            created_at: 1234567890,
+            signature: signature.to_string(),
+            slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
+            extensions: None,
+            source: None,
+        })
+    }
+
+    fn temp_spill_path() -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("spill-test-{}", uuid::Uuid::new_v4()))
+            .join("spill.jsonl")
+    }
+
+    #[tokio::test]
+    async fn test_append_and_replay() {
+        let spill = SpillFile::new(temp_spill_path());
+        assert!(!spill.has_pending().await);
+        assert!(spill.take_for_replay().await.unwrap().is_empty());
+
+        spill
+            .append(&[create_test_event("sig_1"), create_test_event("sig_2")])
+            .await
+            .unwrap();
+        spill.append(&[create_test_event("sig_3")]).await.unwrap();
+        assert_eq!(spill.pending(), 3);
+        assert!(spill.has_pending().await);
+
+        let events = spill.take_for_replay().await.unwrap();
+        let signatures: Vec<&str> = events.iter().map(|e| e.signature()).collect();
+        assert_eq!(signatures, vec!["sig_1", "sig_2", "sig_3"]);
+        assert_eq!(spill.pending(), 0);
+
+        // 重放未完成时新溢出的事件写入新文件，遗留的重放文件优先处理
+        spill.append(&[create_test_event("sig_4")]).await.unwrap();
+        assert_eq!(spill.take_for_replay().await.unwrap().len(), 3);
+
+        spill.complete_replay().await.unwrap();
+        let events = spill.take_for_replay().await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].signature(), "sig_4");
+
+        spill.complete_replay().await.unwrap();
+        assert!(!spill.has_pending().await);
+        let _ = fs::remove_dir_all(spill.path().parent().unwrap()).await;
+    }
+
+    #[tokio::test]
+    async fn test_replay_file_kept_until_written() {
+        let spill = SpillFile::new(temp_spill_path());
+        spill
+            .append(&[
+                create_test_event("sig_1"),
+                create_test_event("sig_2"),
+                create_test_event("sig_3"),
+            ])
+            .await
+            .unwrap();
+        assert!(spill.is_backlogged());
+
+        // 只截掉已写入的事件，剩余事件在下一轮重放时仍然存在
+        let events = spill.take_for_replay().await.unwrap();
+        spill.retain_for_replay(&events[1..]).await.unwrap();
+        let events = spill.take_for_replay().await.unwrap();
+        let signatures: Vec<&str> = events.iter().map(|e| e.signature()).collect();
+        assert_eq!(signatures, vec!["sig_2", "sig_3"]);
+
+        // 文件处理完之前不会退出积压状态
+        assert!(!spill.try_finish_backlog().await);
+        spill.retain_for_replay(&[]).await.unwrap();
+        assert!(spill.try_finish_backlog().await);
+        assert!(!spill.is_backlogged());
+
+        let _ = fs::remove_dir_all(spill.path().parent().unwrap()).await;
+    }
+
+    #[test]
+    fn test_parse_spill_lines_skips_corrupted() {
+        let mut content = serde_json::to_string(&create_test_event("sig_1")).unwrap();
+        content.push_str("\n\n{\"TokenCreation\":{\"truncated");
+
+        let events = parse_spill_lines(&content);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].signature(), "sig_1");
+    }
+}
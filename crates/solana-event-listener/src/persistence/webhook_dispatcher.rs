@@ -0,0 +1,285 @@
+use crate::{config::settings::WebhookSubscriptionConfig, error::Result, persistence::EventStorage};
+use chrono::Utc;
+use database::events::{
+    webhook_delivery::WebhookDelivery,
+    webhook_subscription::{resolve_webhook_url, validate_webhook_url, WebhookSubscription},
+};
+use futures::stream::{self, StreamExt};
+use hmac::{Hmac, Mac};
+use mongodb::bson::oid::ObjectId;
+use reqwest::{header::CONTENT_TYPE, redirect::Policy, Client};
+use sha2::Sha256;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tracing::{error, info, warn};
+
+/// 检查待投递记录的间隔
+const DISPATCH_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 单轮最多处理的投递记录数
+const DISPATCH_BATCH_SIZE: usize = 100;
+
+/// 同时进行的投递请求数
+const DISPATCH_CONCURRENCY: usize = 8;
+
+/// 重试间隔上限
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(3600);
+
+/// 签名请求头：sha256=<hex(HMAC-SHA256(secret, "<timestamp>.<body>"))>
+pub const SIGNATURE_HEADER: &str = "X-Coinfair-Signature";
+/// 签名时间戳请求头（Unix秒）
+pub const TIMESTAMP_HEADER: &str = "X-Coinfair-Timestamp";
+/// 事件类型请求头
+pub const EVENT_TYPE_HEADER: &str = "X-Coinfair-Event";
+/// 投递记录ID请求头（接收方可用于幂等处理）
+pub const DELIVERY_ID_HEADER: &str = "X-Coinfair-Delivery";
+
+/// webhook投递任务
+///
+/// 定期读取已到期的待投递记录，使用订阅密钥签名后POST到订阅地址，
+/// 失败时按指数退避安排下次尝试，达到最大尝试次数或订阅已停用时标记为失败。
+/// 每次投递前重新解析订阅地址，拒绝内网和保留地址，并固定连接到校验过的地址、不跟随重定向
+pub struct WebhookDispatcher {
+    event_storage: Arc<EventStorage>,
+    timeout: Duration,
+    max_attempts: u32,
+    retry_base_delay: Duration,
+}
+
+impl WebhookDispatcher {
+    /// 创建新的webhook投递任务
+    pub fn new(event_storage: Arc<EventStorage>, config: &WebhookSubscriptionConfig) -> Self {
+        Self {
+            event_storage,
+            timeout: Duration::from_millis(config.timeout_ms),
+            max_attempts: config.max_attempts,
+            retry_base_delay: Duration::from_secs(config.retry_base_delay_secs),
+        }
+    }
+
+    /// 启动投递循环
+    pub async fn start(&self) -> Result<()> {
+        info!("🔔 启动webhook订阅投递任务，检查间隔{:?}", DISPATCH_POLL_INTERVAL);
+
+        let mut interval = tokio::time::interval(DISPATCH_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.dispatch_once().await {
+                error!("❌ webhook投递失败: {}", e);
+            }
+        }
+    }
+
+    /// 执行一轮投递，返回处理的记录数
+    pub async fn dispatch_once(&self) -> Result<usize> {
+        let deliveries = self
+            .event_storage
+            .find_due_webhook_deliveries(DISPATCH_BATCH_SIZE)
+            .await?;
+        if deliveries.is_empty() {
+            return Ok(0);
+        }
+
+        let mut subscription_ids: Vec<ObjectId> = deliveries.iter().map(|d| d.subscription_id).collect();
+        subscription_ids.sort();
+        subscription_ids.dedup();
+        let subscriptions: HashMap<ObjectId, WebhookSubscription> = self
+            .event_storage
+            .find_webhook_subscriptions_by_ids(&subscription_ids)
+            .await?
+            .into_iter()
+            .filter_map(|subscription| subscription.id.map(|id| (id, subscription)))
+            .collect();
+
+        let count = deliveries.len();
+        stream::iter(deliveries)
+            .for_each_concurrent(DISPATCH_CONCURRENCY, |delivery| {
+                let subscription = subscriptions.get(&delivery.subscription_id);
+                async move {
+                    if let Err(e) = self.deliver(delivery, subscription).await {
+                        error!("❌ 更新webhook投递记录失败: {}", e);
+                    }
+                }
+            })
+            .await;
+
+        Ok(count)
+    }
+
+    /// 投递单条记录并更新投递日志
+    async fn deliver(&self, delivery: WebhookDelivery, subscription: Option<&WebhookSubscription>) -> Result<()> {
+        let id = match delivery.id {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+
+        let subscription = match subscription {
+            Some(subscription) if subscription.active => subscription,
+            _ => {
+                self.event_storage
+                    .mark_webhook_delivery_failed(&id, None, "订阅已停用或已删除", None)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        if let Err(e) = validate_webhook_url(&subscription.url) {
+            warn!("🚫 webhook投递{}的目标地址不允许访问: {}", id, e);
+            self.event_storage
+                .mark_webhook_delivery_failed(&id, None, &e, None)
+                .await?;
+            return Ok(());
+        }
+
+        let body = build_delivery_body(&id, &delivery)?;
+        let timestamp = Utc::now().timestamp();
+        let signature = sign_payload(&subscription.secret, timestamp, &body);
+
+        let response = match self.pinned_client(&subscription.url).await {
+            Ok(client) => client
+                .post(&subscription.url)
+                .header(CONTENT_TYPE, "application/json")
+                .header(SIGNATURE_HEADER, signature)
+                .header(TIMESTAMP_HEADER, timestamp.to_string())
+                .header(EVENT_TYPE_HEADER, &delivery.event_type)
+                .header(DELIVERY_ID_HEADER, id.to_hex())
+                .body(body)
+                .send()
+                .await
+                .map_err(|e| format!("webhook请求失败: {}", e)),
+            Err(e) => Err(e),
+        };
+
+        let (status_code, error) = match response {
+            Ok(response) if response.status().is_success() => {
+                self.event_storage
+                    .mark_webhook_delivered(&id, response.status().as_u16())
+                    .await?;
+                return Ok(());
+            }
+            Ok(response) => {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                let body: String = body.chars().take(500).collect();
+                (Some(status.as_u16()), format!("webhook返回错误 ({}): {}", status, body))
+            }
+            Err(e) => (None, e),
+        };
+
+        let attempts = delivery.attempts + 1;
+        let next_attempt_at = if attempts < self.max_attempts {
+            let delay = retry_delay(self.retry_base_delay, attempts);
+            Some(Utc::now() + chrono::Duration::from_std(delay).unwrap_or_else(|_| chrono::Duration::hours(1)))
+        } else {
+            None
+        };
+
+        match next_attempt_at {
+            Some(next_attempt_at) => warn!(
+                "🔄 webhook投递{}第{}次失败，{}重试: {}",
+                id, attempts, next_attempt_at, error
+            ),
+            None => error!("❌ webhook投递{}已尝试{}次，放弃投递: {}", id, attempts, error),
+        }
+
+        self.event_storage
+            .mark_webhook_delivery_failed(&id, status_code, &error, next_attempt_at)
+            .await?;
+        Ok(())
+    }
+
+    /// 解析并校验订阅地址，创建只连接校验过的地址、不跟随重定向的客户端
+    ///
+    /// 地址在校验后才固定到客户端，避免域名在校验和连接之间被改指向内网
+    async fn pinned_client(&self, url: &str) -> std::result::Result<Client, String> {
+        let (host, addrs) = resolve_webhook_url(url).await?;
+        Client::builder()
+            .timeout(self.timeout)
+            .redirect(Policy::none())
+            .resolve_to_addrs(&host, &addrs)
+            .build()
+            .map_err(|e| format!("创建webhook客户端失败: {}", e))
+    }
+}
+
+/// 构建投递请求体
+fn build_delivery_body(id: &ObjectId, delivery: &WebhookDelivery) -> Result<String> {
+    let event: serde_json::Value = serde_json::from_str(&delivery.payload)?;
+    let body = serde_json::json!({
+        "delivery_id": id.to_hex(),
+        "event_type": delivery.event_type,
+        "signature": delivery.signature,
+        "slot": delivery.slot,
+        "event": event,
+    });
+    Ok(body.to_string())
+}
+
+/// 使用订阅密钥对请求体签名
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC可以接受任意长度的密钥");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// 计算第attempts次失败后的重试间隔（指数退避，上限1小时）
+fn retry_delay(base: Duration, attempts: u32) -> Duration {
+    let factor = 2_u32.saturating_pow(attempts.saturating_sub(1));
+    std::cmp::min(base.saturating_mul(factor), MAX_RETRY_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload() {
+        let signature = sign_payload("secret", 1700000000, r#"{"event_type":"swap"}"#);
+        // HMAC-SHA256("secret", "1700000000.{\"event_type\":\"swap\"}")
+        assert_eq!(
+            signature,
+            "sha256=93d62826c7291dabefbf90c0a416de172ba45c17ab4f23a03e13725a62693d93"
+        );
+
+        // 密钥、时间戳或请求体变化时签名不同
+        assert_ne!(signature, sign_payload("other", 1700000000, r#"{"event_type":"swap"}"#));
+        assert_ne!(
+            signature,
+            sign_payload("secret", 1700000001, r#"{"event_type":"swap"}"#)
+        );
+        assert_ne!(
+            signature,
+            sign_payload("secret", 1700000000, r#"{"event_type":"launch"}"#)
+        );
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let base = Duration::from_secs(10);
+        assert_eq!(retry_delay(base, 1), Duration::from_secs(10));
+        assert_eq!(retry_delay(base, 2), Duration::from_secs(20));
+        assert_eq!(retry_delay(base, 4), Duration::from_secs(80));
+        assert_eq!(retry_delay(base, 20), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn test_build_delivery_body() {
+        let id = ObjectId::new();
+        let delivery = WebhookDelivery::new(
+            ObjectId::new(),
+            "owner".to_string(),
+            "swap".to_string(),
+            "sig".to_string(),
+            100,
+            r#"{"Swap":{"pool_id":"pool"}}"#.to_string(),
+        );
+
+        let body: serde_json::Value = serde_json::from_str(&build_delivery_body(&id, &delivery).unwrap()).unwrap();
+        assert_eq!(body["delivery_id"], id.to_hex());
+        assert_eq!(body["event_type"], "swap");
+        assert_eq!(body["slot"], 100);
+        assert_eq!(body["event"]["Swap"]["pool_id"], "pool");
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    error::{EventListenerError, Result},
+    parser::ParsedEvent,
+    persistence::event_sink::EventSink,
+};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Serialize;
+use std::time::Duration;
+use tracing::debug;
+
+/// webhook请求体
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    batch_size: usize,
+    events: &'a [ParsedEvent],
+}
+
+/// HTTP webhook输出目标
+///
+/// 每个批次以JSON POST到配置的URL，非2xx响应视为失败并由SinkWorker重试
+pub struct WebhookSink {
+    client: Client,
+    url: String,
+    auth_token: Option<String>,
+}
+
+impl WebhookSink {
+    pub fn new(url: String, auth_token: Option<String>, timeout: Duration) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| EventListenerError::Network(format!("创建webhook客户端失败: {}", e)))?;
+
+        Ok(Self {
+            client,
+            url,
+            auth_token,
+        })
+    }
+}
+
+#[async_trait]
+impl EventSink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn write_batch(&self, events: &[ParsedEvent]) -> Result<u64> {
+        if events.is_empty() {
+            return Ok(0);
+        }
+
+        let payload = WebhookPayload {
+            batch_size: events.len(),
+            events,
+        };
+        let mut request = self.client.post(&self.url).json(&payload);
+        if let Some(token) = &self.auth_token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| EventListenerError::Network(format!("webhook请求失败: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(EventListenerError::Network(format!(
+                "webhook返回错误 ({}): {}",
+                status, body
+            )));
+        }
+
+        debug!("🌐 webhook已接收{}个事件", events.len());
+        Ok(events.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::token_creation_parser::TokenCreationEventData;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn create_test_event(signature: &str) -> ParsedEvent {
+        ParsedEvent::TokenCreation(TokenCreationEventData {
+            project_config: "project_config".to_string(),
+            mint_address: "mint_address".to_string(),
+            name: "Test Token".to_string(),
+            symbol: "TEST".to_string(),
+            metadata_uri: String::new(),
+            logo_uri: String::new(),
+            decimals: 9,
+            supply: 1000000,
+            creator: "creator".to_string(),
+            has_whitelist: false,
+            whitelist_deadline: 0,
+            created_at: 1234567890,
+            signature: signature.to_string(),
+            slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
+            extensions: None,
+            source: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_webhook_sink_posts_batch() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/events"))
+            .and(header("authorization", "Bearer secret"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sink = WebhookSink::new(
+            format!("{}/events", mock_server.uri()),
+            Some("secret".to_string()),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        let written = sink
+            .write_batch(&[create_test_event("sig_1"), create_test_event("sig_2")])
+            .await
+            .unwrap();
+        assert_eq!(written, 2);
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["batch_size"], 2);
+        assert_eq!(body["events"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_sink_error_status() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("unavailable"))
+            .mount(&mock_server)
+            .await;
+
+        let sink = WebhookSink::new(mock_server.uri(), None, Duration::from_secs(5)).unwrap();
+        let result = sink.write_batch(&[create_test_event("sig_1")]).await;
+        assert!(matches!(result, Err(EventListenerError::Network(_))));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{
+    error::Result,
+    parser::ParsedEvent,
+    persistence::{event_sink::EventSink, EventStorage},
+};
+use async_trait::async_trait;
+use database::events::{webhook_delivery::WebhookDelivery, webhook_subscription::WebhookSubscription};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::RwLock;
+use tracing::debug;
+
+/// webhook订阅输出目标
+///
+/// 事件批次写入主存储后，按订阅的过滤条件为每个匹配的订阅生成待投递记录，
+/// 实际发送由WebhookDispatcher完成，订阅列表按刷新间隔缓存
+pub struct WebhookSubscriptionSink {
+    event_storage: Arc<EventStorage>,
+    refresh_interval: Duration,
+    subscriptions: RwLock<Option<(Instant, Arc<Vec<WebhookSubscription>>)>>,
+}
+
+impl WebhookSubscriptionSink {
+    pub fn new(event_storage: Arc<EventStorage>, refresh_interval: Duration) -> Self {
+        Self {
+            event_storage,
+            refresh_interval,
+            subscriptions: RwLock::new(None),
+        }
+    }
+
+    /// 获取启用的订阅（缓存过期时重新加载）
+    async fn active_subscriptions(&self) -> Result<Arc<Vec<WebhookSubscription>>> {
+        if let Some((loaded_at, subscriptions)) = self.subscriptions.read().await.as_ref() {
+            if loaded_at.elapsed() < self.refresh_interval {
+                return Ok(Arc::clone(subscriptions));
+            }
+        }
+
+        let subscriptions = Arc::new(self.event_storage.find_active_webhook_subscriptions().await?);
+        *self.subscriptions.write().await = Some((Instant::now(), Arc::clone(&subscriptions)));
+        debug!("🔔 已加载{}个启用的webhook订阅", subscriptions.len());
+        Ok(subscriptions)
+    }
+}
+
+#[async_trait]
+impl EventSink for WebhookSubscriptionSink {
+    fn name(&self) -> &str {
+        "webhook_subscriptions"
+    }
+
+    async fn write_batch(&self, events: &[ParsedEvent]) -> Result<u64> {
+        let subscriptions = self.active_subscriptions().await?;
+        if subscriptions.is_empty() {
+            return Ok(events.len() as u64);
+        }
+
+        let deliveries = build_webhook_deliveries(events, &subscriptions)?;
+        if !deliveries.is_empty() {
+            let inserted = self.event_storage.insert_webhook_deliveries(&deliveries).await?;
+            debug!("🔔 {}个事件生成{}条webhook投递记录", events.len(), inserted);
+        }
+        Ok(events.len() as u64)
+    }
+}
+
+/// 为每个事件匹配订阅，生成待投递记录
+fn build_webhook_deliveries(
+    events: &[ParsedEvent],
+    subscriptions: &[WebhookSubscription],
+) -> Result<Vec<WebhookDelivery>> {
+    let mut deliveries = Vec::new();
+
+    for event in events {
+        let pool_address = event.pool_address();
+        let mints = event.mints();
+        let wallets = event.wallets();
+
+        let matched: Vec<_> = subscriptions
+            .iter()
+            .filter(|subscription| {
+                subscription
+                    .filter
+                    .matches(event.event_type(), pool_address, &mints, &wallets)
+            })
+            .filter_map(|subscription| subscription.id.map(|id| (id, subscription)))
+            .collect();
+        if matched.is_empty() {
+            continue;
+        }
+
+        let payload = serde_json::to_string(event)?;
+        for (subscription_id, subscription) in matched {
+            deliveries.push(WebhookDelivery::new(
+                subscription_id,
+                subscription.owner.clone(),
+                event.event_type().to_string(),
+                event.signature().to_string(),
+                event.slot(),
+                payload.clone(),
+            ));
+        }
+    }
+
+    Ok(deliveries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::token_creation_parser::TokenCreationEventData;
+    use database::events::webhook_subscription::WebhookFilter;
+    use mongodb::bson::oid::ObjectId;
+
+    fn create_test_event(signature: &str, mint_address: &str) -> ParsedEvent {
+        ParsedEvent::TokenCreation(TokenCreationEventData {
+            project_config: "project_config".to_string(),
+            mint_address: mint_address.to_string(),
+            name: "Test Token".to_string(),
+            symbol: "TEST".to_string(),
+            metadata_uri: String::new(),
+            logo_uri: String::new(),
+            decimals: 9,
+            supply: 1000000,
+            creator: "creator".to_string(),
+            has_whitelist: false,
+            whitelist_deadline: 0,
+            created_at: 1234567890,
+            signature: signature.to_string(),
+            slot: 12345,
+            instruction_index: 0,
+            event_ordinal: 0,
+            extensions: None,
+            source: None,
+        })
+    }
+
+    fn create_subscription(owner: &str, filter: WebhookFilter) -> WebhookSubscription {
+        let mut subscription = WebhookSubscription::new(
+            owner.to_string(),
+            "https://partner.example.com/hooks".to_string(),
+            "secret".to_string(),
+            filter,
+            None,
+        );
+        subscription.id = Some(ObjectId::new());
+        subscription
+    }
+
+    #[test]
+    fn test_build_webhook_deliveries() {
+        let events = vec![
+            create_test_event("sig_1", "mint_a"),
+            create_test_event("sig_2", "mint_b"),
+        ];
+        let subscriptions = vec![
+            create_subscription("all", WebhookFilter::default()),
+            create_subscription(
+                "mint_a_only",
+                WebhookFilter {
+                    mint: Some("mint_a".to_string()),
+                    ..Default::default()
+                },
+            ),
+            create_subscription(
+                "swaps_only",
+                WebhookFilter {
+                    event_types: vec!["swap".to_string()],
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        let deliveries = build_webhook_deliveries(&events, &subscriptions).unwrap();
+        let targets: Vec<(&str, &str)> = deliveries
+            .iter()
+            .map(|delivery| (delivery.owner.as_str(), delivery.signature.as_str()))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![("all", "sig_1"), ("mint_a_only", "sig_1"), ("all", "sig_2")]
+        );
+
+        let delivery = &deliveries[0];
+        assert_eq!(delivery.event_type, "token_creation");
+        assert_eq!(delivery.subscription_id, subscriptions[0].id.unwrap());
+        let payload: ParsedEvent = serde_json::from_str(&delivery.payload).unwrap();
+        assert_eq!(payload.signature(), "sig_1");
+    }
+}
@@ -1,6 +1,8 @@
 use crate::error::{EventListenerError, Result};
 use async_trait::async_trait;
+use database::clmm::position_event::ClmmPositionEventType;
 use database::events::event_model::event_model_repository::EventModelRepository;
+use mongodb::bson::Document;
 use solana_sdk::pubkey::Pubkey;
 use std::{collections::HashMap, sync::Arc};
 use tracing::info;
@@ -26,6 +28,20 @@ pub trait EventBackfillHandler: Send + Sync {
     fn checkpoint_event_name(&self) -> String {
         self.event_type_name().to_lowercase()
     }
+
+    /// 获取链上Anchor事件名称（用于计算discriminator，默认为事件类型名称）
+    ///
+    /// 不同程序存在同名事件时（如CPMM与CLMM的SwapEvent），注册名需区分而discriminator相同
+    fn discriminator_event_name(&self) -> &'static str {
+        self.event_type_name()
+    }
+
+    /// 获取本事件类型在目标集合中的范围过滤条件（默认为整个集合）
+    ///
+    /// 多种事件共用同一集合时（如CLMM仓位事件），重建索引只清理本事件类型的文档
+    fn collection_scope_filter(&self) -> Document {
+        Document::new()
+    }
 }
 
 /// LaunchEvent回填处理器
@@ -203,6 +219,97 @@ impl EventBackfillHandler for ClaimNFTEventHandler {
     }
 }
 
+/// MintNFTEvent回填处理器
+#[derive(Debug, Clone)]
+pub struct MintNFTEventHandler;
+
+#[async_trait]
+impl EventBackfillHandler for MintNFTEventHandler {
+    fn event_type_name(&self) -> &'static str {
+        "MintNFTEvent"
+    }
+
+    fn collection_name(&self) -> &'static str {
+        "NftMintEvent"
+    }
+
+    async fn get_oldest_event_signature(&self, repo: &EventModelRepository) -> Result<String> {
+        match repo.get_oldest_nft_mint_event().await {
+            Ok(Some(nft_mint)) => Ok(nft_mint.signature),
+            Ok(None) => {
+                info!("⚠️ 没有找到NftMintEvent，使用零签名");
+                Ok("1111111111111111111111111111111111111111111111111111111111111111".to_string())
+            }
+            Err(e) => Err(EventListenerError::Unknown(format!("获取最老NftMintEvent失败: {}", e))),
+        }
+    }
+
+    async fn signature_exists(&self, repo: &EventModelRepository, signature: &str) -> Result<bool> {
+        // 检查NftMintEvent集合中是否存在该签名
+        use mongodb::bson::doc;
+        let collection = repo
+            .get_database()
+            .collection::<mongodb::bson::Document>(self.collection_name());
+        let filter = doc! { "signature": signature };
+
+        match collection.count_documents(filter, None).await {
+            Ok(count) => Ok(count > 0),
+            Err(e) => Err(EventListenerError::Unknown(format!(
+                "检查NftMintEvent签名存在性失败: {}",
+                e
+            ))),
+        }
+    }
+}
+
+/// ReferralEstablishedEvent回填处理器
+///
+/// 回填时同时重建Refer集合中的上下级关系
+#[derive(Debug, Clone)]
+pub struct ReferralEstablishedEventHandler;
+
+#[async_trait]
+impl EventBackfillHandler for ReferralEstablishedEventHandler {
+    fn event_type_name(&self) -> &'static str {
+        "ReferralEstablishedEvent"
+    }
+
+    fn collection_name(&self) -> &'static str {
+        "ReferralEstablishedEvent"
+    }
+
+    async fn get_oldest_event_signature(&self, repo: &EventModelRepository) -> Result<String> {
+        match repo.get_oldest_referral_established_event().await {
+            Ok(Some(referral)) => Ok(referral.signature),
+            Ok(None) => {
+                info!("⚠️ 没有找到ReferralEstablishedEvent，使用零签名");
+                Ok("1111111111111111111111111111111111111111111111111111111111111111".to_string())
+            }
+            Err(e) => Err(EventListenerError::Unknown(format!(
+                "获取最老ReferralEstablishedEvent失败: {}",
+                e
+            ))),
+        }
+    }
+
+    async fn signature_exists(&self, repo: &EventModelRepository, signature: &str) -> Result<bool> {
+        // 检查ReferralEstablishedEvent集合中是否存在该签名
+        use mongodb::bson::doc;
+        let collection = repo
+            .get_database()
+            .collection::<mongodb::bson::Document>(self.collection_name());
+        let filter = doc! { "signature": signature };
+
+        match collection.count_documents(filter, None).await {
+            Ok(count) => Ok(count > 0),
+            Err(e) => Err(EventListenerError::Unknown(format!(
+                "检查ReferralEstablishedEvent签名存在性失败: {}",
+                e
+            ))),
+        }
+    }
+}
+
 /// PoolCreatedEvent回填处理器
 #[derive(Debug, Clone)]
 pub struct PoolCreatedEventHandler;
@@ -310,6 +417,10 @@ impl EventBackfillHandler for ReferralRewardEventHandler {
             ))),
         }
     }
+
+    fn collection_scope_filter(&self) -> Document {
+        mongodb::bson::doc! { "is_referral_reward": true }
+    }
 }
 
 /// InitPoolEvent回填处理器
@@ -450,6 +561,174 @@ impl EventBackfillHandler for SwapEventHandler {
     }
 }
 
+/// ClmmSwapEvent回填处理器
+///
+/// CLMM程序的SwapEvent与CPMM同名，注册名使用ClmmSwapEvent，discriminator仍按SwapEvent计算
+#[derive(Debug, Clone)]
+pub struct ClmmSwapEventHandler;
+
+#[async_trait]
+impl EventBackfillHandler for ClmmSwapEventHandler {
+    fn event_type_name(&self) -> &'static str {
+        "ClmmSwapEvent"
+    }
+
+    fn collection_name(&self) -> &'static str {
+        "ClmmSwapEvent"
+    }
+
+    fn discriminator_event_name(&self) -> &'static str {
+        "SwapEvent"
+    }
+
+    async fn get_oldest_event_signature(&self, repo: &EventModelRepository) -> Result<String> {
+        match repo.get_oldest_clmm_swap_event().await {
+            Ok(Some(swap)) => Ok(swap.signature),
+            Ok(None) => {
+                info!("⚠️ 没有找到ClmmSwapEvent，使用零签名");
+                Ok("1111111111111111111111111111111111111111111111111111111111111111".to_string())
+            }
+            Err(e) => Err(EventListenerError::Unknown(format!(
+                "获取最老ClmmSwapEvent失败: {}",
+                e
+            ))),
+        }
+    }
+
+    async fn signature_exists(&self, repo: &EventModelRepository, signature: &str) -> Result<bool> {
+        // 检查ClmmSwapEvent集合中是否存在该签名
+        use mongodb::bson::doc;
+        let collection = repo
+            .get_database()
+            .collection::<mongodb::bson::Document>(self.collection_name());
+        let filter = doc! { "signature": signature };
+
+        match collection.count_documents(filter, None).await {
+            Ok(count) => Ok(count > 0),
+            Err(e) => Err(EventListenerError::Unknown(format!(
+                "检查ClmmSwapEvent签名存在性失败: {}",
+                e
+            ))),
+        }
+    }
+}
+
+/// CLMM仓位事件回填处理器
+///
+/// 开仓/增加流动性/减少流动性/领取手续费四类事件共用ClmmPositionEvent流水集合，按event_type区分
+#[derive(Debug, Clone)]
+pub struct ClmmPositionEventHandler {
+    event_type: ClmmPositionEventType,
+}
+
+impl ClmmPositionEventHandler {
+    /// 创建指定仓位事件类型的回填处理器
+    pub fn new(event_type: ClmmPositionEventType) -> Self {
+        Self { event_type }
+    }
+}
+
+#[async_trait]
+impl EventBackfillHandler for ClmmPositionEventHandler {
+    fn event_type_name(&self) -> &'static str {
+        match self.event_type {
+            ClmmPositionEventType::CreatePosition => "CreatePersonalPositionEvent",
+            ClmmPositionEventType::IncreaseLiquidity => "IncreaseLiquidityEvent",
+            ClmmPositionEventType::DecreaseLiquidity => "DecreaseLiquidityEvent",
+            ClmmPositionEventType::CollectPersonalFee => "CollectPersonalFeeEvent",
+        }
+    }
+
+    fn collection_name(&self) -> &'static str {
+        "ClmmPositionEvent"
+    }
+
+    async fn get_oldest_event_signature(&self, repo: &EventModelRepository) -> Result<String> {
+        match repo.get_oldest_clmm_position_event(self.event_type).await {
+            Ok(Some(event)) => Ok(event.signature),
+            Ok(None) => {
+                info!("⚠️ 没有找到{}，使用零签名", self.event_type_name());
+                Ok("1111111111111111111111111111111111111111111111111111111111111111".to_string())
+            }
+            Err(e) => Err(EventListenerError::Unknown(format!(
+                "获取最老{}失败: {}",
+                self.event_type_name(),
+                e
+            ))),
+        }
+    }
+
+    async fn signature_exists(&self, repo: &EventModelRepository, signature: &str) -> Result<bool> {
+        // 检查ClmmPositionEvent集合中是否存在该签名的同类事件
+        use mongodb::bson::doc;
+        let collection = repo
+            .get_database()
+            .collection::<mongodb::bson::Document>(self.collection_name());
+        let filter = doc! { "signature": signature, "event_type": self.event_type.as_str() };
+
+        match collection.count_documents(filter, None).await {
+            Ok(count) => Ok(count > 0),
+            Err(e) => Err(EventListenerError::Unknown(format!(
+                "检查{}签名存在性失败: {}",
+                self.event_type_name(),
+                e
+            ))),
+        }
+    }
+
+    fn collection_scope_filter(&self) -> Document {
+        mongodb::bson::doc! { "event_type": self.event_type.as_str() }
+    }
+}
+
+/// CLMM协议费领取事件回填处理器
+///
+/// collect_protocol_fee与collect_fund_fee共用CollectProtocolFeeEvent，写入ClmmProtocolFeeEvent流水集合
+#[derive(Debug, Clone)]
+pub struct ClmmProtocolFeeEventHandler;
+
+#[async_trait]
+impl EventBackfillHandler for ClmmProtocolFeeEventHandler {
+    fn event_type_name(&self) -> &'static str {
+        "CollectProtocolFeeEvent"
+    }
+
+    fn collection_name(&self) -> &'static str {
+        "ClmmProtocolFeeEvent"
+    }
+
+    async fn get_oldest_event_signature(&self, repo: &EventModelRepository) -> Result<String> {
+        match repo.get_oldest_clmm_protocol_fee_event().await {
+            Ok(Some(event)) => Ok(event.signature),
+            Ok(None) => {
+                info!("⚠️ 没有找到ClmmProtocolFeeEvent，使用零签名");
+                Ok("1111111111111111111111111111111111111111111111111111111111111111".to_string())
+            }
+            Err(e) => Err(EventListenerError::Unknown(format!(
+                "获取最老ClmmProtocolFeeEvent失败: {}",
+                e
+            ))),
+        }
+    }
+
+    async fn signature_exists(&self, repo: &EventModelRepository, signature: &str) -> Result<bool> {
+        // 检查ClmmProtocolFeeEvent集合中是否存在该签名
+        use mongodb::bson::doc;
+        let collection = repo
+            .get_database()
+            .collection::<mongodb::bson::Document>(self.collection_name());
+        let filter = doc! { "signature": signature };
+
+        match collection.count_documents(filter, None).await {
+            Ok(count) => Ok(count > 0),
+            Err(e) => Err(EventListenerError::Unknown(format!(
+                "检查ClmmProtocolFeeEvent签名存在性失败: {}",
+                e
+            ))),
+        }
+    }
+}
+
 /// 事件回填处理器注册中心
 ///
 /// 管理所有事件类型的处理器，支持动态注册和查询
@@ -475,11 +754,24 @@ impl BackfillEventRegistry {
         self.register_handler("TokenCreationEvent", Arc::new(TokenCreationEventHandler));
         self.register_handler("DepositEvent", Arc::new(DepositEventHandler));
         self.register_handler("ClaimNFTEvent", Arc::new(ClaimNFTEventHandler));
+        self.register_handler("MintNFTEvent", Arc::new(MintNFTEventHandler));
+        self.register_handler("ReferralEstablishedEvent", Arc::new(ReferralEstablishedEventHandler));
         self.register_handler("PoolCreatedEvent", Arc::new(PoolCreatedEventHandler));
         self.register_handler("ReferralRewardEvent", Arc::new(ReferralRewardEventHandler));
         self.register_handler("InitPoolEvent", Arc::new(InitPoolEventHandler));
         self.register_handler("LpChangeEvent", Arc::new(LpChangeEventHandler));
         self.register_handler("SwapEvent", Arc::new(SwapEventHandler));
+        self.register_handler("ClmmSwapEvent", Arc::new(ClmmSwapEventHandler));
+        for event_type in [
+            ClmmPositionEventType::CreatePosition,
+            ClmmPositionEventType::IncreaseLiquidity,
+            ClmmPositionEventType::DecreaseLiquidity,
+            ClmmPositionEventType::CollectPersonalFee,
+        ] {
+            let handler = ClmmPositionEventHandler::new(event_type);
+            self.register_handler(handler.event_type_name(), Arc::new(handler));
+        }
+        self.register_handler("CollectProtocolFeeEvent", Arc::new(ClmmProtocolFeeEventHandler));
     }
 
     /// 注册事件处理器
@@ -503,6 +795,14 @@ impl BackfillEventRegistry {
         self.handlers.contains_key(event_type)
     }
 
+    /// 获取事件类型对应的discriminator事件名称（未注册的事件类型直接使用原名称）
+    pub fn get_discriminator_event_name(&self, event_type: &str) -> String {
+        self.handlers
+            .get(event_type)
+            .map(|handler| handler.discriminator_event_name().to_string())
+            .unwrap_or_else(|| event_type.to_string())
+    }
+
     /// 获取注册的处理器数量
     pub fn handler_count(&self) -> usize {
         self.handlers.len()
@@ -518,7 +818,7 @@ impl Default for BackfillEventRegistry {
 /// 回填事件配置
 ///
 /// 描述单个事件类型的回填配置
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BackfillEventConfig {
     /// 事件类型名称
     pub event_type: String,
@@ -573,7 +873,15 @@ mod tests {
         assert!(registry.supports_event_type("InitPoolEvent"));
         assert!(registry.supports_event_type("LpChangeEvent"));
         assert!(registry.supports_event_type("SwapEvent"));
-        assert_eq!(registry.handler_count(), 9);
+        assert!(registry.supports_event_type("ClmmSwapEvent"));
+        assert!(registry.supports_event_type("CreatePersonalPositionEvent"));
+        assert!(registry.supports_event_type("IncreaseLiquidityEvent"));
+        assert!(registry.supports_event_type("DecreaseLiquidityEvent"));
+        assert!(registry.supports_event_type("CollectPersonalFeeEvent"));
+        assert!(registry.supports_event_type("MintNFTEvent"));
+        assert!(registry.supports_event_type("ReferralEstablishedEvent"));
+        assert!(registry.supports_event_type("CollectProtocolFeeEvent"));
+        assert_eq!(registry.handler_count(), 17);
 
         let event_types = registry.get_registered_event_types();
         assert!(event_types.contains(&"LaunchEvent".to_string()));
@@ -585,6 +893,22 @@ mod tests {
         assert!(event_types.contains(&"InitPoolEvent".to_string()));
         assert!(event_types.contains(&"LpChangeEvent".to_string()));
         assert!(event_types.contains(&"SwapEvent".to_string()));
+        assert!(event_types.contains(&"ClmmSwapEvent".to_string()));
+    }
+
+    #[test]
+    fn test_collection_scope_filter() {
+        assert!(LaunchEventHandler.collection_scope_filter().is_empty());
+        assert_eq!(
+            ReferralRewardEventHandler.collection_scope_filter(),
+            mongodb::bson::doc! { "is_referral_reward": true }
+        );
+
+        let handler = ClmmPositionEventHandler::new(ClmmPositionEventType::IncreaseLiquidity);
+        assert_eq!(
+            handler.collection_scope_filter(),
+            mongodb::bson::doc! { "event_type": ClmmPositionEventType::IncreaseLiquidity.as_str() }
+        );
     }
 
     #[test]
@@ -637,6 +961,28 @@ mod tests {
         assert_eq!(handler.checkpoint_event_name(), "claimnftevent");
     }
 
+    #[test]
+    fn test_referral_program_event_handler_properties() {
+        let handler = MintNFTEventHandler;
+        assert_eq!(handler.event_type_name(), "MintNFTEvent");
+        assert_eq!(handler.collection_name(), "NftMintEvent");
+        assert_eq!(handler.checkpoint_event_name(), "mintnftevent");
+
+        let handler = ReferralEstablishedEventHandler;
+        assert_eq!(handler.event_type_name(), "ReferralEstablishedEvent");
+        assert_eq!(handler.collection_name(), "ReferralEstablishedEvent");
+        assert_eq!(handler.checkpoint_event_name(), "referralestablishedevent");
+    }
+
+    #[test]
+    fn test_clmm_protocol_fee_event_handler_properties() {
+        let handler = ClmmProtocolFeeEventHandler;
+
+        assert_eq!(handler.event_type_name(), "CollectProtocolFeeEvent");
+        assert_eq!(handler.collection_name(), "ClmmProtocolFeeEvent");
+        assert_eq!(handler.checkpoint_event_name(), "collectprotocolfeeevent");
+    }
+
     #[test]
     fn test_pool_created_event_handler_properties() {
         let handler = PoolCreatedEventHandler;
@@ -698,4 +1044,38 @@ mod tests {
         assert_eq!(handler.collection_name(), "SwapEvent");
         assert_eq!(handler.checkpoint_event_name(), "swapevent");
     }
+
+    #[test]
+    fn test_clmm_swap_event_handler_properties() {
+        let handler = ClmmSwapEventHandler;
+
+        assert_eq!(handler.event_type_name(), "ClmmSwapEvent");
+        assert_eq!(handler.collection_name(), "ClmmSwapEvent");
+        assert_eq!(handler.checkpoint_event_name(), "clmmswapevent");
+        assert_eq!(handler.discriminator_event_name(), "SwapEvent");
+    }
+
+    #[test]
+    fn test_clmm_position_event_handler_properties() {
+        let handler = ClmmPositionEventHandler::new(ClmmPositionEventType::IncreaseLiquidity);
+
+        assert_eq!(handler.event_type_name(), "IncreaseLiquidityEvent");
+        assert_eq!(handler.collection_name(), "ClmmPositionEvent");
+        assert_eq!(handler.checkpoint_event_name(), "increaseliquidityevent");
+        assert_eq!(handler.discriminator_event_name(), "IncreaseLiquidityEvent");
+
+        let handler = ClmmPositionEventHandler::new(ClmmPositionEventType::CreatePosition);
+        assert_eq!(handler.event_type_name(), "CreatePersonalPositionEvent");
+        assert_eq!(handler.collection_name(), "ClmmPositionEvent");
+    }
+
+    #[test]
+    fn test_get_discriminator_event_name() {
+        let registry = BackfillEventRegistry::new();
+
+        assert_eq!(registry.get_discriminator_event_name("ClmmSwapEvent"), "SwapEvent");
+        assert_eq!(registry.get_discriminator_event_name("SwapEvent"), "SwapEvent");
+        assert_eq!(registry.get_discriminator_event_name("LaunchEvent"), "LaunchEvent");
+        assert_eq!(registry.get_discriminator_event_name("UnknownEvent"), "UnknownEvent");
+    }
 }
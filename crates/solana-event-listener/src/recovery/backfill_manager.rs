@@ -2,20 +2,32 @@ use crate::{
     config::EventListenerConfig,
     error::Result,
     metrics::MetricsCollector,
-    parser::EventParserRegistry,
+    parser::{
+        event_parser::{calculate_event_discriminator, ParserKey},
+        EventParserRegistry,
+    },
     recovery::{
         backfill_handler::{BackfillEventConfig, BackfillEventRegistry},
         backfill_task_context::BackfillTaskContext,
         checkpoint_persistence::CheckpointPersistence,
+        gap_tracker::GapTracker,
         scan_record_persistence::ScanRecordPersistence,
     },
+    subscriber::endpoint_pool::{EndpointPool, EndpointRole},
     BatchWriter,
 };
-use solana_client::rpc_client::RpcClient;
-use solana_sdk::commitment_config::CommitmentConfig;
-use std::{sync::Arc, time::Duration};
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+use tokio::{sync::Mutex, task::JoinHandle};
 use tracing::{error, info, warn};
 
+/// 运行中的回填任务（按事件类型和程序ID索引）
+type EventTaskMap = HashMap<(String, Pubkey), (BackfillEventConfig, JoinHandle<()>)>;
+
 /// 通用回填服务管理器
 ///
 /// 支持多种事件类型的回填，使用事件处理器策略模式
@@ -24,7 +36,8 @@ use tracing::{error, info, warn};
 #[allow(dead_code)]
 pub struct BackfillManager {
     config: Arc<EventListenerConfig>,
-    rpc_client: Arc<RpcClient>,
+    /// RPC端点池（支持多端点故障切换）
+    endpoint_pool: Arc<EndpointPool>,
     parser_registry: Arc<EventParserRegistry>,
     batch_writer: Arc<BatchWriter>,
     metrics: Arc<MetricsCollector>,
@@ -32,8 +45,10 @@ pub struct BackfillManager {
     scan_record_persistence: Arc<ScanRecordPersistence>,
     /// 事件处理器注册中心
     event_registry: Arc<BackfillEventRegistry>,
-    /// 事件配置列表
-    event_configs: Vec<BackfillEventConfig>,
+    /// 事件配置列表（运行时可更新）
+    event_configs: RwLock<Vec<BackfillEventConfig>>,
+    /// 运行中的事件回填任务
+    event_tasks: Mutex<EventTaskMap>,
     /// 默认检查间隔
     default_check_interval: Duration,
 }
@@ -51,12 +66,9 @@ impl BackfillManager {
         scan_record_persistence: Arc<ScanRecordPersistence>,
         event_configs: Vec<BackfillEventConfig>,
         default_check_interval_secs: u64,
-    ) -> Self {
+    ) -> Result<Self> {
         let config = Arc::new(config.clone());
-        let rpc_client = Arc::new(RpcClient::new_with_commitment(
-            &config.solana.rpc_url,
-            CommitmentConfig::confirmed(),
-        ));
+        let endpoint_pool = Arc::new(EndpointPool::new(&config, EndpointRole::Rpc)?);
 
         let event_registry = Arc::new(BackfillEventRegistry::new());
         info!("🔧 回填管理器配置了 {} 种事件类型", event_configs.len());
@@ -70,57 +82,172 @@ impl BackfillManager {
 
         let manager = Self {
             config,
-            rpc_client,
+            endpoint_pool,
             parser_registry,
             batch_writer,
             metrics,
             checkpoint_persistence,
             scan_record_persistence,
             event_registry,
-            event_configs,
+            event_configs: RwLock::new(event_configs),
+            event_tasks: Mutex::new(HashMap::new()),
             default_check_interval: Duration::from_secs(default_check_interval_secs),
         };
 
         info!("✅ 回填管理器初始化完成，ParserKey配置已在注册表构造时设置");
 
-        manager
+        Ok(manager)
     }
 
     /// 启动多事件回填服务
+    ///
+    /// 每种启用的事件类型启动一个独立任务，运行时配置变化由reconcile_event_configs调整
     pub async fn start(&self) -> Result<()> {
-        info!("🔄 启动通用回填服务，支持 {} 种事件类型", self.event_configs.len());
+        let event_configs = self.get_event_configs();
+        info!("🔄 启动通用回填服务，支持 {} 种事件类型", event_configs.len());
+
+        for event_config in event_configs.iter().filter(|config| !config.enabled) {
+            info!("⏸️ 跳过已禁用的事件类型: {}", event_config.event_type);
+        }
+
+        self.reconcile_event_configs(event_configs).await;
+
+        if self.event_tasks.lock().await.is_empty() {
+            warn!("⚠️ 没有启用的事件类型，等待运行时配置启用回填任务");
+        }
+
+        Ok(())
+    }
+
+    /// 按新的事件配置调整回填任务
+    ///
+    /// 停止已移除、已禁用或配置变化的任务，启动新增的任务，并同步解析器的回填ParserKey
+    pub async fn reconcile_event_configs(&self, event_configs: Vec<BackfillEventConfig>) {
+        let desired: HashMap<(String, Pubkey), BackfillEventConfig> = event_configs
+            .iter()
+            .filter(|config| config.enabled)
+            .map(|config| ((config.event_type.clone(), config.program_id), config.clone()))
+            .collect();
 
-        // 启动每种事件类型的回填任务
-        let mut handles = Vec::new();
+        let mut tasks = self.event_tasks.lock().await;
+        let mut stopped = 0;
+        tasks.retain(|key, (running_config, handle)| match desired.get(key) {
+            Some(config) if config == running_config && !handle.is_finished() => true,
+            _ => {
+                info!("⏹️ 停止 {} 事件回填任务 (程序ID: {})", key.0, key.1);
+                handle.abort();
+                stopped += 1;
+                false
+            }
+        });
 
-        for event_config in &self.event_configs {
-            if !event_config.enabled {
-                info!("⏸️ 跳过已禁用的事件类型: {}", event_config.event_type);
+        let mut started = 0;
+        for (key, config) in desired {
+            if tasks.contains_key(&key) {
                 continue;
             }
+            let handle = self.spawn_event_task(config.clone());
+            tasks.insert(key, (config, handle));
+            started += 1;
+        }
 
-            let config = event_config.clone();
-            let task_context = self.create_task_context();
+        self.parser_registry
+            .set_backfill_parser_keys(compute_backfill_parser_keys(&self.event_registry, &event_configs));
+        *self.event_configs.write().unwrap() = event_configs;
 
-            let handle = tokio::spawn(async move { task_context.start_event_backfill_loop(config).await });
+        info!(
+            "🔄 回填任务已按运行时配置调整: 启动{}个, 停止{}个, 运行中{}个",
+            started,
+            stopped,
+            tasks.len()
+        );
+    }
 
-            handles.push(handle);
+    /// 停止所有事件回填任务
+    pub async fn stop(&self) {
+        let mut tasks = self.event_tasks.lock().await;
+        for (_, (_, handle)) in tasks.drain() {
+            handle.abort();
         }
+    }
 
-        if handles.is_empty() {
-            warn!("⚠️ 没有启用的事件类型，回填服务将退出");
+    /// 启动单个事件类型的回填任务
+    fn spawn_event_task(&self, event_config: BackfillEventConfig) -> JoinHandle<()> {
+        let task_context = self.create_task_context();
+        tokio::spawn(async move {
+            let event_type = event_config.event_type.clone();
+            if let Err(e) = task_context.start_event_backfill_loop(event_config).await {
+                error!("❌ {} 回填任务异常终止: {}", event_type, e);
+            }
+        })
+    }
+
+    /// 启动断线缺口回填服务
+    ///
+    /// 消费GapTracker在WebSocket重连时调度的缺口窗口，立即执行定向回填
+    pub async fn run_gap_recovery(&self, gap_tracker: Arc<GapTracker>) -> Result<()> {
+        let enabled = self
+            .config
+            .backfill
+            .as_ref()
+            .and_then(|backfill| backfill.gap_backfill_enabled)
+            .unwrap_or(true);
+        if !enabled {
+            info!("⏸️ 断线缺口回填已禁用");
             return Ok(());
         }
 
-        info!("🚀 已启动 {} 个事件回填任务", handles.len());
+        let mut gap_receiver = match gap_tracker.take_receiver() {
+            Some(receiver) => receiver,
+            None => {
+                warn!("⚠️ 断线缺口接收器已被占用，跳过启动缺口回填服务");
+                return Ok(());
+            }
+        };
+
+        info!("🩹 启动断线缺口回填服务");
+        let task_context = self.create_task_context();
 
-        // 等待所有任务完成（实际上应该永远运行）
-        for handle in handles {
-            if let Err(e) = handle.await {
-                error!("❌ 回填任务异常终止: {}", e);
+        while let Some(gap) = gap_receiver.recv().await {
+            match task_context.backfill_gap(&gap, &gap_tracker).await {
+                Ok(result) => {
+                    let recovery_latency = gap.reconnected_at.elapsed();
+                    info!(
+                        "✅ 程序 {} 缺口回填完成: {}个签名, {}个slot, 回填{}个, 恢复耗时{}ms",
+                        gap.program_id,
+                        result.gap_signatures,
+                        result.gap_slots,
+                        result.backfilled_count,
+                        recovery_latency.as_millis()
+                    );
+                    if result.failed_count > 0 || result.remaining_signatures > 0 {
+                        warn!(
+                            "⚠️ 程序 {} 缺口未完全回填: 失败{}个交易, 剩余{}个签名已重新排队",
+                            gap.program_id, result.failed_count, result.remaining_signatures
+                        );
+                    }
+
+                    if let Err(e) = self
+                        .metrics
+                        .record_gap_backfill(
+                            result.gap_signatures,
+                            result.gap_slots,
+                            result.backfilled_count,
+                            recovery_latency,
+                        )
+                        .await
+                    {
+                        warn!("记录缺口回填指标失败: {}", e);
+                    }
+                }
+                Err(e) => {
+                    // 缺口回填失败时由周期回填兜底
+                    error!("❌ 程序 {} 缺口回填失败: {}", gap.program_id, e);
+                }
             }
         }
 
+        info!("🩹 断线缺口回填服务已停止");
         Ok(())
     }
 
@@ -128,7 +255,7 @@ impl BackfillManager {
     fn create_task_context(&self) -> BackfillTaskContext {
         BackfillTaskContext {
             config: Arc::clone(&self.config),
-            rpc_client: Arc::clone(&self.rpc_client),
+            endpoint_pool: Arc::clone(&self.endpoint_pool),
             parser_registry: Arc::clone(&self.parser_registry),
             batch_writer: Arc::clone(&self.batch_writer),
             metrics: Arc::clone(&self.metrics),
@@ -139,14 +266,30 @@ impl BackfillManager {
         }
     }
 
+    /// 获取RPC端点池（用于指标导出）
+    pub fn endpoint_pool(&self) -> Arc<EndpointPool> {
+        Arc::clone(&self.endpoint_pool)
+    }
+
+    /// 获取扫描记录持久化服务（用于状态查询）
+    pub fn scan_record_persistence(&self) -> Arc<ScanRecordPersistence> {
+        Arc::clone(&self.scan_record_persistence)
+    }
+
     /// 获取事件配置（用于测试和调试）
-    pub fn get_event_configs(&self) -> &[BackfillEventConfig] {
-        &self.event_configs
+    pub fn get_event_configs(&self) -> Vec<BackfillEventConfig> {
+        self.event_configs.read().unwrap().clone()
     }
 
     /// 获取已启用的事件配置
-    pub fn get_enabled_event_configs(&self) -> Vec<&BackfillEventConfig> {
-        self.event_configs.iter().filter(|config| config.enabled).collect()
+    pub fn get_enabled_event_configs(&self) -> Vec<BackfillEventConfig> {
+        self.event_configs
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|config| config.enabled)
+            .cloned()
+            .collect()
     }
 
     /// 检查是否支持某种事件类型
@@ -160,6 +303,28 @@ impl BackfillManager {
     }
 }
 
+/// 计算已启用回填事件对应的ParserKey集合
+///
+/// 同名事件（如ClmmSwapEvent与SwapEvent）通过处理器映射到链上事件名计算discriminator
+pub fn compute_backfill_parser_keys(
+    event_registry: &BackfillEventRegistry,
+    event_configs: &[BackfillEventConfig],
+) -> HashSet<ParserKey> {
+    event_configs
+        .iter()
+        .filter(|config| config.enabled)
+        .map(|config| {
+            let discriminator_event_name = event_registry.get_discriminator_event_name(&config.event_type);
+            let discriminator = calculate_event_discriminator(&discriminator_event_name);
+            info!(
+                "🔑 回填事件 {} 的ParserKey: program={}, discriminator={:?}",
+                config.event_type, config.program_id, discriminator
+            );
+            ParserKey::for_program(config.program_id, discriminator)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,6 +364,26 @@ mod tests {
         assert!(event_configs[1].enabled);
     }
 
+    #[test]
+    fn test_compute_backfill_parser_keys() {
+        let registry = BackfillEventRegistry::new();
+        let program_id = Pubkey::from_str("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX").unwrap();
+
+        let event_configs = vec![
+            BackfillEventConfig::new("SwapEvent", program_id),
+            BackfillEventConfig::new("LaunchEvent", program_id).with_enabled(false),
+        ];
+        let keys = compute_backfill_parser_keys(&registry, &event_configs);
+
+        // 禁用的事件不参与回填ParserKey计算
+        assert_eq!(keys.len(), 1);
+        let swap_key = ParserKey::for_program(program_id, calculate_event_discriminator("SwapEvent"));
+        assert!(keys.contains(&swap_key));
+
+        let keys = compute_backfill_parser_keys(&registry, &[]);
+        assert!(keys.is_empty());
+    }
+
     #[test]
     fn test_event_registry_functionality() {
         let registry = BackfillEventRegistry::new();
@@ -212,10 +397,19 @@ mod tests {
         assert!(registry.supports_event_type("ReferralRewardEvent"));
         assert!(registry.supports_event_type("InitPoolEvent"));
         assert!(registry.supports_event_type("LpChangeEvent"));
+        assert!(registry.supports_event_type("SwapEvent"));
+        assert!(registry.supports_event_type("ClmmSwapEvent"));
+        assert!(registry.supports_event_type("CreatePersonalPositionEvent"));
+        assert!(registry.supports_event_type("IncreaseLiquidityEvent"));
+        assert!(registry.supports_event_type("DecreaseLiquidityEvent"));
+        assert!(registry.supports_event_type("CollectPersonalFeeEvent"));
+        assert!(registry.supports_event_type("MintNFTEvent"));
+        assert!(registry.supports_event_type("ReferralEstablishedEvent"));
+        assert!(registry.supports_event_type("CollectProtocolFeeEvent"));
         assert!(!registry.supports_event_type("UnsupportedEvent"));
 
         let event_types = registry.get_registered_event_types();
-        assert_eq!(event_types.len(), 8);
+        assert_eq!(event_types.len(), 17);
         assert!(event_types.contains(&"LaunchEvent".to_string()));
         assert!(event_types.contains(&"TokenCreationEvent".to_string()));
         assert!(event_types.contains(&"DepositEvent".to_string()));
@@ -2,19 +2,24 @@ use crate::{
     config::EventListenerConfig,
     error::{EventListenerError, Result},
     metrics::MetricsCollector,
-    parser::{EventDataSource, EventParserRegistry},
+    parser::{
+        transaction_fallback::{is_log_truncated, recover_truncated_events},
+        EventDataSource, EventParserRegistry,
+    },
     recovery::{
         backfill_handler::{BackfillEventConfig, BackfillEventRegistry, EventBackfillHandler},
         checkpoint_persistence::CheckpointPersistence,
+        gap_tracker::{GapTracker, GapWindow},
         scan_record_persistence::ScanRecordPersistence,
     },
+    subscriber::endpoint_pool::EndpointPool,
     BatchWriter,
 };
 use anyhow::anyhow;
 use chrono::Utc;
 use database::events::event_scanner::model::{EventScannerCheckpoints, ScanRecords, ScanStatus};
 use solana_client::{
-    rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
     rpc_config::RpcTransactionConfig,
     rpc_response::RpcLogsResponse,
 };
@@ -32,6 +37,23 @@ pub struct SignatureWithSlot {
     pub slot: u64,
 }
 
+/// 断线缺口回填结果
+#[derive(Debug, Clone, Default)]
+pub struct GapBackfillResult {
+    /// 缺口内的签名总数
+    pub gap_signatures: u64,
+    /// 缺口跨越的slot数
+    pub gap_slots: u64,
+    /// 实时路径已处理、跳过的签名数
+    pub skipped_signatures: u64,
+    /// 成功回填的交易数
+    pub backfilled_count: u64,
+    /// 回填失败的交易数
+    pub failed_count: u64,
+    /// 超出单次上限、交由后续缺口回填的签名数
+    pub remaining_signatures: u64,
+}
+
 /// 回填任务上下文
 ///
 /// 包含执行单个事件类型回填所需的所有组件
@@ -39,7 +61,7 @@ pub struct SignatureWithSlot {
 #[derive(Clone)]
 pub struct BackfillTaskContext {
     pub config: Arc<EventListenerConfig>,
-    pub rpc_client: Arc<RpcClient>,
+    pub endpoint_pool: Arc<EndpointPool>,
     pub parser_registry: Arc<EventParserRegistry>,
     pub batch_writer: Arc<BatchWriter>,
     pub metrics: Arc<MetricsCollector>,
@@ -195,6 +217,169 @@ impl BackfillTaskContext {
         Ok(())
     }
 
+    /// 回填WebSocket断线期间的缺口
+    ///
+    /// 从断线前最后处理的签名开始向新翻页，直到链上最新签名，
+    /// 跳过实时路径已处理的签名后按时间顺序回填。
+    /// 签名数超过单次上限时只回填最老的一段，扫描记录如实记录覆盖范围，
+    /// 剩余部分以新的缺口窗口重新排队，保证缺口从旧到新连续回填
+    pub async fn backfill_gap(&self, gap: &GapWindow, gap_tracker: &GapTracker) -> Result<GapBackfillResult> {
+        const GAP_EVENT_NAME: &str = "GapBackfill";
+        let max_signatures = self
+            .config
+            .backfill
+            .as_ref()
+            .and_then(|backfill| backfill.gap_backfill_max_signatures)
+            .unwrap_or(5000)
+            .max(1);
+
+        let scan_id = Uuid::new_v4().to_string();
+        let mut scan_record = self
+            .create_scan_record(
+                &scan_id,
+                &gap.until_signature,
+                Some(gap.until_slot),
+                "",
+                None,
+                &gap.program_id,
+                GAP_EVENT_NAME,
+            )
+            .await?;
+
+        info!(
+            "🩹 开始缺口回填 ID: {}, 程序: {}, 起点: {} (slot {})",
+            scan_id, gap.program_id, gap.until_signature, gap.until_slot
+        );
+
+        // 1. 分页获取缺口内的全部签名（RPC按从新到旧返回，签名列表开销小，上限只约束交易回填）
+        let mut gap_signatures: Vec<SignatureWithSlot> = Vec::new();
+        let mut before = String::new();
+        loop {
+            let (page, _, _) = match self
+                .fetch_signatures(&before, &gap.until_signature, &gap.program_id)
+                .await
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    scan_record.status = ScanStatus::Failed;
+                    scan_record.error_message = Some(format!("获取缺口签名失败: {}", e));
+                    scan_record.completed_at = Some(Utc::now());
+                    self.scan_record_persistence.update_scan_record(&scan_record).await?;
+                    return Err(e);
+                }
+            };
+
+            let page_len = page.len();
+            if let Some(last) = page.last() {
+                before = last.signature.clone();
+            }
+            gap_signatures.extend(page);
+
+            if page_len < 1000 {
+                break;
+            }
+        }
+
+        // 2. 从最老一端截取本次回填的签名，记录实际覆盖的范围
+        let (covered, remaining) = split_gap_signatures(gap_signatures, max_signatures);
+        if remaining > 0 {
+            warn!(
+                "⚠️ 缺口签名数超过单次上限{}，本次回填最老的{}个，剩余{}个交由后续缺口回填: {}",
+                max_signatures,
+                covered.len(),
+                remaining,
+                gap.program_id
+            );
+        }
+
+        let newest_covered = covered.last().cloned();
+        if let Some(newest) = &newest_covered {
+            scan_record.before_signature = newest.signature.clone();
+            scan_record.before_slot = Some(newest.slot);
+        }
+
+        let mut result = GapBackfillResult {
+            gap_signatures: covered.len() as u64,
+            gap_slots: newest_covered
+                .as_ref()
+                .map(|sig| sig.slot.saturating_sub(gap.until_slot))
+                .unwrap_or(0),
+            remaining_signatures: remaining as u64,
+            ..Default::default()
+        };
+
+        // 3. 跳过实时路径已处理的签名，按从旧到新的顺序回填
+        let missing: Vec<SignatureWithSlot> = covered
+            .into_iter()
+            .filter(|sig| !gap_tracker.is_signature_seen(&sig.signature))
+            .collect();
+        result.skipped_signatures = result.gap_signatures - missing.len() as u64;
+        scan_record.events_found = missing.len() as u64;
+
+        info!(
+            "🔍 缺口内本次覆盖{}个签名，跳过{}个已处理签名，待回填{}个",
+            result.gap_signatures,
+            result.skipped_signatures,
+            missing.len()
+        );
+
+        let mut backfilled_signatures = Vec::new();
+        let mut failed_signatures = Vec::new();
+        for sig_with_slot in &missing {
+            match self.process_missing_transaction(&sig_with_slot.signature, &gap.program_id).await {
+                Ok(true) => backfilled_signatures.push(sig_with_slot.signature.clone()),
+                Ok(false) => {}
+                Err(e) => {
+                    warn!("⚠️ 缺口回填交易失败 {}: {}", sig_with_slot.signature, e);
+                    failed_signatures.push(sig_with_slot.signature.clone());
+                }
+            }
+        }
+
+        // 4. 剩余部分以最新已覆盖的签名为起点重新排队
+        if let (true, Some(newest)) = (remaining > 0, &newest_covered) {
+            let next_gap = GapWindow {
+                until_signature: newest.signature.clone(),
+                until_slot: newest.slot,
+                ..gap.clone()
+            };
+            if gap_tracker.schedule(next_gap) {
+                info!("🩹 剩余缺口已重新排队: {} (起点 {})", gap.program_id, newest.signature);
+            }
+        }
+
+        // 5. 更新扫描记录：有交易失败记为失败，超出上限记为部分完成
+        result.backfilled_count = backfilled_signatures.len() as u64;
+        result.failed_count = failed_signatures.len() as u64;
+        scan_record.events_backfilled_count = result.backfilled_count;
+        scan_record.events_backfilled_signatures = backfilled_signatures;
+        if !failed_signatures.is_empty() {
+            scan_record.status = ScanStatus::Failed;
+            scan_record.error_message = Some(format!(
+                "{}个交易回填失败: {}",
+                failed_signatures.len(),
+                failed_signatures.join(",")
+            ));
+        } else if remaining > 0 {
+            scan_record.status = ScanStatus::Partial;
+            scan_record.error_message = Some(format!(
+                "超出单次上限{}，剩余{}个签名已交由后续缺口回填",
+                max_signatures, remaining
+            ));
+        } else {
+            scan_record.status = ScanStatus::Completed;
+        }
+        scan_record.completed_at = Some(Utc::now());
+        self.scan_record_persistence.update_scan_record(&scan_record).await?;
+
+        info!(
+            "🎉 缺口回填结束 ID: {}, 程序: {}, 回填{}个交易, 失败{}个, 剩余{}个签名",
+            scan_id, gap.program_id, result.backfilled_count, result.failed_count, result.remaining_signatures
+        );
+
+        Ok(result)
+    }
+
     /// 确定扫描范围 (until_signature, before_signature)
     async fn determine_scan_range(
         &self,
@@ -215,6 +400,12 @@ impl BackfillTaskContext {
 
         match checkpoint {
             Some(cp) if cp.last_signature.is_some() => {
+                if let Some(slot) = cp.slot {
+                    self.metrics
+                        .lag_tracker()
+                        .record_backfill_checkpoint(&program_id.to_string(), &event_name, slot);
+                }
+
                 // 有检查点，从检查点开始到链上最新签名
                 // 使用空字符串表示before=None，让RPC获取最新签名
                 let until_signature = cp
@@ -341,8 +532,18 @@ impl BackfillTaskContext {
         );
 
         let signatures = self
-            .rpc_client
-            .get_signatures_for_address_with_config(program_id, config)
+            .endpoint_pool
+            .call("get_signatures_for_address", |client| {
+                client.get_signatures_for_address_with_config(
+                    program_id,
+                    GetConfirmedSignaturesForAddress2Config {
+                        before: config.before,
+                        until: config.until,
+                        limit: config.limit,
+                        commitment: config.commitment,
+                    },
+                )
+            })
             .map_err(|e| EventListenerError::SolanaRpc(format!("获取签名列表失败: {}", e)))?;
 
         let signatures_with_slots: Vec<SignatureWithSlot> = signatures.iter()
@@ -468,15 +669,19 @@ impl BackfillTaskContext {
             .map_err(|e| EventListenerError::SolanaRpc(format!("解析签名失败: {}", e)))?;
 
         let transaction = self
-            .rpc_client
-            .get_transaction_with_config(&signature_obj, tx_config)
+            .endpoint_pool
+            .call("get_transaction", |client| {
+                client.get_transaction_with_config(&signature_obj, tx_config)
+            })
             .map_err(|e| EventListenerError::SolanaRpc(format!("获取交易详情失败: {}", e)))?;
 
         // 2. 适配：EncodedConfirmedTransactionWithStatusMeta -> RpcLogsResponse
-        let logs_response = self.adapt_transaction_to_logs_response(transaction, signature)?;
+        let logs_response = self.adapt_transaction_to_logs_response(&transaction, signature)?;
 
         // 3. 复用现有的事件处理流程
-        let processed = self.process_backfilled_event(logs_response, program_id).await?;
+        let processed = self
+            .process_backfilled_event(logs_response, program_id, &transaction)
+            .await?;
 
         Ok(processed)
     }
@@ -484,12 +689,13 @@ impl BackfillTaskContext {
     /// 适配：将 EncodedConfirmedTransactionWithStatusMeta 适配为 RpcLogsResponse
     fn adapt_transaction_to_logs_response(
         &self,
-        transaction: EncodedConfirmedTransactionWithStatusMeta,
+        transaction: &EncodedConfirmedTransactionWithStatusMeta,
         signature: &str,
     ) -> Result<RpcLogsResponse> {
         let meta = transaction
             .transaction
             .meta
+            .clone()
             .ok_or_else(|| EventListenerError::EventParsing("交易meta为空".to_string()))?;
 
         // 提取日志
@@ -510,23 +716,61 @@ impl BackfillTaskContext {
         &self,
         logs_response: RpcLogsResponse,
         program_id: &solana_sdk::pubkey::Pubkey,
+        transaction: &EncodedConfirmedTransactionWithStatusMeta,
     ) -> Result<bool> {
         let signature = &logs_response.signature;
 
         info!("🔄 回填处理事件: {}", signature);
 
-        // 获取当前slot（回填事件使用当前slot）
-        let slot = self
-            .rpc_client
-            .get_slot()
-            .map_err(|e| EventListenerError::SolanaRpc(format!("获取slot失败: {}", e)))?;
+        // 使用交易实际所在的slot
+        let slot = transaction.slot;
 
         // 解析事件 - 标记为回填服务数据源
-        match self
+        let parse_result = self
             .parser_registry
             .parse_all_events_with_context(&logs_response.logs, signature, slot, &vec![*program_id], Some(EventDataSource::BackfillService))
-            .await
-        {
+            .await;
+
+        // 日志被截断时从已获取的完整交易中补回事件
+        let parse_result = match parse_result {
+            Ok(parsed_events) if is_log_truncated(&logs_response.logs) => {
+                warn!("✂️ 回填交易日志被截断，从完整交易中解析事件: {}", signature);
+                match recover_truncated_events(
+                    &self.parser_registry,
+                    transaction,
+                    parsed_events.clone(),
+                    signature,
+                    slot,
+                    &[*program_id],
+                    Some(EventDataSource::BackfillService),
+                )
+                .await
+                {
+                    Ok((events, recovered)) => {
+                        self.metrics.record_truncated_log_fallback(true, recovered);
+                        Ok(events)
+                    }
+                    Err(e) => {
+                        error!("❌ 日志截断回退解析失败，仅保留日志中的事件: {} - {}", signature, e);
+                        self.metrics.record_truncated_log_fallback(false, 0);
+                        Ok(parsed_events)
+                    }
+                }
+            }
+            other => other,
+        };
+
+        // 回填交易自带出块时间，直接写入事件
+        let parse_result = parse_result.map(|mut parsed_events| {
+            if let Some(block_time) = transaction.block_time {
+                for event in &mut parsed_events {
+                    event.set_block_time(block_time);
+                }
+            }
+            parsed_events
+        });
+
+        match parse_result {
             Ok(parsed_events) if !parsed_events.is_empty() => {
                 info!("✅ 回填事件解析成功: {} -> {}个事件", signature, parsed_events.len());
 
@@ -577,12 +821,24 @@ impl BackfillTaskContext {
         };
 
         self.checkpoint_persistence.update_checkpoint(&checkpoint).await?;
+        if let Some(slot) = last_slot {
+            self.metrics
+                .lag_tracker()
+                .record_backfill_checkpoint(&program_id.to_string(), event_name, slot);
+        }
 
         info!("📍 更新检查点 {}: {}", event_name, last_signature);
         Ok(())
     }
 }
 
+/// 切分缺口签名：输入为RPC返回的从新到旧顺序，返回本次按时间顺序回填的最老部分和剩余签名数
+fn split_gap_signatures(newest_first: Vec<SignatureWithSlot>, max_signatures: usize) -> (Vec<SignatureWithSlot>, usize) {
+    let remaining = newest_first.len().saturating_sub(max_signatures);
+    let covered = newest_first.into_iter().rev().take(max_signatures).collect();
+    (covered, remaining)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -830,4 +1086,29 @@ mod tests {
         assert_eq!(scan_record_without_slots.until_slot, None);
         assert_eq!(scan_record_without_slots.before_slot, None);
     }
+
+    #[test]
+    fn test_split_gap_signatures_keeps_oldest() {
+        let newest_first: Vec<SignatureWithSlot> = (1..=5)
+            .rev()
+            .map(|slot| SignatureWithSlot {
+                signature: format!("sig_{}", slot),
+                slot,
+            })
+            .collect();
+
+        // 超出上限时从最老一端截取，按时间顺序返回
+        let (covered, remaining) = split_gap_signatures(newest_first.clone(), 3);
+        assert_eq!(remaining, 2);
+        assert_eq!(
+            covered.iter().map(|sig| sig.slot).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+
+        // 未超出上限时全部覆盖
+        let (covered, remaining) = split_gap_signatures(newest_first, 10);
+        assert_eq!(remaining, 0);
+        assert_eq!(covered.len(), 5);
+        assert_eq!(covered.last().unwrap().slot, 5);
+    }
 }
@@ -0,0 +1,212 @@
+use crate::{
+    config::{settings::FinalityConfig, EventListenerConfig},
+    error::{EventListenerError, Result},
+    metrics::MetricsCollector,
+    persistence::EventStorage,
+    subscriber::endpoint_pool::{EndpointPool, EndpointRole},
+};
+use solana_sdk::{commitment_config::CommitmentConfig, signature::Signature};
+use solana_transaction_status::TransactionStatus;
+use std::{str::FromStr, sync::Arc, time::Duration};
+use tracing::{debug, error, info, warn};
+
+/// 单笔临时交易的最终确认判定结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinalityDecision {
+    /// 已在finalized承诺级别确认
+    Finalized,
+    /// 尚未最终确认，下一轮继续检查
+    Pending,
+    /// 超时仍未最终确认或交易执行失败，需要回滚
+    RollBack,
+}
+
+/// 一轮最终确认检查的结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FinalityRoundResult {
+    pub checked: u64,
+    pub finalized: u64,
+    pub rolled_back: u64,
+    pub pending: u64,
+}
+
+/// 根据签名状态判定临时交易的最终确认结果
+///
+/// - 状态满足finalized: 已确认
+/// - 交易执行失败: 回滚（失败交易的状态变更不会生效，已写入的事件同样不应保留）
+/// - 状态缺失或停留在较低承诺级别: 未超时继续等待，超时后回滚
+pub fn decide_finality(
+    status: Option<&TransactionStatus>,
+    age_secs: i64,
+    rollback_timeout_secs: u64,
+) -> FinalityDecision {
+    if let Some(status) = status {
+        if status.err.is_some() {
+            return FinalityDecision::RollBack;
+        }
+        if status.satisfies_commitment(CommitmentConfig::finalized()) {
+            return FinalityDecision::Finalized;
+        }
+    }
+
+    if age_secs >= rollback_timeout_secs as i64 {
+        FinalityDecision::RollBack
+    } else {
+        FinalityDecision::Pending
+    }
+}
+
+/// 承诺级别最终确认任务
+///
+/// 负责:
+/// - 定期在finalized承诺级别复查以confirmed写入的交易签名
+/// - 确认后将临时交易标记为Finalized
+/// - 超时仍未最终确认的交易通过EventStorage回滚其事件和派生数据
+pub struct CommitmentFinalizer {
+    config: FinalityConfig,
+    endpoint_pool: Arc<EndpointPool>,
+    event_storage: Arc<EventStorage>,
+    metrics: Arc<MetricsCollector>,
+}
+
+impl CommitmentFinalizer {
+    /// 创建新的最终确认任务
+    pub fn new(
+        config: &EventListenerConfig,
+        event_storage: Arc<EventStorage>,
+        metrics: Arc<MetricsCollector>,
+    ) -> Result<Self> {
+        Ok(Self {
+            config: config.finality.clone().unwrap_or_default(),
+            endpoint_pool: Arc::new(EndpointPool::new(config, EndpointRole::Rpc)?),
+            event_storage,
+            metrics,
+        })
+    }
+
+    /// 启动最终确认循环
+    pub async fn start(&self) -> Result<()> {
+        info!(
+            "🔏 启动最终确认任务: 检查间隔{}秒, 批大小{}, 回滚超时{}秒",
+            self.config.check_interval_secs, self.config.batch_size, self.config.rollback_timeout_secs
+        );
+
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.check_interval_secs));
+        loop {
+            interval.tick().await;
+
+            match self.run_once().await {
+                Ok(result) if result.checked > 0 => {
+                    debug!(
+                        "🔏 最终确认检查完成: 检查{}笔, 确认{}笔, 回滚{}笔, 待确认{}笔",
+                        result.checked, result.finalized, result.rolled_back, result.pending
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => error!("❌ 最终确认检查失败: {}", e),
+            }
+        }
+    }
+
+    /// 执行一轮最终确认检查
+    pub async fn run_once(&self) -> Result<FinalityRoundResult> {
+        let records = self
+            .event_storage
+            .find_pending_provisional(self.config.batch_size)
+            .await?;
+        if records.is_empty() {
+            self.metrics.record_finality_check(0, 0, 0);
+            return Ok(FinalityRoundResult::default());
+        }
+
+        let mut signatures = Vec::with_capacity(records.len());
+        for record in &records {
+            // 签名格式非法的记录无法查询，按默认值处理，由超时回滚兜底
+            signatures.push(Signature::from_str(&record.signature).unwrap_or_default());
+        }
+
+        let statuses = self
+            .endpoint_pool
+            .call("get_signature_statuses", |client| {
+                client.get_signature_statuses_with_history(&signatures)
+            })
+            .map_err(|e| EventListenerError::SolanaRpc(format!("查询签名状态失败: {}", e)))?
+            .value;
+
+        let checked: Vec<String> = records.iter().map(|record| record.signature.clone()).collect();
+        self.event_storage.record_finality_check(&checked).await?;
+
+        let mut result = FinalityRoundResult {
+            checked: records.len() as u64,
+            ..Default::default()
+        };
+        let mut finalized = Vec::new();
+
+        for (record, status) in records.iter().zip(statuses.iter()) {
+            match decide_finality(status.as_ref(), record.age_secs(), self.config.rollback_timeout_secs) {
+                FinalityDecision::Finalized => finalized.push(record.signature.clone()),
+                FinalityDecision::Pending => result.pending += 1,
+                FinalityDecision::RollBack => match self.event_storage.rollback_transaction(record).await {
+                    Ok(_) => result.rolled_back += 1,
+                    Err(e) => {
+                        // 回滚失败的交易保持临时状态，下一轮重试
+                        error!("❌ 回滚交易失败: signature={} - {}", record.signature, e);
+                        result.pending += 1;
+                    }
+                },
+            }
+        }
+
+        result.finalized = self.event_storage.mark_transactions_finalized(&finalized).await?;
+        if result.rolled_back > 0 {
+            warn!("↩️ 本轮共回滚{}笔未能最终确认的交易", result.rolled_back);
+        }
+
+        self.metrics
+            .record_finality_check(result.finalized, result.rolled_back, result.pending);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::transaction::TransactionError;
+    use solana_transaction_status::TransactionConfirmationStatus;
+
+    fn status(confirmation_status: TransactionConfirmationStatus, failed: bool) -> TransactionStatus {
+        let err = failed.then_some(TransactionError::AccountNotFound);
+        TransactionStatus {
+            slot: 100,
+            confirmations: None,
+            status: err.clone().map_or(Ok(()), Err),
+            err,
+            confirmation_status: Some(confirmation_status),
+        }
+    }
+
+    #[test]
+    fn test_decide_finality_finalized() {
+        let finalized = status(TransactionConfirmationStatus::Finalized, false);
+        assert_eq!(decide_finality(Some(&finalized), 5, 180), FinalityDecision::Finalized);
+        // 已确认的交易不受超时影响
+        assert_eq!(decide_finality(Some(&finalized), 500, 180), FinalityDecision::Finalized);
+    }
+
+    #[test]
+    fn test_decide_finality_pending_until_timeout() {
+        let confirmed = status(TransactionConfirmationStatus::Confirmed, false);
+        assert_eq!(decide_finality(Some(&confirmed), 30, 180), FinalityDecision::Pending);
+        assert_eq!(decide_finality(None, 30, 180), FinalityDecision::Pending);
+
+        // 超时仍未最终确认（含签名已从链上消失）时回滚
+        assert_eq!(decide_finality(Some(&confirmed), 180, 180), FinalityDecision::RollBack);
+        assert_eq!(decide_finality(None, 600, 180), FinalityDecision::RollBack);
+    }
+
+    #[test]
+    fn test_decide_finality_failed_transaction() {
+        let failed = status(TransactionConfirmationStatus::Finalized, true);
+        assert_eq!(decide_finality(Some(&failed), 5, 180), FinalityDecision::RollBack);
+    }
+}
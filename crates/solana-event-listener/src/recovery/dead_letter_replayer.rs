@@ -0,0 +1,104 @@
+use crate::{
+    error::Result,
+    parser::ParsedEvent,
+    persistence::{BatchWriter, EventStorage},
+};
+use database::events::dead_letter::DeadLetterEvent;
+use mongodb::bson::oid::ObjectId;
+use std::{sync::Arc, time::Duration};
+use tracing::{error, info, warn};
+
+/// 检查重放请求的间隔
+const REPLAY_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 单次重放的最大事件数
+const REPLAY_BATCH_SIZE: usize = 100;
+
+/// 死信事件重放任务
+///
+/// 管理接口只负责把死信事件标记为ReplayRequested，由监听器进程中的本任务
+/// 还原原始事件并通过BatchWriter::submit_events重新提交，保证重放与实时事件走同一条写入路径
+pub struct DeadLetterReplayer {
+    batch_writer: Arc<BatchWriter>,
+    event_storage: Arc<EventStorage>,
+}
+
+impl DeadLetterReplayer {
+    /// 创建新的死信重放任务
+    pub fn new(batch_writer: Arc<BatchWriter>) -> Self {
+        let event_storage = batch_writer.event_storage();
+        Self {
+            batch_writer,
+            event_storage,
+        }
+    }
+
+    /// 启动重放循环
+    pub async fn start(&self) -> Result<()> {
+        info!("📮 启动死信事件重放任务，检查间隔{:?}", REPLAY_POLL_INTERVAL);
+
+        let mut interval = tokio::time::interval(REPLAY_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.replay_once().await {
+                error!("❌ 死信事件重放失败: {}", e);
+            }
+        }
+    }
+
+    /// 执行一轮重放，返回重新提交的事件数
+    pub async fn replay_once(&self) -> Result<u64> {
+        let dead_letters = self
+            .event_storage
+            .find_replay_requested_dead_letters(REPLAY_BATCH_SIZE)
+            .await?;
+        if dead_letters.is_empty() {
+            return Ok(0);
+        }
+
+        let (ids, events) = self.restore_events(dead_letters).await;
+        if events.is_empty() {
+            return Ok(0);
+        }
+
+        let count = events.len();
+        self.batch_writer.submit_events(events).await?;
+        self.event_storage.mark_dead_letters_replayed(&ids).await?;
+
+        info!("📮 已重新提交{}个死信事件", count);
+        Ok(count as u64)
+    }
+
+    /// 还原死信事件的原始负载，无法还原的事件记录失败原因后退回等待处理
+    async fn restore_events(&self, dead_letters: Vec<DeadLetterEvent>) -> (Vec<ObjectId>, Vec<ParsedEvent>) {
+        let mut ids = Vec::with_capacity(dead_letters.len());
+        let mut events = Vec::with_capacity(dead_letters.len());
+
+        for dead_letter in dead_letters {
+            let id = match dead_letter.id {
+                Some(id) => id,
+                None => continue,
+            };
+
+            match serde_json::from_str::<ParsedEvent>(&dead_letter.payload) {
+                Ok(event) => {
+                    ids.push(id);
+                    events.push(event);
+                }
+                Err(e) => {
+                    warn!(
+                        "⚠️ 死信事件负载无法还原: id={}, signature={} - {}",
+                        id, dead_letter.signature, e
+                    );
+                    let reason = format!("负载反序列化失败: {}", e);
+                    if let Err(e) = self.event_storage.mark_dead_letter_replay_failed(&id, &reason).await {
+                        error!("❌ 记录死信事件重放失败出错: id={} - {}", id, e);
+                    }
+                }
+            }
+        }
+
+        (ids, events)
+    }
+}
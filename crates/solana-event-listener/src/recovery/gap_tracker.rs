@@ -0,0 +1,249 @@
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+/// 实时订阅路径上某个程序最后处理到的位置
+#[derive(Debug, Clone)]
+pub struct LiveCursor {
+    pub signature: String,
+    pub slot: u64,
+    pub updated_at: Instant,
+}
+
+/// WebSocket断线期间的缺口窗口
+///
+/// 下界为断线前最后处理的签名，上界为重连后的链上最新位置
+#[derive(Debug, Clone)]
+pub struct GapWindow {
+    pub program_id: Pubkey,
+    /// 断线前最后处理的签名（回填时作为until）
+    pub until_signature: String,
+    /// 断线前最后处理的slot
+    pub until_slot: u64,
+    pub disconnected_at: Instant,
+    pub reconnected_at: Instant,
+}
+
+/// 断线缺口追踪器
+///
+/// 负责:
+/// - 记录实时订阅路径上每个程序最后处理的签名和slot
+/// - 在WebSocket断开时快照当前位置
+/// - 在重连成功后为每个程序生成缺口窗口，交给回填服务立即处理
+pub struct GapTracker {
+    cursors: DashMap<Pubkey, LiveCursor>,
+    /// 断线时的位置快照（None表示当前未处于断线状态）
+    disconnect_snapshot: Mutex<Option<(Instant, Vec<(Pubkey, LiveCursor)>)>>,
+    /// 订阅路径的签名缓存，回填时跳过已由实时路径处理的签名
+    signature_cache: Arc<DashMap<String, Instant>>,
+    gap_sender: mpsc::UnboundedSender<GapWindow>,
+    gap_receiver: Mutex<Option<mpsc::UnboundedReceiver<GapWindow>>>,
+    has_consumer: AtomicBool,
+}
+
+impl GapTracker {
+    /// 创建新的缺口追踪器
+    pub fn new(signature_cache: Arc<DashMap<String, Instant>>) -> Self {
+        let (gap_sender, gap_receiver) = mpsc::unbounded_channel();
+
+        Self {
+            cursors: DashMap::new(),
+            disconnect_snapshot: Mutex::new(None),
+            signature_cache,
+            gap_sender,
+            gap_receiver: Mutex::new(Some(gap_receiver)),
+            has_consumer: AtomicBool::new(false),
+        }
+    }
+
+    /// 记录实时路径处理的事件位置（只向前推进）
+    pub fn record_processed(&self, program_id: Pubkey, signature: &str, slot: u64) {
+        let cursor = LiveCursor {
+            signature: signature.to_string(),
+            slot,
+            updated_at: Instant::now(),
+        };
+
+        self.cursors
+            .entry(program_id)
+            .and_modify(|existing| {
+                if slot >= existing.slot {
+                    *existing = cursor.clone();
+                }
+            })
+            .or_insert(cursor);
+    }
+
+    /// 获取程序的最后处理位置
+    pub fn get_cursor(&self, program_id: &Pubkey) -> Option<LiveCursor> {
+        self.cursors.get(program_id).map(|cursor| cursor.clone())
+    }
+
+    /// 签名是否已由实时路径处理
+    pub fn is_signature_seen(&self, signature: &str) -> bool {
+        self.signature_cache.contains_key(signature)
+    }
+
+    /// 取出缺口接收器（只能由一个回填消费者取出）
+    pub fn take_receiver(&self) -> Option<mpsc::UnboundedReceiver<GapWindow>> {
+        let receiver = self.gap_receiver.lock().unwrap().take();
+        if receiver.is_some() {
+            self.has_consumer.store(true, Ordering::Relaxed);
+        }
+        receiver
+    }
+
+    /// 调度一个缺口窗口（缺口回填超出单次上限时，剩余部分由此重新排队）
+    pub fn schedule(&self, gap: GapWindow) -> bool {
+        match self.gap_sender.send(gap) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("❌ 调度缺口回填失败: {}", e);
+                false
+            }
+        }
+    }
+
+    /// WebSocket断开时调用，快照各程序的最后处理位置
+    ///
+    /// 连续的断线（重连失败）只保留第一次的快照
+    pub fn mark_disconnected(&self) {
+        let mut snapshot = self.disconnect_snapshot.lock().unwrap();
+        if snapshot.is_some() {
+            return;
+        }
+
+        let cursors: Vec<(Pubkey, LiveCursor)> = self
+            .cursors
+            .iter()
+            .map(|entry| (*entry.key(), entry.value().clone()))
+            .collect();
+
+        if cursors.is_empty() {
+            debug!("ℹ️ 断线前尚未处理任何事件，无需记录缺口");
+            return;
+        }
+
+        info!("📍 WebSocket断开，记录{}个程序的最后处理位置", cursors.len());
+        *snapshot = Some((Instant::now(), cursors));
+    }
+
+    /// WebSocket重连成功后调用，为断线期间的缺口调度回填
+    ///
+    /// 返回调度的缺口数量
+    pub fn on_reconnected(&self) -> usize {
+        let (disconnected_at, cursors) = match self.disconnect_snapshot.lock().unwrap().take() {
+            Some(snapshot) => snapshot,
+            None => return 0,
+        };
+
+        if !self.has_consumer.load(Ordering::Relaxed) {
+            warn!(
+                "⚠️ 回填服务未启用，跳过断线缺口回填（断线{:?}）",
+                disconnected_at.elapsed()
+            );
+            return 0;
+        }
+
+        let reconnected_at = Instant::now();
+        let mut scheduled = 0;
+        for (program_id, cursor) in cursors {
+            let gap = GapWindow {
+                program_id,
+                until_signature: cursor.signature,
+                until_slot: cursor.slot,
+                disconnected_at,
+                reconnected_at,
+            };
+
+            if self.schedule(gap) {
+                scheduled += 1;
+            }
+        }
+
+        info!(
+            "🩹 WebSocket重连成功，已调度{}个缺口回填（断线{}ms）",
+            scheduled,
+            reconnected_at.duration_since(disconnected_at).as_millis()
+        );
+        scheduled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_tracker() -> GapTracker {
+        GapTracker::new(Arc::new(DashMap::new()))
+    }
+
+    #[test]
+    fn test_record_processed_only_moves_forward() {
+        let tracker = create_test_tracker();
+        let program_id = Pubkey::new_unique();
+
+        tracker.record_processed(program_id, "sig_200", 200);
+        tracker.record_processed(program_id, "sig_100", 100);
+
+        let cursor = tracker.get_cursor(&program_id).unwrap();
+        assert_eq!(cursor.signature, "sig_200");
+        assert_eq!(cursor.slot, 200);
+    }
+
+    #[tokio::test]
+    async fn test_gap_scheduled_on_reconnect() {
+        let tracker = create_test_tracker();
+        let mut receiver = tracker.take_receiver().unwrap();
+        assert!(tracker.take_receiver().is_none());
+
+        let program_id = Pubkey::new_unique();
+        tracker.record_processed(program_id, "sig_before_disconnect", 100);
+
+        tracker.mark_disconnected();
+        // 断线期间收到的位置不影响快照
+        tracker.record_processed(program_id, "sig_late", 150);
+        tracker.mark_disconnected();
+
+        assert_eq!(tracker.on_reconnected(), 1);
+        let gap = receiver.recv().await.unwrap();
+        assert_eq!(gap.program_id, program_id);
+        assert_eq!(gap.until_signature, "sig_before_disconnect");
+        assert_eq!(gap.until_slot, 100);
+
+        // 没有新的断线时重连不会重复调度
+        assert_eq!(tracker.on_reconnected(), 0);
+    }
+
+    #[test]
+    fn test_no_gap_without_consumer_or_cursor() {
+        let tracker = create_test_tracker();
+
+        // 没有处理过事件时不记录缺口
+        tracker.mark_disconnected();
+        assert_eq!(tracker.on_reconnected(), 0);
+
+        // 没有回填消费者时不调度
+        tracker.record_processed(Pubkey::new_unique(), "sig", 1);
+        tracker.mark_disconnected();
+        assert_eq!(tracker.on_reconnected(), 0);
+    }
+
+    #[test]
+    fn test_is_signature_seen() {
+        let cache = Arc::new(DashMap::new());
+        cache.insert("seen".to_string(), Instant::now());
+        let tracker = GapTracker::new(cache);
+
+        assert!(tracker.is_signature_seen("seen"));
+        assert!(!tracker.is_signature_seen("unseen"));
+    }
+}
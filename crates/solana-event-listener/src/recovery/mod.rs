@@ -3,6 +3,10 @@ pub mod backfill_manager;
 pub mod backfill_handler;
 pub mod backfill_task_context;
 pub mod checkpoint_persistence;
+pub mod commitment_finalizer;
+pub mod dead_letter_replayer;
+pub mod gap_tracker;
+pub mod reindexer;
 pub mod scan_record_persistence;
 
 // 导出主要的回填服务组件
@@ -10,4 +14,8 @@ pub use backfill_manager::BackfillManager;
 pub use backfill_handler::{BackfillEventConfig, BackfillEventRegistry, EventBackfillHandler};
 pub use backfill_task_context::BackfillTaskContext;
 pub use checkpoint_persistence::CheckpointPersistence;
+pub use commitment_finalizer::{CommitmentFinalizer, FinalityDecision};
+pub use dead_letter_replayer::DeadLetterReplayer;
+pub use gap_tracker::{GapTracker, GapWindow};
+pub use reindexer::{ReindexOptions, ReindexRange, ReindexSummary, Reindexer};
 pub use scan_record_persistence::{ScanRecordPersistence, ScanStatistics};
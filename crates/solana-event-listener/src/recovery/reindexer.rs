@@ -0,0 +1,587 @@
+use crate::{
+    config::EventListenerConfig,
+    error::{EventListenerError, Result},
+    parser::{
+        event_parser::{calculate_event_discriminator, ParserKey},
+        transaction_fallback::{fetch_transaction, is_log_truncated, recover_truncated_events},
+        EventDataSource, EventParserRegistry, ParsedEvent,
+    },
+    persistence::{EventStorage, WriteMode},
+    recovery::{
+        backfill_handler::{BackfillEventRegistry, EventBackfillHandler},
+        backfill_task_context::SignatureWithSlot,
+    },
+    subscriber::endpoint_pool::{EndpointPool, EndpointRole},
+};
+use chrono::Utc;
+use database::analytics::candle::CandleInterval;
+use database::events::event_model::event_model_repository::EventModelRepository;
+use mongodb::bson::{doc, Document};
+use solana_client::{
+    rpc_client::GetConfirmedSignaturesForAddress2Config, rpc_response::RpcConfirmedTransactionStatusWithSignature,
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use std::{
+    collections::{BTreeSet, HashSet},
+    str::FromStr,
+    sync::Arc,
+    time::Instant,
+};
+use tracing::{info, warn};
+use utils::{MetaplexService, TokenMetadataProvider};
+
+/// 单次获取签名的最大数量（RPC上限）
+const SIGNATURE_PAGE_LIMIT: usize = 1000;
+
+/// 重建索引的范围
+///
+/// 签名边界与getSignaturesForAddress的before/until语义一致（均不包含边界签名本身），
+/// slot边界为闭区间；未指定的边界不做限制
+#[derive(Debug, Clone, Default)]
+pub struct ReindexRange {
+    /// 起始slot（包含）
+    pub start_slot: Option<u64>,
+    /// 结束slot（包含）
+    pub end_slot: Option<u64>,
+    /// 从该签名之前（更老）开始扫描
+    pub before_signature: Option<String>,
+    /// 扫描到该签名为止（不包含）
+    pub until_signature: Option<String>,
+}
+
+impl ReindexRange {
+    /// 校验范围参数
+    pub fn validate(&self) -> Result<()> {
+        if self.start_slot.is_none() && self.until_signature.is_none() {
+            return Err(EventListenerError::Config(
+                "必须指定起始slot或until签名，避免扫描程序的全部历史交易".to_string(),
+            ));
+        }
+        if let (Some(start), Some(end)) = (self.start_slot, self.end_slot) {
+            if start > end {
+                return Err(EventListenerError::Config(format!(
+                    "起始slot({})不能大于结束slot({})",
+                    start, end
+                )));
+            }
+        }
+        for signature in [&self.before_signature, &self.until_signature].into_iter().flatten() {
+            Signature::from_str(signature)
+                .map_err(|e| EventListenerError::Config(format!("签名格式无效: {} - {}", signature, e)))?;
+        }
+        Ok(())
+    }
+
+    /// 筛选一页签名（按时间倒序），返回(范围内的签名, 跳过的失败交易数, 是否已越过起始slot)
+    fn select_page(&self, page: &[RpcConfirmedTransactionStatusWithSignature]) -> (Vec<SignatureWithSlot>, u64, bool) {
+        let mut selected = Vec::new();
+        let mut failed = 0u64;
+
+        for status in page {
+            if self.start_slot.is_some_and(|start| status.slot < start) {
+                return (selected, failed, true);
+            }
+            if self.end_slot.is_some_and(|end| status.slot > end) {
+                continue;
+            }
+            // 失败交易不会产生有效事件
+            if status.err.is_some() {
+                failed += 1;
+                continue;
+            }
+            selected.push(SignatureWithSlot {
+                signature: status.signature.clone(),
+                slot: status.slot,
+            });
+        }
+
+        (selected, failed, false)
+    }
+}
+
+/// 重建索引选项
+#[derive(Debug, Clone)]
+pub struct ReindexOptions {
+    /// 事件类型（与回填处理器注册名一致，如SwapEvent、ClmmSwapEvent）
+    pub event_type: String,
+    /// 目标程序ID
+    pub program_id: Pubkey,
+    /// 扫描范围
+    pub range: ReindexRange,
+    /// 重建前清空目标集合中本事件类型的全部文档
+    pub wipe: bool,
+    /// 只解析不写入
+    pub dry_run: bool,
+    /// 每批处理的交易数
+    pub batch_size: usize,
+}
+
+/// 重建索引结果
+#[derive(Debug, Clone, Default)]
+pub struct ReindexSummary {
+    /// 范围内的签名数
+    pub signatures_scanned: u64,
+    /// 跳过的失败交易数
+    pub failed_transactions_skipped: u64,
+    /// 成功获取并解析的交易数
+    pub transactions_processed: u64,
+    /// 获取或解析失败的交易数
+    pub transactions_failed: u64,
+    /// 解析出的事件数
+    pub events_parsed: u64,
+    /// 写入的事件数
+    pub events_written: u64,
+    /// 写入失败的事件数
+    pub events_failed: u64,
+    /// 清理的旧文档数
+    pub documents_deleted: u64,
+    /// 重建的K线数
+    pub candles_rebuilt: u64,
+    /// 按流水重算的CLMM仓位数
+    pub positions_rebuilt: u64,
+}
+
+/// 重建索引写入的事件所影响的派生数据
+#[derive(Debug, Default)]
+struct DerivedTargets {
+    /// 写入过交换的(池子, 成交日开始时间)
+    candle_days: BTreeSet<(String, i64)>,
+    /// 写入过流水的CLMM仓位
+    positions: BTreeSet<String>,
+}
+
+impl DerivedTargets {
+    /// 记录一个已写入事件影响的K线和仓位
+    fn record(&mut self, event: &ParsedEvent) {
+        match event {
+            ParsedEvent::Swap(_) | ParsedEvent::ClmmSwap(_) => {
+                // 缺少出块时间的交换按入库时间分桶，与K线重建的取值一致
+                let timestamp = event.block_time().unwrap_or_else(|| Utc::now().timestamp());
+                if let Some(pool_id) = event.pool_address() {
+                    self.candle_days
+                        .insert((pool_id.to_string(), CandleInterval::OneDay.bucket_start(timestamp)));
+                }
+            }
+            ParsedEvent::ClmmCreatePosition(data) => {
+                if let Some(position_key) = &data.position_key {
+                    self.positions.insert(position_key.clone());
+                }
+            }
+            ParsedEvent::ClmmIncreaseLiquidity(data) => {
+                self.positions.insert(data.position_key.clone());
+            }
+            ParsedEvent::ClmmDecreaseLiquidity(data) => {
+                self.positions.insert(data.position_key.clone());
+            }
+            ParsedEvent::ClmmCollectPersonalFee(data) => {
+                self.positions.insert(data.position_key.clone());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 按slot或签名范围重建单个事件类型的索引
+///
+/// 复用EventParserRegistry解析、EventStorage写入，与回填服务走同一条解析路径。
+/// EventStorage按签名去重，已存在的事件不会被覆盖，因此写入前会先清理范围内的旧文档。
+/// 写入使用只写原始事件模式，不会重复累加积分、K线成交量和仓位流动性，也不登记临时交易；
+/// 全部写入后按写入的事件重建受影响池子当日的K线，并按事件流水重算受影响的CLMM仓位。
+/// 用户积分不随重建索引重算
+pub struct Reindexer {
+    options: ReindexOptions,
+    handler: Arc<dyn EventBackfillHandler>,
+    endpoint_pool: EndpointPool,
+    parser_registry: EventParserRegistry,
+    event_storage: EventStorage,
+    repository: EventModelRepository,
+}
+
+impl Reindexer {
+    /// 创建重建索引任务
+    pub async fn new(config: &EventListenerConfig, options: ReindexOptions) -> Result<Self> {
+        options.range.validate()?;
+        if options.batch_size == 0 {
+            return Err(EventListenerError::Config("batch_size必须大于0".to_string()));
+        }
+
+        let event_registry = BackfillEventRegistry::new();
+        let handler = event_registry.get_handler(&options.event_type).ok_or_else(|| {
+            let mut supported = event_registry.get_registered_event_types();
+            supported.sort();
+            EventListenerError::Config(format!(
+                "不支持的事件类型: {}，可选: {}",
+                options.event_type,
+                supported.join(", ")
+            ))
+        })?;
+
+        // 只放行目标事件类型的discriminator，其他事件即使出现在同一交易中也不会被解析
+        let discriminator = calculate_event_discriminator(handler.discriminator_event_name());
+        let parser_keys = HashSet::from([ParserKey::for_program(options.program_id, discriminator)]);
+
+        let metadata_provider = match MetaplexService::new(None) {
+            Ok(service) => {
+                let provider: Arc<tokio::sync::Mutex<dyn TokenMetadataProvider>> =
+                    Arc::new(tokio::sync::Mutex::new(service));
+                Some(provider)
+            }
+            Err(e) => {
+                warn!("⚠️ 创建代币元数据提供者失败: {}, 将使用基础链上查询", e);
+                None
+            }
+        };
+        let parser_registry =
+            EventParserRegistry::new_with_metadata_provider_and_backfill(config, metadata_provider, Some(parser_keys))?;
+
+        let repository = EventModelRepository::new(&config.database.uri, &config.database.database_name)
+            .await
+            .map_err(|e| EventListenerError::Unknown(format!("创建EventModelRepository失败: {}", e)))?;
+
+        Ok(Self {
+            handler,
+            endpoint_pool: EndpointPool::new(config, EndpointRole::Rpc)?,
+            parser_registry,
+            event_storage: EventStorage::new(config).await?.with_write_mode(WriteMode::RawOnly),
+            repository,
+            options,
+        })
+    }
+
+    /// 执行重建索引
+    pub async fn run(&self) -> Result<ReindexSummary> {
+        let started = Instant::now();
+        let mut summary = ReindexSummary::default();
+        let mode = if self.options.dry_run {
+            "（演练模式，不写入）"
+        } else {
+            ""
+        };
+
+        info!(
+            "🔁 开始重建索引{}: 事件类型={}, 集合={}, 程序={}, 范围={:?}",
+            mode,
+            self.options.event_type,
+            self.handler.collection_name(),
+            self.options.program_id,
+            self.options.range
+        );
+
+        let signatures = self.collect_signatures(&mut summary)?;
+        info!(
+            "📝 范围内共{}个签名（跳过{}个失败交易）",
+            signatures.len(),
+            summary.failed_transactions_skipped
+        );
+
+        if self.options.wipe {
+            if self.options.dry_run {
+                let count = self.count_documents(self.handler.collection_scope_filter()).await?;
+                info!("🧹 [演练] 将清空{}中的{}个文档", self.handler.collection_name(), count);
+            } else {
+                summary.documents_deleted += self.delete_documents(self.handler.collection_scope_filter()).await?;
+                warn!(
+                    "🧹 已清空{}中的{}个文档",
+                    self.handler.collection_name(),
+                    summary.documents_deleted
+                );
+            }
+        }
+
+        let total = signatures.len();
+        let mut processed = 0usize;
+        let mut targets = DerivedTargets::default();
+        for chunk in signatures.chunks(self.options.batch_size) {
+            self.process_chunk(chunk, &mut summary, &mut targets).await?;
+            processed += chunk.len();
+
+            info!(
+                "⏳ 进度: {}/{} ({:.1}%) - 解析{}个事件，写入{}个，失败交易{}个",
+                processed,
+                total,
+                processed as f64 * 100.0 / total as f64,
+                summary.events_parsed,
+                summary.events_written,
+                summary.transactions_failed
+            );
+        }
+
+        if !self.options.dry_run {
+            self.rebuild_derived(&targets, &mut summary).await?;
+        }
+
+        info!("🎉 重建索引完成{}，耗时{:?}: {:?}", mode, started.elapsed(), summary);
+        Ok(summary)
+    }
+
+    /// 分页获取范围内的签名，按时间正序返回
+    fn collect_signatures(&self, summary: &mut ReindexSummary) -> Result<Vec<SignatureWithSlot>> {
+        let range = &self.options.range;
+        let until = range
+            .until_signature
+            .as_deref()
+            .map(Signature::from_str)
+            .transpose()
+            .map_err(|e| EventListenerError::Config(format!("until签名解析失败: {}", e)))?;
+        let mut before = range
+            .before_signature
+            .as_deref()
+            .map(Signature::from_str)
+            .transpose()
+            .map_err(|e| EventListenerError::Config(format!("before签名解析失败: {}", e)))?;
+
+        let mut signatures = Vec::new();
+        loop {
+            let page = self
+                .endpoint_pool
+                .call("get_signatures_for_address", |client| {
+                    client.get_signatures_for_address_with_config(
+                        &self.options.program_id,
+                        GetConfirmedSignaturesForAddress2Config {
+                            before,
+                            until,
+                            limit: Some(SIGNATURE_PAGE_LIMIT),
+                            commitment: Some(CommitmentConfig::confirmed()),
+                        },
+                    )
+                })
+                .map_err(|e| EventListenerError::SolanaRpc(format!("获取签名列表失败: {}", e)))?;
+
+            let (selected, failed, reached_start) = range.select_page(&page);
+            signatures.extend(selected);
+            summary.failed_transactions_skipped += failed;
+
+            let last = match page.last() {
+                Some(last) => last,
+                None => break,
+            };
+            info!("🔍 已扫描到slot {}，累计{}个签名", last.slot, signatures.len());
+
+            if reached_start || page.len() < SIGNATURE_PAGE_LIMIT {
+                break;
+            }
+            before = Some(
+                Signature::from_str(&last.signature)
+                    .map_err(|e| EventListenerError::SolanaRpc(format!("解析签名失败: {}", e)))?,
+            );
+        }
+
+        signatures.reverse();
+        summary.signatures_scanned = signatures.len() as u64;
+        Ok(signatures)
+    }
+
+    /// 处理一批交易：解析、清理旧文档、写入
+    async fn process_chunk(
+        &self,
+        chunk: &[SignatureWithSlot],
+        summary: &mut ReindexSummary,
+        targets: &mut DerivedTargets,
+    ) -> Result<()> {
+        let mut events = Vec::new();
+        let mut processed_signatures = Vec::with_capacity(chunk.len());
+
+        for sig_with_slot in chunk {
+            match self.parse_transaction(sig_with_slot).await {
+                Ok(parsed_events) => {
+                    summary.transactions_processed += 1;
+                    summary.events_parsed += parsed_events.len() as u64;
+                    processed_signatures.push(sig_with_slot.signature.clone());
+                    events.extend(parsed_events);
+                }
+                Err(e) => {
+                    // 解析失败的交易保留旧文档，不参与清理
+                    summary.transactions_failed += 1;
+                    warn!("⚠️ 处理交易失败 {}: {}", sig_with_slot.signature, e);
+                }
+            }
+        }
+
+        if self.options.dry_run {
+            return Ok(());
+        }
+
+        // 清空模式下集合已整体清理，否则只替换本批交易对应的旧文档
+        if !self.options.wipe && !processed_signatures.is_empty() {
+            let mut filter = self.handler.collection_scope_filter();
+            filter.insert("signature", doc! { "$in": processed_signatures });
+            summary.documents_deleted += self.delete_documents(filter).await?;
+        }
+
+        if !events.is_empty() {
+            let outcome = self.event_storage.write_batch(&events).await?;
+            summary.events_written += outcome.written;
+            summary.events_failed += outcome.failed.len() as u64;
+            for failed in &outcome.failed {
+                warn!("⚠️ 事件写入失败 {}: {}", failed.event.get_unique_id(), failed.error);
+            }
+            for event in &events {
+                targets.record(event);
+            }
+        }
+        Ok(())
+    }
+
+    /// 按写入的事件重建派生数据：受影响池子当日的K线和受影响的CLMM仓位
+    async fn rebuild_derived(&self, targets: &DerivedTargets, summary: &mut ReindexSummary) -> Result<()> {
+        if !targets.candle_days.is_empty() {
+            let days: Vec<(String, i64)> = targets.candle_days.iter().cloned().collect();
+            summary.candles_rebuilt = self.event_storage.rebuild_pool_candles_for_days(&days).await?;
+            info!("📈 已重建{}个池子日的K线，共{}根", days.len(), summary.candles_rebuilt);
+        }
+
+        if !targets.positions.is_empty() {
+            let position_keys: Vec<String> = targets.positions.iter().cloned().collect();
+            summary.positions_rebuilt = self.event_storage.rebuild_clmm_positions(&position_keys).await?;
+            info!(
+                "📍 已按流水重算{}/{}个CLMM仓位",
+                summary.positions_rebuilt,
+                position_keys.len()
+            );
+        }
+        Ok(())
+    }
+
+    /// 获取单笔交易并解析目标事件（日志截断时从完整交易补回）
+    async fn parse_transaction(&self, sig_with_slot: &SignatureWithSlot) -> Result<Vec<ParsedEvent>> {
+        let signature = &sig_with_slot.signature;
+        let transaction = fetch_transaction(&self.endpoint_pool, signature)?;
+        let logs = transaction
+            .transaction
+            .meta
+            .as_ref()
+            .ok_or_else(|| EventListenerError::EventParsing("交易meta为空".to_string()))?
+            .log_messages
+            .clone()
+            .unwrap_or(vec![]);
+
+        let programs = [self.options.program_id];
+        let events = self
+            .parser_registry
+            .parse_all_events_with_context(
+                &logs,
+                signature,
+                transaction.slot,
+                &programs,
+                Some(EventDataSource::BackfillService),
+            )
+            .await?;
+
+        let mut events = if is_log_truncated(&logs) {
+            warn!("✂️ 交易日志被截断，从完整交易中解析事件: {}", signature);
+            recover_truncated_events(
+                &self.parser_registry,
+                &transaction,
+                events,
+                signature,
+                transaction.slot,
+                &programs,
+                Some(EventDataSource::BackfillService),
+            )
+            .await?
+            .0
+        } else {
+            events
+        };
+
+        if let Some(block_time) = transaction.block_time {
+            for event in &mut events {
+                event.set_block_time(block_time);
+            }
+        }
+        Ok(events)
+    }
+
+    /// 统计目标集合中匹配的文档数
+    async fn count_documents(&self, filter: Document) -> Result<u64> {
+        self.repository
+            .get_database()
+            .collection::<Document>(self.handler.collection_name())
+            .count_documents(filter, None)
+            .await
+            .map_err(EventListenerError::Database)
+    }
+
+    /// 删除目标集合中匹配的文档
+    async fn delete_documents(&self, filter: Document) -> Result<u64> {
+        let result = self
+            .repository
+            .get_database()
+            .collection::<Document>(self.handler.collection_name())
+            .delete_many(filter, None)
+            .await
+            .map_err(EventListenerError::Database)?;
+        Ok(result.deleted_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::transaction::TransactionError;
+
+    fn status(signature: &str, slot: u64, failed: bool) -> RpcConfirmedTransactionStatusWithSignature {
+        RpcConfirmedTransactionStatusWithSignature {
+            signature: signature.to_string(),
+            slot,
+            err: failed.then_some(TransactionError::AccountInUse),
+            memo: None,
+            block_time: None,
+            confirmation_status: None,
+        }
+    }
+
+    #[test]
+    fn test_range_validation() {
+        assert!(ReindexRange::default().validate().is_err());
+
+        let range = ReindexRange {
+            start_slot: Some(200),
+            end_slot: Some(100),
+            ..Default::default()
+        };
+        assert!(range.validate().is_err());
+
+        let range = ReindexRange {
+            start_slot: Some(100),
+            before_signature: Some("invalid".to_string()),
+            ..Default::default()
+        };
+        assert!(range.validate().is_err());
+
+        let range = ReindexRange {
+            start_slot: Some(100),
+            end_slot: Some(200),
+            ..Default::default()
+        };
+        assert!(range.validate().is_ok());
+    }
+
+    #[test]
+    fn test_select_page() {
+        let range = ReindexRange {
+            start_slot: Some(100),
+            end_slot: Some(200),
+            ..Default::default()
+        };
+        let page = vec![
+            status("sig_newest", 250, false),
+            status("sig_in_range", 200, false),
+            status("sig_failed", 150, true),
+            status("sig_start", 100, false),
+            status("sig_too_old", 99, false),
+            status("sig_older", 90, false),
+        ];
+
+        let (selected, failed, reached_start) = range.select_page(&page);
+        let signatures: Vec<&str> = selected.iter().map(|s| s.signature.as_str()).collect();
+        assert_eq!(signatures, vec!["sig_in_range", "sig_start"]);
+        assert_eq!(failed, 1);
+        assert!(reached_start);
+
+        // 未越过起始slot时继续翻页
+        let (selected, _, reached_start) = range.select_page(&page[..3]);
+        assert_eq!(selected.len(), 1);
+        assert!(!reached_start);
+    }
+}
@@ -94,6 +94,7 @@ impl ScanRecordPersistence {
             ScanStatus::Completed => "Completed",
             ScanStatus::Failed => "Failed",
             ScanStatus::Cancelled => "Cancelled",
+            ScanStatus::Partial => "Partial",
         };
         
         let filter = doc! { "status": status_str };
@@ -255,14 +256,19 @@ impl ScanRecordPersistence {
                     "Cancelled" => {
                         statistics.cancelled_count = count;
                     }
+                    "Partial" => {
+                        statistics.partial_count = count;
+                        statistics.partial_events_found = events_found;
+                        statistics.partial_events_backfilled = events_backfilled;
+                    }
                     _ => {}
                 }
             }
         }
         
-        statistics.total_scans = statistics.running_count + statistics.completed_count + statistics.failed_count + statistics.cancelled_count;
-        statistics.total_events_found = statistics.running_events_found + statistics.completed_events_found + statistics.failed_events_found;
-        statistics.total_events_backfilled = statistics.running_events_backfilled + statistics.completed_events_backfilled;
+        statistics.total_scans = statistics.running_count + statistics.completed_count + statistics.failed_count + statistics.cancelled_count + statistics.partial_count;
+        statistics.total_events_found = statistics.running_events_found + statistics.completed_events_found + statistics.failed_events_found + statistics.partial_events_found;
+        statistics.total_events_backfilled = statistics.running_events_backfilled + statistics.completed_events_backfilled + statistics.partial_events_backfilled;
         
         debug!("📊 扫描统计: 总扫描{}, 总发现{}, 总回填{}", 
             statistics.total_scans, 
@@ -285,13 +291,14 @@ impl ScanRecordPersistence {
 }
 
 /// 扫描统计信息
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct ScanStatistics {
     pub total_scans: u64,
     pub running_count: u64,
     pub completed_count: u64,
     pub failed_count: u64,
     pub cancelled_count: u64,
+    pub partial_count: u64,
     
     pub total_events_found: u64,
     pub total_events_backfilled: u64,
@@ -301,6 +308,8 @@ pub struct ScanStatistics {
     pub completed_events_found: u64,
     pub completed_events_backfilled: u64,
     pub failed_events_found: u64,
+    pub partial_events_found: u64,
+    pub partial_events_backfilled: u64,
 }
 
 impl ScanStatistics {
@@ -1,3 +0,0 @@
-pub mod migration_client;
-
-pub use migration_client::MigrationClient;
\ No newline at end of file
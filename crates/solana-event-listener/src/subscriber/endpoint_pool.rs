@@ -0,0 +1,508 @@
+use crate::config::{settings::RpcEndpointConfig, EventListenerConfig};
+use crate::error::{EventListenerError, Result};
+use serde::Serialize;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_client::RpcClient,
+};
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use tracing::{debug, info, warn};
+
+/// 健康分数的指数加权系数（越大越看重最近一次结果）
+const HEALTH_SCORE_ALPHA: f64 = 0.2;
+
+/// 端点用途
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointRole {
+    /// WebSocket日志订阅
+    WebSocket,
+    /// HTTP RPC请求（回填等）
+    Rpc,
+}
+
+impl EndpointRole {
+    /// 获取用于日志和指标标签的名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EndpointRole::WebSocket => "websocket",
+            EndpointRole::Rpc => "rpc",
+        }
+    }
+}
+
+/// 单个端点的统计信息
+///
+/// 只暴露端点名称，不暴露URL（URL中通常带有服务商的API Key）
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointStats {
+    pub name: String,
+    pub role: &'static str,
+    pub weight: u32,
+    pub health_score: f64,
+    pub is_active: bool,
+    pub is_available: bool,
+    pub consecutive_failures: u32,
+    pub total_successes: u64,
+    pub total_failures: u64,
+    pub last_error: Option<String>,
+    pub seconds_since_last_failure: Option<u64>,
+}
+
+/// 端点运行时健康状态
+struct EndpointState {
+    config: RpcEndpointConfig,
+    health_score: f64,
+    consecutive_failures: u32,
+    total_successes: u64,
+    total_failures: u64,
+    last_failure: Option<Instant>,
+    last_error: Option<String>,
+}
+
+impl EndpointState {
+    fn new(config: RpcEndpointConfig) -> Self {
+        Self {
+            config,
+            health_score: 1.0,
+            consecutive_failures: 0,
+            total_successes: 0,
+            total_failures: 0,
+            last_failure: None,
+            last_error: None,
+        }
+    }
+
+    /// 连续失败未达到阈值，或冷却时间已过，端点即视为可用
+    fn is_available(&self, failure_threshold: u32, cooldown: Duration) -> bool {
+        if self.consecutive_failures < failure_threshold {
+            return true;
+        }
+        self.last_failure.map(|time| time.elapsed() >= cooldown).unwrap_or(true)
+    }
+}
+
+/// 多端点池
+///
+/// 负责:
+/// - 按权重和健康分数选择当前端点
+/// - 记录每个端点的成功/失败并计算健康分数
+/// - 主端点故障时切换到备用端点，恢复后切回（failback）
+/// - 为RPC调用提供带故障切换的执行入口
+pub struct EndpointPool {
+    role: EndpointRole,
+    endpoints: Mutex<Vec<EndpointState>>,
+    /// RPC客户端（仅Rpc用途创建，与endpoints一一对应）
+    rpc_clients: Vec<Arc<RpcClient>>,
+    active_index: AtomicUsize,
+    failover_count: AtomicU64,
+    failure_threshold: u32,
+    cooldown: Duration,
+}
+
+impl EndpointPool {
+    /// 根据配置创建端点池
+    pub fn new(config: &EventListenerConfig, role: EndpointRole) -> Result<Self> {
+        Self::with_endpoints(
+            config.get_all_endpoints(),
+            role,
+            config.solana.failover.failure_threshold,
+            Duration::from_secs(config.solana.failover.cooldown_secs),
+        )
+    }
+
+    /// 使用指定端点列表创建端点池
+    ///
+    /// 端点列表不能为空，端点选择依赖至少存在一个端点
+    pub fn with_endpoints(
+        mut endpoints: Vec<RpcEndpointConfig>,
+        role: EndpointRole,
+        failure_threshold: u32,
+        cooldown: Duration,
+    ) -> Result<Self> {
+        if endpoints.is_empty() {
+            return Err(EventListenerError::Config(format!(
+                "{}端点池至少需要配置一个端点",
+                role.as_str()
+            )));
+        }
+
+        // 按权重从高到低排列，权重相同时保持配置顺序（主端点优先）
+        endpoints.sort_by(|a, b| b.weight.cmp(&a.weight));
+
+        let rpc_clients = match role {
+            EndpointRole::Rpc => endpoints
+                .iter()
+                .map(|endpoint| {
+                    Arc::new(RpcClient::new_with_commitment(
+                        endpoint.rpc_url.clone(),
+                        CommitmentConfig::confirmed(),
+                    ))
+                })
+                .collect(),
+            EndpointRole::WebSocket => Vec::new(),
+        };
+
+        info!(
+            "🛟 初始化{}端点池: {:?}",
+            role.as_str(),
+            endpoints
+                .iter()
+                .map(|e| format!("{}({})", e.name, e.weight))
+                .collect::<Vec<_>>()
+        );
+
+        Ok(Self {
+            role,
+            endpoints: Mutex::new(endpoints.into_iter().map(EndpointState::new).collect()),
+            rpc_clients,
+            active_index: AtomicUsize::new(0),
+            failover_count: AtomicU64::new(0),
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+        })
+    }
+
+    /// 端点用途
+    pub fn role(&self) -> EndpointRole {
+        self.role
+    }
+
+    /// 端点数量
+    pub fn len(&self) -> usize {
+        self.endpoints.lock().unwrap().len()
+    }
+
+    /// 端点池是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 故障切换次数
+    pub fn failover_count(&self) -> u64 {
+        self.failover_count.load(Ordering::Relaxed)
+    }
+
+    /// 当前使用的端点名称
+    pub fn current_endpoint_name(&self) -> Option<String> {
+        let endpoints = self.endpoints.lock().unwrap();
+        endpoints
+            .get(self.active_index.load(Ordering::Relaxed))
+            .map(|state| state.config.name.clone())
+    }
+
+    /// 选择当前应使用的端点，返回(索引, 端点配置)
+    pub fn select(&self) -> (usize, RpcEndpointConfig) {
+        self.select_excluding(&[])
+    }
+
+    /// 选择端点，跳过本轮已尝试过的端点
+    fn select_excluding(&self, tried: &[usize]) -> (usize, RpcEndpointConfig) {
+        let endpoints = self.endpoints.lock().unwrap();
+
+        let index = self
+            .best_available_index(&endpoints, tried)
+            .or_else(|| {
+                // 全部不可用时，退而选择健康分数最高的未尝试端点
+                endpoints
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| !tried.contains(i))
+                    .max_by(|(_, a), (_, b)| a.health_score.total_cmp(&b.health_score))
+                    .map(|(i, _)| i)
+            })
+            .unwrap_or(0);
+
+        let previous = self.active_index.swap(index, Ordering::Relaxed);
+        if previous != index {
+            self.failover_count.fetch_add(1, Ordering::Relaxed);
+            warn!(
+                "🔀 {}端点切换: {} -> {}",
+                self.role.as_str(),
+                endpoints[previous].config.name,
+                endpoints[index].config.name
+            );
+        }
+
+        (index, endpoints[index].config.clone())
+    }
+
+    /// 按权重优先、健康分数次之，找出最优的可用端点
+    fn best_available_index(&self, endpoints: &[EndpointState], tried: &[usize]) -> Option<usize> {
+        endpoints
+            .iter()
+            .enumerate()
+            .filter(|(i, state)| !tried.contains(i) && state.is_available(self.failure_threshold, self.cooldown))
+            .max_by(|(ia, a), (ib, b)| {
+                a.config
+                    .weight
+                    .cmp(&b.config.weight)
+                    .then(a.health_score.total_cmp(&b.health_score))
+                    // 完全相同时取靠前的端点
+                    .then(ib.cmp(ia))
+            })
+            .map(|(i, _)| i)
+    }
+
+    /// 是否存在比当前端点更优的可用端点（用于切回主端点）
+    pub fn should_failback(&self) -> bool {
+        let endpoints = self.endpoints.lock().unwrap();
+        let active = self.active_index.load(Ordering::Relaxed);
+
+        match self.best_available_index(&endpoints, &[]) {
+            Some(best) if best != active => endpoints[best].config.weight > endpoints[active].config.weight,
+            _ => false,
+        }
+    }
+
+    /// 记录端点请求成功
+    pub fn record_success(&self, index: usize) {
+        let mut endpoints = self.endpoints.lock().unwrap();
+        if let Some(state) = endpoints.get_mut(index) {
+            state.health_score = state.health_score * (1.0 - HEALTH_SCORE_ALPHA) + HEALTH_SCORE_ALPHA;
+            state.consecutive_failures = 0;
+            state.total_successes += 1;
+        }
+    }
+
+    /// 记录端点请求失败
+    pub fn record_failure(&self, index: usize, error: &str) {
+        let mut endpoints = self.endpoints.lock().unwrap();
+        if let Some(state) = endpoints.get_mut(index) {
+            state.health_score *= 1.0 - HEALTH_SCORE_ALPHA;
+            state.consecutive_failures += 1;
+            state.total_failures += 1;
+            state.last_failure = Some(Instant::now());
+            state.last_error = Some(error.to_string());
+
+            if state.consecutive_failures == self.failure_threshold {
+                warn!(
+                    "🚫 {}端点 {} 连续失败{}次，冷却{}秒: {}",
+                    self.role.as_str(),
+                    state.config.name,
+                    state.consecutive_failures,
+                    self.cooldown.as_secs(),
+                    error
+                );
+            }
+        }
+    }
+
+    /// 获取所有端点的统计信息
+    pub fn stats(&self) -> Vec<EndpointStats> {
+        let endpoints = self.endpoints.lock().unwrap();
+        let active = self.active_index.load(Ordering::Relaxed);
+
+        endpoints
+            .iter()
+            .enumerate()
+            .map(|(i, state)| EndpointStats {
+                name: state.config.name.clone(),
+                role: self.role.as_str(),
+                weight: state.config.weight,
+                health_score: state.health_score,
+                is_active: i == active,
+                is_available: state.is_available(self.failure_threshold, self.cooldown),
+                consecutive_failures: state.consecutive_failures,
+                total_successes: state.total_successes,
+                total_failures: state.total_failures,
+                last_error: state.last_error.clone(),
+                seconds_since_last_failure: state.last_failure.map(|time| time.elapsed().as_secs()),
+            })
+            .collect()
+    }
+
+    /// 执行RPC调用，遇到传输层错误时依次切换到其他端点重试
+    ///
+    /// RPC返回的业务错误（如交易不存在）说明端点本身可用，不会触发切换
+    pub fn call<T, F>(&self, operation: &str, f: F) -> ClientResult<T>
+    where
+        F: Fn(&RpcClient) -> ClientResult<T>,
+    {
+        let mut tried = Vec::new();
+
+        loop {
+            let (index, endpoint) = self.select_excluding(&tried);
+            let client = match self.rpc_clients.get(index) {
+                Some(client) => client,
+                None => {
+                    return Err(ClientError::from(ClientErrorKind::Custom(format!(
+                        "{}端点池没有可用的RPC客户端",
+                        self.role.as_str()
+                    ))))
+                }
+            };
+
+            match f(client) {
+                Ok(result) => {
+                    self.record_success(index);
+                    return Ok(result);
+                }
+                Err(e) if is_transport_error(&e) => {
+                    self.record_failure(index, &e.to_string());
+                    tried.push(index);
+
+                    if tried.len() >= self.rpc_clients.len() {
+                        warn!("❌ {} 在所有RPC端点上均失败: {}", operation, e);
+                        return Err(e);
+                    }
+                    warn!(
+                        "⚠️ {} 在端点 {} 上失败，尝试下一个端点: {}",
+                        operation, endpoint.name, e
+                    );
+                }
+                Err(e) => {
+                    debug!("ℹ️ {} 返回业务错误（端点 {} 正常）: {}", operation, endpoint.name, e);
+                    self.record_success(index);
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+/// 判断是否为端点不可达类错误
+fn is_transport_error(error: &ClientError) -> bool {
+    matches!(
+        error.kind(),
+        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) | ClientErrorKind::Middleware(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint(name: &str, weight: u32) -> RpcEndpointConfig {
+        RpcEndpointConfig {
+            name: name.to_string(),
+            rpc_url: format!("https://{}.example.com", name),
+            ws_url: format!("wss://{}.example.com", name),
+            weight,
+        }
+    }
+
+    fn create_test_pool(cooldown: Duration) -> EndpointPool {
+        EndpointPool::with_endpoints(
+            vec![endpoint("backup", 50), endpoint("primary", 100)],
+            EndpointRole::WebSocket,
+            2,
+            cooldown,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_empty_endpoints_rejected() {
+        let result = EndpointPool::with_endpoints(Vec::new(), EndpointRole::Rpc, 3, Duration::from_secs(30));
+        assert!(matches!(result, Err(EventListenerError::Config(_))));
+    }
+
+    #[test]
+    fn test_select_prefers_highest_weight() {
+        let pool = create_test_pool(Duration::from_secs(30));
+
+        let (index, selected) = pool.select();
+        assert_eq!(index, 0);
+        assert_eq!(selected.name, "primary");
+        assert_eq!(pool.failover_count(), 0);
+        assert!(!pool.should_failback());
+    }
+
+    #[test]
+    fn test_failover_after_threshold() {
+        let pool = create_test_pool(Duration::from_secs(30));
+
+        // 未达到阈值前仍使用主端点
+        pool.record_failure(0, "connection refused");
+        assert_eq!(pool.select().1.name, "primary");
+
+        pool.record_failure(0, "connection refused");
+        assert_eq!(pool.select().1.name, "backup");
+        assert_eq!(pool.failover_count(), 1);
+        assert_eq!(pool.current_endpoint_name().as_deref(), Some("backup"));
+
+        let stats = pool.stats();
+        assert!(!stats[0].is_available);
+        assert_eq!(stats[0].total_failures, 2);
+        assert!(stats[0].health_score < 1.0);
+        assert!(stats[1].is_active);
+        assert!(!pool.should_failback());
+    }
+
+    #[test]
+    fn test_failback_after_cooldown() {
+        let pool = create_test_pool(Duration::from_millis(0));
+
+        pool.record_failure(0, "timeout");
+        pool.record_failure(0, "timeout");
+        // 冷却为0时主端点立即恢复可用
+        assert_eq!(pool.select().1.name, "primary");
+
+        let pool = create_test_pool(Duration::from_secs(30));
+        pool.record_failure(0, "timeout");
+        pool.record_failure(0, "timeout");
+        assert_eq!(pool.select().1.name, "backup");
+
+        // 主端点恢复成功后应切回
+        pool.record_success(0);
+        assert!(pool.should_failback());
+        assert_eq!(pool.select().1.name, "primary");
+        assert_eq!(pool.failover_count(), 2);
+    }
+
+    #[test]
+    fn test_call_fails_over_on_transport_error() {
+        let pool = EndpointPool::with_endpoints(
+            vec![endpoint("primary", 100), endpoint("backup", 50)],
+            EndpointRole::Rpc,
+            3,
+            Duration::from_secs(30),
+        )
+        .unwrap();
+
+        let result = pool.call("get_slot", |client| {
+            if client.url().contains("primary") {
+                Err(ClientError::from(std::io::Error::new(
+                    std::io::ErrorKind::ConnectionRefused,
+                    "refused",
+                )))
+            } else {
+                Ok(42u64)
+            }
+        });
+
+        assert_eq!(result.unwrap(), 42);
+        let stats = pool.stats();
+        assert_eq!(stats[0].total_failures, 1);
+        assert_eq!(stats[1].total_successes, 1);
+        assert!(stats[1].is_active);
+    }
+
+    #[test]
+    fn test_call_does_not_fail_over_on_rpc_error() {
+        let pool = EndpointPool::with_endpoints(
+            vec![endpoint("primary", 100), endpoint("backup", 50)],
+            EndpointRole::Rpc,
+            3,
+            Duration::from_secs(30),
+        )
+        .unwrap();
+
+        let result: ClientResult<u64> = pool.call("get_transaction", |_| {
+            Err(ClientError::from(ClientErrorKind::Custom("not found".into())))
+        });
+
+        assert!(result.is_err());
+        let stats = pool.stats();
+        assert_eq!(stats[0].total_failures, 0);
+        assert!(stats[0].is_active);
+        assert_eq!(stats[1].total_successes + stats[1].total_failures, 0);
+    }
+}
@@ -0,0 +1,530 @@
+use crate::{
+    error::{EventListenerError, Result},
+    subscriber::{ingestion_source::IngestionSource, websocket_manager::ConnectionStats},
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_response::{Response, RpcLogsResponse};
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        RwLock as StdRwLock,
+    },
+    time::{Duration, Instant},
+};
+use tokio::{
+    fs,
+    io::AsyncWriteExt,
+    sync::{broadcast, Mutex, Notify, RwLock},
+};
+use tracing::{info, warn};
+
+/// 夹具记录（JSONL文件中每行一条）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FixtureRecord {
+    /// logsSubscribe通知（包含slot上下文）
+    Logs { notification: Response<RpcLogsResponse> },
+    /// 日志截断时获取的完整交易
+    Transaction {
+        signature: String,
+        transaction: EncodedConfirmedTransactionWithStatusMeta,
+    },
+}
+
+/// 从文件或目录加载夹具记录
+///
+/// 目录下的.jsonl文件按文件名顺序读取，空行会被跳过
+pub async fn load_fixture_records(path: impl AsRef<Path>) -> Result<Vec<FixtureRecord>> {
+    let path = path.as_ref();
+    let files = if fs::metadata(path).await?.is_dir() {
+        let mut files = Vec::new();
+        let mut entries = fs::read_dir(path).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file = entry.path();
+            if file.extension().is_some_and(|ext| ext == "jsonl") {
+                files.push(file);
+            }
+        }
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut records = Vec::new();
+    for file in files {
+        let content = fs::read_to_string(&file).await?;
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record = serde_json::from_str(line).map_err(|e| {
+                EventListenerError::Config(format!("解析夹具文件{}第{}行失败: {}", file.display(), index + 1, e))
+            })?;
+            records.push(record);
+        }
+    }
+
+    Ok(records)
+}
+
+/// 夹具回放来源
+///
+/// 按录制顺序广播夹具中的日志通知，日志截断时优先使用夹具中录制的完整交易，
+/// 使解析和持久化流程可以在没有RPC的环境下确定性地运行。
+/// 回放结束后保持运行状态直到stop被调用，与实时订阅的生命周期一致
+pub struct FixtureReplaySource {
+    fixture_path: PathBuf,
+    notifications: Vec<Response<RpcLogsResponse>>,
+    transactions: HashMap<String, EncodedConfirmedTransactionWithStatusMeta>,
+    program_ids: StdRwLock<Vec<Pubkey>>,
+    replay_interval: Duration,
+    is_running: AtomicBool,
+    is_exhausted: AtomicBool,
+    replayed_count: AtomicU64,
+    started_at: RwLock<Option<Instant>>,
+    exhausted_notify: Notify,
+    stop_notify: Notify,
+    event_sender: broadcast::Sender<Response<RpcLogsResponse>>,
+}
+
+impl FixtureReplaySource {
+    /// 从夹具文件或目录创建回放来源
+    pub async fn from_path(
+        fixture_path: impl AsRef<Path>,
+        program_ids: Vec<Pubkey>,
+        replay_interval: Duration,
+    ) -> Result<Self> {
+        let fixture_path = fixture_path.as_ref().to_path_buf();
+        let records = load_fixture_records(&fixture_path).await?;
+        info!("📼 已加载{}条夹具记录: {}", records.len(), fixture_path.display());
+        Ok(Self::new(fixture_path, records, program_ids, replay_interval))
+    }
+
+    /// 从已加载的夹具记录创建回放来源
+    pub fn new(
+        fixture_path: PathBuf,
+        records: Vec<FixtureRecord>,
+        program_ids: Vec<Pubkey>,
+        replay_interval: Duration,
+    ) -> Self {
+        let mut notifications = Vec::new();
+        let mut transactions = HashMap::new();
+        for record in records {
+            match record {
+                FixtureRecord::Logs { notification } => notifications.push(notification),
+                FixtureRecord::Transaction { signature, transaction } => {
+                    transactions.insert(signature, transaction);
+                }
+            }
+        }
+
+        // 回放不会因为接收方处理慢而丢弃通知，缓冲区至少容纳全部记录
+        let (event_sender, _) = broadcast::channel(notifications.len().max(1024));
+
+        Self {
+            fixture_path,
+            notifications,
+            transactions,
+            program_ids: StdRwLock::new(program_ids),
+            replay_interval,
+            is_running: AtomicBool::new(false),
+            is_exhausted: AtomicBool::new(false),
+            replayed_count: AtomicU64::new(0),
+            started_at: RwLock::new(None),
+            exhausted_notify: Notify::new(),
+            stop_notify: Notify::new(),
+            event_sender,
+        }
+    }
+
+    /// 夹具中的日志通知数量
+    pub fn notification_count(&self) -> usize {
+        self.notifications.len()
+    }
+
+    /// 已回放的日志通知数量
+    pub fn replayed_count(&self) -> u64 {
+        self.replayed_count.load(Ordering::Relaxed)
+    }
+
+    /// 是否已回放完全部通知
+    pub fn is_exhausted(&self) -> bool {
+        self.is_exhausted.load(Ordering::Relaxed)
+    }
+
+    /// 等待全部通知回放完成
+    pub async fn wait_until_exhausted(&self) {
+        loop {
+            let notified = self.exhausted_notify.notified();
+            if self.is_exhausted() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+#[async_trait]
+impl IngestionSource for FixtureReplaySource {
+    fn name(&self) -> &str {
+        "fixture_replay"
+    }
+
+    async fn start(&self) -> Result<()> {
+        if self.is_running.swap(true, Ordering::Relaxed) {
+            warn!("夹具回放已在运行中");
+            return Ok(());
+        }
+
+        *self.started_at.write().await = Some(Instant::now());
+        info!(
+            "📼 开始回放夹具: {} ({}条日志通知, {}笔录制交易)",
+            self.fixture_path.display(),
+            self.notifications.len(),
+            self.transactions.len()
+        );
+
+        for notification in &self.notifications {
+            if !self.is_running.load(Ordering::Relaxed) {
+                break;
+            }
+
+            if self.event_sender.send(notification.clone()).is_err() {
+                warn!("❌ 回放通知失败，没有活跃的接收者: {}", notification.value.signature);
+            }
+            self.replayed_count.fetch_add(1, Ordering::Relaxed);
+
+            if self.replay_interval.is_zero() {
+                tokio::task::yield_now().await;
+            } else {
+                tokio::time::sleep(self.replay_interval).await;
+            }
+        }
+
+        self.is_exhausted.store(true, Ordering::Relaxed);
+        self.exhausted_notify.notify_waiters();
+        info!("📼 夹具回放完成: 共回放{}条日志通知", self.replayed_count());
+
+        while self.is_running.load(Ordering::Relaxed) {
+            self.stop_notify.notified().await;
+        }
+
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<()> {
+        info!("🛑 停止夹具回放");
+        self.is_running.store(false, Ordering::Relaxed);
+        self.stop_notify.notify_one();
+        Ok(())
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<Response<RpcLogsResponse>> {
+        self.event_sender.subscribe()
+    }
+
+    fn program_ids(&self) -> Vec<Pubkey> {
+        self.program_ids.read().unwrap().clone()
+    }
+
+    fn update_program_ids(&self, program_ids: Vec<Pubkey>) -> Result<bool> {
+        if program_ids.is_empty() {
+            return Err(EventListenerError::Config("程序ID列表不能为空".to_string()));
+        }
+
+        let mut current = self.program_ids.write().unwrap();
+        if *current == program_ids {
+            return Ok(false);
+        }
+        *current = program_ids;
+        Ok(true)
+    }
+
+    async fn is_healthy(&self) -> bool {
+        self.is_running.load(Ordering::Relaxed)
+    }
+
+    async fn get_stats(&self) -> ConnectionStats {
+        let started_at = *self.started_at.read().await;
+        let is_running = self.is_running.load(Ordering::Relaxed);
+
+        ConnectionStats {
+            is_connected: is_running,
+            is_running,
+            connection_count: u64::from(started_at.is_some()),
+            last_connection_time: started_at,
+            uptime_seconds: started_at.map(|time| time.elapsed().as_secs()),
+            current_endpoint: Some(format!("fixture:{}", self.fixture_path.display())),
+            failover_count: 0,
+            endpoints: Vec::new(),
+        }
+    }
+
+    fn recorded_transaction(&self, signature: &str) -> Option<EncodedConfirmedTransactionWithStatusMeta> {
+        self.transactions.get(signature).cloned()
+    }
+}
+
+/// 夹具录制器
+///
+/// 把实时订阅收到的日志通知和日志截断时获取的完整交易追加写入JSONL文件，
+/// 录制结果可直接作为回放来源的夹具
+pub struct FixtureRecorder {
+    path: PathBuf,
+    write_lock: Mutex<()>,
+    recorded_count: AtomicU64,
+}
+
+impl FixtureRecorder {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            write_lock: Mutex::new(()),
+            recorded_count: AtomicU64::new(0),
+        }
+    }
+
+    /// 录制文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 已录制的记录数
+    pub fn recorded_count(&self) -> u64 {
+        self.recorded_count.load(Ordering::Relaxed)
+    }
+
+    /// 录制日志通知
+    pub async fn record_logs(&self, notification: &Response<RpcLogsResponse>) -> Result<()> {
+        self.append(&FixtureRecord::Logs {
+            notification: notification.clone(),
+        })
+        .await
+    }
+
+    /// 录制完整交易
+    pub async fn record_transaction(
+        &self,
+        signature: &str,
+        transaction: &EncodedConfirmedTransactionWithStatusMeta,
+    ) -> Result<()> {
+        self.append(&FixtureRecord::Transaction {
+            signature: signature.to_string(),
+            transaction: transaction.clone(),
+        })
+        .await
+    }
+
+    /// 追加写入一条记录
+    async fn append(&self, record: &FixtureRecord) -> Result<()> {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+
+        let _guard = self.write_lock.lock().await;
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+
+        self.recorded_count.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_client::rpc_response::RpcResponseContext;
+    use solana_sdk::{message::MessageHeader, transaction::TransactionVersion};
+    use solana_transaction_status::{
+        EncodedTransaction, EncodedTransactionWithStatusMeta, UiMessage, UiRawMessage, UiTransaction,
+        UiTransactionStatusMeta,
+    };
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("fixture-source-test-{}-{}", name, uuid::Uuid::new_v4()))
+    }
+
+    fn notification(signature: &str, slot: u64) -> Response<RpcLogsResponse> {
+        Response {
+            context: RpcResponseContext {
+                slot,
+                api_version: None,
+            },
+            value: RpcLogsResponse {
+                signature: signature.to_string(),
+                err: None,
+                logs: vec![
+                    "Program FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX invoke [1]".to_string(),
+                    "Log truncated".to_string(),
+                ],
+            },
+        }
+    }
+
+    fn transaction(signature: &str) -> EncodedConfirmedTransactionWithStatusMeta {
+        let meta: UiTransactionStatusMeta = serde_json::from_value(serde_json::json!({
+            "err": null,
+            "status": { "Ok": null },
+            "fee": 5000,
+            "preBalances": [],
+            "postBalances": [],
+        }))
+        .unwrap();
+
+        EncodedConfirmedTransactionWithStatusMeta {
+            slot: 100,
+            transaction: EncodedTransactionWithStatusMeta {
+                transaction: EncodedTransaction::Json(UiTransaction {
+                    signatures: vec![signature.to_string()],
+                    message: UiMessage::Raw(UiRawMessage {
+                        header: MessageHeader::default(),
+                        account_keys: vec!["FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX".to_string()],
+                        recent_blockhash: String::new(),
+                        instructions: vec![],
+                        address_table_lookups: None,
+                    }),
+                }),
+                meta: Some(meta),
+                version: Some(TransactionVersion::LEGACY),
+            },
+            block_time: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_and_load_roundtrip() {
+        let path = temp_path("roundtrip").join("recorded.jsonl");
+        let recorder = FixtureRecorder::new(&path);
+
+        recorder.record_logs(&notification("sig_1", 10)).await.unwrap();
+        recorder
+            .record_transaction("sig_1", &transaction("sig_1"))
+            .await
+            .unwrap();
+        recorder.record_logs(&notification("sig_2", 11)).await.unwrap();
+        assert_eq!(recorder.recorded_count(), 3);
+
+        let records = load_fixture_records(&path).await.unwrap();
+        assert_eq!(records.len(), 3);
+        match &records[0] {
+            FixtureRecord::Logs { notification } => {
+                assert_eq!(notification.context.slot, 10);
+                assert_eq!(notification.value.signature, "sig_1");
+            }
+            other => panic!("期望日志记录，实际为: {:?}", other),
+        }
+        match &records[1] {
+            FixtureRecord::Transaction {
+                signature,
+                transaction: recorded,
+            } => {
+                assert_eq!(signature, "sig_1");
+                assert_eq!(
+                    serde_json::to_value(recorded).unwrap(),
+                    serde_json::to_value(transaction("sig_1")).unwrap()
+                );
+            }
+            other => panic!("期望交易记录，实际为: {:?}", other),
+        }
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_load_directory_in_file_order() {
+        let dir = temp_path("directory");
+        std::fs::create_dir_all(&dir).unwrap();
+        FixtureRecorder::new(dir.join("b.jsonl"))
+            .record_logs(&notification("sig_b", 2))
+            .await
+            .unwrap();
+        FixtureRecorder::new(dir.join("a.jsonl"))
+            .record_logs(&notification("sig_a", 1))
+            .await
+            .unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a fixture").unwrap();
+
+        let signatures: Vec<String> = load_fixture_records(&dir)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|record| match record {
+                FixtureRecord::Logs { notification } => notification.value.signature,
+                FixtureRecord::Transaction { signature, .. } => signature,
+            })
+            .collect();
+        assert_eq!(signatures, vec!["sig_a".to_string(), "sig_b".to_string()]);
+
+        std::fs::write(dir.join("c.jsonl"), "{invalid").unwrap();
+        assert!(load_fixture_records(&dir).await.is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_replay_in_recorded_order() {
+        let program_id = Pubkey::new_unique();
+        let source = std::sync::Arc::new(FixtureReplaySource::new(
+            PathBuf::from("memory"),
+            vec![
+                FixtureRecord::Logs {
+                    notification: notification("sig_1", 10),
+                },
+                FixtureRecord::Transaction {
+                    signature: "sig_1".to_string(),
+                    transaction: transaction("sig_1"),
+                },
+                FixtureRecord::Logs {
+                    notification: notification("sig_2", 11),
+                },
+            ],
+            vec![program_id],
+            Duration::ZERO,
+        ));
+        assert_eq!(source.notification_count(), 2);
+        assert!(source.recorded_transaction("sig_1").is_some());
+        assert!(source.recorded_transaction("sig_2").is_none());
+
+        let mut receiver = source.subscribe();
+        let task = tokio::spawn({
+            let source = std::sync::Arc::clone(&source);
+            async move { source.start().await }
+        });
+
+        tokio::time::timeout(Duration::from_secs(5), source.wait_until_exhausted())
+            .await
+            .unwrap();
+        assert_eq!(source.replayed_count(), 2);
+        assert!(source.is_healthy().await);
+
+        let first = receiver.recv().await.unwrap();
+        let second = receiver.recv().await.unwrap();
+        assert_eq!((first.value.signature.as_str(), first.context.slot), ("sig_1", 10));
+        assert_eq!((second.value.signature.as_str(), second.context.slot), ("sig_2", 11));
+
+        assert!(!source.update_program_ids(vec![program_id]).unwrap());
+        assert!(source.update_program_ids(vec![]).is_err());
+
+        source.stop().await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), task)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert!(!source.is_healthy().await);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{
+    error::Result,
+    subscriber::{websocket_manager::ConnectionStats, WebSocketManager},
+};
+use async_trait::async_trait;
+use solana_client::rpc_response::{Response, RpcLogsResponse};
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use tokio::sync::broadcast;
+
+/// 事件摄取来源
+///
+/// 订阅管理器只通过该接口获取交易日志，实时订阅和夹具回放共用同一条解析和持久化路径。
+/// 广播的通知保留slot上下文，解析出的事件以通知中的slot为准
+#[async_trait]
+pub trait IngestionSource: Send + Sync {
+    /// 来源名称（用于日志）
+    fn name(&self) -> &str;
+
+    /// 启动摄取，持续运行直到stop被调用
+    async fn start(&self) -> Result<()>;
+
+    /// 停止摄取
+    async fn stop(&self) -> Result<()>;
+
+    /// 获取日志通知接收器
+    fn subscribe(&self) -> broadcast::Receiver<Response<RpcLogsResponse>>;
+
+    /// 获取当前摄取的程序ID列表
+    fn program_ids(&self) -> Vec<Pubkey>;
+
+    /// 运行时更新程序ID列表，返回列表是否发生变化
+    fn update_program_ids(&self, program_ids: Vec<Pubkey>) -> Result<bool>;
+
+    /// 检查来源是否健康
+    async fn is_healthy(&self) -> bool;
+
+    /// 获取连接统计信息
+    async fn get_stats(&self) -> ConnectionStats;
+
+    /// 查询来源自带的完整交易（如夹具中录制的交易），没有时由调用方通过RPC获取
+    fn recorded_transaction(&self, _signature: &str) -> Option<EncodedConfirmedTransactionWithStatusMeta> {
+        None
+    }
+}
+
+/// logsSubscribe实时订阅来源
+#[async_trait]
+impl IngestionSource for WebSocketManager {
+    fn name(&self) -> &str {
+        "logs_subscribe"
+    }
+
+    async fn start(&self) -> Result<()> {
+        WebSocketManager::start(self).await
+    }
+
+    async fn stop(&self) -> Result<()> {
+        WebSocketManager::stop(self).await
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<Response<RpcLogsResponse>> {
+        WebSocketManager::subscribe(self)
+    }
+
+    fn program_ids(&self) -> Vec<Pubkey> {
+        WebSocketManager::program_ids(self)
+    }
+
+    fn update_program_ids(&self, program_ids: Vec<Pubkey>) -> Result<bool> {
+        WebSocketManager::update_program_ids(self, program_ids)
+    }
+
+    async fn is_healthy(&self) -> bool {
+        WebSocketManager::is_healthy(self).await
+    }
+
+    async fn get_stats(&self) -> ConnectionStats {
+        WebSocketManager::get_stats(self).await
+    }
+}
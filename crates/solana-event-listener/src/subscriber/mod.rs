@@ -1,7 +1,13 @@
+pub mod endpoint_pool;
 pub mod event_filter;
+pub mod fixture_source;
+pub mod ingestion_source;
 pub mod subscription_manager;
 pub mod websocket_manager;
 
+pub use endpoint_pool::{EndpointPool, EndpointRole, EndpointStats};
 pub use event_filter::EventFilter;
+pub use fixture_source::{FixtureRecord, FixtureRecorder, FixtureReplaySource};
+pub use ingestion_source::IngestionSource;
 pub use subscription_manager::SubscriptionManager;
 pub use websocket_manager::WebSocketManager;
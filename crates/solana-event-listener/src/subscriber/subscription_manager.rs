@@ -1,13 +1,24 @@
 use crate::{
-    config::EventListenerConfig,
-    error::Result,
-    metrics::MetricsCollector,
-    parser::{EventDataSource, EventParserRegistry},
+    config::{settings::IngestionMode, EventListenerConfig},
+    error::{EventListenerError, Result},
+    metrics::{LagVerdict, MetricsCollector},
+    parser::{
+        transaction_fallback::{fetch_transaction, is_log_truncated, recover_truncated_events},
+        EventDataSource, EventParserRegistry, ParsedEvent,
+    },
     persistence::BatchWriter,
-    subscriber::{EventFilter, WebSocketManager},
+    recovery::gap_tracker::GapTracker,
+    subscriber::{
+        endpoint_pool::{EndpointPool, EndpointRole},
+        EventFilter, FixtureRecorder, FixtureReplaySource, IngestionSource, WebSocketManager,
+    },
 };
 use dashmap::DashMap;
-use solana_client::{rpc_client::RpcClient, rpc_response::RpcLogsResponse};
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_response::{Response, RpcLogsResponse},
+};
+use solana_sdk::pubkey::Pubkey;
 use std::{
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
@@ -15,24 +26,31 @@ use std::{
     },
     time::{Duration, Instant},
 };
-use tokio::{sync::RwLock, time::interval};
+use tokio::{
+    sync::{broadcast, RwLock},
+    time::interval,
+};
 use tracing::{debug, error, info, warn};
 pub const ZERO_SIG: &str = "1111111111111111111111111111111111111111111111111111111111111111";
 /// 订阅管理器
 ///
 /// 负责协调所有订阅相关的组件:
-/// - WebSocket连接管理
+/// - 事件摄取来源管理（WebSocket实时订阅或夹具回放）
 /// - 事件过滤和路由
 /// - 事件解析和持久化
 /// - 性能监控和统计
 pub struct SubscriptionManager {
     config: Arc<EventListenerConfig>,
-    websocket_manager: Arc<WebSocketManager>,
-    event_filter: Arc<EventFilter>,
+    ingestion_source: Arc<dyn IngestionSource>,
+    // 录制模式下把收到的日志和补回的交易写入夹具文件
+    fixture_recorder: Option<Arc<FixtureRecorder>>,
+    event_filter: Arc<RwLock<EventFilter>>,
     parser_registry: Arc<EventParserRegistry>,
     batch_writer: Arc<BatchWriter>,
     metrics: Arc<MetricsCollector>,
     rpc_client: Arc<RpcClient>,
+    // 日志截断时获取完整交易的RPC端点池
+    rpc_pool: Arc<EndpointPool>,
 
     // 运行状态
     is_running: Arc<AtomicBool>,
@@ -44,6 +62,9 @@ pub struct SubscriptionManager {
 
     // 签名缓存（防重复处理）
     signature_cache: Arc<DashMap<String, Instant>>,
+
+    // 断线缺口追踪
+    gap_tracker: Arc<GapTracker>,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -58,7 +79,7 @@ pub struct SubscriptionStats {
 }
 
 impl SubscriptionManager {
-    /// 创建新的订阅管理器
+    /// 创建新的订阅管理器，按配置选择事件摄取来源
     pub async fn new(
         config: &EventListenerConfig,
         parser_registry: Arc<EventParserRegistry>,
@@ -67,35 +88,117 @@ impl SubscriptionManager {
     ) -> Result<Self> {
         let config = Arc::new(config.clone());
 
+        // 创建签名缓存
+        let signature_cache = Arc::new(DashMap::new());
+
+        // 创建断线缺口追踪器
+        let gap_tracker = Arc::new(GapTracker::new(Arc::clone(&signature_cache)));
+
+        // 创建事件摄取来源
+        let ingestion = config.ingestion.clone().unwrap_or_default();
+        let ingestion_source: Arc<dyn IngestionSource> = match ingestion.mode {
+            IngestionMode::LogsSubscribe => {
+                let websocket_manager =
+                    Arc::new(WebSocketManager::new(Arc::clone(&config))?.with_gap_tracker(Arc::clone(&gap_tracker)));
+                metrics.register_endpoint_pool(websocket_manager.endpoint_pool()).await;
+                websocket_manager
+            }
+            IngestionMode::Replay => {
+                let fixture_path = ingestion
+                    .fixture_path
+                    .as_deref()
+                    .ok_or_else(|| EventListenerError::Config("回放模式必须配置夹具路径".to_string()))?;
+                Arc::new(
+                    FixtureReplaySource::from_path(
+                        fixture_path,
+                        config.solana.program_ids.clone(),
+                        Duration::from_millis(ingestion.replay_interval_ms),
+                    )
+                    .await?,
+                )
+            }
+        };
+
+        Self::assemble(
+            config,
+            ingestion_source,
+            signature_cache,
+            gap_tracker,
+            parser_registry,
+            batch_writer,
+            metrics,
+        )
+    }
+
+    /// 使用指定的事件摄取来源创建订阅管理器（用于夹具回放测试等场景）
+    pub fn with_ingestion_source(
+        config: &EventListenerConfig,
+        ingestion_source: Arc<dyn IngestionSource>,
+        parser_registry: Arc<EventParserRegistry>,
+        batch_writer: Arc<BatchWriter>,
+        metrics: Arc<MetricsCollector>,
+    ) -> Result<Self> {
+        let signature_cache = Arc::new(DashMap::new());
+        let gap_tracker = Arc::new(GapTracker::new(Arc::clone(&signature_cache)));
+
+        Self::assemble(
+            Arc::new(config.clone()),
+            ingestion_source,
+            signature_cache,
+            gap_tracker,
+            parser_registry,
+            batch_writer,
+            metrics,
+        )
+    }
+
+    fn assemble(
+        config: Arc<EventListenerConfig>,
+        ingestion_source: Arc<dyn IngestionSource>,
+        signature_cache: Arc<DashMap<String, Instant>>,
+        gap_tracker: Arc<GapTracker>,
+        parser_registry: Arc<EventParserRegistry>,
+        batch_writer: Arc<BatchWriter>,
+        metrics: Arc<MetricsCollector>,
+    ) -> Result<Self> {
+        info!("📡 事件摄取来源: {}", ingestion_source.name());
+
         // 创建RPC客户端
         let rpc_client = Arc::new(RpcClient::new(&config.solana.rpc_url));
+        let rpc_pool = Arc::new(EndpointPool::new(&config, EndpointRole::Rpc)?);
 
-        // 创建WebSocket管理器
-        let websocket_manager = Arc::new(WebSocketManager::new(Arc::clone(&config))?);
+        let fixture_recorder = config
+            .ingestion
+            .as_ref()
+            .and_then(|ingestion| ingestion.record_path.as_ref())
+            .map(|path| {
+                info!("📼 录制模式已启用，夹具写入: {}", path);
+                Arc::new(FixtureRecorder::new(path))
+            });
 
         // 创建事件过滤器
-        let event_filter = Arc::new(
-            EventFilter::accept_all(config.solana.program_ids.clone()) // 传递多个程序ID
+        let event_filter = Arc::new(RwLock::new(
+            EventFilter::accept_all(ingestion_source.program_ids()) // 传递多个程序ID
                 .with_error_filtering(true) // 过滤失败的交易
                 .with_min_log_length(1), // 至少要有一条日志
-        );
-
-        // 创建签名缓存
-        let signature_cache = Arc::new(DashMap::new());
+        ));
 
         Ok(Self {
             config,
-            websocket_manager,
+            ingestion_source,
+            fixture_recorder,
             event_filter,
             parser_registry,
             batch_writer,
             metrics,
             rpc_client,
+            rpc_pool,
             is_running: Arc::new(AtomicBool::new(false)),
             processed_events: Arc::new(AtomicU64::new(0)),
             failed_events: Arc::new(AtomicU64::new(0)),
             last_activity: Arc::new(RwLock::new(None)),
             signature_cache,
+            gap_tracker,
         })
     }
 
@@ -109,17 +212,20 @@ impl SubscriptionManager {
         self.is_running.store(true, Ordering::Relaxed);
         info!("🚀 启动订阅管理器");
 
-        // 启动WebSocket管理器
-        let websocket_manager = Arc::clone(&self.websocket_manager);
+        // 先订阅再启动摄取来源，避免回放来源在处理循环就绪前发出的通知丢失
+        let event_receiver = self.ingestion_source.subscribe();
+
+        // 启动事件摄取来源
+        let ingestion_source = Arc::clone(&self.ingestion_source);
         let ws_task = tokio::spawn(async move {
-            if let Err(e) = websocket_manager.start().await {
-                error!("WebSocket管理器启动失败: {}", e);
+            if let Err(e) = ingestion_source.start().await {
+                error!("事件摄取来源{}启动失败: {}", ingestion_source.name(), e);
             }
         });
 
         // 启动WebSocket连接状态监控
         let ws_monitor_task = {
-            let websocket_manager = Arc::clone(&self.websocket_manager);
+            let ingestion_source = Arc::clone(&self.ingestion_source);
             let metrics = Arc::clone(&self.metrics);
             tokio::spawn(async move {
                 let mut last_connected = false;
@@ -129,7 +235,7 @@ impl SubscriptionManager {
                 loop {
                     interval.tick().await;
 
-                    let stats = websocket_manager.get_stats().await;
+                    let stats = ingestion_source.get_stats().await;
                     let currently_connected = stats.is_connected;
 
                     // 检测到新连接
@@ -162,7 +268,7 @@ impl SubscriptionManager {
         let event_processing_task = {
             let manager = self.clone();
             tokio::spawn(async move {
-                manager.event_processing_loop().await;
+                manager.event_processing_loop(event_receiver).await;
             })
         };
 
@@ -174,6 +280,19 @@ impl SubscriptionManager {
             })
         };
 
+        // 启动链上进度落后监控（回放模式不连接RPC，不查询链上slot）
+        let replay = self
+            .config
+            .ingestion
+            .as_ref()
+            .is_some_and(|ingestion| ingestion.mode == IngestionMode::Replay);
+        if !replay {
+            let manager = self.clone();
+            tokio::spawn(async move {
+                manager.lag_monitor_loop().await;
+            });
+        }
+
         // 等待任务完成或停止信号
         tokio::select! {
             _ = ws_task => {
@@ -198,36 +317,41 @@ impl SubscriptionManager {
         info!("🛑 停止订阅管理器");
         self.is_running.store(false, Ordering::Relaxed);
 
-        // 停止WebSocket管理器
-        self.websocket_manager.stop().await?;
+        // 停止事件摄取来源
+        self.ingestion_source.stop().await?;
 
         Ok(())
     }
 
     /// 事件处理主循环
-    async fn event_processing_loop(&self) {
+    async fn event_processing_loop(&self, mut event_receiver: broadcast::Receiver<Response<RpcLogsResponse>>) {
         info!("📡 启动事件处理循环");
 
-        let mut event_receiver = self.websocket_manager.subscribe();
-        info!("📡 已订阅WebSocket事件，开始处理循环");
-
         while self.is_running.load(Ordering::Relaxed) {
             match tokio::time::timeout(Duration::from_millis(100), event_receiver.recv()).await {
-                Ok(Ok(log_response)) => {
-                    info!("📨 订阅管理器接收到事件: {}", log_response.signature);
-                    if log_response.signature == ZERO_SIG {
-                        info!("📨 跳过零事件: {}", log_response.signature);
+                Ok(Ok(notification)) => {
+                    let signature = &notification.value.signature;
+                    info!("📨 订阅管理器接收到事件: {}", signature);
+                    if signature == ZERO_SIG {
+                        info!("📨 跳过零事件: {}", signature);
                         continue;
                     }
 
                     // 在这里进行去重检查，防止重复事件进入处理队列
-                    if self.is_signature_processed(&log_response.signature) {
-                        info!("⏭️ 事件已处理，跳过: {}", log_response.signature);
+                    if self.is_signature_processed(signature) {
+                        info!("⏭️ 事件已处理，跳过: {}", signature);
                         continue;
                     }
 
                     // 立即标记为已处理，防止并发重复
-                    self.mark_signature_processed(&log_response.signature);
+                    self.mark_signature_processed(signature);
+
+                    // 录制模式下写入夹具文件
+                    if let Some(recorder) = &self.fixture_recorder {
+                        if let Err(e) = recorder.record_logs(&notification).await {
+                            warn!("📼 录制日志通知失败: {} - {}", signature, e);
+                        }
+                    }
 
                     // 更新活动时间
                     {
@@ -238,7 +362,7 @@ impl SubscriptionManager {
                     // 异步处理事件（不阻塞接收）
                     let manager_clone = self.clone();
                     tokio::spawn(async move {
-                        if let Err(e) = manager_clone.process_event(log_response).await {
+                        if let Err(e) = manager_clone.process_event(notification).await {
                             debug!("处理事件失败: {}", e);
                         }
                     });
@@ -252,8 +376,8 @@ impl SubscriptionManager {
                         tokio::sync::broadcast::error::RecvError::Lagged(skipped) => {
                             warn!("⚠️ 事件接收器滞后，跳过了 {} 个事件 - 尝试继续处理", skipped);
                             // 重新订阅以获取新的接收器
-                            event_receiver = self.websocket_manager.subscribe();
-                            info!("📡 重新订阅WebSocket事件");
+                            event_receiver = self.ingestion_source.subscribe();
+                            info!("📡 重新订阅摄取来源事件");
                             continue;
                         }
                     }
@@ -270,8 +394,6 @@ impl SubscriptionManager {
 
     /// 获取当前slot
     async fn get_current_slot_internal(&self) -> Result<u64> {
-        use crate::error::EventListenerError;
-
         tokio::task::spawn_blocking({
             let rpc_client = Arc::clone(&self.rpc_client);
             move || {
@@ -284,6 +406,65 @@ impl SubscriptionManager {
         .map_err(|e| EventListenerError::Unknown(format!("异步任务执行失败: {}", e)))?
     }
 
+    /// 查询slot的出块时间
+    async fn get_block_time_internal(&self, slot: u64) -> Result<i64> {
+        tokio::task::spawn_blocking({
+            let rpc_client = Arc::clone(&self.rpc_client);
+            move || {
+                rpc_client
+                    .get_block_time(slot)
+                    .map_err(|e| EventListenerError::WebSocket(format!("获取slot {}出块时间失败: {}", slot, e)))
+            }
+        })
+        .await
+        .map_err(|e| EventListenerError::Unknown(format!("异步任务执行失败: {}", e)))?
+    }
+
+    /// 链上进度落后监控循环
+    ///
+    /// 定期查询链上最新slot和最近处理事件的出块时间，超过阈值时输出告警
+    async fn lag_monitor_loop(&self) {
+        // 每轮最多查询的出块时间数量，避免事件类型很多时占满RPC
+        const MAX_BLOCK_TIME_LOOKUPS: usize = 16;
+
+        let lag_tracker = self.metrics.lag_tracker();
+        let poll_interval = Duration::from_secs(lag_tracker.config().tip_poll_interval_secs.max(1));
+        info!("🐢 启动链上进度落后监控，查询间隔{:?}", poll_interval);
+
+        let mut interval = interval(poll_interval);
+        let mut was_degraded = false;
+        while self.is_running.load(Ordering::Relaxed) {
+            interval.tick().await;
+
+            match self.get_current_slot_internal().await {
+                Ok(slot) => lag_tracker.record_chain_tip(slot),
+                Err(e) => warn!("⚠️ 查询链上最新slot失败: {}", e),
+            }
+
+            for slot in lag_tracker
+                .slots_missing_block_time()
+                .into_iter()
+                .take(MAX_BLOCK_TIME_LOOKUPS)
+            {
+                match self.get_block_time_internal(slot).await {
+                    Ok(block_time) => lag_tracker.record_block_time(slot, block_time),
+                    Err(e) => debug!("查询出块时间失败: {}", e),
+                }
+            }
+
+            let report = lag_tracker.report();
+            let degraded = report.verdict == LagVerdict::Degraded;
+            if degraded {
+                warn!("🐢 事件处理落后链上: {}", report.reasons.join("; "));
+            } else if was_degraded {
+                info!("✅ 事件处理已追上链上进度");
+            }
+            was_degraded = degraded;
+        }
+
+        info!("🐢 链上进度落后监控已停止");
+    }
+
     /// 获取当前slot (测试可见)
     #[cfg(test)]
     pub async fn get_current_slot(&self) -> Result<u64> {
@@ -292,43 +473,66 @@ impl SubscriptionManager {
     }
 
     /// 处理单个事件
-    async fn process_event(&self, log_response: RpcLogsResponse) -> Result<()> {
+    async fn process_event(&self, notification: Response<RpcLogsResponse>) -> Result<()> {
+        let Response {
+            context,
+            value: log_response,
+        } = notification;
         let signature = &log_response.signature;
 
         info!("🔍 开始处理事件: {}", signature);
         info!("🔍 事件日志: {:?}", log_response.logs);
 
-        // 获取当前slot，如果失败则使用0作为备用值
-        let slot = match self.get_current_slot_internal().await {
-            Ok(slot) => slot,
-            Err(e) => {
-                warn!("⚠️ 无法获取当前slot: {}, 使用默认值0", e);
-                0
+        // 优先使用通知上下文中的slot，缺失时查询当前slot，查询失败则使用0作为备用值
+        let slot = if context.slot > 0 {
+            context.slot
+        } else {
+            match self.get_current_slot_internal().await {
+                Ok(slot) => slot,
+                Err(e) => {
+                    warn!("⚠️ 无法获取当前slot: {}, 使用默认值0", e);
+                    0
+                }
             }
         };
 
         debug!("🔍 处理事件: {} (slot: {})", signature, slot);
 
         // 应用事件过滤器
-        if !self.event_filter.should_process(&log_response) {
+        if !self.event_filter.read().await.should_process(&log_response) {
             info!("🚫 事件被过滤器拒绝: {}", signature);
             return Ok(());
         }
 
         info!("🔍 事件通过过滤器，开始解析: {}", signature);
 
+        // 记录各程序在实时路径上的处理位置，用于断线后的缺口回填
+        for program_id in self.extract_program_ids_from_logs(&log_response.logs) {
+            self.gap_tracker.record_processed(program_id, signature, slot);
+        }
+
         // 尝试解析所有事件（使用智能路由多事件处理）- 标记为WebSocket订阅数据源
-        match self
+        let program_ids = self.ingestion_source.program_ids();
+        let parse_result = self
             .parser_registry
             .parse_all_events_with_context(
                 &log_response.logs,
                 signature,
                 slot,
-                &self.config.solana.program_ids,
+                &program_ids,
                 Some(EventDataSource::WebSocketSubscription),
             )
-            .await
-        {
+            .await;
+
+        // 日志被截断时，截断之后的事件只能从完整交易的指令中补回
+        let parse_result = match parse_result {
+            Ok(parsed_events) if is_log_truncated(&log_response.logs) => {
+                Ok(self.recover_from_full_transaction(signature, slot, parsed_events).await)
+            }
+            other => other,
+        };
+
+        match parse_result {
             Ok(parsed_events) if !parsed_events.is_empty() => {
                 info!(
                     "✅ 事件解析成功: {} -> 发现{}个事件: {:?}",
@@ -354,6 +558,12 @@ impl SubscriptionManager {
                     for _ in 0..event_count {
                         self.metrics.record_event_processed_for_program(&prog_id).await?;
                     }
+
+                    // 记录各事件类型的最新处理位置，用于判断是否落后链上
+                    let lag_tracker = self.metrics.lag_tracker();
+                    for event in &parsed_events {
+                        lag_tracker.record_processed(&prog_id, event.event_type(), event.slot());
+                    }
                 }
                 self.processed_events.fetch_add(event_count as u64, Ordering::Relaxed);
 
@@ -384,8 +594,69 @@ impl SubscriptionManager {
         Ok(())
     }
 
+    /// 日志被截断时从完整交易中补回事件，获取或解析失败时保留日志中已解析的事件
+    async fn recover_from_full_transaction(
+        &self,
+        signature: &str,
+        slot: u64,
+        log_events: Vec<ParsedEvent>,
+    ) -> Vec<ParsedEvent> {
+        warn!("✂️ 交易日志被截断，从完整交易中解析事件: {}", signature);
+
+        let transaction = match self.ingestion_source.recorded_transaction(signature) {
+            Some(transaction) => Ok(transaction),
+            None => {
+                let transaction = tokio::task::spawn_blocking({
+                    let rpc_pool = Arc::clone(&self.rpc_pool);
+                    let signature = signature.to_string();
+                    move || fetch_transaction(&rpc_pool, &signature)
+                })
+                .await
+                .map_err(|e| EventListenerError::Unknown(format!("异步任务执行失败: {}", e)))
+                .and_then(|result| result);
+
+                // 录制模式下把获取到的完整交易写入夹具，回放时无需RPC
+                if let (Ok(transaction), Some(recorder)) = (&transaction, &self.fixture_recorder) {
+                    if let Err(e) = recorder.record_transaction(signature, transaction).await {
+                        warn!("📼 录制完整交易失败: {} - {}", signature, e);
+                    }
+                }
+                transaction
+            }
+        };
+
+        let result = match transaction {
+            Ok(transaction) => {
+                recover_truncated_events(
+                    &self.parser_registry,
+                    &transaction,
+                    log_events.clone(),
+                    signature,
+                    slot,
+                    &self.ingestion_source.program_ids(),
+                    Some(EventDataSource::WebSocketSubscription),
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok((events, recovered)) => {
+                self.metrics.record_truncated_log_fallback(true, recovered);
+                events
+            }
+            Err(e) => {
+                error!("❌ 日志截断回退解析失败，仅保留日志中的事件: {} - {}", signature, e);
+                self.metrics.record_truncated_log_fallback(false, 0);
+                log_events
+            }
+        }
+    }
+
     /// 从日志中提取程序ID
     fn extract_program_id_from_logs(&self, logs: &[String]) -> Option<String> {
+        let target_ids = self.ingestion_source.program_ids();
         for log in logs {
             // 查找形如 "Program 11111111111111111111111111111111 invoke [1]" 的日志
             if log.starts_with("Program ") && log.contains(" invoke [") {
@@ -393,7 +664,7 @@ impl SubscriptionManager {
                 if parts.len() >= 3 {
                     let program_id = parts[1];
                     // 验证是否是我们监听的程序ID之一
-                    for target_id in &self.config.solana.program_ids {
+                    for target_id in &target_ids {
                         if target_id.to_string() == program_id {
                             return Some(program_id.to_string());
                         }
@@ -404,10 +675,42 @@ impl SubscriptionManager {
         None
     }
 
-    /// 将错误分类为监控类别
-    fn classify_error(&self, error: &crate::error::EventListenerError) -> String {
-        use crate::error::EventListenerError;
+    /// 从日志中提取所有被调用的监听程序ID
+    fn extract_program_ids_from_logs(&self, logs: &[String]) -> Vec<Pubkey> {
+        self.ingestion_source
+            .program_ids()
+            .into_iter()
+            .filter(|program_id| {
+                let invoke_prefix = format!("Program {} invoke [", program_id);
+                logs.iter().any(|log| log.starts_with(&invoke_prefix))
+            })
+            .collect()
+    }
+
+    /// 获取当前订阅的程序ID列表
+    pub fn program_ids(&self) -> Vec<Pubkey> {
+        self.ingestion_source.program_ids()
+    }
+
+    /// 运行时更新订阅的程序ID列表
+    ///
+    /// 同步更新事件过滤器，并让摄取来源按新列表重新订阅；返回列表是否发生变化
+    pub async fn update_program_ids(&self, program_ids: Vec<Pubkey>) -> Result<bool> {
+        let changed = self.ingestion_source.update_program_ids(program_ids.clone())?;
+        if changed {
+            self.event_filter.write().await.target_programs = program_ids;
+            info!("✅ 订阅程序列表已更新，等待重新订阅");
+        }
+        Ok(changed)
+    }
+
+    /// 获取断线缺口追踪器
+    pub fn gap_tracker(&self) -> Arc<GapTracker> {
+        Arc::clone(&self.gap_tracker)
+    }
 
+    /// 将错误分类为监控类别
+    fn classify_error(&self, error: &EventListenerError) -> String {
         match error {
             EventListenerError::EventParsing(_) => "parse_error".to_string(),
             EventListenerError::Database(_) => "database_error".to_string(),
@@ -416,6 +719,7 @@ impl SubscriptionManager {
             EventListenerError::Config(_) => "config_error".to_string(),
             EventListenerError::DiscriminatorMismatch => "discriminator_mismatch".to_string(),
             EventListenerError::Persistence(_) => "persistence_error".to_string(),
+            EventListenerError::OutOfOrder(_) => "out_of_order_error".to_string(),
             EventListenerError::Checkpoint(_) => "checkpoint_error".to_string(),
             EventListenerError::Metrics(_) => "metrics_error".to_string(),
             EventListenerError::SolanaRpc(_) => "solana_rpc_error".to_string(),
@@ -495,7 +799,7 @@ impl SubscriptionManager {
     /// 检查订阅管理器是否健康
     pub async fn is_healthy(&self) -> bool {
         // 检查各个组件的健康状态
-        let websocket_healthy = self.websocket_manager.is_healthy().await;
+        let websocket_healthy = self.ingestion_source.is_healthy().await;
         let batch_writer_healthy = self.batch_writer.is_healthy().await;
 
         // 检查最近是否有活动
@@ -545,17 +849,20 @@ impl Clone for SubscriptionManager {
     fn clone(&self) -> Self {
         Self {
             config: Arc::clone(&self.config),
-            websocket_manager: Arc::clone(&self.websocket_manager),
+            ingestion_source: Arc::clone(&self.ingestion_source),
+            fixture_recorder: self.fixture_recorder.clone(),
             event_filter: Arc::clone(&self.event_filter),
             parser_registry: Arc::clone(&self.parser_registry),
             batch_writer: Arc::clone(&self.batch_writer),
             metrics: Arc::clone(&self.metrics),
             rpc_client: Arc::clone(&self.rpc_client),
+            rpc_pool: Arc::clone(&self.rpc_pool),
             is_running: Arc::clone(&self.is_running),
             processed_events: Arc::clone(&self.processed_events),
             failed_events: Arc::clone(&self.failed_events),
             last_activity: Arc::clone(&self.last_activity),
             signature_cache: Arc::clone(&self.signature_cache),
+            gap_tracker: Arc::clone(&self.gap_tracker),
         }
     }
 }
@@ -576,6 +883,7 @@ mod tests {
                     solana_sdk::pubkey::Pubkey::from_str("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK").unwrap(),
                 ],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -597,8 +905,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         }
     }
 
@@ -654,6 +967,41 @@ mod tests {
         assert_eq!(updated_stats.success_rate, 10.0 / 12.0);
     }
 
+    #[tokio::test]
+    async fn test_gap_tracker_wiring() {
+        let config = create_test_config();
+        let parser_registry = Arc::new(EventParserRegistry::new(&config).unwrap());
+        let batch_writer = Arc::new(BatchWriter::new(&config).await.unwrap());
+        let metrics = Arc::new(MetricsCollector::new(&config).unwrap());
+
+        let manager = SubscriptionManager::new(&config, parser_registry, batch_writer, metrics)
+            .await
+            .unwrap();
+
+        let logs = vec![
+            "Program FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX invoke [1]".to_string(),
+            "Program log: Instruction: Swap".to_string(),
+            "Program 11111111111111111111111111111111 invoke [2]".to_string(),
+        ];
+        let program_ids = manager.extract_program_ids_from_logs(&logs);
+        assert_eq!(program_ids, vec![config.solana.program_ids[0]]);
+
+        // 运行时移除程序后不再记录其处理位置
+        assert!(manager
+            .update_program_ids(vec![config.solana.program_ids[1]])
+            .await
+            .unwrap());
+        assert!(manager.extract_program_ids_from_logs(&logs).is_empty());
+        assert_eq!(
+            manager.event_filter.read().await.target_programs,
+            vec![config.solana.program_ids[1]]
+        );
+
+        // 缺口追踪器与订阅路径共享签名缓存
+        manager.mark_signature_processed("gap_test_signature");
+        assert!(manager.gap_tracker().is_signature_seen("gap_test_signature"));
+    }
+
     #[tokio::test]
     async fn test_get_current_slot() {
         let config = create_test_config();
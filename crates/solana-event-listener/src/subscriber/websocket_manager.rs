@@ -1,24 +1,26 @@
 use crate::{
     config::EventListenerConfig,
     error::{EventListenerError, Result},
+    recovery::gap_tracker::GapTracker,
+    subscriber::endpoint_pool::{EndpointPool, EndpointRole, EndpointStats},
 };
 use backoff::{future::retry, ExponentialBackoff};
 use futures::StreamExt;
 use solana_client::{
     nonblocking::pubsub_client::PubsubClient,
     rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
-    rpc_response::RpcLogsResponse,
+    rpc_response::{Response, RpcLogsResponse},
 };
 use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
 use std::{
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, RwLock as StdRwLock,
     },
     time::{Duration, Instant},
 };
 use tokio::{
-    sync::{broadcast, RwLock},
+    sync::{broadcast, Notify, RwLock},
     time::sleep,
 };
 use tracing::{debug, error, info, warn};
@@ -28,17 +30,23 @@ use tracing::{debug, error, info, warn};
 /// 负责:
 /// - 维护与Solana WebSocket的持久连接
 /// - 实现断线重连和指数退避
-/// - 处理订阅和取消订阅
+/// - 在多个端点之间故障切换，主端点恢复后自动切回
+/// - 处理订阅和取消订阅，程序列表变化时重新订阅
 /// - 提供连接状态监控
 pub struct WebSocketManager {
     config: Arc<EventListenerConfig>,
-    program_ids: Vec<Pubkey>,
+    /// 订阅的程序ID列表（运行时配置可更新）
+    program_ids: Arc<StdRwLock<Vec<Pubkey>>>,
+    /// 程序列表变化时通知当前连接重新订阅
+    resubscribe_notify: Arc<Notify>,
     is_connected: Arc<AtomicBool>,
     is_running: Arc<AtomicBool>,
     connection_count: Arc<RwLock<u64>>,
     last_connection_time: Arc<RwLock<Option<Instant>>>,
-    event_sender: broadcast::Sender<RpcLogsResponse>,
-    _event_receiver: broadcast::Receiver<RpcLogsResponse>,
+    endpoint_pool: Arc<EndpointPool>,
+    gap_tracker: Option<Arc<GapTracker>>,
+    event_sender: broadcast::Sender<Response<RpcLogsResponse>>,
+    _event_receiver: broadcast::Receiver<Response<RpcLogsResponse>>,
 }
 
 #[derive(Debug, Clone)]
@@ -48,6 +56,9 @@ pub struct ConnectionStats {
     pub connection_count: u64,
     pub last_connection_time: Option<Instant>,
     pub uptime_seconds: Option<u64>,
+    pub current_endpoint: Option<String>,
+    pub failover_count: u64,
+    pub endpoints: Vec<EndpointStats>,
 }
 
 impl WebSocketManager {
@@ -60,19 +71,29 @@ impl WebSocketManager {
         }
 
         let (event_sender, event_receiver) = broadcast::channel(10240); // 增加到10倍缓冲区
+        let endpoint_pool = Arc::new(EndpointPool::new(&config, EndpointRole::WebSocket)?);
 
         Ok(Self {
             config,
-            program_ids,
+            program_ids: Arc::new(StdRwLock::new(program_ids)),
+            resubscribe_notify: Arc::new(Notify::new()),
             is_connected: Arc::new(AtomicBool::new(false)),
             is_running: Arc::new(AtomicBool::new(false)),
             connection_count: Arc::new(RwLock::new(0)),
             last_connection_time: Arc::new(RwLock::new(None)),
+            endpoint_pool,
+            gap_tracker: None,
             event_sender,
             _event_receiver: event_receiver,
         })
     }
 
+    /// 设置断线缺口追踪器，断线和重连时通知其调度缺口回填
+    pub fn with_gap_tracker(mut self, gap_tracker: Arc<GapTracker>) -> Self {
+        self.gap_tracker = Some(gap_tracker);
+        self
+    }
+
     /// 启动WebSocket连接管理
     pub async fn start(&self) -> Result<()> {
         if self.is_running.load(Ordering::Relaxed) {
@@ -81,10 +102,11 @@ impl WebSocketManager {
         }
 
         self.is_running.store(true, Ordering::Relaxed);
+        let program_ids = self.program_ids();
         info!(
             "🔌 启动WebSocket连接管理器，监听{}个程序: {:?}",
-            self.program_ids.len(),
-            self.program_ids
+            program_ids.len(),
+            program_ids
         );
 
         // 根据配置选择重连策略
@@ -165,10 +187,33 @@ impl WebSocketManager {
 
     /// 建立连接并订阅事件
     async fn connect_and_subscribe(&self) -> Result<()> {
-        debug!("🔗 尝试连接到WebSocket: {}", self.config.solana.ws_url);
+        let (endpoint_index, endpoint) = self.endpoint_pool.select();
+        debug!("🔗 尝试连接到WebSocket端点: {}", endpoint.name);
+
+        let result = match self.subscribe_and_listen(&endpoint.ws_url, endpoint_index).await {
+            Ok(false) => return Ok(()),
+            // 主动断开以切回更高优先级端点或按新的程序列表重新订阅，不计入当前端点的失败
+            Ok(true) => Err(EventListenerError::WebSocket("主动断开以重新建立订阅".to_string())),
+            Err(e) => {
+                self.endpoint_pool.record_failure(endpoint_index, &e.to_string());
+                Err(e)
+            }
+        };
+
+        // 记录断线位置，重连后回填断线期间的缺口
+        if let Some(gap_tracker) = &self.gap_tracker {
+            gap_tracker.mark_disconnected();
+        }
 
+        result
+    }
+
+    /// 在指定端点上订阅并处理事件流
+    ///
+    /// 返回true表示需要重新建立订阅（切回更高优先级端点或程序列表已变化）
+    async fn subscribe_and_listen(&self, ws_url: &str, endpoint_index: usize) -> Result<bool> {
         // 创建PubSub客户端
-        let pubsub_client = PubsubClient::new(&self.config.solana.ws_url)
+        let pubsub_client = PubsubClient::new(ws_url)
             .await
             .map_err(|e| EventListenerError::WebSocket(format!("创建PubSub客户端失败: {}", e)))?;
 
@@ -177,21 +222,18 @@ impl WebSocketManager {
             commitment: Some(parse_commitment_config(&self.config.solana.commitment)),
         };
 
-        info!("📡 为{}个程序创建独立订阅", self.program_ids.len());
+        // 本次连接使用的程序列表快照，列表变化时通过resubscribe_notify重新订阅
+        let program_ids = self.program_ids();
+        info!("📡 为{}个程序创建独立订阅", program_ids.len());
 
         // 存储所有订阅流和取消订阅句柄
         let mut all_subscriptions = Vec::new();
         let mut _all_unsubscribes = Vec::new();
 
         // 为每个程序ID创建独立的订阅
-        for (index, program_id) in self.program_ids.iter().enumerate() {
+        for (index, program_id) in program_ids.iter().enumerate() {
             let program_id_string = program_id.to_string();
-            info!(
-                "📡 订阅程序 {}/{}: {}",
-                index + 1,
-                self.program_ids.len(),
-                program_id_string
-            );
+            info!("📡 订阅程序 {}/{}: {}", index + 1, program_ids.len(), program_id_string);
 
             // 为单个程序ID创建订阅
             let (logs_subscription, logs_unsubscribe) = pubsub_client
@@ -208,7 +250,11 @@ impl WebSocketManager {
         }
 
         // 更新连接状态
+        self.endpoint_pool.record_success(endpoint_index);
         self.is_connected.store(true, Ordering::Relaxed);
+        if let Some(gap_tracker) = &self.gap_tracker {
+            gap_tracker.on_reconnected();
+        }
         {
             let mut count = self.connection_count.write().await;
             *count += 1;
@@ -237,18 +283,41 @@ impl WebSocketManager {
 
         let mut merged_stream = select_all(streams);
 
+        // 定期检查是否可以切回更高权重的端点
+        let mut failback_timer = tokio::time::interval(Duration::from_secs(
+            self.config.solana.failover.failback_check_interval_secs.max(1),
+        ));
+        failback_timer.tick().await;
+
         // 处理合并后的事件流
         while self.is_running.load(Ordering::Relaxed) {
-            match merged_stream.next().await {
+            let next_event = tokio::select! {
+                next_event = merged_stream.next() => next_event,
+                _ = failback_timer.tick() => {
+                    if self.endpoint_pool.should_failback() {
+                        info!("🔙 更高优先级的WebSocket端点已恢复，准备切回");
+                        self.is_connected.store(false, Ordering::Relaxed);
+                        return Ok(true);
+                    }
+                    continue;
+                }
+                _ = self.resubscribe_notify.notified() => {
+                    info!("🔁 订阅程序列表已更新，重新建立订阅");
+                    self.is_connected.store(false, Ordering::Relaxed);
+                    return Ok(true);
+                }
+            };
+
+            match next_event {
                 Some((_subscription_idx, program_idx, log_response)) => {
-                    let program_id = &self.program_ids[program_idx];
+                    let program_id = &program_ids[program_idx];
                     debug!(
                         "📨 接收到程序 {} 的日志事件: {}",
                         program_id, log_response.value.signature
                     );
 
-                    // 广播事件给所有订阅者
-                    match self.event_sender.send(log_response.value) {
+                    // 广播事件给所有订阅者（保留通知上下文中的slot）
+                    match self.event_sender.send(log_response) {
                         Ok(receiver_count) => {
                             debug!("✅ 事件广播成功，接收者数量: {}", receiver_count);
                         }
@@ -265,11 +334,37 @@ impl WebSocketManager {
             }
         }
 
-        Ok(())
+        Ok(false)
+    }
+
+    /// 获取当前订阅的程序ID列表
+    pub fn program_ids(&self) -> Vec<Pubkey> {
+        self.program_ids.read().unwrap().clone()
+    }
+
+    /// 更新订阅的程序ID列表，列表变化时通知当前连接重新订阅
+    ///
+    /// 返回列表是否发生变化
+    pub fn update_program_ids(&self, program_ids: Vec<Pubkey>) -> Result<bool> {
+        if program_ids.is_empty() {
+            return Err(EventListenerError::Config("程序ID列表不能为空".to_string()));
+        }
+
+        {
+            let mut current = self.program_ids.write().unwrap();
+            if *current == program_ids {
+                return Ok(false);
+            }
+            info!("📝 订阅程序列表更新: {:?} -> {:?}", *current, program_ids);
+            *current = program_ids;
+        }
+
+        self.resubscribe_notify.notify_one();
+        Ok(true)
     }
 
     /// 获取事件接收器
-    pub fn subscribe(&self) -> broadcast::Receiver<RpcLogsResponse> {
+    pub fn subscribe(&self) -> broadcast::Receiver<Response<RpcLogsResponse>> {
         self.event_sender.subscribe()
     }
 
@@ -290,9 +385,17 @@ impl WebSocketManager {
             connection_count,
             last_connection_time,
             uptime_seconds,
+            current_endpoint: self.endpoint_pool.current_endpoint_name(),
+            failover_count: self.endpoint_pool.failover_count(),
+            endpoints: self.endpoint_pool.stats(),
         }
     }
 
+    /// 获取WebSocket端点池
+    pub fn endpoint_pool(&self) -> Arc<EndpointPool> {
+        Arc::clone(&self.endpoint_pool)
+    }
+
     /// 手动重连
     pub async fn reconnect(&self) -> Result<()> {
         info!("🔄 手动重连WebSocket");
@@ -309,11 +412,14 @@ impl Clone for WebSocketManager {
     fn clone(&self) -> Self {
         Self {
             config: Arc::clone(&self.config),
-            program_ids: self.program_ids.clone(),
+            program_ids: Arc::clone(&self.program_ids),
+            resubscribe_notify: Arc::clone(&self.resubscribe_notify),
             is_connected: Arc::clone(&self.is_connected),
             is_running: Arc::clone(&self.is_running),
             connection_count: Arc::clone(&self.connection_count),
             last_connection_time: Arc::clone(&self.last_connection_time),
+            endpoint_pool: Arc::clone(&self.endpoint_pool),
+            gap_tracker: self.gap_tracker.clone(),
             event_sender: self.event_sender.clone(),
             _event_receiver: self.event_sender.subscribe(),
         }
@@ -344,6 +450,7 @@ mod tests {
                 commitment: "confirmed".to_string(),
                 program_ids: vec![Pubkey::from_str("FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX").unwrap()],
                 private_key: None,
+                failover: crate::config::settings::EndpointFailoverConfig::default(),
             },
             database: crate::config::settings::DatabaseConfig {
                 uri: "mongodb://localhost:27017".to_string(),
@@ -365,8 +472,13 @@ mod tests {
                 metrics_interval_secs: 60,
                 enable_performance_monitoring: true,
                 health_check_interval_secs: 30,
+                http_bind_addr: None,
             },
             backfill: None,
+            finality: None,
+            idl_events: None,
+            ingestion: None,
+            lag_monitor: None,
         }
     }
 
@@ -389,6 +501,31 @@ mod tests {
         assert!(!stats.is_running);
         assert_eq!(stats.connection_count, 0);
         assert!(stats.last_connection_time.is_none());
+        assert_eq!(stats.current_endpoint.as_deref(), Some("primary"));
+        assert_eq!(stats.failover_count, 0);
+        assert_eq!(stats.endpoints.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_update_program_ids() {
+        let config = Arc::new(create_test_config());
+        let manager = WebSocketManager::new(Arc::clone(&config)).unwrap();
+        let cloned = manager.clone();
+
+        assert!(!manager.update_program_ids(config.solana.program_ids.clone()).unwrap());
+        assert!(manager.update_program_ids(Vec::new()).is_err());
+
+        let new_program = Pubkey::new_unique();
+        assert!(manager
+            .update_program_ids(vec![config.solana.program_ids[0], new_program])
+            .unwrap());
+        // 克隆的管理器共享程序列表
+        assert_eq!(cloned.program_ids(), vec![config.solana.program_ids[0], new_program]);
+
+        // 变化后留下重新订阅通知
+        tokio::time::timeout(Duration::from_millis(100), manager.resubscribe_notify.notified())
+            .await
+            .unwrap();
     }
 
     #[test]
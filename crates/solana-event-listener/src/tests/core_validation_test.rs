@@ -39,6 +39,7 @@ fn test_parser_registry() {
             commitment: "confirmed".to_string(),
             program_ids: vec![solana_sdk::pubkey::Pubkey::new_unique()],
             private_key: None,
+            failover: crate::config::settings::EndpointFailoverConfig::default(),
         },
         database: crate::config::settings::DatabaseConfig {
             uri: "mongodb://localhost:27017".to_string(),
@@ -60,8 +61,13 @@ fn test_parser_registry() {
             metrics_interval_secs: 5,
             enable_performance_monitoring: true,
             health_check_interval_secs: 10,
+            http_bind_addr: None,
         },
         backfill: None,
+        finality: None,
+        idl_events: None,
+        ingestion: None,
+        lag_monitor: None,
     };
     let registry = EventParserRegistry::new(&config).unwrap();
     let parser_count = registry.parser_count();
@@ -91,6 +97,7 @@ async fn test_metrics_collector() {
             commitment: "confirmed".to_string(),
             program_ids: vec![solana_sdk::pubkey::Pubkey::new_unique()],
             private_key: None,
+            failover: crate::config::settings::EndpointFailoverConfig::default(),
         },
         database: crate::config::settings::DatabaseConfig {
             uri: "mongodb://localhost:27017".to_string(),
@@ -112,8 +119,13 @@ async fn test_metrics_collector() {
             metrics_interval_secs: 5,
             enable_performance_monitoring: true,
             health_check_interval_secs: 10,
+            http_bind_addr: None,
         },
         backfill: None,
+        finality: None,
+        idl_events: None,
+        ingestion: None,
+        lag_monitor: None,
     };
     let collector = MetricsCollector::new(&config).unwrap();
 
@@ -221,6 +233,7 @@ fn test_parser_creation() -> bool {
             commitment: "confirmed".to_string(),
             program_ids: vec![solana_sdk::pubkey::Pubkey::new_unique()],
             private_key: None,
+            failover: crate::config::settings::EndpointFailoverConfig::default(),
         },
         database: crate::config::settings::DatabaseConfig {
             uri: "mongodb://localhost:27017".to_string(),
@@ -242,8 +255,13 @@ fn test_parser_creation() -> bool {
             metrics_interval_secs: 5,
             enable_performance_monitoring: true,
             health_check_interval_secs: 10,
+            http_bind_addr: None,
         },
         backfill: None,
+        finality: None,
+        idl_events: None,
+        ingestion: None,
+        lag_monitor: None,
     };
     EventParserRegistry::new(&config).is_ok()
 }
@@ -259,6 +277,7 @@ async fn test_metrics_creation() -> bool {
             commitment: "confirmed".to_string(),
             program_ids: vec![solana_sdk::pubkey::Pubkey::new_unique()],
             private_key: None,
+            failover: crate::config::settings::EndpointFailoverConfig::default(),
         },
         database: crate::config::settings::DatabaseConfig {
             uri: "mongodb://localhost:27017".to_string(),
@@ -280,8 +299,13 @@ async fn test_metrics_creation() -> bool {
             metrics_interval_secs: 5,
             enable_performance_monitoring: true,
             health_check_interval_secs: 10,
+            http_bind_addr: None,
         },
         backfill: None,
+        finality: None,
+        idl_events: None,
+        ingestion: None,
+        lag_monitor: None,
     };
     match MetricsCollector::new(&config) {
         Ok(collector) => match collector.start_collection().await {
@@ -33,6 +33,7 @@ fn create_e2e_test_config() -> EventListenerConfig {
             // 使用一个在devnet上活跃的程序ID（Raydium CLMM）
             program_ids: vec!["CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK".parse().unwrap()],
             private_key: None,
+            failover: crate::config::settings::EndpointFailoverConfig::default(),
         },
         database: crate::config::settings::DatabaseConfig {
             uri: "mongodb://localhost:27017".to_string(),
@@ -53,14 +54,22 @@ fn create_e2e_test_config() -> EventListenerConfig {
                 max_wait_ms: 5000,
                 buffer_size: 50,
                 concurrent_writers: 2,
+                overflow_policy: crate::config::settings::OverflowPolicy::Block,
+                spill_path: None,
+                sinks: crate::config::settings::EventSinkConfig::default(),
             },
         },
         monitoring: crate::config::settings::MonitoringConfig {
             metrics_interval_secs: 5,
             enable_performance_monitoring: true,
             health_check_interval_secs: 10,
+            http_bind_addr: None,
         },
         backfill: None,
+        finality: None,
+        idl_events: None,
+        ingestion: None,
+        lag_monitor: None,
     }
 }
 
@@ -188,6 +197,8 @@ async fn test_e2e_database_persistence() {
                     created_at: chrono::Utc::now().timestamp(),
                     signature: format!("e2e_test_signature_{}", chrono::Utc::now().timestamp_millis()),
                     slot: 999999,
+                    instruction_index: 0,
+                    event_ordinal: 0,
                     extensions: None,
                     source: None,
                 }),
@@ -197,7 +208,7 @@ async fn test_e2e_database_persistence() {
             ];
 
             // 批量写入
-            match event_storage.write_batch(&test_events).await {
+            match event_storage.write_batch(&test_events).await.map(|outcome| outcome.written) {
                 Ok(written_count) => {
                     info!("✅ 成功写入 {} 个事件到数据库", written_count);
                     assert!(written_count > 0, "应该写入至少1个事件");
@@ -248,6 +259,8 @@ fn create_test_pool_event() -> PoolCreatedEventData {
         created_at: chrono::Utc::now().timestamp(),
         signature: format!("e2e_pool_test_{}", chrono::Utc::now().timestamp_millis()),
         slot: 999998,
+        instruction_index: 0,
+        event_ordinal: 0,
         processed_at: chrono::Utc::now().to_rfc3339(),
     }
 }
@@ -277,6 +290,8 @@ fn create_test_nft_event() -> NftClaimEventData {
         claimed_at: chrono::Utc::now().timestamp(),
         signature: format!("e2e_nft_test_{}", chrono::Utc::now().timestamp_millis()),
         slot: 999997,
+        instruction_index: 0,
+        event_ordinal: 0,
         processed_at: chrono::Utc::now().to_rfc3339(),
     }
 }
@@ -314,6 +329,8 @@ fn create_test_reward_event() -> RewardDistributionEventData {
         distributed_at: chrono::Utc::now().timestamp(),
         signature: format!("e2e_reward_test_{}", chrono::Utc::now().timestamp_millis()),
         slot: 999996,
+        instruction_index: 0,
+        event_ordinal: 0,
         processed_at: chrono::Utc::now().to_rfc3339(),
     }
 }
@@ -0,0 +1,215 @@
+//! 夹具回放测试
+//!
+//! 不依赖RPC和MongoDB，把录制的logsSubscribe通知回放进完整的订阅→解析→持久化流程，
+//! 事件写入JSONL输出目标后逐条校验
+
+use crate::{
+    config::{
+        settings::{IngestionConfig, IngestionMode, JsonlSinkConfig, SinkRetryConfig},
+        EventListenerConfig,
+    },
+    metrics::MetricsCollector,
+    parser::{
+        clmm_swap_parser::ClmmSwapEvent, event_parser::calculate_event_discriminator, EventParserRegistry, ParsedEvent,
+    },
+    persistence::BatchWriter,
+    subscriber::{FixtureRecorder, SubscriptionManager},
+};
+use base64::{engine::general_purpose, Engine as _};
+use solana_client::rpc_response::{Response, RpcLogsResponse, RpcResponseContext};
+use solana_sdk::pubkey::Pubkey;
+use std::{path::Path, str::FromStr, sync::Arc};
+use tokio::time::{sleep, timeout, Duration};
+
+const CLMM_PROGRAM_ID: &str = "FA1RJDDXysgwg5Gm3fJXWxt26JQzPkAzhTA114miqNUX";
+
+/// 创建夹具回放测试配置（关闭MongoDB输出，事件写入JSONL）
+fn create_replay_test_config(fixture_path: &Path, output_dir: &Path) -> EventListenerConfig {
+    EventListenerConfig {
+        solana: crate::config::settings::SolanaConfig {
+            rpc_url: "http://127.0.0.1:8899".to_string(),
+            ws_url: "ws://127.0.0.1:8900".to_string(),
+            commitment: "confirmed".to_string(),
+            program_ids: vec![Pubkey::from_str(CLMM_PROGRAM_ID).unwrap()],
+            private_key: None,
+            failover: crate::config::settings::EndpointFailoverConfig::default(),
+        },
+        database: crate::config::settings::DatabaseConfig {
+            uri: "mongodb://localhost:27017".to_string(),
+            database_name: "event_listener_replay_test".to_string(),
+            max_connections: 10,
+            min_connections: 2,
+        },
+        listener: crate::config::settings::ListenerConfig {
+            batch_size: 1,
+            sync_interval_secs: 1,
+            max_retries: 3,
+            retry_delay_ms: 100,
+            signature_cache_size: 1000,
+            checkpoint_save_interval_secs: 10,
+            backoff: crate::config::settings::BackoffConfig::default(),
+            batch_write: crate::config::settings::BatchWriteConfig {
+                batch_size: 1,
+                max_wait_ms: 100,
+                buffer_size: 50,
+                concurrent_writers: 1,
+                overflow_policy: crate::config::settings::OverflowPolicy::Block,
+                spill_path: Some(output_dir.join("spill.jsonl").to_string_lossy().to_string()),
+                sinks: crate::config::settings::EventSinkConfig {
+                    mongo_enabled: false,
+                    jsonl: Some(JsonlSinkConfig {
+                        directory: output_dir.to_string_lossy().to_string(),
+                        file_prefix: "events".to_string(),
+                        retry: SinkRetryConfig::default(),
+                    }),
+                    webhook: None,
+                    webhook_subscriptions: None,
+                },
+            },
+        },
+        monitoring: crate::config::settings::MonitoringConfig {
+            metrics_interval_secs: 60,
+            enable_performance_monitoring: false,
+            health_check_interval_secs: 30,
+            http_bind_addr: None,
+        },
+        backfill: None,
+        finality: None,
+        idl_events: None,
+        ingestion: Some(IngestionConfig {
+            mode: IngestionMode::Replay,
+            fixture_path: Some(fixture_path.to_string_lossy().to_string()),
+            replay_interval_ms: 0,
+            record_path: None,
+        }),
+        lag_monitor: None,
+    }
+}
+
+/// 构造一笔CLMM交换交易的logsSubscribe通知
+fn swap_notification(signature: &str, slot: u64, amount_0: u64) -> Response<RpcLogsResponse> {
+    let event = ClmmSwapEvent {
+        pool_state: Pubkey::new_unique(),
+        sender: Pubkey::new_unique(),
+        token_account_0: Pubkey::new_unique(),
+        token_account_1: Pubkey::new_unique(),
+        amount_0,
+        transfer_fee_0: 0,
+        amount_1: amount_0 * 2,
+        transfer_fee_1: 0,
+        zero_for_one: true,
+        sqrt_price_x64: 1u128 << 64,
+        liquidity: 5_000_000_000,
+        tick: -12,
+    };
+    let mut data = calculate_event_discriminator("SwapEvent").to_vec();
+    data.extend(borsh::to_vec(&event).unwrap());
+
+    Response {
+        context: RpcResponseContext {
+            slot,
+            api_version: None,
+        },
+        value: RpcLogsResponse {
+            signature: signature.to_string(),
+            err: None,
+            logs: vec![
+                format!("Program {} invoke [1]", CLMM_PROGRAM_ID),
+                "Program log: Instruction: SwapV2".to_string(),
+                format!("Program data: {}", general_purpose::STANDARD.encode(data)),
+                format!("Program {} success", CLMM_PROGRAM_ID),
+            ],
+        },
+    }
+}
+
+/// 读取JSONL输出目录中的全部事件
+async fn read_output_events(output_dir: &Path) -> Vec<ParsedEvent> {
+    let mut events = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir(output_dir).await else {
+        return events;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with("events-") {
+            continue;
+        }
+        let content = tokio::fs::read_to_string(entry.path()).await.unwrap_or_default();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            events.push(serde_json::from_str(line).unwrap());
+        }
+    }
+    events
+}
+
+/// 夹具回放：录制的通知经过去重、解析后写入输出目标，事件slot取自通知上下文
+#[tokio::test]
+async fn test_fixture_replay_pipeline() {
+    let work_dir = std::env::temp_dir().join(format!("fixture-replay-test-{}", uuid::Uuid::new_v4()));
+    let fixture_path = work_dir.join("fixtures").join("clmm_swap.jsonl");
+    let output_dir = work_dir.join("output");
+
+    // 录制夹具：两笔交换交易，其中一笔重复推送，外加一条零签名通知
+    let recorder = FixtureRecorder::new(&fixture_path);
+    for notification in [
+        swap_notification("replay_swap_1", 4242, 1_000_000),
+        swap_notification("replay_swap_1", 4242, 1_000_000),
+        swap_notification(crate::subscriber::subscription_manager::ZERO_SIG, 4243, 1),
+        swap_notification("replay_swap_2", 4244, 3_000_000),
+    ] {
+        recorder.record_logs(&notification).await.unwrap();
+    }
+
+    let config = create_replay_test_config(&fixture_path, &output_dir);
+    config.validate().unwrap();
+
+    let parser_registry = Arc::new(EventParserRegistry::new(&config).unwrap());
+    let batch_writer = Arc::new(BatchWriter::new(&config).await.unwrap());
+    let metrics = Arc::new(MetricsCollector::new(&config).unwrap());
+    let manager = SubscriptionManager::new(&config, parser_registry, Arc::clone(&batch_writer), metrics)
+        .await
+        .unwrap();
+
+    let writer_task = tokio::spawn({
+        let batch_writer = Arc::clone(&batch_writer);
+        async move { batch_writer.start_batch_processing().await }
+    });
+    let manager_task = tokio::spawn({
+        let manager = manager.clone();
+        async move { manager.start().await }
+    });
+
+    // 等待两笔交易的事件全部写入输出目标
+    let events = timeout(Duration::from_secs(10), async {
+        loop {
+            let events = read_output_events(&output_dir).await;
+            if events.len() >= 2 {
+                return events;
+            }
+            sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("回放事件未在超时时间内写入输出目标");
+
+    let mut written: Vec<(String, u64)> = events
+        .iter()
+        .map(|event| (event.signature().to_string(), event.slot()))
+        .collect();
+    written.sort();
+    assert_eq!(
+        written,
+        vec![("replay_swap_1".to_string(), 4242), ("replay_swap_2".to_string(), 4244)]
+    );
+    assert!(events.iter().all(|event| event.event_type() == "clmm_swap"));
+
+    let stats = manager.get_stats().await;
+    assert_eq!(stats.processed_events, 2);
+    assert_eq!(stats.failed_events, 0);
+
+    manager.stop().await.unwrap();
+    batch_writer.stop().await.unwrap();
+    manager_task.abort();
+    writer_task.abort();
+    let _ = std::fs::remove_dir_all(&work_dir);
+}
@@ -43,6 +43,7 @@ fn create_complete_e2e_config() -> EventListenerConfig {
             // program_id: "CPMDWBwJDtYax9qW7AyRuVC19Cc4L4Vcy4n2BHAbHkCW".parse().unwrap(),
             program_ids: vec!["devi51mZmdwUJGU9hjN27vEz64Gps7uUefqxg27EAtH".parse().unwrap()],
             private_key: None,
+            failover: crate::config::settings::EndpointFailoverConfig::default(),
         },
         database: crate::config::settings::DatabaseConfig {
             uri: "mongodb://localhost:27017".to_string(),
@@ -63,14 +64,22 @@ fn create_complete_e2e_config() -> EventListenerConfig {
                 max_wait_ms: 500,      // 大幅减少等待时间
                 buffer_size: 1000,     // 大幅增加缓冲区
                 concurrent_writers: 8, // 增加并发写入数
+                overflow_policy: crate::config::settings::OverflowPolicy::Block,
+                spill_path: None,
+                sinks: crate::config::settings::EventSinkConfig::default(),
             },
         },
         monitoring: crate::config::settings::MonitoringConfig {
             metrics_interval_secs: 2, // 减少指标收集间隔
             enable_performance_monitoring: true,
             health_check_interval_secs: 5, // 减少健康检查间隔
+            http_bind_addr: None,
         },
         backfill: None,
+        finality: None,
+        idl_events: None,
+        ingestion: None,
+        lag_monitor: None,
     }
 }
 
@@ -396,7 +405,8 @@ async fn test_debug_filter_behavior() {
     // 监听前3个事件进行调试
     for i in 1..=3 {
         match tokio::time::timeout(Duration::from_secs(10), event_receiver.recv()).await {
-            Ok(Ok(log_response)) => {
+            Ok(Ok(response)) => {
+                let log_response = response.value;
                 info!("📨 调试事件 {}: {}", i, log_response.signature);
                 info!(
                     "📋 事件详情: err={:?}, logs_count={}",
@@ -476,7 +486,8 @@ async fn test_simple_event_processing() {
     match tokio::time::timeout(timeout_duration, async {
         while event_count < 10 {
             match event_receiver.recv().await {
-                Ok(log_response) => {
+                Ok(response) => {
+                    let log_response = response.value;
                     event_count += 1;
                     info!("✅ 接收到事件 {}: {}", event_count, log_response.signature);
 
@@ -544,6 +555,8 @@ async fn test_e2e_database_write_verification() {
             created_at: chrono::Utc::now().timestamp(),
             signature: format!("e2e_test_sig_{}", chrono::Utc::now().timestamp_millis()),
             slot: 999999999,
+            instruction_index: 0,
+            event_ordinal: 0,
             extensions: None,
             source: None,
         }),
@@ -559,7 +572,7 @@ async fn test_e2e_database_write_verification() {
     info!("📊 写入前统计: 总代币={}", before_stats.total_tokens);
 
     // 批量写入
-    match event_storage.write_batch(&test_events).await {
+    match event_storage.write_batch(&test_events).await.map(|outcome| outcome.written) {
         Ok(written_count) => {
             info!("✅ 成功写入{}个事件", written_count);
 
@@ -604,6 +617,8 @@ fn create_realistic_pool_event() -> PoolCreatedEventData {
         created_at: chrono::Utc::now().timestamp(),
         signature: format!("pool_e2e_test_{}", chrono::Utc::now().timestamp_millis()),
         slot: 399244500,
+        instruction_index: 0,
+        event_ordinal: 0,
         processed_at: chrono::Utc::now().to_rfc3339(),
     }
 }
@@ -632,6 +647,8 @@ fn create_realistic_nft_event() -> NftClaimEventData {
         claimed_at: chrono::Utc::now().timestamp(),
         signature: format!("nft_e2e_test_{}", chrono::Utc::now().timestamp_millis()),
         slot: 399244501,
+        instruction_index: 0,
+        event_ordinal: 0,
         processed_at: chrono::Utc::now().to_rfc3339(),
     }
 }
@@ -669,6 +686,8 @@ fn create_realistic_reward_event() -> RewardDistributionEventData {
         distributed_at: now.timestamp(),
         signature: format!("reward_e2e_test_{}", now.timestamp_millis()),
         slot: 399244502,
+        instruction_index: 0,
+        event_ordinal: 0,
         processed_at: now.to_rfc3339(),
     }
 }
@@ -28,6 +28,7 @@ fn create_integration_test_config() -> EventListenerConfig {
             commitment: "confirmed".to_string(),
             program_ids: vec![solana_sdk::pubkey::Pubkey::new_unique()],
             private_key: None,
+            failover: crate::config::settings::EndpointFailoverConfig::default(),
         },
         database: crate::config::settings::DatabaseConfig {
             uri: "mongodb://localhost:27017".to_string(),
@@ -48,14 +49,22 @@ fn create_integration_test_config() -> EventListenerConfig {
                 max_wait_ms: 1000,
                 buffer_size: 50,
                 concurrent_writers: 2,
+                overflow_policy: crate::config::settings::OverflowPolicy::Block,
+                spill_path: None,
+                sinks: crate::config::settings::EventSinkConfig::default(),
             },
         },
         monitoring: crate::config::settings::MonitoringConfig {
             metrics_interval_secs: 5,
             enable_performance_monitoring: true,
             health_check_interval_secs: 10,
+            http_bind_addr: None,
         },
         backfill: None,
+        finality: None,
+        idl_events: None,
+        ingestion: None,
+        lag_monitor: None,
     }
 }
 
@@ -143,6 +152,8 @@ async fn test_fix_3_intelligent_retry_logic() {
         created_at: 1234567890,
         signature: "integration_test_signature".to_string(),
         slot: 12345,
+        instruction_index: 0,
+        event_ordinal: 0,
         extensions: None,
         source: None,
     })];
@@ -27,6 +27,7 @@ fn create_metadata_test_config() -> EventListenerConfig {
             commitment: "confirmed".to_string(),
             program_ids: vec!["CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK".parse().unwrap()],
             private_key: None,
+            failover: crate::config::settings::EndpointFailoverConfig::default(),
         },
         database: crate::config::settings::DatabaseConfig {
             uri: "mongodb://localhost:27017".to_string(),
@@ -47,14 +48,22 @@ fn create_metadata_test_config() -> EventListenerConfig {
                 max_wait_ms: 2000,
                 buffer_size: 10,
                 concurrent_writers: 1,
+                overflow_policy: crate::config::settings::OverflowPolicy::Block,
+                spill_path: None,
+                sinks: crate::config::settings::EventSinkConfig::default(),
             },
         },
         monitoring: crate::config::settings::MonitoringConfig {
             metrics_interval_secs: 10,
             enable_performance_monitoring: true,
             health_check_interval_secs: 30,
+            http_bind_addr: None,
         },
         backfill: None,
+        finality: None,
+        idl_events: None,
+        ingestion: None,
+        lag_monitor: None,
     }
 }
 
@@ -328,6 +337,8 @@ async fn test_reward_distribution_parser_metadata_integration() {
         distributed_at: chrono::Utc::now().timestamp(),
         signature: "test_signature_12345".to_string(),
         slot: 12345,
+        instruction_index: 0,
+        event_ordinal: 0,
         processed_at: chrono::Utc::now().to_rfc3339(),
     };
 
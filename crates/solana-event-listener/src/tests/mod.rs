@@ -1,5 +1,6 @@
 pub mod core_validation_test;
 pub mod e2e_tests;
+pub mod fixture_replay_test;
 pub mod full_e2e_test;
 pub mod integration_tests;
 pub mod metadata_integration_test;
@@ -28,6 +28,7 @@ fn create_simple_e2e_config() -> EventListenerConfig {
             // 使用Raydium CLMM程序ID
             program_ids: vec!["CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK".parse().unwrap()],
             private_key: None,
+            failover: crate::config::settings::EndpointFailoverConfig::default(),
         },
         database: crate::config::settings::DatabaseConfig {
             uri: "mongodb://localhost:27017".to_string(),
@@ -48,14 +49,22 @@ fn create_simple_e2e_config() -> EventListenerConfig {
                 max_wait_ms: 5000,
                 buffer_size: 50,
                 concurrent_writers: 2,
+                overflow_policy: crate::config::settings::OverflowPolicy::Block,
+                spill_path: None,
+                sinks: crate::config::settings::EventSinkConfig::default(),
             },
         },
         monitoring: crate::config::settings::MonitoringConfig {
             metrics_interval_secs: 5,
             enable_performance_monitoring: true,
             health_check_interval_secs: 10,
+            http_bind_addr: None,
         },
         backfill: None,
+        finality: None,
+        idl_events: None,
+        ingestion: None,
+        lag_monitor: None,
     }
 }
 
@@ -131,9 +140,9 @@ async fn test_simple_e2e_flow() {
 
     // 批量写入
     let written_count = match event_storage.write_batch(&test_events).await {
-        Ok(count) => {
-            info!("✅ 成功写入{}个事件", count);
-            count
+        Ok(outcome) => {
+            info!("✅ 成功写入{}个事件", outcome.written);
+            outcome.written
         }
         Err(e) => {
             error!("❌ 批量写入失败: {}", e);
@@ -270,6 +279,8 @@ fn create_test_events() -> Vec<ParsedEvent> {
             created_at: chrono::Utc::now().timestamp(),
             signature: format!("simple_e2e_test_sig_{}", chrono::Utc::now().timestamp_millis()),
             slot: 999999999,
+            instruction_index: 0,
+            event_ordinal: 0,
             extensions: None,
             source: None,
         }),
@@ -300,6 +311,8 @@ fn create_test_pool_event() -> PoolCreatedEventData {
         created_at: chrono::Utc::now().timestamp(),
         signature: format!("simple_pool_test_{}", chrono::Utc::now().timestamp_millis()),
         slot: 399244500,
+        instruction_index: 0,
+        event_ordinal: 0,
         processed_at: chrono::Utc::now().to_rfc3339(),
     }
 }
@@ -328,6 +341,8 @@ fn create_test_nft_event() -> NftClaimEventData {
         claimed_at: chrono::Utc::now().timestamp(),
         signature: format!("simple_nft_test_{}", chrono::Utc::now().timestamp_millis()),
         slot: 399244501,
+        instruction_index: 0,
+        event_ordinal: 0,
         processed_at: chrono::Utc::now().to_rfc3339(),
     }
 }
@@ -365,6 +380,8 @@ fn create_test_reward_event() -> RewardDistributionEventData {
         distributed_at: now.timestamp(),
         signature: format!("simple_reward_test_{}", now.timestamp_millis()),
         slot: 399244502,
+        instruction_index: 0,
+        event_ordinal: 0,
         processed_at: now.to_rfc3339(),
     }
 }
@@ -1,7 +1,6 @@
 use anyhow::{anyhow, Result};
 use solana_sdk::pubkey::Pubkey;
 
-use super::constants;
 use spl_token_2022::extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions};
 use tracing::info;
 
@@ -242,17 +241,20 @@ impl MathUtils {
         amount / fee_rate
     }
 
-    /// 简单的SOL/USDC价格转换
-    pub fn convert_sol_to_usdc(sol_amount: u64) -> u64 {
+    /// 按给定的SOL价格（USDC）进行SOL/USDC转换
+    pub fn convert_sol_to_usdc(sol_amount: u64, sol_price_usdc: f64) -> u64 {
         let sol_amount_f64 = sol_amount as f64 / 1_000_000_000.0; // lamports to SOL
-        let usdc_amount = sol_amount_f64 * constants::DEFAULT_SOL_PRICE_USDC;
+        let usdc_amount = sol_amount_f64 * sol_price_usdc;
         (usdc_amount * 1_000_000.0) as u64 // USDC to micro-USDC
     }
 
-    /// 简单的USDC/SOL价格转换
-    pub fn convert_usdc_to_sol(usdc_amount: u64) -> u64 {
+    /// 按给定的SOL价格（USDC）进行USDC/SOL转换，价格无效时返回0
+    pub fn convert_usdc_to_sol(usdc_amount: u64, sol_price_usdc: f64) -> u64 {
+        if sol_price_usdc <= 0.0 {
+            return 0;
+        }
         let usdc_amount_f64 = usdc_amount as f64 / 1_000_000.0; // micro-USDC to USDC
-        let sol_amount = usdc_amount_f64 / constants::DEFAULT_SOL_PRICE_USDC;
+        let sol_amount = usdc_amount_f64 / sol_price_usdc;
         (sol_amount * 1_000_000_000.0) as u64 // SOL to lamports
     }
 }
@@ -478,6 +480,14 @@ mod tests {
     const TEST_SOL_MINT: &str = "So11111111111111111111111111111111111111112";
     const TEST_USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
 
+    #[test]
+    fn test_sol_usdc_conversion_with_price() {
+        // 1 SOL @ 150 USDC
+        assert_eq!(MathUtils::convert_sol_to_usdc(1_000_000_000, 150.0), 150_000_000);
+        assert_eq!(MathUtils::convert_usdc_to_sol(150_000_000, 150.0), 1_000_000_000);
+        assert_eq!(MathUtils::convert_usdc_to_sol(150_000_000, 0.0), 0);
+    }
+
     #[test]
     fn test_calculate_v2_amm_pool_pda() {
         let program_id = Pubkey::from_str(TEST_V2_AMM_PROGRAM_ID).unwrap();
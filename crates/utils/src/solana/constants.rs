@@ -6,6 +6,7 @@ pub const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
 pub const USDC_MINT_STANDARD: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
 pub const USDC_MINT_CONFIG: &str = "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU";
 pub const USDC_MINT_ALTERNATIVE: &str = "A9mUU4qviSctJVPJdBJWkb28deg915LYJKrzQ19ji3FM";
+pub const USDT_MINT: &str = "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB";
 
 // Raydium V3 (CLMM) 常量
 pub const DEFAULT_RAYDIUM_PROGRAM_ID: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK";
@@ -20,8 +21,6 @@ pub const DEFAULT_V2_AMM_OPEN_TIME: u64 = 0;
 // 推荐系统常量
 pub const DEFAULT_REFERRAL_PROGRAM_ID: &str = "REFRpo1ievaQhpSLR8uDwCzjfUDJ8xGsBmNn8J5fC2q";
 
-pub const DEFAULT_SOL_PRICE_USDC: f64 = 100.0;
-
 /// Solana 网络链 ID 枚举
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SolanaChainId {